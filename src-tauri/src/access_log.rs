@@ -0,0 +1,171 @@
+//! Access logs of the embedded HTTP servers
+//!
+//! A middleware on the explorer server (static files and the node proxy), the
+//! MCP server and the control API records every request: method, path, status, latency
+//! (to the response headers; streamed bodies aren't waited for) and, for
+//! proxied requests, the upstream that answered it and why it failed. The
+//! last `MAX_ENTRIES` requests of each server are kept in `AppState` and
+//! returned by `get_logs(service)` (see logging.rs), with `service` being
+//! `explorer`, `mcp` or `control`, and each is appended to the server's log
+//! file.
+
+use axum::extract::{Request, State};
+use axum::middleware::Next;
+use axum::response::Response;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, VecDeque};
+use std::sync::{Arc, Mutex as StdMutex};
+use std::time::Instant;
+
+use crate::SharedState;
+
+const MAX_ENTRIES: usize = 5_000;
+pub const SERVICES: [&str; 3] = ["explorer", "mcp", "control"];
+
+/// Response extension naming the upstream that served a proxied request
+#[derive(Clone)]
+pub struct Upstream(pub String);
+
+/// Response extension with why a request failed
+#[derive(Clone)]
+pub struct AccessError(pub String);
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AccessLogEntry {
+    // Unix time in milliseconds
+    pub timestamp: u64,
+    pub service: String,
+    pub method: String,
+    pub path: String,
+    pub status: u16,
+    pub latency_ms: f64,
+    pub upstream: Option<String>,
+    pub error: Option<String>,
+}
+
+impl AccessLogEntry {
+    // One-line form, e.g. `GET /v1a/status/ 200 3.1ms <- http://127.0.0.1:8080`
+    fn line(&self) -> String {
+        let mut line = format!(
+            "{} {} {} {:.1}ms",
+            self.method, self.path, self.status, self.latency_ms
+        );
+        if let Some(upstream) = &self.upstream {
+            line.push_str(&format!(" <- {}", upstream));
+        }
+        if let Some(error) = &self.error {
+            line.push_str(&format!(" ({})", error));
+        }
+        line
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LogLine {
+    // Unix time in milliseconds
+    pub timestamp: u64,
+    pub service: String,
+    pub message: String,
+    // The structured request, for access log lines
+    pub access: Option<AccessLogEntry>,
+}
+
+/// Recent requests per server, kept in `AppState`
+#[derive(Default)]
+pub struct AccessLog {
+    entries: BTreeMap<&'static str, VecDeque<AccessLogEntry>>,
+}
+
+pub type SharedAccessLog = Arc<StdMutex<AccessLog>>;
+
+impl AccessLog {
+    fn record(&mut self, service: &'static str, entry: AccessLogEntry) {
+        let entries = self.entries.entry(service).or_default();
+        if entries.len() >= MAX_ENTRIES {
+            entries.pop_front();
+        }
+        entries.push_back(entry);
+    }
+}
+
+#[derive(Clone)]
+pub struct AccessLogState {
+    log: SharedAccessLog,
+    service: &'static str,
+}
+
+impl AccessLogState {
+    pub fn new(log: SharedAccessLog, service: &'static str) -> Self {
+        Self { log, service }
+    }
+}
+
+/// Current Unix time in milliseconds
+pub fn unix_millis() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+/// Middleware recording each request; install with
+/// `axum::middleware::from_fn_with_state(AccessLogState::new(..), log_requests)`
+pub async fn log_requests(
+    State(state): State<AccessLogState>,
+    req: Request,
+    next: Next,
+) -> Response {
+    let timestamp = unix_millis();
+    let method = req.method().to_string();
+    let path = req
+        .uri()
+        .path_and_query()
+        .map(|path| path.to_string())
+        .unwrap_or_else(|| req.uri().path().to_string());
+    let started = Instant::now();
+
+    let response = next.run(req).await;
+
+    let entry = AccessLogEntry {
+        timestamp,
+        service: state.service.to_string(),
+        method,
+        path,
+        status: response.status().as_u16(),
+        latency_ms: started.elapsed().as_secs_f64() * 1000.0,
+        upstream: response
+            .extensions()
+            .get::<Upstream>()
+            .map(|upstream| upstream.0.clone()),
+        error: response
+            .extensions()
+            .get::<AccessError>()
+            .map(|error| error.0.clone()),
+    };
+    crate::logging::write(state.service, &entry.line());
+    state
+        .log
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .record(state.service, entry);
+    response
+}
+
+/// A server's recent requests matching `query`, oldest first
+pub async fn get_logs_internal(
+    state: &SharedState,
+    service: &str,
+    query: &crate::logging::LogQuery,
+) -> Vec<LogLine> {
+    let log = state.lock().await.access_log.clone();
+    let log = log.lock().unwrap_or_else(|e| e.into_inner());
+    let Some(entries) = log.entries.get(service) else {
+        return Vec::new();
+    };
+    query.select(entries.iter().map(|entry| LogLine {
+        timestamp: entry.timestamp,
+        service: entry.service.clone(),
+        message: entry.line(),
+        access: Some(entry.clone()),
+    }))
+}
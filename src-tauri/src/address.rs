@@ -0,0 +1,124 @@
+//! Hathor address validation
+//!
+//! An address is base58(version byte || hash160 || checksum), where the checksum
+//! is the first 4 bytes of sha256d over the first 21 bytes. The localnet runs as
+//! privatenet, which uses the testnet version bytes (`W...`/`w...` addresses).
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+const ADDRESS_LENGTH: usize = 25;
+
+// (network, P2PKH version byte, P2SH version byte)
+const NETWORKS: &[(&str, u8, u8)] = &[("privatenet", 0x49, 0x87), ("mainnet", 0x28, 0x64)];
+const ACTIVE_NETWORK: &str = "privatenet";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AddressType {
+    P2PKH,
+    P2SH,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AddressInfo {
+    pub address: String,
+    pub valid: bool,
+    pub address_type: Option<AddressType>,
+    // Network the version byte belongs to (even when it isn't the active one)
+    pub network: Option<String>,
+    pub error: Option<String>,
+}
+
+/// Decode an address and check it belongs to the active network
+pub fn check_address(address: &str) -> Result<AddressType, String> {
+    let (network, address_type) = decode(address)?;
+    if network != ACTIVE_NETWORK {
+        return Err(format!(
+            "Address {} is a {} address; the localnet only accepts {} addresses",
+            address, network, ACTIVE_NETWORK
+        ));
+    }
+    Ok(address_type)
+}
+
+/// Encode a public key hash as a P2PKH address of the active network
+pub fn encode_p2pkh(pubkey_hash: &[u8; 20]) -> String {
+    let version = NETWORKS
+        .iter()
+        .find(|(network, _, _)| *network == ACTIVE_NETWORK)
+        .map(|(_, p2pkh, _)| *p2pkh)
+        .unwrap_or_default();
+
+    let mut bytes = Vec::with_capacity(ADDRESS_LENGTH);
+    bytes.push(version);
+    bytes.extend_from_slice(pubkey_hash);
+    let checksum = Sha256::digest(Sha256::digest(&bytes));
+    bytes.extend_from_slice(&checksum[..4]);
+    bs58::encode(bytes).into_string()
+}
+
+// Decode an address, returning the network its version byte belongs to
+fn decode(address: &str) -> Result<(&'static str, AddressType), String> {
+    let bytes = bs58::decode(address)
+        .into_vec()
+        .map_err(|e| format!("Invalid address {}: not base58 ({})", address, e))?;
+
+    if bytes.len() != ADDRESS_LENGTH {
+        return Err(format!(
+            "Invalid address {}: expected {} bytes, got {}",
+            address,
+            ADDRESS_LENGTH,
+            bytes.len()
+        ));
+    }
+
+    let (payload, checksum) = bytes.split_at(ADDRESS_LENGTH - 4);
+    let expected = Sha256::digest(Sha256::digest(payload));
+    if checksum != &expected[..4] {
+        return Err(format!("Invalid address {}: checksum mismatch", address));
+    }
+
+    let version = payload[0];
+    NETWORKS
+        .iter()
+        .find_map(|(network, p2pkh, p2sh)| {
+            if version == *p2pkh {
+                Some((*network, AddressType::P2PKH))
+            } else if version == *p2sh {
+                Some((*network, AddressType::P2SH))
+            } else {
+                None
+            }
+        })
+        .ok_or_else(|| {
+            format!(
+                "Invalid address {}: unknown version byte 0x{:02x}",
+                address, version
+            )
+        })
+}
+
+/// Validate an address, describing what's wrong with it if it's invalid
+pub fn validate_address_internal(address: &str) -> AddressInfo {
+    let address = address.trim();
+    let decoded = decode(address);
+    let checked = check_address(address);
+
+    AddressInfo {
+        address: address.to_string(),
+        valid: checked.is_ok(),
+        address_type: decoded.as_ref().ok().map(|(_, address_type)| *address_type),
+        network: decoded.ok().map(|(network, _)| network.to_string()),
+        error: checked.err(),
+    }
+}
+
+// ============================================================================
+// Tauri Commands
+// ============================================================================
+
+// Check an address' encoding and network, and get its type (P2PKH/P2SH)
+#[tauri::command]
+pub async fn validate_address(address: String) -> Result<AddressInfo, String> {
+    Ok(validate_address_internal(&address))
+}
@@ -0,0 +1,68 @@
+//! HTR amounts
+//!
+//! Amounts travel as integer cents (1 HTR = 100 cents) everywhere: Tauri
+//! commands, the node and wallet-headless APIs. `HtrAmount` keeps that unit
+//! explicit; decimal HTR only appears at the edges (MCP tool params, messages).
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+const CENTS_PER_HTR: u64 = 100;
+
+/// An amount of HTR, stored in cents. Serializes as the integer number of cents.
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+#[serde(transparent)]
+pub struct HtrAmount(u64);
+
+impl HtrAmount {
+    pub const fn from_cents(cents: u64) -> Self {
+        HtrAmount(cents)
+    }
+
+    /// Convert a decimal HTR amount (e.g. `12.5`), rounding to the nearest cent
+    pub fn from_decimal(htr: f64) -> Result<Self, String> {
+        if !htr.is_finite() || htr < 0.0 {
+            return Err(format!("Invalid HTR amount: {}", htr));
+        }
+        let cents = (htr * CENTS_PER_HTR as f64).round();
+        if cents > u64::MAX as f64 {
+            return Err(format!("HTR amount too large: {}", htr));
+        }
+        Ok(HtrAmount(cents as u64))
+    }
+
+    /// Read a cents amount from a node/headless JSON response (missing or negative is zero)
+    pub fn from_json(value: &serde_json::Value) -> Self {
+        HtrAmount(
+            value
+                .as_u64()
+                .or_else(|| value.as_i64().map(|v| v.max(0) as u64))
+                .unwrap_or(0),
+        )
+    }
+
+    pub const fn cents(self) -> u64 {
+        self.0
+    }
+
+    pub fn to_decimal(self) -> f64 {
+        self.0 as f64 / CENTS_PER_HTR as f64
+    }
+
+    pub const fn is_zero(self) -> bool {
+        self.0 == 0
+    }
+}
+
+impl fmt::Display for HtrAmount {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}.{:02} HTR",
+            self.0 / CENTS_PER_HTR,
+            self.0 % CENTS_PER_HTR
+        )
+    }
+}
@@ -0,0 +1,122 @@
+//! Auto-starting the miner with the node
+//!
+//! A fresh localnet has no funds until blocks are mined, which makes "why is
+//! my balance zero" the most common first question. With `auto_start_miner`
+//! on, the default miner starts mining to the node wallet as soon as the
+//! node's API answers with a best block. The setting lives in
+//! `auto_miner.json` in the app directory.
+
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+
+use crate::settings::JsonSettings;
+use crate::{MinerConfig, NodeConfig, SharedState};
+
+const READY_POLL_INTERVAL: Duration = Duration::from_millis(500);
+const READY_TIMEOUT: Duration = Duration::from_secs(120);
+
+fn default_threads() -> u32 {
+    MinerConfig::default().threads
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AutoMinerSettings {
+    #[serde(default)]
+    pub auto_start_miner: bool,
+    #[serde(default = "default_threads")]
+    pub threads: u32,
+}
+
+impl Default for AutoMinerSettings {
+    fn default() -> Self {
+        Self {
+            auto_start_miner: false,
+            threads: default_threads(),
+        }
+    }
+}
+
+static SETTINGS: JsonSettings<AutoMinerSettings> =
+    JsonSettings::new("auto_miner.json", "auto-miner settings");
+
+/// Turn auto-start on or off, optionally changing the miner's thread count
+pub fn set_auto_start(enabled: bool, threads: Option<u32>) -> Result<AutoMinerSettings, String> {
+    let mut settings = SETTINGS.load();
+    settings.auto_start_miner = enabled;
+    if let Some(threads) = threads {
+        if threads == 0 {
+            return Err("Threads must be at least 1".to_string());
+        }
+        settings.threads = threads;
+    }
+    SETTINGS.save(&settings)?;
+    Ok(settings)
+}
+
+/// Wait until the node's API reports a best block, returning its height
+pub async fn wait_for_node_ready(state: &SharedState) -> Result<u64, String> {
+    let client = reqwest::Client::new();
+    let url = format!(
+        "http://127.0.0.1:{}/v1a/status/",
+        NodeConfig::default().api_port
+    );
+    let started = Instant::now();
+
+    while started.elapsed() < READY_TIMEOUT {
+        if !state.lock().await.node_running {
+            return Err("Node stopped before it was ready".to_string());
+        }
+        if let Ok(response) = client.get(&url).send().await {
+            if let Ok(status) = response.json::<serde_json::Value>().await {
+                if let Some(height) = status["dag"]["best_block"]["height"].as_u64() {
+                    return Ok(height);
+                }
+            }
+        }
+        tokio::time::sleep(READY_POLL_INTERVAL).await;
+    }
+
+    Err(format!(
+        "Node wasn't ready after {}s",
+        READY_TIMEOUT.as_secs()
+    ))
+}
+
+/// Start the default miner once the node is ready, if auto-start is on.
+/// Returns whether a miner was started.
+pub async fn start_when_ready(state: &SharedState) -> Result<bool, String> {
+    let settings = SETTINGS.load();
+    if !settings.auto_start_miner {
+        return Ok(false);
+    }
+
+    wait_for_node_ready(state).await?;
+    crate::start_miner_internal(
+        state,
+        MinerConfig {
+            threads: settings.threads,
+            ..MinerConfig::default()
+        },
+    )
+    .await?;
+    Ok(true)
+}
+
+// ============================================================================
+// Tauri Commands
+// ============================================================================
+
+// Get whether the miner starts with the node
+#[tauri::command]
+pub async fn get_auto_miner_settings() -> Result<AutoMinerSettings, String> {
+    Ok(SETTINGS.load())
+}
+
+// Start mining to the node wallet whenever the node becomes ready (or stop doing so)
+#[tauri::command]
+pub async fn set_auto_start_miner(
+    enabled: bool,
+    threads: Option<u32>,
+) -> Result<AutoMinerSettings, String> {
+    set_auto_start(enabled, threads)
+}
@@ -0,0 +1,292 @@
+//! Password-protected wallet backups
+//!
+//! A backup is a JSON file holding the wallet's seed and metadata (including its
+//! multisig definition, if any) encrypted with AES-256-GCM under a key derived
+//! from the password with Argon2id. The KDF parameters are stored alongside so
+//! they can be raised later without breaking old backups.
+
+use aes_gcm::aead::{Aead, KeyInit};
+use aes_gcm::{Aes256Gcm, Key, Nonce};
+use argon2::{Algorithm, Argon2, Params, Version};
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::fs;
+
+use crate::multisig::{self, MultisigConfig};
+use crate::{headless, wallets, SharedState};
+
+const BACKUP_VERSION: u32 = 1;
+const MIN_PASSWORD_LENGTH: usize = 8;
+
+// Argon2id parameters (memory in KiB)
+const ARGON2_M_COST: u32 = 19 * 1024;
+const ARGON2_T_COST: u32 = 2;
+const ARGON2_P_COST: u32 = 1;
+// Backups asking for more than this many times the above are rejected, so a
+// crafted file can't make an import allocate gigabytes or spin for minutes
+const ARGON2_MAX_COST_FACTOR: u32 = 4;
+
+#[derive(Debug, Serialize, Deserialize)]
+struct KdfParams {
+    algorithm: String,
+    salt: String, // base64
+    m_cost: u32,
+    t_cost: u32,
+    p_cost: u32,
+}
+
+/// Backup file contents; only `wallet_id` and `created_at` are readable without the password
+#[derive(Debug, Serialize, Deserialize)]
+struct BackupFile {
+    version: u32,
+    wallet_id: String,
+    created_at: u64,
+    kdf: KdfParams,
+    nonce: String,      // base64
+    ciphertext: String, // base64
+}
+
+// Encrypted part of the backup
+#[derive(Debug, Serialize, Deserialize)]
+struct BackupPayload {
+    wallet_id: String,
+    seed: String,
+    instance: String,
+    multisig_config: Option<String>,
+    multisig: Option<MultisigConfig>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BackupImportResult {
+    pub wallet_id: String,
+    pub multisig_config: Option<String>,
+    pub started: bool,
+    pub message: String,
+}
+
+fn derive_key(password: &str, kdf: &KdfParams) -> Result<[u8; 32], String> {
+    if kdf.algorithm != "argon2id" {
+        return Err(format!("Unsupported key derivation: {}", kdf.algorithm));
+    }
+    if kdf.m_cost > ARGON2_M_COST * ARGON2_MAX_COST_FACTOR
+        || kdf.t_cost > ARGON2_T_COST * ARGON2_MAX_COST_FACTOR
+        || kdf.p_cost > ARGON2_P_COST * ARGON2_MAX_COST_FACTOR
+    {
+        return Err("Key derivation parameters exceed the supported maximum".to_string());
+    }
+    let salt = BASE64
+        .decode(&kdf.salt)
+        .map_err(|e| format!("Invalid backup salt: {}", e))?;
+    let params = Params::new(kdf.m_cost, kdf.t_cost, kdf.p_cost, Some(32))
+        .map_err(|e| format!("Invalid key derivation parameters: {}", e))?;
+
+    let mut key = [0u8; 32];
+    Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+        .hash_password_into(password.as_bytes(), &salt, &mut key)
+        .map_err(|e| format!("Failed to derive backup key: {}", e))?;
+    Ok(key)
+}
+
+/// Write an encrypted backup of a registered wallet, whose seed is `seed`, to
+/// `path`
+pub fn export_wallet_backup_internal(
+    wallet_id: &str,
+    seed: &str,
+    password: &str,
+    path: &str,
+) -> Result<String, String> {
+    if password.chars().count() < MIN_PASSWORD_LENGTH {
+        return Err(format!(
+            "Backup password must have at least {} characters",
+            MIN_PASSWORD_LENGTH
+        ));
+    }
+
+    let info = wallets::list_wallets()
+        .into_iter()
+        .find(|w| w.wallet_id == wallet_id)
+        .ok_or_else(|| format!("Wallet '{}' is not in the registry", wallet_id))?;
+    let multisig = info
+        .multisig_config
+        .as_ref()
+        .and_then(|name| multisig::load_configs().remove(name));
+
+    let payload = serde_json::to_vec(&BackupPayload {
+        wallet_id: wallet_id.to_string(),
+        seed: seed.to_string(),
+        instance: info.instance,
+        multisig_config: info.multisig_config,
+        multisig,
+    })
+    .map_err(|e| format!("Failed to serialize backup: {}", e))?;
+
+    let mut salt = [0u8; 16];
+    let mut nonce = [0u8; 12];
+    getrandom::getrandom(&mut salt).map_err(|e| format!("Failed to generate salt: {}", e))?;
+    getrandom::getrandom(&mut nonce).map_err(|e| format!("Failed to generate nonce: {}", e))?;
+
+    let kdf = KdfParams {
+        algorithm: "argon2id".to_string(),
+        salt: BASE64.encode(salt),
+        m_cost: ARGON2_M_COST,
+        t_cost: ARGON2_T_COST,
+        p_cost: ARGON2_P_COST,
+    };
+    let key = derive_key(password, &kdf)?;
+    let ciphertext = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&key))
+        .encrypt(Nonce::from_slice(&nonce), payload.as_slice())
+        .map_err(|_| "Failed to encrypt backup".to_string())?;
+
+    let backup = BackupFile {
+        version: BACKUP_VERSION,
+        wallet_id: wallet_id.to_string(),
+        created_at: crate::unix_now(),
+        kdf,
+        nonce: BASE64.encode(nonce),
+        ciphertext: BASE64.encode(ciphertext),
+    };
+    let content = serde_json::to_string_pretty(&backup)
+        .map_err(|e| format!("Failed to serialize backup: {}", e))?;
+    // Keep the backup readable by the current user only
+    crate::write_private_file(path, content)
+        .map_err(|e| format!("Failed to write backup: {}", e))?;
+
+    Ok(format!("Wallet '{}' backed up to {}", wallet_id, path))
+}
+
+fn decrypt_backup(path: &str, password: &str) -> Result<BackupPayload, String> {
+    let content = fs::read_to_string(path).map_err(|e| format!("Failed to read backup: {}", e))?;
+    let backup: BackupFile =
+        serde_json::from_str(&content).map_err(|e| format!("Invalid backup file: {}", e))?;
+    if backup.version != BACKUP_VERSION {
+        return Err(format!("Unsupported backup version {}", backup.version));
+    }
+
+    let key = derive_key(password, &backup.kdf)?;
+    let nonce = BASE64
+        .decode(&backup.nonce)
+        .map_err(|e| format!("Invalid backup nonce: {}", e))?;
+    if nonce.len() != 12 {
+        return Err("Invalid backup nonce length".to_string());
+    }
+    let ciphertext = BASE64
+        .decode(&backup.ciphertext)
+        .map_err(|e| format!("Invalid backup ciphertext: {}", e))?;
+
+    let plaintext = Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&key))
+        .decrypt(Nonce::from_slice(&nonce), ciphertext.as_slice())
+        .map_err(|_| "Wrong password or corrupted backup".to_string())?;
+
+    serde_json::from_slice(&plaintext).map_err(|e| format!("Invalid backup contents: {}", e))
+}
+
+/// Restore a wallet from an encrypted backup, registering and starting it
+/// (optionally under a different wallet ID or instance)
+pub async fn import_wallet_backup_internal(
+    state: &SharedState,
+    path: &str,
+    password: &str,
+    wallet_id: Option<&str>,
+    instance: Option<&str>,
+) -> Result<BackupImportResult, String> {
+    let (backup_path, backup_password) = (path.to_string(), password.to_string());
+    let payload =
+        tokio::task::spawn_blocking(move || decrypt_backup(&backup_path, &backup_password))
+            .await
+            .map_err(|e| format!("Backup task failed: {}", e))??;
+    let seed = wallets::validate_mnemonic(&payload.seed)?;
+    let wallet_id = wallet_id.unwrap_or(&payload.wallet_id).to_string();
+    let instance = instance.unwrap_or(&payload.instance).to_string();
+
+    if let Some(config_name) = payload.multisig_config {
+        let (pubkeys, num_signatures) = match payload.multisig {
+            Some(config) => (Some(config.pubkeys), Some(config.num_signatures)),
+            None => (None, None),
+        };
+        let result = multisig::create_multisig_wallet_internal(
+            state,
+            multisig::CreateMultisigWalletRequest {
+                wallet_id,
+                seed,
+                config_name,
+                pubkeys,
+                num_signatures,
+                instance: Some(instance),
+            },
+        )
+        .await?;
+
+        return Ok(BackupImportResult {
+            wallet_id: result.wallet_id,
+            multisig_config: Some(result.config_name),
+            started: result.started,
+            message: result.message,
+        });
+    }
+
+    let base_url = crate::headless_url(state, Some(&instance)).await?;
+    headless::post(
+        &base_url,
+        "/start",
+        &json!({
+            "wallet-id": wallet_id,
+            "seed": seed,
+        }),
+    )
+    .await
+    .map_err(|e| format!("Failed to start wallet: {}", e))?;
+    wallets::register_wallet(&wallet_id, &seed, &instance, None).await?;
+
+    Ok(BackupImportResult {
+        message: format!("Wallet '{}' restored from backup", wallet_id),
+        wallet_id,
+        multisig_config: None,
+        started: true,
+    })
+}
+
+// ============================================================================
+// Tauri Commands
+// ============================================================================
+
+// Export a registered wallet to a password-encrypted backup file
+#[tauri::command]
+pub async fn export_wallet_backup(
+    wallet_id: String,
+    password: String,
+    path: String,
+) -> Result<String, String> {
+    let seed = wallets::wallet_seed(&wallet_id).await?.ok_or_else(|| {
+        format!(
+            "The seed of wallet '{}' isn't in the keychain; enable keychain storage first",
+            wallet_id
+        )
+    })?;
+    // Argon2 is deliberately slow; keep it off the async runtime
+    tokio::task::spawn_blocking(move || {
+        export_wallet_backup_internal(&wallet_id, &seed, &password, &path)
+    })
+    .await
+    .map_err(|e| format!("Backup task failed: {}", e))?
+}
+
+// Restore a wallet from a backup file made by export_wallet_backup
+#[tauri::command]
+pub async fn import_wallet_backup(
+    state: tauri::State<'_, SharedState>,
+    path: String,
+    password: String,
+    wallet_id: Option<String>,
+    instance: Option<String>,
+) -> Result<BackupImportResult, String> {
+    import_wallet_backup_internal(
+        &state,
+        &path,
+        &password,
+        wallet_id.as_deref(),
+        instance.as_deref(),
+    )
+    .await
+}
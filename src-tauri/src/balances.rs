@@ -0,0 +1,196 @@
+//! Wallet balance-change events
+//!
+//! While the node runs, a background task follows the fullnode's WebSocket. Each
+//! accepted transaction (or burst of them) refreshes the balances of registered
+//! wallets on running wallet-headless instances, and every change is emitted as
+//! a `wallet-balance-changed` event so the UI doesn't have to poll.
+
+use futures_util::StreamExt;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter};
+use tokio_tungstenite::tungstenite::Message;
+
+use crate::{tokens, wallets, SharedState};
+
+const FULLNODE_WS_URL: &str = "ws://127.0.0.1:8080/v1a/ws/";
+const HTR_UID: &str = "00";
+const RECONNECT_DELAY: Duration = Duration::from_secs(2);
+// Lets wallet-headless process the transaction first, and batches bursts of them
+const SETTLE_DELAY: Duration = Duration::from_millis(500);
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BalanceChange {
+    pub wallet_id: String,
+    pub instance: String,
+    pub token: String,
+    pub delta: i64, // Change in cents (available + locked)
+    pub new_balance: u64,
+}
+
+#[derive(Default)]
+struct BalanceCache {
+    // (instance, wallet_id, token) -> available + locked
+    balances: HashMap<(String, String, String), u64>,
+    // Wallets whose balances were seeded; new wallets are seeded silently
+    wallets: HashSet<(String, String)>,
+}
+
+// Token UIDs moved by a `network:new_tx_accepted` message (None for other messages)
+fn tx_tokens(text: &str) -> Option<BTreeSet<String>> {
+    let event: Value = serde_json::from_str(text).ok()?;
+    if event["type"] != "network:new_tx_accepted" {
+        return None;
+    }
+
+    let mut tokens: BTreeSet<String> = ["inputs", "outputs"]
+        .iter()
+        .flat_map(|field| event[*field].as_array().cloned().unwrap_or_default())
+        .filter_map(|io| io["token"].as_str().map(|s| s.to_string()))
+        .collect();
+    tokens.insert(HTR_UID.to_string());
+    Some(tokens)
+}
+
+async fn wallet_balance(
+    client: &reqwest::Client,
+    base_url: &str,
+    wallet_id: &str,
+    token: &str,
+) -> Option<u64> {
+    let balance: Value = client
+        .get(format!("{}/wallet/balance", base_url))
+        .header("X-Wallet-Id", wallet_id)
+        .query(&[("token", token)])
+        .send()
+        .await
+        .ok()?
+        .json()
+        .await
+        .ok()?;
+
+    // Wallets that aren't started answer with an error body instead
+    let available = balance["available"].as_u64()?;
+    Some(available + balance["locked"].as_u64().unwrap_or(0))
+}
+
+// Refresh the balances of every tracked wallet for `tokens`, emitting changes
+async fn refresh(
+    app: &AppHandle,
+    state: &SharedState,
+    cache: &mut BalanceCache,
+    tokens: &BTreeSet<String>,
+) {
+    let client = reqwest::Client::new();
+
+    for wallet in wallets::list_wallets() {
+        let Ok(base_url) = crate::headless_url(state, Some(&wallet.instance)).await else {
+            continue;
+        };
+        let wallet_key = (wallet.instance.clone(), wallet.wallet_id.clone());
+        let seeded = cache.wallets.contains(&wallet_key);
+        let mut reachable = false;
+
+        for token in tokens {
+            let Some(new_balance) =
+                wallet_balance(&client, &base_url, &wallet.wallet_id, token).await
+            else {
+                continue;
+            };
+            reachable = true;
+
+            let key = (
+                wallet.instance.clone(),
+                wallet.wallet_id.clone(),
+                token.clone(),
+            );
+            let previous = cache.balances.insert(key, new_balance).unwrap_or(0);
+            if seeded && previous != new_balance {
+                let _ = app.emit(
+                    "wallet-balance-changed",
+                    BalanceChange {
+                        wallet_id: wallet.wallet_id.clone(),
+                        instance: wallet.instance.clone(),
+                        token: token.clone(),
+                        delta: new_balance as i64 - previous as i64,
+                        new_balance,
+                    },
+                );
+            }
+        }
+
+        if reachable {
+            cache.wallets.insert(wallet_key);
+        }
+    }
+}
+
+/// Follow the node's WebSocket for the lifetime of the app, emitting
+/// `wallet-balance-changed` events for registered wallets
+pub async fn watch_balances(app: AppHandle, state: SharedState) {
+    loop {
+        if !state.lock().await.node_running {
+            tokio::time::sleep(RECONNECT_DELAY).await;
+            continue;
+        }
+        let Ok((mut ws_stream, _)) = tokio_tungstenite::connect_async(FULLNODE_WS_URL).await else {
+            tokio::time::sleep(RECONNECT_DELAY).await;
+            continue;
+        };
+
+        // Seed HTR and registered token balances so the first change has a real delta
+        let mut cache = BalanceCache::default();
+        let mut known_tokens: BTreeSet<String> = tokens::list_tokens_internal(&state)
+            .await
+            .into_iter()
+            .map(|token| token.token_uid)
+            .collect();
+        known_tokens.insert(HTR_UID.to_string());
+        refresh(&app, &state, &mut cache, &known_tokens).await;
+
+        'connection: while let Some(message) = ws_stream.next().await {
+            let mut changed = match message {
+                Ok(Message::Text(text)) => match tx_tokens(&text) {
+                    Some(tokens) => tokens,
+                    None => continue,
+                },
+                Ok(Message::Close(_)) | Err(_) => break,
+                Ok(_) => continue,
+            };
+
+            // Collect the rest of the burst before querying the wallets
+            let deadline = tokio::time::Instant::now() + SETTLE_DELAY;
+            loop {
+                match tokio::time::timeout_at(deadline, ws_stream.next()).await {
+                    Err(_) => break,
+                    Ok(Some(Ok(Message::Text(text)))) => {
+                        changed.extend(tx_tokens(&text).unwrap_or_default());
+                    }
+                    Ok(Some(Ok(Message::Close(_)))) | Ok(Some(Err(_))) | Ok(None) => {
+                        refresh(&app, &state, &mut cache, &changed).await;
+                        break 'connection;
+                    }
+                    Ok(Some(Ok(_))) => {}
+                }
+            }
+
+            // Wallets opened since the last refresh get seeded with every known token
+            known_tokens.extend(changed.iter().cloned());
+            let has_new_wallets = wallets::list_wallets().iter().any(|wallet| {
+                !cache
+                    .wallets
+                    .contains(&(wallet.instance.clone(), wallet.wallet_id.clone()))
+            });
+            let tokens = if has_new_wallets {
+                &known_tokens
+            } else {
+                &changed
+            };
+            refresh(&app, &state, &mut cache, tokens).await;
+        }
+
+        tokio::time::sleep(RECONNECT_DELAY).await;
+    }
+}
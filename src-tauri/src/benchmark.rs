@@ -0,0 +1,147 @@
+//! Miner benchmark
+//!
+//! Runs cpuminer in `--benchmark` mode (hashing without a pool) once per
+//! thread count and reads its hash-rate reports, so users can pick a `threads`
+//! value for their machine. When the node runs, the current block difficulty
+//! turns each rate into an expected time per block.
+
+use serde::{Deserialize, Serialize};
+use std::process::Stdio;
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, BufReader};
+use tokio::process::Command as TokioCommand;
+
+use crate::miner_metrics::MinerMetrics;
+use crate::{miner_options, mining_info, SharedState};
+
+const MIN_SECONDS: u64 = 5;
+const MAX_SECONDS: u64 = 60;
+const DEFAULT_SECONDS: u64 = 10;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BenchmarkRun {
+    pub threads: u32,
+    // Average total hash rate in hashes per second
+    pub hash_rate: Option<f64>,
+    pub hash_rate_per_thread: Option<f64>,
+    pub expected_block_time_secs: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BenchmarkResult {
+    pub algorithm: String,
+    pub seconds_per_run: u64,
+    // Expected hashes per block on the running node, if any
+    pub difficulty: Option<f64>,
+    pub runs: Vec<BenchmarkRun>,
+    // Thread count with the highest hash rate
+    pub best_threads: Option<u32>,
+}
+
+// Hash for `seconds` with `threads` threads and average the reported rates
+async fn run_benchmark(
+    options: &miner_options::MinerOptions,
+    threads: u32,
+    seconds: u64,
+) -> Result<Option<f64>, String> {
+    let binary_path = crate::get_binary_path("cpuminer");
+    let mut child = TokioCommand::new(&binary_path)
+        .args(options.args())
+        .args(["--benchmark", "--threads", &threads.to_string()])
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+        .map_err(|e| format!("Failed to spawn cpuminer at {:?}: {}", binary_path, e))?;
+
+    let mut metrics = MinerMetrics::default();
+    if let Some(stderr) = child.stderr.take() {
+        let mut lines = BufReader::new(stderr).lines();
+        let deadline = tokio::time::Instant::now() + Duration::from_secs(seconds);
+        while let Ok(Ok(Some(line))) = tokio::time::timeout_at(deadline, lines.next_line()).await {
+            metrics.record(&line);
+        }
+    }
+    let _ = child.kill().await;
+
+    Ok(metrics.report().average_hash_rate)
+}
+
+/// Benchmark cpuminer with 1 up to `threads` threads (all cores by default),
+/// `seconds` per thread count
+pub async fn benchmark_miner_internal(
+    state: &SharedState,
+    seconds: Option<u64>,
+    threads: Option<u32>,
+) -> Result<BenchmarkResult, String> {
+    let seconds = seconds.unwrap_or(DEFAULT_SECONDS);
+    if !(MIN_SECONDS..=MAX_SECONDS).contains(&seconds) {
+        return Err(format!(
+            "Benchmark duration must be between {} and {} seconds",
+            MIN_SECONDS, MAX_SECONDS
+        ));
+    }
+    let max_threads = threads.unwrap_or(num_cpus::get() as u32);
+    if max_threads == 0 {
+        return Err("Threads must be at least 1".to_string());
+    }
+    // Running miners would skew the numbers
+    if !state.lock().await.miners.is_empty() {
+        return Err("Stop running miners before benchmarking".to_string());
+    }
+
+    let options = miner_options::load_options();
+    options.validate()?;
+
+    let difficulty = if state.lock().await.node_running {
+        mining_info::get_mining_info_internal(state, None)
+            .await
+            .ok()
+            .and_then(|info| info.difficulty)
+    } else {
+        None
+    };
+
+    let mut runs = Vec::new();
+    for threads in 1..=max_threads {
+        let hash_rate = run_benchmark(&options, threads, seconds).await?;
+        runs.push(BenchmarkRun {
+            threads,
+            hash_rate,
+            hash_rate_per_thread: hash_rate.map(|rate| rate / threads as f64),
+            expected_block_time_secs: difficulty
+                .zip(hash_rate)
+                .filter(|(_, rate)| *rate > 0.0)
+                .map(|(difficulty, rate)| difficulty / rate),
+        });
+    }
+
+    let best_threads = runs
+        .iter()
+        .filter_map(|run| run.hash_rate.map(|rate| (run.threads, rate)))
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(threads, _)| threads);
+
+    Ok(BenchmarkResult {
+        algorithm: options.algorithm,
+        seconds_per_run: seconds,
+        difficulty,
+        runs,
+        best_threads,
+    })
+}
+
+// ============================================================================
+// Tauri Commands
+// ============================================================================
+
+// Measure cpuminer's hash rate for each thread count
+#[tauri::command]
+pub async fn benchmark_miner(
+    state: tauri::State<'_, SharedState>,
+    seconds: Option<u64>,
+    threads: Option<u32>,
+) -> Result<BenchmarkResult, String> {
+    benchmark_miner_internal(&state, seconds, threads).await
+}
@@ -0,0 +1,217 @@
+//! Recent blocks, fetched concurrently and cached
+//!
+//! A block's content never changes, so fetched blocks are kept in an LRU keyed
+//! by hash, along with which hash sits at each height. Only the heights mapping
+//! can go stale: it is dropped on a reorg (seen on the node's event queue) and
+//! whenever the node's best block no longer matches it (e.g. after a data
+//! reset). Missing heights are fetched with a bounded number of requests in
+//! flight.
+
+use lru::LruCache;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::HashMap;
+use std::num::NonZeroUsize;
+use tokio::sync::broadcast;
+use tokio::task::JoinSet;
+
+use crate::events::NodeEvent;
+use crate::SharedState;
+
+const CACHE_CAPACITY: usize = 2_000;
+const MAX_CONCURRENT_FETCHES: usize = 8;
+const DEFAULT_COUNT: u64 = 10;
+const MAX_COUNT: u64 = 200;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BlockPage {
+    pub current_height: u64,
+    // Blocks below the best one that were skipped
+    pub offset: u64,
+    // Newest first, as returned by the node's block_at_height
+    pub blocks: Vec<Value>,
+}
+
+/// Fetched blocks and the hash at each height, kept in `AppState`
+pub struct BlockCache {
+    blocks: LruCache<String, Value>,
+    by_height: HashMap<u64, String>,
+    // Node events, to notice reorgs; subscribed on first use
+    node_events: Option<broadcast::Receiver<NodeEvent>>,
+}
+
+impl Default for BlockCache {
+    fn default() -> Self {
+        Self {
+            blocks: LruCache::new(NonZeroUsize::new(CACHE_CAPACITY).unwrap()),
+            by_height: HashMap::new(),
+            node_events: None,
+        }
+    }
+}
+
+impl BlockCache {
+    // Drop the heights mapping if the best chain may have changed under it
+    fn revalidate(&mut self, height: u64, best_hash: &str) {
+        let mut reorged = false;
+        if let Some(receiver) = self.node_events.as_mut() {
+            loop {
+                match receiver.try_recv() {
+                    Ok(NodeEvent::ReorgStarted { .. }) => reorged = true,
+                    Ok(_) => {}
+                    Err(broadcast::error::TryRecvError::Lagged(_)) => reorged = true,
+                    Err(_) => break,
+                }
+            }
+        }
+        let tip_mismatch = self
+            .by_height
+            .get(&height)
+            .is_some_and(|hash| hash != best_hash);
+        let above_tip = self.by_height.keys().any(|cached| *cached > height);
+        if reorged || tip_mismatch || above_tip {
+            self.by_height.clear();
+        }
+    }
+
+    fn get(&mut self, height: u64) -> Option<Value> {
+        let hash = self.by_height.get(&height)?;
+        self.blocks.get(hash).cloned()
+    }
+
+    fn insert(&mut self, height: u64, block: Value) {
+        let Some(hash) = block["tx_id"].as_str().map(|s| s.to_string()) else {
+            return;
+        };
+        self.by_height.insert(height, hash.clone());
+        self.blocks.put(hash, block);
+    }
+}
+
+// Best block height and hash
+async fn best_block(client: &reqwest::Client) -> Result<(u64, String), String> {
+    let status: Value = client
+        .get("http://127.0.0.1:8080/v1a/status/")
+        .send()
+        .await
+        .map_err(|e| format!("Failed to get node status: {}", e))?
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse node status: {}", e))?;
+    let best = &status["dag"]["best_block"];
+    let height = best["height"]
+        .as_u64()
+        .ok_or("Node status has no best block height")?;
+    Ok((
+        height,
+        best["hash"].as_str().unwrap_or_default().to_string(),
+    ))
+}
+
+async fn block_at_height(client: reqwest::Client, height: u64) -> Option<(u64, Value)> {
+    let response: Value = client
+        .get("http://127.0.0.1:8080/v1a/block_at_height")
+        .query(&[("height", height)])
+        .send()
+        .await
+        .ok()?
+        .json()
+        .await
+        .ok()?;
+    if !response["success"].as_bool().unwrap_or(false) {
+        return None;
+    }
+    Some((height, response["block"].clone()))
+}
+
+// Fetch `heights` with at most MAX_CONCURRENT_FETCHES requests in flight;
+// heights the node didn't answer for are left out
+async fn fetch_blocks(client: &reqwest::Client, heights: Vec<u64>) -> Vec<(u64, Value)> {
+    let mut pending = heights.into_iter();
+    let mut fetches = JoinSet::new();
+    for height in pending.by_ref().take(MAX_CONCURRENT_FETCHES) {
+        fetches.spawn(block_at_height(client.clone(), height));
+    }
+
+    let mut blocks = Vec::new();
+    while let Some(result) = fetches.join_next().await {
+        if let Ok(Some(block)) = result {
+            blocks.push(block);
+        }
+        if let Some(height) = pending.next() {
+            fetches.spawn(block_at_height(client.clone(), height));
+        }
+    }
+    blocks
+}
+
+/// `count` blocks (default 10, at most 200) ending `offset` blocks below the
+/// best block, newest first
+pub async fn get_blocks_internal(
+    state: &SharedState,
+    count: Option<u64>,
+    offset: Option<u64>,
+) -> Result<BlockPage, String> {
+    let client = reqwest::Client::new();
+    let (current_height, best_hash) = best_block(&client).await?;
+    let count = count.unwrap_or(DEFAULT_COUNT).clamp(1, MAX_COUNT);
+    let offset = offset.unwrap_or(0);
+
+    let top = current_height.saturating_sub(offset);
+    let heights: Vec<u64> = if offset > current_height {
+        Vec::new()
+    } else {
+        (top.saturating_sub(count - 1)..=top).rev().collect()
+    };
+
+    let mut found: HashMap<u64, Value> = HashMap::new();
+    let mut missing = Vec::new();
+    {
+        let mut state_guard = state.lock().await;
+        if state_guard.block_cache.node_events.is_none() {
+            state_guard.block_cache.node_events = Some(state_guard.node_events.subscribe());
+        }
+        let cache = &mut state_guard.block_cache;
+        cache.revalidate(current_height, &best_hash);
+        for height in &heights {
+            match cache.get(*height) {
+                Some(block) => {
+                    found.insert(*height, block);
+                }
+                None => missing.push(*height),
+            }
+        }
+    }
+
+    let fetched = fetch_blocks(&client, missing).await;
+    {
+        let mut state_guard = state.lock().await;
+        for (height, block) in &fetched {
+            state_guard.block_cache.insert(*height, block.clone());
+        }
+    }
+    found.extend(fetched);
+
+    Ok(BlockPage {
+        current_height,
+        offset,
+        blocks: heights
+            .iter()
+            .filter_map(|height| found.remove(height))
+            .collect(),
+    })
+}
+
+// ============================================================================
+// Tauri Commands
+// ============================================================================
+
+// Get a page of recent blocks, newest first
+#[tauri::command]
+pub async fn get_recent_blocks(
+    state: tauri::State<'_, SharedState>,
+    count: Option<u64>,
+    offset: Option<u64>,
+) -> Result<BlockPage, String> {
+    get_blocks_internal(&state, count, offset).await
+}
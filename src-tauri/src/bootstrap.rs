@@ -0,0 +1,440 @@
+//! Environment bootstrap
+//!
+//! `bootstrap_environment` builds a populated localnet from a declarative
+//! spec: it starts the node and wallet-headless, mines until the faucet can
+//! pay for everything, creates and funds N wallets, creates K custom tokens
+//! and M random transfers between the wallets, then mines up to a target
+//! height. The returned manifest lists everything that was created.
+//!
+//! The building blocks (node/wallet readiness, mining a number of blocks,
+//! funding from the faucet, sending HTR) are shared with the load generator
+//! and the scenario runner.
+
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::time::{Duration, Instant};
+
+use crate::amount::HtrAmount;
+use crate::tokens::{self, CreatedToken};
+use crate::{
+    auto_miner, faucet, headless, rewards, wallets, HeadlessConfig, MinerConfig, SharedState,
+};
+
+const BOOTSTRAP_MINER: &str = "bootstrap";
+const MAX_WALLETS: u32 = 50;
+const MAX_TOKENS: u32 = 20;
+const MAX_TRANSACTIONS: u32 = 500;
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+const BLOCK_TIMEOUT: Duration = Duration::from_secs(60);
+const FUNDS_TIMEOUT: Duration = Duration::from_secs(600);
+const BALANCE_TIMEOUT: Duration = Duration::from_secs(60);
+
+fn default_wallet_prefix() -> String {
+    "wallet".to_string()
+}
+
+fn default_token_amount() -> u64 {
+    10_000 // 100.00 tokens
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BootstrapSpec {
+    #[serde(default)]
+    pub wallets: u32,
+    // Wallets are named "<prefix>-1", "<prefix>-2", ...
+    #[serde(default = "default_wallet_prefix")]
+    pub wallet_prefix: String,
+    // Sent to each wallet from the faucet
+    #[serde(default)]
+    pub funding: HtrAmount,
+    #[serde(default)]
+    pub tokens: u32,
+    // Supply of each token, in token cents
+    #[serde(default = "default_token_amount")]
+    pub token_amount: u64,
+    // Random HTR transfers between the wallets
+    #[serde(default)]
+    pub transactions: u32,
+    #[serde(default)]
+    pub target_height: Option<u64>,
+    #[serde(default)]
+    pub instance: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BootstrapWallet {
+    pub wallet_id: String,
+    pub seed: String,
+    pub address: String,
+    pub funded: HtrAmount,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Transfer {
+    pub hash: String,
+    pub from: String,
+    pub to: String,
+    pub amount: HtrAmount,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BootstrapManifest {
+    pub instance: String,
+    pub wallets: Vec<BootstrapWallet>,
+    pub funding_tx_hashes: Vec<String>,
+    pub tokens: Vec<CreatedToken>,
+    pub transfers: Vec<Transfer>,
+    pub start_height: u64,
+    pub end_height: u64,
+    pub elapsed_secs: u64,
+}
+
+impl BootstrapSpec {
+    fn validate(&self) -> Result<(), String> {
+        if self.wallets > MAX_WALLETS {
+            return Err(format!("At most {} wallets", MAX_WALLETS));
+        }
+        if self.tokens > MAX_TOKENS {
+            return Err(format!("At most {} tokens", MAX_TOKENS));
+        }
+        if self.transactions > MAX_TRANSACTIONS {
+            return Err(format!("At most {} transactions", MAX_TRANSACTIONS));
+        }
+        if (self.tokens > 0 || self.transactions > 0) && self.funding.is_zero() {
+            return Err("Tokens and transactions need funded wallets (set funding)".to_string());
+        }
+        if self.tokens > 0 && self.wallets == 0 {
+            return Err("Tokens are created by wallets; set wallets to at least 1".to_string());
+        }
+        if self.transactions > 0 && self.wallets < 2 {
+            return Err("Transactions need at least 2 wallets".to_string());
+        }
+        if self.wallet_prefix.is_empty() {
+            return Err("wallet_prefix can't be empty".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// A random number in `0..n` (0 when `n` is 0)
+pub fn random_below(n: u64) -> u64 {
+    let mut bytes = [0u8; 8];
+    if n == 0 || getrandom::getrandom(&mut bytes).is_err() {
+        return 0;
+    }
+    u64::from_le_bytes(bytes) % n
+}
+
+/// Start the node if needed and wait until its API reports a best block
+pub async fn ensure_node_ready(state: &SharedState) -> Result<u64, String> {
+    if !state.lock().await.node_running {
+        crate::start_node_internal(state).await?;
+    }
+    auto_miner::wait_for_node_ready(state).await
+}
+
+/// Start a wallet-headless instance if needed and wait until it answers,
+/// returning its base URL
+pub async fn ensure_headless(
+    state: &SharedState,
+    instance: Option<&str>,
+) -> Result<String, String> {
+    if crate::headless_url(state, instance).await.is_err() {
+        let mut config = HeadlessConfig::default();
+        if let Some(instance) = instance {
+            config.instance = instance.to_string();
+        }
+        crate::start_headless_internal(state, config).await?;
+    }
+    let base_url = crate::headless_url(state, instance).await?;
+    if !wallets::wait_for_headless(&reqwest::Client::new(), &base_url).await {
+        return Err("Wallet-headless did not become available".to_string());
+    }
+    Ok(base_url)
+}
+
+/// Start a wallet (with `seed`, or a new one), register it and wait for it to
+/// sync, returning its seed
+pub async fn open_wallet(
+    base_url: &str,
+    wallet_id: &str,
+    seed: Option<&str>,
+    instance: Option<&str>,
+) -> Result<String, String> {
+    let seed = match seed {
+        Some(seed) => wallets::validate_mnemonic(seed)?,
+        None => crate::generate_seed_internal()?,
+    };
+
+    let client = reqwest::Client::new();
+    let result: serde_json::Value = client
+        .post(format!("{}/start", base_url))
+        .json(&json!({ "wallet-id": wallet_id, "seed": seed }))
+        .send()
+        .await
+        .map_err(|e| format!("Failed to create wallet '{}': {}", wallet_id, e))?
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse response: {}", e))?;
+    if !result["success"].as_bool().unwrap_or(false)
+        && result["errorCode"] != "WALLET_ALREADY_STARTED"
+    {
+        let message = result["message"].as_str().unwrap_or("Unknown error");
+        return Err(format!(
+            "Failed to create wallet '{}': {}",
+            wallet_id, message
+        ));
+    }
+
+    let instance = instance.unwrap_or(crate::DEFAULT_HEADLESS_INSTANCE);
+    if let Err(e) = wallets::register_wallet(wallet_id, &seed, instance, None).await {
+        eprintln!("Failed to register wallet: {}", e);
+    }
+    wallets::wait_for_wallet_ready(&client, base_url, wallet_id)
+        .await
+        .map_err(|e| format!("Wallet '{}': {}", wallet_id, e))?;
+    Ok(seed)
+}
+
+/// The wallet's current receive address
+pub async fn wallet_address(base_url: &str, wallet_id: &str) -> Result<String, String> {
+    let result = headless::wallet_get(base_url, wallet_id, "/wallet/address", &[]).await?;
+    result["address"]
+        .as_str()
+        .map(|s| s.to_string())
+        .ok_or_else(|| format!("Wallet '{}' returned no address", wallet_id))
+}
+
+/// The wallet's spendable HTR balance
+pub async fn wallet_balance(base_url: &str, wallet_id: &str) -> Result<HtrAmount, String> {
+    let balance = headless::wallet_get(base_url, wallet_id, "/wallet/balance", &[]).await?;
+    Ok(HtrAmount::from_json(&balance["available"]))
+}
+
+/// Wait until the wallet can spend at least `amount`
+pub async fn wait_for_balance(
+    base_url: &str,
+    wallet_id: &str,
+    amount: HtrAmount,
+) -> Result<(), String> {
+    let started = Instant::now();
+    while started.elapsed() < BALANCE_TIMEOUT {
+        if wallet_balance(base_url, wallet_id)
+            .await
+            .unwrap_or_default()
+            >= amount
+        {
+            return Ok(());
+        }
+        tokio::time::sleep(POLL_INTERVAL).await;
+    }
+    Err(format!(
+        "Wallet '{}' didn't receive {} within {}s",
+        wallet_id,
+        amount,
+        BALANCE_TIMEOUT.as_secs()
+    ))
+}
+
+/// Send HTR from a wallet, returning the transaction hash
+pub async fn send_htr(
+    base_url: &str,
+    wallet_id: &str,
+    address: &str,
+    amount: HtrAmount,
+) -> Result<String, String> {
+    let result = headless::wallet_post(
+        base_url,
+        wallet_id,
+        "/wallet/simple-send-tx",
+        &json!({ "address": address, "value": amount }),
+    )
+    .await
+    .map_err(|e| format!("Failed to send from '{}': {}", wallet_id, e))?;
+    Ok(result["hash"].as_str().unwrap_or("unknown").to_string())
+}
+
+/// Mine `count` blocks with a dedicated miner, returning the new height
+pub async fn mine_blocks(state: &SharedState, count: u64) -> Result<u64, String> {
+    let client = reqwest::Client::new();
+    let (height, _) = rewards::chain_info(&client).await?;
+    if count == 0 {
+        return Ok(height);
+    }
+    let target = height + count;
+
+    crate::start_miner_internal(
+        state,
+        MinerConfig {
+            name: BOOTSTRAP_MINER.to_string(),
+            stop_at_height: Some(target),
+            ..MinerConfig::default()
+        },
+    )
+    .await?;
+
+    let started = Instant::now();
+    let timeout = BLOCK_TIMEOUT * count as u32;
+    let result = loop {
+        match rewards::chain_info(&client).await {
+            Ok((height, _)) if height >= target => break Ok(height),
+            _ if started.elapsed() > timeout => {
+                break Err(format!("Timed out mining to height {}", target))
+            }
+            _ => tokio::time::sleep(POLL_INTERVAL).await,
+        }
+    };
+    crate::stop_miner_internal(state, Some(BOOTSTRAP_MINER)).await?;
+    result
+}
+
+/// Mine until the faucet can spend at least `amount`
+pub async fn ensure_faucet_funds(state: &SharedState, amount: HtrAmount) -> Result<(), String> {
+    let client = reqwest::Client::new();
+    if faucet::faucet_available(&client).await? >= amount {
+        return Ok(());
+    }
+
+    crate::start_miner_internal(
+        state,
+        MinerConfig {
+            name: BOOTSTRAP_MINER.to_string(),
+            ..MinerConfig::default()
+        },
+    )
+    .await?;
+
+    let started = Instant::now();
+    let result = loop {
+        tokio::time::sleep(POLL_INTERVAL).await;
+        match faucet::faucet_available(&client).await {
+            Ok(available) if available >= amount => break Ok(()),
+            _ if started.elapsed() > FUNDS_TIMEOUT => {
+                break Err(format!(
+                    "Timed out after {}s mining for {} of faucet funds",
+                    FUNDS_TIMEOUT.as_secs(),
+                    amount
+                ))
+            }
+            _ => {}
+        }
+    };
+    crate::stop_miner_internal(state, Some(BOOTSTRAP_MINER)).await?;
+    result
+}
+
+/// Build the environment described by `spec`
+pub async fn bootstrap_environment_internal(
+    state: &SharedState,
+    spec: BootstrapSpec,
+) -> Result<BootstrapManifest, String> {
+    spec.validate()?;
+    let started = Instant::now();
+    let instance = spec.instance.as_deref();
+
+    let start_height = ensure_node_ready(state).await?;
+    let mut manifest = BootstrapManifest {
+        instance: instance
+            .unwrap_or(crate::DEFAULT_HEADLESS_INSTANCE)
+            .to_string(),
+        wallets: Vec::new(),
+        funding_tx_hashes: Vec::new(),
+        tokens: Vec::new(),
+        transfers: Vec::new(),
+        start_height,
+        end_height: start_height,
+        elapsed_secs: 0,
+    };
+
+    if spec.wallets > 0 {
+        let base_url = ensure_headless(state, instance).await?;
+
+        for i in 1..=spec.wallets {
+            let wallet_id = format!("{}-{}", spec.wallet_prefix, i);
+            let seed = open_wallet(&base_url, &wallet_id, None, instance).await?;
+            let address = wallet_address(&base_url, &wallet_id).await?;
+            manifest.wallets.push(BootstrapWallet {
+                wallet_id,
+                seed,
+                address,
+                funded: HtrAmount::default(),
+            });
+        }
+
+        if !spec.funding.is_zero() {
+            let total = HtrAmount::from_cents(spec.funding.cents() * spec.wallets as u64);
+            ensure_faucet_funds(state, total).await?;
+
+            let payments: Vec<faucet::FaucetPayment> = manifest
+                .wallets
+                .iter()
+                .map(|wallet| faucet::FaucetPayment {
+                    address: wallet.address.clone(),
+                    amount: spec.funding,
+                    timelock: None,
+                })
+                .collect();
+            manifest.funding_tx_hashes = faucet::faucet_distribute_internal(state, &payments)
+                .await?
+                .hashes;
+            for wallet in &mut manifest.wallets {
+                wait_for_balance(&base_url, &wallet.wallet_id, spec.funding).await?;
+                wallet.funded = spec.funding;
+            }
+        }
+
+        // Tokens are created round-robin by the wallets
+        for i in 0..spec.tokens {
+            let wallet = &manifest.wallets[i as usize % manifest.wallets.len()];
+            let token = tokens::create_token_internal(
+                state,
+                &wallet.wallet_id,
+                &format!("Token {}", i + 1),
+                &format!("TK{}", i + 1),
+                spec.token_amount,
+                instance,
+            )
+            .await?;
+            manifest.tokens.push(token);
+        }
+
+        // Small random amounts (up to 1% of the funding) between random pairs
+        let max_amount = (spec.funding.cents() / 100).max(1);
+        for _ in 0..spec.transactions {
+            let from = random_below(manifest.wallets.len() as u64) as usize;
+            let offset = 1 + random_below(manifest.wallets.len() as u64 - 1) as usize;
+            let to = (from + offset) % manifest.wallets.len();
+            let amount = HtrAmount::from_cents(1 + random_below(max_amount));
+
+            let (from, to) = (&manifest.wallets[from], &manifest.wallets[to]);
+            let hash = send_htr(&base_url, &from.wallet_id, &to.address, amount).await?;
+            manifest.transfers.push(Transfer {
+                hash,
+                from: from.wallet_id.clone(),
+                to: to.wallet_id.clone(),
+                amount,
+            });
+        }
+    }
+
+    let (height, _) = rewards::chain_info(&reqwest::Client::new()).await?;
+    manifest.end_height = match spec.target_height {
+        Some(target) if target > height => mine_blocks(state, target - height).await?,
+        _ => height,
+    };
+    manifest.elapsed_secs = started.elapsed().as_secs();
+    Ok(manifest)
+}
+
+// ============================================================================
+// Tauri Commands
+// ============================================================================
+
+// Build a populated environment (wallets, funding, tokens, transfers, height) from a spec
+#[tauri::command]
+pub async fn bootstrap_environment(
+    state: tauri::State<'_, SharedState>,
+    spec: BootstrapSpec,
+) -> Result<BootstrapManifest, String> {
+    bootstrap_environment_internal(&state, spec).await
+}
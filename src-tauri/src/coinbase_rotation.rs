@@ -0,0 +1,52 @@
+//! Coinbase address rotation
+//!
+//! cpuminer pays every block to a single `--coinbase-addr`, which leaves the
+//! faucet with one address holding every reward. With rotation, a miner cycles
+//! through the first N node wallet addresses: after each block it finds, the
+//! session tracker restarts it with the next address, so block rewards spread
+//! over the wallet like they would in real use.
+
+use crate::{faucet, MinerConfig};
+
+const MIN_ADDRESSES: u32 = 2;
+const MAX_ADDRESSES: u32 = 100;
+
+fn addresses(count: u32) -> Result<Vec<String>, String> {
+    if !(MIN_ADDRESSES..=MAX_ADDRESSES).contains(&count) {
+        return Err(format!(
+            "Coinbase rotation needs between {} and {} addresses",
+            MIN_ADDRESSES, MAX_ADDRESSES
+        ));
+    }
+    faucet::node_wallet_addresses(count)
+}
+
+/// Point a rotating miner at the first address of its rotation, unless it
+/// already mines to one of them
+pub fn prepare(config: &mut MinerConfig) -> Result<(), String> {
+    let Some(count) = config.rotate_coinbase else {
+        return Ok(());
+    };
+    let addresses = addresses(count)?;
+    if !addresses.contains(&config.address) {
+        config.address = addresses[0].clone();
+    }
+    Ok(())
+}
+
+/// The config to restart a rotating miner with after it found a block
+pub fn next(config: &MinerConfig) -> Result<MinerConfig, String> {
+    let count = config
+        .rotate_coinbase
+        .ok_or("Miner doesn't rotate its coinbase address")?;
+    let addresses = addresses(count)?;
+    let next = addresses
+        .iter()
+        .position(|address| *address == config.address)
+        .map_or(0, |i| (i + 1) % addresses.len());
+
+    Ok(MinerConfig {
+        address: addresses[next].clone(),
+        ..config.clone()
+    })
+}
@@ -0,0 +1,202 @@
+//! hathor-core / wallet-headless compatibility
+//!
+//! Before a wallet-headless instance starts, the node's version (from
+//! `/v1a/version`) and the instance's (from its `package.json`) are checked
+//! against the matrix below. A combination known to be broken refuses to
+//! start unless `ignore_compatibility` is set; one outside the tested ranges
+//! starts with a warning. When either version can't be read the check is
+//! skipped rather than blocking the start.
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::path::Path;
+use tracing::Instrument;
+
+use crate::HeadlessConfig;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+struct Version(u64, u64, u64);
+
+impl Version {
+    // "0.63.0", "v0.63.0-rc.1" or "0.63" (pre-release tags are ignored)
+    fn parse(version: &str) -> Option<Self> {
+        let core = version.trim().trim_start_matches('v');
+        let core = core.split(['-', '+']).next()?;
+        let mut parts = core.split('.').map(|part| part.parse::<u64>());
+        let major = parts.next()?.ok()?;
+        let minor = parts.next().unwrap_or(Ok(0)).ok()?;
+        let patch = parts.next().unwrap_or(Ok(0)).ok()?;
+        Some(Self(major, minor, patch))
+    }
+}
+
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.0, self.1, self.2)
+    }
+}
+
+// Versions from `from` (inclusive) up to `below` (exclusive)
+struct Range {
+    from: Version,
+    below: Version,
+}
+
+impl Range {
+    fn contains(&self, version: Version) -> bool {
+        self.from <= version && version < self.below
+    }
+}
+
+struct KnownIssue {
+    core: Range,
+    headless: Range,
+    reason: &'static str,
+}
+
+// Combinations Forge is built and tested with
+const TESTED_CORE: Range = Range {
+    from: Version(0, 59, 0),
+    below: Version(0, 70, 0),
+};
+const TESTED_HEADLESS: Range = Range {
+    from: Version(0, 30, 0),
+    below: Version(0, 40, 0),
+};
+
+// Combinations known to be broken; add entries as they are found
+const KNOWN_BROKEN: &[KnownIssue] = &[KnownIssue {
+    core: Range {
+        from: Version(0, 0, 0),
+        below: Version(0, 59, 0),
+    },
+    headless: Range {
+        from: Version(0, 30, 0),
+        below: Version(u64::MAX, 0, 0),
+    },
+    reason: "wallet-headless 0.30+ relies on fullnode APIs that hathor-core only has from 0.59",
+}];
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Compatibility {
+    Compatible,
+    // Works as far as known, but isn't a tested combination
+    Untested,
+    Broken,
+    // A version couldn't be read
+    Unknown,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CompatibilityReport {
+    pub core_version: Option<String>,
+    pub headless_version: Option<String>,
+    pub status: Compatibility,
+    pub issues: Vec<String>,
+}
+
+/// hathor-core version of the running node
+pub async fn core_version() -> Option<String> {
+    let url = "http://127.0.0.1:8080/v1a/version";
+    let version: serde_json::Value = reqwest::Client::new()
+        .get(url)
+        .send()
+        .instrument(crate::telemetry::http_span("GET", url))
+        .await
+        .ok()?
+        .json()
+        .await
+        .ok()?;
+    version["version"].as_str().map(|s| s.to_string())
+}
+
+/// wallet-headless version of a dist directory, from its package.json
+pub fn headless_version(dist_path: &Path) -> Option<String> {
+    let package = std::fs::read_to_string(dist_path.join("package.json")).ok()?;
+    let package: serde_json::Value = serde_json::from_str(&package).ok()?;
+    package["version"].as_str().map(|s| s.to_string())
+}
+
+fn evaluate(core_version: Option<String>, headless_version: Option<String>) -> CompatibilityReport {
+    let versions = (
+        core_version.as_deref().and_then(Version::parse),
+        headless_version.as_deref().and_then(Version::parse),
+    );
+    let (Some(core), Some(headless)) = versions else {
+        return CompatibilityReport {
+            core_version,
+            headless_version,
+            status: Compatibility::Unknown,
+            issues: vec!["Could not read the hathor-core or wallet-headless version".to_string()],
+        };
+    };
+
+    let broken: Vec<String> = KNOWN_BROKEN
+        .iter()
+        .filter(|issue| issue.core.contains(core) && issue.headless.contains(headless))
+        .map(|issue| issue.reason.to_string())
+        .collect();
+    let (status, issues) = if !broken.is_empty() {
+        (Compatibility::Broken, broken)
+    } else if !TESTED_CORE.contains(core) || !TESTED_HEADLESS.contains(headless) {
+        (
+            Compatibility::Untested,
+            vec![format!(
+                "hathor-core {} with wallet-headless {} is untested (tested: hathor-core {} to {}, wallet-headless {} to {})",
+                core,
+                headless,
+                TESTED_CORE.from,
+                TESTED_CORE.below,
+                TESTED_HEADLESS.from,
+                TESTED_HEADLESS.below
+            )],
+        )
+    } else {
+        (Compatibility::Compatible, Vec::new())
+    };
+
+    CompatibilityReport {
+        core_version,
+        headless_version,
+        status,
+        issues,
+    }
+}
+
+/// Compare the running node with a wallet-headless dist directory (the
+/// bundled one by default)
+pub async fn check_compatibility_internal(dist_path: Option<&str>) -> CompatibilityReport {
+    let config = HeadlessConfig {
+        dist_path: dist_path.map(|s| s.to_string()),
+        ..HeadlessConfig::default()
+    };
+    let headless_path = crate::resolve_headless_dist_path(&config);
+    evaluate(core_version().await, headless_version(&headless_path))
+}
+
+/// Check the combination a headless instance would run with; a broken one is
+/// an error unless the config overrides it, and an untested one yields a warning
+pub async fn ensure_compatible(config: &HeadlessConfig) -> Result<Option<String>, String> {
+    let report = check_compatibility_internal(config.dist_path.as_deref()).await;
+    match report.status {
+        Compatibility::Broken if !config.ignore_compatibility => Err(format!(
+            "hathor-core {} and wallet-headless {} are incompatible: {}. Start with ignore_compatibility to override.",
+            report.core_version.unwrap_or_default(),
+            report.headless_version.unwrap_or_default(),
+            report.issues.join("; ")
+        )),
+        Compatibility::Broken | Compatibility::Untested => Ok(Some(report.issues.join("; "))),
+        Compatibility::Compatible | Compatibility::Unknown => Ok(None),
+    }
+}
+
+// ============================================================================
+// Tauri Commands
+// ============================================================================
+
+// Check the node and wallet-headless versions against the compatibility matrix
+#[tauri::command]
+pub async fn check_compatibility(dist_path: Option<String>) -> Result<CompatibilityReport, String> {
+    Ok(check_compatibility_internal(dist_path.as_deref()).await)
+}
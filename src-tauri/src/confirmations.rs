@@ -0,0 +1,194 @@
+//! Transaction confirmation and voiding
+//!
+//! `get_tx_confirmation` reports where a transaction stands: the block that
+//! first confirmed it, its accumulated weight (and the node's confidence
+//! level), and whether it's voided and by what. Transactions can be watched:
+//! when the event queue reports a watched one voided (a conflicting
+//! transaction won, or a reorg dropped its block), `watched-tx-voided` is
+//! emitted with its fresh confirmation state. The plain `tx-voided` event
+//! (see events.rs) covers every vertex, watched or not.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeSet;
+use tauri::{AppHandle, Emitter};
+use tokio::sync::broadcast;
+
+use crate::events::NodeEvent;
+use crate::{NodeConfig, SharedState};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TxConfirmation {
+    pub tx_id: String,
+    pub is_block: bool,
+    // Unset while the transaction is in the mempool
+    pub first_block: Option<String>,
+    pub first_block_height: Option<u64>,
+    // log2 of the work confirming it, as the node reports it
+    pub accumulated_weight: Option<f64>,
+    // 0 to 1, how settled the node considers the transaction
+    pub confirmation_level: Option<f64>,
+    pub voided: bool,
+    pub voided_by: Vec<String>,
+    pub conflict_with: Vec<String>,
+    pub watched: bool,
+}
+
+fn api_url(path: &str) -> String {
+    format!(
+        "http://127.0.0.1:{}/v1a/{}",
+        NodeConfig::default().api_port,
+        path
+    )
+}
+
+async fn node_get(client: &reqwest::Client, path: &str, id: &str) -> Result<Value, String> {
+    client
+        .get(api_url(path))
+        .query(&[("id", id)])
+        .send()
+        .await
+        .map_err(|e| format!("Failed to query the node: {}", e))?
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse node response: {}", e))
+}
+
+fn hashes(value: &Value) -> Vec<String> {
+    value
+        .as_array()
+        .map(|hashes| {
+            hashes
+                .iter()
+                .filter_map(|h| h.as_str().map(|s| s.to_string()))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+async fn fetch_confirmation(state: &SharedState, tx_id: &str) -> Result<TxConfirmation, String> {
+    let client = reqwest::Client::new();
+    let response = node_get(&client, "transaction", tx_id).await?;
+    if !response["success"].as_bool().unwrap_or(false) {
+        return Err(format!("Transaction {} not found", tx_id));
+    }
+    let tx = &response["tx"];
+    let meta = &response["meta"];
+    let is_block = matches!(tx["version"].as_u64(), Some(0) | Some(3));
+
+    let first_block = meta["first_block"].as_str().map(|s| s.to_string());
+    let first_block_height = match &first_block {
+        Some(block) => node_get(&client, "transaction", block)
+            .await
+            .ok()
+            .and_then(|response| response["meta"]["height"].as_u64()),
+        None => None,
+    };
+    // Blocks have no accumulated weight of their own worth reporting
+    let weight = if is_block {
+        Value::Null
+    } else {
+        node_get(&client, "transaction_acc_weight", tx_id)
+            .await
+            .unwrap_or(Value::Null)
+    };
+
+    let voided_by = hashes(&meta["voided_by"]);
+    Ok(TxConfirmation {
+        tx_id: tx_id.to_string(),
+        is_block,
+        first_block,
+        first_block_height,
+        accumulated_weight: weight["accumulated_weight"].as_f64(),
+        confirmation_level: weight["confirmation_level"].as_f64(),
+        voided: !voided_by.is_empty(),
+        voided_by,
+        conflict_with: hashes(&meta["conflict_with"]),
+        watched: state.lock().await.watched_txs.contains(tx_id),
+    })
+}
+
+/// Confirmation state of a transaction, optionally watching it for voiding
+pub async fn get_tx_confirmation_internal(
+    state: &SharedState,
+    tx_id: &str,
+    watch: bool,
+) -> Result<TxConfirmation, String> {
+    if !state.lock().await.node_running {
+        return Err("Node is not running".to_string());
+    }
+    let tx_id = tx_id.trim().to_lowercase();
+    let mut confirmation = fetch_confirmation(state, &tx_id).await?;
+    if watch {
+        state.lock().await.watched_txs.insert(tx_id);
+        confirmation.watched = true;
+    }
+    Ok(confirmation)
+}
+
+/// Stop watching a transaction; returns whether it was watched
+pub async fn unwatch_tx_internal(state: &SharedState, tx_id: &str) -> bool {
+    state
+        .lock()
+        .await
+        .watched_txs
+        .remove(&tx_id.trim().to_lowercase())
+}
+
+/// Watched transactions
+pub async fn list_watched_txs_internal(state: &SharedState) -> BTreeSet<String> {
+    state.lock().await.watched_txs.clone()
+}
+
+/// Emit `watched-tx-voided` when a watched transaction is voided, for the
+/// lifetime of the app
+pub async fn watch_voided(app: AppHandle, state: SharedState) {
+    let mut events = state.lock().await.node_events.subscribe();
+    loop {
+        let hash = match events.recv().await {
+            Ok(NodeEvent::TxVoided { hash, .. }) => hash,
+            Ok(_) | Err(broadcast::error::RecvError::Lagged(_)) => continue,
+            Err(broadcast::error::RecvError::Closed) => return,
+        };
+        if !state.lock().await.watched_txs.contains(&hash) {
+            continue;
+        }
+        match fetch_confirmation(&state, &hash).await {
+            Ok(confirmation) => {
+                let _ = app.emit("watched-tx-voided", &confirmation);
+            }
+            Err(e) => eprintln!("Failed to get confirmation of {}: {}", hash, e),
+        }
+    }
+}
+
+// ============================================================================
+// Tauri Commands
+// ============================================================================
+
+// Get a transaction's first block, accumulated weight and voided status
+#[tauri::command]
+pub async fn get_tx_confirmation(
+    state: tauri::State<'_, SharedState>,
+    tx_id: String,
+    watch: Option<bool>,
+) -> Result<TxConfirmation, String> {
+    get_tx_confirmation_internal(&state, &tx_id, watch.unwrap_or(false)).await
+}
+
+// Stop emitting watched-tx-voided for a transaction
+#[tauri::command]
+pub async fn unwatch_tx(
+    state: tauri::State<'_, SharedState>,
+    tx_id: String,
+) -> Result<bool, String> {
+    Ok(unwatch_tx_internal(&state, &tx_id).await)
+}
+
+// List the transactions watched for voiding
+#[tauri::command]
+pub async fn list_watched_txs(
+    state: tauri::State<'_, SharedState>,
+) -> Result<BTreeSet<String>, String> {
+    Ok(list_watched_txs_internal(&state).await)
+}
@@ -0,0 +1,912 @@
+//! HTTP control API for scripts and CI
+//!
+//! A REST server on `127.0.0.1` (port configurable, `DEFAULT_PORT` otherwise)
+//! exposing the core operations: starting and stopping services, status,
+//! snapshots, wallets, the faucet and recent logs. It lets CI pipelines drive
+//! a Forge environment without Tauri or an MCP client. The handlers run the
+//! same operations as the MCP tools of the same name, so both surfaces behave
+//! alike.
+//!
+//! `/events` is a WebSocket streaming the event bus (see event_bus.rs) as JSON
+//! messages, optionally filtered with `categories`, `events`, `services` (log
+//! sources) and `contains` (log line text) query parameters, e.g.
+//! `/events?categories=log&services=node&contains=ERROR`.
+//!
+//! The API is described by an OpenAPI 3 document at `/openapi.json` (built
+//! from the handlers' `utoipa::path` annotations), browsable with Swagger UI
+//! at `/docs`.
+//!
+//! Every route but `/health`, `/openapi.json` and `/docs` requires `Authorization: Bearer <token>` (or a
+//! `token` query parameter, for WebSocket clients that can't set headers). The
+//! token is generated on first use and kept in `control-api-token` in the app
+//! directory (readable only by the user), where scripts can read it. The
+//! server is off by default; the setting lives in `control_api.json` and is
+//! applied at startup and whenever it changes.
+
+use axum::extract::ws::{Message, WebSocket, WebSocketUpgrade};
+use axum::extract::{Path, Query, Request, State};
+use axum::http::{header, StatusCode};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use axum::routing::{delete, get, post};
+use axum::{Json, Router};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::fs;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use tokio::sync::broadcast::error::RecvError;
+use utoipa::openapi::security::{HttpAuthScheme, HttpBuilder, SecurityScheme};
+use utoipa::{IntoParams, Modify, OpenApi, ToSchema};
+use utoipa_swagger_ui::SwaggerUi;
+
+use crate::access_log::AccessError;
+use crate::event_bus::BusEvent;
+use crate::mcp::McpState;
+use crate::settings::JsonSettings;
+use crate::SharedState;
+
+pub const DEFAULT_PORT: u16 = 9877;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ControlApiSettings {
+    pub enabled: bool,
+    pub port: u16,
+}
+
+impl Default for ControlApiSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            port: DEFAULT_PORT,
+        }
+    }
+}
+
+/// The running server, kept in `AppState`
+#[derive(Default)]
+pub struct ControlApi {
+    shutdown: Option<tokio::sync::oneshot::Sender<()>>,
+    url: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ControlApiInfo {
+    pub enabled: bool,
+    pub running: bool,
+    pub port: u16,
+    pub url: Option<String>,
+    pub token: String,
+    pub token_path: String,
+}
+
+pub static SETTINGS: JsonSettings<ControlApiSettings> =
+    JsonSettings::new("control_api.json", "control API settings");
+
+fn token_path() -> std::path::PathBuf {
+    crate::get_app_dir().join("control-api-token")
+}
+
+// Write a fresh random token, readable only by the user
+fn generate_token() -> Result<String, String> {
+    let mut bytes = [0u8; 32];
+    getrandom::getrandom(&mut bytes).map_err(|e| format!("Failed to generate token: {}", e))?;
+    let token = hex::encode(bytes);
+
+    let path = token_path();
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Failed to create app directory: {}", e))?;
+    }
+    crate::write_private_file(&path, &token)
+        .map_err(|e| format!("Failed to write control API token: {}", e))?;
+    Ok(token)
+}
+
+/// The API token, generated on first use
+pub fn token() -> Result<String, String> {
+    match fs::read_to_string(token_path()) {
+        Ok(token) if !token.trim().is_empty() => Ok(token.trim().to_string()),
+        _ => generate_token(),
+    }
+}
+
+// ============================================================================
+// Server
+// ============================================================================
+
+#[derive(Clone)]
+struct ControlState {
+    app_state: SharedState,
+    // Runs the operations; has its own session seed cache
+    mcp: Arc<McpState>,
+}
+
+// Compare without leaking the position of the first mismatch
+fn tokens_match(given: &str, expected: &str) -> bool {
+    given.len() == expected.len()
+        && given
+            .bytes()
+            .zip(expected.bytes())
+            .fold(0u8, |diff, (a, b)| diff | (a ^ b))
+            == 0
+}
+
+fn error_response(status: StatusCode, message: String) -> Response {
+    let mut response = (
+        status,
+        Json(ApiError {
+            success: false,
+            message: message.clone(),
+        }),
+    )
+        .into_response();
+    response.extensions_mut().insert(AccessError(message));
+    response
+}
+
+// Reject requests without the token; it's re-read so a regenerated token
+// applies right away
+async fn require_token(req: Request, next: Next) -> Response {
+    let expected = match token() {
+        Ok(token) => token,
+        Err(e) => return error_response(StatusCode::INTERNAL_SERVER_ERROR, e),
+    };
+    let given = req
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .or_else(|| {
+            req.uri()
+                .query()?
+                .split('&')
+                .find_map(|pair| pair.strip_prefix("token="))
+        })
+        .unwrap_or_default();
+    if !tokens_match(given.trim(), &expected) {
+        return error_response(
+            StatusCode::UNAUTHORIZED,
+            "Missing or invalid bearer token".to_string(),
+        );
+    }
+    next.run(req).await
+}
+
+/// Response of a successful operation
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ApiResult {
+    pub success: bool,
+    /// The operation's JSON output, or `{"message": ...}` for operations that only report text
+    #[schema(value_type = Object)]
+    pub result: Value,
+}
+
+/// Response of a failed or rejected request
+#[derive(Debug, Serialize, ToSchema)]
+pub struct ApiError {
+    pub success: bool,
+    pub message: String,
+}
+
+/// Selects a wallet-headless instance
+#[derive(Debug, Deserialize, IntoParams)]
+pub struct InstanceQuery {
+    /// Wallet-headless instance (default: "default")
+    instance: Option<String>,
+}
+
+/// Narrows down a service's recent log lines
+#[derive(Debug, Serialize, Deserialize, IntoParams)]
+pub struct LogsQuery {
+    /// Most recent matching lines to return (default: 200, max 5000)
+    lines: Option<usize>,
+    /// Only lines after this Unix time in milliseconds
+    since: Option<u64>,
+    /// Only lines containing this text, ignoring case
+    filter: Option<String>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize, ToSchema)]
+pub struct StartMinerRequest {
+    /// Miner name (default: "default")
+    name: Option<String>,
+    /// Mining threads (default: 1)
+    threads: Option<u32>,
+    /// Reward address (default: the node wallet's)
+    address: Option<String>,
+    /// Stop once the chain reaches this height
+    stop_at_height: Option<u64>,
+    /// Stop once the faucet's spendable balance reaches this many HTR
+    stop_at_faucet_balance: Option<f64>,
+    /// Run at the lowest OS priority
+    low_priority: Option<bool>,
+    /// Cap threads to physical cores minus one
+    cap_threads: Option<bool>,
+    /// Rotate the coinbase across the first N node wallet addresses
+    rotate_coinbase: Option<u32>,
+    /// Run this miner binary instead of the bundled cpuminer
+    custom_miner_path: Option<String>,
+    custom_miner_args: Option<Vec<String>>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct SnapshotRequest {
+    /// Snapshot name (letters, digits, '-' and '_')
+    name: String,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct CreateWalletRequest {
+    wallet_id: String,
+    /// 24-word BIP39 seed (generated when absent)
+    seed: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct SendRequest {
+    /// Destination address
+    address: String,
+    /// Amount of HTR (decimal)
+    amount: f64,
+    /// Unix timestamp until which the funds are locked
+    timelock: Option<u64>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize, ToSchema)]
+pub struct FundWalletRequest {
+    /// Amount of HTR (decimal; a reasonable amount when absent)
+    amount: Option<f64>,
+}
+
+#[derive(Debug, Serialize, Deserialize, ToSchema)]
+pub struct DistributeRequest {
+    payments: Vec<SendRequest>,
+}
+
+// Run an operation; its output is JSON for most operations, and a plain
+// message for the rest
+async fn run(api: &ControlState, operation: &str, params: Value) -> Response {
+    match crate::mcp::execute_tool(&api.mcp, operation, &params).await {
+        Ok(output) => {
+            let result = serde_json::from_str::<Value>(&output)
+                .unwrap_or_else(|_| json!({ "message": output }));
+            Json(ApiResult {
+                success: true,
+                result,
+            })
+            .into_response()
+        }
+        Err(e) => error_response(StatusCode::BAD_REQUEST, e),
+    }
+}
+
+// Operation parameters: the request body with path segments and query
+// parameters merged in; unset fields are left out
+fn params(body: impl Serialize, extra: Value) -> Value {
+    let mut params = match serde_json::to_value(body) {
+        Ok(Value::Object(body)) => body,
+        _ => serde_json::Map::new(),
+    };
+    if let Value::Object(extra) = extra {
+        params.extend(extra);
+    }
+    params.retain(|_, value| !value.is_null());
+    Value::Object(params)
+}
+
+async fn health() -> impl IntoResponse {
+    (StatusCode::OK, "OK")
+}
+
+#[utoipa::path(get, path = "/v1/status", tag = "environment",
+    responses((status = 200, description = "Node, miners, wallet-headless instances and faucet balance", body = ApiResult)))]
+async fn status(State(api): State<ControlState>) -> Response {
+    run(&api, "get_full_status", json!({})).await
+}
+
+#[utoipa::path(post, path = "/v1/quick-start", tag = "environment",
+    responses((status = 200, description = "Starts the node, a miner and wallet-headless", body = ApiResult)))]
+async fn quick_start(State(api): State<ControlState>) -> Response {
+    run(&api, "quick_start", json!({})).await
+}
+
+#[utoipa::path(post, path = "/v1/quick-stop", tag = "environment",
+    responses((status = 200, description = "Stops every service", body = ApiResult)))]
+async fn quick_stop(State(api): State<ControlState>) -> Response {
+    run(&api, "quick_stop", json!({})).await
+}
+
+#[utoipa::path(get, path = "/v1/node", tag = "node",
+    responses((status = 200, description = "Whether the node runs, and its /v1a/status", body = ApiResult)))]
+async fn node_status(State(api): State<ControlState>) -> Response {
+    run(&api, "get_node_status", json!({})).await
+}
+
+#[utoipa::path(post, path = "/v1/node/start", tag = "node",
+    responses((status = 200, body = ApiResult), (status = 400, body = ApiError)))]
+async fn start_node(State(api): State<ControlState>) -> Response {
+    run(&api, "start_node", json!({})).await
+}
+
+#[utoipa::path(post, path = "/v1/node/stop", tag = "node",
+    responses((status = 200, description = "Stops the node with its miners and wallet-headless", body = ApiResult), (status = 400, body = ApiError)))]
+async fn stop_node(State(api): State<ControlState>) -> Response {
+    run(&api, "stop_node", json!({})).await
+}
+
+#[utoipa::path(get, path = "/v1/miners", tag = "mining",
+    responses((status = 200, description = "Running miners", body = ApiResult)))]
+async fn miner_status(State(api): State<ControlState>) -> Response {
+    run(&api, "get_miner_status", json!({})).await
+}
+
+#[utoipa::path(post, path = "/v1/miners", tag = "mining", request_body(content = Option<StartMinerRequest>),
+    responses((status = 200, body = ApiResult), (status = 400, body = ApiError)))]
+async fn start_miner(
+    State(api): State<ControlState>,
+    body: Option<Json<StartMinerRequest>>,
+) -> Response {
+    let body = body.map(|Json(body)| body).unwrap_or_default();
+    run(&api, "start_miner", params(body, json!({}))).await
+}
+
+#[utoipa::path(delete, path = "/v1/miners", tag = "mining",
+    responses((status = 200, description = "Stops every miner", body = ApiResult)))]
+async fn stop_miners(State(api): State<ControlState>) -> Response {
+    run(&api, "stop_miner", json!({})).await
+}
+
+#[utoipa::path(delete, path = "/v1/miners/{name}", tag = "mining",
+    params(("name" = String, Path, description = "Miner name")),
+    responses((status = 200, body = ApiResult), (status = 400, body = ApiError)))]
+async fn stop_miner(State(api): State<ControlState>, Path(name): Path<String>) -> Response {
+    run(&api, "stop_miner", json!({ "name": name })).await
+}
+
+#[utoipa::path(post, path = "/v1/headless/start", tag = "wallets", params(InstanceQuery),
+    responses((status = 200, body = ApiResult), (status = 400, body = ApiError)))]
+async fn start_headless(
+    State(api): State<ControlState>,
+    Query(query): Query<InstanceQuery>,
+) -> Response {
+    let mut config = crate::HeadlessConfig::default();
+    if let Some(instance) = query.instance {
+        config.instance = instance;
+    }
+    match crate::start_headless_internal(&api.app_state, config).await {
+        Ok(message) => Json(ApiResult {
+            success: true,
+            result: json!({ "message": message }),
+        })
+        .into_response(),
+        Err(e) => error_response(StatusCode::BAD_REQUEST, e),
+    }
+}
+
+#[utoipa::path(post, path = "/v1/headless/stop", tag = "wallets", params(InstanceQuery),
+    responses((status = 200, body = ApiResult), (status = 400, body = ApiError)))]
+async fn stop_headless(
+    State(api): State<ControlState>,
+    Query(query): Query<InstanceQuery>,
+) -> Response {
+    match crate::stop_headless_internal(&api.app_state, query.instance.as_deref()).await {
+        Ok(message) => Json(ApiResult {
+            success: true,
+            result: json!({ "message": message }),
+        })
+        .into_response(),
+        Err(e) => error_response(StatusCode::BAD_REQUEST, e),
+    }
+}
+
+#[utoipa::path(get, path = "/v1/snapshots", tag = "snapshots",
+    responses((status = 200, description = "Saved snapshots", body = ApiResult)))]
+async fn list_snapshots(State(api): State<ControlState>) -> Response {
+    run(&api, "list_snapshots", json!({})).await
+}
+
+#[utoipa::path(post, path = "/v1/snapshots", tag = "snapshots", request_body = SnapshotRequest,
+    responses((status = 200, description = "Saves the node data and wallet registry; a running node is restarted", body = ApiResult), (status = 400, body = ApiError)))]
+async fn save_snapshot(
+    State(api): State<ControlState>,
+    Json(body): Json<SnapshotRequest>,
+) -> Response {
+    run(&api, "save_snapshot", params(body, json!({}))).await
+}
+
+#[utoipa::path(post, path = "/v1/snapshots/{name}/restore", tag = "snapshots",
+    params(("name" = String, Path, description = "Snapshot name")),
+    responses((status = 200, body = ApiResult), (status = 400, body = ApiError)))]
+async fn restore_snapshot(State(api): State<ControlState>, Path(name): Path<String>) -> Response {
+    run(&api, "restore_snapshot", json!({ "name": name })).await
+}
+
+#[utoipa::path(delete, path = "/v1/snapshots/{name}", tag = "snapshots",
+    params(("name" = String, Path, description = "Snapshot name")),
+    responses((status = 200, body = ApiResult), (status = 400, body = ApiError)))]
+async fn delete_snapshot(State(api): State<ControlState>, Path(name): Path<String>) -> Response {
+    run(&api, "delete_snapshot", json!({ "name": name })).await
+}
+
+#[utoipa::path(get, path = "/v1/logs/{service}", tag = "logs",
+    params(("service" = String, Path, description = "node, miner, headless, poa-node, explorer-service, explorer, mcp or control"), LogsQuery),
+    responses((status = 200, description = "Recent log lines, oldest first", body = ApiResult), (status = 400, body = ApiError)))]
+async fn logs(
+    State(api): State<ControlState>,
+    Path(service): Path<String>,
+    Query(query): Query<LogsQuery>,
+) -> Response {
+    run(
+        &api,
+        "get_logs",
+        params(query, json!({ "service": service })),
+    )
+    .await
+}
+
+#[utoipa::path(post, path = "/v1/wallets", tag = "wallets", params(InstanceQuery), request_body = CreateWalletRequest,
+    responses((status = 200, description = "Starts the wallet on wallet-headless and registers it", body = ApiResult), (status = 400, body = ApiError)))]
+async fn create_wallet(
+    State(api): State<ControlState>,
+    Query(query): Query<InstanceQuery>,
+    Json(body): Json<CreateWalletRequest>,
+) -> Response {
+    let params = params(body, json!({ "instance": query.instance }));
+    run(&api, "create_wallet", params).await
+}
+
+async fn wallet_info(
+    api: &ControlState,
+    operation: &str,
+    wallet_id: String,
+    query: InstanceQuery,
+) -> Response {
+    let extra = json!({ "wallet_id": wallet_id, "instance": query.instance });
+    run(api, operation, params(json!({}), extra)).await
+}
+
+#[utoipa::path(get, path = "/v1/wallets/{wallet_id}/status", tag = "wallets",
+    params(("wallet_id" = String, Path, description = "Wallet ID"), InstanceQuery),
+    responses((status = 200, body = ApiResult), (status = 400, body = ApiError)))]
+async fn wallet_status(
+    State(api): State<ControlState>,
+    Path(wallet_id): Path<String>,
+    Query(query): Query<InstanceQuery>,
+) -> Response {
+    wallet_info(&api, "get_wallet_status", wallet_id, query).await
+}
+
+#[utoipa::path(get, path = "/v1/wallets/{wallet_id}/balance", tag = "wallets",
+    params(("wallet_id" = String, Path, description = "Wallet ID"), InstanceQuery),
+    responses((status = 200, body = ApiResult), (status = 400, body = ApiError)))]
+async fn wallet_balance(
+    State(api): State<ControlState>,
+    Path(wallet_id): Path<String>,
+    Query(query): Query<InstanceQuery>,
+) -> Response {
+    wallet_info(&api, "get_wallet_balance", wallet_id, query).await
+}
+
+#[utoipa::path(get, path = "/v1/wallets/{wallet_id}/addresses", tag = "wallets",
+    params(("wallet_id" = String, Path, description = "Wallet ID"), InstanceQuery),
+    responses((status = 200, body = ApiResult), (status = 400, body = ApiError)))]
+async fn wallet_addresses(
+    State(api): State<ControlState>,
+    Path(wallet_id): Path<String>,
+    Query(query): Query<InstanceQuery>,
+) -> Response {
+    wallet_info(&api, "get_wallet_addresses", wallet_id, query).await
+}
+
+#[utoipa::path(get, path = "/v1/wallets/{wallet_id}/sync", tag = "wallets",
+    params(("wallet_id" = String, Path, description = "Wallet ID"), InstanceQuery),
+    responses((status = 200, description = "Sync progress of a starting wallet", body = ApiResult), (status = 400, body = ApiError)))]
+async fn wallet_sync(
+    State(api): State<ControlState>,
+    Path(wallet_id): Path<String>,
+    Query(query): Query<InstanceQuery>,
+) -> Response {
+    wallet_info(&api, "get_wallet_sync_progress", wallet_id, query).await
+}
+
+#[utoipa::path(post, path = "/v1/wallets/{wallet_id}/send", tag = "wallets",
+    params(("wallet_id" = String, Path, description = "Wallet ID"), InstanceQuery), request_body = SendRequest,
+    responses((status = 200, body = ApiResult), (status = 400, body = ApiError)))]
+async fn send_from_wallet(
+    State(api): State<ControlState>,
+    Path(wallet_id): Path<String>,
+    Query(query): Query<InstanceQuery>,
+    Json(body): Json<SendRequest>,
+) -> Response {
+    let params = params(
+        body,
+        json!({ "wallet_id": wallet_id, "instance": query.instance }),
+    );
+    run(&api, "send_from_wallet", params).await
+}
+
+#[utoipa::path(post, path = "/v1/wallets/{wallet_id}/fund", tag = "wallets",
+    params(("wallet_id" = String, Path, description = "Wallet ID"), InstanceQuery), request_body(content = Option<FundWalletRequest>),
+    responses((status = 200, description = "Pays the wallet's first address from the faucet", body = ApiResult), (status = 400, body = ApiError)))]
+async fn fund_wallet(
+    State(api): State<ControlState>,
+    Path(wallet_id): Path<String>,
+    Query(query): Query<InstanceQuery>,
+    body: Option<Json<FundWalletRequest>>,
+) -> Response {
+    let body = body.map(|Json(body)| body).unwrap_or_default();
+    let params = params(
+        body,
+        json!({ "wallet_id": wallet_id, "instance": query.instance }),
+    );
+    run(&api, "fund_wallet", params).await
+}
+
+#[utoipa::path(get, path = "/v1/faucet", tag = "faucet",
+    responses((status = 200, description = "The node wallet's balance", body = ApiResult)))]
+async fn faucet_balance(State(api): State<ControlState>) -> Response {
+    run(&api, "get_faucet_balance", json!({})).await
+}
+
+#[utoipa::path(post, path = "/v1/faucet/send", tag = "faucet", request_body = SendRequest,
+    responses((status = 200, body = ApiResult), (status = 400, body = ApiError)))]
+async fn send_from_faucet(
+    State(api): State<ControlState>,
+    Json(body): Json<SendRequest>,
+) -> Response {
+    run(&api, "send_from_faucet", params(body, json!({}))).await
+}
+
+#[utoipa::path(post, path = "/v1/faucet/distribute", tag = "faucet", request_body = DistributeRequest,
+    responses((status = 200, description = "Pays many addresses in multi-output transactions", body = ApiResult), (status = 400, body = ApiError)))]
+async fn faucet_distribute(
+    State(api): State<ControlState>,
+    Json(body): Json<DistributeRequest>,
+) -> Response {
+    run(&api, "faucet_distribute", params(body, json!({}))).await
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct EventFilter {
+    // Comma-separated lists; everything passes when absent
+    categories: Option<String>,
+    events: Option<String>,
+    // Log sources: node, miner, headless, poa-node
+    services: Option<String>,
+    // Text log lines must contain
+    contains: Option<String>,
+}
+
+fn listed(list: &Option<String>, value: &str) -> bool {
+    list.as_deref()
+        .is_none_or(|list| list.split(',').any(|item| item.trim() == value))
+}
+
+impl EventFilter {
+    fn matches(&self, event: &BusEvent) -> bool {
+        if !listed(&self.categories, event.category) || !listed(&self.events, &event.event) {
+            return false;
+        }
+        // Service and text filters only narrow down log lines
+        let Some(service) = event.log_service() else {
+            return true;
+        };
+        listed(&self.services, service)
+            && self.contains.as_deref().is_none_or(|text| {
+                event
+                    .payload
+                    .as_str()
+                    .is_some_and(|line| line.contains(text))
+            })
+    }
+}
+
+// Stream bus events until the client goes away
+#[utoipa::path(get, path = "/events", tag = "events",
+    params(
+        ("categories" = Option<String>, Query, description = "Comma-separated: service, block, wallet, log, metrics"),
+        ("events" = Option<String>, Query, description = "Comma-separated event names, e.g. new-vertex,node-log"),
+        ("services" = Option<String>, Query, description = "Log sources: node, miner, headless, poa-node"),
+        ("contains" = Option<String>, Query, description = "Text log lines must contain"),
+    ),
+    responses((status = 101, description = "WebSocket of JSON messages `{timestamp, category, event, payload}`"), (status = 400, body = ApiError)))]
+async fn events(
+    State(api): State<ControlState>,
+    Query(filter): Query<EventFilter>,
+    ws: WebSocketUpgrade,
+) -> Response {
+    if let Some(unknown) = filter.categories.as_deref().and_then(|list| {
+        list.split(',')
+            .map(str::trim)
+            .find(|category| !crate::event_bus::CATEGORIES.contains(category))
+    }) {
+        return error_response(
+            StatusCode::BAD_REQUEST,
+            format!(
+                "Unknown category '{}'; expected one of: {}",
+                unknown,
+                crate::event_bus::CATEGORIES.join(", ")
+            ),
+        );
+    }
+    let events = api.app_state.lock().await.event_bus.subscribe();
+    ws.on_upgrade(move |socket| stream_events(socket, events, filter))
+}
+
+async fn stream_events(
+    mut socket: WebSocket,
+    mut events: tokio::sync::broadcast::Receiver<BusEvent>,
+    filter: EventFilter,
+) {
+    loop {
+        tokio::select! {
+            received = events.recv() => {
+                let message = match received {
+                    Ok(event) if filter.matches(&event) => json!(event),
+                    Ok(_) => continue,
+                    // Tell the client it missed events rather than dropping it
+                    Err(RecvError::Lagged(skipped)) => json!({ "event": "lagged", "skipped": skipped }),
+                    Err(RecvError::Closed) => break,
+                };
+                if socket.send(Message::Text(message.to_string())).await.is_err() {
+                    break;
+                }
+            }
+            incoming = socket.recv() => match incoming {
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                // Pings are answered by axum; clients have nothing else to say
+                Some(Ok(_)) => {}
+            }
+        }
+    }
+}
+
+// ============================================================================
+// OpenAPI
+// ============================================================================
+
+struct BearerToken;
+
+impl Modify for BearerToken {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        openapi
+            .components
+            .get_or_insert_with(Default::default)
+            .add_security_scheme(
+                "token",
+                SecurityScheme::Http(HttpBuilder::new().scheme(HttpAuthScheme::Bearer).build()),
+            );
+    }
+}
+
+/// The control API's OpenAPI 3 document, served at `/openapi.json`
+#[derive(OpenApi)]
+#[openapi(
+    info(
+        title = "Hathor Forge control API",
+        description = "Drive a local Hathor environment from scripts and CI. Amounts are decimal HTR."
+    ),
+    paths(
+        status, quick_start, quick_stop,
+        node_status, start_node, stop_node,
+        miner_status, start_miner, stop_miners, stop_miner,
+        start_headless, stop_headless,
+        list_snapshots, save_snapshot, restore_snapshot, delete_snapshot,
+        logs,
+        create_wallet, wallet_status, wallet_balance, wallet_addresses, wallet_sync,
+        send_from_wallet, fund_wallet,
+        faucet_balance, send_from_faucet, faucet_distribute,
+        events,
+    ),
+    components(schemas(
+        ApiResult, ApiError, StartMinerRequest, SnapshotRequest, CreateWalletRequest,
+        SendRequest, FundWalletRequest, DistributeRequest,
+    )),
+    modifiers(&BearerToken),
+    security(("token" = []))
+)]
+pub struct ApiDoc;
+
+fn router(app_state: SharedState) -> Router {
+    let api = ControlState {
+        mcp: Arc::new(McpState::new(app_state.clone())),
+        app_state,
+    };
+
+    let v1 = Router::new()
+        .route("/status", get(status))
+        .route("/quick-start", post(quick_start))
+        .route("/quick-stop", post(quick_stop))
+        .route("/node", get(node_status))
+        .route("/node/start", post(start_node))
+        .route("/node/stop", post(stop_node))
+        .route(
+            "/miners",
+            get(miner_status).post(start_miner).delete(stop_miners),
+        )
+        .route("/miners/:name", delete(stop_miner))
+        .route("/headless/start", post(start_headless))
+        .route("/headless/stop", post(stop_headless))
+        .route("/snapshots", get(list_snapshots).post(save_snapshot))
+        .route("/snapshots/:name", delete(delete_snapshot))
+        .route("/snapshots/:name/restore", post(restore_snapshot))
+        .route("/logs/:service", get(logs))
+        .route("/wallets", post(create_wallet))
+        .route("/wallets/:wallet_id/send", post(send_from_wallet))
+        .route("/wallets/:wallet_id/fund", post(fund_wallet))
+        .route("/wallets/:wallet_id/status", get(wallet_status))
+        .route("/wallets/:wallet_id/balance", get(wallet_balance))
+        .route("/wallets/:wallet_id/addresses", get(wallet_addresses))
+        .route("/wallets/:wallet_id/sync", get(wallet_sync))
+        .route("/faucet", get(faucet_balance))
+        .route("/faucet/send", post(send_from_faucet))
+        .route("/faucet/distribute", post(faucet_distribute));
+
+    Router::new()
+        .nest("/v1", v1)
+        .route("/events", get(events))
+        .layer(axum::middleware::from_fn(require_token))
+        .with_state(api)
+        .route("/health", get(health))
+        // The spec and Swagger UI need no token; "Authorize" takes it for "Try it out"
+        .merge(SwaggerUi::new("/docs").url("/openapi.json", ApiDoc::openapi()))
+}
+
+/// Start the server on the configured port
+pub async fn start(state: &SharedState) -> Result<String, String> {
+    let settings = SETTINGS.load();
+    token()?;
+
+    let (acceptor, access_log, rate_limits) = {
+        let state_guard = state.lock().await;
+        if state_guard.control_api.shutdown.is_some() {
+            return Err("Control API is already running".to_string());
+        }
+        // HTTPS when enabled (see tls.rs)
+        let acceptor = if crate::tls::enabled() {
+            Some(crate::tls::acceptor(&state_guard)?)
+        } else {
+            None
+        };
+        (
+            acceptor,
+            state_guard.access_log.clone(),
+            state_guard.rate_limits.clone(),
+        )
+    };
+
+    let app = router(state.clone())
+        .layer(axum::middleware::from_fn_with_state(
+            crate::rate_limit::RateLimitState::new(rate_limits, "control"),
+            crate::rate_limit::limit_requests,
+        ))
+        .layer(crate::cors::layer())
+        .layer(axum::middleware::from_fn_with_state(
+            crate::access_log::AccessLogState::new(access_log, "control"),
+            crate::access_log::log_requests,
+        ))
+        .layer(tower_http::trace::TraceLayer::new_for_http());
+    let listener = tokio::net::TcpListener::bind(("127.0.0.1", settings.port))
+        .await
+        .map_err(|e| format!("Failed to bind to port {}: {}", settings.port, e))?;
+    let url = format!(
+        "{}://127.0.0.1:{}",
+        crate::tls::scheme(acceptor.is_some()),
+        settings.port
+    );
+
+    let (shutdown_tx, shutdown_rx) = tokio::sync::oneshot::channel::<()>();
+    {
+        let mut state_guard = state.lock().await;
+        state_guard.control_api = ControlApi {
+            shutdown: Some(shutdown_tx),
+            url: Some(url.clone()),
+        };
+    }
+
+    let exit_state = state.clone();
+    tokio::spawn(async move {
+        let shutdown = async {
+            let _ = shutdown_rx.await;
+        };
+        match acceptor {
+            Some(acceptor) => crate::tls::serve(listener, app, acceptor, shutdown).await,
+            None => {
+                if let Err(e) = axum::serve(
+                    listener,
+                    app.into_make_service_with_connect_info::<SocketAddr>(),
+                )
+                .with_graceful_shutdown(shutdown)
+                .await
+                {
+                    eprintln!("Control API server error: {}", e);
+                }
+            }
+        }
+        // Unless stop() already cleared it, or a new server replaced it
+        let mut state_guard = exit_state.lock().await;
+        if state_guard
+            .control_api
+            .shutdown
+            .as_ref()
+            .is_some_and(|shutdown| shutdown.is_closed())
+        {
+            state_guard.control_api = ControlApi::default();
+        }
+    });
+
+    Ok(format!("Control API listening on {}", url))
+}
+
+/// URL of the running server
+pub fn url(state: &crate::AppState) -> Option<String> {
+    state.control_api.url.clone()
+}
+
+/// Stop the server, if it's running
+pub async fn stop(state: &SharedState) {
+    let control_api = std::mem::take(&mut state.lock().await.control_api);
+    if let Some(shutdown) = control_api.shutdown {
+        let _ = shutdown.send(());
+    }
+}
+
+pub async fn get_control_api_info_internal(state: &SharedState) -> Result<ControlApiInfo, String> {
+    let settings = SETTINGS.load();
+    let url = state.lock().await.control_api.url.clone();
+    Ok(ControlApiInfo {
+        enabled: settings.enabled,
+        running: url.is_some(),
+        port: settings.port,
+        url,
+        token: token()?,
+        token_path: token_path().to_string_lossy().to_string(),
+    })
+}
+
+/// Save the settings and start, stop or restart the server to match
+pub async fn set_control_api_internal(
+    state: &SharedState,
+    settings: ControlApiSettings,
+) -> Result<ControlApiInfo, String> {
+    if settings.port == 0 {
+        return Err("Port must be positive".to_string());
+    }
+    SETTINGS.save(&settings)?;
+    stop(state).await;
+    if settings.enabled {
+        start(state).await?;
+    }
+    get_control_api_info_internal(state).await
+}
+
+// ============================================================================
+// Tauri Commands
+// ============================================================================
+
+// Get the control API's settings, address and token
+#[tauri::command]
+pub async fn get_control_api_info(
+    state: tauri::State<'_, SharedState>,
+) -> Result<ControlApiInfo, String> {
+    get_control_api_info_internal(&state).await
+}
+
+// Enable, disable or move the control API
+#[tauri::command]
+pub async fn set_control_api(
+    state: tauri::State<'_, SharedState>,
+    settings: ControlApiSettings,
+) -> Result<ControlApiInfo, String> {
+    set_control_api_internal(&state, settings).await
+}
+
+// Replace the control API token; the old one stops working immediately
+#[tauri::command]
+pub async fn regenerate_control_api_token(
+    state: tauri::State<'_, SharedState>,
+) -> Result<ControlApiInfo, String> {
+    generate_token()?;
+    get_control_api_info_internal(&state).await
+}
@@ -0,0 +1,120 @@
+//! CORS policy of the embedded servers
+//!
+//! The explorer server, the MCP server and the control API answer
+//! cross-origin requests only from localhost origins (any scheme and port,
+//! which covers dApps on a dev server and the Tauri webview) plus an explicit
+//! allowlist kept in `cors.json`. On a shared machine that stops any page a
+//! user happens to open from scripting the node, the wallets and the faucet.
+//! The permissive toggle restores the old allow-everything behavior for
+//! development setups that need it.
+//!
+//! The servers check the current settings on every request, so changes apply
+//! without restarting them.
+
+use axum::http::HeaderValue;
+use serde::{Deserialize, Serialize};
+use tower_http::cors::{AllowHeaders, AllowMethods, AllowOrigin, CorsLayer};
+
+use crate::settings::JsonSettings;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CorsSettings {
+    // Allow every origin
+    pub permissive: bool,
+    // Origins allowed besides localhost, e.g. `https://app.example.com`
+    pub allowed_origins: Vec<String>,
+}
+
+static SETTINGS: JsonSettings<CorsSettings> = JsonSettings::new("cors.json", "CORS settings");
+
+// `scheme://host[:port]`, lowercased and without a trailing slash
+fn normalize_origin(origin: &str) -> Result<String, String> {
+    let origin = origin.trim().trim_end_matches('/').to_lowercase();
+    let Some((scheme, authority)) = origin.split_once("://") else {
+        return Err(format!(
+            "'{}' is not an origin (scheme://host[:port])",
+            origin
+        ));
+    };
+    if scheme.is_empty() || authority.is_empty() || authority.contains(['/', '?', '#', '*']) {
+        return Err(format!(
+            "'{}' is not an origin (scheme://host[:port])",
+            origin
+        ));
+    }
+    Ok(origin)
+}
+
+// Host of an origin, with the brackets of an IPv6 address
+fn origin_host(origin: &str) -> Option<&str> {
+    let (_, authority) = origin.split_once("://")?;
+    if authority.starts_with('[') {
+        authority.split_inclusive(']').next()
+    } else {
+        authority.split(':').next()
+    }
+}
+
+fn is_local_origin(origin: &str) -> bool {
+    match origin_host(origin) {
+        Some(host) => {
+            let host = host.to_lowercase();
+            host == "localhost"
+                || host.ends_with(".localhost")
+                || host == "127.0.0.1"
+                || host == "[::1]"
+        }
+        None => false,
+    }
+}
+
+/// Whether a cross-origin request from `origin` is allowed
+pub fn origin_allowed(origin: &str) -> bool {
+    let settings = SETTINGS.read();
+    settings.permissive
+        || is_local_origin(origin)
+        || normalize_origin(origin).is_ok_and(|origin| settings.allowed_origins.contains(&origin))
+}
+
+/// CORS layer for an embedded server, following the current settings
+pub fn layer() -> CorsLayer {
+    CorsLayer::new()
+        .allow_origin(AllowOrigin::predicate(|origin: &HeaderValue, _| {
+            origin.to_str().is_ok_and(origin_allowed)
+        }))
+        // Origins are the gate; allowed ones may use any method and header
+        .allow_methods(AllowMethods::mirror_request())
+        .allow_headers(AllowHeaders::mirror_request())
+}
+
+// ============================================================================
+// Tauri Commands
+// ============================================================================
+
+// Get the CORS policy of the embedded servers
+#[tauri::command]
+pub async fn get_cors_settings() -> Result<CorsSettings, String> {
+    Ok(SETTINGS.read().clone())
+}
+
+// Set the CORS policy; applies to the running servers right away
+#[tauri::command]
+pub async fn set_cors_settings(settings: CorsSettings) -> Result<CorsSettings, String> {
+    let mut allowed_origins = Vec::new();
+    for origin in &settings.allowed_origins {
+        if origin.trim().is_empty() {
+            continue;
+        }
+        let origin = normalize_origin(origin)?;
+        if !allowed_origins.contains(&origin) {
+            allowed_origins.push(origin);
+        }
+    }
+    let settings = CorsSettings {
+        permissive: settings.permissive,
+        allowed_origins,
+    };
+    SETTINGS.save(&settings)?;
+    Ok(settings)
+}
@@ -0,0 +1,85 @@
+//! External miner binaries
+//!
+//! Instead of the bundled cpuminer, a miner can run a user-supplied binary
+//! (e.g. a GPU miner) with an argument template. It goes through the same
+//! process layer as cpuminer (logs, status, stop conditions), labelled
+//! "custom miner". Template placeholders are filled from the miner config:
+//! `{stratum_url}`, `{stratum_host}`, `{stratum_port}`, `{address}` and
+//! `{threads}`. cpuminer options don't apply to custom miners.
+
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+pub const CUSTOM_MINER_LABEL: &str = "custom miner";
+
+const PLACEHOLDERS: &[&str] = &[
+    "stratum_url",
+    "stratum_host",
+    "stratum_port",
+    "address",
+    "threads",
+];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CustomMiner {
+    // Path to the miner executable
+    pub path: String,
+    // Arguments, with `{placeholder}`s filled in at start
+    #[serde(default)]
+    pub args: Vec<String>,
+}
+
+// Replace `{name}` placeholders in `arg` with `value(name)`
+fn expand(arg: &str, value: &dyn Fn(&str) -> Option<String>) -> Result<String, String> {
+    let mut expanded = String::new();
+    let mut rest = arg;
+    while let Some(start) = rest.find('{') {
+        let end = rest[start..]
+            .find('}')
+            .map(|end| start + end)
+            .ok_or_else(|| format!("Unclosed placeholder in miner argument '{}'", arg))?;
+        let name = &rest[start + 1..end];
+        let replacement = value(name).ok_or_else(|| {
+            format!(
+                "Unknown placeholder '{{{}}}' in miner argument '{}' (supported: {})",
+                name,
+                arg,
+                PLACEHOLDERS.join(", ")
+            )
+        })?;
+        expanded.push_str(&rest[..start]);
+        expanded.push_str(&replacement);
+        rest = &rest[end + 1..];
+    }
+    expanded.push_str(rest);
+    Ok(expanded)
+}
+
+impl CustomMiner {
+    /// The miner executable, checked to exist
+    pub fn binary_path(&self) -> Result<PathBuf, String> {
+        let path = PathBuf::from(&self.path);
+        if !path.is_file() {
+            return Err(format!("Custom miner binary not found at {:?}", path));
+        }
+        Ok(path)
+    }
+
+    /// Arguments with placeholders filled in for a miner on `stratum_port`
+    pub fn args(
+        &self,
+        stratum_port: u16,
+        address: &str,
+        threads: u32,
+    ) -> Result<Vec<String>, String> {
+        let value = |name: &str| match name {
+            "stratum_url" => Some(format!("stratum+tcp://127.0.0.1:{}", stratum_port)),
+            "stratum_host" => Some("127.0.0.1".to_string()),
+            "stratum_port" => Some(stratum_port.to_string()),
+            "address" => Some(address.to_string()),
+            "threads" => Some(threads.to_string()),
+            _ => None,
+        };
+        self.args.iter().map(|arg| expand(arg, &value)).collect()
+    }
+}
@@ -0,0 +1,187 @@
+//! Disk usage of the workspace
+//!
+//! Reports what the node's data directory is made of (the RocksDB database,
+//! RocksDB's info logs, everything else) together with the snapshots and the
+//! scratch directories Forge leaves behind: the reorg fork node's data and
+//! interrupted snapshot restores. hathor-core has no way to trigger a RocksDB
+//! compaction from outside (the database compacts itself in the background
+//! while the node runs), so `compact_data` reclaims what can safely go with
+//! the node stopped: old info logs and the scratch directories.
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::{reorg, snapshots, SharedState};
+
+// Database directory hathor-core creates in its data dir
+const ROCKSDB_DIR: &str = "data_v2.db";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DataUsage {
+    pub data_dir: String,
+    pub total_bytes: u64,
+    // Database files, excluding its info logs
+    pub rocksdb_bytes: u64,
+    // RocksDB info logs (LOG and rotated LOG.old.*)
+    pub logs_bytes: u64,
+    // Forge's service log files (see logging.rs)
+    pub service_logs_bytes: u64,
+    pub snapshots_bytes: u64,
+    pub snapshot_count: usize,
+    // Reorg fork node data and interrupted restores
+    pub scratch_bytes: u64,
+    // The rest of the data directory (wallet index, token registry, ...)
+    pub other_bytes: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CompactResult {
+    pub freed_bytes: u64,
+    pub removed: Vec<String>,
+    pub usage: DataUsage,
+}
+
+// Size of a file, or of everything below a directory (symlinks not followed)
+fn size_of(path: &Path) -> u64 {
+    let Ok(metadata) = fs::symlink_metadata(path) else {
+        return 0;
+    };
+    if !metadata.is_dir() {
+        return metadata.len();
+    }
+    fs::read_dir(path)
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok())
+                .map(|entry| size_of(&entry.path()))
+                .sum()
+        })
+        .unwrap_or(0)
+}
+
+fn is_info_log(path: &Path) -> bool {
+    path.file_name()
+        .map(|name| name.to_string_lossy())
+        .is_some_and(|name| name == "LOG" || name.starts_with("LOG.old"))
+}
+
+fn info_logs(rocksdb_dir: &Path) -> Vec<PathBuf> {
+    fs::read_dir(rocksdb_dir)
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok())
+                .map(|entry| entry.path())
+                .filter(|path| is_info_log(path))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn scratch_dirs(data_dir: &Path) -> Vec<PathBuf> {
+    vec![reorg::fork_data_dir(), snapshots::staging_dir(data_dir)]
+}
+
+fn measure(data_dir: &Path) -> DataUsage {
+    let rocksdb_dir = data_dir.join(ROCKSDB_DIR);
+    let logs_bytes: u64 = info_logs(&rocksdb_dir)
+        .iter()
+        .map(|path| size_of(path))
+        .sum();
+    let rocksdb_bytes = size_of(&rocksdb_dir).saturating_sub(logs_bytes);
+    let service_logs_bytes = size_of(&data_dir.join(crate::logging::LOGS_DIR));
+    let other_bytes =
+        size_of(data_dir).saturating_sub(rocksdb_bytes + logs_bytes + service_logs_bytes);
+    let snapshots = snapshots::list_snapshots_internal();
+    let snapshots_bytes = snapshots.iter().map(|s| s.size_bytes).sum();
+    let scratch_bytes = scratch_dirs(data_dir).iter().map(|dir| size_of(dir)).sum();
+
+    DataUsage {
+        data_dir: data_dir.to_string_lossy().to_string(),
+        total_bytes: rocksdb_bytes
+            + logs_bytes
+            + service_logs_bytes
+            + other_bytes
+            + snapshots_bytes
+            + scratch_bytes,
+        rocksdb_bytes,
+        logs_bytes,
+        service_logs_bytes,
+        snapshots_bytes,
+        snapshot_count: snapshots.len(),
+        scratch_bytes,
+        other_bytes,
+    }
+}
+
+// Remove rotated info logs and scratch directories, returning what went
+fn compact(data_dir: &Path) -> (u64, Vec<String>) {
+    let mut freed = 0;
+    let mut removed = Vec::new();
+
+    // The current LOG is kept; only rotated ones go
+    let old_logs = info_logs(&data_dir.join(ROCKSDB_DIR))
+        .into_iter()
+        .filter(|path| path.file_name().is_some_and(|name| name != "LOG"));
+    for path in old_logs {
+        let size = size_of(&path);
+        if fs::remove_file(&path).is_ok() {
+            freed += size;
+            removed.push(path.to_string_lossy().to_string());
+        }
+    }
+    for dir in scratch_dirs(data_dir) {
+        let size = size_of(&dir);
+        if dir.exists() && fs::remove_dir_all(&dir).is_ok() {
+            freed += size;
+            removed.push(dir.to_string_lossy().to_string());
+        }
+    }
+
+    (freed, removed)
+}
+
+/// Disk usage of the current workspace
+pub async fn get_data_usage_internal(state: &SharedState) -> Result<DataUsage, String> {
+    let data_dir = crate::workspace_data_dir(&*state.lock().await);
+    tokio::task::spawn_blocking(move || measure(&data_dir))
+        .await
+        .map_err(|e| format!("Failed to measure data usage: {}", e))
+}
+
+/// Delete rotated RocksDB logs and scratch directories; the node must be stopped
+pub async fn compact_data_internal(state: &SharedState) -> Result<CompactResult, String> {
+    let state_guard = state.lock().await;
+    if state_guard.node_running {
+        return Err("Cannot compact data while node is running. Stop the node first.".to_string());
+    }
+    let data_dir = crate::workspace_data_dir(&state_guard);
+    drop(state_guard);
+
+    tokio::task::spawn_blocking(move || {
+        let (freed_bytes, removed) = compact(&data_dir);
+        CompactResult {
+            freed_bytes,
+            removed,
+            usage: measure(&data_dir),
+        }
+    })
+    .await
+    .map_err(|e| format!("Failed to compact data: {}", e))
+}
+
+// ============================================================================
+// Tauri Commands
+// ============================================================================
+
+// Get the size of the database, its logs, snapshots and scratch data
+#[tauri::command]
+pub async fn get_data_usage(state: tauri::State<'_, SharedState>) -> Result<DataUsage, String> {
+    get_data_usage_internal(&state).await
+}
+
+// Delete rotated RocksDB logs and leftover scratch directories
+#[tauri::command]
+pub async fn compact_data(state: tauri::State<'_, SharedState>) -> Result<CompactResult, String> {
+    compact_data_internal(&state).await
+}
@@ -0,0 +1,158 @@
+//! Unified event bus
+//!
+//! Collects what Forge reports while it runs into one broadcast channel for
+//! consumers outside the app, such as the control API's `/events` WebSocket.
+//! The events the backend emits to the UI (new blocks, reorgs, wallet
+//! activity, service logs, ...) are re-published with a category. Services
+//! started through MCP or the control API emit nothing, so service state
+//! changes are detected by watching `AppState` and published as
+//! `service-state` events.
+
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::collections::BTreeSet;
+use std::time::Duration;
+use tauri::{AppHandle, Listener};
+
+use crate::SharedState;
+
+// Events buffered for slow subscribers before the oldest are dropped
+pub const CAPACITY: usize = 1024;
+const SERVICE_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+pub const CATEGORIES: [&str; 5] = ["service", "block", "wallet", "log", "metrics"];
+
+// App events re-published on the bus, with their category
+const FORWARDED: &[(&str, &str)] = &[
+    ("node-terminated", "service"),
+    ("miner-terminated", "service"),
+    ("miner-auto-started", "service"),
+    ("headless-instance-terminated", "service"),
+    ("poa-node-terminated", "service"),
+    ("explorer-started", "service"),
+    ("explorer-terminated", "service"),
+    ("explorer-error", "service"),
+    ("explorer-service-terminated", "service"),
+    ("proxy-ws-reconnecting", "service"),
+    ("proxy-ws-reconnected", "service"),
+    ("proxy-ws-lost", "service"),
+    ("snapshot-saved", "service"),
+    ("new-vertex", "block"),
+    ("block-found", "block"),
+    ("reorg-started", "block"),
+    ("reorg-finished", "block"),
+    ("tx-voided", "block"),
+    ("mempool-size-changed", "block"),
+    ("wallet-balance-changed", "wallet"),
+    ("address-activity", "wallet"),
+    ("payment-received", "wallet"),
+    ("wallet-sync-progress", "wallet"),
+    ("wallets-restored", "wallet"),
+    ("watched-tx-voided", "wallet"),
+    ("token-created", "wallet"),
+    ("nc-tx-confirmed", "wallet"),
+    ("node-log", "log"),
+    ("miner-log", "log"),
+    ("headless-log", "log"),
+    ("poa-node-log", "log"),
+    ("explorer-service-log", "log"),
+    ("miner-stats", "metrics"),
+    ("miner-metrics", "metrics"),
+    ("loadgen-stats", "metrics"),
+];
+
+#[derive(Debug, Clone, Serialize)]
+pub struct BusEvent {
+    // Unix time in milliseconds
+    pub timestamp: u64,
+    pub category: &'static str,
+    pub event: String,
+    pub payload: Value,
+}
+
+impl BusEvent {
+    /// The service a log line came from (`node`, `miner`, `headless`,
+    /// `poa-node` or `explorer-service`)
+    pub fn log_service(&self) -> Option<&str> {
+        (self.category == "log")
+            .then(|| self.event.strip_suffix("-log"))
+            .flatten()
+    }
+}
+
+/// Sender kept in `AppState`; subscribe to receive every event from then on
+pub type EventBus = tokio::sync::broadcast::Sender<BusEvent>;
+
+/// Publish an event; it's dropped when nobody is subscribed
+pub fn publish(bus: &EventBus, category: &'static str, event: &str, payload: Value) {
+    let _ = bus.send(BusEvent {
+        timestamp: crate::access_log::unix_millis(),
+        category,
+        event: event.to_string(),
+        payload,
+    });
+}
+
+// Running services, as (service, instance) pairs
+async fn running_services(state: &SharedState) -> BTreeSet<(&'static str, Option<String>)> {
+    let state_guard = state.lock().await;
+    let mut running = BTreeSet::new();
+    if state_guard.node_running {
+        running.insert(("node", None));
+    }
+    for name in state_guard.miners.keys() {
+        running.insert(("miner", Some(name.clone())));
+    }
+    for name in state_guard.headless_instances.keys() {
+        running.insert(("headless", Some(name.clone())));
+    }
+    for name in state_guard.explorer_servers.keys() {
+        running.insert(("explorer", Some(name.clone())));
+    }
+    if crate::poa::child_id(&state_guard).is_some() {
+        running.insert(("poa-node", None));
+    }
+    if crate::explorer_service::child_id(&state_guard).is_some() {
+        running.insert(("explorer-service", None));
+    }
+    running
+}
+
+/// Re-publish app events on the bus and publish service state changes;
+/// runs for the app's lifetime
+pub async fn run(app: AppHandle, state: SharedState) {
+    let bus = state.lock().await.event_bus.clone();
+
+    for (event, category) in FORWARDED {
+        let bus = bus.clone();
+        let name = event.to_string();
+        app.listen_any(*event, move |emitted| {
+            let payload = serde_json::from_str(emitted.payload()).unwrap_or(Value::Null);
+            publish(&bus, category, &name, payload);
+        });
+    }
+
+    let mut previous = BTreeSet::new();
+    let mut interval = tokio::time::interval(SERVICE_POLL_INTERVAL);
+    loop {
+        interval.tick().await;
+        let current = running_services(&state).await;
+        for (service, instance) in current.difference(&previous) {
+            publish(
+                &bus,
+                "service",
+                "service-state",
+                json!({ "service": service, "instance": instance, "running": true }),
+            );
+        }
+        for (service, instance) in previous.difference(&current) {
+            publish(
+                &bus,
+                "service",
+                "service-state",
+                json!({ "service": service, "instance": instance, "running": false }),
+            );
+        }
+        previous = current;
+    }
+}
@@ -0,0 +1,315 @@
+//! Node events from the event queue
+//!
+//! The node runs with `--enable-event-queue`, which keeps an ordered,
+//! persistent log of what happens to the DAG. A background task streams it
+//! from `/v1a/event_ws`, acknowledging every event, and on reconnect (e.g. a
+//! node restart) resumes right after the last acknowledged one, so nothing is
+//! missed or repeated. When the app starts, or the node's event log no longer
+//! has the resume point (after a data reset), the stream starts at the newest
+//! event instead of replaying history.
+//!
+//! Events are re-emitted typed: `new-vertex`, `reorg-started`,
+//! `reorg-finished` and `tx-voided` (once, when a vertex becomes voided).
+//! They are also published on `AppState.node_events`, which the MCP server's
+//! SSE stream forwards as notifications, and the last few are kept for
+//! `get_node_events`.
+
+use futures_util::{SinkExt, StreamExt};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::{HashSet, VecDeque};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter};
+use tokio::sync::broadcast;
+use tokio_tungstenite::tungstenite::Message;
+
+use crate::{NodeConfig, SharedState};
+
+const RECONNECT_DELAY: Duration = Duration::from_secs(2);
+// Events the node may send before waiting for an ack
+const WINDOW_SIZE: u64 = 100;
+const RECENT_CAPACITY: usize = 500;
+const CHANNEL_CAPACITY: usize = 256;
+const DEFAULT_LIMIT: usize = 50;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "kebab-case")]
+pub enum NodeEvent {
+    NewVertex {
+        event_id: u64,
+        hash: String,
+        is_block: bool,
+        // Blocks only
+        height: Option<u64>,
+        timestamp: u64,
+    },
+    ReorgStarted {
+        event_id: u64,
+        reorg_size: u64,
+        previous_best_block: String,
+        new_best_block: String,
+        common_block: String,
+    },
+    ReorgFinished {
+        event_id: u64,
+    },
+    TxVoided {
+        event_id: u64,
+        hash: String,
+        is_block: bool,
+        voided_by: Vec<String>,
+    },
+}
+
+impl NodeEvent {
+    /// Name of the frontend event
+    pub fn name(&self) -> &'static str {
+        match self {
+            NodeEvent::NewVertex { .. } => "new-vertex",
+            NodeEvent::ReorgStarted { .. } => "reorg-started",
+            NodeEvent::ReorgFinished { .. } => "reorg-finished",
+            NodeEvent::TxVoided { .. } => "tx-voided",
+        }
+    }
+
+    pub fn event_id(&self) -> u64 {
+        match self {
+            NodeEvent::NewVertex { event_id, .. }
+            | NodeEvent::ReorgStarted { event_id, .. }
+            | NodeEvent::ReorgFinished { event_id }
+            | NodeEvent::TxVoided { event_id, .. } => *event_id,
+        }
+    }
+}
+
+/// Published events and the most recent ones, kept in `AppState`
+pub struct NodeEvents {
+    sender: broadcast::Sender<NodeEvent>,
+    recent: VecDeque<NodeEvent>,
+}
+
+impl Default for NodeEvents {
+    fn default() -> Self {
+        Self {
+            sender: broadcast::channel(CHANNEL_CAPACITY).0,
+            recent: VecDeque::new(),
+        }
+    }
+}
+
+impl NodeEvents {
+    pub fn subscribe(&self) -> broadcast::Receiver<NodeEvent> {
+        self.sender.subscribe()
+    }
+
+    fn publish(&mut self, event: NodeEvent) {
+        if self.recent.len() == RECENT_CAPACITY {
+            self.recent.pop_front();
+        }
+        self.recent.push_back(event.clone());
+        let _ = self.sender.send(event);
+    }
+}
+
+fn api_url(scheme: &str, path: &str) -> String {
+    format!(
+        "{}://127.0.0.1:{}/v1a/{}",
+        scheme,
+        NodeConfig::default().api_port,
+        path
+    )
+}
+
+// Newest event id in the node's log (None while the log is empty)
+async fn latest_event_id() -> Result<Option<u64>, String> {
+    let response: Value = reqwest::Client::new()
+        .get(api_url("http", "event"))
+        .query(&[("size", "1")])
+        .send()
+        .await
+        .map_err(|e| format!("Failed to query node events: {}", e))?
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse node events: {}", e))?;
+    Ok(response["latest_event_id"].as_u64())
+}
+
+fn is_block(vertex: &Value) -> bool {
+    // Regular and merge-mined blocks; the other versions are transactions
+    matches!(vertex["version"].as_u64(), Some(0) | Some(3))
+}
+
+// Typed event for a node event, tracking which vertices are voided so
+// `tx-voided` fires once per vertex
+fn translate(event: &Value, voided: &mut HashSet<String>) -> Option<NodeEvent> {
+    let event_id = event["id"].as_u64()?;
+    let data = &event["data"];
+    let hash = || data["hash"].as_str().unwrap_or_default().to_string();
+    let text = |field: &str| data[field].as_str().unwrap_or_default().to_string();
+
+    match event["type"].as_str()? {
+        "NEW_VERTEX_ACCEPTED" => Some(NodeEvent::NewVertex {
+            event_id,
+            hash: hash(),
+            is_block: is_block(data),
+            height: is_block(data)
+                .then(|| data["metadata"]["height"].as_u64())
+                .flatten(),
+            timestamp: data["timestamp"].as_u64().unwrap_or(0),
+        }),
+        "REORG_STARTED" => Some(NodeEvent::ReorgStarted {
+            event_id,
+            reorg_size: data["reorg_size"].as_u64().unwrap_or(0),
+            previous_best_block: text("previous_best_block"),
+            new_best_block: text("new_best_block"),
+            common_block: text("common_block"),
+        }),
+        "REORG_FINISHED" => Some(NodeEvent::ReorgFinished { event_id }),
+        "VERTEX_METADATA_CHANGED" => {
+            let voided_by: Vec<String> = data["metadata"]["voided_by"]
+                .as_array()
+                .map(|hashes| {
+                    hashes
+                        .iter()
+                        .filter_map(|h| h.as_str().map(|s| s.to_string()))
+                        .collect()
+                })
+                .unwrap_or_default();
+            let hash = hash();
+            if voided_by.is_empty() {
+                voided.remove(&hash);
+                return None;
+            }
+            if !voided.insert(hash.clone()) {
+                return None;
+            }
+            Some(NodeEvent::TxVoided {
+                event_id,
+                hash,
+                is_block: is_block(data),
+                voided_by,
+            })
+        }
+        _ => None,
+    }
+}
+
+// Stream events from `resume_after` (the newest one when None) until the
+// connection drops, keeping `resume_after` at the last acknowledged event
+async fn stream_events(
+    app: &AppHandle,
+    state: &SharedState,
+    resume_after: &mut Option<u64>,
+    voided: &mut HashSet<String>,
+) -> Result<(), String> {
+    if resume_after.is_none() {
+        *resume_after = latest_event_id().await?;
+    }
+
+    let (mut ws_stream, _) = tokio_tungstenite::connect_async(api_url("ws", "event_ws"))
+        .await
+        .map_err(|e| format!("Failed to connect to the event stream: {}", e))?;
+    let start = json!({
+        "type": "START_STREAM",
+        "last_ack_event_id": *resume_after,
+        "window_size": WINDOW_SIZE,
+    });
+    ws_stream
+        .send(Message::Text(start.to_string()))
+        .await
+        .map_err(|e| format!("Failed to start the event stream: {}", e))?;
+
+    while let Some(message) = ws_stream.next().await {
+        let text = match message {
+            Ok(Message::Text(text)) => text,
+            Ok(Message::Close(_)) | Err(_) => break,
+            Ok(_) => continue,
+        };
+        let Ok(response) = serde_json::from_str::<Value>(&text) else {
+            continue;
+        };
+        if response["type"] != "EVENT" {
+            // The resume point is past the node's log (the data was reset):
+            // start over at the newest event
+            if response["type"] == "ACK_TOO_LARGE" {
+                *resume_after = None;
+                voided.clear();
+            }
+            if let Some(message) = response["error_message"].as_str() {
+                return Err(format!("Event stream refused: {}", message));
+            }
+            continue;
+        }
+
+        let event = &response["event"];
+        let Some(event_id) = event["id"].as_u64() else {
+            continue;
+        };
+        if let Some(node_event) = translate(event, voided) {
+            let _ = app.emit(node_event.name(), &node_event);
+            state.lock().await.node_events.publish(node_event);
+        }
+
+        *resume_after = Some(event_id);
+        let ack = json!({
+            "type": "ACK",
+            "window_size": WINDOW_SIZE,
+            "ack_event_id": event_id,
+        });
+        if ws_stream
+            .send(Message::Text(ack.to_string()))
+            .await
+            .is_err()
+        {
+            break;
+        }
+    }
+    Ok(())
+}
+
+/// Follow the node's event queue for the lifetime of the app
+pub async fn consume_events(app: AppHandle, state: SharedState) {
+    let mut resume_after = None;
+    let mut voided = HashSet::new();
+
+    loop {
+        if state.lock().await.node_running {
+            if let Err(e) = stream_events(&app, &state, &mut resume_after, &mut voided).await {
+                eprintln!("{}", e);
+            }
+        }
+        tokio::time::sleep(RECONNECT_DELAY).await;
+    }
+}
+
+/// Recent events after `after_id`, oldest first
+pub async fn get_node_events_internal(
+    state: &SharedState,
+    after_id: Option<u64>,
+    limit: Option<usize>,
+) -> Vec<NodeEvent> {
+    let state_guard = state.lock().await;
+    let events: Vec<NodeEvent> = state_guard
+        .node_events
+        .recent
+        .iter()
+        .filter(|event| after_id.is_none_or(|after| event.event_id() > after))
+        .cloned()
+        .collect();
+    let limit = limit.unwrap_or(DEFAULT_LIMIT);
+    events[events.len().saturating_sub(limit)..].to_vec()
+}
+
+// ============================================================================
+// Tauri Commands
+// ============================================================================
+
+// Get recent node events (new vertices, reorgs, voided transactions)
+#[tauri::command]
+pub async fn get_node_events(
+    state: tauri::State<'_, SharedState>,
+    after_id: Option<u64>,
+    limit: Option<usize>,
+) -> Result<Vec<NodeEvent>, String> {
+    Ok(get_node_events_internal(&state, after_id, limit).await)
+}
@@ -0,0 +1,543 @@
+//! Explorer UI builds
+//!
+//! The explorer server serves the bundled build (`explorer-dist/`) by default.
+//! Other builds can be downloaded as `.tar.gz` archives into
+//! `explorer-dists/<name>/` in the app directory, or a local directory (the
+//! `build/` of a hathor-explorer checkout) can be served as is, to try explorer
+//! changes against the localnet without rebuilding Forge. Builds must be made
+//! for the explorer server's own API (`REACT_APP_BASE_URL` at
+//! `http://localhost:3001/v1a/`, as `build-explorer` does).
+//!
+//! The selected build lives in `explorer_dist.json` and the static files are
+//! resolved against it per request, so switching applies to a running explorer
+//! server without restarting it.
+//!
+//! Explorer servers other than the default one serve builds with
+//! `localhost:3001` in their HTML and scripts replaced by their own port, so
+//! the UI talks to the node that server proxies.
+//!
+//! Files with a content hash in their name (`static/js/main.1a2b3c4d.js`) are
+//! cached by browsers for a year; everything else, `index.html` included, is
+//! revalidated on each load. Responses carry an ETag so revalidation is a 304
+//! without a body, which keeps reloads of the explorer bundle near-instant.
+
+use axum::body::{Body, Bytes};
+use axum::extract::Request;
+use axum::http::{header, HeaderMap, HeaderValue, StatusCode};
+use axum::response::{IntoResponse, Response};
+use futures_util::StreamExt;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::path::{Path, PathBuf};
+use tower_http::services::ServeDir;
+
+use crate::settings::JsonSettings;
+
+const MAX_NAME_LEN: usize = 64;
+// Largest archive accepted for download
+const MAX_DOWNLOAD_BYTES: usize = 256 * 1024 * 1024;
+// Cache-Control of files whose name changes with their content
+const IMMUTABLE: &str = "public, max-age=31536000, immutable";
+// Cache-Control of everything else: use the cache only after revalidating
+const REVALIDATE: &str = "no-cache";
+
+/// Which build the explorer server serves
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ExplorerDist {
+    // `explorer-dist/`, from `build-explorer`
+    #[default]
+    Bundled,
+    // A downloaded build in `explorer-dists/<name>/`
+    Installed {
+        name: String,
+    },
+    // A directory on disk, served as it changes
+    Local {
+        path: String,
+    },
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+struct ExplorerDistSettings {
+    active: ExplorerDist,
+}
+
+// Where a downloaded build came from, in `explorer-dists/<name>.json`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct InstalledInfo {
+    url: String,
+    installed_at: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExplorerDistInfo {
+    pub dist: ExplorerDist,
+    pub path: String,
+    // Has an index.html
+    pub available: bool,
+    pub active: bool,
+    // Downloaded builds only
+    pub url: Option<String>,
+    pub installed_at: Option<u64>,
+}
+
+static SETTINGS: JsonSettings<ExplorerDistSettings> =
+    JsonSettings::new("explorer_dist.json", "explorer build settings");
+
+/// Where downloaded builds are kept
+pub fn dists_dir() -> PathBuf {
+    crate::get_app_dir().join("explorer-dists")
+}
+
+fn info_path(name: &str) -> PathBuf {
+    dists_dir().join(format!("{}.json", name))
+}
+
+fn validate_name(name: &str) -> Result<(), String> {
+    if name.is_empty() || name.len() > MAX_NAME_LEN {
+        return Err(format!(
+            "Explorer build name must be 1 to {} characters",
+            MAX_NAME_LEN
+        ));
+    }
+    if !name
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.')
+        || name.starts_with('.')
+    {
+        return Err(
+            "Explorer build name may only contain letters, digits, '-', '_' and '.'".to_string(),
+        );
+    }
+    Ok(())
+}
+
+fn root(dist: &ExplorerDist) -> PathBuf {
+    match dist {
+        ExplorerDist::Bundled => crate::get_explorer_dist_path(),
+        ExplorerDist::Installed { name } => dists_dir().join(name),
+        ExplorerDist::Local { path } => PathBuf::from(path),
+    }
+}
+
+fn is_build(dir: &Path) -> bool {
+    dir.join("index.html").is_file()
+}
+
+fn info(dist: ExplorerDist, active: &ExplorerDist) -> ExplorerDistInfo {
+    let path = root(&dist);
+    let installed = match &dist {
+        ExplorerDist::Installed { name } => fs::read_to_string(info_path(name))
+            .ok()
+            .and_then(|content| serde_json::from_str::<InstalledInfo>(&content).ok()),
+        _ => None,
+    };
+    ExplorerDistInfo {
+        active: &dist == active,
+        available: is_build(&path),
+        path: path.to_string_lossy().to_string(),
+        url: installed.as_ref().map(|info| info.url.clone()),
+        installed_at: installed.map(|info| info.installed_at),
+        dist,
+    }
+}
+
+/// Directory of the selected build, for the explorer server's start check
+pub fn active_root() -> Result<PathBuf, String> {
+    let active = SETTINGS.read().active.clone();
+    let path = root(&active);
+    if path.exists() {
+        return Ok(path);
+    }
+    match active {
+        ExplorerDist::Bundled => Err(format!(
+            "Explorer dist not found at {:?}. Run 'build-explorer' first.",
+            path
+        )),
+        _ => Err(format!(
+            "Explorer build not found at {:?}; select another with set_explorer_dist",
+            path
+        )),
+    }
+}
+
+// Whether a file name carries a content hash: `main.1a2b3c4d.js` and
+// `main.1a2b3c4d.chunk.js` from webpack, `index-BqWz3f_A.js` from Vite
+fn is_hashed(path: &str) -> bool {
+    let name = path.rsplit('/').next().unwrap_or(path);
+    let Some((stem, _extension)) = name.rsplit_once('.') else {
+        return false;
+    };
+    let webpack = stem
+        .split('.')
+        .skip(1)
+        .any(|part| part.len() >= 8 && part.chars().all(|c| c.is_ascii_hexdigit()));
+    let vite = stem.rsplit_once('-').is_some_and(|(_, part)| {
+        part.len() == 8
+            && part.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+            && part.chars().any(|c| c.is_ascii_digit())
+    });
+    webpack || vite
+}
+
+// Weak validator from what identifies the file served: its modification time,
+// length and encoding (a precompressed variant is a different representation)
+fn etag(headers: &HeaderMap) -> Option<HeaderValue> {
+    let last_modified = headers.get(header::LAST_MODIFIED)?;
+    let length = headers.get(header::CONTENT_LENGTH)?;
+    let mut hasher = DefaultHasher::new();
+    last_modified.as_bytes().hash(&mut hasher);
+    headers
+        .get(header::CONTENT_ENCODING)
+        .map(|value| value.as_bytes())
+        .hash(&mut hasher);
+    let length = length.to_str().ok()?.parse::<u64>().ok()?;
+    format!("W/\"{:x}-{:016x}\"", length, hasher.finish())
+        .parse()
+        .ok()
+}
+
+// If-None-Match against an ETag, with the weak comparison
+fn none_match(if_none_match: &str, etag: &HeaderValue) -> bool {
+    let Ok(etag) = etag.to_str() else {
+        return false;
+    };
+    let opaque = |tag: &str| tag.trim().trim_start_matches("W/").to_string();
+    if_none_match
+        .split(',')
+        .any(|tag| tag.trim() == "*" || opaque(tag) == opaque(etag))
+}
+
+// Text files of a build, where the API origin it was built for can appear
+fn is_text(headers: &HeaderMap) -> bool {
+    headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|content_type| {
+            content_type.starts_with("text/")
+                || content_type.contains("javascript")
+                || content_type.contains("json")
+        })
+}
+
+// Point a build made for the default explorer server at the one on `port`
+fn rewrite_origin(body: Bytes, port: u16) -> Bytes {
+    let Ok(text) = std::str::from_utf8(&body) else {
+        return body;
+    };
+    let mut text = text.to_string();
+    for host in ["localhost", "127.0.0.1"] {
+        text = text.replace(
+            &format!("{}:{}", host, crate::lan_share::EXPLORER_PORT),
+            &format!("{}:{}", host, port),
+        );
+    }
+    Bytes::from(text)
+}
+
+/// Fallback handler of an explorer server on `port`: static files of the
+/// selected build (precompressed variants when the build has them), with
+/// cache headers and ETags. Builds call the API on port 3001, so servers on
+/// other ports serve them with that origin replaced by their own
+pub async fn serve_dist(mut req: Request, port: u16) -> Response {
+    let active = SETTINGS.read().active.clone();
+    let cache_control = if is_hashed(req.uri().path()) {
+        IMMUTABLE
+    } else {
+        REVALIDATE
+    };
+    // If-None-Match takes precedence over If-Modified-Since (RFC 9110), which
+    // ServeDir would otherwise answer on its own
+    let if_none_match = req
+        .headers()
+        .get(header::IF_NONE_MATCH)
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string);
+    if if_none_match.is_some() {
+        req.headers_mut().remove(header::IF_MODIFIED_SINCE);
+    }
+
+    let rewrite = port != crate::lan_share::EXPLORER_PORT;
+    let mut serve_dir = ServeDir::new(root(&active)).append_index_html_on_directories(true);
+    // Precompressed files can't be rewritten
+    if !rewrite {
+        serve_dir = serve_dir.precompressed_br().precompressed_gzip();
+    }
+    let result = serve_dir.try_call(req).await;
+    match result {
+        Ok(response) => {
+            let mut response = response.map(Body::new);
+            if rewrite && response.status() == StatusCode::OK && is_text(response.headers()) {
+                let (mut parts, body) = response.into_parts();
+                let Ok(body) = axum::body::to_bytes(body, usize::MAX).await else {
+                    return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+                };
+                let body = rewrite_origin(body, port);
+                parts
+                    .headers
+                    .insert(header::CONTENT_LENGTH, body.len().into());
+                response = Response::from_parts(parts, Body::from(body));
+            }
+            if response.status() == StatusCode::OK {
+                if let Some(etag) = etag(response.headers()) {
+                    if if_none_match
+                        .as_deref()
+                        .is_some_and(|tags| none_match(tags, &etag))
+                    {
+                        // Same validators and caching as the full response
+                        let mut not_modified = StatusCode::NOT_MODIFIED.into_response();
+                        for name in [header::LAST_MODIFIED, header::VARY] {
+                            if let Some(value) = response.headers().get(&name) {
+                                not_modified.headers_mut().insert(name, value.clone());
+                            }
+                        }
+                        response = not_modified;
+                    }
+                    response.headers_mut().insert(header::ETAG, etag);
+                }
+            }
+            if response.status() == StatusCode::OK || response.status() == StatusCode::NOT_MODIFIED
+            {
+                response.headers_mut().insert(
+                    header::CACHE_CONTROL,
+                    HeaderValue::from_static(cache_control),
+                );
+            }
+            response
+        }
+        Err(e) => {
+            let status = match e.kind() {
+                std::io::ErrorKind::NotFound | std::io::ErrorKind::PermissionDenied => {
+                    StatusCode::NOT_FOUND
+                }
+                _ => StatusCode::INTERNAL_SERVER_ERROR,
+            };
+            status.into_response()
+        }
+    }
+}
+
+/// Every known build: bundled, downloaded, and the selected local directory
+pub fn list_internal() -> Vec<ExplorerDistInfo> {
+    let active = SETTINGS.read().active.clone();
+    let mut dists = vec![info(ExplorerDist::Bundled, &active)];
+
+    let mut names: Vec<String> = fs::read_dir(dists_dir())
+        .map(|entries| {
+            entries
+                .flatten()
+                .filter(|entry| entry.path().is_dir())
+                .filter_map(|entry| entry.file_name().into_string().ok())
+                // Downloads still being unpacked
+                .filter(|name| validate_name(name).is_ok())
+                .collect()
+        })
+        .unwrap_or_default();
+    names.sort();
+    dists.extend(
+        names
+            .into_iter()
+            .map(|name| info(ExplorerDist::Installed { name }, &active)),
+    );
+
+    if let ExplorerDist::Local { .. } = &active {
+        dists.push(info(active.clone(), &active));
+    }
+    dists
+}
+
+/// Serve `dist` from now on, including on a running explorer server
+pub fn set_internal(dist: ExplorerDist) -> Result<ExplorerDistInfo, String> {
+    let dist = match dist {
+        ExplorerDist::Installed { name } => {
+            validate_name(&name)?;
+            ExplorerDist::Installed { name }
+        }
+        ExplorerDist::Local { path } => {
+            let path = PathBuf::from(path.trim());
+            if !path.is_absolute() {
+                return Err(format!("{:?} is not an absolute path", path));
+            }
+            ExplorerDist::Local {
+                path: path.to_string_lossy().to_string(),
+            }
+        }
+        ExplorerDist::Bundled => ExplorerDist::Bundled,
+    };
+    let path = root(&dist);
+    if !is_build(&path) {
+        return Err(format!(
+            "{:?} has no index.html; is it an explorer build?",
+            path
+        ));
+    }
+    let settings = ExplorerDistSettings {
+        active: dist.clone(),
+    };
+    SETTINGS.save(&settings)?;
+    Ok(info(dist.clone(), &dist))
+}
+
+// Unpack an archive into `staging` and move the build inside it (at the root
+// or in a single top-level directory, like `build/`) to `target`
+fn install_archive(archive: &[u8], staging: &Path, target: &Path) -> Result<(), String> {
+    if staging.exists() {
+        fs::remove_dir_all(staging)
+            .map_err(|e| format!("Failed to clear download directory: {}", e))?;
+    }
+    tar::Archive::new(flate2::read::GzDecoder::new(archive))
+        .unpack(staging)
+        .map_err(|e| format!("Failed to extract explorer build: {}", e))?;
+
+    let build = if is_build(staging) {
+        staging.to_path_buf()
+    } else {
+        let entries: Vec<PathBuf> = fs::read_dir(staging)
+            .map_err(|e| format!("Failed to read explorer build: {}", e))?
+            .flatten()
+            .map(|entry| entry.path())
+            .collect();
+        match entries.as_slice() {
+            [dir] if is_build(dir) => dir.clone(),
+            _ => {
+                let _ = fs::remove_dir_all(staging);
+                return Err(
+                    "The archive has no index.html at its root or in a single top-level directory"
+                        .to_string(),
+                );
+            }
+        }
+    };
+
+    if target.exists() {
+        fs::remove_dir_all(target)
+            .map_err(|e| format!("Failed to replace explorer build: {}", e))?;
+    }
+    fs::rename(&build, target).map_err(|e| format!("Failed to install explorer build: {}", e))?;
+    let _ = fs::remove_dir_all(staging);
+    Ok(())
+}
+
+/// Download a `.tar.gz` explorer build as `name`, replacing a build of the
+/// same name
+pub async fn download_internal(name: &str, url: &str) -> Result<ExplorerDistInfo, String> {
+    validate_name(name)?;
+    let response = reqwest::get(url)
+        .await
+        .map_err(|e| format!("Failed to download explorer build: {}", e))?;
+    if !response.status().is_success() {
+        return Err(format!(
+            "Failed to download explorer build: HTTP {}",
+            response.status()
+        ));
+    }
+    if response
+        .content_length()
+        .is_some_and(|len| len > MAX_DOWNLOAD_BYTES as u64)
+    {
+        return Err(format!(
+            "Explorer build is larger than {} MiB",
+            MAX_DOWNLOAD_BYTES / (1024 * 1024)
+        ));
+    }
+    let mut archive = Vec::new();
+    let mut stream = response.bytes_stream();
+    while let Some(chunk) = stream.next().await {
+        let chunk = chunk.map_err(|e| format!("Failed to download explorer build: {}", e))?;
+        if archive.len() + chunk.len() > MAX_DOWNLOAD_BYTES {
+            return Err(format!(
+                "Explorer build is larger than {} MiB",
+                MAX_DOWNLOAD_BYTES / (1024 * 1024)
+            ));
+        }
+        archive.extend_from_slice(&chunk);
+    }
+
+    let dir = dists_dir();
+    fs::create_dir_all(&dir)
+        .map_err(|e| format!("Failed to create explorer builds directory: {}", e))?;
+    let staging = dir.join(format!(".{}.partial", name));
+    let target = dir.join(name);
+    tokio::task::spawn_blocking(move || install_archive(&archive, &staging, &target))
+        .await
+        .map_err(|e| format!("Failed to install explorer build: {}", e))??;
+
+    let installed = InstalledInfo {
+        url: url.to_string(),
+        installed_at: crate::unix_now(),
+    };
+    let content = serde_json::to_string_pretty(&installed)
+        .map_err(|e| format!("Failed to serialize explorer build info: {}", e))?;
+    fs::write(info_path(name), content)
+        .map_err(|e| format!("Failed to write explorer build info: {}", e))?;
+
+    let active = SETTINGS.read().active.clone();
+    Ok(info(
+        ExplorerDist::Installed {
+            name: name.to_string(),
+        },
+        &active,
+    ))
+}
+
+/// Delete a downloaded build; the selected one can't be deleted
+pub fn delete_internal(name: &str) -> Result<(), String> {
+    validate_name(name)?;
+    let dist = ExplorerDist::Installed {
+        name: name.to_string(),
+    };
+    if SETTINGS.read().active == dist {
+        return Err(format!(
+            "Explorer build '{}' is being served; select another first",
+            name
+        ));
+    }
+    let path = root(&dist);
+    if !path.exists() {
+        return Err(format!("Explorer build '{}' not found", name));
+    }
+    fs::remove_dir_all(&path).map_err(|e| format!("Failed to delete explorer build: {}", e))?;
+    let _ = fs::remove_file(info_path(name));
+    Ok(())
+}
+
+// ============================================================================
+// Tauri Commands
+// ============================================================================
+
+// List the bundled, downloaded and local explorer builds, marking the one served
+#[tauri::command]
+pub async fn list_explorer_dists() -> Result<Vec<ExplorerDistInfo>, String> {
+    Ok(list_internal())
+}
+
+// Download a .tar.gz explorer build under a name; `activate` also serves it
+#[tauri::command]
+pub async fn download_explorer_dist(
+    name: String,
+    url: String,
+    activate: Option<bool>,
+) -> Result<ExplorerDistInfo, String> {
+    let info = download_internal(name.trim(), url.trim()).await?;
+    if activate.unwrap_or(false) {
+        return set_internal(info.dist);
+    }
+    Ok(info)
+}
+
+// Serve another explorer build (bundled, downloaded or a local directory);
+// applies to the running explorer server right away
+#[tauri::command]
+pub async fn set_explorer_dist(dist: ExplorerDist) -> Result<ExplorerDistInfo, String> {
+    set_internal(dist)
+}
+
+// Delete a downloaded explorer build
+#[tauri::command]
+pub async fn delete_explorer_dist(name: String) -> Result<String, String> {
+    delete_internal(&name)?;
+    Ok(format!("Explorer build '{}' deleted", name))
+}
@@ -0,0 +1,357 @@
+//! Optional explorer-service backend
+//!
+//! Some explorer features (token lists, richer search) need Hathor's
+//! explorer-service next to the node. Forge can run one, either as a local
+//! process (a checkout started with the configured command) or as a docker
+//! container, and the explorer server proxies it under `/explorer-api/*`, so
+//! an explorer build pointed at that path works from the same origin.
+//!
+//! The configuration lives in `explorer_service.json` in the app directory.
+//! The service is told where the localnet node is through `HATHOR_NODE_URL`
+//! (plus `HATHOR_NETWORK` and `PORT`); anything else it needs goes in `env`,
+//! which also overrides those.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::process::Stdio;
+use tauri::{AppHandle, Emitter};
+use tokio::io::{AsyncBufReadExt, BufReader};
+use tokio::process::Command as TokioCommand;
+
+use crate::settings::JsonSettings;
+use crate::SharedState;
+
+const DEFAULT_PORT: u16 = 3002;
+const CONTAINER_NAME: &str = "hathor-forge-explorer-service";
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExplorerServiceMode {
+    // `command` with `args`, in `working_dir`
+    #[default]
+    Process,
+    // `image`, published on 127.0.0.1:`port`
+    Docker,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ExplorerServiceConfig {
+    pub mode: ExplorerServiceMode,
+    pub command: String,
+    pub args: Vec<String>,
+    pub working_dir: Option<String>,
+    pub image: String,
+    // Port the service answers on (on the host, in docker mode)
+    pub port: u16,
+    // Port inside the container; defaults to `port`
+    pub container_port: Option<u16>,
+    pub env: BTreeMap<String, String>,
+}
+
+impl Default for ExplorerServiceConfig {
+    fn default() -> Self {
+        Self {
+            mode: ExplorerServiceMode::Process,
+            command: String::new(),
+            args: Vec::new(),
+            working_dir: None,
+            image: String::new(),
+            port: DEFAULT_PORT,
+            container_port: None,
+            env: BTreeMap::new(),
+        }
+    }
+}
+
+/// The running service, kept in `AppState`
+#[derive(Default)]
+pub struct ExplorerService {
+    // The service process, or the `docker run` attached to the container
+    child_id: Option<u32>,
+    port: u16,
+    mode: ExplorerServiceMode,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExplorerServiceStatus {
+    pub running: bool,
+    // Answered an HTTP request
+    pub reachable: bool,
+    pub mode: ExplorerServiceMode,
+    pub url: Option<String>,
+    // Path the explorer server proxies it under
+    pub proxy_path: String,
+    pub config: ExplorerServiceConfig,
+}
+
+static SETTINGS: JsonSettings<ExplorerServiceConfig> =
+    JsonSettings::new("explorer_service.json", "explorer-service config");
+
+/// Base URL of the running service, for the proxy
+pub fn url(state: &crate::AppState) -> Option<String> {
+    state
+        .explorer_service
+        .child_id
+        .map(|_| format!("http://127.0.0.1:{}", state.explorer_service.port))
+}
+
+/// PID of the running service (or `docker run`), for cleanup on exit
+pub fn child_id(state: &crate::AppState) -> Option<u32> {
+    state.explorer_service.child_id
+}
+
+/// Stop the container on exit, when running in docker mode
+pub fn cleanup(state: &crate::AppState) {
+    if state.explorer_service.child_id.is_some()
+        && state.explorer_service.mode == ExplorerServiceMode::Docker
+    {
+        let _ = std::process::Command::new("docker")
+            .args(["stop", CONTAINER_NAME])
+            .output();
+    }
+}
+
+// Variables the service gets unless `env` sets them
+fn default_env(
+    config: &ExplorerServiceConfig,
+    node_api_port: u16,
+    listen_port: u16,
+) -> BTreeMap<String, String> {
+    let node_host = match config.mode {
+        ExplorerServiceMode::Process => "127.0.0.1",
+        ExplorerServiceMode::Docker => "host.docker.internal",
+    };
+    let mut env = BTreeMap::from([
+        (
+            "HATHOR_NODE_URL".to_string(),
+            format!("http://{}:{}/v1a/", node_host, node_api_port),
+        ),
+        (
+            "HATHOR_NETWORK".to_string(),
+            crate::lan_share::NETWORK_NAME.to_string(),
+        ),
+        ("PORT".to_string(), listen_port.to_string()),
+    ]);
+    env.extend(config.env.clone());
+    env
+}
+
+fn command(config: &ExplorerServiceConfig, node_api_port: u16) -> Result<TokioCommand, String> {
+    match config.mode {
+        ExplorerServiceMode::Process => {
+            if config.command.trim().is_empty() {
+                return Err(
+                    "Set the explorer-service command (or use docker mode) first".to_string(),
+                );
+            }
+            let mut cmd = TokioCommand::new(config.command.trim());
+            cmd.args(&config.args)
+                .envs(default_env(config, node_api_port, config.port));
+            if let Some(dir) = &config.working_dir {
+                cmd.current_dir(dir);
+            }
+            Ok(cmd)
+        }
+        ExplorerServiceMode::Docker => {
+            if config.image.trim().is_empty() {
+                return Err("Set the explorer-service docker image first".to_string());
+            }
+            let container_port = config.container_port.unwrap_or(config.port);
+            let mut cmd = TokioCommand::new("docker");
+            cmd.args([
+                "run",
+                "--rm",
+                "--name",
+                CONTAINER_NAME,
+                "--add-host",
+                "host.docker.internal:host-gateway",
+                "-p",
+                &format!("127.0.0.1:{}:{}", config.port, container_port),
+            ]);
+            for (key, value) in default_env(config, node_api_port, container_port) {
+                cmd.args(["-e", &format!("{}={}", key, value)]);
+            }
+            cmd.arg(config.image.trim()).args(&config.args);
+            Ok(cmd)
+        }
+    }
+}
+
+// Emit the service's output as `explorer-service-log`; drained even with no
+// app to emit to
+fn emit_lines(output: impl tokio::io::AsyncRead + Send + Unpin + 'static, app: Option<AppHandle>) {
+    tokio::spawn(async move {
+        let mut lines = BufReader::new(output).lines();
+        while let Ok(Some(line)) = lines.next_line().await {
+            if let Some(app) = &app {
+                let _ = app.emit("explorer-service-log", &line);
+            }
+        }
+    });
+}
+
+/// Start explorer-service with the saved configuration; logs and termination
+/// are emitted when there's an app to emit them to
+pub async fn start_explorer_service_internal(
+    app: Option<AppHandle>,
+    state: &SharedState,
+) -> Result<String, String> {
+    let config = SETTINGS.load();
+    let node_api_port = {
+        let state_guard = state.lock().await;
+        if state_guard.explorer_service.child_id.is_some() {
+            return Err("explorer-service is already running".to_string());
+        }
+        state_guard.node_api_port
+    };
+
+    if config.mode == ExplorerServiceMode::Docker {
+        // A container left behind by a crash would hold the name
+        let _ = TokioCommand::new("docker")
+            .args(["rm", "-f", CONTAINER_NAME])
+            .output()
+            .await;
+    } else {
+        crate::kill_process_on_port(config.port);
+    }
+
+    let mut child = command(&config, node_api_port)?
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("Failed to start explorer-service: {}", e))?;
+
+    {
+        let mut state_guard = state.lock().await;
+        state_guard.explorer_service = ExplorerService {
+            child_id: child.id(),
+            port: config.port,
+            mode: config.mode,
+        };
+    }
+
+    if let Some(stdout) = child.stdout.take() {
+        emit_lines(stdout, app.clone());
+    }
+    if let Some(stderr) = child.stderr.take() {
+        emit_lines(stderr, app.clone());
+    }
+
+    let exit_state = state.clone();
+    let pid = child.id();
+    tokio::spawn(async move {
+        let code = child.wait().await.ok().and_then(|status| status.code());
+        let mut state_guard = exit_state.lock().await;
+        // Unless a new one was started meanwhile
+        if state_guard.explorer_service.child_id == pid {
+            state_guard.explorer_service = ExplorerService::default();
+        }
+        drop(state_guard);
+        if let Some(app) = app {
+            let _ = app.emit("explorer-service-terminated", code);
+        }
+    });
+
+    Ok(format!(
+        "explorer-service started on port {} (proxied under /explorer-api/)",
+        config.port
+    ))
+}
+
+/// Stop explorer-service
+pub async fn stop_explorer_service_internal(state: &SharedState) -> Result<String, String> {
+    let service = {
+        let mut state_guard = state.lock().await;
+        if state_guard.explorer_service.child_id.is_none() {
+            return Err("explorer-service is not running".to_string());
+        }
+        std::mem::take(&mut state_guard.explorer_service)
+    };
+    if service.mode == ExplorerServiceMode::Docker {
+        TokioCommand::new("docker")
+            .args(["stop", CONTAINER_NAME])
+            .output()
+            .await
+            .map_err(|e| format!("Failed to stop the explorer-service container: {}", e))?;
+    }
+    if let Some(pid) = service.child_id {
+        tokio::task::spawn_blocking(move || crate::kill_process(pid))
+            .await
+            .map_err(|e| format!("Failed to stop explorer-service: {}", e))?;
+    }
+    Ok("explorer-service stopped".to_string())
+}
+
+pub async fn get_explorer_service_status_internal(state: &SharedState) -> ExplorerServiceStatus {
+    let (url, mode) = {
+        let state_guard = state.lock().await;
+        (url(&state_guard), state_guard.explorer_service.mode)
+    };
+    let reachable = match &url {
+        Some(url) => reqwest::Client::new()
+            .get(url)
+            .timeout(std::time::Duration::from_secs(2))
+            .send()
+            .await
+            .is_ok(),
+        None => false,
+    };
+    let config = SETTINGS.load();
+    ExplorerServiceStatus {
+        running: url.is_some(),
+        reachable,
+        mode: if url.is_some() { mode } else { config.mode },
+        url,
+        proxy_path: "/explorer-api/".to_string(),
+        config,
+    }
+}
+
+// ============================================================================
+// Tauri Commands
+// ============================================================================
+
+// Get explorer-service's state and configuration
+#[tauri::command]
+pub async fn get_explorer_service_status(
+    state: tauri::State<'_, SharedState>,
+) -> Result<ExplorerServiceStatus, String> {
+    Ok(get_explorer_service_status_internal(&state).await)
+}
+
+// Save explorer-service's configuration (applied at its next start)
+#[tauri::command]
+pub async fn set_explorer_service_config(
+    config: ExplorerServiceConfig,
+) -> Result<ExplorerServiceConfig, String> {
+    if config.port == 0 || config.container_port == Some(0) {
+        return Err("Ports must be between 1 and 65535".to_string());
+    }
+    if config.port == crate::lan_share::EXPLORER_PORT
+        || config.port == crate::NodeConfig::default().api_port
+    {
+        return Err(format!(
+            "Port {} is taken by Forge; pick another for explorer-service",
+            config.port
+        ));
+    }
+    SETTINGS.save(&config)?;
+    Ok(config)
+}
+
+// Start explorer-service
+#[tauri::command]
+pub async fn start_explorer_service(
+    app: AppHandle,
+    state: tauri::State<'_, SharedState>,
+) -> Result<String, String> {
+    start_explorer_service_internal(Some(app), &state).await
+}
+
+// Stop explorer-service
+#[tauri::command]
+pub async fn stop_explorer_service(state: tauri::State<'_, SharedState>) -> Result<String, String> {
+    stop_explorer_service_internal(&state).await
+}
@@ -0,0 +1,529 @@
+//! Faucet operations backed by the fullnode's built-in wallet
+//!
+//! The node wallet is an HD wallet seeded with `DEV_WALLET_WORDS`, so its
+//! addresses are derived here (m/44'/280'/0'/0/i, like hathor-core) and their
+//! balances read from the node's address index (`--wallet-index`).
+//!
+//! Settings live in `faucet.json` in the app directory. With address rotation
+//! on, the node wallet moves to a fresh address before every funding operation,
+//! so change (and later funds) don't keep landing on the same address.
+//!
+//! The explorer server can also serve a public faucet: `POST /faucet` with
+//! `{"address": ..., "amount": cents}` (amount optional, capped by
+//! `max_amount`) pays from the node wallet, so dApps and teammates on the LAN
+//! can fund themselves without the Forge UI. It's off by default, limited to
+//! `requests_per_minute` overall and one payment per address per
+//! `cooldown_secs`; `GET /faucet` reports the limits.
+
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use axum::{Json, Router};
+use bip32::{ChildNumber, XPrv, XPub};
+use ripemd::Ripemd160;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Arc, Mutex as StdMutex};
+use std::time::{Duration, Instant};
+
+use crate::access_log::AccessError;
+use crate::amount::HtrAmount;
+use crate::settings::JsonSettings;
+use crate::{SharedState, WalletAddress};
+
+// Hathor transactions can have at most 255 outputs (one may be the change)
+const MAX_OUTPUTS_PER_TX: usize = 254;
+
+// hathor-core's HD wallet derivation path (external chain) and gap limit
+const NODE_WALLET_CHAIN_PATH: &str = "m/44'/280'/0'/0";
+const NODE_WALLET_GAP_LIMIT: u32 = 20;
+
+// Window of the public faucet's overall rate limit
+const PUBLIC_RATE_WINDOW: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FaucetSettings {
+    // Derive a fresh node wallet address for each funding operation
+    #[serde(default)]
+    pub rotate_addresses: bool,
+    // The explorer server's /faucet endpoint
+    #[serde(default)]
+    pub public: PublicFaucetSettings,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct PublicFaucetSettings {
+    pub enabled: bool,
+    // Largest (and default) payment per request
+    pub max_amount: HtrAmount,
+    // Time before the same address can be paid again
+    pub cooldown_secs: u64,
+    // Payments per minute, all addresses together
+    pub requests_per_minute: u32,
+}
+
+impl Default for PublicFaucetSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            max_amount: HtrAmount::from_cents(10_000),
+            cooldown_secs: 600,
+            requests_per_minute: 10,
+        }
+    }
+}
+
+/// Recent public faucet payments, kept in `AppState` so limits survive
+/// explorer restarts
+#[derive(Default)]
+pub struct PublicFaucetLimits {
+    recent: VecDeque<Instant>,
+    last_paid: HashMap<String, Instant>,
+}
+
+pub type SharedFaucetLimits = Arc<StdMutex<PublicFaucetLimits>>;
+
+impl PublicFaucetLimits {
+    // Claim a payment to `address`, or how long to wait for one
+    fn reserve(&mut self, address: &str, settings: &PublicFaucetSettings) -> Result<(), Duration> {
+        let now = Instant::now();
+        let cooldown = Duration::from_secs(settings.cooldown_secs);
+        while self
+            .recent
+            .front()
+            .is_some_and(|at| now.duration_since(*at) >= PUBLIC_RATE_WINDOW)
+        {
+            self.recent.pop_front();
+        }
+        self.last_paid
+            .retain(|_, at| now.duration_since(*at) < cooldown);
+
+        if let Some(at) = self.last_paid.get(address) {
+            return Err(cooldown - now.duration_since(*at));
+        }
+        if self.recent.len() >= settings.requests_per_minute as usize {
+            let oldest = self.recent.front().copied().unwrap_or(now);
+            return Err(PUBLIC_RATE_WINDOW.saturating_sub(now.duration_since(oldest)));
+        }
+        self.recent.push_back(now);
+        self.last_paid.insert(address.to_string(), now);
+        Ok(())
+    }
+
+    // Let an address retry after its payment failed
+    fn release(&mut self, address: &str) {
+        self.last_paid.remove(address);
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FaucetPayment {
+    pub address: String,
+    pub amount: HtrAmount,
+    // Unix timestamp until which the output can't be spent
+    #[serde(default)]
+    pub timelock: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FaucetDistribution {
+    // One transaction per batch of up to 254 outputs
+    pub hashes: Vec<String>,
+    pub outputs: usize,
+    pub total: HtrAmount,
+}
+
+static SETTINGS: JsonSettings<FaucetSettings> = JsonSettings::new("faucet.json", "faucet settings");
+
+/// Turn faucet address rotation on or off
+pub fn set_address_rotation(enabled: bool) -> Result<FaucetSettings, String> {
+    let mut settings = SETTINGS.load();
+    settings.rotate_addresses = enabled;
+    SETTINGS.save(&settings)?;
+    Ok(settings)
+}
+
+/// Change the public faucet endpoint's settings; applies to the next request
+pub fn set_public_faucet(public: PublicFaucetSettings) -> Result<FaucetSettings, String> {
+    if public.max_amount.is_zero() {
+        return Err("Maximum amount must be positive".to_string());
+    }
+    if public.requests_per_minute == 0 {
+        return Err("Requests per minute must be positive".to_string());
+    }
+    let mut settings = SETTINGS.load();
+    settings.public = public;
+    SETTINGS.save(&settings)?;
+    Ok(settings)
+}
+
+/// Move the node wallet to a fresh address when rotation is enabled, returning it
+pub async fn rotate_address(client: &reqwest::Client) -> Result<Option<String>, String> {
+    if !SETTINGS.load().rotate_addresses {
+        return Ok(None);
+    }
+
+    let result: serde_json::Value = client
+        .get("http://127.0.0.1:8080/v1a/wallet/address")
+        .query(&[("new", "true")])
+        .send()
+        .await
+        .map_err(|e| format!("Failed to derive a new faucet address: {}", e))?
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse address response: {}", e))?;
+
+    Ok(result["address"].as_str().map(|s| s.to_string()))
+}
+
+/// Spendable (unlocked) balance of the node wallet
+pub async fn faucet_available(client: &reqwest::Client) -> Result<HtrAmount, String> {
+    let balance: serde_json::Value = client
+        .get("http://127.0.0.1:8080/v1a/wallet/balance/")
+        .send()
+        .await
+        .map_err(|e| format!("Failed to get faucet balance: {}", e))?
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse faucet balance: {}", e))?;
+
+    Ok(HtrAmount::from_json(&balance["balance"]["available"]))
+}
+
+// Public key of the node wallet's external chain
+fn node_wallet_chain() -> Result<XPub, String> {
+    let mnemonic = bip39::Mnemonic::parse(crate::DEV_WALLET_WORDS)
+        .map_err(|e| format!("Invalid node wallet words: {}", e))?;
+    let path = NODE_WALLET_CHAIN_PATH
+        .parse()
+        .map_err(|e| format!("Invalid derivation path: {}", e))?;
+    let chain = XPrv::derive_from_path(mnemonic.to_seed(""), &path)
+        .map_err(|e| format!("Failed to derive node wallet key: {}", e))?;
+    Ok(chain.public_key())
+}
+
+fn node_wallet_address(chain: &XPub, index: u32) -> Result<String, String> {
+    let child = ChildNumber::new(index, false)
+        .and_then(|child| chain.derive_child(child))
+        .map_err(|e| format!("Failed to derive address {}: {}", index, e))?;
+    let pubkey_hash: [u8; 20] = Ripemd160::digest(Sha256::digest(child.to_bytes())).into();
+    Ok(crate::address::encode_p2pkh(&pubkey_hash))
+}
+
+/// The first `count` node wallet addresses
+pub fn node_wallet_addresses(count: u32) -> Result<Vec<String>, String> {
+    let chain = node_wallet_chain()?;
+    (0..count)
+        .map(|index| node_wallet_address(&chain, index))
+        .collect()
+}
+
+// HTR balance (received - spent, including locked rewards) and tx count of an address
+async fn address_balance(
+    client: &reqwest::Client,
+    address: &str,
+) -> Result<(HtrAmount, u64), String> {
+    let result: serde_json::Value = client
+        .get("http://127.0.0.1:8080/v1a/thin_wallet/address_balance")
+        .query(&[("address", address)])
+        .send()
+        .await
+        .map_err(|e| format!("Failed to get address balance: {}", e))?
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse address balance: {}", e))?;
+
+    if !result["success"].as_bool().unwrap_or(false) {
+        let message = result["message"].as_str().unwrap_or("Unknown error");
+        return Err(format!("Failed to get address balance: {}", message));
+    }
+
+    let htr = &result["tokens_data"]["00"];
+    let received = htr["received"].as_u64().unwrap_or(0);
+    let spent = htr["spent"].as_u64().unwrap_or(0);
+    Ok((
+        HtrAmount::from_cents(received.saturating_sub(spent)),
+        result["total_transactions"].as_u64().unwrap_or(0),
+    ))
+}
+
+/// Every node wallet address up to the gap limit past the last used one,
+/// with balances and usage flags
+pub async fn faucet_addresses_internal(state: &SharedState) -> Result<Vec<WalletAddress>, String> {
+    if !state.lock().await.node_running {
+        return Err("Node is not running".to_string());
+    }
+
+    let client = reqwest::Client::new();
+    let current: serde_json::Value = client
+        .get("http://127.0.0.1:8080/v1a/wallet/address")
+        .send()
+        .await
+        .map_err(|e| format!("Failed to fetch address: {}", e))?
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse address response: {}", e))?;
+    let current = current["address"].as_str().unwrap_or_default();
+
+    let chain = node_wallet_chain()?;
+    let mut addresses = Vec::new();
+    let mut unused_run = 0;
+    let mut next_index = 0;
+
+    // Scan a gap limit's worth of addresses at a time until enough unused ones follow
+    while unused_run < NODE_WALLET_GAP_LIMIT {
+        let batch = (next_index..next_index + NODE_WALLET_GAP_LIMIT)
+            .map(|index| node_wallet_address(&chain, index).map(|address| (index, address)))
+            .collect::<Result<Vec<_>, String>>()?;
+        next_index += NODE_WALLET_GAP_LIMIT;
+
+        let balances = futures_util::future::join_all(
+            batch
+                .iter()
+                .map(|(_, address)| address_balance(&client, address)),
+        )
+        .await;
+
+        for ((index, address), balance) in batch.into_iter().zip(balances) {
+            let (balance, transactions) = balance?;
+            let used = transactions > 0;
+            unused_run = if used { 0 } else { unused_run + 1 };
+
+            addresses.push(WalletAddress {
+                current: address == current,
+                address,
+                index,
+                balance: Some(balance),
+                used,
+            });
+            if unused_run == NODE_WALLET_GAP_LIMIT {
+                break;
+            }
+        }
+    }
+
+    Ok(addresses)
+}
+
+/// Pay many addresses from the node wallet using multi-output transactions
+pub async fn faucet_distribute_internal(
+    state: &SharedState,
+    payments: &[FaucetPayment],
+) -> Result<FaucetDistribution, String> {
+    if !state.lock().await.node_running {
+        return Err("Node is not running".to_string());
+    }
+    if payments.is_empty() {
+        return Err("At least one payment is required".to_string());
+    }
+
+    let mut total = 0u64;
+    for (i, payment) in payments.iter().enumerate() {
+        crate::address::check_address(&payment.address)
+            .map_err(|e| format!("Payment {}: {}", i, e))?;
+        if payment.amount.is_zero() {
+            return Err(format!("Payment {}: amount must be positive", i));
+        }
+        total = total
+            .checked_add(payment.amount.cents())
+            .ok_or("Total amount overflows")?;
+    }
+    let total = HtrAmount::from_cents(total);
+
+    let client = reqwest::Client::new();
+    let available = faucet_available(&client).await?;
+    if available < total {
+        return Err(format!(
+            "Faucet has {} available but the distribution needs {}. Mine some blocks first.",
+            available, total
+        ));
+    }
+
+    let mut hashes = Vec::new();
+    for batch in payments.chunks(MAX_OUTPUTS_PER_TX) {
+        rotate_address(&client).await?;
+
+        let outputs: Vec<serde_json::Value> = batch
+            .iter()
+            .map(|payment| {
+                let mut output = json!({
+                    "address": payment.address,
+                    "value": payment.amount,
+                });
+                if let Some(timelock) = payment.timelock {
+                    output["timelock"] = json!(timelock);
+                }
+                output
+            })
+            .collect();
+
+        let result: serde_json::Value = client
+            .post("http://127.0.0.1:8080/v1a/wallet/send_tokens/")
+            .json(&json!({
+                "data": {
+                    "inputs": [],
+                    "outputs": outputs,
+                }
+            }))
+            .send()
+            .await
+            .map_err(|e| format!("Failed to send transaction: {}", e))?
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse response: {}", e))?;
+
+        if !result["success"].as_bool().unwrap_or(false) {
+            let message = result["message"].as_str().unwrap_or("Unknown error");
+            return Err(format!(
+                "Transaction failed after {} of {} batches: {}",
+                hashes.len(),
+                payments.len().div_ceil(MAX_OUTPUTS_PER_TX),
+                message
+            ));
+        }
+        let hash = result["tx"]["hash"]
+            .as_str()
+            .or_else(|| result["hash"].as_str())
+            .unwrap_or("unknown");
+        hashes.push(hash.to_string());
+    }
+
+    Ok(FaucetDistribution {
+        hashes,
+        outputs: payments.len(),
+        total,
+    })
+}
+
+#[derive(Debug, Deserialize)]
+struct PublicFaucetRequest {
+    address: String,
+    // In cents; defaults to the maximum
+    #[serde(default)]
+    amount: Option<HtrAmount>,
+}
+
+fn public_error(status: StatusCode, message: String) -> Response {
+    let mut response = (
+        status,
+        Json(json!({ "success": false, "message": message })),
+    )
+        .into_response();
+    response.extensions_mut().insert(AccessError(message));
+    response
+}
+
+// GET /faucet: whether the endpoint is on, and its limits
+async fn public_faucet_info() -> Json<PublicFaucetSettings> {
+    Json(SETTINGS.load().public)
+}
+
+// POST /faucet: pay an address from the node wallet
+async fn public_faucet_request(
+    State(state): State<SharedState>,
+    Json(request): Json<PublicFaucetRequest>,
+) -> Response {
+    let settings = SETTINGS.load().public;
+    if !settings.enabled {
+        return public_error(
+            StatusCode::FORBIDDEN,
+            "The faucet endpoint is disabled in Hathor Forge".to_string(),
+        );
+    }
+    let address = request.address.trim().to_string();
+    if let Err(e) = crate::address::check_address(&address) {
+        return public_error(StatusCode::BAD_REQUEST, e);
+    }
+    let amount = request.amount.unwrap_or(settings.max_amount);
+    if amount.is_zero() || amount > settings.max_amount {
+        return public_error(
+            StatusCode::BAD_REQUEST,
+            format!("Amount must be between 0.01 and {}", settings.max_amount),
+        );
+    }
+
+    let limits = state.lock().await.faucet_limits.clone();
+    let reserved = limits
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .reserve(&address, &settings);
+    if let Err(wait) = reserved {
+        let mut response = public_error(
+            StatusCode::TOO_MANY_REQUESTS,
+            format!("Try again in {} seconds", wait.as_secs().max(1)),
+        );
+        if let Ok(value) = wait.as_secs().max(1).to_string().parse() {
+            response.headers_mut().insert("retry-after", value);
+        }
+        return response;
+    }
+
+    let payment = FaucetPayment {
+        address: address.clone(),
+        amount,
+        timelock: None,
+    };
+    match faucet_distribute_internal(&state, &[payment]).await {
+        Ok(distribution) => Json(json!({
+            "success": true,
+            "hash": distribution.hashes.first(),
+            "address": address,
+            "amount": amount,
+        }))
+        .into_response(),
+        Err(e) => {
+            limits
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .release(&address);
+            public_error(StatusCode::SERVICE_UNAVAILABLE, e)
+        }
+    }
+}
+
+/// The explorer server's /faucet routes
+pub fn router(state: SharedState) -> Router {
+    Router::new()
+        .route(
+            "/faucet",
+            get(public_faucet_info).post(public_faucet_request),
+        )
+        .with_state(state)
+}
+
+// ============================================================================
+// Tauri Commands
+// ============================================================================
+
+// Get the faucet settings
+#[tauri::command]
+pub async fn get_faucet_settings() -> Result<FaucetSettings, String> {
+    Ok(SETTINGS.load())
+}
+
+// Derive a fresh faucet address for each funding operation (or stop doing so)
+#[tauri::command]
+pub async fn set_faucet_address_rotation(enabled: bool) -> Result<FaucetSettings, String> {
+    set_address_rotation(enabled)
+}
+
+// Send HTR from the faucet to many addresses at once
+#[tauri::command]
+pub async fn faucet_distribute(
+    state: tauri::State<'_, SharedState>,
+    requests: Vec<FaucetPayment>,
+) -> Result<FaucetDistribution, String> {
+    faucet_distribute_internal(&state, &requests).await
+}
+
+// Configure the explorer server's public /faucet endpoint
+#[tauri::command]
+pub async fn set_public_faucet_settings(
+    settings: PublicFaucetSettings,
+) -> Result<FaucetSettings, String> {
+    set_public_faucet(settings)
+}
@@ -0,0 +1,237 @@
+//! Feature activation (soft forks) on the localnet
+//!
+//! hathor-core activates consensus features through signaling windows that
+//! last weeks on mainnet. Features listed in `NodeConfig.features` (stored in
+//! `features.json` in the app directory, so later starts keep them) are forced
+//! on: the node then runs with a settings file extending the localnet one,
+//! with evaluation windows of `EVALUATION_INTERVAL` blocks and every listed
+//! feature locking in on timeout. A forced feature is active from height
+//! `ACTIVATION_HEIGHT`. Feature states are kept in block metadata, so
+//! changing the list on an existing chain only applies cleanly after a data
+//! reset.
+//!
+//! The same settings file turns on nano contracts (`NodeConfig.nano_contracts`,
+//! stored alongside), with on-chain blueprints open to any address.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fs;
+
+use crate::settings::JsonSettings;
+use crate::{NodeConfig, SharedState};
+
+const EVALUATION_INTERVAL: u64 = 4;
+// Started at genesis, locked in at the timeout two windows later, active after
+// one more window
+const TIMEOUT_HEIGHT: u64 = 2 * EVALUATION_INTERVAL;
+const ACTIVATION_HEIGHT: u64 = 3 * EVALUATION_INTERVAL;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct FeatureSettings {
+    #[serde(default)]
+    enabled: Vec<String>,
+    #[serde(default)]
+    nano_contracts: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FeatureState {
+    pub name: String,
+    // DEFINED, STARTED, MUST_SIGNAL, LOCKED_IN, ACTIVE or FAILED
+    pub state: String,
+    pub acceptance: Option<f64>,
+    pub threshold: Option<f64>,
+    pub start_height: Option<u64>,
+    pub timeout_height: Option<u64>,
+    pub minimum_activation_height: Option<u64>,
+    pub lock_in_on_timeout: Option<bool>,
+    pub version: Option<String>,
+    // Forced on through NodeConfig.features
+    pub forced: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FeatureStates {
+    pub block_hash: Option<String>,
+    pub block_height: Option<u64>,
+    pub features: Vec<FeatureState>,
+    // Features forced on from the next node start
+    pub enabled: Vec<String>,
+    pub nano_contracts: bool,
+}
+
+static SETTINGS: JsonSettings<FeatureSettings> =
+    JsonSettings::new("features.json", "feature settings");
+
+fn yaml_path() -> std::path::PathBuf {
+    crate::get_app_dir().join("localnet-features.yml")
+}
+
+// Feature names as hathor-core spells them (e.g. NANO_CONTRACTS)
+fn normalize(features: &[String]) -> Result<Vec<String>, String> {
+    let mut names = Vec::new();
+    for feature in features {
+        let name = feature.trim().to_uppercase();
+        if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+            return Err(format!("Invalid feature name '{}'", feature));
+        }
+        if !names.contains(&name) {
+            names.push(name);
+        }
+    }
+    Ok(names)
+}
+
+/// Store the features to force on from the next node start
+pub fn set_enabled_features_internal(features: &[String]) -> Result<Vec<String>, String> {
+    let mut settings = SETTINGS.load();
+    settings.enabled = normalize(features)?;
+    SETTINGS.save(&settings)?;
+    Ok(settings.enabled)
+}
+
+/// Whether the node starts with nano contracts enabled
+pub fn nano_contracts_enabled() -> bool {
+    SETTINGS.load().nano_contracts
+}
+
+/// Store whether nano contracts are enabled from the next node start
+pub fn set_nano_contracts_internal(enabled: bool) -> Result<(), String> {
+    let mut settings = SETTINGS.load();
+    settings.nano_contracts = enabled;
+    SETTINGS.save(&settings)
+}
+
+// Settings extending the localnet ones with nano contracts and the forced
+// features
+fn settings_yaml(settings: &FeatureSettings) -> String {
+    let mut yaml = "extends: localnet.yml\n".to_string();
+    if settings.nano_contracts {
+        yaml.push_str(
+            "ENABLE_NANO_CONTRACTS: true\n\
+             NC_ON_CHAIN_BLUEPRINT_RESTRICTED: false\n",
+        );
+    }
+    if settings.enabled.is_empty() {
+        return yaml;
+    }
+
+    yaml.push_str(&format!(
+        "FEATURE_ACTIVATION:\n  \
+           enable_usage: true\n  \
+           evaluation_interval: {}\n  \
+           default_threshold: {}\n  \
+           features:\n",
+        EVALUATION_INTERVAL,
+        EVALUATION_INTERVAL - 1
+    ));
+    for feature in &settings.enabled {
+        yaml.push_str(&format!(
+            "    {}:\n      \
+               start_height: 0\n      \
+               timeout_height: {}\n      \
+               minimum_activation_height: {}\n      \
+               lock_in_on_timeout: true\n      \
+               version: 0.0.0\n      \
+               signal_support_by_default: true\n",
+            feature, TIMEOUT_HEIGHT, ACTIVATION_HEIGHT
+        ));
+    }
+    yaml
+}
+
+/// hathor-core network arguments for a node start: `--localnet`, or a
+/// settings file extending it when nano contracts or features are turned on.
+/// The config's settings, when given, replace the stored ones.
+pub fn network_args(config: &NodeConfig) -> Result<Vec<String>, String> {
+    if let Some(features) = &config.features {
+        set_enabled_features_internal(features)?;
+    }
+    if let Some(enabled) = config.nano_contracts {
+        set_nano_contracts_internal(enabled)?;
+    }
+    let settings = SETTINGS.load();
+    if settings.enabled.is_empty() && !settings.nano_contracts {
+        return Ok(vec!["--localnet".to_string()]);
+    }
+
+    let path = yaml_path();
+    fs::write(&path, settings_yaml(&settings))
+        .map_err(|e| format!("Failed to write feature settings: {}", e))?;
+    Ok(vec![
+        "--config-yaml".to_string(),
+        path.to_string_lossy().to_string(),
+    ])
+}
+
+/// Feature states at the best block, as the node evaluates them
+pub async fn get_feature_states_internal(state: &SharedState) -> Result<FeatureStates, String> {
+    let settings = SETTINGS.load();
+    let enabled = settings.enabled;
+    if !state.lock().await.node_running {
+        return Err("Node is not running".to_string());
+    }
+
+    let response: Value = reqwest::Client::new()
+        .get(format!(
+            "http://127.0.0.1:{}/v1a/feature",
+            NodeConfig::default().api_port
+        ))
+        .send()
+        .await
+        .map_err(|e| format!("Failed to get feature states: {}", e))?
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse feature states: {}", e))?;
+    if response["success"] == false {
+        let message = response["message"].as_str().unwrap_or("Unknown error");
+        return Err(format!("Failed to get feature states: {}", message));
+    }
+
+    let features = response["features"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .map(|feature| {
+            let name = feature["name"].as_str().unwrap_or_default().to_string();
+            FeatureState {
+                state: feature["state"].as_str().unwrap_or_default().to_string(),
+                acceptance: feature["acceptance"].as_f64(),
+                threshold: feature["threshold"].as_f64(),
+                start_height: feature["start_height"].as_u64(),
+                timeout_height: feature["timeout_height"].as_u64(),
+                minimum_activation_height: feature["minimum_activation_height"].as_u64(),
+                lock_in_on_timeout: feature["lock_in_on_timeout"].as_bool(),
+                version: feature["version"].as_str().map(|s| s.to_string()),
+                forced: enabled.contains(&name),
+                name,
+            }
+        })
+        .collect();
+
+    Ok(FeatureStates {
+        block_hash: response["block_hash"].as_str().map(|s| s.to_string()),
+        block_height: response["block_height"].as_u64(),
+        features,
+        enabled,
+        nano_contracts: settings.nano_contracts,
+    })
+}
+
+// ============================================================================
+// Tauri Commands
+// ============================================================================
+
+// Get the feature activation states at the best block
+#[tauri::command]
+pub async fn get_feature_states(
+    state: tauri::State<'_, SharedState>,
+) -> Result<FeatureStates, String> {
+    get_feature_states_internal(&state).await
+}
+
+// Set the features forced on from the next node start
+#[tauri::command]
+pub async fn set_enabled_features(features: Vec<String>) -> Result<Vec<String>, String> {
+    set_enabled_features_internal(&features)
+}
@@ -0,0 +1,335 @@
+//! Chain fixtures
+//!
+//! `export_chain` writes the blocks in a height range, and the transactions
+//! they confirm, to a JSON file: each vertex with its decoded form (for
+//! reading and diffing) and its raw bytes (for replaying). `import_fixture`
+//! pushes the vertices to the running node in timestamp order, which
+//! reproduces the exact same chain, hashes included. Localnet genesis is
+//! fixed, so a fixture exported from height 1 replays into any freshly reset
+//! localnet; one starting higher needs a node that already has the blocks
+//! below it.
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fs;
+
+use crate::{NodeConfig, SharedState};
+
+const FIXTURE_FORMAT: &str = "hathor-forge-fixture";
+const FIXTURE_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FixtureVertex {
+    pub hash: String,
+    // "block" or "tx"
+    pub kind: String,
+    pub timestamp: u64,
+    // Blocks only
+    pub height: Option<u64>,
+    // Serialized vertex, hex
+    pub raw: String,
+    // The node's JSON for the vertex
+    pub data: Value,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChainFixture {
+    pub format: String,
+    pub version: u32,
+    // Hash of the block at height 0, checked on import
+    pub genesis_block: String,
+    pub from_height: u64,
+    pub to_height: u64,
+    pub exported_at: u64, // Unix timestamp
+    // In timestamp order
+    pub vertices: Vec<FixtureVertex>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportResult {
+    pub path: String,
+    pub blocks: usize,
+    pub transactions: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportResult {
+    pub imported_blocks: usize,
+    pub imported_transactions: usize,
+    // Vertices the node already had
+    pub skipped: usize,
+    pub best_block_height: Option<u64>,
+}
+
+fn api_url(path: &str) -> String {
+    format!(
+        "http://127.0.0.1:{}/v1a/{}",
+        NodeConfig::default().api_port,
+        path
+    )
+}
+
+async fn get_json(
+    client: &reqwest::Client,
+    path: &str,
+    query: &[(&str, String)],
+) -> Result<Value, String> {
+    client
+        .get(api_url(path))
+        .query(query)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to query node: {}", e))?
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse node response: {}", e))
+}
+
+async fn block_hash_at(client: &reqwest::Client, height: u64) -> Result<String, String> {
+    let response = get_json(client, "block_at_height", &[("height", height.to_string())]).await?;
+    response["block"]["tx_id"]
+        .as_str()
+        .map(|s| s.to_string())
+        .ok_or_else(|| format!("No block at height {}", height))
+}
+
+// The node's transaction resource answer for `hash` (tx, meta and raw bytes)
+async fn get_vertex(client: &reqwest::Client, hash: &str) -> Result<Option<Value>, String> {
+    let response = get_json(client, "transaction", &[("id", hash.to_string())]).await?;
+    if response["success"].as_bool() != Some(true) {
+        return Ok(None);
+    }
+    Ok(Some(response))
+}
+
+fn fixture_vertex(
+    response: &Value,
+    kind: &str,
+    height: Option<u64>,
+) -> Result<FixtureVertex, String> {
+    let tx = &response["tx"];
+    let hash = tx["hash"].as_str().ok_or("Vertex has no hash")?;
+    Ok(FixtureVertex {
+        hash: hash.to_string(),
+        kind: kind.to_string(),
+        timestamp: tx["timestamp"].as_u64().unwrap_or(0),
+        height,
+        raw: tx["raw"]
+            .as_str()
+            .ok_or_else(|| format!("Node returned no raw bytes for {}", hash))?
+            .to_string(),
+        data: tx.clone(),
+    })
+}
+
+/// Write blocks `from_height..=to_height` and the transactions they confirm
+/// to `path`
+pub async fn export_chain_internal(
+    state: &SharedState,
+    from_height: u64,
+    to_height: u64,
+    path: &str,
+) -> Result<ExportResult, String> {
+    if !state.lock().await.node_running {
+        return Err("Node is not running".to_string());
+    }
+    if from_height == 0 || from_height > to_height {
+        return Err("Heights must satisfy 1 <= from_height <= to_height".to_string());
+    }
+
+    let client = reqwest::Client::new();
+    let genesis_block = block_hash_at(&client, 0).await?;
+
+    let mut blocks = Vec::new();
+    for height in from_height..=to_height {
+        let hash = block_hash_at(&client, height).await?;
+        let response = get_vertex(&client, &hash)
+            .await?
+            .ok_or_else(|| format!("Block {} not found", hash))?;
+        blocks.push((response, height));
+    }
+    let block_hashes: HashSet<String> = blocks
+        .iter()
+        .filter_map(|(response, _)| response["tx"]["hash"].as_str().map(|s| s.to_string()))
+        .collect();
+
+    // Transactions confirmed by the exported blocks are their ancestors whose
+    // first block is one of them
+    let mut transactions: HashMap<String, FixtureVertex> = HashMap::new();
+    let mut queue: VecDeque<String> = blocks
+        .iter()
+        .flat_map(|(response, _)| {
+            response["tx"]["parents"]
+                .as_array()
+                .cloned()
+                .unwrap_or_default()
+        })
+        .filter_map(|parent| parent.as_str().map(|s| s.to_string()))
+        .collect();
+    let mut seen = HashSet::new();
+    while let Some(hash) = queue.pop_front() {
+        if block_hashes.contains(&hash) || !seen.insert(hash.clone()) {
+            continue;
+        }
+        let Some(response) = get_vertex(&client, &hash).await? else {
+            continue;
+        };
+        // Blocks (including the one below the range) have no first block
+        let first_block = response["meta"]["first_block"].as_str().unwrap_or_default();
+        if !block_hashes.contains(first_block) {
+            continue;
+        }
+        if let Some(parents) = response["tx"]["parents"].as_array() {
+            queue.extend(
+                parents
+                    .iter()
+                    .filter_map(|p| p.as_str().map(|s| s.to_string())),
+            );
+        }
+        transactions.insert(hash, fixture_vertex(&response, "tx", None)?);
+    }
+
+    let mut vertices = blocks
+        .iter()
+        .map(|(response, height)| fixture_vertex(response, "block", Some(*height)))
+        .collect::<Result<Vec<_>, _>>()?;
+    let block_count = vertices.len();
+    let transaction_count = transactions.len();
+    vertices.extend(transactions.into_values());
+    vertices.sort_by_key(|vertex| vertex.timestamp);
+
+    let fixture = ChainFixture {
+        format: FIXTURE_FORMAT.to_string(),
+        version: FIXTURE_VERSION,
+        genesis_block,
+        from_height,
+        to_height,
+        exported_at: crate::unix_now(),
+        vertices,
+    };
+    let content = serde_json::to_string_pretty(&fixture)
+        .map_err(|e| format!("Failed to serialize fixture: {}", e))?;
+    if let Some(parent) = std::path::Path::new(path).parent() {
+        if !parent.as_os_str().is_empty() {
+            fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create fixture directory: {}", e))?;
+        }
+    }
+    fs::write(path, content).map_err(|e| format!("Failed to write fixture: {}", e))?;
+
+    Ok(ExportResult {
+        path: path.to_string(),
+        blocks: block_count,
+        transactions: transaction_count,
+    })
+}
+
+/// Push the vertices of the fixture at `path` to the running node, skipping
+/// those it already has
+pub async fn import_fixture_internal(
+    state: &SharedState,
+    path: &str,
+) -> Result<ImportResult, String> {
+    {
+        let state_guard = state.lock().await;
+        if !state_guard.node_running {
+            return Err("Node is not running".to_string());
+        }
+        // Blocks mined meanwhile would fork away from the fixture
+        if !state_guard.miners.is_empty() {
+            return Err("Stop running miners before importing a fixture".to_string());
+        }
+    }
+
+    let content = fs::read_to_string(path).map_err(|e| format!("Failed to read fixture: {}", e))?;
+    let mut fixture: ChainFixture =
+        serde_json::from_str(&content).map_err(|e| format!("Invalid fixture file: {}", e))?;
+    if fixture.format != FIXTURE_FORMAT {
+        return Err("Not a Hathor Forge chain fixture".to_string());
+    }
+    if fixture.version > FIXTURE_VERSION {
+        return Err(format!(
+            "Fixture version {} is newer than this Forge supports ({})",
+            fixture.version, FIXTURE_VERSION
+        ));
+    }
+
+    let client = reqwest::Client::new();
+    if block_hash_at(&client, 0).await? != fixture.genesis_block {
+        return Err("Fixture was exported from a network with a different genesis".to_string());
+    }
+
+    fixture.vertices.sort_by_key(|vertex| vertex.timestamp);
+    let mut result = ImportResult {
+        imported_blocks: 0,
+        imported_transactions: 0,
+        skipped: 0,
+        best_block_height: None,
+    };
+
+    for vertex in &fixture.vertices {
+        if get_vertex(&client, &vertex.hash).await?.is_some() {
+            result.skipped += 1;
+            continue;
+        }
+
+        let (endpoint, body) = if vertex.kind == "block" {
+            ("submit_block", json!({ "hexdata": vertex.raw }))
+        } else {
+            ("push_tx", json!({ "hex_tx": vertex.raw }))
+        };
+        let response: Value = client
+            .post(api_url(endpoint))
+            .json(&body)
+            .send()
+            .await
+            .map_err(|e| format!("Failed to push {}: {}", vertex.hash, e))?
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse node response: {}", e))?;
+        if response["success"].as_bool() != Some(true) && response["result"].as_bool() != Some(true)
+        {
+            return Err(format!(
+                "Node rejected {} {}: {}",
+                vertex.kind,
+                vertex.hash,
+                response["message"].as_str().unwrap_or("no reason given")
+            ));
+        }
+
+        if vertex.kind == "block" {
+            result.imported_blocks += 1;
+        } else {
+            result.imported_transactions += 1;
+        }
+    }
+
+    let status = get_json(&client, "status/", &[]).await?;
+    result.best_block_height = status["dag"]["best_block"]["height"].as_u64();
+    Ok(result)
+}
+
+// ============================================================================
+// Tauri Commands
+// ============================================================================
+
+// Export blocks in a height range, and the transactions they confirm, to a JSON fixture
+#[tauri::command]
+pub async fn export_chain(
+    state: tauri::State<'_, SharedState>,
+    from_height: u64,
+    to_height: u64,
+    path: String,
+) -> Result<ExportResult, String> {
+    export_chain_internal(&state, from_height, to_height, &path).await
+}
+
+// Replay a JSON fixture into the running node
+#[tauri::command]
+pub async fn import_fixture(
+    state: tauri::State<'_, SharedState>,
+    path: String,
+) -> Result<ImportResult, String> {
+    import_fixture_internal(&state, &path).await
+}
@@ -0,0 +1,499 @@
+//! GraphQL gateway over localnet data
+//!
+//! An optional `/graphql` endpoint on the explorer server giving dApp
+//! frontends one typed query surface for the local environment: blocks,
+//! transactions, addresses and tokens come from the fullnode (through the same
+//! lookups as the search box and block list), wallet summaries from the wallet
+//! registry and wallet-headless. Every object keeps the upstream JSON in a
+//! `raw` field for anything the schema doesn't model. `GET /graphql` serves
+//! GraphiQL.
+//!
+//! It's off by default; the setting lives in `graphql.json` and is applied
+//! when the explorer server starts.
+
+use async_graphql::http::GraphiQLSource;
+use async_graphql::{
+    ComplexObject, Context, EmptyMutation, EmptySubscription, Json, Object, Schema, SimpleObject,
+};
+use axum::extract::State;
+use axum::response::Html;
+use axum::routing::get;
+use axum::Router;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::search::SearchResult;
+use crate::settings::JsonSettings;
+use crate::SharedState;
+
+const NODE_API_URL: &str = "http://127.0.0.1:8080/v1a";
+// Deep queries fan out into many node requests
+const MAX_DEPTH: usize = 8;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GraphqlSettings {
+    #[serde(default)]
+    pub enabled: bool,
+}
+
+pub static SETTINGS: JsonSettings<GraphqlSettings> =
+    JsonSettings::new("graphql.json", "GraphQL settings");
+
+// ============================================================================
+// Schema
+// ============================================================================
+
+#[derive(SimpleObject)]
+struct NodeStatus {
+    best_block_height: Option<u64>,
+    best_block_hash: Option<String>,
+    // The node's /status response
+    raw: Json<Value>,
+}
+
+#[derive(SimpleObject)]
+struct Output {
+    value: u64,
+    token_data: u64,
+    // Decoded from the script, when it's a standard one
+    address: Option<String>,
+    token: Option<String>,
+    timelock: Option<u64>,
+}
+
+#[derive(SimpleObject)]
+struct Input {
+    tx_id: String,
+    index: u64,
+}
+
+#[derive(SimpleObject)]
+struct Block {
+    hash: String,
+    height: Option<u64>,
+    timestamp: Option<u64>,
+    weight: Option<f64>,
+    parents: Vec<String>,
+    outputs: Vec<Output>,
+    // The node's block JSON
+    raw: Json<Value>,
+}
+
+#[derive(SimpleObject)]
+struct Transaction {
+    hash: String,
+    timestamp: Option<u64>,
+    version: Option<u64>,
+    weight: Option<f64>,
+    parents: Vec<String>,
+    inputs: Vec<Input>,
+    outputs: Vec<Output>,
+    tokens: Vec<String>,
+    // Block confirming the transaction
+    first_block: Option<String>,
+    voided: bool,
+    // The node's transaction JSON and metadata
+    raw: Json<Value>,
+    meta: Json<Value>,
+}
+
+#[derive(SimpleObject)]
+struct AddressBalance {
+    token_uid: String,
+    name: Option<String>,
+    symbol: Option<String>,
+    received: u64,
+    spent: u64,
+    balance: i64,
+}
+
+#[derive(SimpleObject)]
+struct Address {
+    address: String,
+    total_transactions: u64,
+    balances: Vec<AddressBalance>,
+}
+
+#[derive(SimpleObject)]
+struct Token {
+    uid: String,
+    name: Option<String>,
+    symbol: Option<String>,
+    total: Option<u64>,
+    transactions_count: Option<u64>,
+    can_mint: bool,
+    can_melt: bool,
+    // Wallet that created it through Forge
+    created_by: Option<String>,
+    // The node's thin_wallet/token response, when the node knows the token
+    raw: Option<Json<Value>>,
+}
+
+#[derive(SimpleObject)]
+struct WalletBalance {
+    token_uid: String,
+    name: String,
+    symbol: String,
+    available: u64,
+    locked: u64,
+}
+
+#[derive(SimpleObject)]
+#[graphql(complex)]
+struct Wallet {
+    wallet_id: String,
+    instance: String,
+    label: Option<String>,
+    auto_start: bool,
+    created_at: u64,
+}
+
+#[ComplexObject]
+impl Wallet {
+    // HTR and registered token balances; needs the wallet open on wallet-headless
+    async fn balances(&self, ctx: &Context<'_>) -> async_graphql::Result<Vec<WalletBalance>> {
+        let state = ctx.data::<SharedState>()?;
+        let balances = crate::tokens::get_token_balances_internal(
+            state,
+            &self.wallet_id,
+            Some(&self.instance),
+        )
+        .await?;
+        Ok(balances
+            .into_iter()
+            .map(|balance| WalletBalance {
+                token_uid: balance.token_uid,
+                name: balance.name,
+                symbol: balance.symbol,
+                available: balance.available,
+                locked: balance.locked,
+            })
+            .collect())
+    }
+}
+
+fn strings(value: &Value) -> Vec<String> {
+    value
+        .as_array()
+        .map(|items| {
+            items
+                .iter()
+                .filter_map(|item| item.as_str().map(String::from))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn outputs(tx: &Value) -> Vec<Output> {
+    tx["outputs"]
+        .as_array()
+        .map(|outputs| {
+            outputs
+                .iter()
+                .map(|output| Output {
+                    value: output["value"].as_u64().unwrap_or(0),
+                    token_data: output["token_data"].as_u64().unwrap_or(0),
+                    address: output["decoded"]["address"].as_str().map(String::from),
+                    token: output["token"].as_str().map(String::from),
+                    timelock: output["decoded"]["timelock"].as_u64(),
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+fn block(hash: String, height: Option<u64>, raw: Value) -> Block {
+    Block {
+        hash,
+        height: height.or(raw["height"].as_u64()),
+        timestamp: raw["timestamp"].as_u64(),
+        weight: raw["weight"].as_f64(),
+        parents: strings(&raw["parents"]),
+        outputs: outputs(&raw),
+        raw: Json(raw),
+    }
+}
+
+fn transaction(hash: String, tx: Value, meta: Value) -> Transaction {
+    let inputs = tx["inputs"]
+        .as_array()
+        .map(|inputs| {
+            inputs
+                .iter()
+                .map(|input| Input {
+                    tx_id: input["tx_id"].as_str().unwrap_or_default().to_string(),
+                    index: input["index"].as_u64().unwrap_or(0),
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    Transaction {
+        hash,
+        timestamp: tx["timestamp"].as_u64(),
+        version: tx["version"].as_u64(),
+        weight: tx["weight"].as_f64(),
+        parents: strings(&tx["parents"]),
+        inputs,
+        outputs: outputs(&tx),
+        tokens: tx["tokens"]
+            .as_array()
+            .map(|tokens| {
+                tokens
+                    .iter()
+                    .filter_map(|token| token["uid"].as_str().or(token.as_str()))
+                    .map(String::from)
+                    .collect()
+            })
+            .unwrap_or_default(),
+        first_block: meta["first_block"].as_str().map(String::from),
+        voided: meta["voided_by"]
+            .as_array()
+            .is_some_and(|voided_by| !voided_by.is_empty()),
+        raw: Json(tx),
+        meta: Json(meta),
+    }
+}
+
+async fn node_get(path: &str, query: &[(&str, &str)]) -> Result<Value, String> {
+    reqwest::Client::new()
+        .get(format!("{}/{}", NODE_API_URL, path))
+        .query(query)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to query the node: {}", e))?
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse node response: {}", e))
+}
+
+pub struct QueryRoot;
+
+#[Object]
+impl QueryRoot {
+    /// The node's best block and raw status
+    async fn status(&self) -> async_graphql::Result<NodeStatus> {
+        let status = node_get("status/", &[]).await?;
+        let best = &status["dag"]["best_block"];
+        Ok(NodeStatus {
+            best_block_height: best["height"].as_u64(),
+            best_block_hash: best["hash"].as_str().map(String::from),
+            raw: Json(status),
+        })
+    }
+
+    /// Recent blocks, newest first (count defaults to 10, at most 200)
+    async fn blocks(
+        &self,
+        ctx: &Context<'_>,
+        count: Option<u64>,
+        offset: Option<u64>,
+    ) -> async_graphql::Result<Vec<Block>> {
+        let state = ctx.data::<SharedState>()?;
+        let page = crate::blocks::get_blocks_internal(state, count, offset).await?;
+        Ok(page
+            .blocks
+            .into_iter()
+            .map(|raw| {
+                let hash = raw["tx_id"].as_str().unwrap_or_default().to_string();
+                block(hash, None, raw)
+            })
+            .collect())
+    }
+
+    /// A block by hash or height
+    async fn block(
+        &self,
+        ctx: &Context<'_>,
+        hash: Option<String>,
+        height: Option<u64>,
+    ) -> async_graphql::Result<Option<Block>> {
+        let query = match (hash, height) {
+            (Some(hash), _) => hash,
+            (None, Some(height)) => height.to_string(),
+            (None, None) => return Err("Pass a hash or a height".into()),
+        };
+        let state = ctx.data::<SharedState>()?;
+        match crate::search::search_chain_internal(state, &query).await? {
+            SearchResult::Block {
+                hash,
+                height,
+                block: raw,
+            } => Ok(Some(block(hash, height, raw))),
+            _ => Ok(None),
+        }
+    }
+
+    /// A transaction (or block, with its metadata) by hash
+    async fn transaction(
+        &self,
+        ctx: &Context<'_>,
+        hash: String,
+    ) -> async_graphql::Result<Option<Transaction>> {
+        if hash.len() != 64 || !hash.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err("A transaction hash is 64 hex characters".into());
+        }
+        let state = ctx.data::<SharedState>()?;
+        match crate::search::search_chain_internal(state, &hash).await? {
+            SearchResult::Transaction { hash, tx, meta } => Ok(Some(transaction(hash, tx, meta))),
+            SearchResult::Block { .. } => {
+                let response = node_get("transaction", &[("id", &hash)]).await?;
+                Ok(Some(transaction(
+                    hash,
+                    response["tx"].clone(),
+                    response["meta"].clone(),
+                )))
+            }
+            _ => Ok(None),
+        }
+    }
+
+    /// Balances of an address, per token
+    async fn address(&self, ctx: &Context<'_>, address: String) -> async_graphql::Result<Address> {
+        crate::address::check_address(&address)?;
+        let state = ctx.data::<SharedState>()?;
+        let SearchResult::Address {
+            address,
+            total_transactions,
+            tokens,
+        } = crate::search::search_chain_internal(state, &address).await?
+        else {
+            return Err(format!("{} is not an address", address).into());
+        };
+        let balances = tokens
+            .as_object()
+            .map(|tokens| {
+                tokens
+                    .iter()
+                    .map(|(token_uid, data)| {
+                        let received = data["received"].as_u64().unwrap_or(0);
+                        let spent = data["spent"].as_u64().unwrap_or(0);
+                        AddressBalance {
+                            token_uid: token_uid.clone(),
+                            name: data["name"].as_str().map(String::from),
+                            symbol: data["symbol"].as_str().map(String::from),
+                            received,
+                            spent,
+                            balance: received as i64 - spent as i64,
+                        }
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        Ok(Address {
+            address,
+            total_transactions,
+            balances,
+        })
+    }
+
+    /// Tokens registered in the workspace
+    async fn tokens(&self, ctx: &Context<'_>) -> async_graphql::Result<Vec<Token>> {
+        let state = ctx.data::<SharedState>()?;
+        Ok(crate::tokens::list_tokens_internal(state)
+            .await
+            .into_iter()
+            .map(|token| Token {
+                uid: token.token_uid,
+                name: Some(token.name),
+                symbol: Some(token.symbol),
+                total: None,
+                transactions_count: None,
+                can_mint: false,
+                can_melt: false,
+                created_by: token.created_by,
+                raw: None,
+            })
+            .collect())
+    }
+
+    /// A token as the node knows it, with supply and authorities
+    async fn token(&self, ctx: &Context<'_>, uid: String) -> async_graphql::Result<Option<Token>> {
+        let state = ctx.data::<SharedState>()?;
+        let response = node_get("thin_wallet/token", &[("id", &uid)]).await?;
+        if !response["success"].as_bool().unwrap_or(false) {
+            return Ok(None);
+        }
+        let created_by = crate::tokens::list_tokens_internal(state)
+            .await
+            .into_iter()
+            .find(|token| token.token_uid == uid)
+            .and_then(|token| token.created_by);
+        let has_authority = |key: &str| {
+            response[key]
+                .as_array()
+                .is_some_and(|utxos| !utxos.is_empty())
+        };
+        Ok(Some(Token {
+            name: response["name"].as_str().map(String::from),
+            symbol: response["symbol"].as_str().map(String::from),
+            total: response["total"].as_u64(),
+            transactions_count: response["transactions_count"].as_u64(),
+            can_mint: has_authority("mint"),
+            can_melt: has_authority("melt"),
+            created_by,
+            raw: Some(Json(response)),
+            uid,
+        }))
+    }
+
+    /// Wallets in the registry, optionally on one wallet-headless instance
+    async fn wallets(&self, instance: Option<String>) -> Vec<Wallet> {
+        crate::wallets::list_wallets()
+            .into_iter()
+            .filter(|wallet| {
+                instance
+                    .as_ref()
+                    .is_none_or(|name| *name == wallet.instance)
+            })
+            .map(|wallet| Wallet {
+                wallet_id: wallet.wallet_id,
+                instance: wallet.instance,
+                label: wallet.settings.label,
+                auto_start: wallet.auto_start,
+                created_at: wallet.created_at,
+            })
+            .collect()
+    }
+}
+
+pub type ForgeSchema = Schema<QueryRoot, EmptyMutation, EmptySubscription>;
+
+async fn graphiql() -> Html<String> {
+    Html(GraphiQLSource::build().endpoint("/graphql").finish())
+}
+
+async fn execute(
+    State(schema): State<ForgeSchema>,
+    axum::Json(request): axum::Json<async_graphql::Request>,
+) -> axum::Json<async_graphql::Response> {
+    axum::Json(schema.execute(request).await)
+}
+
+/// The explorer server's /graphql routes
+pub fn router(state: SharedState) -> Router {
+    let schema = Schema::build(QueryRoot, EmptyMutation, EmptySubscription)
+        .data(state)
+        .limit_depth(MAX_DEPTH)
+        .finish();
+    Router::new()
+        .route("/graphql", get(graphiql).post(execute))
+        .with_state(schema)
+}
+
+// ============================================================================
+// Tauri Commands
+// ============================================================================
+
+// Get whether the explorer server serves /graphql
+#[tauri::command]
+pub async fn get_graphql_settings() -> Result<GraphqlSettings, String> {
+    Ok(SETTINGS.load())
+}
+
+// Serve /graphql on the explorer server from its next start
+#[tauri::command]
+pub async fn set_graphql(enabled: bool) -> Result<GraphqlSettings, String> {
+    let settings = GraphqlSettings { enabled };
+    SETTINGS.save(&settings)?;
+    Ok(settings)
+}
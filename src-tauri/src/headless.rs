@@ -0,0 +1,86 @@
+//! Helpers for calling wallet-headless wallet endpoints
+//!
+//! wallet-headless answers with `{"success": false, "message": ...}` (or `error`)
+//! on failure, often with a 200 status, so every response body is checked.
+
+use serde_json::Value;
+
+// Parse a headless response body and turn `success: false` into an error
+fn parse_response(response_text: &str) -> Result<Value, String> {
+    let result: Value = serde_json::from_str(response_text)
+        .map_err(|e| format!("Failed to parse response: {} - Body: {}", e, response_text))?;
+
+    if result["success"].as_bool().unwrap_or(false) {
+        Ok(result)
+    } else {
+        let message = result["message"]
+            .as_str()
+            .or_else(|| result["error"].as_str())
+            .unwrap_or(response_text)
+            .to_string();
+        Err(message)
+    }
+}
+
+/// GET a wallet endpoint (`X-Wallet-Id` header set) and return the parsed body
+pub async fn wallet_get(
+    base_url: &str,
+    wallet_id: &str,
+    path: &str,
+    query: &[(&str, String)],
+) -> Result<Value, String> {
+    let response = reqwest::Client::new()
+        .get(format!("{}{}", base_url, path))
+        .header("X-Wallet-Id", wallet_id)
+        .query(query)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to reach wallet-headless: {}", e))?;
+
+    let response_text = response
+        .text()
+        .await
+        .map_err(|e| format!("Failed to read response: {}", e))?;
+
+    parse_response(&response_text)
+}
+
+/// POST a JSON body to a wallet endpoint and return the parsed body
+pub async fn wallet_post(
+    base_url: &str,
+    wallet_id: &str,
+    path: &str,
+    body: &Value,
+) -> Result<Value, String> {
+    let response = reqwest::Client::new()
+        .post(format!("{}{}", base_url, path))
+        .header("X-Wallet-Id", wallet_id)
+        .json(body)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to reach wallet-headless: {}", e))?;
+
+    let response_text = response
+        .text()
+        .await
+        .map_err(|e| format!("Failed to read response: {}", e))?;
+
+    parse_response(&response_text)
+}
+
+/// POST a JSON body to a non-wallet endpoint (e.g. `/start`, `/multisig-pubkey`)
+pub async fn post(base_url: &str, path: &str, body: &Value) -> Result<Value, String> {
+    let response = reqwest::Client::new()
+        .post(format!("{}{}", base_url, path))
+        .json(body)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to reach wallet-headless: {}", e))?;
+
+    let response_text = response
+        .text()
+        .await
+        .map_err(|e| format!("Failed to read response: {}", e))?;
+
+    parse_response(&response_text)
+}
@@ -0,0 +1,280 @@
+//! `/health` of the MCP and explorer servers
+//!
+//! Both answer `GET /health` with the state of every service Forge manages
+//! (running, PID, port, URL, version), Forge's own version and the workspace's
+//! data directory, so CI jobs and scripts can gate on one endpoint. With
+//! `?require=node,headless` the answer is a 503 unless each listed service is
+//! healthy, which makes `curl --fail` enough to wait for a localnet:
+//!
+//! ```text
+//! until curl -sf 'http://localhost:9876/health?require=node,miner,headless'; do sleep 1; done
+//! ```
+//!
+//! Names for `require` are `node`, `miner` and `headless` (any instance, or
+//! `miner:<name>`/`headless:<name>` for one), `explorer` (the default server,
+//! or `explorer:<name>`), `mcp`, `control-api`, `poa-node` and
+//! `explorer-service`.
+
+use axum::extract::{Query, State};
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::routing::get;
+use axum::{Json, Router};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use crate::SharedState;
+
+// How long the node's API gets to answer the version probe
+const NODE_PROBE_TIMEOUT: Duration = Duration::from_secs(2);
+// How long the MCP server gets to accept the probe connection
+const MCP_PROBE_TIMEOUT: Duration = Duration::from_millis(500);
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ServiceHealth {
+    pub running: bool,
+    // The node answers its API; other services are healthy while running
+    pub healthy: bool,
+    pub pid: Option<u32>,
+    pub port: Option<u16>,
+    pub url: Option<String>,
+    pub version: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HealthReport {
+    // "ok", or "unavailable" when a required service isn't healthy
+    pub status: String,
+    // Forge's version
+    pub version: String,
+    pub data_dir: String,
+    pub app_dir: String,
+    // node, explorer, mcp, control-api, poa-node, explorer-service
+    pub services: BTreeMap<String, ServiceHealth>,
+    // By miner name
+    pub miners: BTreeMap<String, ServiceHealth>,
+    // By wallet-headless instance
+    pub headless: BTreeMap<String, ServiceHealth>,
+    // By explorer server instance (`services.explorer` is the default one)
+    pub explorers: BTreeMap<String, ServiceHealth>,
+    // Required services that aren't healthy
+    pub unavailable: Vec<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct HealthQuery {
+    // Comma-separated services that must be healthy for a 200
+    pub require: Option<String>,
+}
+
+fn running(pid: Option<u32>, port: Option<u16>, url: Option<String>) -> ServiceHealth {
+    ServiceHealth {
+        running: true,
+        healthy: true,
+        pid,
+        port,
+        url,
+        version: None,
+    }
+}
+
+/// State of every service, without the `require` check
+pub async fn report_internal(state: &SharedState) -> HealthReport {
+    let mut services = BTreeMap::new();
+    let mut miners = BTreeMap::new();
+    let mut headless = BTreeMap::new();
+    let mut explorers = BTreeMap::new();
+    let (mut node, data_dir) = {
+        let state_guard = state.lock().await;
+
+        for (name, miner) in &state_guard.miners {
+            miners.insert(name.clone(), running(miner.child_id, None, None));
+        }
+        for (name, instance) in &state_guard.headless_instances {
+            let mut health = running(
+                instance.child_id,
+                Some(instance.port),
+                Some(format!("http://127.0.0.1:{}", instance.port)),
+            );
+            health.version = crate::compat::headless_version(&instance.dist_path);
+            headless.insert(name.clone(), health);
+        }
+
+        for (name, server) in &state_guard.explorer_servers {
+            explorers.insert(
+                name.clone(),
+                running(
+                    None,
+                    Some(server.port),
+                    Some(format!(
+                        "{}://localhost:{}",
+                        crate::tls::scheme(server.tls),
+                        server.port
+                    )),
+                ),
+            );
+        }
+        services.insert(
+            "explorer".to_string(),
+            explorers
+                .get(crate::DEFAULT_EXPLORER)
+                .cloned()
+                .unwrap_or_default(),
+        );
+
+        let control_api = match crate::control_api::url(&state_guard) {
+            Some(url) => running(
+                None,
+                Some(crate::control_api::SETTINGS.load().port),
+                Some(url),
+            ),
+            None => ServiceHealth::default(),
+        };
+        services.insert("control-api".to_string(), control_api);
+
+        let poa_node = match crate::poa::api_port(&state_guard) {
+            Some(port) => running(
+                crate::poa::child_id(&state_guard),
+                Some(port),
+                Some(format!("http://127.0.0.1:{}", port)),
+            ),
+            None => ServiceHealth::default(),
+        };
+        services.insert("poa-node".to_string(), poa_node);
+
+        let explorer_service = match crate::explorer_service::url(&state_guard) {
+            Some(url) => running(
+                crate::explorer_service::child_id(&state_guard),
+                url.rsplit(':').next().and_then(|port| port.parse().ok()),
+                Some(url),
+            ),
+            None => ServiceHealth::default(),
+        };
+        services.insert("explorer-service".to_string(), explorer_service);
+
+        let node = ServiceHealth {
+            running: state_guard.node_running,
+            healthy: false,
+            pid: state_guard.node_child_id,
+            port: state_guard
+                .node_running
+                .then_some(state_guard.node_api_port),
+            url: state_guard
+                .node_running
+                .then(|| format!("http://127.0.0.1:{}", state_guard.node_api_port)),
+            version: None,
+        };
+        (
+            node,
+            crate::workspace_data_dir(&state_guard)
+                .to_string_lossy()
+                .to_string(),
+        )
+    };
+
+    // The node is healthy once its API answers
+    if node.running {
+        node.version = tokio::time::timeout(NODE_PROBE_TIMEOUT, crate::compat::core_version())
+            .await
+            .ok()
+            .flatten();
+        node.healthy = node.version.is_some();
+    }
+    services.insert("node".to_string(), node);
+
+    // The MCP server runs for the app's lifetime unless its port was taken
+    let mcp_up = tokio::time::timeout(
+        MCP_PROBE_TIMEOUT,
+        tokio::net::TcpStream::connect(("127.0.0.1", crate::MCP_SERVER_PORT)),
+    )
+    .await
+    .is_ok_and(|connected| connected.is_ok());
+    let mcp = if mcp_up {
+        running(
+            None,
+            Some(crate::MCP_SERVER_PORT),
+            Some(format!(
+                "{}://localhost:{}/mcp",
+                crate::tls::scheme(crate::tls::enabled()),
+                crate::MCP_SERVER_PORT
+            )),
+        )
+    } else {
+        ServiceHealth::default()
+    };
+    services.insert("mcp".to_string(), mcp);
+
+    HealthReport {
+        status: "ok".to_string(),
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        data_dir,
+        app_dir: crate::get_app_dir().to_string_lossy().to_string(),
+        services,
+        miners,
+        headless,
+        explorers,
+        unavailable: Vec::new(),
+    }
+}
+
+// Whether a `require` entry is healthy; None for unknown names
+fn requirement_met(report: &HealthReport, name: &str) -> Option<bool> {
+    let any_healthy =
+        |instances: &BTreeMap<String, ServiceHealth>| instances.values().any(|s| s.healthy);
+    match name.split_once(':') {
+        Some(("miner", miner)) => Some(report.miners.get(miner).is_some_and(|s| s.healthy)),
+        Some(("headless", instance)) => {
+            Some(report.headless.get(instance).is_some_and(|s| s.healthy))
+        }
+        Some(("explorer", instance)) => {
+            Some(report.explorers.get(instance).is_some_and(|s| s.healthy))
+        }
+        Some(_) => None,
+        None => match name {
+            "miner" => Some(any_healthy(&report.miners)),
+            "headless" => Some(any_healthy(&report.headless)),
+            _ => report.services.get(name).map(|s| s.healthy),
+        },
+    }
+}
+
+/// `GET /health`: the report, as a 503 when a required service isn't healthy
+/// (400 for unknown names)
+pub async fn health_response(state: &SharedState, query: HealthQuery) -> Response {
+    let mut report = report_internal(state).await;
+    let required = query.require.unwrap_or_default();
+    for name in required.split(',').map(str::trim).filter(|n| !n.is_empty()) {
+        match requirement_met(&report, name) {
+            Some(true) => {}
+            Some(false) => report.unavailable.push(name.to_string()),
+            None => {
+                return (
+                    StatusCode::BAD_REQUEST,
+                    Json(serde_json::json!({
+                        "success": false,
+                        "message": format!("Unknown service '{}' in require", name),
+                    })),
+                )
+                    .into_response()
+            }
+        }
+    }
+    if report.unavailable.is_empty() {
+        (StatusCode::OK, Json(report)).into_response()
+    } else {
+        report.status = "unavailable".to_string();
+        (StatusCode::SERVICE_UNAVAILABLE, Json(report)).into_response()
+    }
+}
+
+async fn health(State(state): State<SharedState>, Query(query): Query<HealthQuery>) -> Response {
+    health_response(&state, query).await
+}
+
+/// `/health` for the explorer server
+pub fn router(state: SharedState) -> Router {
+    Router::new()
+        .route("/health", get(health))
+        .with_state(state)
+}
@@ -0,0 +1,197 @@
+//! Sharing the explorer and node proxy on the LAN
+//!
+//! The explorer server (port 3001) also proxies the node's API and WebSocket
+//! under `/v1a` and wallet-headless under `/headless`, so binding it on 0.0.0.0 lets mobile wallets and teammates'
+//! machines use the localnet through Forge. It is off by default; the setting
+//! lives in `lan-share.json` in the app directory and applies whenever the
+//! explorer server starts (a running server keeps its binding until it is
+//! restarted). While shared, the environment is also advertised over mDNS
+//! (see mdns.rs), and `get_connection_qr` renders the custom network settings
+//! a wallet needs to use it.
+
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::net::{IpAddr, Ipv4Addr};
+
+use crate::settings::JsonSettings;
+use crate::{mdns, qr, stratum_lan, tls, SharedState};
+
+pub const EXPLORER_PORT: u16 = 3001;
+pub const NETWORK_NAME: &str = "privatenet";
+
+const LAN_WARNING: &str = "The explorer, the node API and wallet-headless are reachable by \
+every machine on your network, unauthenticated. Anyone who can connect can read the chain, \
+push transactions to this localnet and spend from the started wallets; only share on \
+networks you trust.";
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct LanShareSettings {
+    #[serde(default)]
+    enabled: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LanShareInfo {
+    pub enabled: bool,
+    pub explorer_running: bool,
+    // The running explorer server is bound on 0.0.0.0
+    pub shared: bool,
+    // The setting differs from the running server's binding
+    pub restart_required: bool,
+    pub local_url: String,
+    // Explorer, node API and wallet-headless URLs for other machines (while
+    // shared)
+    pub lan_url: Option<String>,
+    pub lan_node_url: Option<String>,
+    pub lan_headless_url: Option<String>,
+    pub lan_ip: Option<String>,
+    // The mDNS service advertised while shared
+    pub mdns: Option<mdns::MdnsInfo>,
+    pub warning: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConnectionQr {
+    pub node_url: String,
+    pub explorer_url: String,
+    pub network: String,
+    // The JSON encoded in the QR code
+    pub payload: String,
+    pub qr_png_base64: String,
+}
+
+static SETTINGS: JsonSettings<LanShareSettings> =
+    JsonSettings::new("lan-share.json", "LAN share settings");
+
+/// Address the explorer server binds on
+pub fn bind_ip() -> IpAddr {
+    if SETTINGS.load().enabled {
+        IpAddr::V4(Ipv4Addr::UNSPECIFIED)
+    } else {
+        IpAddr::V4(Ipv4Addr::LOCALHOST)
+    }
+}
+
+/// The warning shown whenever the explorer is shared
+pub fn warning() -> &'static str {
+    LAN_WARNING
+}
+
+/// Advertise the shared environment over mDNS; failures only cost discovery
+pub fn advertise(tls: bool) -> Option<mdns::Advertisement> {
+    let lan_ip = stratum_lan::lan_ip()?;
+    match mdns::advertise(&lan_ip, tls::scheme(tls)) {
+        Ok(advertisement) => Some(advertisement),
+        Err(e) => {
+            eprintln!("{}", e);
+            None
+        }
+    }
+}
+
+/// Whether sharing is on, and how the explorer and node API can be reached
+pub async fn get_lan_share_info_internal(state: &SharedState) -> LanShareInfo {
+    let enabled = SETTINGS.load().enabled;
+    let (explorer_running, shared, scheme, mdns) = {
+        let state_guard = state.lock().await;
+        let server = crate::default_explorer(&state_guard);
+        (
+            server.is_some(),
+            server.is_some_and(|server| server.shared_on_lan),
+            // The running server's, or the one the next start will use
+            tls::scheme(server.map_or_else(tls::enabled, |server| server.tls)),
+            server
+                .and_then(|server| server.mdns_advertisement.as_ref())
+                .map(|advertisement| advertisement.info().clone()),
+        )
+    };
+    let lan_ip = stratum_lan::lan_ip();
+    let lan_base = lan_ip
+        .as_ref()
+        .filter(|_| shared)
+        .map(|ip| format!("{}://{}:{}", scheme, ip, EXPLORER_PORT));
+
+    LanShareInfo {
+        enabled,
+        explorer_running,
+        shared,
+        restart_required: explorer_running && shared != enabled,
+        local_url: format!("{}://localhost:{}", scheme, EXPLORER_PORT),
+        lan_node_url: lan_base.as_ref().map(|base| format!("{}/v1a/", base)),
+        lan_headless_url: lan_base.as_ref().map(|base| format!("{}/headless/", base)),
+        lan_url: lan_base,
+        lan_ip,
+        mdns,
+        warning: enabled.then(|| LAN_WARNING.to_string()),
+    }
+}
+
+/// QR code with the custom network settings (node URL and network name, in
+/// the keys the Hathor wallets use) for reaching the shared localnet
+pub async fn get_connection_qr_internal(
+    state: &SharedState,
+    size: Option<u32>,
+) -> Result<ConnectionQr, String> {
+    let size = qr::check_size(size)?;
+    let info = get_lan_share_info_internal(state).await;
+    let (Some(node_url), Some(explorer_url)) = (info.lan_node_url, info.lan_url) else {
+        return Err(if info.lan_ip.is_none() {
+            "No LAN address found; connect this machine to a network".to_string()
+        } else {
+            "Share the explorer on the LAN and start the network first".to_string()
+        });
+    };
+
+    let payload = json!({
+        "network": NETWORK_NAME,
+        "nodeUrl": node_url,
+        "explorerUrl": explorer_url,
+    })
+    .to_string();
+    Ok(ConnectionQr {
+        qr_png_base64: qr::png_base64(&payload, size)?,
+        node_url,
+        explorer_url,
+        network: NETWORK_NAME.to_string(),
+        payload,
+    })
+}
+
+/// Turn LAN sharing on or off from the next explorer server start
+pub async fn set_lan_share_internal(
+    state: &SharedState,
+    enabled: bool,
+) -> Result<LanShareInfo, String> {
+    SETTINGS.save(&LanShareSettings { enabled })?;
+    Ok(get_lan_share_info_internal(state).await)
+}
+
+// ============================================================================
+// Tauri Commands
+// ============================================================================
+
+// Get the LAN sharing setting and the explorer/node URLs for other machines
+#[tauri::command]
+pub async fn get_lan_share_info(
+    state: tauri::State<'_, SharedState>,
+) -> Result<LanShareInfo, String> {
+    Ok(get_lan_share_info_internal(&state).await)
+}
+
+// Render a QR code wallets scan to use the shared localnet as a custom network
+#[tauri::command]
+pub async fn get_connection_qr(
+    state: tauri::State<'_, SharedState>,
+    size: Option<u32>,
+) -> Result<ConnectionQr, String> {
+    get_connection_qr_internal(&state, size).await
+}
+
+// Share the explorer and node proxy on the LAN (or stop doing so)
+#[tauri::command]
+pub async fn set_lan_share(
+    state: tauri::State<'_, SharedState>,
+    enabled: bool,
+) -> Result<LanShareInfo, String> {
+    set_lan_share_internal(&state, enabled).await
+}
@@ -1,11 +1,5 @@
-use axum::body::Body;
-use axum::extract::ws::{Message, WebSocket, WebSocketUpgrade};
-use axum::extract::{Path, Request};
-use axum::response::{IntoResponse, Response};
-use axum::routing::{any, get};
-use axum::Router;
-use futures_util::{SinkExt, StreamExt};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::fs;
 use std::net::SocketAddr;
 use std::process::Stdio;
@@ -14,22 +8,189 @@ use tauri::Emitter;
 use tokio::io::{AsyncBufReadExt, BufReader};
 use tokio::process::Command as TokioCommand;
 use tokio::sync::Mutex;
-use tokio_tungstenite::tungstenite;
-use tower_http::cors::{Any, CorsLayer};
-use tower_http::services::ServeDir;
-
+use tower_http::compression::CompressionLayer;
+use tower_http::trace::TraceLayer;
+use tracing::Instrument;
+
+use crate::amount::HtrAmount;
+
+mod access_log;
+mod address;
+mod amount;
+mod auto_miner;
+mod backup;
+mod balances;
+mod benchmark;
+mod blocks;
+mod bootstrap;
+mod coinbase_rotation;
+mod compat;
+mod confirmations;
+mod control_api;
+mod cors;
+mod custom_miner;
+mod data_usage;
+mod event_bus;
+mod events;
+mod explorer_dist;
+mod explorer_service;
+mod faucet;
+mod features;
+mod fixtures;
+mod graphql;
+mod headless;
+mod health;
+mod lan_share;
+mod loadgen;
+mod logging;
 mod mcp;
+mod mdns;
+mod mempool;
+mod miner_metrics;
+mod miner_options;
+mod mining_info;
+mod mining_report;
+mod mock;
+mod multisig;
+mod nano;
+mod network_chaos;
+mod notifications;
+mod paced_mining;
+mod payments;
+mod peers;
+mod poa;
+mod proxy;
+mod qr;
+mod rate_limit;
+mod recorder;
+mod reorg;
+mod rewards;
+mod scenario;
+mod search;
+mod secrets;
+mod settings;
+mod snapshot_schedule;
+mod snapshots;
+mod stratum_lan;
+mod stratum_tap;
+mod subscriptions;
+mod swap;
+mod telemetry;
+mod time_travel;
+mod tls;
+mod tokens;
+mod traffic;
+mod transactions;
+mod wallet_sync;
+mod wallets;
+
+// Name of the wallet-headless instance used when none is specified
+const DEFAULT_HEADLESS_INSTANCE: &str = "default";
+
+// Name of the miner used when none is specified
+const DEFAULT_MINER: &str = "default";
+
+// Name of the explorer server used when none is specified: the one on port
+// 3001 that LAN sharing and mDNS apply to
+const DEFAULT_EXPLORER: &str = "default";
+
+// Seed of the fullnode's built-in HD wallet (the faucet); dev-only, never holds real funds
+const DEV_WALLET_WORDS: &str = "avocado spot town typical traffic vault danger century property shallow divorce festival spend attack anchor afford rotate green audit adjust fade wagon depart level";
+
+// Addresses wallet-headless derives past the last used one
+const HEADLESS_GAP_LIMIT: u32 = 20;
+
+// A running wallet-headless instance
+pub struct HeadlessInstance {
+    port: u16,
+    child_id: Option<u32>,
+    dist_path: std::path::PathBuf,
+    // Multisig definitions written to this instance's config.js
+    multisig_configs: Vec<String>,
+}
+
+// A running cpuminer process
+pub struct MinerInstance {
+    child_id: Option<u32>,
+    address: String,
+    threads: u32,
+    // "cpuminer" or "custom miner"
+    label: &'static str,
+}
+
+// A running explorer HTTP server
+pub struct ExplorerServer {
+    port: u16,
+    shutdown: Option<tokio::sync::oneshot::Sender<()>>,
+    // Bound on 0.0.0.0 (see lan_share.rs)
+    shared_on_lan: bool,
+    // Serves HTTPS (see tls.rs)
+    tls: bool,
+    // Node its proxy forwards to; None follows `proxy_target`
+    target: Option<proxy::ProxyTarget>,
+    // mDNS service advertised while shared
+    mdns_advertisement: Option<mdns::Advertisement>,
+}
 
 // Application state
 pub struct AppState {
     node_running: bool,
-    miner_running: bool,
-    explorer_server_running: bool,
-    headless_running: bool,
+    // Running explorer servers, by instance name
+    explorer_servers: HashMap<String, ExplorerServer>,
+    // Recent requests of the explorer and MCP servers
+    access_log: access_log::SharedAccessLog,
+    // Recent output of the node, miners, wallet-headless and other processes
+    service_logs: logging::SharedServiceLogs,
+    // Everything Forge reports, for the control API's /events
+    event_bus: event_bus::EventBus,
+    // The REST control API server
+    control_api: control_api::ControlApi,
+    // Rate limits of the explorer server's /faucet endpoint
+    faucet_limits: faucet::SharedFaucetLimits,
+    // Per-IP request rates of the proxy, faucet and control API
+    rate_limits: rate_limit::SharedRateLimits,
+    // Proxied HTTP exchanges captured for HAR export
+    traffic_recording: traffic::SharedTrafficRecording,
+    // Request counters of the explorer server's node proxy
+    proxy_metrics: proxy::SharedProxyMetrics,
+    // Node the explorer server's proxy forwards to
+    proxy_target: proxy::ProxyTarget,
     node_child_id: Option<u32>,
-    miner_child_id: Option<u32>,
-    headless_child_id: Option<u32>,
-    explorer_shutdown: Option<tokio::sync::oneshot::Sender<()>>,
+    // API port of the running (or last started) node
+    node_api_port: u16,
+    // The node was started under libfaketime (see time_travel.rs)
+    node_clock_mocked: bool,
+    miners: HashMap<String, MinerInstance>,
+    // Metrics of each miner's current or last session, by miner name
+    miner_metrics: HashMap<String, miner_metrics::MinerMetrics>,
+    paced_mining: Option<paced_mining::PacedMining>,
+    // Blocks found by Forge's miners, published by the session trackers
+    block_found: tokio::sync::broadcast::Sender<miner_metrics::BlockFound>,
+    // Typed events from the node's event queue, and the most recent ones
+    node_events: events::NodeEvents,
+    // First-seen times of mempool transactions
+    mempool: mempool::MempoolTracker,
+    // Recently fetched blocks, by hash and height
+    block_cache: blocks::BlockCache,
+    // Addresses the UI gets address-activity events for
+    address_subscriptions: subscriptions::AddressSubscriptions,
+    // Transactions the UI gets watched-tx-voided events for
+    watched_txs: std::collections::BTreeSet<String>,
+    headless_instances: HashMap<String, HeadlessInstance>,
+    // Shuts down the LAN stratum relay
+    stratum_relay: Option<tokio::sync::oneshot::Sender<()>>,
+    // Shuts down the local stratum tap
+    stratum_tap: Option<tokio::sync::oneshot::Sender<()>>,
+    // Recent jobs and submissions seen by the tap
+    stratum_log: stratum_tap::StratumLog,
+    // Pause/latency/partition conditions and the node's peer links
+    network_chaos: network_chaos::NetworkChaos,
+    // Stats channel and stop handle of the transaction load generator
+    loadgen: loadgen::LoadGen,
+    // Proof-of-Authority side-DAG node
+    poa_node: poa::PoaNode,
+    // explorer-service, proxied under /explorer-api
+    explorer_service: explorer_service::ExplorerService,
     data_dir: Option<String>,
 }
 
@@ -37,13 +198,36 @@ impl Default for AppState {
     fn default() -> Self {
         Self {
             node_running: false,
-            miner_running: false,
-            explorer_server_running: false,
-            headless_running: false,
+            explorer_servers: HashMap::new(),
+            access_log: access_log::SharedAccessLog::default(),
+            service_logs: logging::SharedServiceLogs::default(),
+            event_bus: tokio::sync::broadcast::channel(event_bus::CAPACITY).0,
+            control_api: control_api::ControlApi::default(),
+            faucet_limits: faucet::SharedFaucetLimits::default(),
+            rate_limits: rate_limit::SharedRateLimits::default(),
+            traffic_recording: traffic::SharedTrafficRecording::default(),
+            proxy_metrics: proxy::SharedProxyMetrics::default(),
+            proxy_target: proxy::stored_target(),
             node_child_id: None,
-            miner_child_id: None,
-            headless_child_id: None,
-            explorer_shutdown: None,
+            node_api_port: NodeConfig::default().api_port,
+            node_clock_mocked: false,
+            miners: HashMap::new(),
+            miner_metrics: HashMap::new(),
+            paced_mining: None,
+            block_found: tokio::sync::broadcast::channel(BLOCK_FOUND_CAPACITY).0,
+            node_events: events::NodeEvents::default(),
+            mempool: mempool::MempoolTracker::default(),
+            block_cache: blocks::BlockCache::default(),
+            address_subscriptions: subscriptions::AddressSubscriptions::default(),
+            watched_txs: std::collections::BTreeSet::new(),
+            headless_instances: HashMap::new(),
+            stratum_relay: None,
+            stratum_tap: None,
+            stratum_log: stratum_tap::StratumLog::default(),
+            network_chaos: network_chaos::NetworkChaos::default(),
+            loadgen: loadgen::LoadGen::default(),
+            poa_node: poa::PoaNode::default(),
+            explorer_service: explorer_service::ExplorerService::default(),
             data_dir: None,
         }
     }
@@ -51,11 +235,20 @@ impl Default for AppState {
 
 type SharedState = Arc<Mutex<AppState>>;
 
+// Found blocks buffered for slow subscribers before the oldest are dropped
+const BLOCK_FOUND_CAPACITY: usize = 64;
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct NodeConfig {
     pub api_port: u16,
     pub stratum_port: u16,
     pub data_dir: String,
+    // Features forced on (e.g. NANO_CONTRACTS); None keeps the stored list
+    #[serde(default)]
+    pub features: Option<Vec<String>>,
+    // Start with nano contracts enabled; None keeps the stored setting
+    #[serde(default)]
+    pub nano_contracts: Option<bool>,
 }
 
 impl Default for NodeConfig {
@@ -69,31 +262,81 @@ impl Default for NodeConfig {
             api_port: 8080,
             stratum_port: 8000,
             data_dir: data_dir.to_string_lossy().to_string(),
+            features: None,
+            nano_contracts: None,
         }
     }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+fn default_miner_name() -> String {
+    DEFAULT_MINER.to_string()
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MinerConfig {
+    // Miners run side by side under distinct names
+    #[serde(default = "default_miner_name")]
+    pub name: String,
     pub stratum_port: u16,
     pub address: String,
     pub threads: u32,
+    // Stop mining automatically once the best block reaches this height
+    #[serde(default)]
+    pub stop_at_height: Option<u64>,
+    // Stop mining automatically once the faucet's spendable balance reaches this amount
+    #[serde(default)]
+    pub stop_at_faucet_balance: Option<HtrAmount>,
+    // Algorithm, retries and extra cpuminer flags (the stored options when omitted)
+    #[serde(default)]
+    pub options: Option<miner_options::MinerOptions>,
+    // Run cpuminer at the lowest OS priority so the machine stays responsive
+    #[serde(default)]
+    pub low_priority: bool,
+    // Use at most one thread fewer than the physical cores
+    #[serde(default)]
+    pub cap_threads: bool,
+    // Run this binary and argument template instead of the bundled cpuminer
+    #[serde(default)]
+    pub custom_miner: Option<custom_miner::CustomMiner>,
+    // Cycle the coinbase address through the first N node wallet addresses, one per block
+    #[serde(default)]
+    pub rotate_coinbase: Option<u32>,
 }
 
 impl Default for MinerConfig {
     fn default() -> Self {
         Self {
+            name: default_miner_name(),
             stratum_port: 8000,
             address: "WXkMhVgRVmTXTVh47wauPKm1xcrW8Qf3Vb".to_string(), // Default localnet address (from HD wallet)
             threads: 1,
+            stop_at_height: None,
+            stop_at_faucet_balance: None,
+            options: None,
+            low_priority: false,
+            cap_threads: false,
+            custom_miner: None,
+            rotate_coinbase: None,
         }
     }
 }
 
+fn default_headless_instance() -> String {
+    DEFAULT_HEADLESS_INSTANCE.to_string()
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct HeadlessConfig {
     pub port: u16,
     pub fullnode_url: String,
+    #[serde(default = "default_headless_instance")]
+    pub instance: String,
+    // Custom wallet-headless-dist directory (defaults to the bundled one)
+    #[serde(default)]
+    pub dist_path: Option<String>,
+    // Start even when the node and wallet-headless versions are known to be incompatible
+    #[serde(default)]
+    pub ignore_compatibility: bool,
 }
 
 impl Default for HeadlessConfig {
@@ -101,6 +344,9 @@ impl Default for HeadlessConfig {
         Self {
             port: 8001,
             fullnode_url: "http://localhost:8080/v1a/".to_string(),
+            instance: default_headless_instance(),
+            dist_path: None,
+            ignore_compatibility: false,
         }
     }
 }
@@ -110,13 +356,30 @@ pub struct NodeStatus {
     pub running: bool,
     pub block_height: Option<u64>,
     pub hash_rate: Option<f64>,
+    // Connected peers (handshaking and connecting ones aren't counted)
     pub peer_count: Option<u32>,
+    pub version: Option<String>,
+    pub network: Option<String>,
+    pub uptime_secs: Option<f64>,
+    // The node's own state, e.g. "READY" once it is synced and serving
+    pub sync_state: Option<String>,
+    pub best_block_hash: Option<String>,
+    pub best_block_timestamp: Option<u64>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct MinerStatus {
+    pub name: String,
     pub running: bool,
+    pub pid: Option<u32>,
+    pub address: Option<String>,
+    pub threads: Option<u32>,
+    // "cpuminer" or "custom miner"
+    pub label: Option<String>,
     pub hash_rate: Option<f64>,
+    pub average_hash_rate: Option<f64>,
+    pub accepted_shares: u64,
+    pub rejected_shares: u64,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -125,23 +388,58 @@ pub struct HeadlessStatus {
     pub port: Option<u16>,
 }
 
+#[derive(Debug, Serialize, Deserialize)]
+pub struct HeadlessInstanceStatus {
+    pub instance: String,
+    pub port: u16,
+    pub pid: Option<u32>,
+    pub dist_path: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ExplorerServerStatus {
+    pub instance: String,
+    pub port: u16,
+    pub url: String,
+    pub tls: bool,
+    pub shared_on_lan: bool,
+    // Its own upstream, when it doesn't follow the proxy setting
+    pub target: Option<proxy::ProxyTarget>,
+    // Base URL its proxy currently forwards to
+    pub upstream: Option<String>,
+}
+
+// The default explorer server, if it runs
+fn default_explorer(state: &AppState) -> Option<&ExplorerServer> {
+    state.explorer_servers.get(DEFAULT_EXPLORER)
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct WalletAddress {
     pub address: String,
     pub index: u32,
-    pub balance: Option<u64>, // Balance in HTR cents (1 HTR = 100 cents)
+    pub balance: Option<HtrAmount>,
+    // Has at least one transaction
+    #[serde(default)]
+    pub used: bool,
+    // The address the node wallet currently hands out
+    #[serde(default)]
+    pub current: bool,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct SendTxRequest {
     pub address: String,
-    pub amount: u64, // Amount in HTR cents
+    pub amount: HtrAmount,
+    // Unix timestamp until which the output can't be spent
+    #[serde(default)]
+    pub timelock: Option<u64>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct FullnodeBalance {
-    pub available: i64,
-    pub locked: i64,
+    pub available: HtrAmount,
+    pub locked: HtrAmount,
 }
 
 // Headless wallet structures
@@ -156,19 +454,26 @@ pub struct HeadlessWallet {
 pub struct CreateHeadlessWalletRequest {
     pub wallet_id: String,
     pub seed: String,
+    #[serde(default)]
+    pub instance: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct HeadlessWalletBalance {
-    pub available: u64,
-    pub locked: u64,
+    pub available: HtrAmount,
+    pub locked: HtrAmount,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct HeadlessWalletSendTxRequest {
     pub wallet_id: String,
     pub address: String,
-    pub amount: u64,
+    pub amount: HtrAmount,
+    // Unix timestamp until which the output can't be spent
+    #[serde(default)]
+    pub timelock: Option<u64>,
+    #[serde(default)]
+    pub instance: Option<String>,
 }
 
 // Get the path to a binary (handles dev vs production)
@@ -247,14 +552,67 @@ fn get_headless_dist_path() -> std::path::PathBuf {
     std::path::PathBuf::from("wallet-headless-dist")
 }
 
-// Generate wallet-headless config file in the dist directory
+// Resolve the dist directory for a headless config (custom path or bundled dist)
+fn resolve_headless_dist_path(config: &HeadlessConfig) -> std::path::PathBuf {
+    config
+        .dist_path
+        .as_ref()
+        .map(std::path::PathBuf::from)
+        .unwrap_or_else(get_headless_dist_path)
+}
+
+// Check that a new headless instance doesn't collide with running ones.
+// Instances can't share a dist dir because each one needs its own config.js there.
+fn check_headless_conflicts(
+    state: &AppState,
+    config: &HeadlessConfig,
+    dist_path: &std::path::Path,
+) -> Result<(), String> {
+    for (name, instance) in state.headless_instances.iter() {
+        if instance.port == config.port {
+            return Err(format!(
+                "Port {} is already used by wallet-headless instance '{}'",
+                config.port, name
+            ));
+        }
+        if instance.dist_path == dist_path {
+            return Err(format!(
+                "Wallet-headless instance '{}' already uses {:?}. Give each instance its own dist_path.",
+                name, dist_path
+            ));
+        }
+    }
+    Ok(())
+}
+
+// Resolve the base URL of a running wallet-headless instance
+async fn headless_url(state: &SharedState, instance: Option<&str>) -> Result<String, String> {
+    let name = instance.unwrap_or(DEFAULT_HEADLESS_INSTANCE);
+    let state_guard = state.lock().await;
+
+    match state_guard.headless_instances.get(name) {
+        Some(instance) => Ok(format!("http://localhost:{}", instance.port)),
+        None if name == DEFAULT_HEADLESS_INSTANCE => {
+            Err("Wallet-headless is not running".to_string())
+        }
+        None => Err(format!(
+            "Wallet-headless instance '{}' is not running",
+            name
+        )),
+    }
+}
+
+// Generate wallet-headless config file in the dist directory.
+// Returns the names of the multisig definitions included in it.
 fn generate_headless_config(
     config: &HeadlessConfig,
     headless_dist_path: &std::path::Path,
-) -> Result<(), String> {
+) -> Result<Vec<String>, String> {
     // wallet-headless expects config.js in the dist directory (hardcoded as ./config.js)
     let config_path = headless_dist_path.join("dist").join("config.js");
 
+    let multisig_configs = multisig::load_configs();
+
     // Generate config.js for wallet-headless
     // txMiningUrl is required for privatenet - point to local fullnode's mining endpoint
     let config_content = format!(
@@ -265,20 +623,24 @@ fn generate_headless_config(
   server: '{}',
   txMiningUrl: 'http://localhost:8080/v1a/',
   seeds: {{}},
+  multisig: {},
   allowPassphrase: false,
   confirmFirstAddress: false,
   tokenUid: '00',
-  gapLimit: 20,
+  gapLimit: {},
   connectionTimeout: 5000,
 }}
 "#,
-        config.port, config.fullnode_url
+        config.port,
+        config.fullnode_url,
+        multisig::headless_config_section(&multisig_configs),
+        HEADLESS_GAP_LIMIT
     );
 
     fs::write(&config_path, config_content)
         .map_err(|e| format!("Failed to write headless config: {}", e))?;
 
-    Ok(())
+    Ok(multisig_configs.into_keys().collect())
 }
 
 // Kill any process using a specific port (cleanup from previous runs)
@@ -324,6 +686,7 @@ fn kill_process_on_port(port: u16) {
 // ============================================================================
 
 /// Start the Hathor fullnode (internal version without Tauri AppHandle)
+#[tracing::instrument(skip_all)]
 pub async fn start_node_internal(state: &SharedState) -> Result<String, String> {
     let config = NodeConfig::default();
     let state_guard = state.lock().await;
@@ -349,20 +712,23 @@ pub async fn start_node_internal(state: &SharedState) -> Result<String, String>
     // Ensure data directory exists
     fs::create_dir_all(&config.data_dir)
         .map_err(|e| format!("Failed to create data directory: {}", e))?;
-
-    // Development HD wallet seed (DO NOT use in production!)
-    let dev_wallet_words = "avocado spot town typical traffic vault danger century property shallow divorce festival spend attack anchor afford rotate green audit adjust fade wagon depart level";
+    logging::set_workspace(std::path::Path::new(&config.data_dir));
+    wallets::set_workspace(std::path::Path::new(&config.data_dir));
 
     // Set platform-specific library path for bundled libraries
     let internal_dir = binary_path.parent().unwrap().join("_internal");
 
+    // Run on the localnet clock once time travel has been used
+    let clock_env = time_travel::clock_env();
+    // --localnet, or settings extending it with forced features
+    let network_args = features::network_args(&config)?;
+
     // Spawn the process using tokio
     let mut cmd = TokioCommand::new(&binary_path);
     set_library_path_env(&mut cmd, &internal_dir);
     let mut child = cmd
         .args([
             "run_node",
-            "--localnet",
             "--status",
             &config.api_port.to_string(),
             "--stratum",
@@ -372,14 +738,18 @@ pub async fn start_node_internal(state: &SharedState) -> Result<String, String>
             "--wallet",
             "hd",
             "--words",
-            dev_wallet_words,
+            DEV_WALLET_WORDS,
             "--wallet-enable-api",
             "--wallet-index",
             "--allow-mining-without-peers",
             "--test-mode-tx-weight",
+            "--enable-event-queue",
             "--unsafe-mode",
             "privatenet",
         ])
+        .args(&network_args)
+        .args(peers::node_args(&mut state_guard))
+        .envs(clock_env.clone())
         .stdin(Stdio::null())
         .stdout(Stdio::piped())
         .stderr(Stdio::piped())
@@ -389,7 +759,28 @@ pub async fn start_node_internal(state: &SharedState) -> Result<String, String>
     let pid = child.id().unwrap_or(0);
     state_guard.node_running = true;
     state_guard.node_child_id = Some(pid);
+    state_guard.node_clock_mocked = !clock_env.is_empty();
     state_guard.data_dir = Some(config.data_dir.clone());
+    state_guard.node_api_port = config.api_port;
+
+    // Relay stratum to the LAN when that's enabled, and tap Forge's own miners
+    let relay_state = state.clone();
+    tokio::spawn(async move {
+        if let Err(e) = stratum_tap::start_tap(relay_state.clone()).await {
+            eprintln!("{}", e);
+        }
+        if let Err(e) = stratum_lan::start_relay(relay_state).await {
+            eprintln!("{}", e);
+        }
+    });
+
+    // Start mining once the node is ready when auto-start is on
+    let auto_miner_state = state.clone();
+    tokio::spawn(async move {
+        if let Err(e) = auto_miner::start_when_ready(&auto_miner_state).await {
+            eprintln!("Failed to auto-start miner: {}", e);
+        }
+    });
 
     // Consume stdout/stderr in background tasks to prevent pipe buffer issues
     let stdout = child.stdout.take();
@@ -428,21 +819,22 @@ pub async fn start_node_internal(state: &SharedState) -> Result<String, String>
 }
 
 /// Stop the Hathor fullnode (internal version)
+#[tracing::instrument(skip_all)]
 pub async fn stop_node_internal(state: &SharedState) -> Result<String, String> {
     let mut state_guard = state.lock().await;
 
-    // First stop miner if running
-    if let Some(pid) = state_guard.miner_child_id {
-        kill_process(pid);
-        state_guard.miner_running = false;
-        state_guard.miner_child_id = None;
+    // First stop all miners
+    for (_, miner) in state_guard.miners.drain() {
+        if let Some(pid) = miner.child_id {
+            kill_process(pid);
+        }
     }
 
-    // Stop headless if running
-    if let Some(pid) = state_guard.headless_child_id {
-        kill_process(pid);
-        state_guard.headless_running = false;
-        state_guard.headless_child_id = None;
+    // Stop all headless instances
+    for (_, instance) in state_guard.headless_instances.drain() {
+        if let Some(pid) = instance.child_id {
+            kill_process(pid);
+        }
     }
 
     // Stop node
@@ -456,51 +848,154 @@ pub async fn stop_node_internal(state: &SharedState) -> Result<String, String> {
 
     state_guard.node_running = false;
     state_guard.node_child_id = None;
+    if let Some(shutdown_tx) = state_guard.stratum_relay.take() {
+        let _ = shutdown_tx.send(());
+    }
+    if let Some(shutdown_tx) = state_guard.stratum_tap.take() {
+        let _ = shutdown_tx.send(());
+    }
+    state_guard.network_chaos.close_links();
 
     Ok("Node stopped".to_string())
 }
 
+// Thread count for a miner config, capped to physical cores minus one if requested
+fn miner_threads(config: &MinerConfig) -> u32 {
+    if !config.cap_threads {
+        return config.threads;
+    }
+    let max_threads = num_cpus::get_physical().saturating_sub(1).max(1);
+    config.threads.min(max_threads as u32)
+}
+
+// cpuminer command line for a miner config, connecting to `stratum_port`
+fn miner_args(config: &MinerConfig, stratum_port: u16) -> Result<Vec<String>, String> {
+    let options = config
+        .options
+        .clone()
+        .unwrap_or_else(miner_options::load_options);
+    options.validate()?;
+
+    let mut args = options.args();
+    args.extend([
+        "--url".to_string(),
+        format!("stratum+tcp://127.0.0.1:{}", stratum_port),
+        "--coinbase-addr".to_string(),
+        config.address.clone(),
+        "--threads".to_string(),
+        miner_threads(config).to_string(),
+    ]);
+    Ok(args)
+}
+
+// How a miner's process is referred to in status and errors
+fn miner_label(config: &MinerConfig) -> &'static str {
+    match config.custom_miner {
+        Some(_) => custom_miner::CUSTOM_MINER_LABEL,
+        None => "cpuminer",
+    }
+}
+
+// Executable for a miner config: the custom miner's, or the bundled cpuminer
+fn miner_binary(config: &MinerConfig) -> Result<std::path::PathBuf, String> {
+    match &config.custom_miner {
+        Some(custom) => custom.binary_path(),
+        None => Ok(get_binary_path("cpuminer")),
+    }
+}
+
+// Miner process for a miner config connecting to `stratum_port`, at idle
+// priority in low-priority mode
+fn miner_command(
+    binary_path: &std::path::Path,
+    config: &MinerConfig,
+    stratum_port: u16,
+) -> Result<TokioCommand, String> {
+    let args = match &config.custom_miner {
+        Some(custom) => custom.args(stratum_port, &config.address, miner_threads(config))?,
+        None => miner_args(config, stratum_port)?,
+    };
+
+    #[cfg(unix)]
+    let mut command = if config.low_priority {
+        // nice execs the miner, so the spawned pid is still the miner's
+        let mut command = TokioCommand::new("nice");
+        command.args(["-n", "19"]).arg(binary_path);
+        command
+    } else {
+        TokioCommand::new(binary_path)
+    };
+
+    #[cfg(windows)]
+    let mut command = {
+        const IDLE_PRIORITY_CLASS: u32 = 0x0000_0040;
+        let mut command = TokioCommand::new(binary_path);
+        if config.low_priority {
+            command.creation_flags(IDLE_PRIORITY_CLASS);
+        }
+        command
+    };
+
+    command
+        .args(args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+    Ok(command)
+}
+
 /// Start the CPU miner (internal version)
+#[tracing::instrument(skip_all, fields(miner = %config.name))]
 pub async fn start_miner_internal(
     state: &SharedState,
-    address: Option<String>,
+    mut config: MinerConfig,
 ) -> Result<String, String> {
-    let config = MinerConfig {
-        address: address.unwrap_or_else(|| "WXkMhVgRVmTXTVh47wauPKm1xcrW8Qf3Vb".to_string()),
-        ..MinerConfig::default()
-    };
+    coinbase_rotation::prepare(&mut config)?;
     let mut state_guard = state.lock().await;
 
     if !state_guard.node_running {
         return Err("Node must be running before starting miner".to_string());
     }
 
-    if state_guard.miner_running {
-        return Ok("Miner is already running".to_string());
+    if state_guard.miners.contains_key(&config.name) {
+        return Ok(format!("Miner '{}' is already running", config.name));
     }
 
-    let binary_path = get_binary_path("cpuminer");
+    let binary_path = miner_binary(&config)?;
+    // Through the stratum tap, so get_stratum_jobs sees the miner's traffic
+    let stratum_port = stratum_tap::miner_port(&state_guard, config.stratum_port);
 
-    let mut child = TokioCommand::new(&binary_path)
-        .args([
-            "--algo",
-            "sha256d",
-            "--url",
-            &format!("stratum+tcp://127.0.0.1:{}", config.stratum_port),
-            "--coinbase-addr",
-            &config.address,
-            "--threads",
-            &config.threads.to_string(),
-        ])
-        .stdin(Stdio::null())
-        .stdout(Stdio::piped())
-        .stderr(Stdio::piped())
+    let mut child = miner_command(&binary_path, &config, stratum_port)?
         .spawn()
-        .map_err(|e| format!("Failed to spawn cpuminer at {:?}: {}", binary_path, e))?;
+        .map_err(|e| {
+            format!(
+                "Failed to spawn {} at {:?}: {}",
+                miner_label(&config),
+                binary_path,
+                e
+            )
+        })?;
 
     let pid = child.id().unwrap_or(0);
-    state_guard.miner_running = true;
-    state_guard.miner_child_id = Some(pid);
+    let name = config.name.clone();
+    state_guard.miners.insert(
+        name.clone(),
+        MinerInstance {
+            child_id: Some(pid),
+            address: config.address.clone(),
+            threads: miner_threads(&config),
+            label: miner_label(&config),
+        },
+    );
+    state_guard.miner_metrics.insert(
+        name.clone(),
+        miner_metrics::MinerMetrics::new_session(pid, &config),
+    );
+    tokio::spawn(miner_metrics::track_session(
+        state.clone(),
+        name.clone(),
+        pid,
+    ));
 
     // Consume stdout/stderr
     let stdout = child.stdout.take();
@@ -516,55 +1011,116 @@ pub async fn start_miner_internal(
     }
 
     if let Some(stderr) = stderr {
+        let state = state.clone();
+        let name = name.clone();
         tokio::spawn(async move {
             let reader = BufReader::new(stderr);
             let mut lines = reader.lines();
-            while lines.next_line().await.is_ok() {}
+            while let Ok(Some(line)) = lines.next_line().await {
+                miner_metrics::record_miner_line(&state, &name, &line).await;
+            }
         });
     }
 
     tokio::spawn(async move {
         let _ = child.wait().await;
-        let mut state_guard = state_clone.lock().await;
-        state_guard.miner_running = false;
-        state_guard.miner_child_id = None;
+        remove_miner(&state_clone, &name, pid).await;
     });
 
-    Ok(format!("Miner started with {} threads", config.threads))
+    Ok(format!(
+        "Miner '{}' started with {} threads",
+        config.name,
+        miner_threads(&config)
+    ))
 }
 
-/// Stop the CPU miner (internal version)
-pub async fn stop_miner_internal(state: &SharedState) -> Result<String, String> {
+// Forget a miner whose process exited, unless it was already replaced
+// (returns whether no miners are left)
+async fn remove_miner(state: &SharedState, name: &str, pid: u32) -> bool {
     let mut state_guard = state.lock().await;
+    if state_guard.miners.get(name).and_then(|m| m.child_id) == Some(pid) {
+        state_guard.miners.remove(name);
+    }
+    state_guard.miners.is_empty()
+}
 
-    if !state_guard.miner_running {
-        return Ok("Miner is not running".to_string());
+// Status of a miner, with the metrics of its last session when stopped
+fn miner_status(state: &AppState, name: &str) -> MinerStatus {
+    let miner = state.miners.get(name);
+    let metrics = state
+        .miner_metrics
+        .get(name)
+        .map(|m| m.report())
+        .unwrap_or_default();
+
+    MinerStatus {
+        name: name.to_string(),
+        running: miner.is_some(),
+        pid: miner.and_then(|m| m.child_id),
+        address: miner.map(|m| m.address.clone()),
+        threads: miner.map(|m| m.threads),
+        label: miner.map(|m| m.label.to_string()),
+        hash_rate: metrics.hash_rate,
+        average_hash_rate: metrics.average_hash_rate,
+        accepted_shares: metrics.accepted_shares,
+        rejected_shares: metrics.rejected_shares,
     }
+}
+
+/// Status of every running miner, sorted by name
+pub async fn list_miners_internal(state: &SharedState) -> Vec<MinerStatus> {
+    let state_guard = state.lock().await;
+    let mut names: Vec<&String> = state_guard.miners.keys().collect();
+    names.sort();
+    names
+        .into_iter()
+        .map(|name| miner_status(&state_guard, name))
+        .collect()
+}
+
+/// Stop a CPU miner (internal version)
+pub async fn stop_miner_internal(
+    state: &SharedState,
+    name: Option<&str>,
+) -> Result<String, String> {
+    let name = name.unwrap_or(DEFAULT_MINER);
+    let mut state_guard = state.lock().await;
 
-    if let Some(pid) = state_guard.miner_child_id {
+    let Some(miner) = state_guard.miners.remove(name) else {
+        return Ok(format!("Miner '{}' is not running", name));
+    };
+
+    if let Some(pid) = miner.child_id {
         kill_process(pid);
     }
 
-    state_guard.miner_running = false;
-    state_guard.miner_child_id = None;
-
-    Ok("Miner stopped".to_string())
+    Ok(format!("Miner '{}' stopped", name))
 }
 
-/// Start the wallet-headless service (internal version)
-pub async fn start_headless_internal(state: &SharedState) -> Result<String, String> {
-    let config = HeadlessConfig::default();
+/// Start a wallet-headless instance (internal version)
+#[tracing::instrument(skip_all, fields(instance = %config.instance))]
+pub async fn start_headless_internal(
+    state: &SharedState,
+    config: HeadlessConfig,
+) -> Result<String, String> {
+    let compat_warning = compat::ensure_compatible(&config).await?;
     let state_guard = state.lock().await;
 
     if !state_guard.node_running {
         return Err("Node must be running before starting wallet-headless".to_string());
     }
 
-    if state_guard.headless_running {
-        return Ok("Wallet-headless is already running".to_string());
+    if state_guard
+        .headless_instances
+        .contains_key(&config.instance)
+    {
+        return Ok(format!(
+            "Wallet-headless instance '{}' is already running",
+            config.instance
+        ));
     }
 
-    let headless_path = get_headless_dist_path();
+    let headless_path = resolve_headless_dist_path(&config);
     if !headless_path.exists() {
         return Err(format!(
             "Wallet-headless dist not found at {:?}. Run 'build-wallet-headless' first.",
@@ -572,6 +1128,8 @@ pub async fn start_headless_internal(state: &SharedState) -> Result<String, Stri
         ));
     }
 
+    check_headless_conflicts(&state_guard, &config, &headless_path)?;
+
     // Kill any zombie process on the headless port
     kill_process_on_port(config.port);
 
@@ -580,7 +1138,7 @@ pub async fn start_headless_internal(state: &SharedState) -> Result<String, Stri
     let mut state_guard = state.lock().await;
 
     // Generate config file
-    generate_headless_config(&config, &headless_path)?;
+    let multisig_configs = generate_headless_config(&config, &headless_path)?;
 
     let entry_point = headless_path.join("dist").join("index.js");
     let working_dir = headless_path.join("dist");
@@ -588,6 +1146,7 @@ pub async fn start_headless_internal(state: &SharedState) -> Result<String, Stri
     let mut child = TokioCommand::new("node")
         .args([entry_point.to_string_lossy().as_ref()])
         .current_dir(&working_dir)
+        .envs(time_travel::clock_env())
         .stdin(Stdio::null())
         .stdout(Stdio::piped())
         .stderr(Stdio::piped())
@@ -595,8 +1154,15 @@ pub async fn start_headless_internal(state: &SharedState) -> Result<String, Stri
         .map_err(|e| format!("Failed to spawn wallet-headless: {}", e))?;
 
     let pid = child.id().unwrap_or(0);
-    state_guard.headless_running = true;
-    state_guard.headless_child_id = Some(pid);
+    state_guard.headless_instances.insert(
+        config.instance.clone(),
+        HeadlessInstance {
+            port: config.port,
+            child_id: Some(pid),
+            dist_path: headless_path.clone(),
+            multisig_configs,
+        },
+    );
 
     // Consume stdout/stderr
     let stdout = child.stdout.take();
@@ -619,32 +1185,76 @@ pub async fn start_headless_internal(state: &SharedState) -> Result<String, Stri
         });
     }
 
+    let instance_name = config.instance.clone();
     tokio::spawn(async move {
         let _ = child.wait().await;
         let mut state_guard = state_clone.lock().await;
-        state_guard.headless_running = false;
-        state_guard.headless_child_id = None;
+        remove_headless_instance(&mut state_guard, &instance_name, pid);
     });
 
-    Ok(format!("Wallet-headless started on port {}", config.port))
+    // Re-open wallets saved in the registry once the service is up
+    if wallets::has_auto_start_wallets(&config.instance) {
+        let instance_name = config.instance.clone();
+        let port = config.port;
+        tokio::spawn(
+            async move {
+                wallets::restore_wallets(&instance_name, port).await;
+            }
+            .in_current_span(),
+        );
+    }
+
+    Ok(with_compat_warning(
+        format!(
+            "Wallet-headless instance '{}' started on port {}",
+            config.instance, config.port
+        ),
+        compat_warning,
+    ))
 }
 
-/// Stop the wallet-headless service (internal version)
-pub async fn stop_headless_internal(state: &SharedState) -> Result<String, String> {
-    let mut state_guard = state.lock().await;
+// Append a compatibility warning to a start message
+fn with_compat_warning(message: String, warning: Option<String>) -> String {
+    match warning {
+        Some(warning) => {
+            eprintln!("Compatibility warning: {}", warning);
+            format!("{} (warning: {})", message, warning)
+        }
+        None => message,
+    }
+}
 
-    if !state_guard.headless_running {
-        return Ok("Wallet-headless is not running".to_string());
+// Forget a headless instance once its process exits (unless it was already restarted)
+fn remove_headless_instance(state: &mut AppState, instance: &str, pid: u32) {
+    if state
+        .headless_instances
+        .get(instance)
+        .is_some_and(|i| i.child_id == Some(pid))
+    {
+        state.headless_instances.remove(instance);
     }
+}
 
-    if let Some(pid) = state_guard.headless_child_id {
+/// Stop a wallet-headless instance (internal version)
+pub async fn stop_headless_internal(
+    state: &SharedState,
+    instance: Option<&str>,
+) -> Result<String, String> {
+    let name = instance.unwrap_or(DEFAULT_HEADLESS_INSTANCE);
+    let mut state_guard = state.lock().await;
+
+    let Some(instance) = state_guard.headless_instances.remove(name) else {
+        return Ok(format!(
+            "Wallet-headless instance '{}' is not running",
+            name
+        ));
+    };
+
+    if let Some(pid) = instance.child_id {
         kill_process(pid);
     }
 
-    state_guard.headless_running = false;
-    state_guard.headless_child_id = None;
-
-    Ok("Wallet-headless stopped".to_string())
+    Ok(format!("Wallet-headless instance '{}' stopped", name))
 }
 
 /// Generate a new BIP39 seed phrase (internal version)
@@ -691,21 +1301,23 @@ async fn start_node(
     // Ensure data directory exists
     fs::create_dir_all(&config.data_dir)
         .map_err(|e| format!("Failed to create data directory: {}", e))?;
-
-    // Development HD wallet seed (DO NOT use in production!)
-    // This is a fixed seed for local development only
-    let dev_wallet_words = "avocado spot town typical traffic vault danger century property shallow divorce festival spend attack anchor afford rotate green audit adjust fade wagon depart level";
+    logging::set_workspace(std::path::Path::new(&config.data_dir));
+    wallets::set_workspace(std::path::Path::new(&config.data_dir));
 
     // Set platform-specific library path for bundled libraries
     let internal_dir = binary_path.parent().unwrap().join("_internal");
 
+    // Run on the localnet clock once time travel has been used
+    let clock_env = time_travel::clock_env();
+    // --localnet, or settings extending it with forced features
+    let network_args = features::network_args(&config)?;
+
     // Spawn the process using tokio
     let mut cmd = TokioCommand::new(&binary_path);
     set_library_path_env(&mut cmd, &internal_dir);
     let mut child = cmd
         .args([
             "run_node",
-            "--localnet",
             "--status",
             &config.api_port.to_string(),
             "--stratum",
@@ -715,14 +1327,18 @@ async fn start_node(
             "--wallet",
             "hd",
             "--words",
-            dev_wallet_words,
+            DEV_WALLET_WORDS,
             "--wallet-enable-api",
             "--wallet-index",
             "--allow-mining-without-peers",
             "--test-mode-tx-weight",
+            "--enable-event-queue",
             "--unsafe-mode",
             "privatenet",
         ])
+        .args(&network_args)
+        .args(peers::node_args(&mut state_guard))
+        .envs(clock_env.clone())
         .stdin(Stdio::null())
         .stdout(Stdio::piped())
         .stderr(Stdio::piped())
@@ -732,7 +1348,36 @@ async fn start_node(
     let pid = child.id().unwrap_or(0);
     state_guard.node_running = true;
     state_guard.node_child_id = Some(pid);
+    state_guard.node_clock_mocked = !clock_env.is_empty();
     state_guard.data_dir = Some(config.data_dir.clone());
+    state_guard.node_api_port = config.api_port;
+
+    // Relay stratum to the LAN when that's enabled, and tap Forge's own miners
+    let relay_state = state.inner().clone();
+    tokio::spawn(async move {
+        if let Err(e) = stratum_tap::start_tap(relay_state.clone()).await {
+            eprintln!("{}", e);
+        }
+        if let Err(e) = stratum_lan::start_relay(relay_state).await {
+            eprintln!("{}", e);
+        }
+    });
+
+    // Start mining once the node is ready when auto-start is on
+    let auto_miner_state = state.inner().clone();
+    let auto_miner_app = app.clone();
+    tokio::spawn(async move {
+        match auto_miner::start_when_ready(&auto_miner_state).await {
+            Ok(true) => {
+                let _ = auto_miner_app.emit("miner-auto-started", DEFAULT_MINER);
+            }
+            Ok(false) => {}
+            Err(e) => {
+                let _ =
+                    auto_miner_app.emit("node-log", format!("Failed to auto-start miner: {}", e));
+            }
+        }
+    });
 
     // Handle stdout
     let stdout = child.stdout.take();
@@ -816,6 +1461,13 @@ async fn stop_node(state: tauri::State<'_, SharedState>) -> Result<String, Strin
 
     state_guard.node_running = false;
     state_guard.node_child_id = None;
+    if let Some(shutdown_tx) = state_guard.stratum_relay.take() {
+        let _ = shutdown_tx.send(());
+    }
+    if let Some(shutdown_tx) = state_guard.stratum_tap.take() {
+        let _ = shutdown_tx.send(());
+    }
+    state_guard.network_chaos.close_links();
 
     Ok("Node stopped".to_string())
 }
@@ -827,40 +1479,61 @@ async fn start_miner(
     state: tauri::State<'_, SharedState>,
     config: Option<MinerConfig>,
 ) -> Result<String, String> {
-    let config = config.unwrap_or_default();
+    let mut config = config.unwrap_or_default();
+    coinbase_rotation::prepare(&mut config)?;
     let mut state_guard = state.lock().await;
 
     if !state_guard.node_running {
         return Err("Node must be running before starting miner".to_string());
     }
 
-    if state_guard.miner_running {
-        return Err("Miner is already running".to_string());
+    if state_guard.miners.contains_key(&config.name) {
+        return Err(format!("Miner '{}' is already running", config.name));
     }
 
-    let binary_path = get_binary_path("cpuminer");
+    let binary_path = miner_binary(&config)?;
+    // Through the stratum tap, so get_stratum_jobs sees the miner's traffic
+    let stratum_port = stratum_tap::miner_port(&state_guard, config.stratum_port);
 
     // Spawn the process using tokio
-    let mut child = TokioCommand::new(&binary_path)
-        .args([
-            "--algo",
-            "sha256d",
-            "--url",
-            &format!("stratum+tcp://127.0.0.1:{}", config.stratum_port),
-            "--coinbase-addr",
-            &config.address,
-            "--threads",
-            &config.threads.to_string(),
-        ])
-        .stdin(Stdio::null())
-        .stdout(Stdio::piped())
-        .stderr(Stdio::piped())
+    let mut child = miner_command(&binary_path, &config, stratum_port)?
         .spawn()
-        .map_err(|e| format!("Failed to spawn cpuminer at {:?}: {}", binary_path, e))?;
+        .map_err(|e| {
+            format!(
+                "Failed to spawn {} at {:?}: {}",
+                miner_label(&config),
+                binary_path,
+                e
+            )
+        })?;
 
     let pid = child.id().unwrap_or(0);
-    state_guard.miner_running = true;
-    state_guard.miner_child_id = Some(pid);
+    let name = config.name.clone();
+    state_guard.miners.insert(
+        name.clone(),
+        MinerInstance {
+            child_id: Some(pid),
+            address: config.address.clone(),
+            threads: miner_threads(&config),
+            label: miner_label(&config),
+        },
+    );
+    state_guard.miner_metrics.insert(
+        name.clone(),
+        miner_metrics::MinerMetrics::new_session(pid, &config),
+    );
+    tokio::spawn(miner_metrics::track_session(
+        state.inner().clone(),
+        name.clone(),
+        pid,
+    ));
+
+    // Lines of additional miners are tagged with their name
+    let prefix = if name == DEFAULT_MINER {
+        String::new()
+    } else {
+        format!("[{}] ", name)
+    };
 
     // Handle stdout
     let stdout = child.stdout.take();
@@ -871,22 +1544,29 @@ async fn start_miner(
 
     // Spawn task for stdout
     if let Some(stdout) = stdout {
+        let prefix = prefix.clone();
         tokio::spawn(async move {
             let reader = BufReader::new(stdout);
             let mut lines = reader.lines();
             while let Ok(Some(line)) = lines.next_line().await {
-                let _ = app_handle.emit("miner-log", &line);
+                let _ = app_handle.emit("miner-log", format!("{}{}", prefix, line));
             }
         });
     }
 
     // Spawn task for stderr (cpuminer outputs stats here)
     if let Some(stderr) = stderr {
+        let state = state.inner().clone();
+        let name = name.clone();
         tokio::spawn(async move {
             let reader = BufReader::new(stderr);
             let mut lines = reader.lines();
             while let Ok(Some(line)) = lines.next_line().await {
-                let _ = app_handle2.emit("miner-stats", &line);
+                if let Some(metrics) = miner_metrics::record_miner_line(&state, &name, &line).await
+                {
+                    let _ = app_handle2.emit("miner-metrics", &metrics);
+                }
+                let _ = app_handle2.emit("miner-stats", format!("{}{}", prefix, line));
             }
         });
     }
@@ -898,30 +1578,34 @@ async fn start_miner(
         let status = child.wait().await;
         let code = status.map(|s| s.code()).ok().flatten();
 
-        // Reset state when process terminates
-        {
-            let mut state_guard = state_clone.lock().await;
-            state_guard.miner_running = false;
-            state_guard.miner_child_id = None;
+        // Reset state when process terminates; the UI only tracks whether any miner runs
+        if remove_miner(&state_clone, &name, pid).await {
+            let _ = app_handle3.emit("miner-terminated", code);
         }
-
-        let _ = app_handle3.emit("miner-terminated", code);
     });
 
-    Ok(format!("Miner started with {} threads", config.threads))
+    Ok(format!(
+        "Miner '{}' started with {} threads",
+        config.name,
+        miner_threads(&config)
+    ))
 }
 
-// Stop the CPU miner
+// Stop a CPU miner
 #[tauri::command]
-async fn stop_miner(state: tauri::State<'_, SharedState>) -> Result<String, String> {
+async fn stop_miner(
+    state: tauri::State<'_, SharedState>,
+    name: Option<String>,
+) -> Result<String, String> {
+    let name = name.as_deref().unwrap_or(DEFAULT_MINER);
     let mut state_guard = state.lock().await;
 
-    if !state_guard.miner_running {
-        return Err("Miner is not running".to_string());
-    }
+    let Some(miner) = state_guard.miners.remove(name) else {
+        return Err(format!("Miner '{}' is not running", name));
+    };
 
     // Kill the process
-    if let Some(pid) = state_guard.miner_child_id {
+    if let Some(pid) = miner.child_id {
         #[cfg(unix)]
         {
             use std::process::Command;
@@ -939,70 +1623,93 @@ async fn stop_miner(state: tauri::State<'_, SharedState>) -> Result<String, Stri
         }
     }
 
-    state_guard.miner_running = false;
-    state_guard.miner_child_id = None;
-
-    Ok("Miner stopped".to_string())
+    Ok(format!("Miner '{}' stopped", name))
 }
 
-// Get node status from the API
-#[tauri::command]
-async fn get_node_status(state: tauri::State<'_, SharedState>) -> Result<NodeStatus, String> {
-    let state_guard = state.lock().await;
-
-    if !state_guard.node_running {
-        return Ok(NodeStatus {
-            running: false,
-            block_height: None,
-            hash_rate: None,
-            peer_count: None,
-        });
+/// Node status from the API; only `running` is set while the API isn't up
+pub async fn get_node_status_internal(state: &SharedState) -> NodeStatus {
+    let mut status = NodeStatus {
+        running: state.lock().await.node_running,
+        block_height: None,
+        hash_rate: None,
+        peer_count: None,
+        version: None,
+        network: None,
+        uptime_secs: None,
+        sync_state: None,
+        best_block_hash: None,
+        best_block_timestamp: None,
+    };
+    if !status.running {
+        return status;
     }
 
-    // Try to fetch status from the node API
     let client = reqwest::Client::new();
-    match client.get("http://127.0.0.1:8080/v1a/status").send().await {
-        Ok(response) => {
-            if let Ok(json) = response.json::<serde_json::Value>().await {
-                let block_height = json
-                    .get("dag")
-                    .and_then(|d| d.get("best_block"))
-                    .and_then(|b| b.get("height"))
-                    .and_then(|h| h.as_u64());
-
-                Ok(NodeStatus {
-                    running: true,
-                    block_height,
-                    hash_rate: None,
-                    peer_count: Some(0), // Localnet has no peers
-                })
-            } else {
-                Ok(NodeStatus {
-                    running: true,
-                    block_height: None,
-                    hash_rate: None,
-                    peer_count: None,
-                })
+    let Ok(json) = async {
+        client
+            .get("http://127.0.0.1:8080/v1a/status")
+            .send()
+            .await?
+            .json::<serde_json::Value>()
+            .await
+    }
+    .await
+    else {
+        // Process is running but the API might not be ready
+        return status;
+    };
+
+    let server = &json["server"];
+    let best_block = &json["dag"]["best_block"];
+    status.block_height = best_block["height"].as_u64();
+    status.best_block_hash = best_block["hash"].as_str().map(|s| s.to_string());
+    status.peer_count = json["connections"]["connected_peers"]
+        .as_array()
+        .map(|peers| peers.len() as u32);
+    status.version = server["app_version"].as_str().map(|s| s.to_string());
+    status.network = server["network"].as_str().map(|s| s.to_string());
+    status.uptime_secs = server["uptime"].as_f64();
+    status.sync_state = server["state"].as_str().map(|s| s.to_string());
+
+    // The status only names the best block; its timestamp comes from the block
+    if let Some(hash) = &status.best_block_hash {
+        if let Ok(response) = client
+            .get("http://127.0.0.1:8080/v1a/transaction")
+            .query(&[("id", hash)])
+            .send()
+            .await
+        {
+            if let Ok(block) = response.json::<serde_json::Value>().await {
+                status.best_block_timestamp = block["tx"]["timestamp"].as_u64();
             }
         }
-        Err(_) => Ok(NodeStatus {
-            running: true, // Process is running but API might not be ready
-            block_height: None,
-            hash_rate: None,
-            peer_count: None,
-        }),
     }
+    status
+}
+
+// Get node status from the API
+#[tauri::command]
+async fn get_node_status(state: tauri::State<'_, SharedState>) -> Result<NodeStatus, String> {
+    Ok(get_node_status_internal(&state).await)
 }
 
 // Get miner status
 #[tauri::command]
-async fn get_miner_status(state: tauri::State<'_, SharedState>) -> Result<MinerStatus, String> {
+async fn get_miner_status(
+    state: tauri::State<'_, SharedState>,
+    name: Option<String>,
+) -> Result<MinerStatus, String> {
     let state_guard = state.lock().await;
+    Ok(miner_status(
+        &state_guard,
+        name.as_deref().unwrap_or(DEFAULT_MINER),
+    ))
+}
 
-    Ok(MinerStatus {
-        running: state_guard.miner_running,
-        hash_rate: None, // TODO: Parse from miner output
-    })
+// List all running miners
+#[tauri::command]
+async fn list_miners(state: tauri::State<'_, SharedState>) -> Result<Vec<MinerStatus>, String> {
+    Ok(list_miners_internal(&state).await)
 }
 
 // Get current state
@@ -1012,19 +1719,67 @@ async fn get_state(state: tauri::State<'_, SharedState>) -> Result<serde_json::V
 
     Ok(serde_json::json!({
         "node_running": state_guard.node_running,
-        "miner_running": state_guard.miner_running,
-        "explorer_server_running": state_guard.explorer_server_running,
-        "headless_running": state_guard.headless_running,
+        "miner_running": !state_guard.miners.is_empty(),
+        "miners": state_guard.miners.keys().collect::<Vec<_>>(),
+        "explorer_server_running": state_guard.explorer_servers.contains_key(DEFAULT_EXPLORER),
+        "explorer_servers": state_guard.explorer_servers.keys().collect::<Vec<_>>(),
+        "headless_running": state_guard.headless_instances.contains_key(DEFAULT_HEADLESS_INSTANCE),
+        "headless_instances": state_guard.headless_instances.keys().collect::<Vec<_>>(),
         "data_dir": state_guard.data_dir,
     }))
 }
 
-// Get the default data directory path
-fn get_default_data_dir() -> std::path::PathBuf {
+// Get the app directory (parent of the node data dir, holds Forge's own files)
+fn get_app_dir() -> std::path::PathBuf {
     dirs::data_local_dir()
         .unwrap_or_else(|| std::path::PathBuf::from("."))
         .join("hathor-forge")
-        .join("data")
+}
+
+// Get the default data directory path
+fn get_default_data_dir() -> std::path::PathBuf {
+    get_app_dir().join("data")
+}
+
+// Data directory of the current workspace (the stored data dir or the default)
+fn workspace_data_dir(state: &AppState) -> std::path::PathBuf {
+    state
+        .data_dir
+        .as_ref()
+        .map(std::path::PathBuf::from)
+        .unwrap_or_else(get_default_data_dir)
+}
+
+// Current Unix time in seconds
+pub(crate) fn unix_now() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+// Write a file only the current user can read. On Unix it's created with mode
+// 0600 (and an existing file is restricted) before any content is written
+pub(crate) fn write_private_file(
+    path: impl AsRef<std::path::Path>,
+    contents: impl AsRef<[u8]>,
+) -> std::io::Result<()> {
+    use std::io::Write;
+
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut file = options.open(path)?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        file.set_permissions(std::fs::Permissions::from_mode(0o600))?;
+    }
+    file.write_all(contents.as_ref())
 }
 
 // Reset blockchain data (removes the data directory)
@@ -1037,77 +1792,28 @@ async fn reset_data(state: tauri::State<'_, SharedState>) -> Result<String, Stri
         return Err("Cannot reset data while node is running. Stop the node first.".to_string());
     }
 
-    // Use the stored data dir or default
-    let data_dir = state_guard
-        .data_dir
-        .as_ref()
-        .map(std::path::PathBuf::from)
-        .unwrap_or_else(get_default_data_dir);
+    let data_dir = workspace_data_dir(&state_guard);
 
     drop(state_guard); // Release lock before file operations
 
     if data_dir.exists() {
+        // The log files in it are reopened on the next line
+        logging::close_files();
         fs::remove_dir_all(&data_dir)
             .map_err(|e| format!("Failed to remove data directory: {}", e))?;
     }
+    // A fresh chain can start over on real time
+    time_travel::clear_offset();
 
     Ok(format!("Data directory removed: {:?}", data_dir))
 }
 
-// Get wallet addresses with balances
+// Get every derived node wallet address with its balance and usage flags
 #[tauri::command]
 async fn get_wallet_addresses(
     state: tauri::State<'_, SharedState>,
 ) -> Result<Vec<WalletAddress>, String> {
-    let state_guard = state.lock().await;
-
-    if !state_guard.node_running {
-        return Err("Node is not running".to_string());
-    }
-
-    drop(state_guard);
-
-    let client = reqwest::Client::new();
-
-    // Get current address from the wallet
-    let address_response = client
-        .get("http://127.0.0.1:8080/v1a/wallet/address")
-        .send()
-        .await
-        .map_err(|e| format!("Failed to fetch address: {}", e))?;
-
-    let address_json: serde_json::Value = address_response
-        .json()
-        .await
-        .map_err(|e| format!("Failed to parse address response: {}", e))?;
-
-    let current_address = address_json["address"]
-        .as_str()
-        .ok_or("Invalid address format")?
-        .to_string();
-
-    // Get wallet balance
-    let balance_response = client
-        .get("http://127.0.0.1:8080/v1a/wallet/balance")
-        .send()
-        .await
-        .map_err(|e| format!("Failed to fetch balance: {}", e))?;
-
-    let balance_json: serde_json::Value = balance_response
-        .json()
-        .await
-        .map_err(|e| format!("Failed to parse balance response: {}", e))?;
-
-    let balance = balance_json["balance"]["available"].as_u64();
-
-    // Return the current address with its balance
-    let wallet_addresses = vec![WalletAddress {
-        address: current_address,
-        index: 0,
-        balance,
-    }];
-
-    Ok(wallet_addresses)
+    faucet::faucet_addresses_internal(state.inner()).await
 }
 
 // Get fullnode wallet balance
@@ -1142,8 +1848,8 @@ async fn get_fullnode_balance(
     if result["success"].as_bool().unwrap_or(false) {
         let balance = &result["balance"];
         Ok(FullnodeBalance {
-            available: balance["available"].as_i64().unwrap_or(0),
-            locked: balance["locked"].as_i64().unwrap_or(0),
+            available: HtrAmount::from_json(&balance["available"]),
+            locked: HtrAmount::from_json(&balance["locked"]),
         })
     } else {
         let message = result["message"]
@@ -1160,6 +1866,11 @@ async fn send_tx(
     state: tauri::State<'_, SharedState>,
     request: SendTxRequest,
 ) -> Result<String, String> {
+    address::check_address(&request.address)?;
+    if request.amount.is_zero() {
+        return Err("Amount must be positive".to_string());
+    }
+
     let state_guard = state.lock().await;
 
     if !state_guard.node_running {
@@ -1169,6 +1880,15 @@ async fn send_tx(
     drop(state_guard);
 
     let client = reqwest::Client::new();
+    faucet::rotate_address(&client).await?;
+
+    let mut output = serde_json::json!({
+        "address": request.address,
+        "value": request.amount,
+    });
+    if let Some(timelock) = request.timelock {
+        output["timelock"] = timelock.into();
+    }
 
     // Use the fullnode's wallet send_tokens endpoint
     let response = client
@@ -1176,10 +1896,7 @@ async fn send_tx(
         .json(&serde_json::json!({
             "data": {
                 "inputs": [],
-                "outputs": [{
-                    "address": request.address,
-                    "value": request.amount,
-                }]
+                "outputs": [output]
             }
         }))
         .send()
@@ -1214,17 +1931,24 @@ async fn start_headless(
     config: Option<HeadlessConfig>,
 ) -> Result<String, String> {
     let config = config.unwrap_or_default();
+    let compat_warning = compat::ensure_compatible(&config).await?;
     let mut state_guard = state.lock().await;
 
     if !state_guard.node_running {
         return Err("Node must be running before starting wallet-headless".to_string());
     }
 
-    if state_guard.headless_running {
-        return Err("Wallet-headless is already running".to_string());
+    if state_guard
+        .headless_instances
+        .contains_key(&config.instance)
+    {
+        return Err(format!(
+            "Wallet-headless instance '{}' is already running",
+            config.instance
+        ));
     }
 
-    let headless_path = get_headless_dist_path();
+    let headless_path = resolve_headless_dist_path(&config);
     if !headless_path.exists() {
         return Err(format!(
             "Wallet-headless dist not found at {:?}. Run 'build-wallet-headless' first.",
@@ -1232,12 +1956,14 @@ async fn start_headless(
         ));
     }
 
+    check_headless_conflicts(&state_guard, &config, &headless_path)?;
+
     // Kill any zombie process on the headless port
     kill_process_on_port(config.port);
     tokio::time::sleep(tokio::time::Duration::from_millis(300)).await;
 
     // Generate config file in the dist directory
-    generate_headless_config(&config, &headless_path)?;
+    let multisig_configs = generate_headless_config(&config, &headless_path)?;
 
     // Find node binary to run with
     let entry_point = headless_path.join("dist").join("index.js");
@@ -1247,6 +1973,7 @@ async fn start_headless(
     let mut child = TokioCommand::new("node")
         .args([entry_point.to_string_lossy().as_ref()])
         .current_dir(&working_dir)
+        .envs(time_travel::clock_env())
         .stdin(Stdio::null())
         .stdout(Stdio::piped())
         .stderr(Stdio::piped())
@@ -1254,8 +1981,15 @@ async fn start_headless(
         .map_err(|e| format!("Failed to spawn wallet-headless: {}", e))?;
 
     let pid = child.id().unwrap_or(0);
-    state_guard.headless_running = true;
-    state_guard.headless_child_id = Some(pid);
+    state_guard.headless_instances.insert(
+        config.instance.clone(),
+        HeadlessInstance {
+            port: config.port,
+            child_id: Some(pid),
+            dist_path: headless_path.clone(),
+            multisig_configs,
+        },
+    );
 
     // Handle stdout
     let stdout = child.stdout.take();
@@ -1264,13 +1998,21 @@ async fn start_headless(
     let app_handle = app.clone();
     let app_handle2 = app.clone();
 
+    // Prefix log lines of non-default instances so they can be told apart
+    let log_prefix = if config.instance == DEFAULT_HEADLESS_INSTANCE {
+        String::new()
+    } else {
+        format!("[{}] ", config.instance)
+    };
+    let log_prefix2 = log_prefix.clone();
+
     // Spawn task for stdout
     if let Some(stdout) = stdout {
         tokio::spawn(async move {
             let reader = BufReader::new(stdout);
             let mut lines = reader.lines();
             while let Ok(Some(line)) = lines.next_line().await {
-                let _ = app_handle.emit("headless-log", &line);
+                let _ = app_handle.emit("headless-log", format!("{}{}", log_prefix, line));
             }
         });
     }
@@ -1281,7 +2023,7 @@ async fn start_headless(
             let reader = BufReader::new(stderr);
             let mut lines = reader.lines();
             while let Ok(Some(line)) = lines.next_line().await {
-                let _ = app_handle2.emit("headless-log", &line);
+                let _ = app_handle2.emit("headless-log", format!("{}{}", log_prefix2, line));
             }
         });
     }
@@ -1289,6 +2031,7 @@ async fn start_headless(
     // Spawn task to wait for process termination and reset state
     let app_handle3 = app.clone();
     let state_clone = state.inner().clone();
+    let instance_name = config.instance.clone();
     tokio::spawn(async move {
         let status = child.wait().await;
         let code = status.map(|s| s.code()).ok().flatten();
@@ -1296,27 +2039,67 @@ async fn start_headless(
         // Reset state when process terminates
         {
             let mut state_guard = state_clone.lock().await;
-            state_guard.headless_running = false;
-            state_guard.headless_child_id = None;
+            remove_headless_instance(&mut state_guard, &instance_name, pid);
         }
 
-        let _ = app_handle3.emit("headless-terminated", code);
+        if instance_name == DEFAULT_HEADLESS_INSTANCE {
+            let _ = app_handle3.emit("headless-terminated", code);
+        }
+        let _ = app_handle3.emit(
+            "headless-instance-terminated",
+            serde_json::json!({ "instance": instance_name, "code": code }),
+        );
     });
 
-    Ok(format!("Wallet-headless started on port {}", config.port))
+    // Re-open wallets saved in the registry once the service is up
+    if wallets::has_auto_start_wallets(&config.instance) {
+        for wallet in wallets::list_wallets() {
+            if wallet.auto_start && wallet.instance == config.instance {
+                tokio::spawn(wallet_sync::track_wallet_sync(
+                    app.clone(),
+                    state.inner().clone(),
+                    wallet.wallet_id,
+                    Some(wallet.instance),
+                ));
+            }
+        }
+
+        let app_handle4 = app.clone();
+        let instance_name = config.instance.clone();
+        let port = config.port;
+        tokio::spawn(async move {
+            let results = wallets::restore_wallets(&instance_name, port).await;
+            let _ = app_handle4.emit("wallets-restored", &results);
+        });
+    }
+
+    Ok(with_compat_warning(
+        format!(
+            "Wallet-headless instance '{}' started on port {}",
+            config.instance, config.port
+        ),
+        compat_warning,
+    ))
 }
 
-// Stop the wallet-headless service
+// Stop a wallet-headless instance
 #[tauri::command]
-async fn stop_headless(state: tauri::State<'_, SharedState>) -> Result<String, String> {
+async fn stop_headless(
+    state: tauri::State<'_, SharedState>,
+    instance: Option<String>,
+) -> Result<String, String> {
+    let name = instance.as_deref().unwrap_or(DEFAULT_HEADLESS_INSTANCE);
     let mut state_guard = state.lock().await;
 
-    if !state_guard.headless_running {
-        return Err("Wallet-headless is not running".to_string());
-    }
+    let Some(instance) = state_guard.headless_instances.remove(name) else {
+        return Err(format!(
+            "Wallet-headless instance '{}' is not running",
+            name
+        ));
+    };
 
     // Kill the process
-    if let Some(pid) = state_guard.headless_child_id {
+    if let Some(pid) = instance.child_id {
         #[cfg(unix)]
         {
             use std::process::Command;
@@ -1334,29 +2117,48 @@ async fn stop_headless(state: tauri::State<'_, SharedState>) -> Result<String, S
         }
     }
 
-    state_guard.headless_running = false;
-    state_guard.headless_child_id = None;
-
-    Ok("Wallet-headless stopped".to_string())
+    Ok(format!("Wallet-headless instance '{}' stopped", name))
 }
 
 // Get headless status
 #[tauri::command]
 async fn get_headless_status(
     state: tauri::State<'_, SharedState>,
+    instance: Option<String>,
 ) -> Result<HeadlessStatus, String> {
     let state_guard = state.lock().await;
+    let instance = state_guard
+        .headless_instances
+        .get(instance.as_deref().unwrap_or(DEFAULT_HEADLESS_INSTANCE));
 
     Ok(HeadlessStatus {
-        running: state_guard.headless_running,
-        port: if state_guard.headless_running {
-            Some(8001)
-        } else {
-            None
-        },
+        running: instance.is_some(),
+        port: instance.map(|i| i.port),
     })
 }
 
+// List all running wallet-headless instances
+#[tauri::command]
+async fn list_headless_instances(
+    state: tauri::State<'_, SharedState>,
+) -> Result<Vec<HeadlessInstanceStatus>, String> {
+    let state_guard = state.lock().await;
+
+    let mut instances: Vec<HeadlessInstanceStatus> = state_guard
+        .headless_instances
+        .iter()
+        .map(|(name, instance)| HeadlessInstanceStatus {
+            instance: name.clone(),
+            port: instance.port,
+            pid: instance.child_id,
+            dist_path: instance.dist_path.to_string_lossy().to_string(),
+        })
+        .collect();
+    instances.sort_by(|a, b| a.instance.cmp(&b.instance));
+
+    Ok(instances)
+}
+
 // Generate a new BIP39 seed phrase (24 words)
 #[tauri::command]
 async fn generate_seed() -> Result<String, String> {
@@ -1376,22 +2178,19 @@ async fn generate_seed() -> Result<String, String> {
 // Create a new wallet via wallet-headless
 #[tauri::command]
 async fn create_headless_wallet(
+    app: tauri::AppHandle,
     state: tauri::State<'_, SharedState>,
-    request: CreateHeadlessWalletRequest,
+    mut request: CreateHeadlessWalletRequest,
 ) -> Result<HeadlessWallet, String> {
-    let state_guard = state.lock().await;
-
-    if !state_guard.headless_running {
-        return Err("Wallet-headless is not running".to_string());
-    }
-
-    drop(state_guard);
+    // Catch bad seeds here; wallet-headless only reports a generic failure
+    request.seed = wallets::validate_mnemonic(&request.seed)?;
+    let base_url = headless_url(state.inner(), request.instance.as_deref()).await?;
 
     let client = reqwest::Client::new();
 
     // Start a wallet with the provided seed
     let response = client
-        .post("http://localhost:8001/start")
+        .post(format!("{}/start", base_url))
         .json(&serde_json::json!({
             "wallet-id": request.wallet_id,
             "seed": request.seed,
@@ -1406,6 +2205,25 @@ async fn create_headless_wallet(
         .map_err(|e| format!("Failed to parse response: {}", e))?;
 
     if result["success"].as_bool().unwrap_or(false) {
+        // Remember the wallet (and, when the user has opted in, its seed) so
+        // it is re-opened after a restart
+        let instance = request
+            .instance
+            .as_deref()
+            .unwrap_or(DEFAULT_HEADLESS_INSTANCE);
+        if let Err(e) =
+            wallets::register_wallet(&request.wallet_id, &request.seed, instance, None).await
+        {
+            eprintln!("Failed to register wallet: {}", e);
+        }
+
+        tokio::spawn(wallet_sync::track_wallet_sync(
+            app,
+            state.inner().clone(),
+            request.wallet_id.clone(),
+            request.instance.clone(),
+        ));
+
         Ok(HeadlessWallet {
             wallet_id: request.wallet_id,
             status: "starting".to_string(),
@@ -1425,19 +2243,14 @@ async fn create_headless_wallet(
 async fn get_headless_wallet_status(
     state: tauri::State<'_, SharedState>,
     wallet_id: String,
+    instance: Option<String>,
 ) -> Result<HeadlessWallet, String> {
-    let state_guard = state.lock().await;
-
-    if !state_guard.headless_running {
-        return Err("Wallet-headless is not running".to_string());
-    }
-
-    drop(state_guard);
+    let base_url = headless_url(state.inner(), instance.as_deref()).await?;
 
     let client = reqwest::Client::new();
 
     let response = client
-        .get("http://localhost:8001/wallet/status")
+        .get(format!("{}/wallet/status", base_url))
         .header("X-Wallet-Id", &wallet_id)
         .send()
         .await
@@ -1466,19 +2279,14 @@ async fn get_headless_wallet_status(
 async fn get_headless_wallet_balance(
     state: tauri::State<'_, SharedState>,
     wallet_id: String,
+    instance: Option<String>,
 ) -> Result<HeadlessWalletBalance, String> {
-    let state_guard = state.lock().await;
-
-    if !state_guard.headless_running {
-        return Err("Wallet-headless is not running".to_string());
-    }
-
-    drop(state_guard);
+    let base_url = headless_url(state.inner(), instance.as_deref()).await?;
 
     let client = reqwest::Client::new();
 
     let response = client
-        .get("http://localhost:8001/wallet/balance")
+        .get(format!("{}/wallet/balance", base_url))
         .header("X-Wallet-Id", &wallet_id)
         .send()
         .await
@@ -1489,8 +2297,8 @@ async fn get_headless_wallet_balance(
         .await
         .map_err(|e| format!("Failed to parse response: {}", e))?;
 
-    let available = result["available"].as_u64().unwrap_or(0);
-    let locked = result["locked"].as_u64().unwrap_or(0);
+    let available = HtrAmount::from_json(&result["available"]);
+    let locked = HtrAmount::from_json(&result["locked"]);
 
     Ok(HeadlessWalletBalance { available, locked })
 }
@@ -1500,19 +2308,14 @@ async fn get_headless_wallet_balance(
 async fn get_headless_wallet_addresses(
     state: tauri::State<'_, SharedState>,
     wallet_id: String,
+    instance: Option<String>,
 ) -> Result<Vec<String>, String> {
-    let state_guard = state.lock().await;
-
-    if !state_guard.headless_running {
-        return Err("Wallet-headless is not running".to_string());
-    }
-
-    drop(state_guard);
+    let base_url = headless_url(state.inner(), instance.as_deref()).await?;
 
     let client = reqwest::Client::new();
 
     let response = client
-        .get("http://localhost:8001/wallet/addresses")
+        .get(format!("{}/wallet/addresses", base_url))
         .header("X-Wallet-Id", &wallet_id)
         .send()
         .await
@@ -1541,23 +2344,40 @@ async fn headless_wallet_send_tx(
     state: tauri::State<'_, SharedState>,
     request: HeadlessWalletSendTxRequest,
 ) -> Result<String, String> {
-    let state_guard = state.lock().await;
-
-    if !state_guard.headless_running {
-        return Err("Wallet-headless is not running".to_string());
+    address::check_address(&request.address)?;
+    if request.amount.is_zero() {
+        return Err("Amount must be positive".to_string());
     }
-
-    drop(state_guard);
+    let base_url = headless_url(state.inner(), request.instance.as_deref()).await?;
 
     let client = reqwest::Client::new();
 
+    // simple-send-tx has no timelock support; timelocked sends go through send-tx
+    let (path, mut body) = match request.timelock {
+        Some(timelock) => (
+            "/wallet/send-tx",
+            serde_json::json!({
+                "outputs": [{
+                    "address": request.address,
+                    "value": request.amount,
+                    "timelock": timelock,
+                }]
+            }),
+        ),
+        None => (
+            "/wallet/simple-send-tx",
+            serde_json::json!({
+                "address": request.address,
+                "value": request.amount,
+            }),
+        ),
+    };
+    wallets::apply_send_defaults(&mut body, &base_url, &request.wallet_id).await?;
+
     let response = client
-        .post("http://localhost:8001/wallet/simple-send-tx")
+        .post(format!("{}{}", base_url, path))
         .header("X-Wallet-Id", &request.wallet_id)
-        .json(&serde_json::json!({
-            "address": request.address,
-            "value": request.amount,
-        }))
+        .json(&body)
         .send()
         .await
         .map_err(|e| format!("Failed to send transaction: {}", e))?;
@@ -1580,7 +2400,21 @@ async fn headless_wallet_send_tx(
             .or_else(|| result["error"].as_str())
             .unwrap_or(&response_text)
             .to_string();
-        Err(format!("Transaction failed: {}", message))
+        let check = transactions::BuildTxRequest {
+            wallet_id: request.wallet_id,
+            outputs: vec![transactions::TxOutput {
+                address: request.address,
+                value: request.amount.cents(),
+                token: None,
+                timelock: request.timelock,
+            }],
+            inputs: Vec::new(),
+            change_address: None,
+            dry_run: true,
+            unsigned: false,
+            instance: request.instance,
+        };
+        Err(transactions::send_failure_message(state.inner(), &check, &message).await)
     }
 }
 
@@ -1589,19 +2423,14 @@ async fn headless_wallet_send_tx(
 async fn close_headless_wallet(
     state: tauri::State<'_, SharedState>,
     wallet_id: String,
+    instance: Option<String>,
 ) -> Result<String, String> {
-    let state_guard = state.lock().await;
-
-    if !state_guard.headless_running {
-        return Err("Wallet-headless is not running".to_string());
-    }
-
-    drop(state_guard);
+    let base_url = headless_url(state.inner(), instance.as_deref()).await?;
 
     let client = reqwest::Client::new();
 
     let response = client
-        .post("http://localhost:8001/wallet/stop")
+        .post(format!("{}/wallet/stop", base_url))
         .header("X-Wallet-Id", &wallet_id)
         .send()
         .await
@@ -1613,6 +2442,8 @@ async fn close_headless_wallet(
         .map_err(|e| format!("Failed to parse response: {}", e))?;
 
     if result["success"].as_bool().unwrap_or(false) {
+        // Explicitly closed wallets stay registered but are no longer re-opened
+        let _ = wallets::set_auto_start(&wallet_id, false).await;
         Ok(format!("Wallet '{}' closed", wallet_id))
     } else {
         let message = result["message"]
@@ -1623,204 +2454,7 @@ async fn close_headless_wallet(
     }
 }
 
-// Proxy HTTP requests to the fullnode
-async fn proxy_api(Path(path): Path<String>, req: Request) -> Response {
-    // Include query string if present
-    let query = req
-        .uri()
-        .query()
-        .map(|q| format!("?{}", q))
-        .unwrap_or_default();
-    let fullnode_url = format!("http://127.0.0.1:8080/v1a/{}{}", path, query);
-
-    let client = reqwest::Client::new();
-    let method = req.method().clone();
-    let headers = req.headers().clone();
-
-    // Build the request to the fullnode
-    let mut builder = match method.as_str() {
-        "GET" => client.get(&fullnode_url),
-        "POST" => client.post(&fullnode_url),
-        "PUT" => client.put(&fullnode_url),
-        "DELETE" => client.delete(&fullnode_url),
-        "PATCH" => client.patch(&fullnode_url),
-        _ => client.get(&fullnode_url),
-    };
-
-    // Forward headers (except host)
-    for (name, value) in headers.iter() {
-        if name != "host" {
-            if let Ok(header_name) = reqwest::header::HeaderName::try_from(name.as_str()) {
-                if let Ok(header_value) = reqwest::header::HeaderValue::from_bytes(value.as_bytes())
-                {
-                    builder = builder.header(header_name, header_value);
-                }
-            }
-        }
-    }
-
-    // Forward body for POST/PUT/PATCH
-    if method == "POST" || method == "PUT" || method == "PATCH" {
-        let body_bytes = match axum::body::to_bytes(req.into_body(), 10 * 1024 * 1024).await {
-            Ok(bytes) => bytes,
-            Err(_) => {
-                return Response::builder()
-                    .status(500)
-                    .body(Body::from("Failed to read request body"))
-                    .unwrap();
-            }
-        };
-        builder = builder.body(body_bytes.to_vec());
-    }
-
-    // Make the request
-    match builder.send().await {
-        Ok(response) => {
-            let status = response.status();
-            let headers = response.headers().clone();
-
-            match response.bytes().await {
-                Ok(body) => {
-                    let mut builder = Response::builder().status(status.as_u16());
-
-                    // Forward response headers
-                    for (name, value) in headers.iter() {
-                        if let Ok(header_name) = axum::http::HeaderName::try_from(name.as_str()) {
-                            if let Ok(header_value) =
-                                axum::http::HeaderValue::from_bytes(value.as_bytes())
-                            {
-                                builder = builder.header(header_name, header_value);
-                            }
-                        }
-                    }
-
-                    builder.body(Body::from(body.to_vec())).unwrap()
-                }
-                Err(_) => Response::builder()
-                    .status(502)
-                    .body(Body::from("Failed to read response from fullnode"))
-                    .unwrap(),
-            }
-        }
-        Err(e) => Response::builder()
-            .status(502)
-            .body(Body::from(format!("Failed to connect to fullnode: {}", e)))
-            .unwrap(),
-    }
-}
-
-// Proxy WebSocket connections to the fullnode
-async fn proxy_ws(ws: WebSocketUpgrade) -> impl IntoResponse {
-    ws.on_upgrade(handle_ws_proxy)
-}
-
-async fn handle_ws_proxy(mut client_ws: WebSocket) {
-    // Connect to fullnode WebSocket
-    let fullnode_url = "ws://127.0.0.1:8080/v1a/ws/";
-
-    let ws_stream = match tokio_tungstenite::connect_async(fullnode_url).await {
-        Ok((stream, _)) => stream,
-        Err(e) => {
-            let _ = client_ws
-                .send(Message::Close(Some(axum::extract::ws::CloseFrame {
-                    code: 1011,
-                    reason: format!("Failed to connect to fullnode: {}", e).into(),
-                })))
-                .await;
-            return;
-        }
-    };
-
-    let (mut fullnode_sink, mut fullnode_stream) = ws_stream.split();
-    let (mut client_sink, mut client_stream) = client_ws.split();
-
-    // Forward messages from client to fullnode
-    let client_to_fullnode = async {
-        while let Some(msg) = client_stream.next().await {
-            match msg {
-                Ok(Message::Text(text)) => {
-                    if fullnode_sink
-                        .send(tungstenite::Message::Text(text.to_string()))
-                        .await
-                        .is_err()
-                    {
-                        break;
-                    }
-                }
-                Ok(Message::Binary(data)) => {
-                    if fullnode_sink
-                        .send(tungstenite::Message::Binary(data.to_vec()))
-                        .await
-                        .is_err()
-                    {
-                        break;
-                    }
-                }
-                Ok(Message::Ping(data)) => {
-                    if fullnode_sink
-                        .send(tungstenite::Message::Ping(data.to_vec()))
-                        .await
-                        .is_err()
-                    {
-                        break;
-                    }
-                }
-                Ok(Message::Pong(data)) => {
-                    if fullnode_sink
-                        .send(tungstenite::Message::Pong(data.to_vec()))
-                        .await
-                        .is_err()
-                    {
-                        break;
-                    }
-                }
-                Ok(Message::Close(_)) | Err(_) => break,
-            }
-        }
-    };
-
-    // Forward messages from fullnode to client
-    let fullnode_to_client = async {
-        while let Some(msg) = fullnode_stream.next().await {
-            match msg {
-                Ok(tungstenite::Message::Text(text)) => {
-                    if client_sink.send(Message::Text(text.into())).await.is_err() {
-                        break;
-                    }
-                }
-                Ok(tungstenite::Message::Binary(data)) => {
-                    if client_sink
-                        .send(Message::Binary(data.into()))
-                        .await
-                        .is_err()
-                    {
-                        break;
-                    }
-                }
-                Ok(tungstenite::Message::Ping(data)) => {
-                    if client_sink.send(Message::Ping(data.into())).await.is_err() {
-                        break;
-                    }
-                }
-                Ok(tungstenite::Message::Pong(data)) => {
-                    if client_sink.send(Message::Pong(data.into())).await.is_err() {
-                        break;
-                    }
-                }
-                Ok(tungstenite::Message::Close(_)) | Err(_) => break,
-                _ => {}
-            }
-        }
-    };
-
-    // Run both directions concurrently
-    tokio::select! {
-        _ = client_to_fullnode => {},
-        _ = fullnode_to_client => {},
-    }
-}
-
-// Get the path to the explorer-dist directory
+// Get the path to the bundled explorer-dist directory
 fn get_explorer_dist_path() -> std::path::PathBuf {
     // In dev mode, explorer-dist is in src-tauri/explorer-dist/
     let dev_path = std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("explorer-dist");
@@ -1832,96 +2466,260 @@ fn get_explorer_dist_path() -> std::path::PathBuf {
     std::path::PathBuf::from("explorer-dist")
 }
 
-// Start the explorer HTTP server
+// Port of a running explorer server, or a reason another can't use `port`
+fn check_explorer_port(state: &AppState, instance: &str, port: u16) -> Result<(), String> {
+    if port == 0 {
+        return Err("Port must be positive".to_string());
+    }
+    if instance != DEFAULT_EXPLORER && port == lan_share::EXPLORER_PORT {
+        return Err(format!(
+            "Port {} belongs to the default explorer server",
+            lan_share::EXPLORER_PORT
+        ));
+    }
+    if let Some((name, _)) = state
+        .explorer_servers
+        .iter()
+        .find(|(_, server)| server.port == port)
+    {
+        return Err(format!(
+            "Port {} is taken by explorer server '{}'",
+            port, name
+        ));
+    }
+    Ok(())
+}
+
+// Start an explorer HTTP server: the default one on port 3001, or another
+// instance on its own port, optionally proxying a node of its own
 #[tauri::command]
 async fn start_explorer_server(
     app: tauri::AppHandle,
     state: tauri::State<'_, SharedState>,
+    instance: Option<String>,
+    port: Option<u16>,
+    target: Option<proxy::ProxyTarget>,
 ) -> Result<String, String> {
+    let instance = instance.unwrap_or_else(|| DEFAULT_EXPLORER.to_string());
+    let target = target.map(proxy::validate_target).transpose()?;
     let mut state_guard = state.lock().await;
 
-    if state_guard.explorer_server_running {
-        return Err("Explorer server is already running".to_string());
+    if state_guard.explorer_servers.contains_key(&instance) {
+        return Err(format!("Explorer server '{}' is already running", instance));
     }
+    let port = match port {
+        Some(port) => port,
+        None if instance == DEFAULT_EXPLORER => lan_share::EXPLORER_PORT,
+        None => return Err(format!("Give explorer server '{}' a port", instance)),
+    };
+    check_explorer_port(&state_guard, &instance, port)?;
 
-    let explorer_path = get_explorer_dist_path();
-    if !explorer_path.exists() {
-        return Err(format!(
-            "Explorer dist not found at {:?}. Run 'build-explorer' first.",
-            explorer_path
-        ));
-    }
+    // The selected build; switching it later applies without a restart
+    explorer_dist::active_root()?;
 
     // Create shutdown channel
     let (shutdown_tx, shutdown_rx) = tokio::sync::oneshot::channel::<()>();
 
-    // Build the router with CORS support and API proxy
-    let cors = CorsLayer::new()
-        .allow_origin(Any)
-        .allow_methods(Any)
-        .allow_headers(Any);
-
-    let app_router = Router::new()
-        // API proxy routes
-        .route("/v1a/ws/", get(proxy_ws))
-        .route("/v1a/*path", any(proxy_api))
-        // Static files for explorer
-        .fallback_service(ServeDir::new(&explorer_path).append_index_html_on_directories(true))
-        .layer(cors);
+    // Build the router with the API proxy
+    let proxy_state = proxy::ProxyState::new(
+        app.clone(),
+        state.inner().clone(),
+        &state_guard,
+        target.clone(),
+    )?;
+    let limited = |router: axum::Router, service| {
+        router.layer(axum::middleware::from_fn_with_state(
+            rate_limit::RateLimitState::new(state_guard.rate_limits.clone(), service),
+            rate_limit::limit_requests,
+        ))
+    };
+    let tls = tls::enabled();
+    let proxy_router = proxy::router(proxy_state)
+        .layer(axum::middleware::from_fn(mock::serve_mocks))
+        .layer(axum::middleware::from_fn_with_state(
+            traffic::TrafficState::new(state_guard.traffic_recording.clone(), tls),
+            traffic::record_traffic,
+        ));
+    let mut app_router = limited(proxy_router, "proxy")
+        .merge(limited(faucet::router(state.inner().clone()), "faucet"))
+        .merge(health::router(state.inner().clone()));
+    if graphql::SETTINGS.load().enabled {
+        app_router = app_router.merge(graphql::router(state.inner().clone()));
+    }
+    let app_router = app_router
+        // Static files of the selected explorer build, pointed at this server
+        .fallback(move |req| explorer_dist::serve_dist(req, port))
+        // gzip/brotli for clients that accept it; responses the node already
+        // encoded pass through as they are
+        .layer(CompressionLayer::new())
+        .layer(cors::layer())
+        .layer(axum::middleware::from_fn_with_state(
+            access_log::AccessLogState::new(state_guard.access_log.clone(), "explorer"),
+            access_log::log_requests,
+        ))
+        .layer(TraceLayer::new_for_http());
+
+    // Loopback only, unless the default explorer is shared on the LAN
+    let bind_ip = if instance == DEFAULT_EXPLORER {
+        lan_share::bind_ip()
+    } else {
+        std::net::IpAddr::V4(std::net::Ipv4Addr::LOCALHOST)
+    };
+    let addr = SocketAddr::new(bind_ip, port);
 
-    let addr = SocketAddr::from(([127, 0, 0, 1], 3001));
+    // HTTPS when enabled, with the workspace's certificate
+    let acceptor = if tls {
+        Some(tls::acceptor(&state_guard)?)
+    } else {
+        None
+    };
 
     // Create the server
     let listener = tokio::net::TcpListener::bind(addr)
         .await
-        .map_err(|e| format!("Failed to bind to port 3001: {}", e))?;
-
-    state_guard.explorer_server_running = true;
-    state_guard.explorer_shutdown = Some(shutdown_tx);
+        .map_err(|e| format!("Failed to bind to port {}: {}", port, e))?;
+
+    let url = format!("{}://localhost:{}", tls::scheme(tls), port);
+    state_guard.explorer_servers.insert(
+        instance.clone(),
+        ExplorerServer {
+            port,
+            shutdown: Some(shutdown_tx),
+            shared_on_lan: bind_ip.is_unspecified(),
+            tls,
+            target,
+            mdns_advertisement: if bind_ip.is_unspecified() {
+                lan_share::advertise(tls)
+            } else {
+                None
+            },
+        },
+    );
+    let _ = app.emit(
+        "explorer-started",
+        serde_json::json!({ "instance": instance, "url": url, "port": port }),
+    );
 
     let app_handle = app.clone();
     let state_clone = state.inner().clone();
+    let name = instance.clone();
 
     // Spawn the server
     tokio::spawn(async move {
-        let server = axum::serve(listener, app_router).with_graceful_shutdown(async {
-            let _ = shutdown_rx.await;
-        });
+        let result = match acceptor {
+            Some(acceptor) => {
+                tls::serve(listener, app_router, acceptor, async {
+                    let _ = shutdown_rx.await;
+                })
+                .await;
+                Ok(())
+            }
+            None => {
+                axum::serve(
+                    listener,
+                    app_router.into_make_service_with_connect_info::<SocketAddr>(),
+                )
+                .with_graceful_shutdown(async {
+                    let _ = shutdown_rx.await;
+                })
+                .await
+            }
+        };
 
-        if let Err(e) = server.await {
-            let _ = app_handle.emit("explorer-error", format!("Explorer server error: {}", e));
+        if let Err(e) = result {
+            let _ = app_handle.emit(
+                "explorer-error",
+                format!("Explorer server '{}' error: {}", name, e),
+            );
         }
 
-        // Reset state when server stops
+        // Forget the server when it stops, unless a new one took its name
         {
             let mut state_guard = state_clone.lock().await;
-            state_guard.explorer_server_running = false;
-            state_guard.explorer_shutdown = None;
+            if state_guard
+                .explorer_servers
+                .get(&name)
+                .is_some_and(|server| {
+                    server
+                        .shutdown
+                        .as_ref()
+                        .is_some_and(|shutdown| shutdown.is_closed())
+                })
+            {
+                state_guard.explorer_servers.remove(&name);
+            }
         }
 
-        let _ = app_handle.emit("explorer-terminated", ());
+        let _ = app_handle.emit("explorer-terminated", &name);
     });
 
-    Ok("Explorer server started on http://localhost:3001".to_string())
+    if bind_ip.is_unspecified() {
+        return Ok(format!(
+            "Explorer server started on {}://0.0.0.0:{}. WARNING: {}",
+            tls::scheme(tls),
+            port,
+            lan_share::warning()
+        ));
+    }
+    if instance == DEFAULT_EXPLORER {
+        return Ok(format!("Explorer server started on {}", url));
+    }
+    Ok(format!("Explorer server '{}' started on {}", instance, url))
 }
 
-// Stop the explorer HTTP server
+// Stop an explorer HTTP server (the default one when no instance is given)
 #[tauri::command]
-async fn stop_explorer_server(state: tauri::State<'_, SharedState>) -> Result<String, String> {
+async fn stop_explorer_server(
+    state: tauri::State<'_, SharedState>,
+    instance: Option<String>,
+) -> Result<String, String> {
+    let name = instance.as_deref().unwrap_or(DEFAULT_EXPLORER);
     let mut state_guard = state.lock().await;
 
-    if !state_guard.explorer_server_running {
-        return Err("Explorer server is not running".to_string());
-    }
+    let Some(mut server) = state_guard.explorer_servers.remove(name) else {
+        if name == DEFAULT_EXPLORER {
+            return Err("Explorer server is not running".to_string());
+        }
+        return Err(format!("Explorer server '{}' is not running", name));
+    };
 
-    // Send shutdown signal
-    if let Some(shutdown_tx) = state_guard.explorer_shutdown.take() {
+    // Send shutdown signal; dropping the server withdraws its mDNS service
+    if let Some(shutdown_tx) = server.shutdown.take() {
         let _ = shutdown_tx.send(());
     }
 
-    state_guard.explorer_server_running = false;
+    if name == DEFAULT_EXPLORER {
+        return Ok("Explorer server stopped".to_string());
+    }
+    Ok(format!("Explorer server '{}' stopped", name))
+}
+
+// List running explorer servers
+#[tauri::command]
+async fn list_explorer_servers(
+    state: tauri::State<'_, SharedState>,
+) -> Result<Vec<ExplorerServerStatus>, String> {
+    let state_guard = state.lock().await;
+
+    let mut servers: Vec<ExplorerServerStatus> = state_guard
+        .explorer_servers
+        .iter()
+        .map(|(name, server)| {
+            let target = server.target.as_ref().unwrap_or(&state_guard.proxy_target);
+            ExplorerServerStatus {
+                instance: name.clone(),
+                port: server.port,
+                url: format!("{}://localhost:{}", tls::scheme(server.tls), server.port),
+                tls: server.tls,
+                shared_on_lan: server.shared_on_lan,
+                target: server.target.clone(),
+                upstream: proxy::resolve(target, &state_guard),
+            }
+        })
+        .collect();
+    servers.sort_by(|a, b| a.instance.cmp(&b.instance));
 
-    Ok("Explorer server stopped".to_string())
+    Ok(servers)
 }
 
 // Helper function to kill a process by PID
@@ -1954,20 +2752,117 @@ const MCP_SERVER_PORT: u16 = 9876;
 
 #[cfg_attr(mobile, tauri::mobile_entry_point)]
 pub fn run() {
+    telemetry::init();
     let state = Arc::new(Mutex::new(AppState::default())) as SharedState;
     let cleanup_state = state.clone();
     let mcp_state = state.clone();
+    let control_api_state = state.clone();
+    let event_bus_state = state.clone();
+    let service_logs = state.blocking_lock().service_logs.clone();
+    let balances_state = state.clone();
+    let notifications_state = state.clone();
+    let snapshot_schedule_state = state.clone();
+    let loadgen_state = state.clone();
+    let events_state = state.clone();
+    let subscriptions_state = state.clone();
+    let mempool_state = state.clone();
+    let confirmations_state = state.clone();
 
     tauri::Builder::default()
         .plugin(tauri_plugin_opener::init())
+        .plugin(tauri_plugin_notification::init())
         .manage(state)
-        .invoke_handler(tauri::generate_handler![
+        .invoke_handler(recorder::capture(tauri::generate_handler![
             start_node,
             stop_node,
             start_miner,
             stop_miner,
             get_node_status,
             get_miner_status,
+            paced_mining::start_paced_mining,
+            paced_mining::stop_paced_mining,
+            paced_mining::get_paced_mining_status,
+            miner_options::get_miner_options,
+            miner_options::set_miner_options,
+            stratum_lan::get_stratum_connection_info,
+            stratum_tap::get_stratum_jobs,
+            peers::list_peers,
+            peers::add_peer,
+            peers::remove_peer,
+            peers::set_p2p_listen_port,
+            network_chaos::get_network_chaos,
+            network_chaos::pause_p2p,
+            network_chaos::resume_p2p,
+            network_chaos::set_p2p_latency,
+            network_chaos::partition_network,
+            network_chaos::heal_network,
+            snapshots::save_snapshot,
+            snapshots::restore_snapshot,
+            snapshots::list_snapshots,
+            snapshots::delete_snapshot,
+            snapshot_schedule::get_snapshot_schedule,
+            snapshot_schedule::set_snapshot_schedule,
+            data_usage::get_data_usage,
+            data_usage::compact_data,
+            fixtures::export_chain,
+            fixtures::import_fixture,
+            bootstrap::bootstrap_environment,
+            loadgen::start_load,
+            loadgen::stop_load,
+            loadgen::get_load_stats,
+            scenario::run_scenario,
+            recorder::start_recording,
+            recorder::stop_recording,
+            recorder::get_recording,
+            recorder::export_recording,
+            time_travel::advance_time,
+            time_travel::set_time,
+            time_travel::get_clock,
+            events::get_node_events,
+            subscriptions::subscribe_address,
+            subscriptions::unsubscribe_address,
+            subscriptions::list_address_subscriptions,
+            mempool::get_mempool,
+            confirmations::get_tx_confirmation,
+            confirmations::unwatch_tx,
+            confirmations::list_watched_txs,
+            poa::generate_poa_signer,
+            poa::start_poa_node,
+            poa::stop_poa_node,
+            poa::get_poa_status,
+            explorer_service::get_explorer_service_status,
+            explorer_service::set_explorer_service_config,
+            explorer_service::start_explorer_service,
+            explorer_service::stop_explorer_service,
+            explorer_dist::list_explorer_dists,
+            explorer_dist::download_explorer_dist,
+            explorer_dist::set_explorer_dist,
+            explorer_dist::delete_explorer_dist,
+            compat::check_compatibility,
+            features::get_feature_states,
+            features::set_enabled_features,
+            nano::get_nano_contracts_mode,
+            nano::set_nano_contracts_mode,
+            nano::list_blueprints,
+            nano::get_blueprint_source,
+            nano::upload_blueprint,
+            nano::list_uploaded_blueprints,
+            nano::nc_create,
+            nano::nc_execute,
+            nano::nc_get_state,
+            nano::nc_get_history,
+            search::search_chain,
+            blocks::get_recent_blocks,
+            stratum_lan::set_stratum_lan,
+            list_miners,
+            miner_metrics::get_miner_session_stats,
+            mining_report::get_mining_report,
+            mining_info::get_mining_info,
+            benchmark::benchmark_miner,
+            auto_miner::get_auto_miner_settings,
+            auto_miner::set_auto_start_miner,
+            reorg::simulate_reorg,
+            rewards::unlock_rewards,
             get_state,
             reset_data,
             get_wallet_addresses,
@@ -1975,9 +2870,27 @@ pub fn run() {
             send_tx,
             start_explorer_server,
             stop_explorer_server,
+            list_explorer_servers,
+            logging::get_log_settings,
+            logging::set_log_settings,
+            logging::list_log_files,
+            logging::get_logs,
+            lan_share::get_lan_share_info,
+            tls::get_tls_info,
+            tls::set_tls,
+            tls::regenerate_tls_cert,
+            tls::export_tls_cert,
+            lan_share::set_lan_share,
+            lan_share::get_connection_qr,
+            proxy::get_proxy_metrics,
+            proxy::get_proxy_stats,
+            proxy::close_proxy_ws,
+            proxy::get_proxy_upstream,
+            proxy::set_proxy_upstream,
             start_headless,
             stop_headless,
             get_headless_status,
+            list_headless_instances,
             generate_seed,
             create_headless_wallet,
             get_headless_wallet_status,
@@ -1985,14 +2898,127 @@ pub fn run() {
             get_headless_wallet_addresses,
             headless_wallet_send_tx,
             close_headless_wallet,
-        ])
-        .setup(move |_app| {
+            secrets::get_secrets_consent,
+            secrets::set_secrets_consent,
+            secrets::save_api_key,
+            secrets::get_api_key,
+            secrets::delete_api_key,
+            secrets::forget_all_secrets,
+            wallets::import_wallet,
+            backup::export_wallet_backup,
+            backup::import_wallet_backup,
+            wallets::list_registered_wallets,
+            wallets::set_wallet_auto_start,
+            wallets::get_wallet_settings,
+            wallets::set_wallet_settings,
+            wallets::unregister_wallet,
+            multisig::get_multisig_xpub,
+            multisig::list_multisig_configs,
+            multisig::create_multisig_wallet,
+            multisig::create_multisig_proposal,
+            multisig::get_multisig_signatures,
+            multisig::sign_and_push_multisig_tx,
+            multisig::send_multisig_tx,
+            transactions::build_and_send_tx,
+            transactions::estimate_tx,
+            transactions::submit_signed_tx,
+            swap::atomic_swap,
+            wallet_sync::get_wallet_sync_progress,
+            tokens::create_token,
+            tokens::create_nft,
+            tokens::nft_gallery,
+            tokens::list_tokens,
+            tokens::register_token,
+            tokens::get_token_balances,
+            tokens::get_tx_history,
+            payments::create_payment_request,
+            payments::watch_address,
+            qr::get_address_qr,
+            address::validate_address,
+            faucet::get_faucet_settings,
+            faucet::set_faucet_address_rotation,
+            faucet::faucet_distribute,
+            faucet::set_public_faucet_settings,
+            control_api::get_control_api_info,
+            control_api::set_control_api,
+            control_api::regenerate_control_api_token,
+            graphql::get_graphql_settings,
+            graphql::set_graphql,
+            traffic::start_traffic_recording,
+            traffic::stop_traffic_recording,
+            traffic::get_traffic_recording_status,
+            traffic::get_traffic_entries,
+            traffic::export_traffic_recording,
+            mock::get_proxy_mocks,
+            mock::set_proxy_mocks,
+            mock::mock_from_recording,
+            rate_limit::get_rate_limit_settings,
+            rate_limit::set_rate_limit_settings,
+            cors::get_cors_settings,
+            cors::set_cors_settings,
+            telemetry::get_telemetry_settings,
+            telemetry::set_telemetry,
+            tokens::mint_tokens,
+            tokens::melt_tokens,
+            tokens::delegate_authority,
+            tokens::list_authority_holders,
+            tokens::get_token_authorities,
+            notifications::get_notification_settings,
+            notifications::set_block_notifications,
+        ]))
+        .setup(move |app| {
             // Start the MCP server in the background using Tauri's async runtime
             tauri::async_runtime::spawn(async move {
                 if let Err(e) = mcp::start_mcp_server(mcp_state, MCP_SERVER_PORT).await {
                     eprintln!("Failed to start MCP server: {}", e);
                 }
             });
+            // Collect app events and service state changes on the event bus
+            tauri::async_runtime::spawn(event_bus::run(app.handle().clone(), event_bus_state));
+            // Keep service output in the workspace's log files
+            logging::listen(app.handle(), service_logs);
+            // Start the control API when it's enabled
+            if control_api::SETTINGS.load().enabled {
+                tauri::async_runtime::spawn(async move {
+                    if let Err(e) = control_api::start(&control_api_state).await {
+                        eprintln!("Failed to start control API: {}", e);
+                    }
+                });
+            }
+            // Emit wallet-balance-changed events while the node runs
+            tauri::async_runtime::spawn(balances::watch_balances(
+                app.handle().clone(),
+                balances_state,
+            ));
+            // Emit block-found events (and notifications) for blocks Forge's miners find
+            tauri::async_runtime::spawn(notifications::forward_blocks_found(
+                app.handle().clone(),
+                notifications_state,
+            ));
+            // Take automatic snapshots when a schedule is set
+            tauri::async_runtime::spawn(snapshot_schedule::run_schedule(
+                app.handle().clone(),
+                snapshot_schedule_state,
+            ));
+            // Emit loadgen-stats events while load generation runs
+            tauri::async_runtime::spawn(loadgen::emit_stats(app.handle().clone(), loadgen_state));
+            // Emit new-vertex, reorg and tx-voided events from the node's event queue
+            tauri::async_runtime::spawn(events::consume_events(app.handle().clone(), events_state));
+            // Emit address-activity events for subscribed addresses
+            tauri::async_runtime::spawn(subscriptions::follow_addresses(
+                app.handle().clone(),
+                subscriptions_state,
+            ));
+            // Track first-seen times and emit mempool-size-changed events
+            tauri::async_runtime::spawn(mempool::watch_mempool(
+                app.handle().clone(),
+                mempool_state,
+            ));
+            // Emit watched-tx-voided events for watched transactions
+            tauri::async_runtime::spawn(confirmations::watch_voided(
+                app.handle().clone(),
+                confirmations_state,
+            ));
             Ok(())
         })
         .build(tauri::generate_context!())
@@ -2002,20 +3028,40 @@ pub fn run() {
                 // Cleanup: kill any running processes
                 let state = cleanup_state.blocking_lock();
 
-                if let Some(pid) = state.miner_child_id {
-                    eprintln!("Cleaning up miner process (PID: {})", pid);
-                    kill_process(pid);
+                for (name, miner) in state.miners.iter() {
+                    if let Some(pid) = miner.child_id {
+                        eprintln!("Cleaning up miner '{}' (PID: {})", name, pid);
+                        kill_process(pid);
+                    }
                 }
 
-                if let Some(pid) = state.headless_child_id {
-                    eprintln!("Cleaning up wallet-headless process (PID: {})", pid);
-                    kill_process(pid);
+                for (name, instance) in state.headless_instances.iter() {
+                    if let Some(pid) = instance.child_id {
+                        eprintln!(
+                            "Cleaning up wallet-headless instance '{}' (PID: {})",
+                            name, pid
+                        );
+                        kill_process(pid);
+                    }
                 }
 
                 if let Some(pid) = state.node_child_id {
                     eprintln!("Cleaning up node process (PID: {})", pid);
                     kill_process(pid);
                 }
+
+                if let Some(pid) = poa::child_id(&state) {
+                    eprintln!("Cleaning up PoA node process (PID: {})", pid);
+                    kill_process(pid);
+                }
+
+                if let Some(pid) = explorer_service::child_id(&state) {
+                    eprintln!("Cleaning up explorer-service (PID: {})", pid);
+                    explorer_service::cleanup(&state);
+                    kill_process(pid);
+                }
+
+                telemetry::shutdown();
             }
         });
 }
@@ -0,0 +1,514 @@
+//! Transaction load generator
+//!
+//! `start_load` keeps a pool of wallets (`loadgen-1`, `loadgen-2`, ...) on
+//! wallet-headless, tops them up from the faucet, and then sends small HTR
+//! transfers between them at a target rate until stopped or until the
+//! duration runs out. The rate follows a pattern (constant, a linear ramp, or
+//! bursts). Each wallet has at most one transfer in flight, so when the pool
+//! can't keep up the tick is counted as skipped rather than queued. Latency is
+//! the time wallet-headless takes to build, solve and push a transfer.
+//!
+//! Stats are published once a second and emitted as `loadgen-stats`.
+
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter};
+use tokio::sync::{oneshot, watch};
+
+use crate::amount::HtrAmount;
+use crate::{bootstrap, faucet, wallets, SharedState};
+
+const WALLET_PREFIX: &str = "loadgen";
+const MAX_TPS: f64 = 100.0;
+const MIN_WALLETS: u32 = 2;
+const MAX_WALLETS: u32 = 50;
+const DEFAULT_WALLETS: u32 = 5;
+// Each pool wallet is topped up to this when it runs low
+const FUNDING: HtrAmount = HtrAmount::from_cents(10_000);
+const LOW_BALANCE: HtrAmount = HtrAmount::from_cents(100);
+const TRANSFER_AMOUNT: HtrAmount = HtrAmount::from_cents(1);
+const TICK: Duration = Duration::from_millis(100);
+const PUBLISH_INTERVAL: Duration = Duration::from_secs(1);
+// Window for the achieved rate
+const RATE_WINDOW: Duration = Duration::from_secs(10);
+// Latencies kept for the percentiles
+const MAX_SAMPLES: usize = 2000;
+// How long a stopped run waits for transfers still in flight
+const DRAIN_TIMEOUT: Duration = Duration::from_secs(30);
+// Ramp length when the run has no duration
+const DEFAULT_RAMP_SECS: u64 = 60;
+const BURST_ON_SECS: u64 = 5;
+const BURST_PERIOD_SECS: u64 = 15;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LoadPattern {
+    // The target rate the whole time
+    #[default]
+    Constant,
+    // From zero up to the target rate over the duration (or a minute), then steady
+    Ramp,
+    // The target rate for 5 seconds out of every 15, idle in between
+    Burst,
+}
+
+impl LoadPattern {
+    fn rate(self, tps: f64, elapsed: Duration, duration: Option<Duration>) -> f64 {
+        match self {
+            LoadPattern::Constant => tps,
+            LoadPattern::Ramp => {
+                let ramp = duration.unwrap_or(Duration::from_secs(DEFAULT_RAMP_SECS));
+                tps * (elapsed.as_secs_f64() / ramp.as_secs_f64()).min(1.0)
+            }
+            LoadPattern::Burst => {
+                if elapsed.as_secs() % BURST_PERIOD_SECS < BURST_ON_SECS {
+                    tps
+                } else {
+                    0.0
+                }
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LatencyPercentiles {
+    pub p50_ms: Option<u64>,
+    pub p90_ms: Option<u64>,
+    pub p99_ms: Option<u64>,
+    pub max_ms: Option<u64>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LoadStats {
+    pub running: bool,
+    pub pattern: LoadPattern,
+    pub tps: f64,
+    // Rate the pattern asks for right now
+    pub target_tps: f64,
+    // Successful transfers per second over the last 10 seconds
+    pub achieved_tps: f64,
+    pub elapsed_secs: u64,
+    pub duration_secs: Option<u64>,
+    pub sent: u64,
+    pub succeeded: u64,
+    pub failed: u64,
+    // Ticks dropped because every wallet had a transfer in flight
+    pub skipped: u64,
+    pub latency: LatencyPercentiles,
+    pub last_error: Option<String>,
+    pub wallets: Vec<String>,
+}
+
+/// Load generator state kept in `AppState`
+pub struct LoadGen {
+    stats: watch::Sender<LoadStats>,
+    // Set from the start of a run (including its setup) until it ends
+    shutdown: Option<oneshot::Sender<()>>,
+}
+
+impl Default for LoadGen {
+    fn default() -> Self {
+        Self {
+            stats: watch::channel(LoadStats::default()).0,
+            shutdown: None,
+        }
+    }
+}
+
+struct PoolWallet {
+    wallet_id: String,
+    address: String,
+    busy: AtomicBool,
+}
+
+// Counters shared with the transfer tasks
+struct Collector {
+    started: Instant,
+    sent: u64,
+    succeeded: u64,
+    failed: u64,
+    skipped: u64,
+    latencies: VecDeque<u64>,
+    completions: VecDeque<Instant>,
+    last_error: Option<String>,
+}
+
+impl Collector {
+    fn new() -> Self {
+        Self {
+            started: Instant::now(),
+            sent: 0,
+            succeeded: 0,
+            failed: 0,
+            skipped: 0,
+            latencies: VecDeque::new(),
+            completions: VecDeque::new(),
+            last_error: None,
+        }
+    }
+
+    fn record(&mut self, result: Result<Duration, String>) {
+        match result {
+            Ok(latency) => {
+                self.succeeded += 1;
+                if self.latencies.len() == MAX_SAMPLES {
+                    self.latencies.pop_front();
+                }
+                self.latencies.push_back(latency.as_millis() as u64);
+                self.completions.push_back(Instant::now());
+            }
+            Err(e) => {
+                self.failed += 1;
+                self.last_error = Some(e);
+            }
+        }
+    }
+
+    fn percentiles(&self) -> LatencyPercentiles {
+        let mut samples: Vec<u64> = self.latencies.iter().copied().collect();
+        samples.sort_unstable();
+        let at = |p: usize| {
+            samples
+                .len()
+                .checked_sub(1)
+                .map(|last| samples[last * p / 100])
+        };
+        LatencyPercentiles {
+            p50_ms: at(50),
+            p90_ms: at(90),
+            p99_ms: at(99),
+            max_ms: samples.last().copied(),
+        }
+    }
+
+    fn achieved_tps(&mut self) -> f64 {
+        while self
+            .completions
+            .front()
+            .is_some_and(|at| at.elapsed() > RATE_WINDOW)
+        {
+            self.completions.pop_front();
+        }
+        let window = self.started.elapsed().min(RATE_WINDOW).as_secs_f64();
+        if window > 0.0 {
+            self.completions.len() as f64 / window
+        } else {
+            0.0
+        }
+    }
+}
+
+struct Run {
+    tps: f64,
+    pattern: LoadPattern,
+    duration: Option<Duration>,
+    base_url: String,
+    pool: Arc<Vec<PoolWallet>>,
+}
+
+impl Run {
+    fn stats(&self, collector: &mut Collector, target_tps: f64, running: bool) -> LoadStats {
+        LoadStats {
+            running,
+            pattern: self.pattern,
+            tps: self.tps,
+            target_tps,
+            achieved_tps: collector.achieved_tps(),
+            elapsed_secs: collector.started.elapsed().as_secs(),
+            duration_secs: self.duration.map(|d| d.as_secs()),
+            sent: collector.sent,
+            succeeded: collector.succeeded,
+            failed: collector.failed,
+            skipped: collector.skipped,
+            latency: collector.percentiles(),
+            last_error: collector.last_error.clone(),
+            wallets: self.pool.iter().map(|w| w.wallet_id.clone()).collect(),
+        }
+    }
+
+    // Start a transfer from the next idle wallet, if any
+    fn dispatch(&self, cursor: &mut usize, collector: &Arc<Mutex<Collector>>) {
+        let size = self.pool.len();
+        let sender = (0..size).map(|k| (*cursor + k) % size).find(|&i| {
+            self.pool[i]
+                .busy
+                .compare_exchange(false, true, Ordering::AcqRel, Ordering::Acquire)
+                .is_ok()
+        });
+        let mut counters = collector.lock().unwrap_or_else(|e| e.into_inner());
+        let Some(sender) = sender else {
+            counters.skipped += 1;
+            return;
+        };
+        counters.sent += 1;
+        drop(counters);
+        *cursor = (sender + 1) % size;
+
+        let offset = 1 + bootstrap::random_below(size as u64 - 1) as usize;
+        let recipient = (sender + offset) % size;
+        let pool = self.pool.clone();
+        let base_url = self.base_url.clone();
+        let collector = collector.clone();
+        tokio::spawn(async move {
+            let started = Instant::now();
+            let result = bootstrap::send_htr(
+                &base_url,
+                &pool[sender].wallet_id,
+                &pool[recipient].address,
+                TRANSFER_AMOUNT,
+            )
+            .await
+            .map(|_| started.elapsed());
+            collector
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .record(result);
+            pool[sender].busy.store(false, Ordering::Release);
+        });
+    }
+}
+
+// Open the pool wallets (reusing their seeds from earlier runs) and top up
+// the ones running low
+async fn prepare_pool(
+    state: &SharedState,
+    base_url: &str,
+    size: u32,
+    instance: Option<&str>,
+) -> Result<Vec<PoolWallet>, String> {
+    let mut pool = Vec::new();
+    for i in 1..=size {
+        let wallet_id = format!("{}-{}", WALLET_PREFIX, i);
+        let seed = wallets::wallet_seed(&wallet_id).await?;
+        bootstrap::open_wallet(base_url, &wallet_id, seed.as_deref(), instance).await?;
+        pool.push(PoolWallet {
+            address: bootstrap::wallet_address(base_url, &wallet_id).await?,
+            wallet_id,
+            busy: AtomicBool::new(false),
+        });
+    }
+
+    let mut low = Vec::new();
+    for wallet in &pool {
+        let balance = bootstrap::wallet_balance(base_url, &wallet.wallet_id).await?;
+        if balance < LOW_BALANCE {
+            low.push(wallet);
+        }
+    }
+    if low.is_empty() {
+        return Ok(pool);
+    }
+
+    bootstrap::ensure_faucet_funds(
+        state,
+        HtrAmount::from_cents(FUNDING.cents() * low.len() as u64),
+    )
+    .await?;
+    let payments: Vec<faucet::FaucetPayment> = low
+        .iter()
+        .map(|wallet| faucet::FaucetPayment {
+            address: wallet.address.clone(),
+            amount: FUNDING,
+            timelock: None,
+        })
+        .collect();
+    faucet::faucet_distribute_internal(state, &payments).await?;
+    for wallet in &low {
+        bootstrap::wait_for_balance(base_url, &wallet.wallet_id, LOW_BALANCE).await?;
+    }
+    Ok(pool)
+}
+
+async fn run_load(state: SharedState, run: Run, mut shutdown: oneshot::Receiver<()>) {
+    let stats = state.lock().await.loadgen.stats.clone();
+    let collector = Arc::new(Mutex::new(Collector::new()));
+    let mut cursor = 0;
+    // Fractional transfers owed at the current rate
+    let mut owed = 0.0;
+    let mut target_tps = 0.0;
+    let mut last_publish = Instant::now();
+
+    loop {
+        tokio::select! {
+            _ = &mut shutdown => break,
+            _ = tokio::time::sleep(TICK) => {}
+        }
+        let elapsed = collector
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .started
+            .elapsed();
+        if run.duration.is_some_and(|duration| elapsed >= duration) {
+            break;
+        }
+
+        target_tps = run.pattern.rate(run.tps, elapsed, run.duration);
+        owed += target_tps * TICK.as_secs_f64();
+        while owed >= 1.0 {
+            owed -= 1.0;
+            run.dispatch(&mut cursor, &collector);
+        }
+
+        if last_publish.elapsed() >= PUBLISH_INTERVAL {
+            let mut counters = collector.lock().unwrap_or_else(|e| e.into_inner());
+            stats.send_replace(run.stats(&mut counters, target_tps, true));
+            last_publish = Instant::now();
+        }
+    }
+
+    // Let transfers in flight land so the final numbers add up
+    let drain_started = Instant::now();
+    while run.pool.iter().any(|w| w.busy.load(Ordering::Acquire))
+        && drain_started.elapsed() < DRAIN_TIMEOUT
+    {
+        tokio::time::sleep(TICK).await;
+    }
+
+    {
+        let mut counters = collector.lock().unwrap_or_else(|e| e.into_inner());
+        stats.send_replace(run.stats(&mut counters, target_tps, false));
+    }
+
+    // Clear the handle unless a new run has taken its place
+    drop(shutdown);
+    let mut state_guard = state.lock().await;
+    if state_guard
+        .loadgen
+        .shutdown
+        .as_ref()
+        .is_some_and(|sender| sender.is_closed())
+    {
+        state_guard.loadgen.shutdown = None;
+    }
+}
+
+/// Set up the wallet pool and start generating `tps` transfers per second
+/// following `pattern`, for `duration_secs` or until stopped
+pub async fn start_load_internal(
+    state: &SharedState,
+    tps: f64,
+    duration_secs: Option<u64>,
+    pattern: LoadPattern,
+    wallets: Option<u32>,
+    instance: Option<&str>,
+) -> Result<String, String> {
+    if !(tps > 0.0 && tps <= MAX_TPS) {
+        return Err(format!("tps must be above 0 and at most {}", MAX_TPS));
+    }
+    if duration_secs == Some(0) {
+        return Err("Duration must be at least 1 second".to_string());
+    }
+    let wallet_count = wallets.unwrap_or(DEFAULT_WALLETS);
+    if !(MIN_WALLETS..=MAX_WALLETS).contains(&wallet_count) {
+        return Err(format!(
+            "The wallet pool must have between {} and {} wallets",
+            MIN_WALLETS, MAX_WALLETS
+        ));
+    }
+
+    let (shutdown_tx, shutdown_rx) = oneshot::channel();
+    {
+        let mut state_guard = state.lock().await;
+        if state_guard.loadgen.shutdown.is_some() {
+            return Err("Load generation is already running".to_string());
+        }
+        state_guard.loadgen.shutdown = Some(shutdown_tx);
+    }
+
+    let setup = async {
+        bootstrap::ensure_node_ready(state).await?;
+        let base_url = bootstrap::ensure_headless(state, instance).await?;
+        let pool = prepare_pool(state, &base_url, wallet_count, instance).await?;
+        Ok::<_, String>((base_url, pool))
+    };
+    let (base_url, pool) = match setup.await {
+        Ok(ready) => ready,
+        Err(e) => {
+            state.lock().await.loadgen.shutdown = None;
+            return Err(e);
+        }
+    };
+
+    tokio::spawn(run_load(
+        state.clone(),
+        Run {
+            tps,
+            pattern,
+            duration: duration_secs.map(Duration::from_secs),
+            base_url,
+            pool: Arc::new(pool),
+        },
+        shutdown_rx,
+    ));
+
+    Ok(format!(
+        "Load generation started: {} tps ({:?}) across {} wallets",
+        tps, pattern, wallet_count
+    ))
+}
+
+/// Stop load generation; the final stats are published once transfers in
+/// flight have landed
+pub async fn stop_load_internal(state: &SharedState) -> Result<String, String> {
+    let mut state_guard = state.lock().await;
+    let Some(shutdown) = state_guard.loadgen.shutdown.take() else {
+        return Ok("Load generation is not running".to_string());
+    };
+    let _ = shutdown.send(());
+    Ok("Load generation stopped".to_string())
+}
+
+/// Stats of the current or last run
+pub async fn get_load_stats_internal(state: &SharedState) -> LoadStats {
+    state.lock().await.loadgen.stats.borrow().clone()
+}
+
+/// Emit `loadgen-stats` whenever a run publishes stats, for the lifetime of the app
+pub async fn emit_stats(app: AppHandle, state: SharedState) {
+    let mut stats = state.lock().await.loadgen.stats.subscribe();
+    while stats.changed().await.is_ok() {
+        let current = stats.borrow_and_update().clone();
+        let _ = app.emit("loadgen-stats", &current);
+    }
+}
+
+// ============================================================================
+// Tauri Commands
+// ============================================================================
+
+// Generate HTR transfers among a wallet pool at a target rate (duration in seconds)
+#[tauri::command]
+pub async fn start_load(
+    state: tauri::State<'_, SharedState>,
+    tps: f64,
+    duration: Option<u64>,
+    pattern: Option<LoadPattern>,
+    wallets: Option<u32>,
+    instance: Option<String>,
+) -> Result<String, String> {
+    start_load_internal(
+        &state,
+        tps,
+        duration,
+        pattern.unwrap_or_default(),
+        wallets,
+        instance.as_deref(),
+    )
+    .await
+}
+
+// Stop generating load
+#[tauri::command]
+pub async fn stop_load(state: tauri::State<'_, SharedState>) -> Result<String, String> {
+    stop_load_internal(&state).await
+}
+
+// Get achieved TPS, latency percentiles and failures of the current or last run
+#[tauri::command]
+pub async fn get_load_stats(state: tauri::State<'_, SharedState>) -> Result<LoadStats, String> {
+    Ok(get_load_stats_internal(&state).await)
+}
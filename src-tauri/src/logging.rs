@@ -0,0 +1,465 @@
+//! Log files per service
+//!
+//! Service output only reaches the UI as Tauri events, which are gone once
+//! emitted. Every line is also appended, with a UTC timestamp, to a file per
+//! service in the workspace's `logs/` directory (inside the data dir):
+//! `node.log`, `miner.log`, `headless.log`, `poa-node.log` and
+//! `explorer-service.log` from the process output events, and `explorer.log`,
+//! `mcp.log` and `control.log` from the servers' access logs.
+//!
+//! A file that would grow past `max_file_bytes` is rotated to `node.log.1`
+//! (shifting older ones to `.2`, `.3`, ...), keeping `max_files` rotated files;
+//! with `max_age_days` set, older rotated files are deleted too. Settings
+//! live in `logging.json` in the app directory and apply right away.
+//! Snapshots leave `logs/` out and restoring one keeps it.
+//!
+//! The last `MAX_LINES` lines of each service are also kept in memory, so
+//! output emitted before a listener attached (or before a page reload) can be
+//! fetched with `get_logs(service, lines, since, filter)`. That covers the
+//! process output services and, through access_log.rs, the servers' requests.
+
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap, VecDeque};
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex as StdMutex, OnceLock};
+use std::time::{Duration, SystemTime};
+use tauri::{AppHandle, Listener};
+
+use crate::access_log::LogLine;
+use crate::settings::JsonSettings;
+use crate::SharedState;
+
+/// Directory of the log files, inside the workspace's data dir
+pub const LOGS_DIR: &str = "logs";
+
+/// Services whose process output is kept, besides the access-logged servers
+pub const SERVICES: [&str; 5] = ["node", "miner", "headless", "poa-node", "explorer-service"];
+
+// Lines kept in memory per service
+const MAX_LINES: usize = 5_000;
+const DEFAULT_LINES: usize = 200;
+
+// Process output events and the file each goes to
+const EVENTS: [(&str, &str); 5] = [
+    ("node-log", "node"),
+    ("miner-log", "miner"),
+    ("headless-log", "headless"),
+    ("poa-node-log", "poa-node"),
+    ("explorer-service-log", "explorer-service"),
+];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct LogSettings {
+    pub enabled: bool,
+    // Size at which a file is rotated
+    pub max_file_bytes: u64,
+    // Rotated files kept per service
+    pub max_files: u32,
+    // Rotated files older than this are deleted
+    pub max_age_days: Option<u32>,
+}
+
+impl Default for LogSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            max_file_bytes: 10 * 1024 * 1024,
+            max_files: 5,
+            max_age_days: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LogFileInfo {
+    pub service: String,
+    pub name: String,
+    pub path: String,
+    pub size_bytes: u64,
+    // Unix time in seconds
+    pub modified: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LogFiles {
+    pub dir: String,
+    pub settings: LogSettings,
+    // By service: the current file, then rotated ones from newest to oldest
+    pub files: Vec<LogFileInfo>,
+}
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct LogQuery {
+    // Most recent matching lines to return
+    pub lines: Option<usize>,
+    // Only lines after this Unix time in milliseconds
+    pub since: Option<u64>,
+    // Only lines containing this text, ignoring case
+    pub filter: Option<String>,
+}
+
+impl LogQuery {
+    /// The newest lines matching the query, oldest first
+    pub fn select(&self, lines: impl DoubleEndedIterator<Item = LogLine>) -> Vec<LogLine> {
+        let filter = self
+            .filter
+            .as_deref()
+            .map(str::to_lowercase)
+            .filter(|filter| !filter.is_empty());
+        let mut selected: Vec<LogLine> = lines
+            .rev()
+            .filter(|line| self.since.is_none_or(|since| line.timestamp > since))
+            .filter(|line| {
+                filter
+                    .as_ref()
+                    .is_none_or(|filter| line.message.to_lowercase().contains(filter))
+            })
+            .take(self.lines.unwrap_or(DEFAULT_LINES).min(MAX_LINES))
+            .collect();
+        selected.reverse();
+        selected
+    }
+}
+
+/// Recent process output per service, kept in `AppState`
+#[derive(Default)]
+pub struct ServiceLogs {
+    lines: BTreeMap<&'static str, VecDeque<LogLine>>,
+}
+
+pub type SharedServiceLogs = Arc<StdMutex<ServiceLogs>>;
+
+impl ServiceLogs {
+    fn record(&mut self, service: &'static str, message: String) {
+        let lines = self.lines.entry(service).or_default();
+        if lines.len() >= MAX_LINES {
+            lines.pop_front();
+        }
+        lines.push_back(LogLine {
+            timestamp: crate::access_log::unix_millis(),
+            service: service.to_string(),
+            message,
+            access: None,
+        });
+    }
+}
+
+struct LogFile {
+    file: fs::File,
+    size: u64,
+}
+
+// Open files of the current workspace, by service
+struct Logger {
+    dir: PathBuf,
+    files: HashMap<String, LogFile>,
+}
+
+static SETTINGS: JsonSettings<LogSettings> = JsonSettings::new("logging.json", "log settings");
+static LOGGER: OnceLock<StdMutex<Logger>> = OnceLock::new();
+
+fn logger() -> std::sync::MutexGuard<'static, Logger> {
+    LOGGER
+        .get_or_init(|| {
+            StdMutex::new(Logger {
+                dir: crate::get_default_data_dir().join(LOGS_DIR),
+                files: HashMap::new(),
+            })
+        })
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+}
+
+// `2026-01-31T12:34:56.789Z`
+fn timestamp() -> String {
+    let now = time::OffsetDateTime::now_utc();
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.{:03}Z",
+        now.year(),
+        u8::from(now.month()),
+        now.day(),
+        now.hour(),
+        now.minute(),
+        now.second(),
+        now.millisecond()
+    )
+}
+
+fn file_path(dir: &Path, service: &str, index: u32) -> PathBuf {
+    match index {
+        0 => dir.join(format!("{}.log", service)),
+        index => dir.join(format!("{}.log.{}", service, index)),
+    }
+}
+
+fn open(dir: &Path, service: &str) -> std::io::Result<LogFile> {
+    fs::create_dir_all(dir)?;
+    let file = fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(file_path(dir, service, 0))?;
+    let size = file.metadata()?.len();
+    Ok(LogFile { file, size })
+}
+
+// Rotated files of a service, by index
+fn rotated_files(dir: &Path, service: &str) -> Vec<(u32, PathBuf)> {
+    let prefix = format!("{}.log.", service);
+    let mut files: Vec<(u32, PathBuf)> = fs::read_dir(dir)
+        .map(|entries| {
+            entries
+                .flatten()
+                .filter_map(|entry| {
+                    let name = entry.file_name().into_string().ok()?;
+                    let index = name.strip_prefix(&prefix)?.parse().ok()?;
+                    Some((index, entry.path()))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    files.sort();
+    files
+}
+
+// Delete rotated files past the count or age limits
+fn prune(dir: &Path, service: &str, settings: &LogSettings) {
+    let max_age = settings
+        .max_age_days
+        .map(|days| Duration::from_secs(u64::from(days) * 24 * 60 * 60));
+    for (index, path) in rotated_files(dir, service) {
+        let expired = max_age.is_some_and(|max_age| {
+            fs::metadata(&path)
+                .and_then(|metadata| metadata.modified())
+                .ok()
+                .and_then(|modified| SystemTime::now().duration_since(modified).ok())
+                .is_some_and(|age| age > max_age)
+        });
+        if index > settings.max_files || expired {
+            let _ = fs::remove_file(path);
+        }
+    }
+}
+
+// `service.log` -> `service.log.1`, shifting the older ones
+fn rotate(dir: &Path, service: &str, settings: &LogSettings) -> std::io::Result<()> {
+    for (index, path) in rotated_files(dir, service).into_iter().rev() {
+        if index >= settings.max_files {
+            fs::remove_file(path)?;
+        } else {
+            fs::rename(path, file_path(dir, service, index + 1))?;
+        }
+    }
+    let current = file_path(dir, service, 0);
+    if settings.max_files == 0 {
+        fs::remove_file(current)?;
+    } else {
+        fs::rename(current, file_path(dir, service, 1))?;
+    }
+    prune(dir, service, settings);
+    Ok(())
+}
+
+impl Logger {
+    fn write(&mut self, service: &str, line: &str, settings: &LogSettings) -> std::io::Result<()> {
+        let text = format!("{} {}\n", timestamp(), line.trim_end());
+        let len = text.len() as u64;
+        let full = self
+            .files
+            .get(service)
+            .is_some_and(|log| log.size > 0 && log.size + len > settings.max_file_bytes);
+        if full {
+            // Closed before it's renamed
+            self.files.remove(service);
+            rotate(&self.dir, service, settings)?;
+        }
+        let log = match self.files.entry(service.to_string()) {
+            std::collections::hash_map::Entry::Occupied(entry) => entry.into_mut(),
+            std::collections::hash_map::Entry::Vacant(entry) => {
+                entry.insert(open(&self.dir, service)?)
+            }
+        };
+        log.file.write_all(text.as_bytes())?;
+        log.size += len;
+        Ok(())
+    }
+}
+
+/// Append a line to a service's log file; failures are ignored so logging
+/// never gets in a service's way
+pub fn write(service: &str, line: &str) {
+    let settings = SETTINGS.read().clone();
+    if settings.enabled {
+        let _ = logger().write(service, line, &settings);
+    }
+}
+
+/// Log into the `logs/` directory of the workspace in `data_dir` from now on
+pub fn set_workspace(data_dir: &Path) {
+    let mut logger = logger();
+    let dir = data_dir.join(LOGS_DIR);
+    if logger.dir != dir {
+        logger.dir = dir;
+        logger.files.clear();
+    }
+}
+
+/// Close the open files (reopened on the next line), e.g. before the data
+/// dir is swapped
+pub fn close_files() {
+    logger().files.clear();
+}
+
+/// Write the process output events to their files and keep them in
+/// `service_logs`; call once at setup
+pub fn listen(app: &AppHandle, service_logs: SharedServiceLogs) {
+    for (event, service) in EVENTS {
+        let service_logs = service_logs.clone();
+        app.listen_any(event, move |emitted| {
+            // Payloads are JSON strings
+            let line = serde_json::from_str::<String>(emitted.payload())
+                .unwrap_or_else(|_| emitted.payload().to_string());
+            write(service, &line);
+            service_logs
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .record(service, line);
+        });
+    }
+}
+
+/// Recent lines of a service's output, or of a server's requests, oldest first
+pub async fn get_logs_internal(
+    state: &SharedState,
+    service: &str,
+    query: &LogQuery,
+) -> Result<Vec<LogLine>, String> {
+    if crate::access_log::SERVICES.contains(&service) {
+        return Ok(crate::access_log::get_logs_internal(state, service, query).await);
+    }
+    let service = SERVICES
+        .iter()
+        .find(|known| **known == service)
+        .ok_or_else(|| {
+            format!(
+                "Unknown service '{}'; expected one of: {}",
+                service,
+                SERVICES
+                    .iter()
+                    .chain(crate::access_log::SERVICES.iter())
+                    .copied()
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            )
+        })?;
+
+    let logs = state.lock().await.service_logs.clone();
+    let logs = logs.lock().unwrap_or_else(|e| e.into_inner());
+    Ok(logs
+        .lines
+        .get(service)
+        .map(|lines| query.select(lines.iter().cloned()))
+        .unwrap_or_default())
+}
+
+/// The current workspace's log files
+pub fn list_internal() -> LogFiles {
+    let dir = logger().dir.clone();
+    let mut files: Vec<LogFileInfo> = fs::read_dir(&dir)
+        .map(|entries| {
+            entries
+                .flatten()
+                .filter_map(|entry| {
+                    let name = entry.file_name().into_string().ok()?;
+                    let service = name.split(".log").next()?.to_string();
+                    let metadata = entry.metadata().ok()?;
+                    Some(LogFileInfo {
+                        service,
+                        path: entry.path().to_string_lossy().to_string(),
+                        size_bytes: metadata.len(),
+                        modified: metadata
+                            .modified()
+                            .ok()
+                            .and_then(|modified| {
+                                modified.duration_since(std::time::UNIX_EPOCH).ok()
+                            })
+                            .map(|d| d.as_secs()),
+                        name,
+                    })
+                })
+                .filter(|file| file.name.contains(".log"))
+                .collect()
+        })
+        .unwrap_or_default();
+    // node.log, node.log.1, node.log.2, ... with multi-digit indexes in order
+    files.sort_by_key(|file| {
+        let index = file
+            .name
+            .rsplit_once(".log.")
+            .and_then(|(_, index)| index.parse::<u32>().ok())
+            .unwrap_or(0);
+        (file.service.clone(), index)
+    });
+    LogFiles {
+        dir: dir.to_string_lossy().to_string(),
+        settings: SETTINGS.read().clone(),
+        files,
+    }
+}
+
+// ============================================================================
+// Tauri Commands
+// ============================================================================
+
+// Get the log file settings
+#[tauri::command]
+pub async fn get_log_settings() -> Result<LogSettings, String> {
+    Ok(SETTINGS.read().clone())
+}
+
+// Set the log file settings; applies right away, deleting rotated files past
+// the new limits
+#[tauri::command]
+pub async fn set_log_settings(settings: LogSettings) -> Result<LogSettings, String> {
+    if settings.max_file_bytes < 1024 {
+        return Err("Log files must be allowed at least 1024 bytes".to_string());
+    }
+    SETTINGS.save(&settings)?;
+
+    let dir = logger().dir.clone();
+    let services: BTreeSet<String> = list_internal()
+        .files
+        .into_iter()
+        .map(|file| file.service)
+        .collect();
+    for service in services {
+        prune(&dir, &service, &settings);
+    }
+    Ok(settings)
+}
+
+// List the current workspace's log files
+#[tauri::command]
+pub async fn list_log_files() -> Result<LogFiles, String> {
+    Ok(list_internal())
+}
+
+// Get a service's recent output (node, miner, headless, poa-node,
+// explorer-service) or a server's recent requests (explorer, mcp, control)
+#[tauri::command]
+pub async fn get_logs(
+    state: tauri::State<'_, SharedState>,
+    service: String,
+    lines: Option<usize>,
+    since: Option<u64>,
+    filter: Option<String>,
+) -> Result<Vec<LogLine>, String> {
+    let query = LogQuery {
+        lines,
+        since,
+        filter,
+    };
+    get_logs_internal(&state, &service, &query).await
+}
@@ -4,9 +4,8 @@
 //! the Hathor development environment.
 
 use axum::{
-    extract::State,
-    http::StatusCode,
-    response::{sse::Event, IntoResponse, Sse},
+    extract::{Query, State},
+    response::{sse::Event, Response, Sse},
     routing::{get, post},
     Json, Router,
 };
@@ -14,8 +13,10 @@ use futures_util::stream::{self, Stream};
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
 use std::{collections::HashMap, convert::Infallible, sync::Arc, time::Duration};
+use tokio::sync::broadcast::error::RecvError;
 use tokio::sync::Mutex;
 
+use crate::amount::HtrAmount;
 use crate::SharedState;
 
 // ============================================================================
@@ -115,13 +116,50 @@ fn get_tools() -> Vec<McpTool> {
         // Miner Management
         McpTool {
             name: "start_miner".to_string(),
-            description: "Start the CPU miner. The node must be running first.".to_string(),
+            description: "Start a CPU miner. The node must be running first. Several miners can run side by side under different names (e.g. to simulate competing miners).".to_string(),
             input_schema: json!({
                 "type": "object",
                 "properties": {
+                    "name": {
+                        "type": "string",
+                        "description": "Miner name (default: \"default\")"
+                    },
+                    "threads": {
+                        "type": "integer",
+                        "description": "Mining threads (default: 1)"
+                    },
                     "address": {
                         "type": "string",
                         "description": "Mining reward address (uses node's wallet address if not provided)"
+                    },
+                    "stop_at_height": {
+                        "type": "integer",
+                        "description": "Stop mining automatically once the chain reaches this height"
+                    },
+                    "stop_at_faucet_balance": {
+                        "type": "number",
+                        "description": "Stop mining automatically once the faucet's spendable balance reaches this many HTR"
+                    },
+                    "low_priority": {
+                        "type": "boolean",
+                        "description": "Run the miner at the lowest OS priority (default: false)"
+                    },
+                    "cap_threads": {
+                        "type": "boolean",
+                        "description": "Cap threads to physical cores minus one (default: false)"
+                    },
+                    "rotate_coinbase": {
+                        "type": "integer",
+                        "description": "Rotate the coinbase address across the first N node wallet addresses, moving to the next after each block"
+                    },
+                    "custom_miner_path": {
+                        "type": "string",
+                        "description": "Run this miner binary instead of the bundled cpuminer (e.g. a GPU miner)"
+                    },
+                    "custom_miner_args": {
+                        "type": "array",
+                        "items": { "type": "string" },
+                        "description": "Arguments for the custom miner; {stratum_url}, {stratum_host}, {stratum_port}, {address} and {threads} are filled in"
                     }
                 },
                 "required": []
@@ -129,26 +167,48 @@ fn get_tools() -> Vec<McpTool> {
         },
         McpTool {
             name: "stop_miner".to_string(),
-            description: "Stop the CPU miner.".to_string(),
+            description: "Stop a CPU miner.".to_string(),
             input_schema: json!({
                 "type": "object",
-                "properties": {},
+                "properties": {
+                    "name": {
+                        "type": "string",
+                        "description": "Miner name (default: \"default\")"
+                    }
+                },
                 "required": []
             }),
         },
         McpTool {
             name: "get_miner_status".to_string(),
-            description: "Get the current status of the CPU miner.".to_string(),
+            description: "Get the status of every running CPU miner, including its hash rate (latest and one-minute average) and accepted/rejected shares.".to_string(),
             input_schema: json!({
                 "type": "object",
                 "properties": {},
                 "required": []
             }),
         },
-        // Wallet Service
         McpTool {
-            name: "start_wallet_service".to_string(),
-            description: "Start the wallet-headless service for multi-wallet support. Node must be running first.".to_string(),
+            name: "start_paced_mining".to_string(),
+            description: "Mine one block every interval_secs seconds regardless of CPU speed, for realistic chain timing. A dedicated miner named \"paced\" is started and stopped around each block.".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "interval_secs": {
+                        "type": "integer",
+                        "description": "Target seconds between blocks"
+                    },
+                    "address": {
+                        "type": "string",
+                        "description": "Mining reward address (uses node's wallet address if not provided)"
+                    }
+                },
+                "required": ["interval_secs"]
+            }),
+        },
+        McpTool {
+            name: "stop_paced_mining".to_string(),
+            description: "Stop paced mining.".to_string(),
             input_schema: json!({
                 "type": "object",
                 "properties": {},
@@ -156,8 +216,8 @@ fn get_tools() -> Vec<McpTool> {
             }),
         },
         McpTool {
-            name: "stop_wallet_service".to_string(),
-            description: "Stop the wallet-headless service.".to_string(),
+            name: "get_stratum_connection_info".to_string(),
+            description: "Get the stratum URLs for miners on this machine and, when LAN access is enabled in Forge, for miners on other machines (e.g. an ASIC).".to_string(),
             input_schema: json!({
                 "type": "object",
                 "properties": {},
@@ -165,18 +225,23 @@ fn get_tools() -> Vec<McpTool> {
             }),
         },
         McpTool {
-            name: "get_wallet_service_status".to_string(),
-            description: "Get the status of the wallet-headless service.".to_string(),
+            name: "get_stratum_jobs".to_string(),
+            description: "Get recent stratum jobs sent to miners (job id, weight) and the miners' submissions with the node's answer, as seen by Forge's stratum tap. Useful to debug miner connectivity.".to_string(),
             input_schema: json!({
                 "type": "object",
-                "properties": {},
+                "properties": {
+                    "limit": {
+                        "type": "integer",
+                        "description": "Number of jobs to return, newest first (default: 20)"
+                    }
+                },
                 "required": []
             }),
         },
-        // Wallet Operations
+        // Peers
         McpTool {
-            name: "generate_seed".to_string(),
-            description: "Generate a new 24-word BIP39 seed phrase for wallet creation.".to_string(),
+            name: "list_peers".to_string(),
+            description: "List the node's connected, handshaking and connecting peers, plus the stored listen port and bootstrap peers.".to_string(),
             input_schema: json!({
                 "type": "object",
                 "properties": {},
@@ -184,119 +249,153 @@ fn get_tools() -> Vec<McpTool> {
             }),
         },
         McpTool {
-            name: "create_wallet".to_string(),
-            description: "Create a new wallet. Generates a seed if not provided.".to_string(),
+            name: "add_peer".to_string(),
+            description: "Connect the node to a peer (e.g. another Forge node or a dev machine) now and on every start.".to_string(),
             input_schema: json!({
                 "type": "object",
                 "properties": {
-                    "wallet_id": {
+                    "url": {
                         "type": "string",
-                        "description": "Unique identifier for the wallet"
-                    },
-                    "seed": {
-                        "type": "string",
-                        "description": "24-word BIP39 seed phrase (generated if not provided)"
+                        "description": "Peer entrypoint, e.g. tcp://192.168.1.20:40403"
                     }
                 },
-                "required": ["wallet_id"]
+                "required": ["url"]
             }),
         },
         McpTool {
-            name: "get_wallet_seed".to_string(),
-            description: "Retrieve the seed phrase for a wallet created in this session.".to_string(),
+            name: "remove_peer".to_string(),
+            description: "Disconnect a peer by id, block it from reconnecting, and remove it from the bootstrap peers.".to_string(),
             input_schema: json!({
                 "type": "object",
                 "properties": {
-                    "wallet_id": {
+                    "peer_id": {
                         "type": "string",
-                        "description": "The wallet ID"
+                        "description": "Peer id from list_peers"
                     }
                 },
-                "required": ["wallet_id"]
+                "required": ["peer_id"]
             }),
         },
+        // Network chaos
         McpTool {
-            name: "get_wallet_status".to_string(),
-            description: "Get the sync status of a wallet (statusCode 3 = Ready).".to_string(),
+            name: "get_network_chaos".to_string(),
+            description: "Get the current p2p chaos conditions (pause, latency, partition) and the links the node reaches its peers through.".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {},
+                "required": []
+            }),
+        },
+        McpTool {
+            name: "pause_p2p".to_string(),
+            description: "Hold all traffic between the node and its peers until resume_p2p. Long pauses end in the peers timing each other out.".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {},
+                "required": []
+            }),
+        },
+        McpTool {
+            name: "resume_p2p".to_string(),
+            description: "Let held p2p traffic through again.".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {},
+                "required": []
+            }),
+        },
+        McpTool {
+            name: "set_p2p_latency".to_string(),
+            description: "Delay traffic between the node and its peers in each direction (0 turns it off).".to_string(),
             input_schema: json!({
                 "type": "object",
                 "properties": {
-                    "wallet_id": {
-                        "type": "string",
-                        "description": "The wallet ID"
+                    "latency_ms": {
+                        "type": "integer",
+                        "description": "Added latency in milliseconds (max 10000)"
                     }
                 },
-                "required": ["wallet_id"]
+                "required": ["latency_ms"]
             }),
         },
         McpTool {
-            name: "get_wallet_balance".to_string(),
-            description: "Get the balance of a wallet (available and locked HTR in cents).".to_string(),
+            name: "partition_network".to_string(),
+            description: "Drop the node's peer connections and refuse new ones for a period, then let the node reconnect.".to_string(),
             input_schema: json!({
                 "type": "object",
                 "properties": {
-                    "wallet_id": {
-                        "type": "string",
-                        "description": "The wallet ID"
+                    "seconds": {
+                        "type": "integer",
+                        "description": "How long the partition lasts (max 3600)"
                     }
                 },
-                "required": ["wallet_id"]
+                "required": ["seconds"]
             }),
         },
         McpTool {
-            name: "get_wallet_addresses".to_string(),
-            description: "Get the addresses of a wallet.".to_string(),
+            name: "heal_network".to_string(),
+            description: "End any partition, pause and added latency.".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {},
+                "required": []
+            }),
+        },
+        // Snapshots
+        McpTool {
+            name: "save_snapshot".to_string(),
+            description: "Save the node data and wallet registry as a named snapshot. A running node is stopped (with its miners and wallet-headless) and restarted.".to_string(),
             input_schema: json!({
                 "type": "object",
                 "properties": {
-                    "wallet_id": {
+                    "name": {
                         "type": "string",
-                        "description": "The wallet ID"
+                        "description": "Snapshot name (letters, digits, '-' and '_')"
                     }
                 },
-                "required": ["wallet_id"]
+                "required": ["name"]
             }),
         },
         McpTool {
-            name: "send_from_wallet".to_string(),
-            description: "Send HTR from a wallet to an address.".to_string(),
+            name: "restore_snapshot".to_string(),
+            description: "Roll the node data and wallet registry back to a named snapshot. A running node is stopped (with its miners and wallet-headless) and restarted.".to_string(),
             input_schema: json!({
                 "type": "object",
                 "properties": {
-                    "wallet_id": {
-                        "type": "string",
-                        "description": "The wallet ID to send from"
-                    },
-                    "address": {
+                    "name": {
                         "type": "string",
-                        "description": "Destination Hathor address"
-                    },
-                    "amount": {
-                        "type": "number",
-                        "description": "Amount of HTR to send"
+                        "description": "Snapshot name (letters, digits, '-' and '_')"
                     }
                 },
-                "required": ["wallet_id", "address", "amount"]
+                "required": ["name"]
             }),
         },
         McpTool {
-            name: "close_wallet".to_string(),
-            description: "Close a wallet and remove it from the wallet-headless service.".to_string(),
+            name: "list_snapshots".to_string(),
+            description: "List saved snapshots with their size, creation time and best block height.".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {},
+                "required": []
+            }),
+        },
+        McpTool {
+            name: "delete_snapshot".to_string(),
+            description: "Delete a saved snapshot.".to_string(),
             input_schema: json!({
                 "type": "object",
                 "properties": {
-                    "wallet_id": {
+                    "name": {
                         "type": "string",
-                        "description": "The wallet ID"
+                        "description": "Snapshot name (letters, digits, '-' and '_')"
                     }
                 },
-                "required": ["wallet_id"]
+                "required": ["name"]
             }),
         },
-        // Faucet
         McpTool {
-            name: "get_faucet_balance".to_string(),
-            description: "Get the balance of the fullnode's built-in wallet (faucet).".to_string(),
+            name: "get_snapshot_schedule".to_string(),
+            description: "Get the automatic snapshot schedule and retention.".to_string(),
             input_schema: json!({
                 "type": "object",
                 "properties": {},
@@ -304,74 +403,190 @@ fn get_tools() -> Vec<McpTool> {
             }),
         },
         McpTool {
-            name: "send_from_faucet".to_string(),
-            description: "Send HTR from the fullnode's built-in wallet (faucet) to an address.".to_string(),
+            name: "set_snapshot_schedule".to_string(),
+            description: "Take snapshots automatically while the node runs, every N minutes and/or every N blocks, keeping the last N automatic ones. Each snapshot restarts the node.".to_string(),
             input_schema: json!({
                 "type": "object",
                 "properties": {
-                    "address": {
-                        "type": "string",
-                        "description": "Destination Hathor address"
+                    "enabled": {
+                        "type": "boolean",
+                        "description": "Turn the schedule on or off"
                     },
-                    "amount": {
-                        "type": "number",
-                        "description": "Amount of HTR to send"
+                    "every_minutes": {
+                        "type": "integer",
+                        "description": "Minutes between snapshots"
+                    },
+                    "every_blocks": {
+                        "type": "integer",
+                        "description": "Blocks between snapshots"
+                    },
+                    "keep_last": {
+                        "type": "integer",
+                        "description": "Automatic snapshots to keep (default: 5, max 100)"
                     }
                 },
-                "required": ["address", "amount"]
+                "required": ["enabled"]
             }),
         },
+        // Disk usage
         McpTool {
-            name: "fund_wallet".to_string(),
-            description: "Send HTR from the faucet to a wallet. Auto-determines address and reasonable amount.".to_string(),
+            name: "get_data_usage".to_string(),
+            description: "Get the disk usage of the workspace: RocksDB database, RocksDB logs, snapshots and leftover scratch data, in bytes.".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {},
+                "required": []
+            }),
+        },
+        McpTool {
+            name: "compact_data".to_string(),
+            description: "Delete rotated RocksDB logs and leftover scratch directories (reorg fork data, interrupted restores). The node must be stopped.".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {},
+                "required": []
+            }),
+        },
+        // Service logs
+        McpTool {
+            name: "get_logs".to_string(),
+            description: "Get recent log lines of a service, oldest first: the output of node, miner, headless, poa-node or explorer-service, or the requests handled by explorer, mcp or control. Lines are kept in memory from app start, including those emitted before anyone was listening.".to_string(),
             input_schema: json!({
                 "type": "object",
                 "properties": {
-                    "wallet_id": {
+                    "service": {
                         "type": "string",
-                        "description": "The wallet ID to fund"
+                        "description": "node, miner, headless, poa-node, explorer-service, explorer, mcp or control"
                     },
-                    "amount": {
-                        "type": "number",
-                        "description": "Amount of HTR to send (auto-calculated if not provided)"
+                    "lines": {
+                        "type": "integer",
+                        "description": "Most recent matching lines to return (default: 200, max 5000)"
+                    },
+                    "since": {
+                        "type": "integer",
+                        "description": "Only lines after this Unix time in milliseconds"
+                    },
+                    "filter": {
+                        "type": "string",
+                        "description": "Only lines containing this text, ignoring case"
                     }
                 },
-                "required": ["wallet_id"]
+                "required": ["service"]
             }),
         },
-        // Blockchain
+        // Chain fixtures
         McpTool {
-            name: "get_blocks".to_string(),
-            description: "Get recent blocks from the blockchain.".to_string(),
+            name: "export_chain".to_string(),
+            description: "Export the blocks in a height range, and the transactions they confirm, to a JSON fixture file (decoded data plus raw bytes).".to_string(),
             input_schema: json!({
                 "type": "object",
                 "properties": {
-                    "count": {
+                    "from_height": {
+                        "type": "integer",
+                        "description": "First block height (at least 1; use 1 for a fixture that replays into a fresh localnet)"
+                    },
+                    "to_height": {
+                        "type": "integer",
+                        "description": "Last block height"
+                    },
+                    "path": {
+                        "type": "string",
+                        "description": "File to write the fixture to"
+                    }
+                },
+                "required": ["from_height", "to_height", "path"]
+            }),
+        },
+        McpTool {
+            name: "import_fixture".to_string(),
+            description: "Replay a JSON chain fixture into the running node, recreating the same blocks and transactions. Reset the data first for a fresh localnet; miners must be stopped.".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "path": {
+                        "type": "string",
+                        "description": "Fixture file written by export_chain"
+                    }
+                },
+                "required": ["path"]
+            }),
+        },
+        McpTool {
+            name: "bootstrap_environment".to_string(),
+            description: "Build a populated localnet in one call: starts the node and wallet-headless, mines until the faucet can pay, creates and funds N wallets, creates K custom tokens, sends M random HTR transfers between the wallets and mines up to a target height. Returns a manifest with the wallets (ids, seeds, addresses), tokens and transaction hashes.".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "wallets": {
+                        "type": "integer",
+                        "description": "Number of wallets to create (max 50)"
+                    },
+                    "wallet_prefix": {
+                        "type": "string",
+                        "description": "Wallets are named <prefix>-1, <prefix>-2, ... (default: wallet)"
+                    },
+                    "funding": {
+                        "type": "number",
+                        "description": "HTR sent from the faucet to each wallet"
+                    },
+                    "tokens": {
+                        "type": "integer",
+                        "description": "Number of custom tokens to create, round-robin across the wallets (max 20)"
+                    },
+                    "token_amount": {
                         "type": "integer",
-                        "description": "Number of blocks to retrieve (default: 10)"
+                        "description": "Supply of each token in cents (default: 10000)"
+                    },
+                    "transactions": {
+                        "type": "integer",
+                        "description": "Random HTR transfers between the wallets (max 500, needs 2+ wallets)"
+                    },
+                    "target_height": {
+                        "type": "integer",
+                        "description": "Mine until the best block reaches this height"
+                    },
+                    "instance": {
+                        "type": "string",
+                        "description": "Wallet-headless instance (default: the default instance)"
                     }
                 },
                 "required": []
             }),
         },
         McpTool {
-            name: "get_transaction".to_string(),
-            description: "Get details of a specific transaction.".to_string(),
+            name: "start_load".to_string(),
+            description: "Generate a steady stream of small HTR transfers among a pool of wallets (loadgen-1, loadgen-2, ...) for benchmarking the node or a dApp under load. Sets up and funds the pool first (starting the node and wallet-headless if needed), then runs until stopped or for the given duration. Check progress with get_load_stats.".to_string(),
             input_schema: json!({
                 "type": "object",
                 "properties": {
-                    "tx_id": {
+                    "tps": {
+                        "type": "number",
+                        "description": "Target transfers per second (max 100)"
+                    },
+                    "duration": {
+                        "type": "integer",
+                        "description": "Seconds to run (default: until stop_load)"
+                    },
+                    "pattern": {
                         "type": "string",
-                        "description": "Transaction ID (hash)"
+                        "enum": ["constant", "ramp", "burst"],
+                        "description": "constant, ramp (from 0 up to tps over the duration or a minute) or burst (5s on, 10s off). Default: constant"
+                    },
+                    "wallets": {
+                        "type": "integer",
+                        "description": "Wallets in the pool, 2 to 50 (default: 5). Each has one transfer in flight at a time"
+                    },
+                    "instance": {
+                        "type": "string",
+                        "description": "Wallet-headless instance (default: the default instance)"
                     }
                 },
-                "required": ["tx_id"]
+                "required": ["tps"]
             }),
         },
-        // Utilities
         McpTool {
-            name: "quick_start".to_string(),
-            description: "Quickly start the full environment: node, miner, and wallet service.".to_string(),
+            name: "stop_load".to_string(),
+            description: "Stop the transaction load generator".to_string(),
             input_schema: json!({
                 "type": "object",
                 "properties": {},
@@ -379,8 +594,8 @@ fn get_tools() -> Vec<McpTool> {
             }),
         },
         McpTool {
-            name: "quick_stop".to_string(),
-            description: "Stop all services: node, miner, and wallet service.".to_string(),
+            name: "get_load_stats".to_string(),
+            description: "Get stats of the current or last load generator run: target and achieved TPS, sent/succeeded/failed/skipped counts, latency percentiles and the last error".to_string(),
             input_schema: json!({
                 "type": "object",
                 "properties": {},
@@ -388,8 +603,22 @@ fn get_tools() -> Vec<McpTool> {
             }),
         },
         McpTool {
-            name: "get_full_status".to_string(),
-            description: "Get comprehensive status of all services, balances, and active wallets.".to_string(),
+            name: "run_scenario".to_string(),
+            description: "Run a YAML or JSON scenario file: a list of steps (start_node, stop_node, mine: N, sleep: secs, create_wallet: id or {id, seed}, fund: {wallet, amount}, send: {from, to, amount}, create_token: {wallet, name, symbol, amount}, save_snapshot/restore_snapshot: name, assert_balance: {wallet, equals|at_least|at_most, within}, assert_height: {equals|at_least|at_most, within}). Amounts are decimal HTR. Returns each step's result; remaining steps are skipped after a failure unless stop_on_failure is false.".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "path": {
+                        "type": "string",
+                        "description": "Scenario file (.json for JSON, YAML otherwise)"
+                    }
+                },
+                "required": ["path"]
+            }),
+        },
+        McpTool {
+            name: "start_recording".to_string(),
+            description: "Start recording a session: every state-changing app command and MCP tool call is captured (with the block height at the time) until stop_recording, replacing any previous recording".to_string(),
             input_schema: json!({
                 "type": "object",
                 "properties": {},
@@ -397,72 +626,2219 @@ fn get_tools() -> Vec<McpTool> {
             }),
         },
         McpTool {
-            name: "reset_data".to_string(),
-            description: "Reset all blockchain data and stop all services. USE WITH CAUTION.".to_string(),
+            name: "stop_recording".to_string(),
+            description: "Stop recording the session; returns the captured calls".to_string(),
             input_schema: json!({
                 "type": "object",
                 "properties": {},
                 "required": []
             }),
         },
-    ]
-}
-
-// ============================================================================
-// Tool Execution
-// ============================================================================
-
-async fn execute_tool(state: &McpState, name: &str, params: &Value) -> Result<String, String> {
-    let client = reqwest::Client::new();
+        McpTool {
+            name: "get_recording".to_string(),
+            description: "Get the calls captured in the current or last recorded session".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {},
+                "required": []
+            }),
+        },
+        McpTool {
+            name: "export_recording".to_string(),
+            description: "Write the recorded session as a scenario file for run_scenario. Blocks mined between calls become mine steps; calls without a scenario step are returned as unmapped.".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "path": {
+                        "type": "string",
+                        "description": "Scenario file to write (.json for JSON, YAML otherwise)"
+                    }
+                },
+                "required": ["path"]
+            }),
+        },
+        McpTool {
+            name: "advance_time".to_string(),
+            description: "Move the localnet clock forward, e.g. to make timelocked outputs spendable or test time-dependent contracts without waiting. The node runs under libfaketime (must be installed); the first call restarts a running node to load it, and wallet-headless must be started again afterwards to share the clock. The clock can't go back except by resetting the data.".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "seconds": {
+                        "type": "integer",
+                        "description": "Seconds to move forward"
+                    }
+                },
+                "required": ["seconds"]
+            }),
+        },
+        McpTool {
+            name: "set_time".to_string(),
+            description: "Set the localnet clock to a Unix timestamp, which must not be earlier than the clock's current time. Same mechanism as advance_time.".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "timestamp": {
+                        "type": "integer",
+                        "description": "Unix timestamp in seconds"
+                    }
+                },
+                "required": ["timestamp"]
+            }),
+        },
+        McpTool {
+            name: "get_clock".to_string(),
+            description: "Get the localnet clock: real time, localnet time, the offset between them and whether the running node follows it".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {},
+                "required": []
+            }),
+        },
+        McpTool {
+            name: "get_node_events".to_string(),
+            description: "Get recent events from the node's event queue, oldest first: new-vertex (with is_block and height), reorg-started, reorg-finished and tx-voided. Pass the last event_id seen as after_id to get only newer ones. The same events are pushed as notifications on the SSE stream.".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "after_id": {
+                        "type": "integer",
+                        "description": "Only events with a greater event_id"
+                    },
+                    "limit": {
+                        "type": "integer",
+                        "description": "Maximum number of events, the most recent ones (default: 50)"
+                    }
+                },
+                "required": []
+            }),
+        },
+        McpTool {
+            name: "get_mempool".to_string(),
+            description: "Get the node's unconfirmed transactions, oldest first: tx_id, first_seen (when Forge first saw it in the mempool), age_secs, timestamp, weight, parents and voided_by. Useful to debug transactions that never confirm.".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "limit": {
+                        "type": "integer",
+                        "description": "Maximum number of transactions, the oldest ones (default: 100)"
+                    }
+                },
+                "required": []
+            }),
+        },
+        McpTool {
+            name: "unlock_rewards".to_string(),
+            description: "Mine just enough blocks for the faucet's block rewards to become spendable (rewards are locked for the node's reward_spend_min_blocks). Starts the miner if needed and waits until the faucet's available balance is nonzero.".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {},
+                "required": []
+            }),
+        },
+        McpTool {
+            name: "get_miner_session_stats".to_string(),
+            description: "Get stats of a miner's current (or last) session: uptime, accepted/rejected shares, and blocks found by its coinbase address.".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "name": {
+                        "type": "string",
+                        "description": "Miner name (default: \"default\")"
+                    }
+                },
+                "required": []
+            }),
+        },
+        McpTool {
+            name: "get_mining_report".to_string(),
+            description: "Summarize blocks mined in the miners' current (or last) sessions: total rewards, spendable vs still locked, and the unlock height of each block's reward.".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "name": {
+                        "type": "string",
+                        "description": "Only report this miner (default: all miners)"
+                    }
+                },
+                "required": []
+            }),
+        },
+        McpTool {
+            name: "get_mining_info".to_string(),
+            description: "Get the current block weight and difficulty, the running miners' measured hash rate, and the expected time to the next block at a given thread count.".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "threads": {
+                        "type": "integer",
+                        "description": "Thread count to estimate for (default: the running miners' threads)"
+                    }
+                },
+                "required": []
+            }),
+        },
+        McpTool {
+            name: "benchmark_miner".to_string(),
+            description: "Benchmark cpuminer (no node needed) with 1 up to `threads` threads and return the hash rate for each thread count, plus the expected block time when the node runs. Miners must be stopped. Takes `seconds` per thread count.".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "seconds": {
+                        "type": "integer",
+                        "description": "Seconds per thread count, 5-60 (default: 10)"
+                    },
+                    "threads": {
+                        "type": "integer",
+                        "description": "Highest thread count to test (default: all cores)"
+                    }
+                },
+                "required": []
+            }),
+        },
+        McpTool {
+            name: "set_auto_start_miner".to_string(),
+            description: "Make the miner start automatically (mining to the node wallet) as soon as the node is ready after it starts (persisted setting), or turn that off.".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "enabled": {
+                        "type": "boolean",
+                        "description": "Whether to auto-start the miner"
+                    },
+                    "threads": {
+                        "type": "integer",
+                        "description": "Mining threads for the auto-started miner (default: unchanged)"
+                    }
+                },
+                "required": ["enabled"]
+            }),
+        },
+        McpTool {
+            name: "simulate_reorg".to_string(),
+            description: "Force a chain reorganization that orphans the last `depth` blocks (up to 20). A temporary second fullnode mines a longer fork and is connected back, so the node's newest blocks are voided and their transactions return to the mempool. Stops Forge's miners first; takes a minute or more.".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "depth": {
+                        "type": "integer",
+                        "description": "Number of blocks to orphan"
+                    }
+                },
+                "required": ["depth"]
+            }),
+        },
+        // Wallet Service
+        McpTool {
+            name: "start_wallet_service".to_string(),
+            description: "Start a wallet-headless instance for multi-wallet support. Node must be running first.".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "instance": {
+                        "type": "string",
+                        "description": "Instance name (default: \"default\")"
+                    },
+                    "port": {
+                        "type": "integer",
+                        "description": "HTTP port for the instance (default: 8001)"
+                    },
+                    "dist_path": {
+                        "type": "string",
+                        "description": "Custom wallet-headless-dist directory (required for additional instances)"
+                    },
+                    "ignore_compatibility": {
+                        "type": "boolean",
+                        "description": "Start even if the node and wallet-headless versions are known to be incompatible (default: false)"
+                    }
+                },
+                "required": []
+            }),
+        },
+        McpTool {
+            name: "get_feature_states".to_string(),
+            description: "Get hathor-core feature activation (soft fork) states at the best block: name, state (DEFINED, STARTED, MUST_SIGNAL, LOCKED_IN, ACTIVE, FAILED), signaling acceptance and heights. forced marks features force-enabled with set_enabled_features.".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {},
+                "required": []
+            }),
+        },
+        McpTool {
+            name: "set_enabled_features".to_string(),
+            description: "Force-enable feature activation features (e.g. NANO_CONTRACTS) on the localnet from the next node start; they become active at height 12. An empty list goes back to the plain localnet settings. Reset the data for the change to apply cleanly to the chain.".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "features": {
+                        "type": "array",
+                        "items": { "type": "string" },
+                        "description": "Feature names as hathor-core names them"
+                    }
+                },
+                "required": ["features"]
+            }),
+        },
+        McpTool {
+            name: "set_nano_contracts_mode".to_string(),
+            description: "Enable or disable nano contracts on the localnet from the next node start (restart the node to apply). On-chain blueprints are open to any address while enabled.".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "enabled": {
+                        "type": "boolean",
+                        "description": "Whether the node starts with nano contracts enabled"
+                    }
+                },
+                "required": ["enabled"]
+            }),
+        },
+        McpTool {
+            name: "list_blueprints".to_string(),
+            description: "List the nano contract blueprints known to the node: id, name, kind (built_in or on_chain) and created_at for on-chain ones.".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {},
+                "required": []
+            }),
+        },
+        McpTool {
+            name: "get_blueprint_source".to_string(),
+            description: "Get the Python source code of a nano contract blueprint.".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "blueprint_id": {
+                        "type": "string",
+                        "description": "Blueprint ID"
+                    }
+                },
+                "required": ["blueprint_id"]
+            }),
+        },
+        McpTool {
+            name: "upload_blueprint".to_string(),
+            description: "Upload a Python blueprint file as an on-chain blueprint from a wallet (nano contracts mode must be on). The file must be a .py of at most 24 KiB assigning its class to __blueprint__. Returns the blueprint id, which is also recorded in the workspace registry.".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "wallet_id": {
+                        "type": "string",
+                        "description": "Wallet paying for and signing the transaction"
+                    },
+                    "python_source_path": {
+                        "type": "string",
+                        "description": "Path to the blueprint's .py file"
+                    },
+                    "address": {
+                        "type": "string",
+                        "description": "Uploader address (default: the wallet's current address)"
+                    },
+                    "instance": {
+                        "type": "string",
+                        "description": "Wallet-headless instance (default: the default instance)"
+                    }
+                },
+                "required": ["wallet_id", "python_source_path"]
+            }),
+        },
+        McpTool {
+            name: "nc_create".to_string(),
+            description: "Create a nano contract from a blueprint, calling its initialize method from a wallet. The contract id (nc_id) is the transaction hash.".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "blueprint_id": {
+                        "type": "string",
+                        "description": "Blueprint ID"
+                    },
+                    "wallet_id": {
+                        "type": "string",
+                        "description": "Calling wallet"
+                    },
+                    "args": {
+                        "type": "array",
+                        "description": "Method arguments, in the order the blueprint declares them"
+                    },
+                    "actions": {
+                        "type": "array",
+                        "description": "Deposits and withdrawals, e.g. [{\"type\": \"deposit\", \"token\": \"00\", \"amount\": 100}] (amounts in cents)"
+                    },
+                    "address": {
+                        "type": "string",
+                        "description": "Caller address (default: the wallet's current address)"
+                    },
+                    "wait": {
+                        "type": "boolean",
+                        "description": "Wait until a block confirms the transaction and include the confirmation (default: false)"
+                    },
+                    "instance": {
+                        "type": "string",
+                        "description": "Wallet-headless instance (default: \"default\")"
+                    }
+                },
+                "required": ["blueprint_id", "wallet_id"]
+            }),
+        },
+        McpTool {
+            name: "nc_execute".to_string(),
+            description: "Call a public method of a nano contract from a wallet.".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "nc_id": {
+                        "type": "string",
+                        "description": "Nano contract ID"
+                    },
+                    "method": {
+                        "type": "string",
+                        "description": "Public method name"
+                    },
+                    "wallet_id": {
+                        "type": "string",
+                        "description": "Calling wallet"
+                    },
+                    "args": {
+                        "type": "array",
+                        "description": "Method arguments, in the order the blueprint declares them"
+                    },
+                    "actions": {
+                        "type": "array",
+                        "description": "Deposits and withdrawals, e.g. [{\"type\": \"deposit\", \"token\": \"00\", \"amount\": 100}] (amounts in cents)"
+                    },
+                    "address": {
+                        "type": "string",
+                        "description": "Caller address (default: the wallet's current address)"
+                    },
+                    "wait": {
+                        "type": "boolean",
+                        "description": "Wait until a block confirms the transaction and include the confirmation (default: false)"
+                    },
+                    "instance": {
+                        "type": "string",
+                        "description": "Wallet-headless instance (default: \"default\")"
+                    }
+                },
+                "required": ["nc_id", "method", "wallet_id"]
+            }),
+        },
+        McpTool {
+            name: "nc_get_state".to_string(),
+            description: "Get a nano contract's blueprint, the decoded values of the named fields (an error object for fields that can't be read) and its balances in cents per token UID (HTR always included).".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "nc_id": {
+                        "type": "string",
+                        "description": "Nano contract ID"
+                    },
+                    "fields": {
+                        "type": "array",
+                        "items": { "type": "string" },
+                        "description": "Field names to read (the node only returns named fields)"
+                    },
+                    "tokens": {
+                        "type": "array",
+                        "items": { "type": "string" },
+                        "description": "Token UIDs whose balances to include besides HTR"
+                    }
+                },
+                "required": ["nc_id"]
+            }),
+        },
+        McpTool {
+            name: "nc_get_history".to_string(),
+            description: "Get the transactions that created or called a nano contract, newest first: tx_id, method, args, caller, timestamp, first_block and voided. Pass the last tx_id as after to page back.".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "nc_id": {
+                        "type": "string",
+                        "description": "Nano contract ID"
+                    },
+                    "count": {
+                        "type": "integer",
+                        "description": "Number of calls (default: 50)"
+                    },
+                    "after": {
+                        "type": "string",
+                        "description": "Only calls older than this transaction"
+                    }
+                },
+                "required": ["nc_id"]
+            }),
+        },
+        McpTool {
+            name: "get_tx_confirmation".to_string(),
+            description: "Get where a transaction stands: first block (hash and height, unset while in the mempool), accumulated weight and confirmation level, and whether it's voided, by which transactions, and what it conflicts with.".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "tx_id": {
+                        "type": "string",
+                        "description": "Transaction hash"
+                    }
+                },
+                "required": ["tx_id"]
+            }),
+        },
+        McpTool {
+            name: "start_poa_node".to_string(),
+            description: "Start a Proof-of-Authority side-DAG node next to the localnet, with its own data directory and a single signer (its key is generated on first use). It signs its own blocks, so no miner is needed.".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "api_port": {
+                        "type": "integer",
+                        "description": "API port (default: 8085)"
+                    },
+                    "block_time": {
+                        "type": "integer",
+                        "description": "Target seconds between blocks (default: 5)"
+                    },
+                    "listen": {
+                        "type": "boolean",
+                        "description": "Accept peers on port 40420 (default: false)"
+                    }
+                }
+            }),
+        },
+        McpTool {
+            name: "stop_poa_node".to_string(),
+            description: "Stop the Proof-of-Authority side-DAG node.".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {}
+            }),
+        },
+        McpTool {
+            name: "get_poa_status".to_string(),
+            description: "Get whether the PoA side-DAG node is running, its API port, data directory, signer public key and address, and best block height.".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {}
+            }),
+        },
+        McpTool {
+            name: "get_lan_share_info".to_string(),
+            description: "Get whether the explorer and node API proxy are shared on the LAN, and the URLs other machines (e.g. mobile wallets) can use to reach them.".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {}
+            }),
+        },
+        McpTool {
+            name: "check_compatibility".to_string(),
+            description: "Compare the running node's hathor-core version with a wallet-headless build against Forge's compatibility matrix. Status is compatible, untested, broken (start_wallet_service refuses it without ignore_compatibility) or unknown.".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "dist_path": {
+                        "type": "string",
+                        "description": "Custom wallet-headless-dist directory (default: the bundled one)"
+                    }
+                },
+                "required": []
+            }),
+        },
+        McpTool {
+            name: "stop_wallet_service".to_string(),
+            description: "Stop a wallet-headless instance.".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "instance": {
+                        "type": "string",
+                        "description": "Instance name (default: \"default\")"
+                    }
+                },
+                "required": []
+            }),
+        },
+        McpTool {
+            name: "get_wallet_service_status".to_string(),
+            description: "Get the status of all running wallet-headless instances.".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {},
+                "required": []
+            }),
+        },
+        // Wallet Operations
+        McpTool {
+            name: "validate_address".to_string(),
+            description: "Check that an address is well-formed (base58, checksum) and belongs to the localnet, and get its type (P2PKH/P2SH).".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "address": {
+                        "type": "string",
+                        "description": "Hathor address"
+                    }
+                },
+                "required": ["address"]
+            }),
+        },
+        McpTool {
+            name: "generate_seed".to_string(),
+            description: "Generate a new 24-word BIP39 seed phrase for wallet creation.".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {},
+                "required": []
+            }),
+        },
+        McpTool {
+            name: "create_wallet".to_string(),
+            description: "Create a new wallet. Generates a seed if not provided.".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "instance": {
+                        "type": "string",
+                        "description": "Wallet-headless instance (default: \"default\")"
+                    },
+                    "wallet_id": {
+                        "type": "string",
+                        "description": "Unique identifier for the wallet"
+                    },
+                    "seed": {
+                        "type": "string",
+                        "description": "24-word BIP39 seed phrase (generated if not provided)"
+                    }
+                },
+                "required": ["wallet_id"]
+            }),
+        },
+        McpTool {
+            name: "import_wallet".to_string(),
+            description: "Import a wallet from a 24-word mnemonic or a path to a file containing one. The seed's words and checksum are validated before the wallet is started.".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "instance": {
+                        "type": "string",
+                        "description": "Wallet-headless instance (default: \"default\")"
+                    },
+                    "wallet_id": {
+                        "type": "string",
+                        "description": "Unique identifier for the wallet"
+                    },
+                    "seed_source": {
+                        "type": "string",
+                        "description": "Mnemonic, or path to a file containing it"
+                    }
+                },
+                "required": ["wallet_id", "seed_source"]
+            }),
+        },
+        McpTool {
+            name: "set_wallet_settings".to_string(),
+            description: "Set a registered wallet's defaults for sends: label, default token and change address strategy. Sends that omit a token or change address use these.".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "wallet_id": {
+                        "type": "string",
+                        "description": "Registered wallet ID"
+                    },
+                    "label": {
+                        "type": "string",
+                        "description": "Display label"
+                    },
+                    "default_token": {
+                        "type": "string",
+                        "description": "Token UID used when a send doesn't name one (HTR when omitted)"
+                    },
+                    "change_strategy": {
+                        "type": "string",
+                        "enum": ["default", "first_address", "fixed"],
+                        "description": "Where change goes: a fresh address (default), the wallet's first address, or a fixed address"
+                    },
+                    "change_address": {
+                        "type": "string",
+                        "description": "Change address for the \"fixed\" strategy"
+                    }
+                },
+                "required": ["wallet_id"]
+            }),
+        },
+        McpTool {
+            name: "get_wallet_seed".to_string(),
+            description: "Retrieve the seed phrase for a wallet created in this session.".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "instance": {
+                        "type": "string",
+                        "description": "Wallet-headless instance (default: \"default\")"
+                    },
+                    "wallet_id": {
+                        "type": "string",
+                        "description": "The wallet ID"
+                    }
+                },
+                "required": ["wallet_id"]
+            }),
+        },
+        McpTool {
+            name: "get_wallet_status".to_string(),
+            description: "Get the sync status of a wallet (statusCode 3 = Ready).".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "instance": {
+                        "type": "string",
+                        "description": "Wallet-headless instance (default: \"default\")"
+                    },
+                    "wallet_id": {
+                        "type": "string",
+                        "description": "The wallet ID"
+                    }
+                },
+                "required": ["wallet_id"]
+            }),
+        },
+        McpTool {
+            name: "get_wallet_sync_progress".to_string(),
+            description: "Get a wallet's sync phase and, once it is ready, how many addresses were scanned (vs the gap limit) and how many transactions of history were loaded.".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "instance": {
+                        "type": "string",
+                        "description": "Wallet-headless instance (default: \"default\")"
+                    },
+                    "wallet_id": {
+                        "type": "string",
+                        "description": "The wallet ID"
+                    }
+                },
+                "required": ["wallet_id"]
+            }),
+        },
+        McpTool {
+            name: "get_wallet_balance".to_string(),
+            description: "Get the balance of a wallet (available and locked HTR in cents).".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "instance": {
+                        "type": "string",
+                        "description": "Wallet-headless instance (default: \"default\")"
+                    },
+                    "wallet_id": {
+                        "type": "string",
+                        "description": "The wallet ID"
+                    }
+                },
+                "required": ["wallet_id"]
+            }),
+        },
+        McpTool {
+            name: "get_wallet_addresses".to_string(),
+            description: "Get the addresses of a wallet.".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "instance": {
+                        "type": "string",
+                        "description": "Wallet-headless instance (default: \"default\")"
+                    },
+                    "wallet_id": {
+                        "type": "string",
+                        "description": "The wallet ID"
+                    }
+                },
+                "required": ["wallet_id"]
+            }),
+        },
+        McpTool {
+            name: "send_from_wallet".to_string(),
+            description: "Send HTR from a wallet to an address.".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "instance": {
+                        "type": "string",
+                        "description": "Wallet-headless instance (default: \"default\")"
+                    },
+                    "wallet_id": {
+                        "type": "string",
+                        "description": "The wallet ID to send from"
+                    },
+                    "address": {
+                        "type": "string",
+                        "description": "Destination Hathor address"
+                    },
+                    "amount": {
+                        "type": "number",
+                        "description": "Amount of HTR to send"
+                    },
+                    "timelock": {
+                        "type": "integer",
+                        "description": "Unix timestamp until which the received funds are locked"
+                    }
+                },
+                "required": ["wallet_id", "address", "amount"]
+            }),
+        },
+        McpTool {
+            name: "build_and_send_tx".to_string(),
+            description: "Send a transaction with multiple outputs (any token, optional timelocks), optional explicit inputs and change address. Use dry_run to get the decoded transaction without pushing it.".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "instance": {
+                        "type": "string",
+                        "description": "Wallet-headless instance (default: \"default\")"
+                    },
+                    "wallet_id": {
+                        "type": "string",
+                        "description": "The wallet ID to send from"
+                    },
+                    "outputs": {
+                        "type": "array",
+                        "items": {
+                            "type": "object",
+                            "properties": {
+                                "address": {"type": "string"},
+                                "value": {"type": "integer", "description": "Amount in cents (100 = 1 HTR)"},
+                                "token": {"type": "string", "description": "Token UID (default: HTR)"},
+                                "timelock": {"type": "integer", "description": "Unix timestamp until which the output is locked"}
+                            },
+                            "required": ["address", "value"]
+                        },
+                        "description": "Transaction outputs"
+                    },
+                    "inputs": {
+                        "type": "array",
+                        "items": {
+                            "type": "object",
+                            "properties": {
+                                "hash": {"type": "string"},
+                                "index": {"type": "integer"}
+                            },
+                            "required": ["hash", "index"]
+                        },
+                        "description": "Inputs to spend (chosen automatically if omitted)"
+                    },
+                    "change_address": {
+                        "type": "string",
+                        "description": "Address that receives the change"
+                    },
+                    "dry_run": {
+                        "type": "boolean",
+                        "description": "Build and decode the transaction without pushing it"
+                    },
+                    "unsigned": {
+                        "type": "boolean",
+                        "description": "Return the unsigned transaction with its sighash and input paths for an external signer (push it with submit_signed_tx)"
+                    }
+                },
+                "required": ["wallet_id", "outputs"]
+            }),
+        },
+        McpTool {
+            name: "estimate_tx".to_string(),
+            description: "Check a transaction before sending it (valid addresses, enough unlocked balance per token) and estimate its size, weight and mining time. Takes the same parameters as build_and_send_tx.".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "instance": {
+                        "type": "string",
+                        "description": "Wallet-headless instance (default: \"default\")"
+                    },
+                    "wallet_id": {
+                        "type": "string",
+                        "description": "The wallet ID to send from"
+                    },
+                    "outputs": {
+                        "type": "array",
+                        "items": {
+                            "type": "object",
+                            "properties": {
+                                "address": {"type": "string"},
+                                "value": {"type": "integer", "description": "Amount in cents (100 = 1 HTR)"},
+                                "token": {"type": "string", "description": "Token UID (default: HTR)"},
+                                "timelock": {"type": "integer", "description": "Unix timestamp until which the output is locked"}
+                            },
+                            "required": ["address", "value"]
+                        },
+                        "description": "Transaction outputs"
+                    },
+                    "inputs": {
+                        "type": "array",
+                        "items": {
+                            "type": "object",
+                            "properties": {
+                                "hash": {"type": "string"},
+                                "index": {"type": "integer"}
+                            },
+                            "required": ["hash", "index"]
+                        },
+                        "description": "Inputs to spend (chosen automatically if omitted)"
+                    },
+                    "change_address": {
+                        "type": "string",
+                        "description": "Address that receives the change"
+                    }
+                },
+                "required": ["wallet_id", "outputs"]
+            }),
+        },
+        McpTool {
+            name: "atomic_swap".to_string(),
+            description: "Swap tokens between two wallets in a single transaction: wallet A gives offer_a and receives offer_b, wallet B the reverse. Runs the whole propose/sign/push sequence.".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "instance": {
+                        "type": "string",
+                        "description": "Wallet-headless instance (default: \"default\")"
+                    },
+                    "wallet_a": {
+                        "type": "string",
+                        "description": "First wallet ID"
+                    },
+                    "offer_a": {
+                        "type": "object",
+                        "properties": {
+                            "token": {"type": "string", "description": "Token UID (default: HTR)"},
+                            "amount": {"type": "integer", "description": "Amount in cents (100 = 1 token)"}
+                        },
+                        "required": ["amount"],
+                        "description": "What wallet A gives"
+                    },
+                    "wallet_b": {
+                        "type": "string",
+                        "description": "Second wallet ID"
+                    },
+                    "offer_b": {
+                        "type": "object",
+                        "properties": {
+                            "token": {"type": "string", "description": "Token UID (default: HTR)"},
+                            "amount": {"type": "integer", "description": "Amount in cents (100 = 1 token)"}
+                        },
+                        "required": ["amount"],
+                        "description": "What wallet B gives"
+                    }
+                },
+                "required": ["wallet_a", "offer_a", "wallet_b", "offer_b"]
+            }),
+        },
+        McpTool {
+            name: "submit_signed_tx".to_string(),
+            description: "Push a transaction built with build_and_send_tx (unsigned: true) using signatures produced by an external signer. Each signature is a DER signature of sighash_digest with its input's address_index, or complete input_data.".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "instance": {
+                        "type": "string",
+                        "description": "Wallet-headless instance (default: \"default\")"
+                    },
+                    "wallet_id": {
+                        "type": "string",
+                        "description": "The wallet that built the transaction"
+                    },
+                    "tx_hex": {
+                        "type": "string",
+                        "description": "Unsigned transaction hex"
+                    },
+                    "signatures": {
+                        "type": "array",
+                        "items": {
+                            "type": "object",
+                            "properties": {
+                                "input_index": {"type": "integer"},
+                                "address_index": {"type": "integer"},
+                                "signature": {"type": "string", "description": "DER signature (hex)"},
+                                "input_data": {"type": "string", "description": "Complete input data (hex)"}
+                            },
+                            "required": ["input_index"]
+                        },
+                        "description": "One entry per input"
+                    }
+                },
+                "required": ["wallet_id", "tx_hex", "signatures"]
+            }),
+        },
+        McpTool {
+            name: "close_wallet".to_string(),
+            description: "Close a wallet and remove it from the wallet-headless service.".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "instance": {
+                        "type": "string",
+                        "description": "Wallet-headless instance (default: \"default\")"
+                    },
+                    "wallet_id": {
+                        "type": "string",
+                        "description": "The wallet ID"
+                    }
+                },
+                "required": ["wallet_id"]
+            }),
+        },
+        // Faucet
+        McpTool {
+            name: "get_faucet_balance".to_string(),
+            description: "Get the balance of the fullnode's built-in wallet (faucet).".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {},
+                "required": []
+            }),
+        },
+        McpTool {
+            name: "get_faucet_addresses".to_string(),
+            description: "List the fullnode wallet's (faucet) derived addresses with their HTR balance (in cents, including locked rewards) and used/current flags.".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {},
+                "required": []
+            }),
+        },
+        McpTool {
+            name: "set_faucet_address_rotation".to_string(),
+            description: "Make the faucet move to a fresh node wallet address before every funding operation (persisted setting), or turn that off.".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "enabled": {
+                        "type": "boolean",
+                        "description": "Whether to rotate addresses"
+                    }
+                },
+                "required": ["enabled"]
+            }),
+        },
+        McpTool {
+            name: "send_from_faucet".to_string(),
+            description: "Send HTR from the fullnode's built-in wallet (faucet) to an address.".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "address": {
+                        "type": "string",
+                        "description": "Destination Hathor address"
+                    },
+                    "amount": {
+                        "type": "number",
+                        "description": "Amount of HTR to send"
+                    },
+                    "timelock": {
+                        "type": "integer",
+                        "description": "Unix timestamp until which the received funds are locked"
+                    }
+                },
+                "required": ["address", "amount"]
+            }),
+        },
+        McpTool {
+            name: "faucet_distribute".to_string(),
+            description: "Send HTR from the faucet to many addresses in a single multi-output transaction (batched by 254 outputs). Much faster than repeated send_from_faucet calls.".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "payments": {
+                        "type": "array",
+                        "items": {
+                            "type": "object",
+                            "properties": {
+                                "address": {"type": "string"},
+                                "amount": {"type": "number", "description": "Amount of HTR"},
+                                "timelock": {"type": "integer", "description": "Unix timestamp until which the funds are locked"}
+                            },
+                            "required": ["address", "amount"]
+                        },
+                        "description": "Addresses and amounts to pay"
+                    }
+                },
+                "required": ["payments"]
+            }),
+        },
+        McpTool {
+            name: "fund_wallet".to_string(),
+            description: "Send HTR from the faucet to a wallet. Auto-determines address and reasonable amount.".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "instance": {
+                        "type": "string",
+                        "description": "Wallet-headless instance (default: \"default\")"
+                    },
+                    "wallet_id": {
+                        "type": "string",
+                        "description": "The wallet ID to fund"
+                    },
+                    "amount": {
+                        "type": "number",
+                        "description": "Amount of HTR to send (auto-calculated if not provided)"
+                    }
+                },
+                "required": ["wallet_id"]
+            }),
+        },
+        // Tokens
+        McpTool {
+            name: "create_token".to_string(),
+            description: "Create a custom token from a wallet. Returns the token UID and its configuration string.".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "instance": {
+                        "type": "string",
+                        "description": "Wallet-headless instance (default: \"default\")"
+                    },
+                    "wallet_id": {
+                        "type": "string",
+                        "description": "The wallet ID that creates (and receives) the token"
+                    },
+                    "name": {
+                        "type": "string",
+                        "description": "Token name"
+                    },
+                    "symbol": {
+                        "type": "string",
+                        "description": "Token symbol (e.g. MYT)"
+                    },
+                    "amount": {
+                        "type": "integer",
+                        "description": "Amount to mint in cents (100 = 1 token)"
+                    }
+                },
+                "required": ["wallet_id", "name", "symbol", "amount"]
+            }),
+        },
+        McpTool {
+            name: "create_nft".to_string(),
+            description: "Create an NFT whose creation transaction carries a data field (usually a metadata URI).".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "instance": {
+                        "type": "string",
+                        "description": "Wallet-headless instance (default: \"default\")"
+                    },
+                    "wallet_id": {
+                        "type": "string",
+                        "description": "The wallet ID that creates (and receives) the NFT"
+                    },
+                    "name": {
+                        "type": "string",
+                        "description": "NFT name"
+                    },
+                    "symbol": {
+                        "type": "string",
+                        "description": "NFT symbol"
+                    },
+                    "amount": {
+                        "type": "integer",
+                        "description": "Number of units to create (default: 1)"
+                    },
+                    "data": {
+                        "type": "string",
+                        "description": "Data field, e.g. ipfs://... metadata URI"
+                    }
+                },
+                "required": ["wallet_id", "name", "symbol", "data"]
+            }),
+        },
+        McpTool {
+            name: "nft_gallery".to_string(),
+            description: "List the NFTs held by a wallet with their decoded data fields.".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "instance": {
+                        "type": "string",
+                        "description": "Wallet-headless instance (default: \"default\")"
+                    },
+                    "wallet_id": {
+                        "type": "string",
+                        "description": "The wallet ID"
+                    }
+                },
+                "required": ["wallet_id"]
+            }),
+        },
+        McpTool {
+            name: "list_tokens".to_string(),
+            description: "List custom tokens registered in the current workspace (UID, name, symbol, creator, configuration string).".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {},
+                "required": []
+            }),
+        },
+        McpTool {
+            name: "register_token".to_string(),
+            description: "Register a token in the workspace registry, by configuration string or by UID, name and symbol.".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "configuration_string": {
+                        "type": "string",
+                        "description": "Token configuration string ([name:symbol:uid:checksum])"
+                    },
+                    "token_uid": {
+                        "type": "string",
+                        "description": "Token UID"
+                    },
+                    "name": {
+                        "type": "string",
+                        "description": "Token name"
+                    },
+                    "symbol": {
+                        "type": "string",
+                        "description": "Token symbol"
+                    }
+                },
+                "required": []
+            }),
+        },
+        McpTool {
+            name: "get_token_balances".to_string(),
+            description: "Get a wallet's balance of HTR and every registered token, with names and symbols.".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "instance": {
+                        "type": "string",
+                        "description": "Wallet-headless instance (default: \"default\")"
+                    },
+                    "wallet_id": {
+                        "type": "string",
+                        "description": "The wallet ID"
+                    }
+                },
+                "required": ["wallet_id"]
+            }),
+        },
+        McpTool {
+            name: "get_tx_history".to_string(),
+            description: "Get a wallet's transaction history, with the symbol of every registered token in its inputs and outputs.".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "instance": {
+                        "type": "string",
+                        "description": "Wallet-headless instance (default: \"default\")"
+                    },
+                    "limit": {
+                        "type": "integer",
+                        "description": "Maximum number of transactions to return"
+                    },
+                    "wallet_id": {
+                        "type": "string",
+                        "description": "The wallet ID"
+                    }
+                },
+                "required": ["wallet_id"]
+            }),
+        },
+        McpTool {
+            name: "mint_tokens".to_string(),
+            description: "Mint more of a custom token. The wallet must hold its mint authority.".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "instance": {
+                        "type": "string",
+                        "description": "Wallet-headless instance (default: \"default\")"
+                    },
+                    "wallet_id": {
+                        "type": "string",
+                        "description": "The wallet ID holding the mint authority"
+                    },
+                    "token_uid": {
+                        "type": "string",
+                        "description": "Token UID"
+                    },
+                    "amount": {
+                        "type": "integer",
+                        "description": "Amount to mint in cents (100 = 1 token)"
+                    },
+                    "address": {
+                        "type": "string",
+                        "description": "Address that receives the tokens (wallet's own address if omitted)"
+                    }
+                },
+                "required": ["wallet_id", "token_uid", "amount"]
+            }),
+        },
+        McpTool {
+            name: "melt_tokens".to_string(),
+            description: "Melt (destroy) tokens held by a wallet. The wallet must hold the token's melt authority.".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "instance": {
+                        "type": "string",
+                        "description": "Wallet-headless instance (default: \"default\")"
+                    },
+                    "wallet_id": {
+                        "type": "string",
+                        "description": "The wallet ID holding the tokens and melt authority"
+                    },
+                    "token_uid": {
+                        "type": "string",
+                        "description": "Token UID"
+                    },
+                    "amount": {
+                        "type": "integer",
+                        "description": "Amount to melt in cents (100 = 1 token)"
+                    }
+                },
+                "required": ["wallet_id", "token_uid", "amount"]
+            }),
+        },
+        McpTool {
+            name: "delegate_authority".to_string(),
+            description: "Send a token's mint or melt authority to another address.".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "instance": {
+                        "type": "string",
+                        "description": "Wallet-headless instance (default: \"default\")"
+                    },
+                    "wallet_id": {
+                        "type": "string",
+                        "description": "The wallet ID holding the authority"
+                    },
+                    "token_uid": {
+                        "type": "string",
+                        "description": "Token UID"
+                    },
+                    "authority": {
+                        "type": "string",
+                        "enum": ["mint", "melt"],
+                        "description": "Authority to delegate"
+                    },
+                    "address": {
+                        "type": "string",
+                        "description": "Address that receives the authority"
+                    },
+                    "keep_authority": {
+                        "type": "boolean",
+                        "description": "Keep an authority output in this wallet too (default: true)"
+                    }
+                },
+                "required": ["wallet_id", "token_uid", "authority", "address"]
+            }),
+        },
+        McpTool {
+            name: "get_token_authorities".to_string(),
+            description: "Show which registered wallets and addresses currently hold a token's mint and melt authority outputs.".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "instance": {
+                        "type": "string",
+                        "description": "Wallet-headless instance (default: \"default\")"
+                    },
+                    "token_uid": {
+                        "type": "string",
+                        "description": "Token UID"
+                    }
+                },
+                "required": ["token_uid"]
+            }),
+        },
+        // Payments
+        McpTool {
+            name: "watch_address".to_string(),
+            description: "Wait until a transaction paying at least min_amount to an address arrives (via the node WebSocket). Returns the matching payment or times out.".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "address": {
+                        "type": "string",
+                        "description": "Address to watch"
+                    },
+                    "min_amount": {
+                        "type": "integer",
+                        "description": "Minimum amount in cents (default: any amount)"
+                    },
+                    "token": {
+                        "type": "string",
+                        "description": "Token UID (default: HTR)"
+                    },
+                    "timeout": {
+                        "type": "integer",
+                        "description": "Seconds to wait (default: 60)"
+                    }
+                },
+                "required": ["address"]
+            }),
+        },
+        // Multisig
+        McpTool {
+            name: "get_multisig_xpub".to_string(),
+            description: "Get the multisig xpub of a seed (or a registered wallet) to share with the other participants.".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "seed": {
+                        "type": "string",
+                        "description": "24-word seed phrase"
+                    },
+                    "wallet_id": {
+                        "type": "string",
+                        "description": "Registered wallet whose seed should be used"
+                    },
+                    "instance": {
+                        "type": "string",
+                        "description": "Wallet-headless instance (default: 'default')"
+                    }
+                },
+                "required": []
+            }),
+        },
+        McpTool {
+            name: "create_multisig_wallet".to_string(),
+            description: "Create one participant's m-of-n multisig (P2SH) wallet. Every participant uses the same config_name, pubkeys and num_signatures with their own seed. If the definition is new, wallet-headless must be restarted before the wallet opens.".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "wallet_id": {
+                        "type": "string",
+                        "description": "Unique identifier for this participant's wallet"
+                    },
+                    "seed": {
+                        "type": "string",
+                        "description": "This participant's seed (generated if not provided)"
+                    },
+                    "config_name": {
+                        "type": "string",
+                        "description": "Name of the shared multisig definition"
+                    },
+                    "pubkeys": {
+                        "type": "array",
+                        "items": {"type": "string"},
+                        "description": "Multisig xpubs of all participants (omit to reuse an existing definition)"
+                    },
+                    "num_signatures": {
+                        "type": "integer",
+                        "description": "Signatures required to spend (m)"
+                    },
+                    "instance": {
+                        "type": "string",
+                        "description": "Wallet-headless instance (default: 'default')"
+                    }
+                },
+                "required": ["wallet_id", "config_name"]
+            }),
+        },
+        McpTool {
+            name: "send_multisig_tx".to_string(),
+            description: "Send from a multisig wallet: builds the proposal, collects signatures from the given local participant wallets and pushes the transaction.".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "wallet_id": {
+                        "type": "string",
+                        "description": "Multisig wallet that builds and pushes the transaction"
+                    },
+                    "outputs": {
+                        "type": "array",
+                        "items": {
+                            "type": "object",
+                            "properties": {
+                                "address": {"type": "string"},
+                                "value": {"type": "integer", "description": "Amount in cents (100 = 1 HTR)"},
+                                "token": {"type": "string", "description": "Token UID (default: HTR)"}
+                            },
+                            "required": ["address", "value"]
+                        },
+                        "description": "Transaction outputs"
+                    },
+                    "signer_wallet_ids": {
+                        "type": "array",
+                        "items": {"type": "string"},
+                        "description": "Local multisig wallets (same definition) that sign the proposal"
+                    },
+                    "instance": {
+                        "type": "string",
+                        "description": "Wallet-headless instance (default: 'default')"
+                    }
+                },
+                "required": ["wallet_id", "outputs", "signer_wallet_ids"]
+            }),
+        },
+        // Blockchain
+        McpTool {
+            name: "get_blocks".to_string(),
+            description: "Get recent blocks from the blockchain, newest first. Use offset to page back from the best block.".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "count": {
+                        "type": "integer",
+                        "description": "Number of blocks to retrieve (default: 10, max: 200)"
+                    },
+                    "offset": {
+                        "type": "integer",
+                        "description": "Number of blocks below the best block to start from (default: 0)"
+                    }
+                },
+                "required": []
+            }),
+        },
+        McpTool {
+            name: "get_transaction".to_string(),
+            description: "Get details of a specific transaction.".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "tx_id": {
+                        "type": "string",
+                        "description": "Transaction ID (hash)"
+                    }
+                },
+                "required": ["tx_id"]
+            }),
+        },
+        McpTool {
+            name: "search_chain".to_string(),
+            description: "Look up a block height, block/transaction hash or address. The result's kind is block (hash, height, block), transaction (hash, tx, meta), address (total_transactions, tokens with received/spent per token UID) or not_found.".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {
+                    "query": {
+                        "type": "string",
+                        "description": "Block height, block or transaction hash, or address"
+                    }
+                },
+                "required": ["query"]
+            }),
+        },
+        // Utilities
+        McpTool {
+            name: "quick_start".to_string(),
+            description: "Quickly start the full environment: node, miner, and wallet service.".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {},
+                "required": []
+            }),
+        },
+        McpTool {
+            name: "quick_stop".to_string(),
+            description: "Stop all services: node, miner, and wallet service.".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {},
+                "required": []
+            }),
+        },
+        McpTool {
+            name: "get_full_status".to_string(),
+            description: "Get comprehensive status of all services, balances, and active wallets.".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {},
+                "required": []
+            }),
+        },
+        McpTool {
+            name: "reset_data".to_string(),
+            description: "Reset all blockchain data and stop all services. USE WITH CAUTION.".to_string(),
+            input_schema: json!({
+                "type": "object",
+                "properties": {},
+                "required": []
+            }),
+        },
+    ]
+}
+
+// ============================================================================
+// Tool Execution
+// ============================================================================
+
+// Resolve the wallet-headless instance selected by the optional "instance" param
+async fn headless_url(state: &McpState, params: &Value) -> Result<String, String> {
+    let instance = params.get("instance").and_then(|v| v.as_str());
+    crate::headless_url(&state.app_state, instance).await
+}
+
+#[tracing::instrument(name = "mcp.tool", skip_all, fields(tool = %name))]
+pub(crate) async fn execute_tool(
+    state: &McpState,
+    name: &str,
+    params: &Value,
+) -> Result<String, String> {
+    let client = reqwest::Client::new();
+
+    match name {
+        // Node Management
+        "start_node" => crate::start_node_internal(&state.app_state).await,
+
+        "stop_node" => crate::stop_node_internal(&state.app_state).await,
+
+        "get_node_status" => {
+            let app_state = state.app_state.lock().await;
+            if !app_state.node_running {
+                return Ok(json!({"running": false}).to_string());
+            }
+            drop(app_state);
+
+            match client.get("http://127.0.0.1:8080/v1a/status/").send().await {
+                Ok(resp) => {
+                    let text = resp.text().await.unwrap_or_default();
+                    Ok(format!(r#"{{"running": true, "status": {}}}"#, text))
+                }
+                Err(e) => Ok(json!({"running": false, "error": e.to_string()}).to_string()),
+            }
+        }
+
+        // Miner Management
+        "start_miner" => {
+            let mut config = crate::MinerConfig::default();
+            if let Some(name) = params.get("name").and_then(|v| v.as_str()) {
+                config.name = name.to_string();
+            }
+            if let Some(address) = params.get("address").and_then(|v| v.as_str()) {
+                config.address = address.to_string();
+            }
+            if let Some(threads) = params.get("threads").and_then(|v| v.as_u64()) {
+                config.threads = u32::try_from(threads).map_err(|_| "Invalid threads")?;
+            }
+            config.stop_at_height = params.get("stop_at_height").and_then(|v| v.as_u64());
+            config.stop_at_faucet_balance = params
+                .get("stop_at_faucet_balance")
+                .and_then(|v| v.as_f64())
+                .map(HtrAmount::from_decimal)
+                .transpose()?;
+            config.low_priority = params
+                .get("low_priority")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false);
+            config.cap_threads = params
+                .get("cap_threads")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false);
+            config.rotate_coinbase = params
+                .get("rotate_coinbase")
+                .and_then(|v| v.as_u64())
+                .map(|n| u32::try_from(n).map_err(|_| "Invalid rotate_coinbase"))
+                .transpose()?;
+            if let Some(path) = params.get("custom_miner_path").and_then(|v| v.as_str()) {
+                let args = params
+                    .get("custom_miner_args")
+                    .and_then(|v| v.as_array())
+                    .map(|args| {
+                        args.iter()
+                            .filter_map(|arg| arg.as_str().map(|s| s.to_string()))
+                            .collect()
+                    })
+                    .unwrap_or_default();
+                config.custom_miner = Some(crate::custom_miner::CustomMiner {
+                    path: path.to_string(),
+                    args,
+                });
+            }
+            crate::start_miner_internal(&state.app_state, config).await
+        }
+
+        "stop_miner" => {
+            let name = params.get("name").and_then(|v| v.as_str());
+            crate::stop_miner_internal(&state.app_state, name).await
+        }
+
+        "get_miner_status" => {
+            let miners = crate::list_miners_internal(&state.app_state).await;
+            Ok(json!({
+                "running": !miners.is_empty(),
+                "miners": miners,
+            })
+            .to_string())
+        }
+
+        "start_paced_mining" => {
+            let interval_secs = params
+                .get("interval_secs")
+                .and_then(|v| v.as_u64())
+                .ok_or("interval_secs is required")?;
+            let address = params
+                .get("address")
+                .and_then(|v| v.as_str())
+                .map(String::from);
+            crate::paced_mining::start_paced_mining_internal(
+                &state.app_state,
+                interval_secs,
+                address,
+            )
+            .await
+        }
+
+        "stop_paced_mining" => {
+            crate::paced_mining::stop_paced_mining_internal(&state.app_state).await
+        }
+
+        // Peers
+        "list_peers" => {
+            let peers = crate::peers::list_peers_internal(&state.app_state).await?;
+            serde_json::to_string(&peers).map_err(|e| e.to_string())
+        }
+
+        "add_peer" => {
+            let url = params
+                .get("url")
+                .and_then(|v| v.as_str())
+                .ok_or("Missing url")?;
+            crate::peers::add_peer_internal(&state.app_state, url).await
+        }
+
+        "remove_peer" => {
+            let peer_id = params
+                .get("peer_id")
+                .and_then(|v| v.as_str())
+                .ok_or("Missing peer_id")?;
+            crate::peers::remove_peer_internal(&state.app_state, peer_id).await
+        }
+
+        // Network chaos
+        "get_network_chaos" => {
+            let status = crate::network_chaos::get_network_chaos_internal(&state.app_state).await;
+            serde_json::to_string(&status).map_err(|e| e.to_string())
+        }
+
+        "pause_p2p" => {
+            let status = crate::network_chaos::pause_p2p_internal(&state.app_state).await;
+            serde_json::to_string(&status).map_err(|e| e.to_string())
+        }
+
+        "resume_p2p" => {
+            let status = crate::network_chaos::resume_p2p_internal(&state.app_state).await;
+            serde_json::to_string(&status).map_err(|e| e.to_string())
+        }
+
+        "set_p2p_latency" => {
+            let latency_ms = params
+                .get("latency_ms")
+                .and_then(|v| v.as_u64())
+                .ok_or("Missing latency_ms")?;
+            let status =
+                crate::network_chaos::set_p2p_latency_internal(&state.app_state, latency_ms)
+                    .await?;
+            serde_json::to_string(&status).map_err(|e| e.to_string())
+        }
+
+        "partition_network" => {
+            let seconds = params
+                .get("seconds")
+                .and_then(|v| v.as_u64())
+                .ok_or("Missing seconds")?;
+            let status =
+                crate::network_chaos::partition_network_internal(&state.app_state, seconds).await?;
+            serde_json::to_string(&status).map_err(|e| e.to_string())
+        }
+
+        "heal_network" => {
+            let status = crate::network_chaos::heal_network_internal(&state.app_state).await;
+            serde_json::to_string(&status).map_err(|e| e.to_string())
+        }
+
+        // Snapshots
+        "save_snapshot" => {
+            let name = params
+                .get("name")
+                .and_then(|v| v.as_str())
+                .ok_or("Missing name")?;
+            let result = crate::snapshots::save_snapshot_internal(&state.app_state, name).await?;
+            serde_json::to_string(&result).map_err(|e| e.to_string())
+        }
+
+        "restore_snapshot" => {
+            let name = params
+                .get("name")
+                .and_then(|v| v.as_str())
+                .ok_or("Missing name")?;
+            let result =
+                crate::snapshots::restore_snapshot_internal(&state.app_state, name).await?;
+            serde_json::to_string(&result).map_err(|e| e.to_string())
+        }
+
+        "list_snapshots" => {
+            let snapshots = crate::snapshots::list_snapshots_internal();
+            serde_json::to_string(&snapshots).map_err(|e| e.to_string())
+        }
+
+        "delete_snapshot" => {
+            let name = params
+                .get("name")
+                .and_then(|v| v.as_str())
+                .ok_or("Missing name")?;
+            crate::snapshots::delete_snapshot_internal(name)
+        }
+
+        "get_snapshot_schedule" => {
+            let schedule = crate::snapshot_schedule::SETTINGS.load();
+            serde_json::to_string(&schedule).map_err(|e| e.to_string())
+        }
+
+        "set_snapshot_schedule" => {
+            let schedule = serde_json::from_value(params.clone())
+                .map_err(|e| format!("Invalid schedule: {}", e))?;
+            let schedule = crate::snapshot_schedule::set_schedule(schedule)?;
+            serde_json::to_string(&schedule).map_err(|e| e.to_string())
+        }
+
+        // Disk usage
+        "get_data_usage" => {
+            let usage = crate::data_usage::get_data_usage_internal(&state.app_state).await?;
+            serde_json::to_string(&usage).map_err(|e| e.to_string())
+        }
+
+        "compact_data" => {
+            let result = crate::data_usage::compact_data_internal(&state.app_state).await?;
+            serde_json::to_string(&result).map_err(|e| e.to_string())
+        }
+
+        // Service logs
+        "get_logs" => {
+            let service = params
+                .get("service")
+                .and_then(|v| v.as_str())
+                .ok_or("Missing service")?;
+            let query = crate::logging::LogQuery {
+                lines: params
+                    .get("lines")
+                    .and_then(|v| v.as_u64())
+                    .map(|n| n as usize),
+                since: params.get("since").and_then(|v| v.as_u64()),
+                filter: params
+                    .get("filter")
+                    .and_then(|v| v.as_str())
+                    .map(String::from),
+            };
+            let lines =
+                crate::logging::get_logs_internal(&state.app_state, service, &query).await?;
+            serde_json::to_string(&lines).map_err(|e| e.to_string())
+        }
+
+        // Chain fixtures
+        "export_chain" => {
+            let from_height = params
+                .get("from_height")
+                .and_then(|v| v.as_u64())
+                .ok_or("Missing from_height")?;
+            let to_height = params
+                .get("to_height")
+                .and_then(|v| v.as_u64())
+                .ok_or("Missing to_height")?;
+            let path = params
+                .get("path")
+                .and_then(|v| v.as_str())
+                .ok_or("Missing path")?;
+            let result = crate::fixtures::export_chain_internal(
+                &state.app_state,
+                from_height,
+                to_height,
+                path,
+            )
+            .await?;
+            serde_json::to_string(&result).map_err(|e| e.to_string())
+        }
+
+        "import_fixture" => {
+            let path = params
+                .get("path")
+                .and_then(|v| v.as_str())
+                .ok_or("Missing path")?;
+            let result = crate::fixtures::import_fixture_internal(&state.app_state, path).await?;
+            serde_json::to_string(&result).map_err(|e| e.to_string())
+        }
+
+        // Environment bootstrap
+        "bootstrap_environment" => {
+            let mut params = params.clone();
+            // MCP takes decimal HTR; the spec is in cents
+            if let Some(funding) = params.get("funding").and_then(|v| v.as_f64()) {
+                params["funding"] = json!(HtrAmount::from_decimal(funding)?);
+            }
+            let spec = serde_json::from_value(params)
+                .map_err(|e| format!("Invalid bootstrap spec: {}", e))?;
+            let manifest =
+                crate::bootstrap::bootstrap_environment_internal(&state.app_state, spec).await?;
+            serde_json::to_string(&manifest).map_err(|e| e.to_string())
+        }
+
+        // Load generator
+        "start_load" => {
+            let tps = params
+                .get("tps")
+                .and_then(|v| v.as_f64())
+                .ok_or("Missing tps")?;
+            let duration = params.get("duration").and_then(|v| v.as_u64());
+            let pattern = match params.get("pattern") {
+                Some(pattern) => serde_json::from_value(pattern.clone())
+                    .map_err(|e| format!("Invalid pattern: {}", e))?,
+                None => crate::loadgen::LoadPattern::default(),
+            };
+            let wallets = params
+                .get("wallets")
+                .and_then(|v| v.as_u64())
+                .map(|v| v as u32);
+            let instance = params.get("instance").and_then(|v| v.as_str());
+            crate::loadgen::start_load_internal(
+                &state.app_state,
+                tps,
+                duration,
+                pattern,
+                wallets,
+                instance,
+            )
+            .await
+        }
+
+        "stop_load" => crate::loadgen::stop_load_internal(&state.app_state).await,
+
+        "get_load_stats" => {
+            let stats = crate::loadgen::get_load_stats_internal(&state.app_state).await;
+            serde_json::to_string(&stats).map_err(|e| e.to_string())
+        }
+
+        // Scenarios
+        "run_scenario" => {
+            let path = params
+                .get("path")
+                .and_then(|v| v.as_str())
+                .ok_or("Missing path")?;
+            let result = crate::scenario::run_scenario_internal(&state.app_state, path).await?;
+            serde_json::to_string(&result).map_err(|e| e.to_string())
+        }
+
+        // Session recorder
+        "start_recording" => {
+            let recording = crate::recorder::start_recording_internal().await;
+            serde_json::to_string(&recording).map_err(|e| e.to_string())
+        }
+
+        "stop_recording" => {
+            let recording = crate::recorder::stop_recording_internal().await;
+            serde_json::to_string(&recording).map_err(|e| e.to_string())
+        }
+
+        "get_recording" => {
+            let recording = crate::recorder::get_recording_internal();
+            serde_json::to_string(&recording).map_err(|e| e.to_string())
+        }
+
+        "export_recording" => {
+            let path = params
+                .get("path")
+                .and_then(|v| v.as_str())
+                .ok_or("Missing path")?;
+            let exported = crate::recorder::export_recording_internal(path)?;
+            serde_json::to_string(&exported).map_err(|e| e.to_string())
+        }
+
+        // Time travel
+        "advance_time" => {
+            let seconds = params
+                .get("seconds")
+                .and_then(|v| v.as_u64())
+                .ok_or("Missing seconds")?;
+            let result =
+                crate::time_travel::advance_time_internal(&state.app_state, seconds).await?;
+            serde_json::to_string(&result).map_err(|e| e.to_string())
+        }
+
+        "set_time" => {
+            let timestamp = params
+                .get("timestamp")
+                .and_then(|v| v.as_u64())
+                .ok_or("Missing timestamp")?;
+            let result = crate::time_travel::set_time_internal(&state.app_state, timestamp).await?;
+            serde_json::to_string(&result).map_err(|e| e.to_string())
+        }
+
+        "get_clock" => {
+            let clock = crate::time_travel::get_clock_internal(&state.app_state).await;
+            serde_json::to_string(&clock).map_err(|e| e.to_string())
+        }
+
+        // Node events
+        "get_node_events" => {
+            let after_id = params.get("after_id").and_then(|v| v.as_u64());
+            let limit = params
+                .get("limit")
+                .and_then(|v| v.as_u64())
+                .map(|v| v as usize);
+            let events =
+                crate::events::get_node_events_internal(&state.app_state, after_id, limit).await;
+            serde_json::to_string(&events).map_err(|e| e.to_string())
+        }
+
+        "get_mempool" => {
+            let limit = params
+                .get("limit")
+                .and_then(|v| v.as_u64())
+                .map(|v| v as usize);
+            let mempool = crate::mempool::get_mempool_internal(&state.app_state, limit).await?;
+            serde_json::to_string(&mempool).map_err(|e| e.to_string())
+        }
+
+        "get_stratum_jobs" => {
+            let limit = params
+                .get("limit")
+                .and_then(|v| v.as_u64())
+                .map(|l| l as usize);
+            let jobs = crate::stratum_tap::get_stratum_jobs_internal(&state.app_state, limit).await;
+            serde_json::to_string(&jobs).map_err(|e| e.to_string())
+        }
+
+        "get_stratum_connection_info" => {
+            let info =
+                crate::stratum_lan::get_stratum_connection_info_internal(&state.app_state).await;
+            serde_json::to_string(&info).map_err(|e| e.to_string())
+        }
+
+        "unlock_rewards" => {
+            let result = crate::rewards::unlock_rewards_internal(&state.app_state).await?;
+            serde_json::to_string(&result).map_err(|e| e.to_string())
+        }
+
+        "get_miner_session_stats" => {
+            let name = params.get("name").and_then(|v| v.as_str());
+            let stats = crate::miner_metrics::miner_session_stats(&state.app_state, name).await;
+            serde_json::to_string(&stats).map_err(|e| e.to_string())
+        }
+
+        "get_mining_report" => {
+            let name = params.get("name").and_then(|v| v.as_str());
+            let report =
+                crate::mining_report::get_mining_report_internal(&state.app_state, name).await?;
+            serde_json::to_string(&report).map_err(|e| e.to_string())
+        }
+
+        "get_mining_info" => {
+            let threads = params
+                .get("threads")
+                .and_then(|v| v.as_u64())
+                .map(|t| u32::try_from(t).map_err(|_| "Invalid threads"))
+                .transpose()?;
+            let info =
+                crate::mining_info::get_mining_info_internal(&state.app_state, threads).await?;
+            serde_json::to_string(&info).map_err(|e| e.to_string())
+        }
+
+        "benchmark_miner" => {
+            let seconds = params.get("seconds").and_then(|v| v.as_u64());
+            let threads = params
+                .get("threads")
+                .and_then(|v| v.as_u64())
+                .map(|t| u32::try_from(t).map_err(|_| "Invalid threads"))
+                .transpose()?;
+            let result =
+                crate::benchmark::benchmark_miner_internal(&state.app_state, seconds, threads)
+                    .await?;
+            serde_json::to_string(&result).map_err(|e| e.to_string())
+        }
+
+        "simulate_reorg" => {
+            let depth = params
+                .get("depth")
+                .and_then(|v| v.as_u64())
+                .ok_or("Missing depth")?;
+            let result = crate::reorg::simulate_reorg_internal(&state.app_state, depth).await?;
+            serde_json::to_string(&result).map_err(|e| e.to_string())
+        }
+
+        // Wallet Service
+        "start_wallet_service" => {
+            let mut config = crate::HeadlessConfig::default();
+            if let Some(instance) = params.get("instance").and_then(|v| v.as_str()) {
+                config.instance = instance.to_string();
+            }
+            if let Some(port) = params.get("port").and_then(|v| v.as_u64()) {
+                config.port = u16::try_from(port).map_err(|_| "Invalid port")?;
+            }
+            config.dist_path = params
+                .get("dist_path")
+                .and_then(|v| v.as_str())
+                .map(String::from);
+            config.ignore_compatibility = params
+                .get("ignore_compatibility")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false);
+            crate::start_headless_internal(&state.app_state, config).await
+        }
+
+        "get_feature_states" => {
+            let states = crate::features::get_feature_states_internal(&state.app_state).await?;
+            serde_json::to_string(&states).map_err(|e| e.to_string())
+        }
+
+        "set_enabled_features" => {
+            let features: Vec<String> = serde_json::from_value(
+                params
+                    .get("features")
+                    .cloned()
+                    .ok_or("features is required")?,
+            )
+            .map_err(|e| format!("Invalid features: {}", e))?;
+            let enabled = crate::features::set_enabled_features_internal(&features)?;
+            Ok(json!({ "enabled": enabled }).to_string())
+        }
+
+        "set_nano_contracts_mode" => {
+            let enabled = params
+                .get("enabled")
+                .and_then(|v| v.as_bool())
+                .ok_or("enabled is required")?;
+            let mode =
+                crate::nano::set_nano_contracts_mode_internal(&state.app_state, enabled).await?;
+            serde_json::to_string(&mode).map_err(|e| e.to_string())
+        }
+
+        "list_blueprints" => {
+            let blueprints = crate::nano::list_blueprints_internal(&state.app_state).await?;
+            serde_json::to_string(&blueprints).map_err(|e| e.to_string())
+        }
+
+        "get_blueprint_source" => {
+            let blueprint_id = params
+                .get("blueprint_id")
+                .and_then(|v| v.as_str())
+                .ok_or("blueprint_id is required")?;
+            let source =
+                crate::nano::get_blueprint_source_internal(&state.app_state, blueprint_id).await?;
+            serde_json::to_string(&source).map_err(|e| e.to_string())
+        }
 
-    match name {
-        // Node Management
-        "start_node" => crate::start_node_internal(&state.app_state).await,
+        "upload_blueprint" => {
+            let wallet_id = params
+                .get("wallet_id")
+                .and_then(|v| v.as_str())
+                .ok_or("wallet_id is required")?;
+            let source_path = params
+                .get("python_source_path")
+                .and_then(|v| v.as_str())
+                .ok_or("python_source_path is required")?;
+            let address = params.get("address").and_then(|v| v.as_str());
+            let instance = params.get("instance").and_then(|v| v.as_str());
+            let blueprint = crate::nano::upload_blueprint_internal(
+                &state.app_state,
+                wallet_id,
+                source_path,
+                address,
+                instance,
+            )
+            .await?;
+            serde_json::to_string(&blueprint).map_err(|e| e.to_string())
+        }
 
-        "stop_node" => crate::stop_node_internal(&state.app_state).await,
+        "nc_create" | "nc_execute" => {
+            let request: crate::nano::NcCallRequest = serde_json::from_value(params.clone())
+                .map_err(|e| format!("Invalid parameters: {}", e))?;
+            let tx = if name == "nc_create" {
+                crate::nano::nc_create_internal(&state.app_state, &request).await?
+            } else {
+                crate::nano::nc_execute_internal(&state.app_state, &request).await?
+            };
+            let confirmation = if params.get("wait").and_then(|v| v.as_bool()) == Some(true) {
+                Some(crate::nano::wait_for_confirmation(&tx).await?)
+            } else {
+                None
+            };
+            Ok(json!({ "tx": tx, "confirmation": confirmation }).to_string())
+        }
 
-        "get_node_status" => {
-            let app_state = state.app_state.lock().await;
-            if !app_state.node_running {
-                return Ok(json!({"running": false}).to_string());
-            }
-            drop(app_state);
+        "nc_get_state" => {
+            let nc_id = params
+                .get("nc_id")
+                .and_then(|v| v.as_str())
+                .ok_or("nc_id is required")?;
+            let strings = |key: &str| -> Vec<String> {
+                params
+                    .get(key)
+                    .and_then(|v| v.as_array())
+                    .map(|items| {
+                        items
+                            .iter()
+                            .filter_map(|item| item.as_str().map(String::from))
+                            .collect()
+                    })
+                    .unwrap_or_default()
+            };
+            let nc_state = crate::nano::nc_get_state_internal(
+                &state.app_state,
+                nc_id,
+                &strings("fields"),
+                &strings("tokens"),
+            )
+            .await?;
+            serde_json::to_string(&nc_state).map_err(|e| e.to_string())
+        }
 
-            match client.get("http://127.0.0.1:8080/v1a/status/").send().await {
-                Ok(resp) => {
-                    let text = resp.text().await.unwrap_or_default();
-                    Ok(format!(r#"{{"running": true, "status": {}}}"#, text))
-                }
-                Err(e) => Ok(json!({"running": false, "error": e.to_string()}).to_string()),
-            }
+        "nc_get_history" => {
+            let nc_id = params
+                .get("nc_id")
+                .and_then(|v| v.as_str())
+                .ok_or("nc_id is required")?;
+            let count = params.get("count").and_then(|v| v.as_u64());
+            let after = params.get("after").and_then(|v| v.as_str());
+            let history =
+                crate::nano::nc_get_history_internal(&state.app_state, nc_id, count, after).await?;
+            serde_json::to_string(&history).map_err(|e| e.to_string())
         }
 
-        // Miner Management
-        "start_miner" => {
-            let address = params
-                .get("address")
+        "get_tx_confirmation" => {
+            let tx_id = params
+                .get("tx_id")
                 .and_then(|v| v.as_str())
-                .map(String::from);
-            crate::start_miner_internal(&state.app_state, address).await
+                .ok_or("tx_id is required")?;
+            let confirmation =
+                crate::confirmations::get_tx_confirmation_internal(&state.app_state, tx_id, false)
+                    .await?;
+            serde_json::to_string(&confirmation).map_err(|e| e.to_string())
+        }
+
+        "start_poa_node" => {
+            let config: crate::poa::PoaConfig = serde_json::from_value(params.clone())
+                .map_err(|e| format!("Invalid parameters: {}", e))?;
+            crate::poa::start_poa_node_internal(None, &state.app_state, &config).await
         }
 
-        "stop_miner" => crate::stop_miner_internal(&state.app_state).await,
+        "stop_poa_node" => crate::poa::stop_poa_node_internal(&state.app_state).await,
 
-        "get_miner_status" => {
-            let app_state = state.app_state.lock().await;
-            Ok(json!({"running": app_state.miner_running}).to_string())
+        "get_poa_status" => {
+            let status = crate::poa::get_poa_status_internal(&state.app_state).await;
+            serde_json::to_string(&status).map_err(|e| e.to_string())
         }
 
-        // Wallet Service
-        "start_wallet_service" => crate::start_headless_internal(&state.app_state).await,
+        "get_lan_share_info" => {
+            let info = crate::lan_share::get_lan_share_info_internal(&state.app_state).await;
+            serde_json::to_string(&info).map_err(|e| e.to_string())
+        }
+
+        "check_compatibility" => {
+            let dist_path = params.get("dist_path").and_then(|v| v.as_str());
+            let report = crate::compat::check_compatibility_internal(dist_path).await;
+            serde_json::to_string(&report).map_err(|e| e.to_string())
+        }
 
-        "stop_wallet_service" => crate::stop_headless_internal(&state.app_state).await,
+        "stop_wallet_service" => {
+            let instance = params.get("instance").and_then(|v| v.as_str());
+            crate::stop_headless_internal(&state.app_state, instance).await
+        }
 
         "get_wallet_service_status" => {
             let app_state = state.app_state.lock().await;
+            let instances: Vec<Value> = app_state
+                .headless_instances
+                .iter()
+                .map(|(name, instance)| json!({"instance": name, "port": instance.port}))
+                .collect();
             Ok(json!({
-                "running": app_state.headless_running,
-                "port": if app_state.headless_running { Some(8001) } else { None }
+                "running": !instances.is_empty(),
+                "instances": instances,
             })
             .to_string())
         }
@@ -470,28 +2846,37 @@ async fn execute_tool(state: &McpState, name: &str, params: &Value) -> Result<St
         // Wallet Operations
         "generate_seed" => crate::generate_seed_internal(),
 
+        "validate_address" => {
+            let address = params
+                .get("address")
+                .and_then(|v| v.as_str())
+                .ok_or("address is required")?;
+            let info = crate::address::validate_address_internal(address);
+            serde_json::to_string(&info).map_err(|e| e.to_string())
+        }
+
         "create_wallet" => {
             let wallet_id = params
                 .get("wallet_id")
                 .and_then(|v| v.as_str())
                 .ok_or("wallet_id is required")?;
+            let headless_url = headless_url(state, params).await?;
             let seed = params.get("seed").and_then(|v| v.as_str());
 
             let wallet_seed = match seed {
-                Some(s) => s.to_string(),
+                Some(s) => crate::wallets::validate_mnemonic(s)?,
                 None => crate::generate_seed_internal()?,
             };
 
-            // Store seed
+            // Store seed (in memory, and in the OS keychain if the user opted in)
             state
                 .wallet_seeds
                 .lock()
                 .await
                 .insert(wallet_id.to_string(), wallet_seed.clone());
-
             // Create wallet via API
             let resp = client
-                .post("http://localhost:8001/start")
+                .post(format!("{}/start", headless_url))
                 .json(&json!({
                     "wallet-id": wallet_id,
                     "seed": wallet_seed,
@@ -504,24 +2889,93 @@ async fn execute_tool(state: &McpState, name: &str, params: &Value) -> Result<St
                 .json()
                 .await
                 .unwrap_or(json!({"error": "Failed to parse response"}));
+            let success = result
+                .get("success")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(false);
+
+            // Remember the wallet (and, when the user has opted in, its seed)
+            // so it is re-opened after a restart
+            let mut seed_persisted = false;
+            if success {
+                let instance = params
+                    .get("instance")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or(crate::DEFAULT_HEADLESS_INSTANCE);
+                match crate::wallets::register_wallet(wallet_id, &wallet_seed, instance, None).await
+                {
+                    Ok(stored) => seed_persisted = stored,
+                    Err(e) => eprintln!("Failed to register wallet: {}", e),
+                }
+            }
+
             Ok(json!({
-                "success": result.get("success").and_then(|v| v.as_bool()).unwrap_or(false),
+                "success": success,
                 "wallet_id": wallet_id,
                 "seed_stored": true,
+                "seed_persisted": seed_persisted,
                 "message": if seed.is_some() { "Wallet created with provided seed" } else { "Wallet created with generated seed (use get_wallet_seed to retrieve)" }
             }).to_string())
         }
 
+        "import_wallet" => {
+            let seed_source = params
+                .get("seed_source")
+                .and_then(|v| v.as_str())
+                .ok_or("seed_source is required")?;
+            let seed = crate::wallets::read_seed_source(seed_source)?;
+
+            let mut params = params.clone();
+            params["seed"] = json!(seed);
+            Box::pin(execute_tool(state, "create_wallet", &params)).await
+        }
+
+        "set_wallet_settings" => {
+            let wallet_id = params
+                .get("wallet_id")
+                .and_then(|v| v.as_str())
+                .ok_or("wallet_id is required")?;
+            let text = |key: &str| params.get(key).and_then(|v| v.as_str()).map(String::from);
+
+            let change_address = match text("change_strategy").as_deref() {
+                None | Some("default") => crate::wallets::ChangeAddressStrategy::Default,
+                Some("first_address") => crate::wallets::ChangeAddressStrategy::FirstAddress,
+                Some("fixed") => crate::wallets::ChangeAddressStrategy::Fixed {
+                    address: text("change_address")
+                        .ok_or("change_address is required for the fixed strategy")?,
+                },
+                Some(other) => return Err(format!("Unknown change strategy: {}", other)),
+            };
+            let settings = crate::wallets::WalletSettings {
+                label: text("label"),
+                default_token: text("default_token"),
+                change_address,
+            };
+            crate::wallets::set_wallet_settings_internal(wallet_id, settings.clone())?;
+
+            Ok(json!({
+                "success": true,
+                "wallet_id": wallet_id,
+                "settings": settings
+            })
+            .to_string())
+        }
+
         "get_wallet_seed" => {
             let wallet_id = params
                 .get("wallet_id")
                 .and_then(|v| v.as_str())
                 .ok_or("wallet_id is required")?;
 
-            let seeds = state.wallet_seeds.lock().await;
-            match seeds.get(wallet_id) {
+            let session_seed = state.wallet_seeds.lock().await.get(wallet_id).cloned();
+            let seed = match session_seed {
+                Some(seed) => Some(seed),
+                None => crate::wallets::wallet_seed(wallet_id).await?,
+            };
+
+            match seed {
                 Some(seed) => Ok(json!({"wallet_id": wallet_id, "seed": seed}).to_string()),
-                None => Ok(json!({"error": "Seed not found. Only seeds from wallets created in this session are stored."}).to_string()),
+                None => Ok(json!({"error": "Seed not found. Only seeds from wallets created through Forge are stored."}).to_string()),
             }
         }
 
@@ -530,9 +2984,10 @@ async fn execute_tool(state: &McpState, name: &str, params: &Value) -> Result<St
                 .get("wallet_id")
                 .and_then(|v| v.as_str())
                 .ok_or("wallet_id is required")?;
+            let headless_url = headless_url(state, params).await?;
 
             let resp = client
-                .get("http://localhost:8001/wallet/status")
+                .get(format!("{}/wallet/status", headless_url))
                 .header("X-Wallet-Id", wallet_id)
                 .send()
                 .await
@@ -542,14 +2997,31 @@ async fn execute_tool(state: &McpState, name: &str, params: &Value) -> Result<St
             Ok(text)
         }
 
+        "get_wallet_sync_progress" => {
+            let wallet_id = params
+                .get("wallet_id")
+                .and_then(|v| v.as_str())
+                .ok_or("wallet_id is required")?;
+            let instance = params.get("instance").and_then(|v| v.as_str());
+
+            let progress = crate::wallet_sync::get_wallet_sync_progress_internal(
+                &state.app_state,
+                wallet_id,
+                instance,
+            )
+            .await?;
+            serde_json::to_string(&progress).map_err(|e| e.to_string())
+        }
+
         "get_wallet_balance" => {
             let wallet_id = params
                 .get("wallet_id")
                 .and_then(|v| v.as_str())
                 .ok_or("wallet_id is required")?;
+            let headless_url = headless_url(state, params).await?;
 
             let resp = client
-                .get("http://localhost:8001/wallet/balance")
+                .get(format!("{}/wallet/balance", headless_url))
                 .header("X-Wallet-Id", wallet_id)
                 .send()
                 .await
@@ -564,9 +3036,10 @@ async fn execute_tool(state: &McpState, name: &str, params: &Value) -> Result<St
                 .get("wallet_id")
                 .and_then(|v| v.as_str())
                 .ok_or("wallet_id is required")?;
+            let headless_url = headless_url(state, params).await?;
 
             let resp = client
-                .get("http://localhost:8001/wallet/addresses")
+                .get(format!("{}/wallet/addresses", headless_url))
                 .header("X-Wallet-Id", wallet_id)
                 .send()
                 .await
@@ -581,22 +3054,45 @@ async fn execute_tool(state: &McpState, name: &str, params: &Value) -> Result<St
                 .get("wallet_id")
                 .and_then(|v| v.as_str())
                 .ok_or("wallet_id is required")?;
+            let headless_url = headless_url(state, params).await?;
             let address = params
                 .get("address")
                 .and_then(|v| v.as_str())
                 .ok_or("address is required")?;
+            crate::address::check_address(address)?;
             let amount = params
                 .get("amount")
                 .and_then(|v| v.as_f64())
                 .ok_or("amount is required")?;
+            let amount = HtrAmount::from_decimal(amount)?;
+            let timelock = params.get("timelock").and_then(|v| v.as_u64());
+
+            // simple-send-tx has no timelock support; timelocked sends go through send-tx
+            let (path, mut body) = match timelock {
+                Some(timelock) => (
+                    "/wallet/send-tx",
+                    json!({
+                        "outputs": [{
+                            "address": address,
+                            "value": amount,
+                            "timelock": timelock,
+                        }]
+                    }),
+                ),
+                None => (
+                    "/wallet/simple-send-tx",
+                    json!({
+                        "address": address,
+                        "value": amount,
+                    }),
+                ),
+            };
+            crate::wallets::apply_send_defaults(&mut body, &headless_url, wallet_id).await?;
 
             let resp = client
-                .post("http://localhost:8001/wallet/simple-send-tx")
+                .post(format!("{}{}", headless_url, path))
                 .header("X-Wallet-Id", wallet_id)
-                .json(&json!({
-                    "address": address,
-                    "value": (amount * 100.0) as i64,
-                }))
+                .json(&body)
                 .send()
                 .await
                 .map_err(|e| format!("Failed to send transaction: {}", e))?;
@@ -605,26 +3101,94 @@ async fn execute_tool(state: &McpState, name: &str, params: &Value) -> Result<St
             Ok(text)
         }
 
+        "build_and_send_tx" => {
+            let request: crate::transactions::BuildTxRequest =
+                serde_json::from_value(params.clone())
+                    .map_err(|e| format!("Invalid parameters: {}", e))?;
+
+            let result =
+                crate::transactions::build_and_send_tx_internal(&state.app_state, &request).await?;
+            serde_json::to_string(&result).map_err(|e| e.to_string())
+        }
+
+        "estimate_tx" => {
+            let request: crate::transactions::BuildTxRequest =
+                serde_json::from_value(params.clone())
+                    .map_err(|e| format!("Invalid parameters: {}", e))?;
+
+            let estimate =
+                crate::transactions::estimate_tx_internal(&state.app_state, &request).await?;
+            serde_json::to_string(&estimate).map_err(|e| e.to_string())
+        }
+
+        "atomic_swap" => {
+            let request: crate::swap::AtomicSwapRequest = serde_json::from_value(params.clone())
+                .map_err(|e| format!("Invalid parameters: {}", e))?;
+
+            let result = crate::swap::atomic_swap_internal(&state.app_state, &request).await?;
+            serde_json::to_string(&result).map_err(|e| e.to_string())
+        }
+
+        "submit_signed_tx" => {
+            let request: crate::transactions::SubmitSignedTxRequest =
+                serde_json::from_value(params.clone())
+                    .map_err(|e| format!("Invalid parameters: {}", e))?;
+
+            let result =
+                crate::transactions::submit_signed_tx_internal(&state.app_state, &request).await?;
+            serde_json::to_string(&result).map_err(|e| e.to_string())
+        }
+
         "close_wallet" => {
             let wallet_id = params
                 .get("wallet_id")
                 .and_then(|v| v.as_str())
                 .ok_or("wallet_id is required")?;
+            let headless_url = headless_url(state, params).await?;
 
             let resp = client
-                .post("http://localhost:8001/wallet/stop")
+                .post(format!("{}/wallet/stop", headless_url))
                 .header("X-Wallet-Id", wallet_id)
                 .send()
                 .await
                 .map_err(|e| format!("Failed to close wallet: {}", e))?;
 
             state.wallet_seeds.lock().await.remove(wallet_id);
+            let _ = crate::wallets::set_auto_start(wallet_id, false).await;
 
             let text = resp.text().await.unwrap_or_default();
             Ok(text)
         }
 
         // Faucet
+        "get_faucet_addresses" => {
+            let addresses = crate::faucet::faucet_addresses_internal(&state.app_state).await?;
+            serde_json::to_string(&addresses).map_err(|e| e.to_string())
+        }
+
+        "set_auto_start_miner" => {
+            let enabled = params
+                .get("enabled")
+                .and_then(|v| v.as_bool())
+                .ok_or("enabled is required")?;
+            let threads = params
+                .get("threads")
+                .and_then(|v| v.as_u64())
+                .map(|t| u32::try_from(t).map_err(|_| "Invalid threads"))
+                .transpose()?;
+            let settings = crate::auto_miner::set_auto_start(enabled, threads)?;
+            serde_json::to_string(&settings).map_err(|e| e.to_string())
+        }
+
+        "set_faucet_address_rotation" => {
+            let enabled = params
+                .get("enabled")
+                .and_then(|v| v.as_bool())
+                .ok_or("enabled is required")?;
+            let settings = crate::faucet::set_address_rotation(enabled)?;
+            serde_json::to_string(&settings).map_err(|e| e.to_string())
+        }
+
         "get_faucet_balance" => {
             let resp = client
                 .get("http://127.0.0.1:8080/v1a/wallet/balance/")
@@ -641,20 +3205,28 @@ async fn execute_tool(state: &McpState, name: &str, params: &Value) -> Result<St
                 .get("address")
                 .and_then(|v| v.as_str())
                 .ok_or("address is required")?;
+            crate::address::check_address(address)?;
             let amount = params
                 .get("amount")
                 .and_then(|v| v.as_f64())
                 .ok_or("amount is required")?;
+            let amount = HtrAmount::from_decimal(amount)?;
 
+            let mut output = json!({
+                "address": address,
+                "value": amount,
+            });
+            if let Some(timelock) = params.get("timelock").and_then(|v| v.as_u64()) {
+                output["timelock"] = json!(timelock);
+            }
+
+            crate::faucet::rotate_address(&client).await?;
             let resp = client
                 .post("http://127.0.0.1:8080/v1a/wallet/send_tokens/")
                 .json(&json!({
                     "data": {
                         "inputs": [],
-                        "outputs": [{
-                            "address": address,
-                            "value": (amount * 100.0) as i64,
-                        }]
+                        "outputs": [output]
                     }
                 }))
                 .send()
@@ -665,16 +3237,49 @@ async fn execute_tool(state: &McpState, name: &str, params: &Value) -> Result<St
             Ok(text)
         }
 
+        "faucet_distribute" => {
+            let payments = params
+                .get("payments")
+                .and_then(|v| v.as_array())
+                .ok_or("payments is required")?
+                .iter()
+                .map(|payment| {
+                    let address = payment
+                        .get("address")
+                        .and_then(|v| v.as_str())
+                        .ok_or("Each payment needs an address")?;
+                    let amount = payment
+                        .get("amount")
+                        .and_then(|v| v.as_f64())
+                        .ok_or("Each payment needs an amount")?;
+                    Ok(crate::faucet::FaucetPayment {
+                        address: address.to_string(),
+                        amount: HtrAmount::from_decimal(amount)?,
+                        timelock: payment.get("timelock").and_then(|v| v.as_u64()),
+                    })
+                })
+                .collect::<Result<Vec<_>, String>>()?;
+
+            let result =
+                crate::faucet::faucet_distribute_internal(&state.app_state, &payments).await?;
+            serde_json::to_string(&result).map_err(|e| e.to_string())
+        }
+
         "fund_wallet" => {
             let wallet_id = params
                 .get("wallet_id")
                 .and_then(|v| v.as_str())
                 .ok_or("wallet_id is required")?;
-            let amount = params.get("amount").and_then(|v| v.as_f64());
+            let headless_url = headless_url(state, params).await?;
+            let amount = params
+                .get("amount")
+                .and_then(|v| v.as_f64())
+                .map(HtrAmount::from_decimal)
+                .transpose()?;
 
             // Get wallet's first address
             let addresses_resp = client
-                .get("http://localhost:8001/wallet/addresses")
+                .get(format!("{}/wallet/addresses", headless_url))
                 .header("X-Wallet-Id", wallet_id)
                 .send()
                 .await
@@ -704,26 +3309,21 @@ async fn execute_tool(state: &McpState, name: &str, params: &Value) -> Result<St
                 .await
                 .map_err(|_| "Failed to parse faucet balance")?;
 
-            let available = balance
-                .get("balance")
-                .and_then(|b| b.get("available"))
-                .and_then(|a| a.as_i64())
-                .unwrap_or(0);
+            let available = HtrAmount::from_json(&balance["balance"]["available"]);
 
-            if available <= 0 {
+            if available.is_zero() {
                 return Err("Faucet has no funds. Mine some blocks first.".to_string());
             }
 
             // Calculate amount
+            // Default: 10% of the faucet, between 1 and 100 HTR
             let fund_amount = match amount {
-                Some(a) => (a * 100.0) as i64,
-                None => {
-                    let ten_percent = available / 10;
-                    ten_percent.max(100).min(10000)
-                }
+                Some(a) => a,
+                None => HtrAmount::from_cents((available.cents() / 10).clamp(100, 10000)),
             };
 
             // Send from faucet
+            crate::faucet::rotate_address(&client).await?;
             let send_resp = client
                 .post("http://127.0.0.1:8080/v1a/wallet/send_tokens/")
                 .json(&json!({
@@ -743,50 +3343,343 @@ async fn execute_tool(state: &McpState, name: &str, params: &Value) -> Result<St
             Ok(format!(
                 r#"{{"funded": true, "wallet_id": "{}", "amount": {}, "result": {}}}"#,
                 wallet_id,
-                fund_amount as f64 / 100.0,
+                fund_amount.to_decimal(),
                 text
             ))
         }
 
-        // Blockchain
-        "get_blocks" => {
-            let count = params.get("count").and_then(|v| v.as_i64()).unwrap_or(10) as usize;
+        // Tokens
+        "create_token" => {
+            let wallet_id = params
+                .get("wallet_id")
+                .and_then(|v| v.as_str())
+                .ok_or("wallet_id is required")?;
+            let name = params
+                .get("name")
+                .and_then(|v| v.as_str())
+                .ok_or("name is required")?;
+            let symbol = params
+                .get("symbol")
+                .and_then(|v| v.as_str())
+                .ok_or("symbol is required")?;
+            let amount = params
+                .get("amount")
+                .and_then(|v| v.as_u64())
+                .ok_or("amount is required")?;
+            let instance = params.get("instance").and_then(|v| v.as_str());
 
-            let status_resp = client
-                .get("http://127.0.0.1:8080/v1a/status/")
-                .send()
-                .await
-                .map_err(|e| format!("Failed to get status: {}", e))?;
+            let token = crate::tokens::create_token_internal(
+                &state.app_state,
+                wallet_id,
+                name,
+                symbol,
+                amount,
+                instance,
+            )
+            .await?;
+            serde_json::to_string(&token).map_err(|e| e.to_string())
+        }
 
-            let status: Value = status_resp
-                .json()
-                .await
-                .map_err(|_| "Failed to parse status")?;
-
-            let height = status
-                .get("dag")
-                .and_then(|d| d.get("best_block"))
-                .and_then(|b| b.get("height"))
-                .and_then(|h| h.as_i64())
-                .unwrap_or(0) as usize;
-
-            let mut blocks = Vec::new();
-            for i in (height.saturating_sub(count)..=height).rev() {
-                if let Ok(resp) = client
-                    .get(format!(
-                        "http://127.0.0.1:8080/v1a/block_at_height?height={}",
-                        i
-                    ))
-                    .send()
-                    .await
-                {
-                    if let Ok(block) = resp.json::<Value>().await {
-                        blocks.push(block);
+        "create_nft" => {
+            let wallet_id = params
+                .get("wallet_id")
+                .and_then(|v| v.as_str())
+                .ok_or("wallet_id is required")?;
+            let name = params
+                .get("name")
+                .and_then(|v| v.as_str())
+                .ok_or("name is required")?;
+            let symbol = params
+                .get("symbol")
+                .and_then(|v| v.as_str())
+                .ok_or("symbol is required")?;
+            let data = params
+                .get("data")
+                .and_then(|v| v.as_str())
+                .ok_or("data is required")?;
+            let amount = params.get("amount").and_then(|v| v.as_u64()).unwrap_or(1);
+            let instance = params.get("instance").and_then(|v| v.as_str());
+
+            let token = crate::tokens::create_nft_internal(
+                &state.app_state,
+                wallet_id,
+                name,
+                symbol,
+                amount,
+                data,
+                instance,
+            )
+            .await?;
+            serde_json::to_string(&token).map_err(|e| e.to_string())
+        }
+
+        "nft_gallery" => {
+            let wallet_id = params
+                .get("wallet_id")
+                .and_then(|v| v.as_str())
+                .ok_or("wallet_id is required")?;
+            let instance = params.get("instance").and_then(|v| v.as_str());
+
+            let nfts =
+                crate::tokens::nft_gallery_internal(&state.app_state, wallet_id, instance).await?;
+            Ok(json!({"wallet_id": wallet_id, "nfts": nfts}).to_string())
+        }
+
+        "list_tokens" => {
+            let tokens = crate::tokens::list_tokens_internal(&state.app_state).await;
+            Ok(json!({"tokens": tokens}).to_string())
+        }
+
+        "register_token" => {
+            let (token_uid, name, symbol) =
+                match params.get("configuration_string").and_then(|v| v.as_str()) {
+                    Some(config) => crate::tokens::parse_configuration_string(config)?,
+                    None => {
+                        let field = |key: &str| {
+                            params
+                                .get(key)
+                                .and_then(|v| v.as_str())
+                                .map(String::from)
+                                .ok_or(format!(
+                                    "{} is required without a configuration_string",
+                                    key
+                                ))
+                        };
+                        (field("token_uid")?, field("name")?, field("symbol")?)
                     }
-                }
+                };
+
+            let token = crate::tokens::register_token_internal(
+                &state.app_state,
+                &token_uid,
+                &name,
+                &symbol,
+                None,
+            )
+            .await?;
+            serde_json::to_string(&token).map_err(|e| e.to_string())
+        }
+
+        "get_token_balances" => {
+            let wallet_id = params
+                .get("wallet_id")
+                .and_then(|v| v.as_str())
+                .ok_or("wallet_id is required")?;
+            let instance = params.get("instance").and_then(|v| v.as_str());
+
+            let balances =
+                crate::tokens::get_token_balances_internal(&state.app_state, wallet_id, instance)
+                    .await?;
+            Ok(json!({"wallet_id": wallet_id, "balances": balances}).to_string())
+        }
+
+        "get_tx_history" => {
+            let wallet_id = params
+                .get("wallet_id")
+                .and_then(|v| v.as_str())
+                .ok_or("wallet_id is required")?;
+            let limit = params.get("limit").and_then(|v| v.as_u64());
+            let instance = params.get("instance").and_then(|v| v.as_str());
+
+            let history = crate::tokens::get_tx_history_internal(
+                &state.app_state,
+                wallet_id,
+                limit,
+                instance,
+            )
+            .await?;
+            Ok(json!({"wallet_id": wallet_id, "history": history}).to_string())
+        }
+
+        "mint_tokens" | "melt_tokens" => {
+            let wallet_id = params
+                .get("wallet_id")
+                .and_then(|v| v.as_str())
+                .ok_or("wallet_id is required")?;
+            let token_uid = params
+                .get("token_uid")
+                .and_then(|v| v.as_str())
+                .ok_or("token_uid is required")?;
+            let amount = params
+                .get("amount")
+                .and_then(|v| v.as_u64())
+                .ok_or("amount is required")?;
+            let instance = params.get("instance").and_then(|v| v.as_str());
+
+            let result = if name == "mint_tokens" {
+                let address = params.get("address").and_then(|v| v.as_str());
+                crate::tokens::mint_tokens_internal(
+                    &state.app_state,
+                    wallet_id,
+                    token_uid,
+                    amount,
+                    address,
+                    instance,
+                )
+                .await?
+            } else {
+                crate::tokens::melt_tokens_internal(
+                    &state.app_state,
+                    wallet_id,
+                    token_uid,
+                    amount,
+                    instance,
+                )
+                .await?
+            };
+            serde_json::to_string(&result).map_err(|e| e.to_string())
+        }
+
+        "delegate_authority" => {
+            let wallet_id = params
+                .get("wallet_id")
+                .and_then(|v| v.as_str())
+                .ok_or("wallet_id is required")?;
+            let token_uid = params
+                .get("token_uid")
+                .and_then(|v| v.as_str())
+                .ok_or("token_uid is required")?;
+            let authority: crate::tokens::Authority = params
+                .get("authority")
+                .cloned()
+                .map(serde_json::from_value)
+                .ok_or("authority is required")?
+                .map_err(|_| "authority must be \"mint\" or \"melt\"")?;
+            let address = params
+                .get("address")
+                .and_then(|v| v.as_str())
+                .ok_or("address is required")?;
+            let keep_authority = params
+                .get("keep_authority")
+                .and_then(|v| v.as_bool())
+                .unwrap_or(true);
+            let instance = params.get("instance").and_then(|v| v.as_str());
+
+            let result = crate::tokens::delegate_authority_internal(
+                &state.app_state,
+                wallet_id,
+                token_uid,
+                authority,
+                address,
+                keep_authority,
+                instance,
+            )
+            .await?;
+            serde_json::to_string(&result).map_err(|e| e.to_string())
+        }
+
+        "get_token_authorities" => {
+            let token_uid = params
+                .get("token_uid")
+                .and_then(|v| v.as_str())
+                .ok_or("token_uid is required")?;
+            let instance = params.get("instance").and_then(|v| v.as_str());
+
+            let result = crate::tokens::get_token_authorities_internal(
+                &state.app_state,
+                token_uid,
+                instance,
+            )
+            .await?;
+            serde_json::to_string(&result).map_err(|e| e.to_string())
+        }
+
+        // Payments
+        "watch_address" => {
+            let address = params
+                .get("address")
+                .and_then(|v| v.as_str())
+                .ok_or("address is required")?;
+            let min_amount = params
+                .get("min_amount")
+                .and_then(|v| v.as_u64())
+                .unwrap_or(0);
+            let token = params.get("token").and_then(|v| v.as_str());
+            let timeout = params.get("timeout").and_then(|v| v.as_u64());
+
+            let payment = crate::payments::watch_address_internal(
+                &state.app_state,
+                address,
+                min_amount,
+                token,
+                timeout,
+            )
+            .await?;
+            serde_json::to_string(&payment).map_err(|e| e.to_string())
+        }
+
+        // Multisig
+        "get_multisig_xpub" => {
+            let seed = params
+                .get("seed")
+                .and_then(|v| v.as_str())
+                .map(String::from);
+            let wallet_id = params
+                .get("wallet_id")
+                .and_then(|v| v.as_str())
+                .map(String::from);
+            let instance = params.get("instance").and_then(|v| v.as_str());
+
+            let xpub = crate::multisig::get_multisig_xpub_internal(
+                &state.app_state,
+                seed,
+                wallet_id,
+                instance,
+            )
+            .await?;
+            Ok(json!({"xpubkey": xpub}).to_string())
+        }
+
+        "create_multisig_wallet" => {
+            let mut request: crate::multisig::CreateMultisigWalletRequest =
+                serde_json::from_value(json!({
+                    "wallet_id": params.get("wallet_id"),
+                    "seed": params
+                        .get("seed")
+                        .cloned()
+                        .unwrap_or_else(|| json!("")),
+                    "config_name": params.get("config_name"),
+                    "pubkeys": params.get("pubkeys"),
+                    "num_signatures": params.get("num_signatures"),
+                    "instance": params.get("instance"),
+                }))
+                .map_err(|e| format!("Invalid parameters: {}", e))?;
+
+            if request.seed.is_empty() {
+                request.seed = crate::generate_seed_internal()?;
             }
+            state
+                .wallet_seeds
+                .lock()
+                .await
+                .insert(request.wallet_id.clone(), request.seed.clone());
+
+            let result =
+                crate::multisig::create_multisig_wallet_internal(&state.app_state, request).await?;
+            serde_json::to_string(&result).map_err(|e| e.to_string())
+        }
+
+        "send_multisig_tx" => {
+            let request: crate::multisig::MultisigSendRequest =
+                serde_json::from_value(params.clone())
+                    .map_err(|e| format!("Invalid parameters: {}", e))?;
 
-            Ok(json!({"blocks": blocks, "currentHeight": height}).to_string())
+            let result =
+                crate::multisig::send_multisig_tx_internal(&state.app_state, &request).await?;
+            serde_json::to_string(&result).map_err(|e| e.to_string())
+        }
+
+        // Blockchain
+        "get_blocks" => {
+            let count = params.get("count").and_then(|v| v.as_u64());
+            let offset = params.get("offset").and_then(|v| v.as_u64());
+            let page = crate::blocks::get_blocks_internal(&state.app_state, count, offset).await?;
+            Ok(json!({
+                "blocks": page.blocks,
+                "currentHeight": page.current_height,
+                "offset": page.offset,
+            })
+            .to_string())
         }
 
         "get_transaction" => {
@@ -808,6 +3701,15 @@ async fn execute_tool(state: &McpState, name: &str, params: &Value) -> Result<St
             Ok(text)
         }
 
+        "search_chain" => {
+            let query = params
+                .get("query")
+                .and_then(|v| v.as_str())
+                .ok_or("query is required")?;
+            let result = crate::search::search_chain_internal(&state.app_state, query).await?;
+            serde_json::to_string(&result).map_err(|e| e.to_string())
+        }
+
         // Utilities
         "quick_start" => {
             let mut results = Vec::new();
@@ -822,13 +3724,16 @@ async fn execute_tool(state: &McpState, name: &str, params: &Value) -> Result<St
             tokio::time::sleep(Duration::from_secs(2)).await;
 
             // Start miner
-            match crate::start_miner_internal(&state.app_state, None).await {
+            match crate::start_miner_internal(&state.app_state, crate::MinerConfig::default()).await
+            {
                 Ok(msg) => results.push(msg),
                 Err(e) => results.push(format!("Miner: {}", e)),
             }
 
             // Start headless
-            match crate::start_headless_internal(&state.app_state).await {
+            match crate::start_headless_internal(&state.app_state, crate::HeadlessConfig::default())
+                .await
+            {
                 Ok(msg) => results.push(msg),
                 Err(e) => results.push(format!("Headless: {}", e)),
             }
@@ -848,12 +3753,26 @@ async fn execute_tool(state: &McpState, name: &str, params: &Value) -> Result<St
                     "pid": app_state.node_child_id,
                 },
                 "miner": {
-                    "running": app_state.miner_running,
-                    "pid": app_state.miner_child_id,
+                    "running": !app_state.miners.is_empty(),
+                    "miners": app_state
+                        .miners
+                        .iter()
+                        .map(|(name, miner)| json!({"name": name, "pid": miner.child_id}))
+                        .collect::<Vec<_>>(),
+                    // Current or last session of each miner started so far
+                    "sessions": app_state
+                        .miner_metrics
+                        .values()
+                        .filter_map(|metrics| metrics.session_stats())
+                        .collect::<Vec<_>>(),
                 },
                 "headless": {
-                    "running": app_state.headless_running,
-                    "port": if app_state.headless_running { Some(8001) } else { None },
+                    "running": !app_state.headless_instances.is_empty(),
+                    "instances": app_state
+                        .headless_instances
+                        .iter()
+                        .map(|(name, instance)| json!({"instance": name, "port": instance.port}))
+                        .collect::<Vec<_>>(),
                 },
                 "activeWallets": seeds.keys().collect::<Vec<_>>(),
             });
@@ -886,6 +3805,8 @@ async fn execute_tool(state: &McpState, name: &str, params: &Value) -> Result<St
             if let Some(data_dir) = dirs::home_dir() {
                 let hathor_dir = data_dir.join(".hathor-forge");
                 if hathor_dir.exists() {
+                    // The log files in it are reopened on the next line
+                    crate::logging::close_files();
                     let _ = std::fs::remove_dir_all(&hathor_dir);
                 }
             }
@@ -952,6 +3873,7 @@ async fn handle_mcp_request(
                 .cloned()
                 .unwrap_or(json!({}));
 
+            crate::recorder::record(crate::recorder::CallSource::Mcp, tool_name, &arguments);
             match execute_tool(&state, tool_name, &arguments).await {
                 Ok(result) => JsonRpcResponse {
                     jsonrpc: "2.0".to_string(),
@@ -1002,12 +3924,32 @@ async fn handle_mcp_request(
 }
 
 async fn handle_sse(
-    State(_state): State<McpSharedState>,
+    State(state): State<McpSharedState>,
 ) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
-    // For now, just send periodic keepalive events
-    let stream = stream::unfold((), |_| async {
-        tokio::time::sleep(Duration::from_secs(30)).await;
-        Some((Ok(Event::default().comment("keepalive")), ()))
+    // Node events are pushed as logging notifications
+    let events = state.app_state.lock().await.node_events.subscribe();
+    let stream = stream::unfold(events, |mut events| async move {
+        loop {
+            match events.recv().await {
+                Ok(event) => {
+                    let notification = json!({
+                        "jsonrpc": "2.0",
+                        "method": "notifications/message",
+                        "params": {
+                            "level": "info",
+                            "logger": "hathor-node",
+                            "data": event,
+                        }
+                    });
+                    let message = Event::default()
+                        .event("message")
+                        .data(notification.to_string());
+                    return Some((Ok(message), events));
+                }
+                Err(RecvError::Lagged(_)) => continue,
+                Err(RecvError::Closed) => return None,
+            }
+        }
     });
 
     Sse::new(stream).keep_alive(
@@ -1017,8 +3959,11 @@ async fn handle_sse(
     )
 }
 
-async fn handle_health() -> impl IntoResponse {
-    (StatusCode::OK, "OK")
+async fn handle_health(
+    State(state): State<Arc<McpState>>,
+    Query(query): Query<crate::health::HealthQuery>,
+) -> Response {
+    crate::health::health_response(&state.app_state, query).await
 }
 
 // ============================================================================
@@ -1040,12 +3985,35 @@ pub async fn start_mcp_server(
     app_state: SharedState,
     port: u16,
 ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
-    let app = create_mcp_router(app_state);
+    // HTTPS when enabled (see tls.rs); the setting is read once, at startup
+    let (acceptor, access_log) = {
+        let state_guard = app_state.lock().await;
+        let acceptor = if crate::tls::enabled() {
+            Some(crate::tls::acceptor(&state_guard)?)
+        } else {
+            None
+        };
+        (acceptor, state_guard.access_log.clone())
+    };
+    let app = create_mcp_router(app_state)
+        .layer(crate::cors::layer())
+        .layer(axum::middleware::from_fn_with_state(
+            crate::access_log::AccessLogState::new(access_log, "mcp"),
+            crate::access_log::log_requests,
+        ))
+        .layer(tower_http::trace::TraceLayer::new_for_http());
 
     let listener = tokio::net::TcpListener::bind(format!("127.0.0.1:{}", port)).await?;
-    eprintln!("MCP Server listening on http://127.0.0.1:{}", port);
-
-    axum::serve(listener, app).await?;
+    eprintln!(
+        "MCP Server listening on {}://127.0.0.1:{}",
+        crate::tls::scheme(acceptor.is_some()),
+        port
+    );
+
+    match acceptor {
+        Some(acceptor) => crate::tls::serve(listener, app, acceptor, std::future::pending()).await,
+        None => axum::serve(listener, app).await?,
+    }
 
     Ok(())
 }
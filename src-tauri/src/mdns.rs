@@ -0,0 +1,115 @@
+//! mDNS advertisement of the shared environment
+//!
+//! While the explorer is shared on the LAN (see lan_share.rs), Forge
+//! advertises a `_hathor-forge._tcp` service on the explorer port, so
+//! companion tools and mobile test apps can discover the environment instead
+//! of typing IPs. The TXT record carries the node, wallet-headless (proxied
+//! under `/headless`) and explorer URLs and the network name. The service is
+//! unregistered when the advertisement is dropped, i.e. when the explorer
+//! server stops.
+
+use mdns_sd::{ServiceDaemon, ServiceInfo};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use crate::lan_share::{EXPLORER_PORT, NETWORK_NAME};
+
+pub const SERVICE_TYPE: &str = "_hathor-forge._tcp.local.";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MdnsInfo {
+    pub service_type: String,
+    pub instance_name: String,
+    pub port: u16,
+    // The TXT record
+    pub properties: BTreeMap<String, String>,
+}
+
+/// A registered service, kept in `AppState` while the explorer is shared
+pub struct Advertisement {
+    daemon: ServiceDaemon,
+    fullname: String,
+    info: MdnsInfo,
+}
+
+impl Advertisement {
+    pub fn info(&self) -> &MdnsInfo {
+        &self.info
+    }
+}
+
+impl Drop for Advertisement {
+    fn drop(&mut self) {
+        let _ = self.daemon.unregister(&self.fullname);
+        let _ = self.daemon.shutdown();
+    }
+}
+
+// This machine's name, for the instance and host names
+fn machine_name() -> String {
+    let name = std::env::var("HOSTNAME")
+        .or_else(|_| std::env::var("COMPUTERNAME"))
+        .unwrap_or_default();
+    let name: String = name
+        .split('.')
+        .next()
+        .unwrap_or_default()
+        .chars()
+        .filter(|c| c.is_ascii_alphanumeric() || *c == '-')
+        .collect();
+    if name.is_empty() {
+        "localhost".to_string()
+    } else {
+        name.to_lowercase()
+    }
+}
+
+/// Advertise the environment as reachable at `lan_ip`, over `scheme`
+pub fn advertise(lan_ip: &str, scheme: &str) -> Result<Advertisement, String> {
+    let machine = machine_name();
+    let instance_name = format!("Hathor Forge on {}", machine);
+    let host_name = format!("hathor-forge-{}.local.", machine);
+
+    let explorer_url = format!("{}://{}:{}", scheme, lan_ip, EXPLORER_PORT);
+    let properties = BTreeMap::from([
+        ("node".to_string(), format!("{}/v1a/", explorer_url)),
+        (
+            "headless".to_string(),
+            format!("{}/headless/", explorer_url),
+        ),
+        ("explorer".to_string(), explorer_url),
+        ("network".to_string(), NETWORK_NAME.to_string()),
+        ("version".to_string(), env!("CARGO_PKG_VERSION").to_string()),
+    ]);
+
+    let service = ServiceInfo::new(
+        SERVICE_TYPE,
+        &instance_name,
+        &host_name,
+        lan_ip,
+        EXPLORER_PORT,
+        properties
+            .clone()
+            .into_iter()
+            .collect::<std::collections::HashMap<_, _>>(),
+    )
+    .map_err(|e| format!("Failed to build mDNS service: {}", e))?;
+    let fullname = service.get_fullname().to_string();
+
+    let daemon =
+        ServiceDaemon::new().map_err(|e| format!("Failed to start mDNS responder: {}", e))?;
+    daemon
+        .register(service)
+        .map_err(|e| format!("Failed to register mDNS service: {}", e))?;
+
+    Ok(Advertisement {
+        daemon,
+        fullname,
+        info: MdnsInfo {
+            service_type: SERVICE_TYPE.to_string(),
+            instance_name,
+            port: EXPLORER_PORT,
+            properties,
+        },
+    })
+}
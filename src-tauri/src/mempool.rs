@@ -0,0 +1,202 @@
+//! Mempool inspection
+//!
+//! The node reports which transactions are unconfirmed but not when it got
+//! them, so while the node runs a background task polls `/v1a/mempool` and
+//! remembers when each transaction first showed up. It emits
+//! `mempool-size-changed` when the size moves by at least
+//! `SIGNIFICANT_CHANGE` since the last event, or the mempool drains, which
+//! makes transactions piling up without confirming easy to spot.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter};
+
+use crate::SharedState;
+
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+const SIGNIFICANT_CHANGE: usize = 10;
+const DEFAULT_LIMIT: usize = 100;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MempoolTx {
+    pub tx_id: String,
+    // When Forge first saw it in the mempool (Unix timestamp)
+    pub first_seen: u64,
+    pub age_secs: u64,
+    pub timestamp: Option<u64>,
+    pub weight: Option<f64>,
+    pub parents: Vec<String>,
+    pub voided_by: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Mempool {
+    pub size: usize,
+    // Oldest first, up to the requested limit
+    pub transactions: Vec<MempoolTx>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MempoolSizeChange {
+    pub previous: usize,
+    pub size: usize,
+}
+
+/// First-seen times of mempool transactions, kept in `AppState`
+#[derive(Default)]
+pub struct MempoolTracker {
+    first_seen: HashMap<String, u64>,
+    // Size in the last `mempool-size-changed` event
+    reported_size: usize,
+}
+
+impl MempoolTracker {
+    // Track the current mempool, forgetting confirmed transactions
+    fn update(&mut self, hashes: &[String]) {
+        let now = crate::unix_now();
+        let current: HashSet<&String> = hashes.iter().collect();
+        self.first_seen.retain(|hash, _| current.contains(hash));
+        for hash in hashes {
+            self.first_seen.entry(hash.clone()).or_insert(now);
+        }
+    }
+
+    // The change to report, if the size moved enough since the last one
+    fn size_change(&mut self, size: usize) -> Option<MempoolSizeChange> {
+        let previous = self.reported_size;
+        let drained = size == 0 && previous > 0;
+        if !drained && size.abs_diff(previous) < SIGNIFICANT_CHANGE {
+            return None;
+        }
+        self.reported_size = size;
+        Some(MempoolSizeChange { previous, size })
+    }
+}
+
+fn strings(value: &Value) -> Vec<String> {
+    value
+        .as_array()
+        .map(|items| {
+            items
+                .iter()
+                .filter_map(|item| item.as_str().map(|s| s.to_string()))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+// Hashes of the transactions in the node's mempool
+async fn mempool_hashes(client: &reqwest::Client) -> Result<Vec<String>, String> {
+    let response: Value = client
+        .get("http://127.0.0.1:8080/v1a/mempool")
+        .send()
+        .await
+        .map_err(|e| format!("Failed to get mempool: {}", e))?
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse mempool: {}", e))?;
+    if response["success"] == false {
+        return Err("The node refused the mempool request".to_string());
+    }
+    Ok(strings(&response["transactions"]))
+}
+
+async fn mempool_tx(client: &reqwest::Client, tx_id: &str, first_seen: u64) -> MempoolTx {
+    let response: Value = match client
+        .get("http://127.0.0.1:8080/v1a/transaction")
+        .query(&[("id", tx_id)])
+        .send()
+        .await
+    {
+        Ok(response) => response.json().await.unwrap_or_default(),
+        Err(_) => Value::Null,
+    };
+    let tx = &response["tx"];
+
+    MempoolTx {
+        tx_id: tx_id.to_string(),
+        first_seen,
+        age_secs: crate::unix_now().saturating_sub(first_seen),
+        timestamp: tx["timestamp"].as_u64(),
+        weight: tx["weight"].as_f64(),
+        parents: strings(&tx["parents"]),
+        voided_by: strings(&response["meta"]["voided_by"]),
+    }
+}
+
+/// Poll the mempool for the lifetime of the app, emitting
+/// `mempool-size-changed` on significant changes
+pub async fn watch_mempool(app: AppHandle, state: SharedState) {
+    let client = reqwest::Client::new();
+
+    loop {
+        tokio::time::sleep(POLL_INTERVAL).await;
+        {
+            let mut state_guard = state.lock().await;
+            if !state_guard.node_running {
+                state_guard.mempool = MempoolTracker::default();
+                continue;
+            }
+        }
+        let Ok(hashes) = mempool_hashes(&client).await else {
+            continue;
+        };
+
+        let change = {
+            let mut state_guard = state.lock().await;
+            state_guard.mempool.update(&hashes);
+            state_guard.mempool.size_change(hashes.len())
+        };
+        if let Some(change) = change {
+            let _ = app.emit("mempool-size-changed", &change);
+        }
+    }
+}
+
+/// Unconfirmed transactions with when they were first seen, oldest first
+pub async fn get_mempool_internal(
+    state: &SharedState,
+    limit: Option<usize>,
+) -> Result<Mempool, String> {
+    if !state.lock().await.node_running {
+        return Err("Node is not running".to_string());
+    }
+    let client = reqwest::Client::new();
+    let hashes = mempool_hashes(&client).await?;
+
+    let mut seen: Vec<(String, u64)> = {
+        let mut state_guard = state.lock().await;
+        state_guard.mempool.update(&hashes);
+        hashes
+            .iter()
+            .map(|hash| (hash.clone(), state_guard.mempool.first_seen[hash]))
+            .collect()
+    };
+    seen.sort_by_key(|(_, first_seen)| *first_seen);
+    seen.truncate(limit.unwrap_or(DEFAULT_LIMIT));
+
+    let mut transactions = Vec::with_capacity(seen.len());
+    for (tx_id, first_seen) in &seen {
+        transactions.push(mempool_tx(&client, tx_id, *first_seen).await);
+    }
+
+    Ok(Mempool {
+        size: hashes.len(),
+        transactions,
+    })
+}
+
+// ============================================================================
+// Tauri Commands
+// ============================================================================
+
+// Get the node's unconfirmed transactions with first-seen time, weight and parents
+#[tauri::command]
+pub async fn get_mempool(
+    state: tauri::State<'_, SharedState>,
+    limit: Option<usize>,
+) -> Result<Mempool, String> {
+    get_mempool_internal(&state, limit).await
+}
@@ -0,0 +1,506 @@
+//! Miner hash-rate, share and session metrics
+//!
+//! cpuminer reports per-thread hash rates (`thread 0: 2097152 hashes, 1423 khash/s`)
+//! and share results (`accepted: 3/4 (75.00%), 1423 khash/s (yay!!!)`) on stderr.
+//! Each line is parsed as it arrives and folded into the `AppState` metrics, which
+//! keep a rolling average of the total hash rate.
+//!
+//! Every miner start opens a session. While it runs, a tracker follows the node's
+//! height, counts the new blocks whose coinbase pays the miner's address, and
+//! stops the miner once the session's stop height or faucet balance is reached.
+//! Every block found is also published as a `BlockFound` on the `AppState`
+//! block channel. Miners rotating their coinbase address are restarted with the
+//! next address after each block, and the session carries on across restarts.
+
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, VecDeque};
+use std::future::Future;
+use std::pin::Pin;
+use std::time::{Duration, Instant};
+
+use crate::amount::HtrAmount;
+use crate::{coinbase_rotation, faucet, MinerConfig, SharedState};
+
+// Window of the rolling hash-rate average
+const AVERAGE_WINDOW: Duration = Duration::from_secs(60);
+const SESSION_POLL_INTERVAL: Duration = Duration::from_secs(2);
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct MinerMetricsReport {
+    pub miner: String,
+    // Latest total hash rate in hashes per second
+    pub hash_rate: Option<f64>,
+    // Average over the last minute
+    pub average_hash_rate: Option<f64>,
+    pub accepted_shares: u64,
+    pub rejected_shares: u64,
+    pub threads: usize,
+}
+
+/// Statistics of the current (or last) miner session
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MinerSessionStats {
+    pub miner: String,
+    pub running: bool,
+    pub address: String,
+    pub started_at: u64, // Unix timestamp
+    pub uptime_secs: u64,
+    pub accepted_shares: u64,
+    pub rejected_shares: u64,
+    pub blocks_found: u64,
+    pub start_height: Option<u64>,
+    pub current_height: Option<u64>,
+    pub last_block_hash: Option<String>,
+    pub stop_at_height: Option<u64>,
+    pub stop_at_faucet_balance: Option<HtrAmount>,
+    // Why the miner was stopped automatically, if it was
+    pub stop_reason: Option<String>,
+}
+
+/// A block whose coinbase pays a miner session's address
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BlockFound {
+    pub miner: String,
+    pub address: String,
+    pub height: u64,
+    pub hash: String,
+    // Coinbase value paid to the address
+    pub reward: HtrAmount,
+}
+
+#[derive(Debug)]
+struct MinerSession {
+    pid: u32,
+    address: String,
+    started: Instant,
+    started_at: u64,
+    ended: Option<Instant>,
+    start_height: Option<u64>,
+    current_height: Option<u64>,
+    blocks_found: u64,
+    last_block_hash: Option<String>,
+    // Every block found this session, oldest first
+    blocks: Vec<BlockFound>,
+    stop_at_height: Option<u64>,
+    stop_at_faucet_balance: Option<HtrAmount>,
+    stop_reason: Option<String>,
+    // Config to restart with after each block, for miners rotating their coinbase
+    rotation: Option<MinerConfig>,
+}
+
+/// Metrics of the current miner process, reset when a miner starts
+#[derive(Debug, Default)]
+pub struct MinerMetrics {
+    miner: String,
+    thread_rates: BTreeMap<u32, f64>,
+    samples: VecDeque<(Instant, f64)>,
+    accepted: u64,
+    rejected: u64,
+    session: Option<MinerSession>,
+}
+
+// What a cpuminer line tells us
+enum MinerLine {
+    ThreadRate {
+        thread: u32,
+        rate: f64,
+    },
+    Shares {
+        accepted: u64,
+        total: u64,
+        rate: Option<f64>,
+    },
+}
+
+// Parse "1423" + "khash/s" into hashes per second
+fn parse_rate(value: &str, unit: &str) -> Option<f64> {
+    let value: f64 = value.trim_end_matches(',').parse().ok()?;
+    let multiplier = match unit.strip_suffix("hash/s")? {
+        "" => 1.0,
+        "k" | "K" => 1e3,
+        "M" => 1e6,
+        "G" => 1e9,
+        "T" => 1e12,
+        _ => return None,
+    };
+    Some(value * multiplier)
+}
+
+fn parse_line(line: &str) -> Option<MinerLine> {
+    let words: Vec<&str> = line.split_whitespace().collect();
+    let rate = words
+        .windows(2)
+        .find_map(|pair| parse_rate(pair[0], pair[1]));
+
+    if let Some(pos) = words.iter().position(|w| *w == "accepted:") {
+        let (accepted, total) = words.get(pos + 1)?.split_once('/')?;
+        return Some(MinerLine::Shares {
+            accepted: accepted.parse().ok()?,
+            total: total.parse().ok()?,
+            rate,
+        });
+    }
+
+    let pos = words.iter().position(|w| *w == "thread")?;
+    let thread = words.get(pos + 1)?.trim_end_matches(':').parse().ok()?;
+    Some(MinerLine::ThreadRate {
+        thread,
+        rate: rate?,
+    })
+}
+
+impl MinerMetrics {
+    /// Fresh metrics for miner process `pid` started with `config`
+    pub fn new_session(pid: u32, config: &MinerConfig) -> Self {
+        let started_at = crate::unix_now();
+
+        Self {
+            miner: config.name.clone(),
+            session: Some(MinerSession {
+                pid,
+                address: config.address.clone(),
+                started: Instant::now(),
+                started_at,
+                ended: None,
+                start_height: None,
+                current_height: None,
+                blocks_found: 0,
+                last_block_hash: None,
+                blocks: Vec::new(),
+                stop_at_height: config.stop_at_height,
+                stop_at_faucet_balance: config.stop_at_faucet_balance,
+                stop_reason: None,
+                rotation: config.rotate_coinbase.map(|_| config.clone()),
+            }),
+            ..Self::default()
+        }
+    }
+
+    fn push_sample(&mut self, rate: f64) {
+        let now = Instant::now();
+        self.samples.push_back((now, rate));
+        while let Some((at, _)) = self.samples.front() {
+            if now.duration_since(*at) <= AVERAGE_WINDOW {
+                break;
+            }
+            self.samples.pop_front();
+        }
+    }
+
+    /// Fold a line of cpuminer output into the metrics; returns whether it carried any
+    pub fn record(&mut self, line: &str) -> bool {
+        match parse_line(line) {
+            Some(MinerLine::ThreadRate { thread, rate }) => {
+                self.thread_rates.insert(thread, rate);
+                let total = self.thread_rates.values().sum();
+                self.push_sample(total);
+            }
+            Some(MinerLine::Shares {
+                accepted,
+                total,
+                rate,
+            }) => {
+                self.accepted = accepted;
+                self.rejected = total.saturating_sub(accepted);
+                if let Some(rate) = rate {
+                    self.push_sample(rate);
+                }
+            }
+            None => return false,
+        }
+        true
+    }
+
+    pub fn report(&self) -> MinerMetricsReport {
+        let average = (!self.samples.is_empty()).then(|| {
+            self.samples.iter().map(|(_, rate)| rate).sum::<f64>() / self.samples.len() as f64
+        });
+
+        MinerMetricsReport {
+            miner: self.miner.clone(),
+            hash_rate: self.samples.back().map(|(_, rate)| *rate),
+            average_hash_rate: average,
+            accepted_shares: self.accepted,
+            rejected_shares: self.rejected,
+            threads: self.thread_rates.len(),
+        }
+    }
+
+    pub fn session_stats(&self) -> Option<MinerSessionStats> {
+        let session = self.session.as_ref()?;
+        let uptime = session
+            .ended
+            .unwrap_or_else(Instant::now)
+            .duration_since(session.started);
+
+        Some(MinerSessionStats {
+            miner: self.miner.clone(),
+            running: session.ended.is_none(),
+            address: session.address.clone(),
+            started_at: session.started_at,
+            uptime_secs: uptime.as_secs(),
+            accepted_shares: self.accepted,
+            rejected_shares: self.rejected,
+            blocks_found: session.blocks_found,
+            start_height: session.start_height,
+            current_height: session.current_height,
+            last_block_hash: session.last_block_hash.clone(),
+            stop_at_height: session.stop_at_height,
+            stop_at_faucet_balance: session.stop_at_faucet_balance,
+            stop_reason: session.stop_reason.clone(),
+        })
+    }
+
+    // Carry a session over from the process this one replaced
+    fn continue_from(&mut self, previous: MinerMetrics) {
+        let (Some(session), Some(previous)) = (self.session.as_mut(), previous.session) else {
+            return;
+        };
+        session.started = previous.started;
+        session.started_at = previous.started_at;
+        session.start_height = previous.start_height.or(session.start_height);
+        session.blocks_found += previous.blocks_found;
+        session.last_block_hash = session.last_block_hash.take().or(previous.last_block_hash);
+        let blocks = std::mem::take(&mut session.blocks);
+        session.blocks = previous.blocks;
+        session.blocks.extend(blocks);
+    }
+
+    /// Blocks found in the current (or last) session
+    pub fn session_blocks(&self) -> &[BlockFound] {
+        self.session
+            .as_ref()
+            .map(|session| session.blocks.as_slice())
+            .unwrap_or_default()
+    }
+}
+
+/// Parse a line of miner output into the shared metrics, returning the updated
+/// report when the line carried a hash rate or share count
+pub async fn record_miner_line(
+    state: &SharedState,
+    miner: &str,
+    line: &str,
+) -> Option<MinerMetricsReport> {
+    let mut state_guard = state.lock().await;
+    let metrics = state_guard.miner_metrics.get_mut(miner)?;
+    metrics.record(line).then(|| metrics.report())
+}
+
+/// Stats of a miner's current (or last) session (the default miner when `name` is None)
+pub async fn miner_session_stats(
+    state: &SharedState,
+    name: Option<&str>,
+) -> Option<MinerSessionStats> {
+    let state_guard = state.lock().await;
+    state_guard
+        .miner_metrics
+        .get(name.unwrap_or(crate::DEFAULT_MINER))?
+        .session_stats()
+}
+
+// Session of miner `name` if it still belongs to process `pid`
+fn session_mut<'a>(
+    state: &'a mut crate::AppState,
+    name: &str,
+    pid: u32,
+) -> Option<&'a mut MinerSession> {
+    state
+        .miner_metrics
+        .get_mut(name)?
+        .session
+        .as_mut()
+        .filter(|s| s.pid == pid)
+}
+
+async fn best_height(client: &reqwest::Client) -> Option<u64> {
+    let status: serde_json::Value = client
+        .get("http://127.0.0.1:8080/v1a/status/")
+        .send()
+        .await
+        .ok()?
+        .json()
+        .await
+        .ok()?;
+    status["dag"]["best_block"]["height"].as_u64()
+}
+
+// Hash of the block at `height` and the value paid to `address`, if its coinbase pays it
+async fn block_paying(
+    client: &reqwest::Client,
+    height: u64,
+    address: &str,
+) -> Option<(String, HtrAmount)> {
+    let block: serde_json::Value = client
+        .get("http://127.0.0.1:8080/v1a/block_at_height")
+        .query(&[("height", height)])
+        .send()
+        .await
+        .ok()?
+        .json()
+        .await
+        .ok()?;
+
+    let paid: Vec<&serde_json::Value> = block["block"]["outputs"]
+        .as_array()?
+        .iter()
+        .filter(|output| output["decoded"]["address"].as_str() == Some(address))
+        .collect();
+    if paid.is_empty() {
+        return None;
+    }
+    let reward = paid
+        .iter()
+        .map(|output| HtrAmount::from_json(&output["value"]).cents())
+        .sum();
+    let hash = block["block"]["tx_id"].as_str()?.to_string();
+    Some((hash, HtrAmount::from_cents(reward)))
+}
+
+// Restart miner `name` with the next address of its coinbase rotation. Boxed
+// because the restarted miner's tracker can end up here again.
+fn rotate_coinbase<'a>(
+    state: &'a SharedState,
+    name: &'a str,
+    config: &'a MinerConfig,
+) -> Pin<Box<dyn Future<Output = Result<(), String>> + Send + 'a>> {
+    Box::pin(async move {
+        let next = coinbase_rotation::next(config)?;
+        crate::stop_miner_internal(state, Some(name)).await?;
+        let previous = state.lock().await.miner_metrics.remove(name);
+
+        if let Err(e) = crate::start_miner_internal(state, next).await {
+            // Keep the stopped session's stats
+            if let Some(previous) = previous {
+                let mut state_guard = state.lock().await;
+                state_guard.miner_metrics.insert(name.to_string(), previous);
+            }
+            return Err(e);
+        }
+
+        let mut state_guard = state.lock().await;
+        if let (Some(metrics), Some(previous)) = (state_guard.miner_metrics.get_mut(name), previous)
+        {
+            metrics.continue_from(previous);
+        }
+        Ok(())
+    })
+}
+
+/// Follow the node's height while miner `name` (process `pid`) runs, counting
+/// the blocks it found and applying its stop conditions, and close its session
+/// when it exits
+pub async fn track_session(state: SharedState, name: String, pid: u32) {
+    let client = reqwest::Client::new();
+
+    loop {
+        let (address, stop_at_height, stop_at_faucet_balance) = {
+            let mut state_guard = state.lock().await;
+            let running = state_guard.miners.get(&name).and_then(|m| m.child_id) == Some(pid);
+            // A newer miner with the same name replaced this session
+            let Some(session) = session_mut(&mut state_guard, &name, pid) else {
+                return;
+            };
+            if !running {
+                session.ended.get_or_insert_with(Instant::now);
+                return;
+            }
+            (
+                session.address.clone(),
+                session.stop_at_height,
+                session.stop_at_faucet_balance,
+            )
+        };
+
+        let height = best_height(&client).await;
+        if let Some(height) = height {
+            let last = {
+                let mut state_guard = state.lock().await;
+                let Some(session) = session_mut(&mut state_guard, &name, pid) else {
+                    return;
+                };
+                session.start_height.get_or_insert(height);
+                session.current_height.replace(height)
+            };
+
+            // Check every block added since the last poll
+            let mut found = Vec::new();
+            if let Some(last) = last {
+                for h in last + 1..=height {
+                    if let Some((hash, reward)) = block_paying(&client, h, &address).await {
+                        found.push(BlockFound {
+                            miner: name.clone(),
+                            address: address.clone(),
+                            height: h,
+                            hash,
+                            reward,
+                        });
+                    }
+                }
+            }
+            let mut state_guard = state.lock().await;
+            if let Some(session) = session_mut(&mut state_guard, &name, pid) {
+                session.blocks_found += found.len() as u64;
+                if let Some(block) = found.last() {
+                    session.last_block_hash = Some(block.hash.clone());
+                }
+                session.blocks.extend(found.iter().cloned());
+            }
+            let rotation = session_mut(&mut state_guard, &name, pid)
+                .and_then(|session| session.rotation.clone())
+                .filter(|_| !found.is_empty());
+            for block in found {
+                // No receivers just means nobody is listening yet
+                let _ = state_guard.block_found.send(block);
+            }
+            drop(state_guard);
+
+            // The restarted miner gets its own tracker
+            if let Some(config) = rotation {
+                if let Err(e) = rotate_coinbase(&state, &name, &config).await {
+                    eprintln!(
+                        "Failed to rotate coinbase address of miner '{}': {}",
+                        name, e
+                    );
+                }
+                return;
+            }
+        }
+
+        let mut stop_reason = match (height, stop_at_height) {
+            (Some(height), Some(target)) if height >= target => {
+                Some(format!("Reached height {}", height))
+            }
+            _ => None,
+        };
+        if let (None, Some(target)) = (&stop_reason, stop_at_faucet_balance) {
+            if let Ok(available) = faucet::faucet_available(&client).await {
+                if available >= target {
+                    stop_reason = Some(format!("Faucet balance reached {}", available));
+                }
+            }
+        }
+        if let Some(reason) = stop_reason {
+            if let Some(session) = session_mut(&mut *state.lock().await, &name, pid) {
+                session.stop_reason = Some(reason);
+            }
+            let _ = crate::stop_miner_internal(&state, Some(&name)).await;
+            continue;
+        }
+
+        tokio::time::sleep(SESSION_POLL_INTERVAL).await;
+    }
+}
+
+// ============================================================================
+// Tauri Commands
+// ============================================================================
+
+// Get shares, blocks found and uptime of a miner's current (or last) session
+#[tauri::command]
+pub async fn get_miner_session_stats(
+    state: tauri::State<'_, SharedState>,
+    name: Option<String>,
+) -> Result<Option<MinerSessionStats>, String> {
+    Ok(miner_session_stats(&state, name.as_deref()).await)
+}
@@ -0,0 +1,138 @@
+//! cpuminer command-line options
+//!
+//! The hashing algorithm, retry behaviour and a short list of extra flags can
+//! be changed to match custom core builds. Options are stored in `miner.json`
+//! in the app directory and used by every miner started without its own.
+//! Everything is checked against the cpuminer options Forge knows about, so a
+//! typo fails at start instead of leaving a miner that silently never connects.
+
+use serde::{Deserialize, Serialize};
+
+use crate::settings::JsonSettings;
+
+// Hathor's stratum only hands out sha256d jobs
+const SUPPORTED_ALGORITHMS: &[&str] = &["sha256d"];
+
+// Extra flags that may be passed through, and whether each takes a value.
+// Flags Forge sets itself (URL, coinbase address, threads, algorithm) are excluded.
+const EXTRA_FLAGS: &[(&str, bool)] = &[
+    ("--quiet", false),
+    ("--debug", false),
+    ("--protocol-dump", false),
+    ("--no-longpoll", false),
+    ("--no-redirect", false),
+    ("--scantime", true),
+    ("--timeout", true),
+];
+
+fn default_algorithm() -> String {
+    "sha256d".to_string()
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MinerOptions {
+    #[serde(default = "default_algorithm")]
+    pub algorithm: String,
+    // Times to retry a failed connection (-1 retries forever; cpuminer's default when unset)
+    #[serde(default)]
+    pub retries: Option<i32>,
+    // Seconds to wait between retries
+    #[serde(default)]
+    pub retry_pause: Option<u32>,
+    #[serde(default)]
+    pub extra_args: Vec<String>,
+}
+
+impl Default for MinerOptions {
+    fn default() -> Self {
+        Self {
+            algorithm: default_algorithm(),
+            retries: None,
+            retry_pause: None,
+            extra_args: Vec::new(),
+        }
+    }
+}
+
+impl MinerOptions {
+    pub fn validate(&self) -> Result<(), String> {
+        if !SUPPORTED_ALGORITHMS.contains(&self.algorithm.as_str()) {
+            return Err(format!(
+                "Unsupported mining algorithm '{}' (supported: {})",
+                self.algorithm,
+                SUPPORTED_ALGORITHMS.join(", ")
+            ));
+        }
+        if self.retries.is_some_and(|r| r < -1) {
+            return Err("Retries must be -1 (forever) or more".to_string());
+        }
+
+        let mut args = self.extra_args.iter();
+        while let Some(arg) = args.next() {
+            let (flag, inline_value) = match arg.split_once('=') {
+                Some((flag, value)) => (flag, Some(value)),
+                None => (arg.as_str(), None),
+            };
+            let Some((_, takes_value)) = EXTRA_FLAGS.iter().find(|(f, _)| *f == flag) else {
+                return Err(format!("Unsupported miner argument '{}'", arg));
+            };
+
+            if *takes_value {
+                let value = match inline_value {
+                    Some(value) => value,
+                    None => args
+                        .next()
+                        .ok_or_else(|| format!("Miner argument '{}' needs a value", flag))?,
+                };
+                value.parse::<u32>().map_err(|_| {
+                    format!("Invalid value '{}' for miner argument '{}'", value, flag)
+                })?;
+            } else if inline_value.is_some() {
+                return Err(format!("Miner argument '{}' takes no value", flag));
+            }
+        }
+        Ok(())
+    }
+
+    /// Command-line arguments for these options
+    pub fn args(&self) -> Vec<String> {
+        let mut args = vec!["--algo".to_string(), self.algorithm.clone()];
+        if let Some(retries) = self.retries {
+            args.extend(["--retries".to_string(), retries.to_string()]);
+        }
+        if let Some(retry_pause) = self.retry_pause {
+            args.extend(["--retry-pause".to_string(), retry_pause.to_string()]);
+        }
+        args.extend(self.extra_args.iter().cloned());
+        args
+    }
+}
+
+static SETTINGS: JsonSettings<MinerOptions> = JsonSettings::new("miner.json", "miner options");
+
+pub fn load_options() -> MinerOptions {
+    SETTINGS.load()
+}
+
+/// Validate and store the options used by miners started without their own
+pub fn save_options(options: &MinerOptions) -> Result<(), String> {
+    options.validate()?;
+    SETTINGS.save(options)
+}
+
+// ============================================================================
+// Tauri Commands
+// ============================================================================
+
+// Get the stored cpuminer options
+#[tauri::command]
+pub async fn get_miner_options() -> Result<MinerOptions, String> {
+    Ok(load_options())
+}
+
+// Store cpuminer options for the next miners started
+#[tauri::command]
+pub async fn set_miner_options(options: MinerOptions) -> Result<MinerOptions, String> {
+    save_options(&options)?;
+    Ok(options)
+}
@@ -0,0 +1,134 @@
+//! Mining difficulty and expected block time
+//!
+//! Hathor block weights are log2 of the expected number of hashes per block,
+//! so the time to find the next block follows from the weight and the miners'
+//! hash rate. The rate per thread is measured from the running miners; when
+//! none runs there is nothing to estimate from yet.
+
+use serde::{Deserialize, Serialize};
+
+use crate::{MinerConfig, NodeConfig, SharedState};
+
+// Time-to-block above which the report warns that mining looks too slow
+const SLOW_BLOCK_SECS: f64 = 60.0;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MiningInfo {
+    pub best_height: u64,
+    // Weight of the best block and of the block being mined next
+    pub best_block_weight: Option<f64>,
+    pub next_block_weight: Option<f64>,
+    // Expected hashes per block (2^weight)
+    pub difficulty: Option<f64>,
+    pub running_miners: usize,
+    // Measured total and per-thread hash rates of the running miners
+    pub hash_rate: Option<f64>,
+    pub hash_rate_per_thread: Option<f64>,
+    // Thread count the estimate is for
+    pub threads: u32,
+    pub expected_block_time_secs: Option<f64>,
+    pub message: String,
+}
+
+async fn get_json(client: &reqwest::Client, url: &str) -> Result<serde_json::Value, String> {
+    client
+        .get(url)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to query node: {}", e))?
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse node response: {}", e))
+}
+
+/// Block weight, difficulty and expected time to the next block with `threads`
+/// threads (those of the running miners, or the default miner's, when None)
+pub async fn get_mining_info_internal(
+    state: &SharedState,
+    threads: Option<u32>,
+) -> Result<MiningInfo, String> {
+    let client = reqwest::Client::new();
+    let api = format!("http://127.0.0.1:{}/v1a", NodeConfig::default().api_port);
+
+    let status = get_json(&client, &format!("{}/status/", api)).await?;
+    let best_block = &status["dag"]["best_block"];
+    let best_height = best_block["height"]
+        .as_u64()
+        .ok_or("Node status has no best block height")?;
+
+    let best_block_weight = match best_block["hash"].as_str() {
+        Some(hash) => get_json(&client, &format!("{}/transaction?id={}", api, hash))
+            .await
+            .ok()
+            .and_then(|tx| tx["tx"]["weight"].as_f64()),
+        None => None,
+    };
+    let next_block_weight = get_json(&client, &format!("{}/get_block_template", api))
+        .await
+        .ok()
+        .and_then(|template| template["weight"].as_f64());
+    let weight = next_block_weight.or(best_block_weight);
+    let difficulty = weight.map(|w| 2f64.powf(w));
+
+    let miners = crate::list_miners_internal(state).await;
+    let running_threads: u32 = miners.iter().filter_map(|m| m.threads).sum();
+    let hash_rate = miners
+        .iter()
+        .filter_map(|m| m.average_hash_rate.or(m.hash_rate))
+        .reduce(|a, b| a + b);
+    let hash_rate_per_thread = hash_rate
+        .filter(|_| running_threads > 0)
+        .map(|rate| rate / running_threads as f64);
+
+    let threads = threads.unwrap_or(if running_threads > 0 {
+        running_threads
+    } else {
+        MinerConfig::default().threads
+    });
+    let expected_block_time_secs = match (difficulty, hash_rate_per_thread) {
+        (Some(difficulty), Some(rate)) if rate > 0.0 => {
+            Some(difficulty / (rate * threads.max(1) as f64))
+        }
+        _ => None,
+    };
+
+    let message = match expected_block_time_secs {
+        Some(secs) if secs > SLOW_BLOCK_SECS => format!(
+            "Expect a block about every {:.0}s with {} threads; that's slow for a localnet, \
+             check the weight and add threads",
+            secs, threads
+        ),
+        Some(secs) => format!(
+            "Expect a block about every {:.1}s with {} threads",
+            secs, threads
+        ),
+        None if difficulty.is_none() => "The node didn't report a block weight".to_string(),
+        None => "Start a miner to measure the hash rate and estimate the block time".to_string(),
+    };
+
+    Ok(MiningInfo {
+        best_height,
+        best_block_weight,
+        next_block_weight,
+        difficulty,
+        running_miners: miners.len(),
+        hash_rate,
+        hash_rate_per_thread,
+        threads,
+        expected_block_time_secs,
+        message,
+    })
+}
+
+// ============================================================================
+// Tauri Commands
+// ============================================================================
+
+// Get block weight, difficulty and the expected time to the next block
+#[tauri::command]
+pub async fn get_mining_info(
+    state: tauri::State<'_, SharedState>,
+    threads: Option<u32>,
+) -> Result<MiningInfo, String> {
+    get_mining_info_internal(&state, threads).await
+}
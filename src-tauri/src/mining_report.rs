@@ -0,0 +1,121 @@
+//! Mining rewards report
+//!
+//! Combines the blocks found by each miner session with the node's best height
+//! and reward lock: how much was mined, how much of it is spendable, and at
+//! which height each still-locked reward unlocks.
+
+use serde::{Deserialize, Serialize};
+
+use crate::amount::HtrAmount;
+use crate::miner_metrics::MinerSessionStats;
+use crate::{rewards, SharedState};
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MinedBlock {
+    pub miner: String,
+    pub address: String,
+    pub height: u64,
+    pub hash: String,
+    pub reward: HtrAmount,
+    // First height at which the reward can be spent
+    pub unlock_height: u64,
+    pub spendable: bool,
+    pub blocks_until_unlock: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MiningReport {
+    pub best_height: u64,
+    pub reward_spend_min_blocks: u64,
+    pub blocks_mined: u64,
+    pub total_rewards: HtrAmount,
+    pub spendable_rewards: HtrAmount,
+    pub locked_rewards: HtrAmount,
+    // Height at which the next locked reward unlocks
+    pub next_unlock_height: Option<u64>,
+    pub sessions: Vec<MinerSessionStats>,
+    pub blocks: Vec<MinedBlock>,
+}
+
+/// Summarize the blocks and rewards of the current (or last) session of every
+/// miner, or only of miner `name`
+pub async fn get_mining_report_internal(
+    state: &SharedState,
+    name: Option<&str>,
+) -> Result<MiningReport, String> {
+    let client = reqwest::Client::new();
+    let (best_height, reward_lock) = rewards::chain_info(&client).await?;
+
+    let (mut sessions, found) = {
+        let state_guard = state.lock().await;
+        let mut sessions = Vec::new();
+        let mut found = Vec::new();
+        for (miner, metrics) in state_guard.miner_metrics.iter() {
+            if name.is_some_and(|name| name != miner) {
+                continue;
+            }
+            sessions.extend(metrics.session_stats());
+            found.extend(metrics.session_blocks().iter().cloned());
+        }
+        (sessions, found)
+    };
+    sessions.sort_by(|a, b| a.miner.cmp(&b.miner));
+
+    let mut blocks: Vec<MinedBlock> = found
+        .into_iter()
+        .map(|block| {
+            let unlock_height = block.height + reward_lock;
+            MinedBlock {
+                miner: block.miner,
+                address: block.address,
+                height: block.height,
+                hash: block.hash,
+                reward: block.reward,
+                unlock_height,
+                spendable: best_height >= unlock_height,
+                blocks_until_unlock: unlock_height.saturating_sub(best_height),
+            }
+        })
+        .collect();
+    blocks.sort_by_key(|block| block.height);
+
+    let sum = |spendable: bool| {
+        HtrAmount::from_cents(
+            blocks
+                .iter()
+                .filter(|block| block.spendable == spendable)
+                .map(|block| block.reward.cents())
+                .sum(),
+        )
+    };
+    let spendable_rewards = sum(true);
+    let locked_rewards = sum(false);
+
+    Ok(MiningReport {
+        best_height,
+        reward_spend_min_blocks: reward_lock,
+        blocks_mined: blocks.len() as u64,
+        total_rewards: HtrAmount::from_cents(spendable_rewards.cents() + locked_rewards.cents()),
+        spendable_rewards,
+        locked_rewards,
+        next_unlock_height: blocks
+            .iter()
+            .find(|block| !block.spendable)
+            .map(|block| block.unlock_height),
+        sessions,
+        blocks,
+    })
+}
+
+// ============================================================================
+// Tauri Commands
+// ============================================================================
+
+// Get blocks mined, rewards and their unlock heights for the miner sessions
+#[tauri::command]
+pub async fn get_mining_report(
+    state: tauri::State<'_, SharedState>,
+    name: Option<String>,
+) -> Result<MiningReport, String> {
+    get_mining_report_internal(&state, name.as_deref()).await
+}
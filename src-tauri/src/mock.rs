@@ -0,0 +1,313 @@
+//! Mock responses on the explorer server's proxy
+//!
+//! With mock mode on, the proxy answers requests matching a rule with a canned
+//! response instead of (or, for `offline` rules, only when it can't reach) the
+//! node or wallet-headless. That keeps explorer and frontend work going with
+//! the node stopped, and lets error paths be exercised on purpose: a rule for
+//! `POST /v1a/push_tx` with status 503 makes every push fail until it's
+//! removed. Rules can be written by hand or made from exchanges captured by
+//! the traffic recorder (see traffic.rs), so a recorded session can be
+//! replayed offline.
+//!
+//! Rules live in `proxy_mocks.json` and are checked per request, so changes
+//! apply to a running explorer server. Mocked responses show `mock` as their
+//! upstream in the access log.
+
+use axum::body::Body;
+use axum::extract::Request;
+use axum::http::{header, HeaderValue, StatusCode};
+use axum::middleware::Next;
+use axum::response::Response;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::time::Duration;
+
+use crate::access_log::{AccessError, Upstream};
+use crate::settings::JsonSettings;
+use crate::SharedState;
+
+// Longest artificial latency of a rule
+const MAX_DELAY: Duration = Duration::from_secs(60);
+
+/// When a rule answers instead of the upstream
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MockWhen {
+    // Every matching request
+    #[default]
+    Always,
+    // Only when the upstream isn't running or can't be reached
+    Offline,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MockRule {
+    #[serde(default = "enabled_by_default")]
+    pub enabled: bool,
+    // Any method when unset
+    #[serde(default)]
+    pub method: Option<String>,
+    // Path on the explorer server, e.g. `/v1a/version`; a trailing `*` matches
+    // a prefix, and a query (`/v1a/transaction?id=...`) must match exactly
+    pub path: String,
+    #[serde(default)]
+    pub when: MockWhen,
+    pub status: u16,
+    // JSON, or a string sent as is
+    #[serde(default)]
+    pub body: Value,
+    // Defaults to application/json for JSON bodies, text/plain for strings
+    #[serde(default)]
+    pub content_type: Option<String>,
+    // Latency added before answering
+    #[serde(default)]
+    pub delay_ms: u64,
+}
+
+fn enabled_by_default() -> bool {
+    true
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MockSettings {
+    pub enabled: bool,
+    // First matching rule wins
+    pub rules: Vec<MockRule>,
+}
+
+static SETTINGS: JsonSettings<MockSettings> = JsonSettings::new("proxy_mocks.json", "proxy mocks");
+
+impl MockRule {
+    fn matches(&self, method: &str, path: &str, query: Option<&str>) -> bool {
+        if !self.enabled
+            || self
+                .method
+                .as_ref()
+                .is_some_and(|m| !m.eq_ignore_ascii_case(method))
+        {
+            return false;
+        }
+        let (rule_path, rule_query) = match self.path.split_once('?') {
+            Some((path, query)) => (path, Some(query)),
+            None => (self.path.as_str(), None),
+        };
+        let path_matches = match rule_path.strip_suffix('*') {
+            Some(prefix) => path.starts_with(prefix),
+            None => path == rule_path,
+        };
+        path_matches && rule_query.is_none_or(|rule_query| query == Some(rule_query))
+    }
+
+    fn response(&self) -> Response {
+        let (body, content_type) = match &self.body {
+            Value::String(text) => (text.clone(), "text/plain; charset=utf-8"),
+            Value::Null => (String::new(), "text/plain; charset=utf-8"),
+            json => (json.to_string(), "application/json"),
+        };
+        let built = Response::builder()
+            .status(self.status)
+            .header(
+                header::CONTENT_TYPE,
+                self.content_type.as_deref().unwrap_or(content_type),
+            )
+            .body(Body::from(body));
+        // Rules are validated when saved; a bad one still mustn't take the
+        // handler down
+        let mut response = match built {
+            Ok(response) => response,
+            Err(e) => {
+                let mut response = Response::new(Body::from(format!(
+                    "Invalid mock response for {}: {}",
+                    self.path, e
+                )));
+                *response.status_mut() = StatusCode::INTERNAL_SERVER_ERROR;
+                response.extensions_mut().insert(AccessError(format!(
+                    "Invalid mock for {}: {}",
+                    self.path, e
+                )));
+                return response;
+            }
+        };
+        response
+            .extensions_mut()
+            .insert(Upstream("mock".to_string()));
+        if StatusCode::from_u16(self.status).is_ok_and(|status| !status.is_success()) {
+            response.extensions_mut().insert(AccessError(format!(
+                "Mocked {} for {}",
+                self.status, self.path
+            )));
+        }
+        response
+    }
+}
+
+fn validate(rule: &MockRule) -> Result<(), String> {
+    if !rule.path.starts_with('/') {
+        return Err(format!("Mock path '{}' must start with /", rule.path));
+    }
+    if StatusCode::from_u16(rule.status).is_err() {
+        return Err(format!("Invalid status {} for {}", rule.status, rule.path));
+    }
+    if let Some(content_type) = &rule.content_type {
+        if HeaderValue::from_str(content_type).is_err() {
+            return Err(format!(
+                "Invalid content type '{}' for {}",
+                content_type, rule.path
+            ));
+        }
+    }
+    if Duration::from_millis(rule.delay_ms) > MAX_DELAY {
+        return Err(format!(
+            "Delay of {} ms for {} exceeds {} seconds",
+            rule.delay_ms,
+            rule.path,
+            MAX_DELAY.as_secs()
+        ));
+    }
+    Ok(())
+}
+
+/// Middleware answering requests that match a mock rule; install on the proxy
+/// routes with `axum::middleware::from_fn(serve_mocks)`
+pub async fn serve_mocks(req: Request, next: Next) -> Response {
+    // WebSocket upgrades always go to the upstream
+    if req.headers().contains_key(header::UPGRADE) {
+        return next.run(req).await;
+    }
+    let rule = {
+        let settings = SETTINGS.read();
+        if !settings.enabled {
+            None
+        } else {
+            let method = req.method().as_str();
+            let uri = req.uri();
+            settings
+                .rules
+                .iter()
+                .find(|rule| rule.matches(method, uri.path(), uri.query()))
+                .cloned()
+        }
+    };
+    let Some(rule) = rule else {
+        return next.run(req).await;
+    };
+
+    if rule.when == MockWhen::Offline {
+        let response = next.run(req).await;
+        // The proxy answers 502 when the upstream isn't there
+        let unreachable = response.status() == StatusCode::BAD_GATEWAY
+            && response.extensions().get::<AccessError>().is_some();
+        if !unreachable {
+            return response;
+        }
+    }
+    if rule.delay_ms > 0 {
+        tokio::time::sleep(Duration::from_millis(rule.delay_ms)).await;
+    }
+    rule.response()
+}
+
+// A recorded exchange as a rule
+fn rule_from_entry(entry: &crate::traffic::TrafficEntry, when: MockWhen) -> Option<MockRule> {
+    let url = reqwest::Url::parse(&entry.url).ok()?;
+    let path = match url.query() {
+        Some(query) => format!("{}?{}", url.path(), query),
+        None => url.path().to_string(),
+    };
+    // A partial body would only break clients
+    let text = match &entry.response_body {
+        Some(body) if body.truncated => return None,
+        Some(body) => body.text.clone(),
+        None => String::new(),
+    };
+    let content_type = entry
+        .response_headers
+        .iter()
+        .find(|header| header.name.eq_ignore_ascii_case("content-type"))
+        .map(|header| header.value.clone());
+    let body = if content_type
+        .as_deref()
+        .is_some_and(|content_type| content_type.contains("json"))
+    {
+        serde_json::from_str(&text).unwrap_or(Value::String(text))
+    } else {
+        Value::String(text)
+    };
+    Some(MockRule {
+        enabled: true,
+        method: Some(entry.method.clone()),
+        path,
+        when,
+        status: entry.status,
+        body,
+        content_type,
+        delay_ms: 0,
+    })
+}
+
+/// Replace the mock settings
+pub fn set_mocks(settings: MockSettings) -> Result<MockSettings, String> {
+    for rule in &settings.rules {
+        validate(rule)?;
+    }
+    SETTINGS.save(&settings)?;
+    Ok(settings)
+}
+
+/// Add rules replaying recorded exchanges (all of them, or those in `ids`);
+/// later recordings of the same request replace earlier ones, and truncated
+/// responses are skipped
+pub async fn mock_from_recording_internal(
+    state: &SharedState,
+    ids: Option<Vec<u64>>,
+    when: MockWhen,
+) -> Result<MockSettings, String> {
+    let entries = crate::traffic::get_entries_internal(state).await;
+    let mut settings = SETTINGS.read().clone();
+    let mut added = 0;
+    for entry in entries
+        .iter()
+        .filter(|entry| ids.as_ref().is_none_or(|ids| ids.contains(&entry.id)))
+    {
+        let Some(rule) = rule_from_entry(entry, when) else {
+            continue;
+        };
+        settings
+            .rules
+            .retain(|existing| existing.method != rule.method || existing.path != rule.path);
+        settings.rules.push(rule);
+        added += 1;
+    }
+    if added == 0 {
+        return Err("No recorded exchanges to turn into mocks".to_string());
+    }
+    set_mocks(settings)
+}
+
+// ============================================================================
+// Tauri Commands
+// ============================================================================
+
+// Get the proxy's mock mode and rules
+#[tauri::command]
+pub async fn get_proxy_mocks() -> Result<MockSettings, String> {
+    Ok(SETTINGS.read().clone())
+}
+
+// Set the proxy's mock mode and rules; applies to the running explorer server
+#[tauri::command]
+pub async fn set_proxy_mocks(settings: MockSettings) -> Result<MockSettings, String> {
+    set_mocks(settings)
+}
+
+// Add mock rules replaying exchanges from the traffic recording (by default
+// only while the upstream is offline)
+#[tauri::command]
+pub async fn mock_from_recording(
+    state: tauri::State<'_, SharedState>,
+    ids: Option<Vec<u64>>,
+    when: Option<MockWhen>,
+) -> Result<MockSettings, String> {
+    mock_from_recording_internal(&state, ids, when.unwrap_or(MockWhen::Offline)).await
+}
@@ -0,0 +1,446 @@
+//! Multisig (P2SH) wallets
+//!
+//! wallet-headless only learns about multisig wallets from the `multisig` section
+//! of its `config.js`, which is read at startup. Forge keeps the definitions in
+//! `multisig.json` and writes them into every generated headless config; wallets
+//! whose definition isn't loaded yet are registered and opened on the next restart.
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+use std::fs;
+
+use crate::{headless, wallets, SharedState, DEFAULT_HEADLESS_INSTANCE};
+
+// Hathor script limits for P2SH multisig
+const MAX_MULTISIG_PUBKEYS: usize = 20;
+const MAX_MULTISIG_SIGNATURES: u8 = 15;
+
+/// An m-of-n multisig definition shared by all participants
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MultisigConfig {
+    pub num_signatures: u8,
+    pub pubkeys: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateMultisigWalletRequest {
+    pub wallet_id: String,
+    // This participant's seed
+    pub seed: String,
+    // Name of the multisig definition (shared by every participant's wallet)
+    pub config_name: String,
+    // Participant xpubs; may be omitted if `config_name` is already defined
+    #[serde(default)]
+    pub pubkeys: Option<Vec<String>>,
+    #[serde(default)]
+    pub num_signatures: Option<u8>,
+    #[serde(default)]
+    pub instance: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MultisigWalletResult {
+    pub wallet_id: String,
+    pub config_name: String,
+    // false when wallet-headless must be restarted to load the definition
+    pub started: bool,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MultisigOutput {
+    pub address: String,
+    pub value: u64, // Amount in cents of the token
+    #[serde(default)]
+    pub token: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MultisigSendRequest {
+    // Multisig wallet that builds and pushes the transaction
+    pub wallet_id: String,
+    pub outputs: Vec<MultisigOutput>,
+    // Local multisig wallets (same definition) whose signatures are collected
+    pub signer_wallet_ids: Vec<String>,
+    #[serde(default)]
+    pub instance: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MultisigSendResult {
+    pub hash: String,
+    pub tx_hex: String,
+    pub signatures: usize,
+}
+
+fn configs_path() -> std::path::PathBuf {
+    crate::get_app_dir().join("multisig.json")
+}
+
+/// Load the saved multisig definitions, keyed by name
+pub fn load_configs() -> BTreeMap<String, MultisigConfig> {
+    fs::read_to_string(configs_path())
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn save_configs(configs: &BTreeMap<String, MultisigConfig>) -> Result<(), String> {
+    fs::create_dir_all(crate::get_app_dir())
+        .map_err(|e| format!("Failed to create app directory: {}", e))?;
+    let content = serde_json::to_string_pretty(configs)
+        .map_err(|e| format!("Failed to serialize multisig configs: {}", e))?;
+    fs::write(configs_path(), content)
+        .map_err(|e| format!("Failed to write multisig configs: {}", e))
+}
+
+fn validate_config(config: &MultisigConfig) -> Result<(), String> {
+    let total = config.pubkeys.len();
+    if total == 0 || total > MAX_MULTISIG_PUBKEYS {
+        return Err(format!(
+            "A multisig wallet needs between 1 and {} participant xpubs",
+            MAX_MULTISIG_PUBKEYS
+        ));
+    }
+    if config.num_signatures == 0
+        || config.num_signatures > MAX_MULTISIG_SIGNATURES
+        || config.num_signatures as usize > total
+    {
+        return Err(format!(
+            "num_signatures must be between 1 and {} (and at most {})",
+            total.min(MAX_MULTISIG_SIGNATURES as usize),
+            MAX_MULTISIG_SIGNATURES
+        ));
+    }
+    if config.pubkeys.iter().any(|pubkey| pubkey.trim().is_empty()) {
+        return Err("Participant xpubs can't be empty".to_string());
+    }
+    Ok(())
+}
+
+/// Render the `multisig` section of the wallet-headless config (a JS object literal)
+pub fn headless_config_section(configs: &BTreeMap<String, MultisigConfig>) -> String {
+    let section: serde_json::Map<String, Value> = configs
+        .iter()
+        .map(|(name, config)| {
+            (
+                name.clone(),
+                json!({
+                    "total": config.pubkeys.len(),
+                    "numSignatures": config.num_signatures,
+                    "pubkeys": config.pubkeys,
+                }),
+            )
+        })
+        .collect();
+    Value::Object(section).to_string()
+}
+
+// Whether a running instance was started with `config_name` in its config.js
+async fn instance_has_config(state: &SharedState, instance: &str, config_name: &str) -> bool {
+    let state_guard = state.lock().await;
+    state_guard
+        .headless_instances
+        .get(instance)
+        .map(|i| i.multisig_configs.iter().any(|name| name == config_name))
+        .unwrap_or(false)
+}
+
+/// Get the multisig xpub for a seed (or for a registered wallet's seed)
+pub async fn get_multisig_xpub_internal(
+    state: &SharedState,
+    seed: Option<String>,
+    wallet_id: Option<String>,
+    instance: Option<&str>,
+) -> Result<String, String> {
+    let seed = match (seed, wallet_id) {
+        (Some(seed), _) => seed,
+        (None, Some(wallet_id)) => wallets::wallet_seed(&wallet_id)
+            .await?
+            .ok_or_else(|| format!("The seed of wallet '{}' isn't in the keychain", wallet_id))?,
+        (None, None) => return Err("Either seed or wallet_id is required".to_string()),
+    };
+
+    let base_url = crate::headless_url(state, instance).await?;
+    let result = headless::post(&base_url, "/multisig-pubkey", &json!({ "seed": seed }))
+        .await
+        .map_err(|e| format!("Failed to get multisig xpub: {}", e))?;
+
+    result["xpubkey"]
+        .as_str()
+        .map(|s| s.to_string())
+        .ok_or_else(|| "Missing xpubkey in response".to_string())
+}
+
+/// Save the multisig definition (if given) and open this participant's wallet
+pub async fn create_multisig_wallet_internal(
+    state: &SharedState,
+    request: CreateMultisigWalletRequest,
+) -> Result<MultisigWalletResult, String> {
+    let instance = request
+        .instance
+        .as_deref()
+        .unwrap_or(DEFAULT_HEADLESS_INSTANCE);
+
+    let mut configs = load_configs();
+    match (request.pubkeys, request.num_signatures) {
+        (Some(pubkeys), Some(num_signatures)) => {
+            let config = MultisigConfig {
+                num_signatures,
+                pubkeys,
+            };
+            validate_config(&config)?;
+            match configs.get(&request.config_name) {
+                Some(existing) if existing != &config => {
+                    return Err(format!(
+                        "Multisig config '{}' already exists with different participants",
+                        request.config_name
+                    ));
+                }
+                Some(_) => {}
+                None => {
+                    configs.insert(request.config_name.clone(), config);
+                    save_configs(&configs)?;
+                }
+            }
+        }
+        (None, None) => {
+            if !configs.contains_key(&request.config_name) {
+                return Err(format!(
+                    "Multisig config '{}' doesn't exist. Provide pubkeys and num_signatures to create it.",
+                    request.config_name
+                ));
+            }
+        }
+        _ => return Err("pubkeys and num_signatures must be given together".to_string()),
+    }
+
+    // Registering first means the wallet is opened automatically once headless
+    // restarts with the new definition
+    wallets::register_wallet(
+        &request.wallet_id,
+        &request.seed,
+        instance,
+        Some(&request.config_name),
+    )
+    .await?;
+
+    if !instance_has_config(state, instance, &request.config_name).await {
+        return Ok(MultisigWalletResult {
+            wallet_id: request.wallet_id,
+            config_name: request.config_name,
+            started: false,
+            message: "Multisig config saved. Restart wallet-headless to load it; the wallet will be opened automatically.".to_string(),
+        });
+    }
+
+    let base_url = crate::headless_url(state, Some(instance)).await?;
+    headless::post(
+        &base_url,
+        "/start",
+        &json!({
+            "wallet-id": request.wallet_id,
+            "seed": request.seed,
+            "multisig": true,
+            "multisigKey": request.config_name,
+        }),
+    )
+    .await
+    .map_err(|e| format!("Failed to start multisig wallet: {}", e))?;
+
+    Ok(MultisigWalletResult {
+        message: format!("Multisig wallet '{}' started", request.wallet_id),
+        wallet_id: request.wallet_id,
+        config_name: request.config_name,
+        started: true,
+    })
+}
+
+/// Build an unsigned P2SH transaction proposal, returning its hex
+pub async fn create_multisig_proposal_internal(
+    state: &SharedState,
+    wallet_id: &str,
+    outputs: &[MultisigOutput],
+    instance: Option<&str>,
+) -> Result<String, String> {
+    let base_url = crate::headless_url(state, instance).await?;
+    let result = headless::wallet_post(
+        &base_url,
+        wallet_id,
+        "/wallet/p2sh/tx-proposal",
+        &json!({ "outputs": outputs }),
+    )
+    .await
+    .map_err(|e| format!("Failed to create proposal: {}", e))?;
+
+    result["txHex"]
+        .as_str()
+        .map(|s| s.to_string())
+        .ok_or_else(|| "Missing txHex in response".to_string())
+}
+
+/// Get one participant's signatures for a proposal
+pub async fn get_multisig_signatures_internal(
+    state: &SharedState,
+    wallet_id: &str,
+    tx_hex: &str,
+    instance: Option<&str>,
+) -> Result<String, String> {
+    let base_url = crate::headless_url(state, instance).await?;
+    let result = headless::wallet_post(
+        &base_url,
+        wallet_id,
+        "/wallet/p2sh/tx-proposal/get-my-signatures",
+        &json!({ "txHex": tx_hex }),
+    )
+    .await
+    .map_err(|e| format!("Failed to sign proposal with '{}': {}", wallet_id, e))?;
+
+    result["signatures"]
+        .as_str()
+        .map(|s| s.to_string())
+        .ok_or_else(|| "Missing signatures in response".to_string())
+}
+
+/// Assemble the collected signatures into the proposal and push it
+pub async fn sign_and_push_multisig_tx_internal(
+    state: &SharedState,
+    wallet_id: &str,
+    tx_hex: &str,
+    signatures: &[String],
+    instance: Option<&str>,
+) -> Result<String, String> {
+    let base_url = crate::headless_url(state, instance).await?;
+    let result = headless::wallet_post(
+        &base_url,
+        wallet_id,
+        "/wallet/p2sh/tx-proposal/sign-and-push",
+        &json!({ "txHex": tx_hex, "signatures": signatures }),
+    )
+    .await
+    .map_err(|e| format!("Failed to push multisig transaction: {}", e))?;
+
+    result["hash"]
+        .as_str()
+        .or_else(|| result["tx"]["hash"].as_str())
+        .map(|hash| hash.to_string())
+        .ok_or_else(|| {
+            "Failed to push multisig transaction: no transaction hash in response".to_string()
+        })
+}
+
+/// Run the whole m-of-n flow: propose, collect signatures from local wallets, push
+pub async fn send_multisig_tx_internal(
+    state: &SharedState,
+    request: &MultisigSendRequest,
+) -> Result<MultisigSendResult, String> {
+    if request.signer_wallet_ids.is_empty() {
+        return Err("At least one signer wallet is required".to_string());
+    }
+
+    let instance = request.instance.as_deref();
+    let tx_hex =
+        create_multisig_proposal_internal(state, &request.wallet_id, &request.outputs, instance)
+            .await?;
+
+    let mut signatures = Vec::new();
+    for signer in &request.signer_wallet_ids {
+        signatures.push(get_multisig_signatures_internal(state, signer, &tx_hex, instance).await?);
+    }
+
+    let hash = sign_and_push_multisig_tx_internal(
+        state,
+        &request.wallet_id,
+        &tx_hex,
+        &signatures,
+        instance,
+    )
+    .await?;
+
+    Ok(MultisigSendResult {
+        hash,
+        tx_hex,
+        signatures: signatures.len(),
+    })
+}
+
+// ============================================================================
+// Tauri Commands
+// ============================================================================
+
+// Get the multisig xpub to share with other participants
+#[tauri::command]
+pub async fn get_multisig_xpub(
+    state: tauri::State<'_, SharedState>,
+    seed: Option<String>,
+    wallet_id: Option<String>,
+    instance: Option<String>,
+) -> Result<String, String> {
+    get_multisig_xpub_internal(&state, seed, wallet_id, instance.as_deref()).await
+}
+
+// List saved multisig definitions
+#[tauri::command]
+pub async fn list_multisig_configs() -> Result<BTreeMap<String, MultisigConfig>, String> {
+    Ok(load_configs())
+}
+
+// Create a participant's multisig wallet from the participants' xpubs
+#[tauri::command]
+pub async fn create_multisig_wallet(
+    state: tauri::State<'_, SharedState>,
+    request: CreateMultisigWalletRequest,
+) -> Result<MultisigWalletResult, String> {
+    create_multisig_wallet_internal(&state, request).await
+}
+
+// Build an unsigned spend proposal from a multisig wallet
+#[tauri::command]
+pub async fn create_multisig_proposal(
+    state: tauri::State<'_, SharedState>,
+    wallet_id: String,
+    outputs: Vec<MultisigOutput>,
+    instance: Option<String>,
+) -> Result<String, String> {
+    create_multisig_proposal_internal(&state, &wallet_id, &outputs, instance.as_deref()).await
+}
+
+// Sign a proposal with one participant's multisig wallet
+#[tauri::command]
+pub async fn get_multisig_signatures(
+    state: tauri::State<'_, SharedState>,
+    wallet_id: String,
+    tx_hex: String,
+    instance: Option<String>,
+) -> Result<String, String> {
+    get_multisig_signatures_internal(&state, &wallet_id, &tx_hex, instance.as_deref()).await
+}
+
+// Push a proposal once enough signatures were collected
+#[tauri::command]
+pub async fn sign_and_push_multisig_tx(
+    state: tauri::State<'_, SharedState>,
+    wallet_id: String,
+    tx_hex: String,
+    signatures: Vec<String>,
+    instance: Option<String>,
+) -> Result<String, String> {
+    sign_and_push_multisig_tx_internal(
+        &state,
+        &wallet_id,
+        &tx_hex,
+        &signatures,
+        instance.as_deref(),
+    )
+    .await
+}
+
+// Propose, sign with local participant wallets and push in one step
+#[tauri::command]
+pub async fn send_multisig_tx(
+    state: tauri::State<'_, SharedState>,
+    request: MultisigSendRequest,
+) -> Result<MultisigSendResult, String> {
+    send_multisig_tx_internal(&state, &request).await
+}
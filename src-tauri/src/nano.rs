@@ -0,0 +1,723 @@
+//! Nano contracts
+//!
+//! Nano contracts mode starts the node with nano contracts enabled (through
+//! the settings `features.rs` writes) and on-chain blueprints open to every
+//! address, so contracts can be developed against the localnet. Blueprints
+//! are read from the node's nano contract API: the built-in ones shipped with
+//! hathor-core and the on-chain ones uploaded to this chain. Blueprints
+//! uploaded through Forge are also recorded in a per-workspace registry (next
+//! to the token one), which remembers the wallet and file they came from.
+//!
+//! Contracts are created and called through wallet-headless, from an address
+//! of the calling wallet. The app is told with `nc-tx-confirmed` once the
+//! transaction is confirmed by a block, along with whether it executed. A
+//! contract's state (decoded by the node) and its call history come straight
+//! from the fullnode.
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::fs;
+use std::path::Path;
+use std::sync::Mutex as StdMutex;
+use std::time::Duration;
+use tauri::{AppHandle, Emitter};
+use tokio::time::Instant;
+
+use crate::{bootstrap, features, headless, NodeConfig, SharedState};
+
+// Page size when listing blueprints
+const PAGE_SIZE: u64 = 100;
+const CONFIRMATION_POLL_INTERVAL: Duration = Duration::from_secs(1);
+const CONFIRMATION_TIMEOUT: Duration = Duration::from_secs(300);
+const HTR_UID: &str = "00";
+const DEFAULT_HISTORY_COUNT: u64 = 50;
+// hathor-core's NC_ON_CHAIN_BLUEPRINT_CODE_MAX_SIZE_UNCOMPRESSED
+const MAX_BLUEPRINT_SOURCE_SIZE: u64 = 24 * 1024;
+
+// Serializes read-modify-write cycles on the blueprint registry file
+static BLUEPRINT_REGISTRY_LOCK: StdMutex<()> = StdMutex::new(());
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BlueprintKind {
+    BuiltIn,
+    OnChain,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Blueprint {
+    pub id: String,
+    pub name: String,
+    pub kind: BlueprintKind,
+    // Timestamp of the blueprint's transaction (on-chain ones only)
+    pub created_at: Option<u64>,
+    // Wallet that uploaded it, when it was uploaded through Forge
+    pub uploaded_by: Option<String>,
+}
+
+/// An on-chain blueprint uploaded through Forge
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RegisteredBlueprint {
+    pub blueprint_id: String,
+    // The class assigned to `__blueprint__`
+    pub name: String,
+    pub uploaded_by: String,
+    pub source_path: String,
+    pub registered_at: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BlueprintSource {
+    pub blueprint_id: String,
+    pub source_code: String,
+}
+
+/// A nano contract creation (`blueprint_id`) or method call (`nc_id` and
+/// `method`) made by a wallet
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NcCallRequest {
+    pub wallet_id: String,
+    #[serde(default)]
+    pub blueprint_id: Option<String>,
+    #[serde(default)]
+    pub nc_id: Option<String>,
+    #[serde(default)]
+    pub method: Option<String>,
+    // Method arguments, in the order the blueprint declares them
+    #[serde(default)]
+    pub args: Vec<Value>,
+    // Deposits and withdrawals, as wallet-headless takes them
+    // ({"type": "deposit", "token": "00", "amount": 100})
+    #[serde(default)]
+    pub actions: Vec<Value>,
+    // Caller address (defaults to the wallet's current address)
+    #[serde(default)]
+    pub address: Option<String>,
+    #[serde(default)]
+    pub instance: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NcTransaction {
+    pub hash: String,
+    // The created contract's id is its creation transaction's hash
+    pub nc_id: String,
+    pub method: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NcConfirmation {
+    pub hash: String,
+    pub nc_id: String,
+    pub method: String,
+    pub first_block: String,
+    pub voided: bool,
+    // The node's execution result (e.g. "success", "failure") when it reports one
+    pub execution: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NcState {
+    pub nc_id: String,
+    pub blueprint_id: Option<String>,
+    pub blueprint_name: Option<String>,
+    // Field name -> decoded value, or the node's error for that field
+    pub fields: serde_json::Map<String, Value>,
+    // Token UID -> balance in cents
+    pub balances: serde_json::Map<String, Value>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NcCall {
+    pub tx_id: String,
+    pub method: Option<String>,
+    // As the node reports them (hex-encoded unless it decodes them)
+    pub args: Value,
+    pub caller: Option<String>,
+    pub timestamp: Option<u64>,
+    pub first_block: Option<String>,
+    pub voided: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NcHistory {
+    pub nc_id: String,
+    // Newest first
+    pub calls: Vec<NcCall>,
+    pub has_more: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NanoContractsMode {
+    // From the next node start
+    pub enabled: bool,
+    pub node_running: bool,
+}
+
+fn api_url(path: &str) -> String {
+    format!(
+        "http://127.0.0.1:{}/v1a/nano_contract/{}",
+        NodeConfig::default().api_port,
+        path
+    )
+}
+
+/// GET a nano contract endpoint, turning the node's error responses into errors
+pub async fn nc_get(path: &str, query: &[(&str, String)]) -> Result<Value, String> {
+    let response = reqwest::Client::new()
+        .get(api_url(path))
+        .query(query)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to query nano contracts: {}", e))?;
+    if response.status() == reqwest::StatusCode::NOT_FOUND && !features::nano_contracts_enabled() {
+        return Err("Nano contracts are disabled; enable them and restart the node".to_string());
+    }
+    let body: Value = response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse nano contract response: {}", e))?;
+    if body["success"] == false {
+        let message = body["message"]
+            .as_str()
+            .or_else(|| body["error"].as_str())
+            .unwrap_or("Unknown error");
+        return Err(format!("Nano contract request failed: {}", message));
+    }
+    Ok(body)
+}
+
+async fn ensure_node_running(state: &SharedState) -> Result<(), String> {
+    if !state.lock().await.node_running {
+        return Err("Node is not running".to_string());
+    }
+    Ok(())
+}
+
+// Every page of a blueprint listing
+async fn list_kind(path: &str, kind: BlueprintKind) -> Result<Vec<Blueprint>, String> {
+    let mut blueprints = Vec::new();
+    let mut after: Option<String> = None;
+    loop {
+        let mut query = vec![("count", PAGE_SIZE.to_string())];
+        if let Some(after) = &after {
+            query.push(("after", after.clone()));
+        }
+        let page = nc_get(path, &query).await?;
+        let entries = page["blueprints"].as_array().cloned().unwrap_or_default();
+        blueprints.extend(entries.iter().map(|entry| Blueprint {
+            id: entry["id"].as_str().unwrap_or_default().to_string(),
+            name: entry["name"].as_str().unwrap_or_default().to_string(),
+            kind,
+            created_at: entry["created_at"].as_u64(),
+            uploaded_by: None,
+        }));
+
+        let last = blueprints.last().map(|blueprint| blueprint.id.clone());
+        if !page["has_more"].as_bool().unwrap_or(false) || entries.is_empty() || last == after {
+            return Ok(blueprints);
+        }
+        after = last;
+    }
+}
+
+/// Built-in and on-chain blueprints known to the node
+pub async fn list_blueprints_internal(state: &SharedState) -> Result<Vec<Blueprint>, String> {
+    ensure_node_running(state).await?;
+    let mut blueprints = list_kind("blueprint/builtin", BlueprintKind::BuiltIn).await?;
+    blueprints.extend(list_kind("blueprint/on_chain", BlueprintKind::OnChain).await?);
+
+    let uploaded = list_uploaded_blueprints_internal(state).await;
+    for blueprint in &mut blueprints {
+        if let Some(entry) = uploaded
+            .iter()
+            .find(|entry| entry.blueprint_id == blueprint.id)
+        {
+            blueprint.uploaded_by = Some(entry.uploaded_by.clone());
+        }
+    }
+    Ok(blueprints)
+}
+
+async fn blueprint_registry_path(state: &SharedState) -> std::path::PathBuf {
+    let state_guard = state.lock().await;
+    crate::workspace_data_dir(&state_guard).join("forge-blueprints.json")
+}
+
+fn load_blueprint_registry(path: &Path) -> Vec<RegisteredBlueprint> {
+    fs::read_to_string(path)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+async fn register_blueprint(
+    state: &SharedState,
+    blueprint: &RegisteredBlueprint,
+) -> Result<(), String> {
+    let path = blueprint_registry_path(state).await;
+    let _guard = BLUEPRINT_REGISTRY_LOCK
+        .lock()
+        .unwrap_or_else(|e| e.into_inner());
+
+    let mut blueprints = load_blueprint_registry(&path);
+    blueprints.retain(|entry| entry.blueprint_id != blueprint.blueprint_id);
+    blueprints.push(blueprint.clone());
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create data directory: {}", e))?;
+    }
+    let content = serde_json::to_string_pretty(&blueprints)
+        .map_err(|e| format!("Failed to serialize blueprint registry: {}", e))?;
+    fs::write(&path, content).map_err(|e| format!("Failed to write blueprint registry: {}", e))
+}
+
+/// Blueprints uploaded through Forge in the current workspace
+pub async fn list_uploaded_blueprints_internal(state: &SharedState) -> Vec<RegisteredBlueprint> {
+    let path = blueprint_registry_path(state).await;
+    let _guard = BLUEPRINT_REGISTRY_LOCK
+        .lock()
+        .unwrap_or_else(|e| e.into_inner());
+    load_blueprint_registry(&path)
+}
+
+// Read a blueprint file, checking what the node would otherwise reject after
+// the transaction is sent; returns the source and the blueprint class name
+fn read_blueprint_source(path: &Path) -> Result<(String, String), String> {
+    if path.extension().and_then(|ext| ext.to_str()) != Some("py") {
+        return Err(format!("{} is not a Python file", path.display()));
+    }
+    let size = fs::metadata(path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?
+        .len();
+    if size == 0 {
+        return Err(format!("{} is empty", path.display()));
+    }
+    if size > MAX_BLUEPRINT_SOURCE_SIZE {
+        return Err(format!(
+            "{} is {} bytes; on-chain blueprints are limited to {} bytes",
+            path.display(),
+            size,
+            MAX_BLUEPRINT_SOURCE_SIZE
+        ));
+    }
+    let source = fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read {} as UTF-8: {}", path.display(), e))?;
+
+    // The node loads the class the module assigns to `__blueprint__`
+    let name = source
+        .lines()
+        .filter_map(|line| line.strip_prefix("__blueprint__"))
+        .filter_map(|rest| rest.trim_start().strip_prefix('='))
+        .map(|value| value.trim().to_string())
+        .next_back()
+        .filter(|name| {
+            !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+        })
+        .ok_or_else(|| {
+            format!(
+                "{} doesn't assign its blueprint class to __blueprint__",
+                path.display()
+            )
+        })?;
+    Ok((source, name))
+}
+
+/// Upload a Python blueprint as an on-chain blueprint transaction from a
+/// wallet and register it in the workspace; the blueprint id is the
+/// transaction's hash
+pub async fn upload_blueprint_internal(
+    state: &SharedState,
+    wallet_id: &str,
+    source_path: &str,
+    address: Option<&str>,
+    instance: Option<&str>,
+) -> Result<RegisteredBlueprint, String> {
+    ensure_node_running(state).await?;
+    if !features::nano_contracts_enabled() {
+        return Err("Nano contracts are disabled; enable them and restart the node".to_string());
+    }
+    let (source, name) = read_blueprint_source(Path::new(source_path))?;
+
+    let base_url = crate::headless_url(state, instance).await?;
+    let address = match address {
+        Some(address) => address.to_string(),
+        None => bootstrap::wallet_address(&base_url, wallet_id).await?,
+    };
+    let result = headless::wallet_post(
+        &base_url,
+        wallet_id,
+        "/wallet/nano-contracts/create-on-chain-blueprint",
+        &json!({ "code": source, "address": address }),
+    )
+    .await
+    .map_err(|e| format!("Failed to upload blueprint: {}", e))?;
+
+    let blueprint = RegisteredBlueprint {
+        blueprint_id: result["hash"]
+            .as_str()
+            .ok_or("wallet-headless returned no transaction hash")?
+            .to_string(),
+        name,
+        uploaded_by: wallet_id.to_string(),
+        source_path: source_path.to_string(),
+        registered_at: crate::unix_now(),
+    };
+    register_blueprint(state, &blueprint).await?;
+    Ok(blueprint)
+}
+
+/// Python source of a blueprint
+pub async fn get_blueprint_source_internal(
+    state: &SharedState,
+    blueprint_id: &str,
+) -> Result<BlueprintSource, String> {
+    ensure_node_running(state).await?;
+    let response = nc_get(
+        "blueprint/source",
+        &[("blueprint_id", blueprint_id.to_string())],
+    )
+    .await?;
+    Ok(BlueprintSource {
+        blueprint_id: blueprint_id.to_string(),
+        source_code: response["source_code"]
+            .as_str()
+            .ok_or("The node returned no source for the blueprint")?
+            .to_string(),
+    })
+}
+
+// Send a nano contract transaction from the wallet
+async fn nc_send(
+    state: &SharedState,
+    request: &NcCallRequest,
+    path: &str,
+    mut body: Value,
+) -> Result<Value, String> {
+    let base_url = crate::headless_url(state, request.instance.as_deref()).await?;
+    let address = match &request.address {
+        Some(address) => address.clone(),
+        None => bootstrap::wallet_address(&base_url, &request.wallet_id).await?,
+    };
+    body["address"] = json!(address);
+    body["data"] = json!({ "args": request.args, "actions": request.actions });
+    headless::wallet_post(&base_url, &request.wallet_id, path, &body)
+        .await
+        .map_err(|e| format!("Nano contract transaction failed: {}", e))
+}
+
+/// Create a contract from a blueprint
+pub async fn nc_create_internal(
+    state: &SharedState,
+    request: &NcCallRequest,
+) -> Result<NcTransaction, String> {
+    let blueprint_id = request
+        .blueprint_id
+        .as_deref()
+        .ok_or("blueprint_id is required")?;
+    let result = nc_send(
+        state,
+        request,
+        "/wallet/nano-contracts/create",
+        json!({ "blueprint_id": blueprint_id }),
+    )
+    .await?;
+    let hash = result["hash"]
+        .as_str()
+        .ok_or("wallet-headless returned no transaction hash")?
+        .to_string();
+    Ok(NcTransaction {
+        nc_id: hash.clone(),
+        hash,
+        method: "initialize".to_string(),
+    })
+}
+
+/// Call a public method of a contract
+pub async fn nc_execute_internal(
+    state: &SharedState,
+    request: &NcCallRequest,
+) -> Result<NcTransaction, String> {
+    let nc_id = request.nc_id.as_deref().ok_or("nc_id is required")?;
+    let method = request.method.as_deref().ok_or("method is required")?;
+    let result = nc_send(
+        state,
+        request,
+        "/wallet/nano-contracts/execute",
+        json!({ "nc_id": nc_id, "method": method }),
+    )
+    .await?;
+    Ok(NcTransaction {
+        hash: result["hash"]
+            .as_str()
+            .ok_or("wallet-headless returned no transaction hash")?
+            .to_string(),
+        nc_id: nc_id.to_string(),
+        method: method.to_string(),
+    })
+}
+
+/// Wait until a block confirms a nano contract transaction
+pub async fn wait_for_confirmation(tx: &NcTransaction) -> Result<NcConfirmation, String> {
+    let client = reqwest::Client::new();
+    let url = format!(
+        "http://127.0.0.1:{}/v1a/transaction",
+        NodeConfig::default().api_port
+    );
+    let started = Instant::now();
+    while started.elapsed() < CONFIRMATION_TIMEOUT {
+        if let Ok(response) = client.get(&url).query(&[("id", &tx.hash)]).send().await {
+            if let Ok(body) = response.json::<Value>().await {
+                let meta = &body["meta"];
+                if let Some(first_block) = meta["first_block"].as_str() {
+                    return Ok(NcConfirmation {
+                        hash: tx.hash.clone(),
+                        nc_id: tx.nc_id.clone(),
+                        method: tx.method.clone(),
+                        first_block: first_block.to_string(),
+                        voided: meta["voided_by"]
+                            .as_array()
+                            .is_some_and(|voided_by| !voided_by.is_empty()),
+                        execution: meta["nc_execution"].as_str().map(|s| s.to_string()),
+                    });
+                }
+            }
+        }
+        tokio::time::sleep(CONFIRMATION_POLL_INTERVAL).await;
+    }
+    Err(format!(
+        "Nano contract transaction {} wasn't confirmed within {}s",
+        tx.hash,
+        CONFIRMATION_TIMEOUT.as_secs()
+    ))
+}
+
+// Emit nc-tx-confirmed once the transaction is in a block
+fn emit_confirmation(app: AppHandle, tx: NcTransaction) {
+    tokio::spawn(async move {
+        match wait_for_confirmation(&tx).await {
+            Ok(confirmation) => {
+                let _ = app.emit("nc-tx-confirmed", &confirmation);
+            }
+            Err(e) => eprintln!("{}", e),
+        }
+    });
+}
+
+// A field's value, or its error message
+fn field_value(entry: &Value) -> Value {
+    match entry.get("value") {
+        Some(value) => value.clone(),
+        None => json!({ "error": entry["errmsg"].as_str().unwrap_or("Unknown error") }),
+    }
+}
+
+/// Current values of a contract's fields (all of them need naming) and its
+/// balances (HTR plus any tokens asked for)
+pub async fn nc_get_state_internal(
+    state: &SharedState,
+    nc_id: &str,
+    fields: &[String],
+    tokens: &[String],
+) -> Result<NcState, String> {
+    ensure_node_running(state).await?;
+    let mut query = vec![("id", nc_id.to_string())];
+    query.extend(fields.iter().map(|field| ("fields[]", field.clone())));
+    query.push(("balances[]", HTR_UID.to_string()));
+    query.extend(
+        tokens
+            .iter()
+            .filter(|token| *token != HTR_UID)
+            .map(|token| ("balances[]", token.clone())),
+    );
+    let response = nc_get("state", &query).await?;
+
+    let decode = |key: &str| -> serde_json::Map<String, Value> {
+        response[key]
+            .as_object()
+            .map(|entries| {
+                entries
+                    .iter()
+                    .map(|(name, entry)| (name.clone(), field_value(entry)))
+                    .collect()
+            })
+            .unwrap_or_default()
+    };
+    Ok(NcState {
+        nc_id: nc_id.to_string(),
+        blueprint_id: response["blueprint_id"].as_str().map(|s| s.to_string()),
+        blueprint_name: response["blueprint_name"].as_str().map(|s| s.to_string()),
+        fields: decode("fields"),
+        balances: decode("balances"),
+    })
+}
+
+/// Transactions that created or called a contract, newest first
+pub async fn nc_get_history_internal(
+    state: &SharedState,
+    nc_id: &str,
+    count: Option<u64>,
+    after: Option<&str>,
+) -> Result<NcHistory, String> {
+    ensure_node_running(state).await?;
+    let mut query = vec![
+        ("id", nc_id.to_string()),
+        ("count", count.unwrap_or(DEFAULT_HISTORY_COUNT).to_string()),
+    ];
+    if let Some(after) = after {
+        query.push(("after", after.to_string()));
+    }
+    let response = nc_get("history", &query).await?;
+
+    let calls = response["history"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .map(|tx| NcCall {
+            tx_id: tx["hash"]
+                .as_str()
+                .or_else(|| tx["tx_id"].as_str())
+                .unwrap_or_default()
+                .to_string(),
+            method: tx["nc_method"].as_str().map(|s| s.to_string()),
+            args: tx["nc_args"].clone(),
+            caller: tx["nc_address"].as_str().map(|s| s.to_string()),
+            timestamp: tx["timestamp"].as_u64(),
+            first_block: tx["first_block"].as_str().map(|s| s.to_string()),
+            voided: tx["is_voided"].as_bool().unwrap_or(false),
+        })
+        .collect();
+    Ok(NcHistory {
+        nc_id: nc_id.to_string(),
+        calls,
+        has_more: response["has_more"].as_bool().unwrap_or(false),
+    })
+}
+
+pub async fn get_nano_contracts_mode_internal(state: &SharedState) -> NanoContractsMode {
+    NanoContractsMode {
+        enabled: features::nano_contracts_enabled(),
+        node_running: state.lock().await.node_running,
+    }
+}
+
+/// Turn nano contracts mode on or off from the next node start
+pub async fn set_nano_contracts_mode_internal(
+    state: &SharedState,
+    enabled: bool,
+) -> Result<NanoContractsMode, String> {
+    features::set_nano_contracts_internal(enabled)?;
+    Ok(get_nano_contracts_mode_internal(state).await)
+}
+
+// ============================================================================
+// Tauri Commands
+// ============================================================================
+
+// Get whether the node starts with nano contracts enabled
+#[tauri::command]
+pub async fn get_nano_contracts_mode(
+    state: tauri::State<'_, SharedState>,
+) -> Result<NanoContractsMode, String> {
+    Ok(get_nano_contracts_mode_internal(&state).await)
+}
+
+// Enable or disable nano contracts from the next node start
+#[tauri::command]
+pub async fn set_nano_contracts_mode(
+    state: tauri::State<'_, SharedState>,
+    enabled: bool,
+) -> Result<NanoContractsMode, String> {
+    set_nano_contracts_mode_internal(&state, enabled).await
+}
+
+// Upload a Python file as an on-chain blueprint
+#[tauri::command]
+pub async fn upload_blueprint(
+    state: tauri::State<'_, SharedState>,
+    wallet_id: String,
+    python_source_path: String,
+    address: Option<String>,
+    instance: Option<String>,
+) -> Result<RegisteredBlueprint, String> {
+    upload_blueprint_internal(
+        &state,
+        &wallet_id,
+        &python_source_path,
+        address.as_deref(),
+        instance.as_deref(),
+    )
+    .await
+}
+
+// List the blueprints uploaded through Forge in this workspace
+#[tauri::command]
+pub async fn list_uploaded_blueprints(
+    state: tauri::State<'_, SharedState>,
+) -> Result<Vec<RegisteredBlueprint>, String> {
+    Ok(list_uploaded_blueprints_internal(&state).await)
+}
+
+// Create a nano contract from a blueprint; emits nc-tx-confirmed when confirmed
+#[tauri::command]
+pub async fn nc_create(
+    app: AppHandle,
+    state: tauri::State<'_, SharedState>,
+    request: NcCallRequest,
+) -> Result<NcTransaction, String> {
+    let tx = nc_create_internal(&state, &request).await?;
+    emit_confirmation(app, tx.clone());
+    Ok(tx)
+}
+
+// Call a nano contract method; emits nc-tx-confirmed when confirmed
+#[tauri::command]
+pub async fn nc_execute(
+    app: AppHandle,
+    state: tauri::State<'_, SharedState>,
+    request: NcCallRequest,
+) -> Result<NcTransaction, String> {
+    let tx = nc_execute_internal(&state, &request).await?;
+    emit_confirmation(app, tx.clone());
+    Ok(tx)
+}
+
+// Get the decoded values of a nano contract's fields and its balances
+#[tauri::command]
+pub async fn nc_get_state(
+    state: tauri::State<'_, SharedState>,
+    nc_id: String,
+    fields: Vec<String>,
+    tokens: Option<Vec<String>>,
+) -> Result<NcState, String> {
+    nc_get_state_internal(&state, &nc_id, &fields, &tokens.unwrap_or_default()).await
+}
+
+// Get the calls made to a nano contract, newest first
+#[tauri::command]
+pub async fn nc_get_history(
+    state: tauri::State<'_, SharedState>,
+    nc_id: String,
+    count: Option<u64>,
+    after: Option<String>,
+) -> Result<NcHistory, String> {
+    nc_get_history_internal(&state, &nc_id, count, after.as_deref()).await
+}
+
+// List the built-in and on-chain blueprints
+#[tauri::command]
+pub async fn list_blueprints(
+    state: tauri::State<'_, SharedState>,
+) -> Result<Vec<Blueprint>, String> {
+    list_blueprints_internal(&state).await
+}
+
+// Get the Python source of a blueprint
+#[tauri::command]
+pub async fn get_blueprint_source(
+    state: tauri::State<'_, SharedState>,
+    blueprint_id: String,
+) -> Result<BlueprintSource, String> {
+    get_blueprint_source_internal(&state, &blueprint_id).await
+}
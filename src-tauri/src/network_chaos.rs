@@ -0,0 +1,383 @@
+//! Network chaos for peer connections
+//!
+//! Forge has no say over the node's p2p internals, so the node reaches each
+//! peer added through `peers.rs` via a local link: a TCP forwarder on
+//! 127.0.0.1 that Forge controls. The links can hold traffic (pause), delay
+//! it (latency) or drop every connection and refuse new ones for a while
+//! (partition), which lets wallets and dApps be watched through network splits
+//! and recoveries. Only connections the node opens are affected; a peer that
+//! dialed into this node's listen port needs chaos applied on its side.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::tcp::{OwnedReadHalf, OwnedWriteHalf};
+use tokio::net::TcpStream;
+use tokio::sync::{mpsc, oneshot, watch};
+use tokio::time::Instant;
+
+use crate::{AppState, SharedState};
+
+const MAX_LATENCY_MS: u64 = 10_000;
+const MAX_PARTITION_SECS: u64 = 3600;
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ChaosConditions {
+    // Traffic is held (not dropped) until resumed
+    pub paused: bool,
+    // Added to every chunk of traffic, both directions
+    pub latency_ms: u64,
+    // Connections are dropped and refused until then (Unix timestamp)
+    pub partitioned_until: Option<u64>,
+}
+
+impl ChaosConditions {
+    fn partitioned(&self) -> bool {
+        self.partitioned_until.is_some()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PeerLink {
+    pub entrypoint: String,
+    // What the node connects to instead of the entrypoint
+    pub local_url: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NetworkChaosStatus {
+    pub conditions: ChaosConditions,
+    pub links: Vec<PeerLink>,
+}
+
+struct Link {
+    local_port: u16,
+    shutdown: oneshot::Sender<()>,
+}
+
+/// Chaos conditions and the node's peer links, kept in `AppState`
+pub struct NetworkChaos {
+    conditions: watch::Sender<ChaosConditions>,
+    // Remote entrypoint -> local link
+    links: HashMap<String, Link>,
+    // Bumped on every partition so a timed one only heals itself
+    partition_generation: u64,
+}
+
+impl Default for NetworkChaos {
+    fn default() -> Self {
+        Self {
+            conditions: watch::channel(ChaosConditions::default()).0,
+            links: HashMap::new(),
+            partition_generation: 0,
+        }
+    }
+}
+
+impl NetworkChaos {
+    fn update(&self, change: impl FnOnce(&mut ChaosConditions)) -> ChaosConditions {
+        let mut conditions = self.conditions.borrow().clone();
+        change(&mut conditions);
+        self.conditions.send_replace(conditions.clone());
+        conditions
+    }
+
+    fn status(&self) -> NetworkChaosStatus {
+        let mut links: Vec<PeerLink> = self
+            .links
+            .iter()
+            .map(|(entrypoint, link)| PeerLink {
+                entrypoint: entrypoint.clone(),
+                local_url: format!("tcp://127.0.0.1:{}", link.local_port),
+            })
+            .collect();
+        links.sort_by(|a, b| a.entrypoint.cmp(&b.entrypoint));
+        NetworkChaosStatus {
+            conditions: self.conditions.borrow().clone(),
+            links,
+        }
+    }
+
+    /// The entrypoint behind a link, given the address the node sees for the peer
+    pub fn entrypoint_for(&self, address: &str) -> Option<String> {
+        let port: u16 = address.rsplit_once(':')?.1.parse().ok()?;
+        self.links
+            .iter()
+            .find(|(_, link)| link.local_port == port)
+            .map(|(entrypoint, _)| entrypoint.clone())
+    }
+
+    /// Close every link; called when the node stops
+    pub fn close_links(&mut self) {
+        for (_, link) in self.links.drain() {
+            let _ = link.shutdown.send(());
+        }
+    }
+}
+
+// Copy one direction of a connection, holding it while paused and delaying
+// each chunk by the current latency
+async fn pipe(
+    mut reader: OwnedReadHalf,
+    mut writer: OwnedWriteHalf,
+    mut conditions: watch::Receiver<ChaosConditions>,
+) {
+    let (tx, mut rx) = mpsc::unbounded_channel::<(Instant, Vec<u8>)>();
+
+    let delayed_writer = async move {
+        while let Some((due, bytes)) = rx.recv().await {
+            tokio::time::sleep_until(due).await;
+            if writer.write_all(&bytes).await.is_err() {
+                break;
+            }
+        }
+        let _ = writer.shutdown().await;
+    };
+
+    let paced_reader = async move {
+        let mut buf = vec![0u8; 16 * 1024];
+        loop {
+            while conditions.borrow_and_update().paused {
+                if conditions.changed().await.is_err() {
+                    return;
+                }
+            }
+            let n = tokio::select! {
+                read = reader.read(&mut buf) => match read {
+                    Ok(0) | Err(_) => return,
+                    Ok(n) => n,
+                },
+                // Re-check for a pause before reading on
+                changed = conditions.changed() => {
+                    if changed.is_err() {
+                        return;
+                    }
+                    continue;
+                }
+            };
+            let latency = Duration::from_millis(conditions.borrow().latency_ms);
+            if tx
+                .send((Instant::now() + latency, buf[..n].to_vec()))
+                .is_err()
+            {
+                return;
+            }
+        }
+    };
+
+    tokio::join!(delayed_writer, paced_reader);
+}
+
+async fn wait_for_partition(mut conditions: watch::Receiver<ChaosConditions>) {
+    while !conditions.borrow_and_update().partitioned() {
+        if conditions.changed().await.is_err() {
+            // Never resolve once the conditions are gone
+            std::future::pending::<()>().await;
+        }
+    }
+}
+
+async fn link_connection(
+    inbound: TcpStream,
+    remote: String,
+    conditions: watch::Receiver<ChaosConditions>,
+) {
+    let Ok(outbound) = TcpStream::connect(&remote).await else {
+        return;
+    };
+    let (node_read, node_write) = inbound.into_split();
+    let (peer_read, peer_write) = outbound.into_split();
+
+    tokio::select! {
+        _ = async {
+            tokio::join!(
+                pipe(node_read, peer_write, conditions.clone()),
+                pipe(peer_read, node_write, conditions.clone()),
+            )
+        } => {}
+        _ = wait_for_partition(conditions.clone()) => {}
+    }
+}
+
+/// Local URL the node should dial to reach `entrypoint` through a chaos link,
+/// opening the link if needed. Must be called from within the Tokio runtime.
+pub fn link(state: &mut AppState, entrypoint: &str) -> Result<String, String> {
+    let chaos = &mut state.network_chaos;
+    if let Some(link) = chaos.links.get(entrypoint) {
+        return Ok(format!("tcp://127.0.0.1:{}", link.local_port));
+    }
+
+    let remote = entrypoint
+        .strip_prefix("tcp://")
+        .ok_or_else(|| format!("Unsupported peer URL '{}'", entrypoint))?
+        .trim_end_matches('/')
+        .to_string();
+    let listener = std::net::TcpListener::bind(("127.0.0.1", 0))
+        .and_then(|listener| listener.set_nonblocking(true).map(|_| listener))
+        .map_err(|e| format!("Failed to open a link to {}: {}", entrypoint, e))?;
+    let local_port = listener
+        .local_addr()
+        .map_err(|e| format!("Failed to open a link to {}: {}", entrypoint, e))?
+        .port();
+    let listener = tokio::net::TcpListener::from_std(listener)
+        .map_err(|e| format!("Failed to open a link to {}: {}", entrypoint, e))?;
+
+    let (shutdown_tx, mut shutdown_rx) = oneshot::channel::<()>();
+    let conditions = chaos.conditions.subscribe();
+    tokio::spawn(async move {
+        loop {
+            let inbound = tokio::select! {
+                _ = &mut shutdown_rx => break,
+                accepted = listener.accept() => match accepted {
+                    Ok((stream, _)) => stream,
+                    Err(_) => continue,
+                },
+            };
+            // Refused while partitioned
+            if conditions.borrow().partitioned() {
+                continue;
+            }
+            tokio::spawn(link_connection(inbound, remote.clone(), conditions.clone()));
+        }
+    });
+
+    chaos.links.insert(
+        entrypoint.to_string(),
+        Link {
+            local_port,
+            shutdown: shutdown_tx,
+        },
+    );
+    Ok(format!("tcp://127.0.0.1:{}", local_port))
+}
+
+/// Hold all peer traffic until resumed; a long pause ends in the peers timing
+/// each other out
+pub async fn pause_p2p_internal(state: &SharedState) -> NetworkChaosStatus {
+    let state_guard = state.lock().await;
+    state_guard.network_chaos.update(|c| c.paused = true);
+    state_guard.network_chaos.status()
+}
+
+/// Let held peer traffic through again
+pub async fn resume_p2p_internal(state: &SharedState) -> NetworkChaosStatus {
+    let state_guard = state.lock().await;
+    state_guard.network_chaos.update(|c| c.paused = false);
+    state_guard.network_chaos.status()
+}
+
+/// Delay peer traffic by `latency_ms` in each direction (0 turns it off)
+pub async fn set_p2p_latency_internal(
+    state: &SharedState,
+    latency_ms: u64,
+) -> Result<NetworkChaosStatus, String> {
+    if latency_ms > MAX_LATENCY_MS {
+        return Err(format!("Latency must be at most {} ms", MAX_LATENCY_MS));
+    }
+    let state_guard = state.lock().await;
+    state_guard
+        .network_chaos
+        .update(|c| c.latency_ms = latency_ms);
+    Ok(state_guard.network_chaos.status())
+}
+
+/// Cut the node off from its peers for `seconds`, after which the links
+/// accept connections again and the node redials its peers
+pub async fn partition_network_internal(
+    state: &SharedState,
+    seconds: u64,
+) -> Result<NetworkChaosStatus, String> {
+    if !(1..=MAX_PARTITION_SECS).contains(&seconds) {
+        return Err(format!(
+            "Partition must last between 1 and {} seconds",
+            MAX_PARTITION_SECS
+        ));
+    }
+
+    let generation = {
+        let mut state_guard = state.lock().await;
+        let chaos = &mut state_guard.network_chaos;
+        chaos.partition_generation += 1;
+        chaos.update(|c| c.partitioned_until = Some(crate::unix_now() + seconds));
+        chaos.partition_generation
+    };
+
+    let heal_state = state.clone();
+    tokio::spawn(async move {
+        tokio::time::sleep(Duration::from_secs(seconds)).await;
+        let state_guard = heal_state.lock().await;
+        let chaos = &state_guard.network_chaos;
+        if chaos.partition_generation == generation {
+            chaos.update(|c| c.partitioned_until = None);
+        }
+    });
+
+    Ok(state.lock().await.network_chaos.status())
+}
+
+/// End any partition, pause and latency
+pub async fn heal_network_internal(state: &SharedState) -> NetworkChaosStatus {
+    let mut state_guard = state.lock().await;
+    let chaos = &mut state_guard.network_chaos;
+    chaos.partition_generation += 1;
+    chaos.update(|c| *c = ChaosConditions::default());
+    chaos.status()
+}
+
+/// Current chaos conditions and peer links
+pub async fn get_network_chaos_internal(state: &SharedState) -> NetworkChaosStatus {
+    state.lock().await.network_chaos.status()
+}
+
+// ============================================================================
+// Tauri Commands
+// ============================================================================
+
+// Get the current chaos conditions and the node's peer links
+#[tauri::command]
+pub async fn get_network_chaos(
+    state: tauri::State<'_, SharedState>,
+) -> Result<NetworkChaosStatus, String> {
+    Ok(get_network_chaos_internal(&state).await)
+}
+
+// Hold all peer traffic until resumed
+#[tauri::command]
+pub async fn pause_p2p(state: tauri::State<'_, SharedState>) -> Result<NetworkChaosStatus, String> {
+    Ok(pause_p2p_internal(&state).await)
+}
+
+// Let held peer traffic through again
+#[tauri::command]
+pub async fn resume_p2p(
+    state: tauri::State<'_, SharedState>,
+) -> Result<NetworkChaosStatus, String> {
+    Ok(resume_p2p_internal(&state).await)
+}
+
+// Delay peer traffic by latency_ms in each direction
+#[tauri::command]
+pub async fn set_p2p_latency(
+    state: tauri::State<'_, SharedState>,
+    latency_ms: u64,
+) -> Result<NetworkChaosStatus, String> {
+    set_p2p_latency_internal(&state, latency_ms).await
+}
+
+// Cut the node off from its peers for a number of seconds
+#[tauri::command]
+pub async fn partition_network(
+    state: tauri::State<'_, SharedState>,
+    seconds: u64,
+) -> Result<NetworkChaosStatus, String> {
+    partition_network_internal(&state, seconds).await
+}
+
+// End any partition, pause and latency
+#[tauri::command]
+pub async fn heal_network(
+    state: tauri::State<'_, SharedState>,
+) -> Result<NetworkChaosStatus, String> {
+    Ok(heal_network_internal(&state).await)
+}
@@ -0,0 +1,80 @@
+//! Block-found events and desktop notifications
+//!
+//! Miner session trackers publish every block whose coinbase pays a session
+//! address. A background task forwards them to the UI as `block-found` events
+//! and, when enabled, shows a desktop notification with the block's height and
+//! reward. The setting lives in `notifications.json` in the app directory.
+
+use serde::{Deserialize, Serialize};
+use tauri::{AppHandle, Emitter};
+use tauri_plugin_notification::NotificationExt;
+use tokio::sync::broadcast::error::RecvError;
+
+use crate::miner_metrics::BlockFound;
+use crate::settings::JsonSettings;
+use crate::SharedState;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct NotificationSettings {
+    // Show a desktop notification for every block found
+    #[serde(default)]
+    pub block_found: bool,
+}
+
+static SETTINGS: JsonSettings<NotificationSettings> =
+    JsonSettings::new("notifications.json", "notification settings");
+
+fn notify_block_found(app: &AppHandle, block: &BlockFound) {
+    let title = if block.miner == crate::DEFAULT_MINER {
+        "Block found".to_string()
+    } else {
+        format!("Block found by '{}'", block.miner)
+    };
+    let _ = app
+        .notification()
+        .builder()
+        .title(title)
+        .body(format!(
+            "Block {} paid {} to {}",
+            block.height, block.reward, block.address
+        ))
+        .show();
+}
+
+/// Forward blocks found by Forge's miners for the lifetime of the app
+pub async fn forward_blocks_found(app: AppHandle, state: SharedState) {
+    let mut blocks = state.lock().await.block_found.subscribe();
+
+    loop {
+        let block = match blocks.recv().await {
+            Ok(block) => block,
+            // Dropped blocks were still counted in the session stats
+            Err(RecvError::Lagged(_)) => continue,
+            Err(RecvError::Closed) => return,
+        };
+
+        let _ = app.emit("block-found", &block);
+        if SETTINGS.load().block_found {
+            notify_block_found(&app, &block);
+        }
+    }
+}
+
+// ============================================================================
+// Tauri Commands
+// ============================================================================
+
+// Get the desktop notification settings
+#[tauri::command]
+pub async fn get_notification_settings() -> Result<NotificationSettings, String> {
+    Ok(SETTINGS.load())
+}
+
+// Turn desktop notifications for found blocks on or off
+#[tauri::command]
+pub async fn set_block_notifications(enabled: bool) -> Result<NotificationSettings, String> {
+    let mut settings = SETTINGS.load();
+    settings.block_found = enabled;
+    SETTINGS.save(&settings)?;
+    Ok(settings)
+}
@@ -0,0 +1,227 @@
+//! Paced mining: one block every N seconds
+//!
+//! A free-running cpuminer finds localnet blocks as fast as the CPU allows,
+//! which makes chain timing unrealistic. In paced mode a background loop starts
+//! a dedicated miner, stops it as soon as the next block lands, and waits out
+//! the rest of the interval before mining again.
+
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+
+use crate::{MinerConfig, SharedState};
+
+// Name of the miner driven by the pacing loop
+const PACED_MINER: &str = "paced";
+const MIN_INTERVAL_SECS: u64 = 1;
+const HEIGHT_POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Pacing loop state kept in `AppState` while it runs
+pub struct PacedMining {
+    interval_secs: u64,
+    address: String,
+    started: Instant,
+    blocks_mined: u64,
+    shutdown: Option<tokio::sync::oneshot::Sender<()>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PacedMiningStatus {
+    pub running: bool,
+    pub interval_secs: Option<u64>,
+    pub address: Option<String>,
+    pub blocks_mined: u64,
+    pub uptime_secs: u64,
+}
+
+async fn best_height(client: &reqwest::Client) -> Result<u64, String> {
+    let status: serde_json::Value = client
+        .get("http://127.0.0.1:8080/v1a/status/")
+        .send()
+        .await
+        .map_err(|e| format!("Failed to get node status: {}", e))?
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse node status: {}", e))?;
+    status["dag"]["best_block"]["height"]
+        .as_u64()
+        .ok_or_else(|| "Node status has no best block height".to_string())
+}
+
+// Mine exactly one block (as closely as polling allows) with the paced miner
+async fn mine_one_block(
+    state: &SharedState,
+    client: &reqwest::Client,
+    address: &str,
+    shutdown: &mut tokio::sync::oneshot::Receiver<()>,
+) -> Result<bool, String> {
+    let height = best_height(client).await?;
+    crate::start_miner_internal(
+        state,
+        MinerConfig {
+            name: PACED_MINER.to_string(),
+            address: address.to_string(),
+            ..MinerConfig::default()
+        },
+    )
+    .await?;
+
+    let mined = loop {
+        tokio::select! {
+            _ = &mut *shutdown => break false,
+            _ = tokio::time::sleep(HEIGHT_POLL_INTERVAL) => {}
+        }
+        if best_height(client).await? > height {
+            break true;
+        }
+        if !state.lock().await.miners.contains_key(PACED_MINER) {
+            break false;
+        }
+    };
+
+    crate::stop_miner_internal(state, Some(PACED_MINER)).await?;
+    Ok(mined)
+}
+
+async fn pacing_loop(
+    state: SharedState,
+    interval: Duration,
+    address: String,
+    mut shutdown: tokio::sync::oneshot::Receiver<()>,
+) {
+    let client = reqwest::Client::new();
+
+    loop {
+        let tick = Instant::now();
+        match mine_one_block(&state, &client, &address, &mut shutdown).await {
+            Ok(true) => {
+                if let Some(paced) = state.lock().await.paced_mining.as_mut() {
+                    paced.blocks_mined += 1;
+                }
+            }
+            // Stopped, or the miner went away (e.g. the node stopped)
+            Ok(false) => break,
+            Err(e) => {
+                eprintln!("Paced mining stopped: {}", e);
+                break;
+            }
+        }
+
+        // Wait out the rest of the interval (right away if the block was slow)
+        let remaining = interval.saturating_sub(tick.elapsed());
+        tokio::select! {
+            _ = &mut shutdown => break,
+            _ = tokio::time::sleep(remaining) => {}
+        }
+    }
+
+    let _ = crate::stop_miner_internal(&state, Some(PACED_MINER)).await;
+    state.lock().await.paced_mining = None;
+}
+
+/// Start mining one block every `interval_secs` seconds
+pub async fn start_paced_mining_internal(
+    state: &SharedState,
+    interval_secs: u64,
+    address: Option<String>,
+) -> Result<String, String> {
+    if interval_secs < MIN_INTERVAL_SECS {
+        return Err(format!(
+            "Block interval must be at least {} second",
+            MIN_INTERVAL_SECS
+        ));
+    }
+
+    let mut state_guard = state.lock().await;
+    if !state_guard.node_running {
+        return Err("Node must be running before starting paced mining".to_string());
+    }
+    if state_guard.paced_mining.is_some() {
+        return Err("Paced mining is already running".to_string());
+    }
+
+    let address = address.unwrap_or_else(|| MinerConfig::default().address);
+    let (shutdown_tx, shutdown_rx) = tokio::sync::oneshot::channel();
+    state_guard.paced_mining = Some(PacedMining {
+        interval_secs,
+        address: address.clone(),
+        started: Instant::now(),
+        blocks_mined: 0,
+        shutdown: Some(shutdown_tx),
+    });
+    drop(state_guard);
+
+    tokio::spawn(pacing_loop(
+        state.clone(),
+        Duration::from_secs(interval_secs),
+        address,
+        shutdown_rx,
+    ));
+
+    Ok(format!(
+        "Paced mining started: one block every {}s",
+        interval_secs
+    ))
+}
+
+/// Stop paced mining (and its miner)
+pub async fn stop_paced_mining_internal(state: &SharedState) -> Result<String, String> {
+    let mut state_guard = state.lock().await;
+    let Some(shutdown) = state_guard
+        .paced_mining
+        .as_mut()
+        .and_then(|paced| paced.shutdown.take())
+    else {
+        return Ok("Paced mining is not running".to_string());
+    };
+
+    let _ = shutdown.send(());
+    Ok("Paced mining stopped".to_string())
+}
+
+pub async fn get_paced_mining_status_internal(state: &SharedState) -> PacedMiningStatus {
+    let state_guard = state.lock().await;
+    match state_guard.paced_mining.as_ref() {
+        Some(paced) => PacedMiningStatus {
+            running: true,
+            interval_secs: Some(paced.interval_secs),
+            address: Some(paced.address.clone()),
+            blocks_mined: paced.blocks_mined,
+            uptime_secs: paced.started.elapsed().as_secs(),
+        },
+        None => PacedMiningStatus {
+            running: false,
+            interval_secs: None,
+            address: None,
+            blocks_mined: 0,
+            uptime_secs: 0,
+        },
+    }
+}
+
+// ============================================================================
+// Tauri Commands
+// ============================================================================
+
+// Mine one block every `interval_secs` seconds
+#[tauri::command]
+pub async fn start_paced_mining(
+    state: tauri::State<'_, SharedState>,
+    interval_secs: u64,
+    address: Option<String>,
+) -> Result<String, String> {
+    start_paced_mining_internal(&state, interval_secs, address).await
+}
+
+// Stop paced mining
+#[tauri::command]
+pub async fn stop_paced_mining(state: tauri::State<'_, SharedState>) -> Result<String, String> {
+    stop_paced_mining_internal(&state).await
+}
+
+// Get the paced mining interval and blocks mined so far
+#[tauri::command]
+pub async fn get_paced_mining_status(
+    state: tauri::State<'_, SharedState>,
+) -> Result<PacedMiningStatus, String> {
+    Ok(get_paced_mining_status_internal(&state).await)
+}
@@ -0,0 +1,239 @@
+//! Payment flows: payment requests and waiting for incoming payments
+
+use futures_util::{SinkExt, StreamExt};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use tauri::Emitter;
+use tokio_tungstenite::tungstenite::Message;
+
+use crate::{headless, qr, tokens, SharedState};
+
+const DEFAULT_WATCH_TIMEOUT_SECS: u64 = 60;
+const HTR_UID: &str = "00";
+const PAYMENT_QR_SIZE: u32 = 256;
+
+/// A request for a payment to a fresh wallet address
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PaymentRequest {
+    pub wallet_id: String,
+    pub address: String,
+    pub amount: u64, // Amount in cents
+    pub token_uid: String,
+    pub token_symbol: String,
+    // `hathor:<address>` URI
+    pub payment_uri: String,
+    // Payload encoded in the QR code, in the format the Hathor wallets scan
+    pub qr_payload: String,
+    // PNG, base64-encoded
+    pub qr_png_base64: String,
+}
+
+/// A transaction paying at least the watched amount to an address
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PaymentReceived {
+    pub address: String,
+    pub tx_id: String,
+    pub token: String,
+    pub amount: u64, // Amount in cents received by the address in this tx
+    pub timestamp: Option<u64>,
+}
+
+// Sum the outputs of a tx paying `token` to `address`
+fn received_amount(tx: &Value, address: &str, token: &str) -> u64 {
+    tx["outputs"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter(|output| output["decoded"]["address"].as_str() == Some(address))
+        // Authority outputs carry flags, not amounts
+        .filter(|output| output["token_data"].as_u64().unwrap_or(0) & 0x80 == 0)
+        .filter(|output| output["token"].as_str().unwrap_or(HTR_UID) == token)
+        .filter_map(|output| output["value"].as_u64())
+        .sum()
+}
+
+/// Create a payment request for a fresh receive address of a wallet
+pub async fn create_payment_request_internal(
+    state: &SharedState,
+    wallet_id: &str,
+    amount: u64,
+    token: Option<&str>,
+    instance: Option<&str>,
+) -> Result<PaymentRequest, String> {
+    let token_uid = token.unwrap_or(HTR_UID);
+    let (token_name, token_symbol) = if token_uid == HTR_UID {
+        ("Hathor".to_string(), "HTR".to_string())
+    } else {
+        tokens::list_tokens_internal(state)
+            .await
+            .into_iter()
+            .find(|t| t.token_uid == token_uid)
+            .map(|t| (t.name, t.symbol))
+            .ok_or_else(|| {
+                format!(
+                    "Token {} is not registered. Register it first so the request can name it.",
+                    token_uid
+                )
+            })?
+    };
+
+    let base_url = crate::headless_url(state, instance).await?;
+    let result = headless::wallet_get(
+        &base_url,
+        wallet_id,
+        "/wallet/address",
+        &[("mark_as_used", "true".to_string())],
+    )
+    .await
+    .map_err(|e| format!("Failed to get receive address: {}", e))?;
+    let address = result["address"]
+        .as_str()
+        .ok_or("Missing address in response")?
+        .to_string();
+
+    let payment_uri = format!("hathor:{}", address);
+    let qr_payload = json!({
+        "address": payment_uri,
+        "amount": amount,
+        "token": {
+            "uid": token_uid,
+            "name": token_name,
+            "symbol": token_symbol,
+        },
+    })
+    .to_string();
+    let qr_png_base64 = qr::png_base64(&qr_payload, PAYMENT_QR_SIZE)?;
+
+    Ok(PaymentRequest {
+        wallet_id: wallet_id.to_string(),
+        address,
+        amount,
+        token_uid: token_uid.to_string(),
+        token_symbol,
+        payment_uri,
+        qr_payload,
+        qr_png_base64,
+    })
+}
+
+/// Wait until a transaction paying at least `min_amount` of `token` to `address`
+/// shows up on the node's WebSocket
+pub async fn watch_address_internal(
+    state: &SharedState,
+    address: &str,
+    min_amount: u64,
+    token: Option<&str>,
+    timeout_secs: Option<u64>,
+) -> Result<PaymentReceived, String> {
+    crate::address::check_address(address)?;
+
+    let token = token.unwrap_or(HTR_UID);
+    let timeout =
+        std::time::Duration::from_secs(timeout_secs.unwrap_or(DEFAULT_WATCH_TIMEOUT_SECS));
+
+    let ws_url = format!(
+        "ws://127.0.0.1:{}/v1a/ws/",
+        state.lock().await.node_api_port
+    );
+    let (ws_stream, _) = tokio_tungstenite::connect_async(ws_url)
+        .await
+        .map_err(|e| format!("Failed to connect to node WebSocket: {}", e))?;
+    let (mut sink, mut stream) = ws_stream.split();
+
+    let subscribe = serde_json::json!({
+        "type": "subscribe_address",
+        "address": address,
+    });
+    sink.send(Message::Text(subscribe.to_string()))
+        .await
+        .map_err(|e| format!("Failed to subscribe to address: {}", e))?;
+
+    let wait = async {
+        while let Some(message) = stream.next().await {
+            let text = match message {
+                Ok(Message::Text(text)) => text,
+                Ok(Message::Close(_)) | Err(_) => break,
+                Ok(_) => continue,
+            };
+            let Ok(event) = serde_json::from_str::<Value>(&text) else {
+                continue;
+            };
+
+            if event["type"] != "wallet:address_history" || event["address"] != address {
+                continue;
+            }
+
+            let tx = &event["history"];
+            let amount = received_amount(tx, address, token);
+            if amount > 0 && amount >= min_amount {
+                return Ok(PaymentReceived {
+                    address: address.to_string(),
+                    tx_id: tx["tx_id"].as_str().unwrap_or_default().to_string(),
+                    token: token.to_string(),
+                    amount,
+                    timestamp: tx["timestamp"].as_u64(),
+                });
+            }
+        }
+        Err("Node WebSocket closed while watching address".to_string())
+    };
+
+    let result = tokio::time::timeout(timeout, wait).await.map_err(|_| {
+        format!(
+            "No payment of at least {} to {} within {}s",
+            min_amount,
+            address,
+            timeout.as_secs()
+        )
+    })?;
+
+    let _ = sink.send(Message::Close(None)).await;
+    result
+}
+
+// ============================================================================
+// Tauri Commands
+// ============================================================================
+
+// Create a payment request (fresh address, payment URI and QR code)
+#[tauri::command]
+pub async fn create_payment_request(
+    state: tauri::State<'_, SharedState>,
+    wallet_id: String,
+    amount: u64,
+    token: Option<String>,
+    instance: Option<String>,
+) -> Result<PaymentRequest, String> {
+    create_payment_request_internal(
+        &state,
+        &wallet_id,
+        amount,
+        token.as_deref(),
+        instance.as_deref(),
+    )
+    .await
+}
+
+// Wait for a payment to an address (emits payment-received when it arrives)
+#[tauri::command]
+pub async fn watch_address(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, SharedState>,
+    address: String,
+    min_amount: Option<u64>,
+    token: Option<String>,
+    timeout: Option<u64>,
+) -> Result<PaymentReceived, String> {
+    let payment = watch_address_internal(
+        &state,
+        &address,
+        min_amount.unwrap_or(0),
+        token.as_deref(),
+        timeout,
+    )
+    .await?;
+
+    let _ = app.emit("payment-received", &payment);
+
+    Ok(payment)
+}
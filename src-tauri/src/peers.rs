@@ -0,0 +1,264 @@
+//! Peer management
+//!
+//! The localnet node runs alone by default. Peers can be added on demand
+//! through the node's p2p API, and are remembered as `--bootstrap` entries so
+//! the node reconnects to them on its next start. A p2p listen port (off by
+//! default) lets other Forge nodes or dev machines connect in. Removing a peer
+//! adds a netfilter rule rejecting its peer id, since hathor-core has no
+//! disconnect endpoint. Settings live in `peers.json` in the app directory.
+//!
+//! The node dials its peers through `network_chaos` links, so their traffic
+//! can be paused, delayed or partitioned.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::settings::JsonSettings;
+use crate::{network_chaos, AppState, NodeConfig, SharedState};
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PeerSettings {
+    // Accept p2p connections on this port (all interfaces)
+    #[serde(default)]
+    pub listen_port: Option<u16>,
+    // Entrypoints connected to at start, e.g. tcp://192.168.1.20:40403
+    #[serde(default)]
+    pub bootstrap: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PeerInfo {
+    pub id: Option<String>,
+    pub address: Option<String>,
+    pub state: Option<String>,
+    pub app_version: Option<String>,
+    pub uptime_secs: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PeerList {
+    pub connected: Vec<PeerInfo>,
+    pub handshaking: Vec<PeerInfo>,
+    // Addresses being dialed
+    pub connecting: Vec<String>,
+    pub listen_port: Option<u16>,
+    pub bootstrap: Vec<String>,
+}
+
+static SETTINGS: JsonSettings<PeerSettings> = JsonSettings::new("peers.json", "peer settings");
+
+/// hathor-core arguments for the stored listen port and bootstrap peers, the
+/// latter reached through chaos links
+pub fn node_args(state: &mut AppState) -> Vec<String> {
+    let settings = SETTINGS.load();
+    let mut args = Vec::new();
+    if let Some(port) = settings.listen_port {
+        args.extend(["--listen".to_string(), format!("tcp:{}", port)]);
+    }
+    for entrypoint in settings.bootstrap {
+        let url = network_chaos::link(state, &entrypoint).unwrap_or_else(|e| {
+            eprintln!("{}; connecting directly", e);
+            entrypoint
+        });
+        args.extend(["--bootstrap".to_string(), url]);
+    }
+    args
+}
+
+fn api_url(path: &str) -> String {
+    format!(
+        "http://127.0.0.1:{}/v1a/{}",
+        NodeConfig::default().api_port,
+        path
+    )
+}
+
+// Accept "host:port" as shorthand for "tcp://host:port"
+fn normalize_entrypoint(url: &str) -> Result<String, String> {
+    let url = url.trim();
+    let entrypoint = if url.contains("://") {
+        url.to_string()
+    } else {
+        format!("tcp://{}", url)
+    };
+    let address = entrypoint
+        .strip_prefix("tcp://")
+        .ok_or_else(|| format!("Unsupported peer URL '{}' (expected tcp://host:port)", url))?;
+    let (host, port) = address
+        .rsplit_once(':')
+        .ok_or_else(|| format!("Peer URL '{}' has no port", url))?;
+    if host.is_empty() || port.parse::<u16>().is_err() {
+        return Err(format!("Invalid peer URL '{}'", url));
+    }
+    Ok(entrypoint)
+}
+
+fn peer_info(peer: &Value) -> PeerInfo {
+    PeerInfo {
+        id: peer["id"].as_str().map(|s| s.to_string()),
+        address: peer["address"].as_str().map(|s| s.to_string()),
+        state: peer["state"].as_str().map(|s| s.to_string()),
+        app_version: peer["app_version"].as_str().map(|s| s.to_string()),
+        uptime_secs: peer["uptime"].as_f64(),
+    }
+}
+
+/// Peers the node is connected, handshaking or connecting to
+pub async fn list_peers_internal(state: &SharedState) -> Result<PeerList, String> {
+    if !state.lock().await.node_running {
+        return Err("Node is not running".to_string());
+    }
+
+    let status: Value = reqwest::Client::new()
+        .get(api_url("status/"))
+        .send()
+        .await
+        .map_err(|e| format!("Failed to get node status: {}", e))?
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse node status: {}", e))?;
+    let connections = &status["connections"];
+    let peers = |key: &str| -> Vec<PeerInfo> {
+        connections[key]
+            .as_array()
+            .map(|peers| peers.iter().map(peer_info).collect())
+            .unwrap_or_default()
+    };
+
+    let settings = SETTINGS.load();
+    Ok(PeerList {
+        connected: peers("connected_peers"),
+        handshaking: peers("handshaking_peers"),
+        connecting: connections["connecting_peers"]
+            .as_array()
+            .map(|peers| {
+                peers
+                    .iter()
+                    .filter_map(|peer| peer["address"].as_str().map(|s| s.to_string()))
+                    .collect()
+            })
+            .unwrap_or_default(),
+        listen_port: settings.listen_port,
+        bootstrap: settings.bootstrap,
+    })
+}
+
+/// Connect to a peer now and on every node start
+pub async fn add_peer_internal(state: &SharedState, url: &str) -> Result<String, String> {
+    let entrypoint = normalize_entrypoint(url)?;
+
+    let mut settings = SETTINGS.load();
+    if !settings.bootstrap.contains(&entrypoint) {
+        settings.bootstrap.push(entrypoint.clone());
+        SETTINGS.save(&settings)?;
+    }
+
+    let url = {
+        let mut state_guard = state.lock().await;
+        if !state_guard.node_running {
+            return Ok(format!(
+                "Peer {} saved; the node connects to it when it starts",
+                entrypoint
+            ));
+        }
+        network_chaos::link(&mut state_guard, &entrypoint)?
+    };
+
+    let response = reqwest::Client::new()
+        .post(api_url("p2p/peers"))
+        .json(&[&url])
+        .send()
+        .await
+        .map_err(|e| format!("Failed to add peer: {}", e))?;
+    if !response.status().is_success() {
+        let body = response.text().await.unwrap_or_default();
+        return Err(format!("Node rejected peer {}: {}", entrypoint, body));
+    }
+
+    Ok(format!("Connecting to {}", entrypoint))
+}
+
+/// Drop a peer by id and keep it from reconnecting; its address is also
+/// removed from the bootstrap list
+pub async fn remove_peer_internal(state: &SharedState, peer_id: &str) -> Result<String, String> {
+    let peers = list_peers_internal(state).await?;
+    let address = peers
+        .connected
+        .iter()
+        .chain(peers.handshaking.iter())
+        .find(|peer| peer.id.as_deref() == Some(peer_id))
+        .and_then(|peer| peer.address.clone());
+
+    let rule = serde_json::json!({
+        "chain": { "name": "post_peerid" },
+        "target": { "type": "NetfilterReject", "target_params": {} },
+        "match": {
+            "type": "NetfilterMatchPeerId",
+            "match_params": { "peer_id": peer_id }
+        }
+    });
+    let response = reqwest::Client::new()
+        .post(api_url("p2p/netfilter"))
+        .json(&rule)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to block peer: {}", e))?;
+    if !response.status().is_success() {
+        let body = response.text().await.unwrap_or_default();
+        return Err(format!("Node rejected the peer filter: {}", body));
+    }
+
+    if let Some(address) = address {
+        // Peers dialed through a link show up with the link's local address
+        let linked = state.lock().await.network_chaos.entrypoint_for(&address);
+        let mut settings = SETTINGS.load();
+        let before = settings.bootstrap.len();
+        settings.bootstrap.retain(|entrypoint| {
+            Some(entrypoint) != linked.as_ref() && !entrypoint.ends_with(&format!("://{}", address))
+        });
+        if settings.bootstrap.len() != before {
+            SETTINGS.save(&settings)?;
+        }
+    }
+
+    Ok(format!("Peer {} removed", peer_id))
+}
+
+/// Accept p2p connections on `port` (or stop doing so) from the next node start
+pub fn set_p2p_listen(port: Option<u16>) -> Result<PeerSettings, String> {
+    let mut settings = SETTINGS.load();
+    settings.listen_port = port;
+    SETTINGS.save(&settings)?;
+    Ok(settings)
+}
+
+// ============================================================================
+// Tauri Commands
+// ============================================================================
+
+// List the node's connected, handshaking and connecting peers
+#[tauri::command]
+pub async fn list_peers(state: tauri::State<'_, SharedState>) -> Result<PeerList, String> {
+    list_peers_internal(&state).await
+}
+
+// Connect to a peer (e.g. tcp://192.168.1.20:40403), now and on every start
+#[tauri::command]
+pub async fn add_peer(state: tauri::State<'_, SharedState>, url: String) -> Result<String, String> {
+    add_peer_internal(&state, &url).await
+}
+
+// Disconnect a peer and keep it from reconnecting
+#[tauri::command]
+pub async fn remove_peer(
+    state: tauri::State<'_, SharedState>,
+    peer_id: String,
+) -> Result<String, String> {
+    remove_peer_internal(&state, &peer_id).await
+}
+
+// Set the p2p listen port used from the next node start (None turns it off)
+#[tauri::command]
+pub async fn set_p2p_listen_port(port: Option<u16>) -> Result<PeerSettings, String> {
+    set_p2p_listen(port)
+}
@@ -0,0 +1,341 @@
+//! Proof-of-Authority side-DAG node
+//!
+//! Side-DAGs built on Hathor run hathor-core with Proof-of-Authority
+//! consensus: blocks are signed by a configured set of signers instead of
+//! mined. Forge runs one such node next to the localnet, on its own ports and
+//! data directory (a PoA chain can't share data with a PoW one), with a single
+//! signer whose key is generated once with hathor-core's `generate_poa_keys`
+//! and kept in `poa-signer.json` in the app directory. The node's settings
+//! extend the localnet ones with that signer, so it produces blocks by itself
+//! and needs no miner.
+//!
+//! The signer key is a development key stored in the clear; it must never
+//! sign for a real side-DAG.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fs;
+use std::process::Stdio;
+use tauri::{AppHandle, Emitter};
+use tokio::io::{AsyncBufReadExt, BufReader};
+use tokio::process::Command as TokioCommand;
+
+use crate::SharedState;
+
+const POA_API_PORT: u16 = 8085;
+const POA_P2P_PORT: u16 = 40420;
+// Target time between signed blocks, in seconds
+const DEFAULT_BLOCK_TIME: u64 = 5;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PoaConfig {
+    #[serde(default = "default_api_port")]
+    pub api_port: u16,
+    #[serde(default = "default_block_time")]
+    pub block_time: u64,
+    // Accept peers (e.g. other signers' nodes) on POA_P2P_PORT
+    #[serde(default)]
+    pub listen: bool,
+}
+
+fn default_api_port() -> u16 {
+    POA_API_PORT
+}
+
+fn default_block_time() -> u64 {
+    DEFAULT_BLOCK_TIME
+}
+
+impl Default for PoaConfig {
+    fn default() -> Self {
+        Self {
+            api_port: POA_API_PORT,
+            block_time: DEFAULT_BLOCK_TIME,
+            listen: false,
+        }
+    }
+}
+
+/// The PoA node process, kept in `AppState`
+#[derive(Default)]
+pub struct PoaNode {
+    child_id: Option<u32>,
+    api_port: u16,
+}
+
+/// Public half of the signer file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PoaSigner {
+    pub public_key_hex: String,
+    pub address: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PoaStatus {
+    pub running: bool,
+    pub api_port: Option<u16>,
+    pub data_dir: String,
+    pub signer: Option<PoaSigner>,
+    pub best_block_height: Option<u64>,
+}
+
+fn signer_path() -> std::path::PathBuf {
+    crate::get_app_dir().join("poa-signer.json")
+}
+
+fn yaml_path() -> std::path::PathBuf {
+    crate::get_app_dir().join("poa-sidedag.yml")
+}
+
+pub fn poa_data_dir() -> std::path::PathBuf {
+    crate::get_app_dir().join("poa-data")
+}
+
+fn load_signer() -> Option<PoaSigner> {
+    fs::read_to_string(signer_path())
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+}
+
+/// Generate the signer key with hathor-core, unless there is one already (or
+/// `force` replaces it; the PoA chain then needs a data reset)
+pub async fn generate_poa_signer_internal(force: bool) -> Result<PoaSigner, String> {
+    if !force {
+        if let Some(signer) = load_signer() {
+            return Ok(signer);
+        }
+    }
+
+    let binary_path = crate::get_binary_path("hathor-core");
+    let internal_dir = binary_path.parent().unwrap().join("_internal");
+    let mut cmd = TokioCommand::new(&binary_path);
+    crate::set_library_path_env(&mut cmd, &internal_dir);
+    let output = cmd
+        .arg("generate_poa_keys")
+        .stdin(Stdio::null())
+        .output()
+        .await
+        .map_err(|e| format!("Failed to run hathor-core at {:?}: {}", binary_path, e))?;
+    if !output.status.success() {
+        return Err(format!(
+            "Failed to generate PoA keys: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+
+    // The keys are printed as JSON, possibly after other output
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let json = stdout
+        .find('{')
+        .zip(stdout.rfind('}'))
+        .map(|(start, end)| &stdout[start..=end])
+        .ok_or("hathor-core printed no PoA keys")?;
+    let keys: Value =
+        serde_json::from_str(json).map_err(|e| format!("Failed to parse PoA keys: {}", e))?;
+    let signer: PoaSigner =
+        serde_json::from_value(keys.clone()).map_err(|e| format!("Invalid PoA keys: {}", e))?;
+    if keys["private_key_hex"].as_str().is_none() {
+        return Err("hathor-core printed no PoA private key".to_string());
+    }
+
+    let path = signer_path();
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Failed to create app directory: {}", e))?;
+    }
+    crate::write_private_file(&path, json)
+        .map_err(|e| format!("Failed to write PoA signer file: {}", e))?;
+    Ok(signer)
+}
+
+// Settings extending the localnet ones with PoA consensus and a single signer
+fn settings_yaml(signer: &PoaSigner, block_time: u64) -> String {
+    format!(
+        "extends: localnet.yml\n\
+         AVG_TIME_BETWEEN_BLOCKS: {}\n\
+         CONSENSUS_ALGORITHM:\n  \
+           type: PROOF_OF_AUTHORITY\n  \
+           signers:\n    \
+             - public_key: {}\n      \
+               start_height: 0\n",
+        block_time, signer.public_key_hex
+    )
+}
+
+/// Start the PoA node, generating the signer key on first use; logs and
+/// termination are emitted when there's an app to emit them to
+pub async fn start_poa_node_internal(
+    app: Option<AppHandle>,
+    state: &SharedState,
+    config: &PoaConfig,
+) -> Result<String, String> {
+    if state.lock().await.poa_node.child_id.is_some() {
+        return Err("PoA node is already running".to_string());
+    }
+    if config.block_time == 0 {
+        return Err("Block time must be positive".to_string());
+    }
+    if config.api_port == crate::NodeConfig::default().api_port {
+        return Err(format!(
+            "Port {} is the localnet node's; pick another for the PoA node",
+            config.api_port
+        ));
+    }
+
+    let signer = generate_poa_signer_internal(false).await?;
+    let data_dir = poa_data_dir();
+    fs::create_dir_all(&data_dir)
+        .map_err(|e| format!("Failed to create PoA data directory: {}", e))?;
+    let yaml = yaml_path();
+    fs::write(&yaml, settings_yaml(&signer, config.block_time))
+        .map_err(|e| format!("Failed to write PoA settings: {}", e))?;
+
+    crate::kill_process_on_port(config.api_port);
+    let binary_path = crate::get_binary_path("hathor-core");
+    let internal_dir = binary_path.parent().unwrap().join("_internal");
+    let mut cmd = TokioCommand::new(&binary_path);
+    crate::set_library_path_env(&mut cmd, &internal_dir);
+    cmd.args([
+        "run_node",
+        "--status",
+        &config.api_port.to_string(),
+        "--data",
+        &data_dir.to_string_lossy(),
+        "--config-yaml",
+        &yaml.to_string_lossy(),
+        "--x-poa-signer-file",
+        &signer_path().to_string_lossy(),
+        "--wallet-index",
+        "--allow-mining-without-peers",
+        "--unsafe-mode",
+        "privatenet",
+    ]);
+    if config.listen {
+        cmd.args(["--listen", &format!("tcp:{}", POA_P2P_PORT)]);
+    }
+    let mut child = cmd
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|e| format!("Failed to spawn PoA node at {:?}: {}", binary_path, e))?;
+
+    {
+        let mut state_guard = state.lock().await;
+        state_guard.poa_node = PoaNode {
+            child_id: child.id(),
+            api_port: config.api_port,
+        };
+    }
+
+    // hathor-core logs to stderr; it's drained even with no app to emit to
+    if let Some(stderr) = child.stderr.take() {
+        let log_app = app.clone();
+        tokio::spawn(async move {
+            let mut lines = BufReader::new(stderr).lines();
+            while let Ok(Some(line)) = lines.next_line().await {
+                if let Some(app) = &log_app {
+                    let _ = app.emit("poa-node-log", &line);
+                }
+            }
+        });
+    }
+
+    let exit_state = state.clone();
+    tokio::spawn(async move {
+        let code = child.wait().await.ok().and_then(|status| status.code());
+        exit_state.lock().await.poa_node = PoaNode::default();
+        if let Some(app) = app {
+            let _ = app.emit("poa-node-terminated", code);
+        }
+    });
+
+    Ok(format!("PoA node started on port {}", config.api_port))
+}
+
+/// Stop the PoA node
+pub async fn stop_poa_node_internal(state: &SharedState) -> Result<String, String> {
+    let pid = {
+        let mut state_guard = state.lock().await;
+        let pid = state_guard
+            .poa_node
+            .child_id
+            .ok_or("PoA node is not running")?;
+        state_guard.poa_node = PoaNode::default();
+        pid
+    };
+    tokio::task::spawn_blocking(move || crate::kill_process(pid))
+        .await
+        .map_err(|e| format!("Failed to stop the PoA node: {}", e))?;
+    Ok("PoA node stopped".to_string())
+}
+
+/// API port of the running PoA node
+pub fn api_port(state: &crate::AppState) -> Option<u16> {
+    state.poa_node.child_id.map(|_| state.poa_node.api_port)
+}
+
+/// PID of the running PoA node, for cleanup on exit
+pub fn child_id(state: &crate::AppState) -> Option<u32> {
+    state.poa_node.child_id
+}
+
+pub async fn get_poa_status_internal(state: &SharedState) -> PoaStatus {
+    let (child_id, api_port) = {
+        let state_guard = state.lock().await;
+        (state_guard.poa_node.child_id, state_guard.poa_node.api_port)
+    };
+    let running = child_id.is_some();
+
+    let mut best_block_height = None;
+    if running {
+        if let Ok(response) = reqwest::Client::new()
+            .get(format!("http://127.0.0.1:{}/v1a/status/", api_port))
+            .send()
+            .await
+        {
+            if let Ok(status) = response.json::<Value>().await {
+                best_block_height = status["dag"]["best_block"]["height"].as_u64();
+            }
+        }
+    }
+
+    PoaStatus {
+        running,
+        api_port: running.then_some(api_port),
+        data_dir: poa_data_dir().to_string_lossy().to_string(),
+        signer: load_signer(),
+        best_block_height,
+    }
+}
+
+// ============================================================================
+// Tauri Commands
+// ============================================================================
+
+// Generate the PoA signer key (kept unless force is set)
+#[tauri::command]
+pub async fn generate_poa_signer(force: Option<bool>) -> Result<PoaSigner, String> {
+    generate_poa_signer_internal(force.unwrap_or(false)).await
+}
+
+// Start the PoA side-DAG node
+#[tauri::command]
+pub async fn start_poa_node(
+    app: AppHandle,
+    state: tauri::State<'_, SharedState>,
+    config: Option<PoaConfig>,
+) -> Result<String, String> {
+    start_poa_node_internal(Some(app), &state, &config.unwrap_or_default()).await
+}
+
+// Stop the PoA side-DAG node
+#[tauri::command]
+pub async fn stop_poa_node(state: tauri::State<'_, SharedState>) -> Result<String, String> {
+    stop_poa_node_internal(&state).await
+}
+
+// Get the PoA node's state, signer and best block height
+#[tauri::command]
+pub async fn get_poa_status(state: tauri::State<'_, SharedState>) -> Result<PoaStatus, String> {
+    Ok(get_poa_status_internal(&state).await)
+}
@@ -0,0 +1,1173 @@
+//! Explorer server proxy to the fullnode
+//!
+//! The explorer server forwards `/v1a/*` requests and the `/v1a/ws/`
+//! WebSocket to the node, so the bundled explorer (and, when shared, other
+//! machines) reach it through a single origin. HTTP requests share one pooled
+//! client kept in the router state, so connections to the node are reused
+//! across requests. Bodies are streamed in both directions rather than
+//! buffered, so large and long-running responses pass through unchanged.
+//! The client's `Accept-Encoding` goes to the node as is, and an encoded
+//! response keeps its `Content-Encoding`; the explorer server only compresses
+//! responses that aren't encoded yet.
+//!
+//! The upstream is picked with `set_proxy_upstream` (stored in `proxy.json`
+//! in the app directory): the localnet node on whatever port it was started
+//! with (the default), the PoA side-DAG node, or a remote node by URL. It is
+//! resolved per request, so switching takes effect without restarting the
+//! explorer server. Additional explorer servers can be started with a target
+//! of their own, which they keep regardless of that setting.
+//!
+//! `/headless/*` goes to wallet-headless the same way (the default instance,
+//! or the one named by an `x-headless-instance` header), so a dApp under
+//! development needs a single origin for both the node and the wallet
+//! service. When an API key named `wallet-headless` is stored in the
+//! keychain (see secrets.rs), it's sent as `X-API-KEY`. `/explorer-api/*`
+//! likewise goes to explorer-service when Forge runs it.
+//!
+//! A proxied WebSocket outlives the node connection behind it: when the node
+//! drops it (e.g. on a restart), the proxy reconnects with backoff for up to
+//! a minute, replays the client's address subscriptions and sends what the
+//! client wrote meanwhile. `proxy-ws-reconnecting`, `proxy-ws-reconnected`
+//! and `proxy-ws-lost` (with the attempt number) let the app show it.
+//! Open connections are tracked in the proxy metrics (`get_proxy_stats`), and
+//! stuck ones can be closed with `close_proxy_ws`.
+//!
+//! Immutable chain queries are answered from an LRU cache while the proxy
+//! targets the localnet node (whose event queue tells when they go stale):
+//! `transaction?id=` of confirmed transactions and buried blocks,
+//! `block_at_height` of buried heights, and `dashboard_tx`. Dashboard entries
+//! are dropped on every new vertex, and the whole cache on a reorg, a voided
+//! transaction, a block at or below the known tip (another chain) or a node
+//! restart (which a data reset or a snapshot restore goes through). Cached
+//! responses are buffered; everything else streams.
+//!
+//! Each request is recorded in the proxy metrics: request rate and upstream
+//! latency (time to the node's response headers) over the last minute, plus
+//! running totals that survive explorer restarts.
+
+use axum::body::{Body, Bytes};
+use axum::extract::ws::{Message, WebSocket, WebSocketUpgrade};
+use axum::extract::{Path, Request, State};
+use axum::response::Response;
+use axum::routing::{any, get};
+use axum::Router;
+use futures_util::{SinkExt, StreamExt};
+use lru::LruCache;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+use std::num::NonZeroUsize;
+use std::sync::{Arc, Mutex as StdMutex};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter};
+use tokio::sync::{broadcast, Notify};
+use tokio_tungstenite::tungstenite;
+use tracing::Instrument;
+
+use crate::access_log::{AccessError, Upstream};
+use crate::events::NodeEvent;
+use crate::settings::JsonSettings;
+use crate::{AppState, SharedState};
+
+// Idle connections kept open to the node
+const POOL_MAX_IDLE: usize = 32;
+const POOL_IDLE_TIMEOUT: Duration = Duration::from_secs(90);
+const TCP_KEEPALIVE: Duration = Duration::from_secs(60);
+// Span of the rate and latency figures
+const METRICS_WINDOW: Duration = Duration::from_secs(60);
+const MAX_SAMPLES: usize = 10_000;
+const CACHE_CAPACITY: usize = 1_000;
+// Larger responses are streamed instead of cached
+const MAX_CACHED_BODY: u64 = 256 * 1024;
+// Blocks this far below the best one are considered final
+const CONFIRMED_DEPTH: u64 = 6;
+// Backoff while reconnecting a WebSocket to the node, and when to give up
+const WS_RECONNECT_MIN_DELAY: Duration = Duration::from_millis(500);
+const WS_RECONNECT_MAX_DELAY: Duration = Duration::from_secs(5);
+const WS_RECONNECT_TIMEOUT: Duration = Duration::from_secs(60);
+const WS_MAX_PENDING: usize = 100;
+// Picks the wallet-headless instance behind /headless
+const HEADLESS_INSTANCE_HEADER: &str = "x-headless-instance";
+// Keychain API key sent to wallet-headless, for dists configured to require one
+const HEADLESS_API_KEY_NAME: &str = "wallet-headless";
+// Close codes (RFC 6455) recorded when a client leaves without one
+const CLOSE_GOING_AWAY: u16 = 1001;
+const CLOSE_NO_STATUS: u16 = 1005;
+const CLOSE_ABNORMAL: u16 = 1006;
+const CLOSE_INTERNAL_ERROR: u16 = 1011;
+
+/// Proxy counters, kept in `AppState` and shared with the running server
+#[derive(Default)]
+pub struct ProxyMetrics {
+    total_requests: u64,
+    failed_requests: u64,
+    cache_hits: u64,
+    // Completion time and upstream latency of recent requests
+    recent: VecDeque<(Instant, Duration)>,
+    // Proxied WebSockets
+    ws_next_id: u64,
+    ws_connections: BTreeMap<u64, WsConnection>,
+    ws_recent_messages: VecDeque<Instant>,
+    ws_close_codes: BTreeMap<u16, u64>,
+}
+
+// A proxied WebSocket while it's open
+struct WsConnection {
+    opened_at: u64,
+    last_message: Instant,
+    messages_to_node: u64,
+    messages_to_client: u64,
+    reconnects: u32,
+    reconnecting: bool,
+    // Notified to force-close the connection
+    closer: Arc<Notify>,
+}
+
+pub type SharedProxyMetrics = Arc<StdMutex<ProxyMetrics>>;
+
+impl ProxyMetrics {
+    fn prune(&mut self, now: Instant) {
+        while self
+            .recent
+            .front()
+            .is_some_and(|(at, _)| now.duration_since(*at) > METRICS_WINDOW)
+        {
+            self.recent.pop_front();
+        }
+        while self.recent.len() > MAX_SAMPLES {
+            self.recent.pop_front();
+        }
+        while self
+            .ws_recent_messages
+            .front()
+            .is_some_and(|at| now.duration_since(*at) > METRICS_WINDOW)
+        {
+            self.ws_recent_messages.pop_front();
+        }
+        while self.ws_recent_messages.len() > MAX_SAMPLES {
+            self.ws_recent_messages.pop_front();
+        }
+    }
+
+    fn record(&mut self, latency: Duration, failed: bool) {
+        let now = Instant::now();
+        self.total_requests += 1;
+        if failed {
+            self.failed_requests += 1;
+        }
+        self.recent.push_back((now, latency));
+        self.prune(now);
+    }
+
+    fn ws_opened(&mut self) -> (u64, Arc<Notify>) {
+        self.ws_next_id += 1;
+        let closer = Arc::new(Notify::new());
+        let now = Instant::now();
+        self.ws_connections.insert(
+            self.ws_next_id,
+            WsConnection {
+                opened_at: crate::unix_now(),
+                last_message: now,
+                messages_to_node: 0,
+                messages_to_client: 0,
+                reconnects: 0,
+                reconnecting: false,
+                closer: closer.clone(),
+            },
+        );
+        (self.ws_next_id, closer)
+    }
+
+    fn ws_message(&mut self, id: u64, to_client: bool) {
+        let now = Instant::now();
+        if let Some(connection) = self.ws_connections.get_mut(&id) {
+            connection.last_message = now;
+            if to_client {
+                connection.messages_to_client += 1;
+            } else {
+                connection.messages_to_node += 1;
+            }
+        }
+        self.ws_recent_messages.push_back(now);
+        self.prune(now);
+    }
+
+    fn ws_reconnecting(&mut self, id: u64, reconnecting: bool) {
+        if let Some(connection) = self.ws_connections.get_mut(&id) {
+            if connection.reconnecting && !reconnecting {
+                connection.reconnects += 1;
+            }
+            connection.reconnecting = reconnecting;
+        }
+    }
+
+    fn ws_closed(&mut self, id: u64, code: u16) {
+        self.ws_connections.remove(&id);
+        *self.ws_close_codes.entry(code).or_default() += 1;
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WsConnectionInfo {
+    pub id: u64,
+    pub opened_at: u64,
+    pub idle_secs: u64,
+    pub messages_to_node: u64,
+    pub messages_to_client: u64,
+    pub reconnects: u32,
+    pub reconnecting: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WsProxyStats {
+    pub active_connections: usize,
+    // Since the app started
+    pub total_connections: u64,
+    // Both directions, over the last `window_secs`
+    pub messages_per_sec: f64,
+    // How closed connections ended, by close code
+    pub close_codes: BTreeMap<u16, u64>,
+    pub connections: Vec<WsConnectionInfo>,
+    pub window_secs: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProxyMetricsReport {
+    pub total_requests: u64,
+    pub failed_requests: u64,
+    // Answered from the proxy cache (not counted in the figures above)
+    pub cache_hits: u64,
+    // Over the last `window_secs`
+    pub requests_per_sec: f64,
+    pub avg_latency_ms: Option<f64>,
+    pub p95_latency_ms: Option<f64>,
+    pub window_secs: u64,
+}
+
+/// Which node the proxy forwards to
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ProxyTarget {
+    // The localnet node, on the port it was started with
+    #[default]
+    Node,
+    // The PoA side-DAG node (see poa.rs)
+    Poa,
+    // Any node, by its base URL (e.g. https://node1.testnet.hathor.network)
+    Remote {
+        url: String,
+    },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProxyUpstream {
+    pub target: ProxyTarget,
+    // Base URL requests currently go to (None when the target isn't running)
+    pub url: Option<String>,
+}
+
+static SETTINGS: JsonSettings<ProxyTarget> = JsonSettings::new("proxy.json", "proxy settings");
+
+// "https://host/v1a/" -> "https://host"
+fn normalize_url(url: &str) -> Result<String, String> {
+    let url = url.trim().trim_end_matches('/');
+    let url = url.strip_suffix("/v1a").unwrap_or(url);
+    if !(url.starts_with("http://") || url.starts_with("https://"))
+        || url.contains(char::is_whitespace)
+    {
+        return Err(format!("Invalid node URL '{}'", url));
+    }
+    Ok(url.to_string())
+}
+
+/// Base URL of the target, if it can be reached
+pub fn resolve(target: &ProxyTarget, state: &AppState) -> Option<String> {
+    match target {
+        ProxyTarget::Node => Some(format!("http://127.0.0.1:{}", state.node_api_port)),
+        ProxyTarget::Poa => {
+            crate::poa::api_port(state).map(|port| format!("http://127.0.0.1:{}", port))
+        }
+        ProxyTarget::Remote { url } => Some(url.clone()),
+    }
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum CacheKind {
+    Transaction,
+    BlockAtHeight(u64),
+    Dashboard,
+}
+
+impl CacheKind {
+    fn of(url: &str) -> Option<Self> {
+        let url = reqwest::Url::parse(url).ok()?;
+        let param = |name: &str| {
+            url.query_pairs()
+                .find(|(key, _)| key == name)
+                .map(|(_, value)| value.into_owned())
+        };
+        match url.path().trim_end_matches('/') {
+            "/v1a/transaction" => param("id").map(|_| CacheKind::Transaction),
+            "/v1a/block_at_height" => param("height")
+                .and_then(|height| height.parse().ok())
+                .map(CacheKind::BlockAtHeight),
+            "/v1a/dashboard_tx" => Some(CacheKind::Dashboard),
+            _ => None,
+        }
+    }
+}
+
+struct CachedResponse {
+    kind: CacheKind,
+    status: u16,
+    headers: Vec<(axum::http::HeaderName, axum::http::HeaderValue)>,
+    body: Bytes,
+}
+
+impl CachedResponse {
+    fn response(&self) -> Response {
+        let mut builder = Response::builder().status(self.status);
+        for (name, value) in &self.headers {
+            builder = builder.header(name, value);
+        }
+        builder.body(Body::from(self.body.clone())).unwrap()
+    }
+}
+
+/// Responses to immutable queries, keyed by upstream URL and kept while the
+/// explorer server runs
+struct ProxyCache {
+    responses: LruCache<String, CachedResponse>,
+    // Highest block seen on the event queue, to tell buried heights
+    best_height: Option<u64>,
+    // PID of the node the responses came from
+    node: Option<u32>,
+    node_events: broadcast::Receiver<NodeEvent>,
+}
+
+impl ProxyCache {
+    fn new(node_events: broadcast::Receiver<NodeEvent>) -> Self {
+        Self {
+            responses: LruCache::new(NonZeroUsize::new(CACHE_CAPACITY).unwrap()),
+            best_height: None,
+            node: None,
+            node_events,
+        }
+    }
+
+    fn clear(&mut self) {
+        self.responses.clear();
+        self.best_height = None;
+    }
+
+    // Start over when the node process changed: it may be on another chain
+    fn check_node(&mut self, node: Option<u32>) {
+        if self.node != node {
+            self.clear();
+            self.node = node;
+        }
+    }
+
+    // Apply the node events received since the last access
+    fn invalidate(&mut self) {
+        loop {
+            match self.node_events.try_recv() {
+                Ok(NodeEvent::NewVertex {
+                    is_block, height, ..
+                }) => {
+                    // A block at or below the tip without a reorg is another chain
+                    if is_block && height.is_some_and(|h| self.best_height >= Some(h)) {
+                        self.clear();
+                    }
+                    if is_block {
+                        self.best_height = self.best_height.max(height);
+                    }
+                    let stale: Vec<String> = self
+                        .responses
+                        .iter()
+                        .filter(|(_, cached)| cached.kind == CacheKind::Dashboard)
+                        .map(|(key, _)| key.clone())
+                        .collect();
+                    for key in stale {
+                        self.responses.pop(&key);
+                    }
+                }
+                Ok(NodeEvent::ReorgStarted { .. })
+                | Err(broadcast::error::TryRecvError::Lagged(_)) => self.clear(),
+                // Voiding spreads to descendants; don't try to follow it
+                Ok(NodeEvent::TxVoided { .. }) => self.responses.clear(),
+                Ok(NodeEvent::ReorgFinished { .. }) => {}
+                Err(_) => break,
+            }
+        }
+    }
+
+    fn buried(&self, height: u64) -> bool {
+        self.best_height
+            .is_some_and(|best| height + CONFIRMED_DEPTH <= best)
+    }
+
+    // Whether a successful response may be kept
+    fn cacheable(&self, kind: CacheKind, body: &[u8]) -> bool {
+        let Ok(json) = serde_json::from_slice::<serde_json::Value>(body) else {
+            return false;
+        };
+        if !json["success"].as_bool().unwrap_or(false) {
+            return false;
+        }
+        match kind {
+            CacheKind::Transaction => {
+                let meta = &json["meta"];
+                let voided = meta["voided_by"]
+                    .as_array()
+                    .is_some_and(|hashes| !hashes.is_empty());
+                let is_block = matches!(json["tx"]["version"].as_u64(), Some(0) | Some(3));
+                let settled = if is_block {
+                    meta["height"].as_u64().is_some_and(|h| self.buried(h))
+                } else {
+                    meta["first_block"].is_string()
+                };
+                settled && !voided
+            }
+            CacheKind::BlockAtHeight(height) => self.buried(height),
+            CacheKind::Dashboard => true,
+        }
+    }
+}
+
+/// Router state: the pooled client, the upstream selection, the response
+/// cache and where to record metrics
+#[derive(Clone)]
+pub struct ProxyState {
+    app: AppHandle,
+    client: reqwest::Client,
+    app_state: SharedState,
+    cache: Arc<StdMutex<ProxyCache>>,
+    metrics: SharedProxyMetrics,
+    // The explorer server's own upstream; None follows `proxy_target`
+    target: Option<ProxyTarget>,
+}
+
+impl ProxyState {
+    pub fn new(
+        app: AppHandle,
+        app_state: SharedState,
+        state: &AppState,
+        target: Option<ProxyTarget>,
+    ) -> Result<Self, String> {
+        let client = reqwest::Client::builder()
+            .pool_max_idle_per_host(POOL_MAX_IDLE)
+            .pool_idle_timeout(POOL_IDLE_TIMEOUT)
+            .tcp_keepalive(TCP_KEEPALIVE)
+            .build()
+            .map_err(|e| format!("Failed to build proxy client: {}", e))?;
+        Ok(Self {
+            app,
+            client,
+            app_state,
+            cache: Arc::new(StdMutex::new(ProxyCache::new(
+                state.node_events.subscribe(),
+            ))),
+            metrics: state.proxy_metrics.clone(),
+            target,
+        })
+    }
+
+    // Base URL of the current upstream, and whether its responses may be
+    // cached (only the localnet node's events are followed)
+    async fn upstream(&self) -> Result<(String, bool), String> {
+        let state = self.app_state.lock().await;
+        let target = self.target.as_ref().unwrap_or(&state.proxy_target);
+        if *target == ProxyTarget::Node {
+            self.cache
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .check_node(state.node_child_id);
+        }
+        resolve(target, &state)
+            .map(|url| (url, *target == ProxyTarget::Node))
+            .ok_or_else(|| "The proxy's upstream node is not running".to_string())
+    }
+
+    async fn ws_url(&self) -> Result<String, String> {
+        let (upstream, _) = self.upstream().await?;
+        // http(s)://host -> ws(s)://host/v1a/ws/
+        Ok(format!("ws{}/v1a/ws/", upstream.trim_start_matches("http")))
+    }
+
+    fn cache(&self) -> std::sync::MutexGuard<'_, ProxyCache> {
+        let mut cache = self.cache.lock().unwrap_or_else(|e| e.into_inner());
+        cache.invalidate();
+        cache
+    }
+
+    fn metrics(&self) -> std::sync::MutexGuard<'_, ProxyMetrics> {
+        self.metrics.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn record(&self, latency: Duration, failed: bool) {
+        self.metrics().record(latency, failed);
+    }
+}
+
+/// Routes proxied to the node
+pub fn router(state: ProxyState) -> Router {
+    Router::new()
+        .route("/v1a/ws/", get(proxy_ws))
+        .route("/v1a/*path", any(proxy_api))
+        .route("/headless/*path", any(proxy_headless))
+        .route("/explorer-api/*path", any(proxy_explorer_service))
+        .with_state(state)
+}
+
+// The message also goes to the access log
+fn error_response(status: u16, message: String) -> Response {
+    let mut response = Response::builder()
+        .status(status)
+        .body(Body::from(message.clone()))
+        .unwrap();
+    response.extensions_mut().insert(AccessError(message));
+    response
+}
+
+// Proxy HTTP requests to the fullnode
+async fn proxy_api(
+    State(proxy): State<ProxyState>,
+    Path(path): Path<String>,
+    req: Request,
+) -> Response {
+    let (upstream, cache_enabled) = match proxy.upstream().await {
+        Ok(upstream) => upstream,
+        Err(e) => return error_response(502, e),
+    };
+    let url = format!("{}/v1a/{}", upstream, path);
+    let mut response = forward(&proxy, url, cache_enabled, None, req).await;
+    if response.extensions().get::<Upstream>().is_none() {
+        response.extensions_mut().insert(Upstream(upstream));
+    }
+    response
+}
+
+// Proxy HTTP requests to wallet-headless (the default instance, or the one
+// named by the x-headless-instance header)
+async fn proxy_headless(
+    State(proxy): State<ProxyState>,
+    Path(path): Path<String>,
+    req: Request,
+) -> Response {
+    let instance = req
+        .headers()
+        .get(HEADLESS_INSTANCE_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .map(|value| value.to_string());
+    let headless = match crate::headless_url(&proxy.app_state, instance.as_deref()).await {
+        Ok(url) => url,
+        Err(e) => return error_response(502, e),
+    };
+    let api_key = crate::secrets::load_api_key(HEADLESS_API_KEY_NAME)
+        .await
+        .ok()
+        .flatten();
+
+    let url = format!("{}/{}", headless, path);
+    let mut response = forward(&proxy, url, false, api_key, req).await;
+    response.extensions_mut().insert(Upstream(headless));
+    response
+}
+
+// Proxy explorer-service, when Forge runs one (see explorer_service.rs)
+async fn proxy_explorer_service(
+    State(proxy): State<ProxyState>,
+    Path(path): Path<String>,
+    req: Request,
+) -> Response {
+    let Some(upstream) = crate::explorer_service::url(&*proxy.app_state.lock().await) else {
+        return error_response(502, "explorer-service is not running".to_string());
+    };
+    let url = format!("{}/{}", upstream, path);
+    let mut response = forward(&proxy, url, false, None, req).await;
+    response.extensions_mut().insert(Upstream(upstream));
+    response
+}
+
+// Forward a request to `url` (plus the request's query string), caching
+// immutable node queries when `cache_enabled` and adding `api_key` as
+// X-API-KEY
+async fn forward(
+    proxy: &ProxyState,
+    url: String,
+    cache_enabled: bool,
+    api_key: Option<String>,
+    req: Request,
+) -> Response {
+    // Include query string if present
+    let query = req
+        .uri()
+        .query()
+        .map(|q| format!("?{}", q))
+        .unwrap_or_default();
+    let url = format!("{}{}", url, query);
+
+    let method = reqwest::Method::from_bytes(req.method().as_str().as_bytes())
+        .unwrap_or(reqwest::Method::GET);
+    let cache_kind = if cache_enabled && method == reqwest::Method::GET {
+        CacheKind::of(&url)
+    } else {
+        None
+    };
+    if cache_kind.is_some() {
+        if let Some(cached) = proxy.cache().responses.get(&url) {
+            let mut response = cached.response();
+            proxy.metrics().cache_hits += 1;
+            response
+                .extensions_mut()
+                .insert(Upstream("cache".to_string()));
+            return response;
+        }
+    }
+    let mut builder = proxy.client.request(method.clone(), &url);
+
+    // Forward headers (except host)
+    for (name, value) in req.headers().iter() {
+        if name != "host" {
+            if let Ok(header_name) = reqwest::header::HeaderName::try_from(name.as_str()) {
+                if let Ok(header_value) = reqwest::header::HeaderValue::from_bytes(value.as_bytes())
+                {
+                    builder = builder.header(header_name, header_value);
+                }
+            }
+        }
+    }
+    if let Some(api_key) = api_key {
+        builder = builder.header("x-api-key", api_key);
+    }
+
+    // Stream the body for POST/PUT/PATCH
+    if method == reqwest::Method::POST
+        || method == reqwest::Method::PUT
+        || method == reqwest::Method::PATCH
+    {
+        builder = builder.body(reqwest::Body::wrap_stream(
+            req.into_body().into_data_stream(),
+        ));
+    }
+
+    // Make the request
+    let started = Instant::now();
+    let span = crate::telemetry::http_span(method.as_str(), &url);
+    let response = match builder.send().instrument(span.clone()).await {
+        Ok(response) => response,
+        Err(e) => {
+            proxy.record(started.elapsed(), true);
+            return error_response(502, format!("Failed to connect to {}: {}", url, e));
+        }
+    };
+    let status = response.status();
+    span.record("http.response.status_code", status.as_u16());
+    proxy.record(started.elapsed(), status.is_server_error());
+
+    // Forward response headers
+    let headers: Vec<_> = response
+        .headers()
+        .iter()
+        .filter_map(|(name, value)| {
+            Some((
+                axum::http::HeaderName::try_from(name.as_str()).ok()?,
+                axum::http::HeaderValue::from_bytes(value.as_bytes()).ok()?,
+            ))
+        })
+        .collect();
+
+    // Buffer responses that may be cached; encoded ones would only suit
+    // clients sending the same Accept-Encoding
+    if let Some(kind) = cache_kind.filter(|_| {
+        status == reqwest::StatusCode::OK
+            && !response
+                .headers()
+                .contains_key(reqwest::header::CONTENT_ENCODING)
+            && response
+                .content_length()
+                .is_some_and(|len| len <= MAX_CACHED_BODY)
+    }) {
+        let body = match response.bytes().await {
+            Ok(body) => body,
+            Err(e) => {
+                return error_response(502, format!("Failed to read response from {}: {}", url, e))
+            }
+        };
+        let cached = CachedResponse {
+            kind,
+            status: status.as_u16(),
+            headers,
+            body,
+        };
+        let response = cached.response();
+        let mut cache = proxy.cache();
+        if cache.cacheable(kind, &cached.body) {
+            cache.responses.put(url, cached);
+        }
+        return response;
+    }
+
+    let mut builder = Response::builder().status(status.as_u16());
+    for (name, value) in headers {
+        builder = builder.header(name, value);
+    }
+    builder
+        .body(Body::from_stream(response.bytes_stream()))
+        .unwrap()
+}
+
+// Proxy WebSocket connections to the fullnode
+async fn proxy_ws(State(proxy): State<ProxyState>, ws: WebSocketUpgrade) -> Response {
+    let fullnode_url = match proxy.ws_url().await {
+        Ok(url) => url,
+        Err(e) => return error_response(502, e),
+    };
+    let mut response = ws.on_upgrade({
+        let fullnode_url = fullnode_url.clone();
+        move |socket| handle_ws_proxy(socket, proxy, fullnode_url)
+    });
+    response.extensions_mut().insert(Upstream(fullnode_url));
+    response
+}
+
+type UpstreamWs =
+    tokio_tungstenite::WebSocketStream<tokio_tungstenite::MaybeTlsStream<tokio::net::TcpStream>>;
+
+// The message for the node, or the close code once the client is gone
+fn from_client(msg: Option<Result<Message, axum::Error>>) -> Result<tungstenite::Message, u16> {
+    match msg {
+        Some(Ok(Message::Text(text))) => Ok(tungstenite::Message::Text(text)),
+        Some(Ok(Message::Binary(data))) => Ok(tungstenite::Message::Binary(data)),
+        Some(Ok(Message::Ping(data))) => Ok(tungstenite::Message::Ping(data)),
+        Some(Ok(Message::Pong(data))) => Ok(tungstenite::Message::Pong(data)),
+        Some(Ok(Message::Close(frame))) => Err(frame.map_or(CLOSE_NO_STATUS, |frame| frame.code)),
+        Some(Err(_)) | None => Err(CLOSE_ABNORMAL),
+    }
+}
+
+fn to_client(msg: tungstenite::Message) -> Option<Message> {
+    match msg {
+        tungstenite::Message::Text(text) => Some(Message::Text(text)),
+        tungstenite::Message::Binary(data) => Some(Message::Binary(data)),
+        tungstenite::Message::Ping(data) => Some(Message::Ping(data)),
+        tungstenite::Message::Pong(data) => Some(Message::Pong(data)),
+        _ => None,
+    }
+}
+
+fn close_frame(code: u16, reason: String) -> Message {
+    Message::Close(Some(axum::extract::ws::CloseFrame {
+        code,
+        reason: reason.into(),
+    }))
+}
+
+// Follow the client's address subscriptions, to replay them on reconnection
+fn track_subscription(subscriptions: &mut BTreeSet<String>, msg: &tungstenite::Message) {
+    let tungstenite::Message::Text(text) = msg else {
+        return;
+    };
+    let Ok(msg) = serde_json::from_str::<serde_json::Value>(text) else {
+        return;
+    };
+    match (msg["type"].as_str(), msg["address"].as_str()) {
+        (Some("subscribe_address"), Some(address)) => {
+            subscriptions.insert(address.to_string());
+        }
+        (Some("unsubscribe_address"), Some(address)) => {
+            subscriptions.remove(address);
+        }
+        _ => {}
+    }
+}
+
+// Connect to the node again after losing it (e.g. it restarted), with
+// backoff; the upstream is resolved on each attempt, so a new port or
+// target is followed
+async fn reconnect(proxy: &ProxyState, id: u64) -> Option<UpstreamWs> {
+    proxy.metrics().ws_reconnecting(id, true);
+    let deadline = Instant::now() + WS_RECONNECT_TIMEOUT;
+    let mut delay = WS_RECONNECT_MIN_DELAY;
+    let mut attempt: u32 = 0;
+    loop {
+        attempt += 1;
+        let _ = proxy.app.emit("proxy-ws-reconnecting", attempt);
+        tokio::time::sleep(delay).await;
+        if let Ok(url) = proxy.ws_url().await {
+            if let Ok((stream, _)) = tokio_tungstenite::connect_async(&url).await {
+                proxy.metrics().ws_reconnecting(id, false);
+                let _ = proxy.app.emit("proxy-ws-reconnected", attempt);
+                return Some(stream);
+            }
+        }
+        if Instant::now() + delay >= deadline {
+            let _ = proxy.app.emit("proxy-ws-lost", attempt);
+            return None;
+        }
+        delay = (delay * 2).min(WS_RECONNECT_MAX_DELAY);
+    }
+}
+
+async fn handle_ws_proxy(client_ws: WebSocket, proxy: ProxyState, fullnode_url: String) {
+    let (id, closer) = proxy.metrics().ws_opened();
+    let code = forward_ws(client_ws, &proxy, id, &closer, fullnode_url).await;
+    proxy.metrics().ws_closed(id, code);
+}
+
+// Forward a client's WebSocket to the node; returns how it was closed
+async fn forward_ws(
+    client_ws: WebSocket,
+    proxy: &ProxyState,
+    id: u64,
+    closer: &Notify,
+    fullnode_url: String,
+) -> u16 {
+    let (mut client_sink, mut client_stream) = client_ws.split();
+    let force_closed = || close_frame(CLOSE_GOING_AWAY, "Closed from Hathor Forge".to_string());
+
+    // Connect to fullnode WebSocket
+    let mut upstream = match tokio_tungstenite::connect_async(&fullnode_url).await {
+        Ok((stream, _)) => stream,
+        Err(e) => {
+            let _ = client_sink
+                .send(close_frame(
+                    CLOSE_INTERNAL_ERROR,
+                    format!("Failed to connect to fullnode: {}", e),
+                ))
+                .await;
+            return CLOSE_INTERNAL_ERROR;
+        }
+    };
+    let mut subscriptions = BTreeSet::new();
+    // Client messages that couldn't reach the node, sent once reconnected
+    let mut pending: VecDeque<tungstenite::Message> = VecDeque::new();
+
+    loop {
+        // Forward messages both ways until the node connection drops
+        loop {
+            tokio::select! {
+                msg = client_stream.next() => {
+                    let msg = match from_client(msg) {
+                        Ok(msg) => msg,
+                        Err(code) => return code,
+                    };
+                    track_subscription(&mut subscriptions, &msg);
+                    if upstream.send(msg.clone()).await.is_err() {
+                        if msg.is_text() || msg.is_binary() {
+                            pending.push_back(msg);
+                        }
+                        break;
+                    }
+                    proxy.metrics().ws_message(id, false);
+                }
+                msg = upstream.next() => {
+                    let Some(Ok(msg)) = msg else {
+                        break;
+                    };
+                    if let Some(msg) = to_client(msg) {
+                        if client_sink.send(msg).await.is_err() {
+                            return CLOSE_ABNORMAL;
+                        }
+                        proxy.metrics().ws_message(id, true);
+                    }
+                }
+                _ = closer.notified() => {
+                    let _ = client_sink.send(force_closed()).await;
+                    return CLOSE_GOING_AWAY;
+                }
+            }
+        }
+
+        // Reconnect, buffering what the client sends meanwhile
+        let reconnecting = reconnect(proxy, id);
+        tokio::pin!(reconnecting);
+        let reconnected = loop {
+            tokio::select! {
+                stream = &mut reconnecting => break stream,
+                msg = client_stream.next() => {
+                    let msg = match from_client(msg) {
+                        Ok(msg) => msg,
+                        Err(code) => return code,
+                    };
+                    track_subscription(&mut subscriptions, &msg);
+                    if msg.is_text() || msg.is_binary() {
+                        if pending.len() >= WS_MAX_PENDING {
+                            pending.pop_front();
+                        }
+                        pending.push_back(msg);
+                    }
+                }
+                _ = closer.notified() => {
+                    let _ = client_sink.send(force_closed()).await;
+                    return CLOSE_GOING_AWAY;
+                }
+            }
+        };
+        let Some(stream) = reconnected else {
+            let _ = client_sink
+                .send(close_frame(
+                    CLOSE_INTERNAL_ERROR,
+                    "Lost connection to fullnode".to_string(),
+                ))
+                .await;
+            return CLOSE_INTERNAL_ERROR;
+        };
+        upstream = stream;
+
+        // The new connection starts with no subscriptions; a failed send
+        // shows up as a dropped connection on the next read
+        let resubscribe = subscriptions.iter().map(|address| {
+            tungstenite::Message::Text(
+                serde_json::json!({ "type": "subscribe_address", "address": address }).to_string(),
+            )
+        });
+        for msg in resubscribe.chain(pending.drain(..)).collect::<Vec<_>>() {
+            if upstream.send(msg).await.is_err() {
+                break;
+            }
+        }
+    }
+}
+
+/// The proxy's upstream selection and where it currently points
+pub async fn get_proxy_upstream_internal(state: &SharedState) -> ProxyUpstream {
+    let state_guard = state.lock().await;
+    ProxyUpstream {
+        target: state_guard.proxy_target.clone(),
+        url: resolve(&state_guard.proxy_target, &state_guard),
+    }
+}
+
+/// Point the proxy at another node; applies to new requests and WebSocket
+/// connections right away
+pub async fn set_proxy_upstream_internal(
+    state: &SharedState,
+    target: ProxyTarget,
+) -> Result<ProxyUpstream, String> {
+    let target = validate_target(target)?;
+    SETTINGS.save(&target)?;
+    state.lock().await.proxy_target = target;
+    Ok(get_proxy_upstream_internal(state).await)
+}
+
+/// The target with a remote URL normalized, or an error for an invalid one
+pub fn validate_target(target: ProxyTarget) -> Result<ProxyTarget, String> {
+    Ok(match target {
+        ProxyTarget::Remote { url } => ProxyTarget::Remote {
+            url: normalize_url(&url)?,
+        },
+        target => target,
+    })
+}
+
+/// Stored upstream selection, for `AppState`
+pub fn stored_target() -> ProxyTarget {
+    SETTINGS.load()
+}
+
+/// Request totals, and rate and latency over the last minute
+pub async fn get_proxy_metrics_internal(state: &SharedState) -> ProxyMetricsReport {
+    let metrics = state.lock().await.proxy_metrics.clone();
+    let mut metrics = metrics.lock().unwrap_or_else(|e| e.into_inner());
+    metrics.prune(Instant::now());
+
+    let mut latencies: Vec<f64> = metrics
+        .recent
+        .iter()
+        .map(|(_, latency)| latency.as_secs_f64() * 1000.0)
+        .collect();
+    latencies.sort_by(f64::total_cmp);
+    let avg_latency_ms =
+        (!latencies.is_empty()).then(|| latencies.iter().sum::<f64>() / latencies.len() as f64);
+    let p95_latency_ms = latencies
+        .get((latencies.len() * 95 / 100).min(latencies.len().saturating_sub(1)))
+        .copied();
+
+    ProxyMetricsReport {
+        total_requests: metrics.total_requests,
+        failed_requests: metrics.failed_requests,
+        cache_hits: metrics.cache_hits,
+        requests_per_sec: latencies.len() as f64 / METRICS_WINDOW.as_secs_f64(),
+        avg_latency_ms,
+        p95_latency_ms,
+        window_secs: METRICS_WINDOW.as_secs(),
+    }
+}
+
+/// Open proxied WebSockets, message rate and how past ones were closed
+pub async fn get_proxy_stats_internal(state: &SharedState) -> WsProxyStats {
+    let metrics = state.lock().await.proxy_metrics.clone();
+    let mut metrics = metrics.lock().unwrap_or_else(|e| e.into_inner());
+    let now = Instant::now();
+    metrics.prune(now);
+
+    WsProxyStats {
+        active_connections: metrics.ws_connections.len(),
+        total_connections: metrics.ws_next_id,
+        messages_per_sec: metrics.ws_recent_messages.len() as f64 / METRICS_WINDOW.as_secs_f64(),
+        close_codes: metrics.ws_close_codes.clone(),
+        connections: metrics
+            .ws_connections
+            .iter()
+            .map(|(id, connection)| WsConnectionInfo {
+                id: *id,
+                opened_at: connection.opened_at,
+                idle_secs: now.duration_since(connection.last_message).as_secs(),
+                messages_to_node: connection.messages_to_node,
+                messages_to_client: connection.messages_to_client,
+                reconnects: connection.reconnects,
+                reconnecting: connection.reconnecting,
+            })
+            .collect(),
+        window_secs: METRICS_WINDOW.as_secs(),
+    }
+}
+
+/// Force-close one proxied WebSocket, or all of them; returns how many were
+/// closed (the explorer reconnects on its own)
+pub async fn close_proxy_ws_internal(
+    state: &SharedState,
+    connection_id: Option<u64>,
+) -> Result<usize, String> {
+    let metrics = state.lock().await.proxy_metrics.clone();
+    let metrics = metrics.lock().unwrap_or_else(|e| e.into_inner());
+    let closers: Vec<_> = match connection_id {
+        Some(id) => vec![metrics
+            .ws_connections
+            .get(&id)
+            .ok_or_else(|| format!("No open WebSocket connection {}", id))?
+            .closer
+            .clone()],
+        None => metrics
+            .ws_connections
+            .values()
+            .map(|connection| connection.closer.clone())
+            .collect(),
+    };
+    for closer in &closers {
+        closer.notify_one();
+    }
+    Ok(closers.len())
+}
+
+// ============================================================================
+// Tauri Commands
+// ============================================================================
+
+// Get which node the explorer proxy forwards to
+#[tauri::command]
+pub async fn get_proxy_upstream(
+    state: tauri::State<'_, SharedState>,
+) -> Result<ProxyUpstream, String> {
+    Ok(get_proxy_upstream_internal(&state).await)
+}
+
+// Point the explorer proxy at the localnet node, the PoA node or a remote node
+#[tauri::command]
+pub async fn set_proxy_upstream(
+    state: tauri::State<'_, SharedState>,
+    target: ProxyTarget,
+) -> Result<ProxyUpstream, String> {
+    set_proxy_upstream_internal(&state, target).await
+}
+
+// Get the explorer proxy's request rate and upstream latency
+#[tauri::command]
+pub async fn get_proxy_metrics(
+    state: tauri::State<'_, SharedState>,
+) -> Result<ProxyMetricsReport, String> {
+    Ok(get_proxy_metrics_internal(&state).await)
+}
+
+// Get the explorer's proxied WebSocket connections and message rate
+#[tauri::command]
+pub async fn get_proxy_stats(state: tauri::State<'_, SharedState>) -> Result<WsProxyStats, String> {
+    Ok(get_proxy_stats_internal(&state).await)
+}
+
+// Force-close a stuck proxied WebSocket (or all of them)
+#[tauri::command]
+pub async fn close_proxy_ws(
+    state: tauri::State<'_, SharedState>,
+    connection_id: Option<u64>,
+) -> Result<usize, String> {
+    close_proxy_ws_internal(&state, connection_id).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const URL: &str = "http://127.0.0.1:8080/v1a/block_at_height?height=2";
+
+    fn block(height: u64) -> NodeEvent {
+        NodeEvent::NewVertex {
+            event_id: height,
+            hash: format!("block-{}", height),
+            is_block: true,
+            height: Some(height),
+            timestamp: 0,
+        }
+    }
+
+    // A cache that followed a chain to height 10 and kept block 2
+    fn filled() -> (broadcast::Sender<NodeEvent>, ProxyCache) {
+        let (sender, receiver) = broadcast::channel(64);
+        let mut cache = ProxyCache::new(receiver);
+        cache.check_node(Some(1));
+        for height in 1..=10 {
+            sender.send(block(height)).unwrap();
+        }
+        cache.invalidate();
+        assert!(cache.cacheable(CacheKind::BlockAtHeight(2), br#"{"success": true}"#));
+        cache.responses.put(
+            URL.to_string(),
+            CachedResponse {
+                kind: CacheKind::BlockAtHeight(2),
+                status: 200,
+                headers: Vec::new(),
+                body: Bytes::from_static(br#"{"success": true}"#),
+            },
+        );
+        (sender, cache)
+    }
+
+    #[test]
+    fn reset_data_drops_the_old_chain() {
+        let (sender, mut cache) = filled();
+
+        // The data is reset and the node restarted on a fresh chain
+        cache.check_node(Some(2));
+        sender.send(block(1)).unwrap();
+        cache.invalidate();
+
+        assert!(cache.responses.get(URL).is_none());
+        assert_eq!(cache.best_height, Some(1));
+        assert!(!cache.buried(2));
+    }
+
+    #[test]
+    fn tip_going_back_drops_the_old_chain() {
+        let (sender, mut cache) = filled();
+
+        // Same process, but the event queue starts over on another chain
+        sender.send(block(3)).unwrap();
+        cache.invalidate();
+
+        assert!(cache.responses.get(URL).is_none());
+        assert_eq!(cache.best_height, Some(3));
+        assert!(!cache.buried(2));
+    }
+
+    #[test]
+    fn new_blocks_keep_buried_answers() {
+        let (sender, mut cache) = filled();
+
+        sender.send(block(11)).unwrap();
+        cache.invalidate();
+
+        assert!(cache.responses.get(URL).is_some());
+        assert_eq!(cache.best_height, Some(11));
+    }
+}
@@ -0,0 +1,74 @@
+//! QR code rendering for addresses and payment requests
+
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use qrcode::{Color, QrCode};
+
+// Blank modules around the code, as required by the QR spec
+const QUIET_ZONE: usize = 4;
+
+const DEFAULT_SIZE: u32 = 256;
+const MIN_SIZE: u32 = 64;
+const MAX_SIZE: u32 = 2048;
+
+/// Requested QR size in pixels, defaulting and bounds-checked
+pub fn check_size(size: Option<u32>) -> Result<u32, String> {
+    let size = size.unwrap_or(DEFAULT_SIZE);
+    if !(MIN_SIZE..=MAX_SIZE).contains(&size) {
+        return Err(format!(
+            "QR size must be between {} and {} pixels",
+            MIN_SIZE, MAX_SIZE
+        ));
+    }
+    Ok(size)
+}
+
+/// Render `payload` as a grayscale PNG at least `size` pixels wide (rounded to
+/// whole modules) and return it base64-encoded
+pub fn png_base64(payload: &str, size: u32) -> Result<String, String> {
+    let code =
+        QrCode::new(payload.as_bytes()).map_err(|e| format!("Failed to encode QR code: {}", e))?;
+
+    let modules = code.width();
+    let colors = code.to_colors();
+    let total = modules + 2 * QUIET_ZONE;
+    let scale = (size as usize).div_ceil(total).max(1);
+    let dimension = total * scale;
+
+    let mut pixels = vec![255u8; dimension * dimension];
+    for (i, color) in colors.iter().enumerate() {
+        if *color != Color::Dark {
+            continue;
+        }
+        let (x, y) = (i % modules + QUIET_ZONE, i / modules + QUIET_ZONE);
+        for row in y * scale..(y + 1) * scale {
+            let start = row * dimension + x * scale;
+            pixels[start..start + scale].fill(0);
+        }
+    }
+
+    let mut png_bytes = Vec::new();
+    {
+        let mut encoder = png::Encoder::new(&mut png_bytes, dimension as u32, dimension as u32);
+        encoder.set_color(png::ColorType::Grayscale);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder
+            .write_header()
+            .map_err(|e| format!("Failed to write PNG: {}", e))?;
+        writer
+            .write_image_data(&pixels)
+            .map_err(|e| format!("Failed to write PNG: {}", e))?;
+    }
+
+    Ok(BASE64.encode(png_bytes))
+}
+
+// ============================================================================
+// Tauri Commands
+// ============================================================================
+
+// Render an address as a QR code (base64 PNG) scannable by the Hathor wallets
+#[tauri::command]
+pub async fn get_address_qr(address: String, size: Option<u32>) -> Result<String, String> {
+    png_base64(&format!("hathor:{}", address.trim()), check_size(size)?)
+}
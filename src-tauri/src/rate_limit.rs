@@ -0,0 +1,213 @@
+//! Per-IP rate limits and request body limits
+//!
+//! A runaway polling loop in a dApp can flood the node through the explorer
+//! server's proxy faster than it can answer everyone else. The node proxy
+//! (`/v1a/*`, `/headless/*`), the public `/faucet` endpoint and the control
+//! API therefore give each client IP a token bucket per service:
+//! `requests_per_second` sustained, up to `burst` at once. Requests over the
+//! limit get a 429 with a `Retry-After` hint. Request bodies larger than
+//! `max_body_bytes` are refused with a 413 (or cut off, when streamed without
+//! a length).
+//!
+//! Settings live in `rate_limit.json` and are checked per request, so changes
+//! apply to running servers. Buckets are kept in `AppState` so limits survive
+//! server restarts.
+
+use axum::body::Body;
+use axum::extract::{ConnectInfo, Request, State};
+use axum::http::{header, StatusCode};
+use axum::middleware::Next;
+use axum::response::{IntoResponse, Response};
+use axum::Json;
+use http_body_util::Limited;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use std::collections::HashMap;
+use std::net::{IpAddr, Ipv4Addr, SocketAddr};
+use std::sync::{Arc, Mutex as StdMutex};
+use std::time::{Duration, Instant};
+
+use crate::access_log::AccessError;
+use crate::settings::JsonSettings;
+
+// Buckets idle this long are full again and can be dropped
+const IDLE_BUCKET_TTL: Duration = Duration::from_secs(300);
+// Dropping idle buckets is only worth it past this many
+const PRUNE_THRESHOLD: usize = 1024;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct RateLimitSettings {
+    pub enabled: bool,
+    // Sustained requests per second, per client IP and service
+    pub requests_per_second: u32,
+    // Requests a client can make at once before being limited
+    pub burst: u32,
+    // Largest request body accepted
+    pub max_body_bytes: u64,
+}
+
+impl Default for RateLimitSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            requests_per_second: 50,
+            burst: 100,
+            max_body_bytes: 2 * 1024 * 1024,
+        }
+    }
+}
+
+static SETTINGS: JsonSettings<RateLimitSettings> =
+    JsonSettings::new("rate_limit.json", "rate limit settings");
+
+struct Bucket {
+    tokens: f64,
+    updated: Instant,
+}
+
+/// Token buckets by service and client IP
+#[derive(Default)]
+pub struct RateLimits {
+    buckets: HashMap<(&'static str, IpAddr), Bucket>,
+}
+
+pub type SharedRateLimits = Arc<StdMutex<RateLimits>>;
+
+impl RateLimits {
+    // Take a token for a request, or how long until one is available
+    fn acquire(
+        &mut self,
+        service: &'static str,
+        ip: IpAddr,
+        settings: &RateLimitSettings,
+    ) -> Result<(), Duration> {
+        let now = Instant::now();
+        let rate = settings.requests_per_second.max(1) as f64;
+        let burst = settings.burst.max(1) as f64;
+        if self.buckets.len() > PRUNE_THRESHOLD {
+            self.buckets
+                .retain(|_, bucket| now.duration_since(bucket.updated) < IDLE_BUCKET_TTL);
+        }
+
+        let bucket = self.buckets.entry((service, ip)).or_insert(Bucket {
+            tokens: burst,
+            updated: now,
+        });
+        let elapsed = now.duration_since(bucket.updated).as_secs_f64();
+        bucket.tokens = (bucket.tokens + elapsed * rate).min(burst);
+        bucket.updated = now;
+        if bucket.tokens >= 1.0 {
+            bucket.tokens -= 1.0;
+            Ok(())
+        } else {
+            Err(Duration::from_secs_f64((1.0 - bucket.tokens) / rate))
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct RateLimitState {
+    limits: SharedRateLimits,
+    service: &'static str,
+}
+
+impl RateLimitState {
+    pub fn new(limits: SharedRateLimits, service: &'static str) -> Self {
+        Self { limits, service }
+    }
+}
+
+fn limit_error(status: StatusCode, message: String) -> Response {
+    let mut response = (
+        status,
+        Json(json!({ "success": false, "message": message })),
+    )
+        .into_response();
+    response.extensions_mut().insert(AccessError(message));
+    response
+}
+
+/// Middleware enforcing the limits; install with
+/// `axum::middleware::from_fn_with_state(RateLimitState::new(..), limit_requests)`.
+/// Client IPs come from `ConnectInfo`, so serve with
+/// `into_make_service_with_connect_info::<SocketAddr>()` (`tls::serve` adds it)
+pub async fn limit_requests(
+    State(state): State<RateLimitState>,
+    req: Request,
+    next: Next,
+) -> Response {
+    let settings = SETTINGS.read().clone();
+    if !settings.enabled {
+        return next.run(req).await;
+    }
+
+    let ip = req
+        .extensions()
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|ConnectInfo(addr)| addr.ip())
+        .unwrap_or(IpAddr::V4(Ipv4Addr::UNSPECIFIED));
+    let acquired = state
+        .limits
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .acquire(state.service, ip, &settings);
+    if let Err(wait) = acquired {
+        let seconds = wait.as_secs_f64().ceil().max(1.0) as u64;
+        let mut response = limit_error(
+            StatusCode::TOO_MANY_REQUESTS,
+            format!(
+                "Rate limit of {} requests/s exceeded; try again in {} seconds",
+                settings.requests_per_second, seconds
+            ),
+        );
+        if let Ok(value) = seconds.to_string().parse() {
+            response.headers_mut().insert(header::RETRY_AFTER, value);
+        }
+        return response;
+    }
+
+    let content_length = req
+        .headers()
+        .get(header::CONTENT_LENGTH)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse::<u64>().ok());
+    if content_length.is_some_and(|len| len > settings.max_body_bytes) {
+        return limit_error(
+            StatusCode::PAYLOAD_TOO_LARGE,
+            format!(
+                "Request body is larger than {} bytes",
+                settings.max_body_bytes
+            ),
+        );
+    }
+    // Bodies without a length are cut off at the limit
+    let max_body = usize::try_from(settings.max_body_bytes).unwrap_or(usize::MAX);
+    let req = req.map(|body| Body::new(Limited::new(body, max_body)));
+    next.run(req).await
+}
+
+// ============================================================================
+// Tauri Commands
+// ============================================================================
+
+// Get the rate and body size limits of the proxy, faucet and control API
+#[tauri::command]
+pub async fn get_rate_limit_settings() -> Result<RateLimitSettings, String> {
+    Ok(SETTINGS.read().clone())
+}
+
+// Set the limits; applies to the running servers right away
+#[tauri::command]
+pub async fn set_rate_limit_settings(
+    settings: RateLimitSettings,
+) -> Result<RateLimitSettings, String> {
+    if settings.requests_per_second == 0 || settings.burst == 0 {
+        return Err("Requests per second and burst must be at least 1".to_string());
+    }
+    if settings.max_body_bytes == 0 {
+        return Err("The body size limit must be at least 1 byte".to_string());
+    }
+    SETTINGS.save(&settings)?;
+    Ok(settings)
+}
@@ -0,0 +1,397 @@
+//! Session recorder
+//!
+//! While recording, every state-changing Tauri command the app invokes and
+//! every MCP tool call is captured with its arguments and the best block
+//! height at the time. `export_recording` turns the session into a scenario
+//! for `run_scenario`: calls with a scenario equivalent (node start/stop,
+//! wallet creation, faucet and wallet sends, token creation, snapshots)
+//! become steps, blocks mined between calls become `mine: N` steps, and
+//! anything else is listed as unmapped. Calls are captured as they are made,
+//! so ones that failed are recorded too. Secret arguments (seeds, passwords,
+//! keys) are redacted when captured and keychain commands aren't captured at
+//! all, so seeded and imported wallets are exported as fresh ones.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fs;
+use std::sync::Mutex as StdMutex;
+
+use crate::rewards;
+use crate::scenario::{Scenario, Step, WalletSpec};
+use crate::traffic::{REDACTED, SECRET_FIELDS};
+
+static RECORDER: StdMutex<Recorder> = StdMutex::new(Recorder::new());
+
+// Reads and recorder controls; never captured
+const READ_ONLY_PREFIXES: [&str; 8] = [
+    "get_",
+    "list_",
+    "generate_",
+    "validate_",
+    "estimate_",
+    "watch_",
+    "search_",
+    "export_",
+];
+const RECORDER_COMMANDS: [&str; 2] = ["start_recording", "stop_recording"];
+// Keychain commands (secrets.rs); never captured
+const SECRET_COMMANDS: [&str; 4] = [
+    "set_secrets_consent",
+    "save_api_key",
+    "delete_api_key",
+    "forget_all_secrets",
+];
+// Argument names redacted on top of traffic.rs's secret fields
+const SECRET_ARGS: [&str; 2] = ["key", "seed_source"];
+// Their effect shows up as `mine` steps from the recorded heights
+const MINING_COMMANDS: [&str; 5] = [
+    "start_miner",
+    "stop_miner",
+    "start_paced_mining",
+    "stop_paced_mining",
+    "unlock_rewards",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CallSource {
+    App,
+    Mcp,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RecordedCall {
+    pub at: u64, // Unix timestamp
+    pub source: CallSource,
+    pub command: String,
+    pub args: Value,
+    // Best block when the call was made, if the node was running
+    pub height: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Recording {
+    pub recording: bool,
+    pub started_at: Option<u64>,
+    pub start_height: Option<u64>,
+    pub end_height: Option<u64>,
+    pub calls: Vec<RecordedCall>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportedScenario {
+    pub path: String,
+    pub steps: usize,
+    // Recorded commands with no scenario step
+    pub unmapped: Vec<String>,
+}
+
+struct Recorder {
+    recording: bool,
+    started_at: Option<u64>,
+    start_height: Option<u64>,
+    end_height: Option<u64>,
+    calls: Vec<RecordedCall>,
+}
+
+impl Recorder {
+    const fn new() -> Self {
+        Self {
+            recording: false,
+            started_at: None,
+            start_height: None,
+            end_height: None,
+            calls: Vec::new(),
+        }
+    }
+
+    fn snapshot(&self) -> Recording {
+        Recording {
+            recording: self.recording,
+            started_at: self.started_at,
+            start_height: self.start_height,
+            end_height: self.end_height,
+            calls: self.calls.clone(),
+        }
+    }
+}
+
+fn recorder() -> std::sync::MutexGuard<'static, Recorder> {
+    RECORDER.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+async fn current_height() -> Option<u64> {
+    rewards::chain_info(&reqwest::Client::new())
+        .await
+        .ok()
+        .map(|(height, _)| height)
+}
+
+fn is_recorded(command: &str) -> bool {
+    !READ_ONLY_PREFIXES
+        .iter()
+        .any(|prefix| command.starts_with(prefix))
+        && !RECORDER_COMMANDS.contains(&command)
+        && !SECRET_COMMANDS.contains(&command)
+}
+
+// Whether an argument holds a secret; app arguments come in camelCase
+fn is_secret_arg(name: &str) -> bool {
+    let normalized = name.to_lowercase().replace('_', "");
+    SECRET_FIELDS
+        .iter()
+        .chain(SECRET_ARGS.iter())
+        .any(|field| field.replace('_', "") == normalized)
+}
+
+fn redact_args(value: &mut Value) {
+    match value {
+        Value::Object(fields) => {
+            for (name, field) in fields.iter_mut() {
+                if is_secret_arg(name) {
+                    *field = Value::String(REDACTED.to_string());
+                } else {
+                    redact_args(field);
+                }
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(redact_args),
+        _ => {}
+    }
+}
+
+/// Capture a call if a recording is in progress; the height is filled in
+/// in the background
+pub fn record(source: CallSource, command: &str, args: &Value) {
+    let index = {
+        let mut recorder = recorder();
+        if !recorder.recording || !is_recorded(command) {
+            return;
+        }
+        let mut args = args.clone();
+        redact_args(&mut args);
+        recorder.calls.push(RecordedCall {
+            at: crate::unix_now(),
+            source,
+            command: command.to_string(),
+            args,
+            height: None,
+        });
+        recorder.calls.len() - 1
+    };
+
+    tauri::async_runtime::spawn(async move {
+        let height = current_height().await;
+        if let Some(call) = recorder().calls.get_mut(index) {
+            call.height = height;
+        }
+    });
+}
+
+/// Wrap the app's command handler so commands the frontend invokes are
+/// captured while recording
+pub fn capture<F>(commands: F) -> impl Fn(tauri::ipc::Invoke) -> bool + Send + Sync + 'static
+where
+    F: Fn(tauri::ipc::Invoke) -> bool + Send + Sync + 'static,
+{
+    move |invoke| {
+        if let tauri::ipc::InvokeBody::Json(args) = invoke.message.payload() {
+            record(CallSource::App, invoke.message.command(), args);
+        }
+        commands(invoke)
+    }
+}
+
+// Argument `name` of a call; the frontend passes command arguments in camelCase
+fn arg<'a>(args: &'a Value, name: &str) -> Option<&'a Value> {
+    let mut parts = name.split('_');
+    let first = parts.next().unwrap_or_default().to_string();
+    let camel = parts.fold(first, |mut camel, part| {
+        let mut chars = part.chars();
+        if let Some(c) = chars.next() {
+            camel.push(c.to_ascii_uppercase());
+            camel.push_str(chars.as_str());
+        }
+        camel
+    });
+    args.get(name).or_else(|| args.get(camel))
+}
+
+fn str_arg(args: &Value, name: &str) -> Option<String> {
+    arg(args, name)?.as_str().map(|s| s.to_string())
+}
+
+// Scenario amounts are decimal; the app passes cents, MCP tools decimal HTR
+fn htr_arg(call: &RecordedCall, args: &Value, name: &str) -> Option<f64> {
+    let value = arg(args, name)?;
+    match call.source {
+        CallSource::App => value.as_u64().map(|cents| cents as f64 / 100.0),
+        CallSource::Mcp => value.as_f64(),
+    }
+}
+
+fn step_for(call: &RecordedCall) -> Option<Step> {
+    let args = &call.args;
+    // App commands taking a request struct nest their arguments
+    let request = args.get("request").unwrap_or(args);
+
+    match call.command.as_str() {
+        "start_node" => Some(Step::StartNode),
+        "stop_node" => Some(Step::StopNode),
+        // Seeds are redacted, so these become fresh wallets
+        "create_headless_wallet" | "create_wallet" | "import_wallet" => Some(Step::CreateWallet(
+            WalletSpec::Id(str_arg(request, "wallet_id")?),
+        )),
+        "send_tx" | "send_from_faucet" => Some(Step::Fund {
+            wallet: str_arg(request, "address")?,
+            amount: htr_arg(call, request, "amount")?,
+        }),
+        "fund_wallet" => Some(Step::Fund {
+            wallet: str_arg(request, "wallet_id")?,
+            amount: htr_arg(call, request, "amount")?,
+        }),
+        "headless_wallet_send_tx" | "send_from_wallet" => Some(Step::Send {
+            from: str_arg(request, "wallet_id")?,
+            to: str_arg(request, "address")?,
+            amount: htr_arg(call, request, "amount")?,
+        }),
+        // Token amounts are cents from both sources
+        "create_token" => Some(Step::CreateToken {
+            wallet: str_arg(request, "wallet_id")?,
+            name: str_arg(request, "name")?,
+            symbol: str_arg(request, "symbol")?,
+            amount: arg(request, "amount")?.as_u64()? as f64 / 100.0,
+        }),
+        "save_snapshot" => Some(Step::SaveSnapshot(str_arg(request, "name")?)),
+        "restore_snapshot" => Some(Step::RestoreSnapshot(str_arg(request, "name")?)),
+        _ => None,
+    }
+}
+
+/// Turn a recording into scenario steps, returning the unmapped commands too
+pub fn to_scenario(recording: &Recording) -> (Scenario, Vec<String>) {
+    let mut steps = Vec::new();
+    let mut unmapped = Vec::new();
+    let mut last_height = recording.start_height;
+
+    let mut mined_until = |steps: &mut Vec<Step>, height: Option<u64>| {
+        if let (Some(last), Some(height)) = (last_height, height) {
+            if height > last {
+                steps.push(Step::Mine(height - last));
+            }
+        }
+        if height.is_some() {
+            last_height = height;
+        }
+    };
+
+    for call in &recording.calls {
+        mined_until(&mut steps, call.height);
+        match step_for(call) {
+            Some(step) => steps.push(step),
+            None if MINING_COMMANDS.contains(&call.command.as_str()) => {}
+            None => unmapped.push(call.command.clone()),
+        }
+    }
+    mined_until(&mut steps, recording.end_height);
+
+    let scenario = Scenario {
+        name: Some(format!(
+            "Recorded session {}",
+            recording.started_at.unwrap_or_else(crate::unix_now)
+        )),
+        instance: None,
+        stop_on_failure: true,
+        steps,
+    };
+    (scenario, unmapped)
+}
+
+/// Clear any previous session and start capturing calls
+pub async fn start_recording_internal() -> Recording {
+    let start_height = current_height().await;
+    let mut recorder = recorder();
+    *recorder = Recorder {
+        recording: true,
+        started_at: Some(crate::unix_now()),
+        start_height,
+        end_height: None,
+        calls: Vec::new(),
+    };
+    recorder.snapshot()
+}
+
+/// Stop capturing; the session is kept for export
+pub async fn stop_recording_internal() -> Recording {
+    let end_height = current_height().await;
+    let mut recorder = recorder();
+    if recorder.recording {
+        recorder.recording = false;
+        recorder.end_height = end_height;
+    }
+    recorder.snapshot()
+}
+
+pub fn get_recording_internal() -> Recording {
+    recorder().snapshot()
+}
+
+/// Write the recorded session to `path` as a scenario (JSON for `.json`,
+/// YAML otherwise)
+pub fn export_recording_internal(path: &str) -> Result<ExportedScenario, String> {
+    let recording = get_recording_internal();
+    if recording.calls.is_empty() && recording.started_at.is_none() {
+        return Err("Nothing recorded yet".to_string());
+    }
+    let (scenario, unmapped) = to_scenario(&recording);
+
+    // Going through a JSON value writes steps as `mine: 10` rather than YAML tags
+    let value = serde_json::to_value(&scenario)
+        .map_err(|e| format!("Failed to serialize scenario: {}", e))?;
+    let content = if path.ends_with(".json") {
+        serde_json::to_string_pretty(&value).map_err(|e| e.to_string())?
+    } else {
+        serde_yaml::to_string(&value).map_err(|e| e.to_string())?
+    };
+    if let Some(parent) = std::path::Path::new(path).parent() {
+        if !parent.as_os_str().is_empty() {
+            fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create scenario directory: {}", e))?;
+        }
+    }
+    fs::write(path, content).map_err(|e| format!("Failed to write scenario: {}", e))?;
+
+    Ok(ExportedScenario {
+        path: path.to_string(),
+        steps: scenario.steps.len(),
+        unmapped,
+    })
+}
+
+// ============================================================================
+// Tauri Commands
+// ============================================================================
+
+// Start capturing state-changing commands and MCP tool calls
+#[tauri::command]
+pub async fn start_recording() -> Result<Recording, String> {
+    Ok(start_recording_internal().await)
+}
+
+// Stop capturing calls
+#[tauri::command]
+pub async fn stop_recording() -> Result<Recording, String> {
+    Ok(stop_recording_internal().await)
+}
+
+// Get the calls captured in the current or last session
+#[tauri::command]
+pub async fn get_recording() -> Result<Recording, String> {
+    Ok(get_recording_internal())
+}
+
+// Write the recorded session as a scenario for run_scenario
+#[tauri::command]
+pub async fn export_recording(path: String) -> Result<ExportedScenario, String> {
+    export_recording_internal(&path)
+}
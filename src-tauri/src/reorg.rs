@@ -0,0 +1,352 @@
+//! Reorg simulation
+//!
+//! Forces a chain reorganization on the localnet so wallets and dApps can be
+//! tested against rolled-back blocks. A temporary second fullnode syncs the
+//! chain and is then cut off; the main node mines `depth` blocks (the ones that
+//! will be orphaned) while the fork node mines a longer chain. When the two are
+//! reconnected the main node switches to the fork's chain, voiding its own
+//! blocks and returning their transactions to the mempool.
+
+use serde::{Deserialize, Serialize};
+use std::process::Stdio;
+use std::time::{Duration, Instant};
+use tokio::process::{Child, Command as TokioCommand};
+
+use crate::{features, paced_mining, MinerConfig, NodeConfig, SharedState};
+
+const MAX_DEPTH: u64 = 20;
+// Ports of the temporary fork node
+const FORK_API_PORT: u16 = 8090;
+const FORK_STRATUM_PORT: u16 = 8010;
+const FORK_P2P_PORT: u16 = 40410;
+// Names of the miners mining each side of the fork
+const MAIN_MINER: &str = "reorg-main";
+const FORK_MINER: &str = "reorg-fork";
+// Blocks the fork mines past `depth` when its chain isn't adopted right away
+const MAX_EXTRA_BLOCKS: u64 = 3;
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+const STARTUP_TIMEOUT: Duration = Duration::from_secs(60);
+const SYNC_TIMEOUT: Duration = Duration::from_secs(120);
+const BLOCK_TIMEOUT: Duration = Duration::from_secs(60);
+const REORG_TIMEOUT: Duration = Duration::from_secs(20);
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReorgResult {
+    pub depth: u64,
+    // Last block both chains share
+    pub fork_height: u64,
+    // Main-chain blocks voided by the reorg
+    pub orphaned_blocks: Vec<String>,
+    pub new_best_height: u64,
+    pub new_best_hash: String,
+    pub elapsed_secs: u64,
+    pub message: String,
+}
+
+// Best block (height, hash) of the node whose API listens on `api_port`
+async fn best_block(client: &reqwest::Client, api_port: u16) -> Result<(u64, String), String> {
+    let status: serde_json::Value = client
+        .get(format!("http://127.0.0.1:{}/v1a/status/", api_port))
+        .send()
+        .await
+        .map_err(|e| format!("Failed to get node status: {}", e))?
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse node status: {}", e))?;
+    let best_block = &status["dag"]["best_block"];
+    let height = best_block["height"]
+        .as_u64()
+        .ok_or("Node status has no best block height")?;
+    let hash = best_block["hash"]
+        .as_str()
+        .ok_or("Node status has no best block hash")?;
+    Ok((height, hash.to_string()))
+}
+
+/// Data directory of the temporary fork node
+pub fn fork_data_dir() -> std::path::PathBuf {
+    crate::get_app_dir().join("reorg-fork")
+}
+
+// Start the fork node; it only accepts the main node's connection when `listen` is set
+fn spawn_fork_node(listen: bool) -> Result<Child, String> {
+    let binary_path = crate::get_binary_path("hathor-core");
+    let internal_dir = binary_path.parent().unwrap().join("_internal");
+    let data_dir = fork_data_dir();
+    std::fs::create_dir_all(&data_dir)
+        .map_err(|e| format!("Failed to create fork node data directory: {}", e))?;
+
+    let mut cmd = TokioCommand::new(&binary_path);
+    crate::set_library_path_env(&mut cmd, &internal_dir);
+    cmd.args([
+        "run_node",
+        "--status",
+        &FORK_API_PORT.to_string(),
+        "--stratum",
+        &FORK_STRATUM_PORT.to_string(),
+        "--data",
+        &data_dir.to_string_lossy(),
+        "--allow-mining-without-peers",
+        "--test-mode-tx-weight",
+        "--unsafe-mode",
+        "privatenet",
+    ]);
+    // Same network settings as the main node, forced features included
+    cmd.args(features::network_args(&NodeConfig::default())?);
+    if listen {
+        cmd.args(["--listen", &format!("tcp:{}", FORK_P2P_PORT)]);
+    }
+    cmd.stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .kill_on_drop(true)
+        .spawn()
+        .map_err(|e| format!("Failed to spawn fork node at {:?}: {}", binary_path, e))
+}
+
+async fn stop_fork_node(fork: &mut Option<Child>) {
+    if let Some(mut child) = fork.take() {
+        let _ = child.kill().await;
+    }
+}
+
+// Poll `check` until it returns a value or `timeout` passes
+async fn wait_for<T, F, Fut>(timeout: Duration, what: &str, mut check: F) -> Result<T, String>
+where
+    F: FnMut() -> Fut,
+    Fut: std::future::Future<Output = Option<T>>,
+{
+    let started = Instant::now();
+    while started.elapsed() < timeout {
+        if let Some(value) = check().await {
+            return Ok(value);
+        }
+        tokio::time::sleep(POLL_INTERVAL).await;
+    }
+    Err(format!(
+        "Timed out after {}s waiting for {}",
+        timeout.as_secs(),
+        what
+    ))
+}
+
+// Ask the main node to connect to the fork node
+async fn connect_to_fork(client: &reqwest::Client) -> Result<(), String> {
+    client
+        .post(format!(
+            "http://127.0.0.1:{}/v1a/p2p/peers",
+            NodeConfig::default().api_port
+        ))
+        .json(&[format!("tcp://127.0.0.1:{}", FORK_P2P_PORT)])
+        .send()
+        .await
+        .map_err(|e| format!("Failed to connect the node to the fork node: {}", e))?;
+    Ok(())
+}
+
+// Mine until the node on `api_port` reaches `height`, with a miner on `stratum_port`
+async fn mine_to_height(
+    state: &SharedState,
+    client: &reqwest::Client,
+    miner: &str,
+    stratum_port: u16,
+    api_port: u16,
+    height: u64,
+) -> Result<(), String> {
+    crate::start_miner_internal(
+        state,
+        MinerConfig {
+            name: miner.to_string(),
+            stratum_port,
+            ..MinerConfig::default()
+        },
+    )
+    .await?;
+
+    let blocks = height.saturating_sub(best_block(client, api_port).await?.0);
+    let mined = wait_for(
+        BLOCK_TIMEOUT * blocks.max(1) as u32,
+        &format!("miner '{}' to reach height {}", miner, height),
+        || async {
+            let (best, _) = best_block(client, api_port).await.ok()?;
+            (best >= height).then_some(())
+        },
+    )
+    .await;
+
+    crate::stop_miner_internal(state, Some(miner)).await?;
+    mined
+}
+
+async fn run_reorg(
+    state: &SharedState,
+    client: &reqwest::Client,
+    fork: &mut Option<Child>,
+    depth: u64,
+    started: Instant,
+) -> Result<ReorgResult, String> {
+    let main_api_port = NodeConfig::default().api_port;
+    let main_stratum_port = NodeConfig::default().stratum_port;
+
+    // Sync the fork node with the main chain
+    *fork = Some(spawn_fork_node(true)?);
+    wait_for(STARTUP_TIMEOUT, "the fork node to start", || async {
+        best_block(client, FORK_API_PORT).await.ok()
+    })
+    .await?;
+    connect_to_fork(client).await?;
+    let (fork_height, fork_hash) = best_block(client, main_api_port).await?;
+    wait_for(SYNC_TIMEOUT, "the fork node to sync", || async {
+        let (_, hash) = best_block(client, FORK_API_PORT).await.ok()?;
+        (hash == fork_hash).then_some(())
+    })
+    .await?;
+
+    // Cut it off: restarted without listening, the main node can't reach it
+    stop_fork_node(fork).await;
+    *fork = Some(spawn_fork_node(false)?);
+    wait_for(STARTUP_TIMEOUT, "the fork node to restart", || async {
+        best_block(client, FORK_API_PORT).await.ok()
+    })
+    .await?;
+
+    // Mine the blocks to be orphaned, then a longer chain on the fork
+    mine_to_height(
+        state,
+        client,
+        MAIN_MINER,
+        main_stratum_port,
+        main_api_port,
+        fork_height + depth,
+    )
+    .await?;
+    let mut orphaned_blocks = Vec::new();
+    for height in fork_height + 1..=fork_height + depth {
+        let block: serde_json::Value = client
+            .get(format!(
+                "http://127.0.0.1:{}/v1a/block_at_height",
+                main_api_port
+            ))
+            .query(&[("height", height)])
+            .send()
+            .await
+            .map_err(|e| format!("Failed to get block at height {}: {}", height, e))?
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse block at height {}: {}", height, e))?;
+        if let Some(hash) = block["block"]["tx_id"].as_str() {
+            orphaned_blocks.push(hash.to_string());
+        }
+    }
+
+    let mut fork_target = fork_height + depth + 1;
+    mine_to_height(
+        state,
+        client,
+        FORK_MINER,
+        FORK_STRATUM_PORT,
+        FORK_API_PORT,
+        fork_target,
+    )
+    .await?;
+
+    // Reconnect; the main node adopts the fork's chain once it weighs more
+    stop_fork_node(fork).await;
+    *fork = Some(spawn_fork_node(true)?);
+    wait_for(STARTUP_TIMEOUT, "the fork node to restart", || async {
+        best_block(client, FORK_API_PORT).await.ok()
+    })
+    .await?;
+    connect_to_fork(client).await?;
+
+    loop {
+        let adopted = wait_for(REORG_TIMEOUT, "the node to adopt the fork", || async {
+            let (_, fork_best) = best_block(client, FORK_API_PORT).await.ok()?;
+            let (height, hash) = best_block(client, main_api_port).await.ok()?;
+            (hash == fork_best).then_some((height, hash))
+        })
+        .await;
+
+        match adopted {
+            Ok((new_best_height, new_best_hash)) => {
+                return Ok(ReorgResult {
+                    depth,
+                    fork_height,
+                    message: format!(
+                        "Reorg of {} blocks from height {}: best block is now {} at height {}",
+                        orphaned_blocks.len(),
+                        fork_height,
+                        new_best_hash,
+                        new_best_height
+                    ),
+                    orphaned_blocks,
+                    new_best_height,
+                    new_best_hash,
+                    elapsed_secs: started.elapsed().as_secs(),
+                });
+            }
+            Err(e) if fork_target >= fork_height + depth + 1 + MAX_EXTRA_BLOCKS => return Err(e),
+            Err(_) => {
+                // Not heavier yet: extend the fork by one more block
+                fork_target += 1;
+                mine_to_height(
+                    state,
+                    client,
+                    FORK_MINER,
+                    FORK_STRATUM_PORT,
+                    FORK_API_PORT,
+                    fork_target,
+                )
+                .await?;
+            }
+        }
+    }
+}
+
+/// Force a reorganization that orphans the last `depth` blocks mined on the node.
+/// Forge's own miners (and paced mining) are stopped first.
+pub async fn simulate_reorg_internal(
+    state: &SharedState,
+    depth: u64,
+) -> Result<ReorgResult, String> {
+    if depth == 0 || depth > MAX_DEPTH {
+        return Err(format!("Reorg depth must be between 1 and {}", MAX_DEPTH));
+    }
+    if !state.lock().await.node_running {
+        return Err("Node must be running to simulate a reorg".to_string());
+    }
+
+    // Other miners would extend the main chain while the fork catches up
+    paced_mining::stop_paced_mining_internal(state).await?;
+    for miner in crate::list_miners_internal(state).await {
+        crate::stop_miner_internal(state, Some(&miner.name)).await?;
+    }
+
+    // Leftovers of an interrupted run
+    let _ = std::fs::remove_dir_all(fork_data_dir());
+
+    let client = reqwest::Client::new();
+    let started = Instant::now();
+    let mut fork = None;
+    let result = run_reorg(state, &client, &mut fork, depth, started).await;
+
+    let _ = crate::stop_miner_internal(state, Some(MAIN_MINER)).await;
+    let _ = crate::stop_miner_internal(state, Some(FORK_MINER)).await;
+    stop_fork_node(&mut fork).await;
+    let _ = std::fs::remove_dir_all(fork_data_dir());
+
+    result
+}
+
+// ============================================================================
+// Tauri Commands
+// ============================================================================
+
+// Orphan the last `depth` blocks with a temporary fork node
+#[tauri::command]
+pub async fn simulate_reorg(
+    state: tauri::State<'_, SharedState>,
+    depth: u64,
+) -> Result<ReorgResult, String> {
+    simulate_reorg_internal(&state, depth).await
+}
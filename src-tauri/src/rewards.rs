@@ -0,0 +1,155 @@
+//! Unlocking block rewards
+//!
+//! Block rewards only become spendable `reward_spend_min_blocks` blocks after
+//! the block that created them, so a fresh chain shows an empty faucet even
+//! while it mines. `unlock_rewards` mines just enough blocks to the faucet and
+//! waits until its spendable balance is nonzero.
+
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+
+use crate::amount::HtrAmount;
+use crate::{faucet, MinerConfig, SharedState};
+
+// Mainnet value, used when the node doesn't report its own
+const DEFAULT_REWARD_SPEND_MIN_BLOCKS: u64 = 300;
+const POLL_INTERVAL: Duration = Duration::from_secs(2);
+const UNLOCK_TIMEOUT: Duration = Duration::from_secs(600);
+// Polls to wait for the faucet balance to catch up after the miner stops
+const STOPPED_GRACE_POLLS: u32 = 5;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UnlockRewardsResult {
+    pub reward_spend_min_blocks: u64,
+    pub start_height: u64,
+    pub end_height: u64,
+    pub blocks_mined: u64,
+    pub available: HtrAmount,
+    pub elapsed_secs: u64,
+    pub message: String,
+}
+
+/// Best block height and reward lock reported by the node
+pub async fn chain_info(client: &reqwest::Client) -> Result<(u64, u64), String> {
+    let status: serde_json::Value = client
+        .get("http://127.0.0.1:8080/v1a/status/")
+        .send()
+        .await
+        .map_err(|e| format!("Failed to get node status: {}", e))?
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse node status: {}", e))?;
+    let height = status["dag"]["best_block"]["height"]
+        .as_u64()
+        .ok_or("Node status has no best block height")?;
+
+    let version: serde_json::Value = client
+        .get("http://127.0.0.1:8080/v1a/version")
+        .send()
+        .await
+        .map_err(|e| format!("Failed to get node version: {}", e))?
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse node version: {}", e))?;
+    let reward_lock = version["reward_spend_min_blocks"]
+        .as_u64()
+        .unwrap_or(DEFAULT_REWARD_SPEND_MIN_BLOCKS);
+
+    Ok((height, reward_lock))
+}
+
+/// Mine until the faucet has a spendable balance, starting the miner if needed
+pub async fn unlock_rewards_internal(state: &SharedState) -> Result<UnlockRewardsResult, String> {
+    let client = reqwest::Client::new();
+    let started = Instant::now();
+    let (start_height, reward_lock) = chain_info(&client).await?;
+
+    let available = faucet::faucet_available(&client).await?;
+    if !available.is_zero() {
+        return Ok(UnlockRewardsResult {
+            reward_spend_min_blocks: reward_lock,
+            start_height,
+            end_height: start_height,
+            blocks_mined: 0,
+            available,
+            elapsed_secs: 0,
+            message: format!("Faucet already has {} spendable", available),
+        });
+    }
+
+    // The first reward (height 1) unlocks at height 1 + lock; past that, the
+    // existing blocks didn't pay the faucet and a new reward has to mature
+    let target_height = if start_height <= reward_lock {
+        reward_lock + 1
+    } else {
+        start_height + reward_lock + 1
+    };
+
+    let (node_running, miner_running) = {
+        let state_guard = state.lock().await;
+        (state_guard.node_running, !state_guard.miners.is_empty())
+    };
+    if !node_running {
+        return Err("Node must be running to unlock rewards".to_string());
+    }
+    if !miner_running {
+        crate::start_miner_internal(
+            state,
+            MinerConfig {
+                stop_at_height: Some(target_height),
+                ..MinerConfig::default()
+            },
+        )
+        .await?;
+    }
+
+    let mut stopped_polls = 0;
+    while started.elapsed() < UNLOCK_TIMEOUT {
+        tokio::time::sleep(POLL_INTERVAL).await;
+
+        let available = faucet::faucet_available(&client).await?;
+        if !available.is_zero() {
+            let (end_height, _) = chain_info(&client).await?;
+            return Ok(UnlockRewardsResult {
+                reward_spend_min_blocks: reward_lock,
+                start_height,
+                end_height,
+                blocks_mined: end_height.saturating_sub(start_height),
+                available,
+                elapsed_secs: started.elapsed().as_secs(),
+                message: format!(
+                    "Mined {} blocks; faucet now has {} spendable",
+                    end_height.saturating_sub(start_height),
+                    available
+                ),
+            });
+        }
+        if state.lock().await.miners.is_empty() {
+            stopped_polls += 1;
+        }
+        if stopped_polls > STOPPED_GRACE_POLLS {
+            return Err(format!(
+                "Miner stopped before rewards unlocked (target height {})",
+                target_height
+            ));
+        }
+    }
+
+    Err(format!(
+        "Timed out after {}s waiting for rewards to unlock (target height {})",
+        UNLOCK_TIMEOUT.as_secs(),
+        target_height
+    ))
+}
+
+// ============================================================================
+// Tauri Commands
+// ============================================================================
+
+// Mine until the faucet's block rewards are spendable
+#[tauri::command]
+pub async fn unlock_rewards(
+    state: tauri::State<'_, SharedState>,
+) -> Result<UnlockRewardsResult, String> {
+    unlock_rewards_internal(&state).await
+}
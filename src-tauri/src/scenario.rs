@@ -0,0 +1,423 @@
+//! Declarative scenarios
+//!
+//! `run_scenario` executes a YAML or JSON script of steps against the local
+//! environment, so a dApp repository can commit the setup it needs next to
+//! its code:
+//!
+//! ```yaml
+//! name: transfer
+//! steps:
+//!   - start_node
+//!   - mine: 10
+//!   - create_wallet: alice
+//!   - create_wallet: bob
+//!   - fund: { wallet: alice, amount: 50 }
+//!   - send: { from: alice, to: bob, amount: 5 }
+//!   - assert_balance: { wallet: bob, equals: 5 }
+//! ```
+//!
+//! Amounts are decimal HTR; `fund` and `send` take a scenario wallet or an
+//! address as the recipient. Steps run in order and each one gets a result;
+//! after a failure the remaining steps are skipped unless `stop_on_failure`
+//! is false. Assertions poll for a few seconds before failing, since transfers
+//! reach the receiving wallet asynchronously.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::time::{Duration, Instant};
+
+use crate::amount::HtrAmount;
+use crate::{bootstrap, faucet, rewards, snapshots, tokens, SharedState};
+
+const DEFAULT_ASSERT_WITHIN_SECS: u64 = 10;
+const ASSERT_POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+fn default_true() -> bool {
+    true
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Scenario {
+    #[serde(default)]
+    pub name: Option<String>,
+    // Wallet-headless instance the wallets live on
+    #[serde(default)]
+    pub instance: Option<String>,
+    #[serde(default = "default_true")]
+    pub stop_on_failure: bool,
+    pub steps: Vec<Step>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum WalletSpec {
+    Id(String),
+    Seeded { id: String, seed: String },
+}
+
+// Bounds for assertions; every bound given must hold
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Bounds {
+    #[serde(default)]
+    pub equals: Option<f64>,
+    #[serde(default)]
+    pub at_least: Option<f64>,
+    #[serde(default)]
+    pub at_most: Option<f64>,
+    // Seconds to keep polling before failing
+    #[serde(default)]
+    pub within: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Step {
+    StartNode,
+    StopNode,
+    // Mine this many blocks
+    Mine(u64),
+    // Wait this many seconds
+    Sleep(u64),
+    CreateWallet(WalletSpec),
+    // Send HTR from the faucet to a scenario wallet or an address
+    Fund {
+        wallet: String,
+        amount: f64,
+    },
+    // `to` is a scenario wallet or an address
+    Send {
+        from: String,
+        to: String,
+        amount: f64,
+    },
+    CreateToken {
+        wallet: String,
+        name: String,
+        symbol: String,
+        // Token units (decimal, like HTR)
+        amount: f64,
+    },
+    SaveSnapshot(String),
+    RestoreSnapshot(String),
+    AssertBalance {
+        wallet: String,
+        #[serde(flatten)]
+        bounds: Bounds,
+    },
+    AssertHeight(Bounds),
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StepStatus {
+    Passed,
+    Failed,
+    Skipped,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StepResult {
+    pub index: usize,
+    pub description: String,
+    pub status: StepStatus,
+    // Outcome (a hash, a height, ...) or the failure reason
+    pub message: Option<String>,
+    pub elapsed_ms: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScenarioResult {
+    pub name: String,
+    pub path: String,
+    pub passed: bool,
+    pub steps: Vec<StepResult>,
+    pub elapsed_secs: u64,
+}
+
+impl Bounds {
+    fn describe(&self) -> String {
+        let mut parts = Vec::new();
+        if let Some(value) = self.equals {
+            parts.push(format!("= {}", value));
+        }
+        if let Some(value) = self.at_least {
+            parts.push(format!(">= {}", value));
+        }
+        if let Some(value) = self.at_most {
+            parts.push(format!("<= {}", value));
+        }
+        parts.join(", ")
+    }
+
+    fn validate(&self) -> Result<(), String> {
+        if self.equals.is_none() && self.at_least.is_none() && self.at_most.is_none() {
+            return Err("Assertion needs equals, at_least or at_most".to_string());
+        }
+        Ok(())
+    }
+
+    // Compare in the value's smallest unit (cents for HTR, blocks for height)
+    fn holds(&self, value: u64, scale: f64) -> bool {
+        let to_units = |bound: f64| (bound * scale).round() as u64;
+        self.equals.is_none_or(|bound| value == to_units(bound))
+            && self.at_least.is_none_or(|bound| value >= to_units(bound))
+            && self.at_most.is_none_or(|bound| value <= to_units(bound))
+    }
+
+    fn within(&self) -> Duration {
+        Duration::from_secs(self.within.unwrap_or(DEFAULT_ASSERT_WITHIN_SECS))
+    }
+}
+
+impl Step {
+    fn describe(&self) -> String {
+        match self {
+            Step::StartNode => "start node".to_string(),
+            Step::StopNode => "stop node".to_string(),
+            Step::Mine(count) => format!("mine {} blocks", count),
+            Step::Sleep(secs) => format!("sleep {}s", secs),
+            Step::CreateWallet(WalletSpec::Id(id) | WalletSpec::Seeded { id, .. }) => {
+                format!("create wallet {}", id)
+            }
+            Step::Fund { wallet, amount } => format!("fund {} with {} HTR", wallet, amount),
+            Step::Send { from, to, amount } => format!("send {} HTR {} → {}", amount, from, to),
+            Step::CreateToken {
+                wallet,
+                symbol,
+                amount,
+                ..
+            } => format!("create {} {} in {}", amount, symbol, wallet),
+            Step::SaveSnapshot(name) => format!("save snapshot {}", name),
+            Step::RestoreSnapshot(name) => format!("restore snapshot {}", name),
+            Step::AssertBalance { wallet, bounds } => {
+                format!("assert balance of {} {}", wallet, bounds.describe())
+            }
+            Step::AssertHeight(bounds) => format!("assert height {}", bounds.describe()),
+        }
+    }
+}
+
+// Wallets created by the scenario, by id, with their receive address
+struct Context<'a> {
+    state: &'a SharedState,
+    instance: Option<String>,
+    wallets: HashMap<String, String>,
+}
+
+impl Context<'_> {
+    async fn base_url(&self) -> Result<String, String> {
+        bootstrap::ensure_headless(self.state, self.instance.as_deref()).await
+    }
+
+    fn address_of(&self, wallet_or_address: &str) -> String {
+        self.wallets
+            .get(wallet_or_address)
+            .cloned()
+            .unwrap_or_else(|| wallet_or_address.to_string())
+    }
+
+    async fn run(&mut self, step: &Step) -> Result<String, String> {
+        let state = self.state;
+        match step {
+            Step::StartNode => {
+                let height = bootstrap::ensure_node_ready(state).await?;
+                Ok(format!("Node ready at height {}", height))
+            }
+            Step::StopNode => crate::stop_node_internal(state).await,
+            Step::Mine(count) => {
+                let height = bootstrap::mine_blocks(state, *count).await?;
+                Ok(format!("Height {}", height))
+            }
+            Step::Sleep(secs) => {
+                tokio::time::sleep(Duration::from_secs(*secs)).await;
+                Ok(format!("Slept {}s", secs))
+            }
+            Step::CreateWallet(spec) => {
+                let (id, seed) = match spec {
+                    WalletSpec::Id(id) => (id, None),
+                    WalletSpec::Seeded { id, seed } => (id, Some(seed.as_str())),
+                };
+                let base_url = self.base_url().await?;
+                bootstrap::open_wallet(&base_url, id, seed, self.instance.as_deref()).await?;
+                let address = bootstrap::wallet_address(&base_url, id).await?;
+                self.wallets.insert(id.clone(), address.clone());
+                Ok(format!("Wallet {} ready ({})", id, address))
+            }
+            Step::Fund { wallet, amount } => {
+                let amount = HtrAmount::from_decimal(*amount)?;
+                // Scenario wallets are funded before the next step runs
+                let mut wait_for = None;
+                if self.wallets.contains_key(wallet) {
+                    let base_url = self.base_url().await?;
+                    let before = bootstrap::wallet_balance(&base_url, wallet).await?;
+                    wait_for = Some((base_url, before));
+                }
+                bootstrap::ensure_faucet_funds(state, amount).await?;
+                let payment = faucet::FaucetPayment {
+                    address: self.address_of(wallet),
+                    amount,
+                    timelock: None,
+                };
+                let distribution = faucet::faucet_distribute_internal(state, &[payment]).await?;
+                if let Some((base_url, before)) = wait_for {
+                    bootstrap::wait_for_balance(
+                        &base_url,
+                        wallet,
+                        HtrAmount::from_cents(before.cents() + amount.cents()),
+                    )
+                    .await?;
+                }
+                Ok(distribution.hashes.join(", "))
+            }
+            Step::Send { from, to, amount } => {
+                let amount = HtrAmount::from_decimal(*amount)?;
+                let base_url = self.base_url().await?;
+                bootstrap::send_htr(&base_url, from, &self.address_of(to), amount).await
+            }
+            Step::CreateToken {
+                wallet,
+                name,
+                symbol,
+                amount,
+            } => {
+                let amount = HtrAmount::from_decimal(*amount)?;
+                let token = tokens::create_token_internal(
+                    state,
+                    wallet,
+                    name,
+                    symbol,
+                    amount.cents(),
+                    self.instance.as_deref(),
+                )
+                .await?;
+                Ok(token.token_uid)
+            }
+            Step::SaveSnapshot(name) => {
+                snapshots::save_snapshot_internal(state, name).await?;
+                Ok(format!("Snapshot {} saved", name))
+            }
+            Step::RestoreSnapshot(name) => {
+                snapshots::restore_snapshot_internal(state, name).await?;
+                Ok(format!("Snapshot {} restored", name))
+            }
+            Step::AssertBalance { wallet, bounds } => {
+                bounds.validate()?;
+                let base_url = self.base_url().await?;
+                let deadline = Instant::now() + bounds.within();
+                loop {
+                    let balance = bootstrap::wallet_balance(&base_url, wallet).await?;
+                    if bounds.holds(balance.cents(), 100.0) {
+                        return Ok(format!("Balance {}", balance));
+                    }
+                    if Instant::now() >= deadline {
+                        return Err(format!(
+                            "Balance of {} is {}, expected {}",
+                            wallet,
+                            balance,
+                            bounds.describe()
+                        ));
+                    }
+                    tokio::time::sleep(ASSERT_POLL_INTERVAL).await;
+                }
+            }
+            Step::AssertHeight(bounds) => {
+                bounds.validate()?;
+                let client = reqwest::Client::new();
+                let deadline = Instant::now() + bounds.within();
+                loop {
+                    let (height, _) = rewards::chain_info(&client).await?;
+                    if bounds.holds(height, 1.0) {
+                        return Ok(format!("Height {}", height));
+                    }
+                    if Instant::now() >= deadline {
+                        return Err(format!(
+                            "Height is {}, expected {}",
+                            height,
+                            bounds.describe()
+                        ));
+                    }
+                    tokio::time::sleep(ASSERT_POLL_INTERVAL).await;
+                }
+            }
+        }
+    }
+}
+
+/// Parse a scenario file: JSON for `.json`, YAML otherwise
+pub fn load_scenario(path: &str) -> Result<Scenario, String> {
+    let content =
+        fs::read_to_string(path).map_err(|e| format!("Failed to read scenario: {}", e))?;
+    if path.ends_with(".json") {
+        serde_json::from_str(&content).map_err(|e| format!("Invalid scenario: {}", e))
+    } else {
+        serde_yaml::from_str(&content).map_err(|e| format!("Invalid scenario: {}", e))
+    }
+}
+
+/// Run the scenario at `path`, reporting each step's outcome
+pub async fn run_scenario_internal(
+    state: &SharedState,
+    path: &str,
+) -> Result<ScenarioResult, String> {
+    let scenario = load_scenario(path)?;
+    let started = Instant::now();
+    let mut context = Context {
+        state,
+        instance: scenario.instance.clone(),
+        wallets: HashMap::new(),
+    };
+
+    let mut steps = Vec::new();
+    let mut failed = false;
+    for (index, step) in scenario.steps.iter().enumerate() {
+        let description = step.describe();
+        if failed && scenario.stop_on_failure {
+            steps.push(StepResult {
+                index,
+                description,
+                status: StepStatus::Skipped,
+                message: None,
+                elapsed_ms: 0,
+            });
+            continue;
+        }
+
+        let step_started = Instant::now();
+        let (status, message) = match context.run(step).await {
+            Ok(message) => (StepStatus::Passed, message),
+            Err(e) => {
+                failed = true;
+                (StepStatus::Failed, e)
+            }
+        };
+        steps.push(StepResult {
+            index,
+            description,
+            status,
+            message: Some(message),
+            elapsed_ms: step_started.elapsed().as_millis() as u64,
+        });
+    }
+
+    Ok(ScenarioResult {
+        name: scenario.name.unwrap_or_else(|| path.to_string()),
+        path: path.to_string(),
+        passed: !failed,
+        steps,
+        elapsed_secs: started.elapsed().as_secs(),
+    })
+}
+
+// ============================================================================
+// Tauri Commands
+// ============================================================================
+
+// Run a YAML/JSON scenario of environment steps and assertions
+#[tauri::command]
+pub async fn run_scenario(
+    state: tauri::State<'_, SharedState>,
+    path: String,
+) -> Result<ScenarioResult, String> {
+    run_scenario_internal(&state, &path).await
+}
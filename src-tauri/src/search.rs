@@ -0,0 +1,161 @@
+//! Unified chain search
+//!
+//! `search_chain` takes whatever was typed in the search box and works out
+//! what it is: a number is a block height, 64 hex characters a block or
+//! transaction hash (the node tells which), and anything that decodes as a
+//! localnet address an address. The result is tagged with its `kind` so the
+//! UI can render it without guessing.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::SharedState;
+
+const NODE_API_URL: &str = "http://127.0.0.1:8080/v1a";
+const HASH_LENGTH: usize = 64;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum SearchResult {
+    Block {
+        hash: String,
+        height: Option<u64>,
+        // The node's block JSON
+        block: Value,
+    },
+    Transaction {
+        hash: String,
+        // The node's transaction JSON and metadata
+        tx: Value,
+        meta: Value,
+    },
+    Address {
+        address: String,
+        total_transactions: u64,
+        // Token UID -> received/spent, as reported by the node
+        tokens: Value,
+    },
+    NotFound {
+        query: String,
+        // What the query looked like
+        searched_as: String,
+    },
+}
+
+async fn node_get(
+    client: &reqwest::Client,
+    path: &str,
+    query: &[(&str, &str)],
+) -> Result<Value, String> {
+    client
+        .get(format!("{}/{}", NODE_API_URL, path))
+        .query(query)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to query the node: {}", e))?
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse node response: {}", e))
+}
+
+fn succeeded(response: &Value) -> bool {
+    response["success"].as_bool().unwrap_or(false)
+}
+
+fn is_block(tx: &Value) -> bool {
+    // Regular and merge-mined blocks; the other versions are transactions
+    matches!(tx["version"].as_u64(), Some(0) | Some(3))
+}
+
+fn not_found(query: &str, searched_as: &str) -> SearchResult {
+    SearchResult::NotFound {
+        query: query.to_string(),
+        searched_as: searched_as.to_string(),
+    }
+}
+
+async fn search_height(client: &reqwest::Client, query: &str) -> Result<SearchResult, String> {
+    let response = node_get(client, "block_at_height", &[("height", query)]).await?;
+    if !succeeded(&response) {
+        return Ok(not_found(query, "block height"));
+    }
+    let block = response["block"].clone();
+    Ok(SearchResult::Block {
+        hash: block["tx_id"].as_str().unwrap_or_default().to_string(),
+        height: query.parse().ok(),
+        block,
+    })
+}
+
+async fn search_hash(client: &reqwest::Client, query: &str) -> Result<SearchResult, String> {
+    let response = node_get(client, "transaction", &[("id", query)]).await?;
+    if !succeeded(&response) {
+        return Ok(not_found(query, "hash"));
+    }
+    let tx = response["tx"].clone();
+    let meta = response["meta"].clone();
+    if is_block(&tx) {
+        return Ok(SearchResult::Block {
+            hash: query.to_string(),
+            height: meta["height"].as_u64(),
+            block: tx,
+        });
+    }
+    Ok(SearchResult::Transaction {
+        hash: query.to_string(),
+        tx,
+        meta,
+    })
+}
+
+async fn search_address(client: &reqwest::Client, query: &str) -> Result<SearchResult, String> {
+    let response = node_get(client, "thin_wallet/address_balance", &[("address", query)]).await?;
+    if !succeeded(&response) {
+        let message = response["message"].as_str().unwrap_or("Unknown error");
+        return Err(format!("Failed to get address balance: {}", message));
+    }
+    // Any valid address exists; one never used just has no transactions
+    Ok(SearchResult::Address {
+        address: query.to_string(),
+        total_transactions: response["total_transactions"].as_u64().unwrap_or(0),
+        tokens: response["tokens_data"].clone(),
+    })
+}
+
+/// Look up a block height, block/transaction hash or address on the node
+pub async fn search_chain_internal(
+    state: &SharedState,
+    query: &str,
+) -> Result<SearchResult, String> {
+    if !state.lock().await.node_running {
+        return Err("Node is not running".to_string());
+    }
+    let query = query.trim();
+    if query.is_empty() {
+        return Err("Enter a block height, hash or address".to_string());
+    }
+    let client = reqwest::Client::new();
+
+    if query.chars().all(|c| c.is_ascii_digit()) && query.len() < HASH_LENGTH {
+        return search_height(&client, query).await;
+    }
+    if query.len() == HASH_LENGTH && query.chars().all(|c| c.is_ascii_hexdigit()) {
+        return search_hash(&client, &query.to_lowercase()).await;
+    }
+    crate::address::check_address(query)
+        .map_err(|e| format!("{} is not a block height, hash or address ({})", query, e))?;
+    search_address(&client, query).await
+}
+
+// ============================================================================
+// Tauri Commands
+// ============================================================================
+
+// Find a block, transaction or address from a height, hash or address
+#[tauri::command]
+pub async fn search_chain(
+    state: tauri::State<'_, SharedState>,
+    query: String,
+) -> Result<SearchResult, String> {
+    search_chain_internal(&state, &query).await
+}
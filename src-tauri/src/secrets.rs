@@ -0,0 +1,208 @@
+//! OS keychain storage for wallet seeds and API keys
+//!
+//! Secrets are only written to the keychain (macOS Keychain, Windows Credential
+//! Manager, Secret Service on Linux) after the user explicitly opts in. The
+//! keychain can't enumerate entries, so the *names* of stored secrets are tracked
+//! in `secrets.json` next to the data directory; values never touch disk.
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+
+const KEYRING_SERVICE: &str = "hathor-forge";
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct SecretsIndex {
+    consent: bool,
+    entries: Vec<String>,
+}
+
+fn index_path() -> std::path::PathBuf {
+    crate::get_app_dir().join("secrets.json")
+}
+
+fn load_index() -> SecretsIndex {
+    fs::read_to_string(index_path())
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn save_index(index: &SecretsIndex) -> Result<(), String> {
+    let path = index_path();
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Failed to create app directory: {}", e))?;
+    }
+    let content = serde_json::to_string_pretty(index)
+        .map_err(|e| format!("Failed to serialize secrets index: {}", e))?;
+    fs::write(&path, content).map_err(|e| format!("Failed to write secrets index: {}", e))
+}
+
+fn wallet_seed_key(wallet_id: &str) -> String {
+    format!("wallet-seed:{}", wallet_id)
+}
+
+fn api_key_key(name: &str) -> String {
+    format!("api-key:{}", name)
+}
+
+// Keychain access is blocking (and may prompt the user), so keep it off the async runtime
+async fn keychain<T, F>(f: F) -> Result<T, String>
+where
+    T: Send + 'static,
+    F: FnOnce() -> Result<T, String> + Send + 'static,
+{
+    tokio::task::spawn_blocking(f)
+        .await
+        .map_err(|e| format!("Keychain task failed: {}", e))?
+}
+
+fn entry(name: &str) -> Result<keyring::Entry, String> {
+    keyring::Entry::new(KEYRING_SERVICE, name)
+        .map_err(|e| format!("Failed to open keychain entry: {}", e))
+}
+
+/// Store a secret in the keychain. Returns `false` (without storing) when the
+/// user hasn't consented to keychain storage.
+async fn set_secret(name: String, value: String) -> Result<bool, String> {
+    let mut index = load_index();
+    if !index.consent {
+        return Ok(false);
+    }
+
+    let key = name.clone();
+    keychain(move || {
+        entry(&key)?
+            .set_password(&value)
+            .map_err(|e| format!("Failed to store secret in keychain: {}", e))
+    })
+    .await?;
+
+    if !index.entries.contains(&name) {
+        index.entries.push(name);
+        save_index(&index)?;
+    }
+
+    Ok(true)
+}
+
+async fn get_secret(name: String) -> Result<Option<String>, String> {
+    if !load_index().consent {
+        return Ok(None);
+    }
+
+    keychain(move || match entry(&name)?.get_password() {
+        Ok(value) => Ok(Some(value)),
+        Err(keyring::Error::NoEntry) => Ok(None),
+        Err(e) => Err(format!("Failed to read secret from keychain: {}", e)),
+    })
+    .await
+}
+
+async fn delete_secret(name: String) -> Result<(), String> {
+    let mut index = load_index();
+
+    let key = name.clone();
+    keychain(move || match entry(&key)?.delete_credential() {
+        Ok(()) | Err(keyring::Error::NoEntry) => Ok(()),
+        Err(e) => Err(format!("Failed to delete secret from keychain: {}", e)),
+    })
+    .await?;
+
+    if index.entries.contains(&name) {
+        index.entries.retain(|entry| entry != &name);
+        save_index(&index)?;
+    }
+
+    Ok(())
+}
+
+/// Persist a wallet seed in the keychain (no-op without user consent)
+pub async fn store_wallet_seed(wallet_id: &str, seed: &str) -> Result<bool, String> {
+    set_secret(wallet_seed_key(wallet_id), seed.to_string()).await
+}
+
+/// Load a wallet seed previously stored in the keychain
+pub async fn load_wallet_seed(wallet_id: &str) -> Result<Option<String>, String> {
+    get_secret(wallet_seed_key(wallet_id)).await
+}
+
+/// Load an API key previously stored in the keychain
+pub async fn load_api_key(name: &str) -> Result<Option<String>, String> {
+    get_secret(api_key_key(name)).await
+}
+
+/// Remove every secret Forge has stored in the keychain and turn off
+/// auto-start for the registered wallets, which can't be re-opened without
+/// their seeds. Consent is kept as-is.
+pub async fn forget_all_secrets_internal() -> Result<usize, String> {
+    let entries = load_index().entries;
+    let count = entries.len();
+
+    for name in entries {
+        delete_secret(name).await?;
+    }
+    crate::wallets::disable_all_auto_start()?;
+
+    Ok(count)
+}
+
+// ============================================================================
+// Tauri Commands
+// ============================================================================
+
+// Check whether the user has allowed storing secrets in the OS keychain
+#[tauri::command]
+pub async fn get_secrets_consent() -> Result<bool, String> {
+    Ok(load_index().consent)
+}
+
+// Grant or revoke consent for keychain storage (revoking also forgets stored secrets)
+#[tauri::command]
+pub async fn set_secrets_consent(granted: bool) -> Result<String, String> {
+    if !granted {
+        let count = forget_all_secrets_internal().await?;
+        let mut index = load_index();
+        index.consent = false;
+        save_index(&index)?;
+        return Ok(format!(
+            "Keychain storage disabled ({} secrets removed)",
+            count
+        ));
+    }
+
+    let mut index = load_index();
+    index.consent = true;
+    save_index(&index)?;
+
+    Ok("Keychain storage enabled".to_string())
+}
+
+// Store an API key (e.g. for a remote service) in the OS keychain
+#[tauri::command]
+pub async fn save_api_key(name: String, key: String) -> Result<String, String> {
+    if set_secret(api_key_key(&name), key).await? {
+        Ok(format!("API key '{}' saved to the keychain", name))
+    } else {
+        Err("Keychain storage is disabled. Enable it in settings first.".to_string())
+    }
+}
+
+// Read an API key from the OS keychain
+#[tauri::command]
+pub async fn get_api_key(name: String) -> Result<Option<String>, String> {
+    load_api_key(&name).await
+}
+
+// Remove an API key from the OS keychain
+#[tauri::command]
+pub async fn delete_api_key(name: String) -> Result<String, String> {
+    delete_secret(api_key_key(&name)).await?;
+    Ok(format!("API key '{}' removed from the keychain", name))
+}
+
+// Remove all wallet seeds and API keys stored in the OS keychain
+#[tauri::command]
+pub async fn forget_all_secrets() -> Result<String, String> {
+    let count = forget_all_secrets_internal().await?;
+    Ok(format!("Removed {} secrets from the keychain", count))
+}
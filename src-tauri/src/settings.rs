@@ -0,0 +1,69 @@
+//! JSON settings files in the app directory
+//!
+//! Features keep their settings in a `<name>.json` file in the app directory,
+//! where a missing or unreadable file means the defaults. Settings checked on
+//! every request are read through `read`, which loads the file on first use
+//! and is kept current by `save`, so changes apply without a restart.
+
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::{OnceLock, RwLock, RwLockReadGuard};
+
+/// A settings value stored as JSON in the app directory
+pub struct JsonSettings<T> {
+    file_name: &'static str,
+    // What the file holds, for error messages (e.g. "CORS settings")
+    what: &'static str,
+    cached: OnceLock<RwLock<T>>,
+}
+
+impl<T> JsonSettings<T> {
+    pub const fn new(file_name: &'static str, what: &'static str) -> Self {
+        Self {
+            file_name,
+            what,
+            cached: OnceLock::new(),
+        }
+    }
+}
+
+impl<T: Serialize + DeserializeOwned + Default + Clone> JsonSettings<T> {
+    pub fn path(&self) -> PathBuf {
+        crate::get_app_dir().join(self.file_name)
+    }
+
+    /// Read the settings from disk, falling back to the defaults
+    pub fn load(&self) -> T {
+        fs::read_to_string(self.path())
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    /// Write the settings to disk and to the cached copy, if one was loaded
+    pub fn save(&self, settings: &T) -> Result<(), String> {
+        let path = self.path();
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create app directory: {}", e))?;
+        }
+        let content = serde_json::to_string_pretty(settings)
+            .map_err(|e| format!("Failed to serialize {}: {}", self.what, e))?;
+        fs::write(&path, content).map_err(|e| format!("Failed to write {}: {}", self.what, e))?;
+
+        if let Some(cached) = self.cached.get() {
+            *cached.write().unwrap_or_else(|e| e.into_inner()) = settings.clone();
+        }
+        Ok(())
+    }
+
+    /// The cached settings, loaded from disk on first use
+    pub fn read(&self) -> RwLockReadGuard<'_, T> {
+        self.cached
+            .get_or_init(|| RwLock::new(self.load()))
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+    }
+}
@@ -0,0 +1,158 @@
+//! Scheduled snapshots
+//!
+//! While the node runs, a snapshot can be taken automatically every N minutes
+//! and/or every N blocks, keeping only the last few automatic ones (snapshots
+//! saved by hand are never pruned). Each one restarts the node like
+//! `save_snapshot` does. The schedule lives in `snapshot_schedule.json` in the
+//! app directory and is emitted as `snapshot-saved` when a snapshot is taken.
+
+use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter};
+
+use crate::settings::JsonSettings;
+use crate::snapshots::{self, SnapshotInfo};
+use crate::SharedState;
+
+const CHECK_INTERVAL: Duration = Duration::from_secs(15);
+// Pause after a failed snapshot so a persistent error doesn't keep restarting the node
+const RETRY_AFTER_FAILURE: Duration = Duration::from_secs(300);
+const MAX_KEEP_LAST: u32 = 100;
+
+fn default_keep_last() -> u32 {
+    5
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnapshotSchedule {
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default)]
+    pub every_minutes: Option<u64>,
+    #[serde(default)]
+    pub every_blocks: Option<u64>,
+    // Automatic snapshots kept; older ones are deleted
+    #[serde(default = "default_keep_last")]
+    pub keep_last: u32,
+}
+
+impl Default for SnapshotSchedule {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            every_minutes: None,
+            every_blocks: None,
+            keep_last: default_keep_last(),
+        }
+    }
+}
+
+pub static SETTINGS: JsonSettings<SnapshotSchedule> =
+    JsonSettings::new("snapshot_schedule.json", "snapshot schedule");
+
+/// Validate and store a schedule
+pub fn set_schedule(schedule: SnapshotSchedule) -> Result<SnapshotSchedule, String> {
+    if schedule.enabled && schedule.every_minutes.is_none() && schedule.every_blocks.is_none() {
+        return Err("Set every_minutes and/or every_blocks to schedule snapshots".to_string());
+    }
+    if schedule.every_minutes == Some(0) || schedule.every_blocks == Some(0) {
+        return Err("Snapshot intervals must be at least 1".to_string());
+    }
+    if !(1..=MAX_KEEP_LAST).contains(&schedule.keep_last) {
+        return Err(format!("keep_last must be between 1 and {}", MAX_KEEP_LAST));
+    }
+    SETTINGS.save(&schedule)?;
+    Ok(schedule)
+}
+
+// Newest automatic snapshot, if any
+fn last_automatic() -> Option<SnapshotInfo> {
+    snapshots::list_snapshots_internal()
+        .into_iter()
+        .find(|snapshot| snapshot.automatic)
+}
+
+// Whether a snapshot is due, given the current height and when the schedule
+// started watching (the reference when there is no automatic snapshot yet)
+fn is_due(schedule: &SnapshotSchedule, height: Option<u64>, watching_since: Instant) -> bool {
+    let last = last_automatic();
+
+    let time_due = schedule.every_minutes.is_some_and(|minutes| {
+        let elapsed = match &last {
+            Some(last) => crate::unix_now().saturating_sub(last.created_at),
+            None => watching_since.elapsed().as_secs(),
+        };
+        elapsed >= minutes * 60
+    });
+    let blocks_due = schedule.every_blocks.is_some_and(|blocks| {
+        let last_height = last
+            .as_ref()
+            .and_then(|last| last.best_block_height)
+            .unwrap_or(0);
+        height.is_some_and(|height| height >= last_height + blocks)
+    });
+
+    time_due || blocks_due
+}
+
+// Delete automatic snapshots beyond the newest `keep_last`
+fn prune(keep_last: u32) {
+    for snapshot in snapshots::list_snapshots_internal()
+        .into_iter()
+        .filter(|snapshot| snapshot.automatic)
+        .skip(keep_last as usize)
+    {
+        if let Err(e) = snapshots::delete_snapshot_internal(&snapshot.name) {
+            eprintln!("Failed to prune snapshot '{}': {}", snapshot.name, e);
+        }
+    }
+}
+
+/// Take a snapshot whenever the schedule is due, for as long as the app runs
+pub async fn run_schedule(app: AppHandle, state: SharedState) {
+    let mut watching_since = Instant::now();
+
+    loop {
+        tokio::time::sleep(CHECK_INTERVAL).await;
+
+        let schedule = SETTINGS.load();
+        if !schedule.enabled || !state.lock().await.node_running {
+            watching_since = Instant::now();
+            continue;
+        }
+
+        let height = snapshots::best_block_height().await;
+        if !is_due(&schedule, height, watching_since) {
+            continue;
+        }
+
+        let name = format!("auto-{}", crate::unix_now());
+        match snapshots::save(&state, &name, true).await {
+            Ok(result) => {
+                let _ = app.emit("snapshot-saved", &result.snapshot);
+                prune(schedule.keep_last);
+            }
+            Err(e) => {
+                eprintln!("Scheduled snapshot failed: {}", e);
+                tokio::time::sleep(RETRY_AFTER_FAILURE).await;
+            }
+        }
+        watching_since = Instant::now();
+    }
+}
+
+// ============================================================================
+// Tauri Commands
+// ============================================================================
+
+// Get the automatic snapshot schedule
+#[tauri::command]
+pub async fn get_snapshot_schedule() -> Result<SnapshotSchedule, String> {
+    Ok(SETTINGS.load())
+}
+
+// Set the automatic snapshot schedule and retention
+#[tauri::command]
+pub async fn set_snapshot_schedule(schedule: SnapshotSchedule) -> Result<SnapshotSchedule, String> {
+    set_schedule(schedule)
+}
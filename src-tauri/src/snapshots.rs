@@ -0,0 +1,323 @@
+//! Named blockchain snapshots
+//!
+//! A snapshot is a gzipped tarball of the node's data directory (including
+//! the workspace's wallet registry), stored under `snapshots/` in the app
+//! directory with a small JSON file describing it.
+//! The node is stopped while its data is archived or swapped back (which also
+//! stops miners and wallet-headless) and restarted afterwards if it was
+//! running, so fixtures that took a long time to mine can be rolled back to
+//! in one step.
+
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::{NodeConfig, SharedState};
+
+const MAX_NAME_LEN: usize = 64;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnapshotInfo {
+    pub name: String,
+    pub created_at: u64, // Unix timestamp
+    // Best block when the snapshot was taken, if the node was running
+    pub best_block_height: Option<u64>,
+    // Taken by the snapshot schedule, and subject to its retention
+    #[serde(default)]
+    pub automatic: bool,
+    #[serde(default)]
+    pub size_bytes: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnapshotResult {
+    pub snapshot: SnapshotInfo,
+    // Whether the node was stopped for the operation and started again
+    pub node_restarted: bool,
+}
+
+/// Where snapshot archives and their descriptions are stored
+pub fn snapshots_dir() -> PathBuf {
+    crate::get_app_dir().join("snapshots")
+}
+
+fn archive_path(name: &str) -> PathBuf {
+    snapshots_dir().join(format!("{}.tar.gz", name))
+}
+
+fn info_path(name: &str) -> PathBuf {
+    snapshots_dir().join(format!("{}.json", name))
+}
+
+fn validate_name(name: &str) -> Result<(), String> {
+    if name.is_empty() || name.len() > MAX_NAME_LEN {
+        return Err(format!(
+            "Snapshot name must be 1 to {} characters",
+            MAX_NAME_LEN
+        ));
+    }
+    if !name
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        return Err("Snapshot name may only contain letters, digits, '-' and '_'".to_string());
+    }
+    Ok(())
+}
+
+fn load_info(name: &str) -> Option<SnapshotInfo> {
+    let mut info: SnapshotInfo = fs::read_to_string(info_path(name))
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())?;
+    info.size_bytes = fs::metadata(archive_path(name)).ok()?.len();
+    Some(info)
+}
+
+/// Best block height of the running node
+pub async fn best_block_height() -> Option<u64> {
+    let url = format!(
+        "http://127.0.0.1:{}/v1a/status/",
+        NodeConfig::default().api_port
+    );
+    let status: serde_json::Value = reqwest::get(url).await.ok()?.json().await.ok()?;
+    status["dag"]["best_block"]["height"].as_u64()
+}
+
+// Stop the node if it runs, returning whether it did
+async fn stop_node(state: &SharedState) -> Result<bool, String> {
+    if !state.lock().await.node_running {
+        return Ok(false);
+    }
+    crate::stop_node_internal(state).await?;
+    // Give the OS a moment to release the database files
+    tokio::time::sleep(Duration::from_millis(500)).await;
+    Ok(true)
+}
+
+fn write_archive(archive: &Path, data_dir: &Path) -> Result<(), String> {
+    let partial = archive.with_extension("partial");
+    let file =
+        fs::File::create(&partial).map_err(|e| format!("Failed to create snapshot: {}", e))?;
+    let mut builder = tar::Builder::new(GzEncoder::new(file, Compression::fast()));
+
+    if data_dir.exists() {
+        builder
+            .append_dir("data", data_dir)
+            .map_err(|e| format!("Failed to archive data directory: {}", e))?;
+        let entries =
+            fs::read_dir(data_dir).map_err(|e| format!("Failed to read data directory: {}", e))?;
+        for entry in entries.flatten() {
+            // Log files stay with the workspace, not the snapshot
+            if entry.file_name() == crate::logging::LOGS_DIR {
+                continue;
+            }
+            let path = entry.path();
+            let name = Path::new("data").join(entry.file_name());
+            let appended = if path.is_dir() {
+                builder.append_dir_all(&name, &path)
+            } else {
+                builder.append_path_with_name(&path, &name)
+            };
+            appended.map_err(|e| format!("Failed to archive data directory: {}", e))?;
+        }
+    }
+    builder
+        .into_inner()
+        .and_then(|encoder| encoder.finish())
+        .map_err(|e| format!("Failed to write snapshot: {}", e))?;
+
+    fs::rename(&partial, archive).map_err(|e| format!("Failed to save snapshot: {}", e))
+}
+
+/// Directory a snapshot is unpacked into before being swapped in, next to the
+/// data directory so the swap is a rename
+pub fn staging_dir(data_dir: &Path) -> PathBuf {
+    data_dir.with_extension("restore")
+}
+
+fn extract_archive(archive: &Path, data_dir: &Path) -> Result<(), String> {
+    let staging = staging_dir(data_dir);
+    if staging.exists() {
+        fs::remove_dir_all(&staging)
+            .map_err(|e| format!("Failed to clear restore directory: {}", e))?;
+    }
+    let file = fs::File::open(archive).map_err(|e| format!("Failed to open snapshot: {}", e))?;
+    tar::Archive::new(GzDecoder::new(file))
+        .unpack(&staging)
+        .map_err(|e| format!("Failed to extract snapshot: {}", e))?;
+
+    // The current log files survive the restore
+    crate::logging::close_files();
+    let logs = data_dir.join(crate::logging::LOGS_DIR);
+    let kept_logs = staging.join(crate::logging::LOGS_DIR);
+    if logs.exists() {
+        fs::rename(&logs, &kept_logs).map_err(|e| format!("Failed to keep log files: {}", e))?;
+    }
+
+    if data_dir.exists() {
+        fs::remove_dir_all(data_dir)
+            .map_err(|e| format!("Failed to remove data directory: {}", e))?;
+    }
+    let restored_data = staging.join("data");
+    if restored_data.exists() {
+        fs::rename(&restored_data, data_dir)
+            .map_err(|e| format!("Failed to restore data directory: {}", e))?;
+    }
+    if kept_logs.exists() {
+        fs::create_dir_all(data_dir)
+            .map_err(|e| format!("Failed to restore data directory: {}", e))?;
+        let _ = fs::remove_dir_all(&logs);
+        fs::rename(&kept_logs, &logs).map_err(|e| format!("Failed to restore log files: {}", e))?;
+    }
+
+    let _ = fs::remove_dir_all(&staging);
+    Ok(())
+}
+
+/// Archive the node data and wallet registry as snapshot `name`, replacing
+/// any snapshot with that name
+pub async fn save_snapshot_internal(
+    state: &SharedState,
+    name: &str,
+) -> Result<SnapshotResult, String> {
+    save(state, name, false).await
+}
+
+/// Save a snapshot, marked as taken by the schedule if `automatic`
+pub async fn save(
+    state: &SharedState,
+    name: &str,
+    automatic: bool,
+) -> Result<SnapshotResult, String> {
+    validate_name(name)?;
+    fs::create_dir_all(snapshots_dir())
+        .map_err(|e| format!("Failed to create snapshots directory: {}", e))?;
+
+    let best_block_height = if state.lock().await.node_running {
+        best_block_height().await
+    } else {
+        None
+    };
+    let was_running = stop_node(state).await?;
+    let data_dir = crate::workspace_data_dir(&*state.lock().await);
+
+    let archive = archive_path(name);
+    let archived = tokio::task::spawn_blocking(move || write_archive(&archive, &data_dir))
+        .await
+        .map_err(|e| format!("Snapshot task failed: {}", e))?;
+
+    // Bring the node back even if archiving failed
+    if was_running {
+        crate::start_node_internal(state).await?;
+    }
+    archived?;
+
+    let info = SnapshotInfo {
+        name: name.to_string(),
+        created_at: crate::unix_now(),
+        best_block_height,
+        automatic,
+        size_bytes: 0,
+    };
+    let content = serde_json::to_string_pretty(&info)
+        .map_err(|e| format!("Failed to serialize snapshot info: {}", e))?;
+    fs::write(info_path(name), content)
+        .map_err(|e| format!("Failed to write snapshot info: {}", e))?;
+
+    Ok(SnapshotResult {
+        snapshot: load_info(name).unwrap_or(info),
+        node_restarted: was_running,
+    })
+}
+
+/// Replace the node data and wallet registry with snapshot `name`
+pub async fn restore_snapshot_internal(
+    state: &SharedState,
+    name: &str,
+) -> Result<SnapshotResult, String> {
+    validate_name(name)?;
+    let info = load_info(name).ok_or_else(|| format!("Snapshot '{}' not found", name))?;
+
+    let was_running = stop_node(state).await?;
+    let data_dir = crate::workspace_data_dir(&*state.lock().await);
+
+    let archive = archive_path(name);
+    tokio::task::spawn_blocking(move || extract_archive(&archive, &data_dir))
+        .await
+        .map_err(|e| format!("Restore task failed: {}", e))??;
+
+    if was_running {
+        crate::start_node_internal(state).await?;
+    }
+
+    Ok(SnapshotResult {
+        snapshot: info,
+        node_restarted: was_running,
+    })
+}
+
+/// Saved snapshots, newest first
+pub fn list_snapshots_internal() -> Vec<SnapshotInfo> {
+    let mut snapshots: Vec<SnapshotInfo> = fs::read_dir(snapshots_dir())
+        .map(|entries| {
+            entries
+                .filter_map(|entry| entry.ok())
+                .filter_map(|entry| {
+                    let file_name = entry.file_name().to_string_lossy().to_string();
+                    file_name.strip_suffix(".json").and_then(load_info)
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    snapshots.sort_by_key(|snapshot| std::cmp::Reverse(snapshot.created_at));
+    snapshots
+}
+
+/// Delete snapshot `name`
+pub fn delete_snapshot_internal(name: &str) -> Result<String, String> {
+    validate_name(name)?;
+    if !info_path(name).exists() {
+        return Err(format!("Snapshot '{}' not found", name));
+    }
+    let _ = fs::remove_file(archive_path(name));
+    fs::remove_file(info_path(name)).map_err(|e| format!("Failed to delete snapshot: {}", e))?;
+    Ok(format!("Snapshot '{}' deleted", name))
+}
+
+// ============================================================================
+// Tauri Commands
+// ============================================================================
+
+// Archive the node data and wallet registry under a name
+#[tauri::command]
+pub async fn save_snapshot(
+    state: tauri::State<'_, SharedState>,
+    name: String,
+) -> Result<SnapshotResult, String> {
+    save_snapshot_internal(&state, &name).await
+}
+
+// Roll the node data and wallet registry back to a snapshot
+#[tauri::command]
+pub async fn restore_snapshot(
+    state: tauri::State<'_, SharedState>,
+    name: String,
+) -> Result<SnapshotResult, String> {
+    restore_snapshot_internal(&state, &name).await
+}
+
+// List saved snapshots with their sizes and timestamps
+#[tauri::command]
+pub async fn list_snapshots() -> Result<Vec<SnapshotInfo>, String> {
+    Ok(list_snapshots_internal())
+}
+
+// Delete a snapshot
+#[tauri::command]
+pub async fn delete_snapshot(name: String) -> Result<String, String> {
+    delete_snapshot_internal(&name)
+}
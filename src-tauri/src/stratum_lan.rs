@@ -0,0 +1,168 @@
+//! Stratum access from the LAN
+//!
+//! Forge's own miners talk to the node's stratum port on 127.0.0.1. To let
+//! miners on other machines (e.g. a real ASIC) mine on the localnet, LAN access
+//! opens a port on 0.0.0.0 that relays every connection to the node's stratum
+//! server (through the stratum tap, so their jobs show up in `get_stratum_jobs`). It is off by default; the setting lives in `stratum.json` in the app
+//! directory and is applied whenever the node starts.
+
+use serde::{Deserialize, Serialize};
+use std::net::{SocketAddr, UdpSocket};
+
+use crate::settings::JsonSettings;
+use crate::{stratum_tap, NodeConfig, SharedState};
+
+const DEFAULT_LAN_PORT: u16 = 8100;
+
+const LAN_WARNING: &str = "Stratum is reachable by every machine on your network. \
+Anyone who can connect can mine on this localnet; only enable it on networks you trust.";
+
+fn default_lan_port() -> u16 {
+    DEFAULT_LAN_PORT
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StratumLanSettings {
+    #[serde(default)]
+    pub enabled: bool,
+    // LAN-facing port relayed to the node's stratum port
+    #[serde(default = "default_lan_port")]
+    pub port: u16,
+}
+
+impl Default for StratumLanSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            port: DEFAULT_LAN_PORT,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StratumConnectionInfo {
+    pub lan_enabled: bool,
+    pub relay_running: bool,
+    // URL for miners on this machine
+    pub local_url: String,
+    // URL for miners on other machines (when LAN access is on)
+    pub lan_url: Option<String>,
+    pub lan_ip: Option<String>,
+    pub warning: Option<String>,
+}
+
+static SETTINGS: JsonSettings<StratumLanSettings> =
+    JsonSettings::new("stratum.json", "stratum settings");
+
+// Address of the interface used for outbound traffic (no packet is sent)
+pub fn lan_ip() -> Option<String> {
+    let socket = UdpSocket::bind("0.0.0.0:0").ok()?;
+    socket.connect("8.8.8.8:80").ok()?;
+    let ip = socket.local_addr().ok()?.ip();
+    (!ip.is_loopback() && !ip.is_unspecified()).then(|| ip.to_string())
+}
+
+/// Start the LAN relay if LAN access is enabled and it isn't running yet
+pub async fn start_relay(state: SharedState) -> Result<(), String> {
+    let settings = SETTINGS.load();
+    if !settings.enabled || state.lock().await.stratum_relay.is_some() {
+        return Ok(());
+    }
+
+    let listener = tokio::net::TcpListener::bind(SocketAddr::from(([0, 0, 0, 0], settings.port)))
+        .await
+        .map_err(|e| format!("Failed to bind stratum LAN port {}: {}", settings.port, e))?;
+
+    let (shutdown_tx, mut shutdown_rx) = tokio::sync::oneshot::channel::<()>();
+    state.lock().await.stratum_relay = Some(shutdown_tx);
+
+    tokio::spawn(async move {
+        loop {
+            let inbound = tokio::select! {
+                _ = &mut shutdown_rx => break,
+                accepted = listener.accept() => match accepted {
+                    Ok((stream, _)) => stream,
+                    Err(_) => continue,
+                },
+            };
+            tokio::spawn(stratum_tap::tap_connection(state.clone(), inbound));
+        }
+    });
+
+    Ok(())
+}
+
+/// Stop the LAN relay (connections already relayed stay open until they close)
+pub async fn stop_relay(state: &SharedState) {
+    if let Some(shutdown_tx) = state.lock().await.stratum_relay.take() {
+        let _ = shutdown_tx.send(());
+    }
+}
+
+/// How miners on this machine and on the LAN can reach the node's stratum server
+pub async fn get_stratum_connection_info_internal(state: &SharedState) -> StratumConnectionInfo {
+    let settings = SETTINGS.load();
+    let relay_running = state.lock().await.stratum_relay.is_some();
+    let lan_ip = lan_ip();
+
+    StratumConnectionInfo {
+        lan_enabled: settings.enabled,
+        relay_running,
+        local_url: format!(
+            "stratum+tcp://127.0.0.1:{}",
+            NodeConfig::default().stratum_port
+        ),
+        lan_url: lan_ip
+            .as_ref()
+            .filter(|_| settings.enabled)
+            .map(|ip| format!("stratum+tcp://{}:{}", ip, settings.port)),
+        lan_ip,
+        warning: settings.enabled.then(|| LAN_WARNING.to_string()),
+    }
+}
+
+/// Turn LAN access to stratum on or off, applying it right away if the node runs
+pub async fn set_stratum_lan_internal(
+    state: &SharedState,
+    enabled: bool,
+    port: Option<u16>,
+) -> Result<StratumConnectionInfo, String> {
+    let mut settings = SETTINGS.load();
+    let port_changed = port.is_some_and(|p| p != settings.port);
+    settings.enabled = enabled;
+    if let Some(port) = port {
+        settings.port = port;
+    }
+    SETTINGS.save(&settings)?;
+
+    if !enabled || port_changed {
+        stop_relay(state).await;
+    }
+    if enabled && state.lock().await.node_running {
+        start_relay(state.clone()).await?;
+    }
+
+    Ok(get_stratum_connection_info_internal(state).await)
+}
+
+// ============================================================================
+// Tauri Commands
+// ============================================================================
+
+// Get the local and LAN stratum URLs
+#[tauri::command]
+pub async fn get_stratum_connection_info(
+    state: tauri::State<'_, SharedState>,
+) -> Result<StratumConnectionInfo, String> {
+    Ok(get_stratum_connection_info_internal(&state).await)
+}
+
+// Expose stratum to the LAN (or stop doing so)
+#[tauri::command]
+pub async fn set_stratum_lan(
+    state: tauri::State<'_, SharedState>,
+    enabled: bool,
+    port: Option<u16>,
+) -> Result<StratumConnectionInfo, String> {
+    set_stratum_lan_internal(&state, enabled, port).await
+}
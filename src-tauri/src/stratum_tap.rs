@@ -0,0 +1,298 @@
+//! Stratum traffic inspection
+//!
+//! While the node runs, a tap listens on 127.0.0.1 next to the node's stratum
+//! port and forwards every connection to it line by line. Forge's own miners
+//! connect through the tap (as do LAN miners, via the relay), and the JSON-RPC
+//! messages passing through are parsed: jobs sent by the node with their
+//! weight, and the miners' submissions with the node's answer. The most recent
+//! jobs are kept in `AppState` so connectivity problems can be debugged from
+//! Forge instead of with tcpdump.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{HashMap, VecDeque};
+use std::net::SocketAddr;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::TcpStream;
+
+use crate::{NodeConfig, SharedState};
+
+pub const TAP_PORT: u16 = 8002;
+const MAX_JOBS: usize = 200;
+const DEFAULT_JOBS_LIMIT: usize = 20;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StratumSubmission {
+    pub submitted_at: u64, // Unix timestamp
+    // None until the node answers
+    pub accepted: Option<bool>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StratumJob {
+    pub job_id: String,
+    // Miner connection the job was sent to
+    pub connection: u64,
+    pub peer: String,
+    pub received_at: u64, // Unix timestamp
+    // Block weight of the job (log2 of the expected hashes)
+    pub weight: Option<f64>,
+    pub submissions: Vec<StratumSubmission>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StratumJobs {
+    pub tap_running: bool,
+    pub tap_url: String,
+    pub open_connections: usize,
+    pub total_jobs: u64,
+    pub accepted_submissions: u64,
+    pub rejected_submissions: u64,
+    // Newest first
+    pub jobs: Vec<StratumJob>,
+}
+
+/// Recent stratum traffic, kept in `AppState`
+#[derive(Debug, Default)]
+pub struct StratumLog {
+    next_connection: u64,
+    open_connections: usize,
+    total_jobs: u64,
+    accepted: u64,
+    rejected: u64,
+    jobs: VecDeque<StratumJob>,
+    // (connection, request id) -> job id of submissions awaiting an answer
+    pending: HashMap<(u64, String), String>,
+}
+
+impl StratumLog {
+    fn job_mut(&mut self, connection: u64, job_id: &str) -> Option<&mut StratumJob> {
+        self.jobs
+            .iter_mut()
+            .rev()
+            .find(|job| job.connection == connection && job.job_id == job_id)
+    }
+
+    // A line the node sent to a miner
+    fn record_from_node(&mut self, connection: u64, peer: &str, message: &Value) {
+        match message["method"].as_str() {
+            Some("job") | Some("mining.notify") => {
+                let params = &message["params"];
+                let Some(job_id) = params["job_id"].as_str().or(params[0].as_str()) else {
+                    return;
+                };
+                self.total_jobs += 1;
+                self.jobs.push_back(StratumJob {
+                    job_id: job_id.to_string(),
+                    connection,
+                    peer: peer.to_string(),
+                    received_at: crate::unix_now(),
+                    weight: params["weight"].as_f64(),
+                    submissions: Vec::new(),
+                });
+                while self.jobs.len() > MAX_JOBS {
+                    self.jobs.pop_front();
+                }
+            }
+            // An answer to a request, possibly a submission
+            None => {
+                let Some(job_id) = self
+                    .pending
+                    .remove(&(connection, message["id"].to_string()))
+                else {
+                    return;
+                };
+                let error = match &message["error"] {
+                    Value::Null => None,
+                    error => Some(
+                        error["message"]
+                            .as_str()
+                            .map(|s| s.to_string())
+                            .unwrap_or_else(|| error.to_string()),
+                    ),
+                };
+                let accepted = error.is_none() && message["result"] != Value::Bool(false);
+                if accepted {
+                    self.accepted += 1;
+                } else {
+                    self.rejected += 1;
+                }
+                if let Some(submission) = self.job_mut(connection, &job_id).and_then(|job| {
+                    job.submissions
+                        .iter_mut()
+                        .rev()
+                        .find(|s| s.accepted.is_none())
+                }) {
+                    submission.accepted = Some(accepted);
+                    submission.error = error;
+                }
+            }
+            Some(_) => {}
+        }
+    }
+
+    // A line a miner sent to the node
+    fn record_from_miner(&mut self, connection: u64, message: &Value) {
+        let params = &message["params"];
+        let job_id = match message["method"].as_str() {
+            Some("submit") => params["job_id"].as_str(),
+            Some("mining.submit") => params[1].as_str(),
+            _ => None,
+        };
+        let Some(job_id) = job_id else {
+            return;
+        };
+
+        self.pending
+            .insert((connection, message["id"].to_string()), job_id.to_string());
+        if let Some(job) = self.job_mut(connection, job_id) {
+            job.submissions.push(StratumSubmission {
+                submitted_at: crate::unix_now(),
+                accepted: None,
+                error: None,
+            });
+        }
+    }
+}
+
+// Forward lines from `reader` to `writer`, recording each with `record`
+async fn forward<R, W, F>(reader: R, mut writer: W, state: SharedState, mut record: F)
+where
+    R: tokio::io::AsyncRead + Unpin,
+    W: tokio::io::AsyncWrite + Unpin,
+    F: FnMut(&mut StratumLog, &Value),
+{
+    let mut lines = BufReader::new(reader).lines();
+    while let Ok(Some(line)) = lines.next_line().await {
+        if let Ok(message) = serde_json::from_str::<Value>(&line) {
+            record(&mut state.lock().await.stratum_log, &message);
+        }
+        if writer.write_all(line.as_bytes()).await.is_err()
+            || writer.write_all(b"\n").await.is_err()
+        {
+            break;
+        }
+    }
+    let _ = writer.shutdown().await;
+}
+
+/// Relay a miner connection to the node's stratum server, recording its jobs
+/// and submissions
+pub async fn tap_connection(state: SharedState, inbound: TcpStream) {
+    let stratum_addr = SocketAddr::from(([127, 0, 0, 1], NodeConfig::default().stratum_port));
+    let Ok(outbound) = TcpStream::connect(stratum_addr).await else {
+        return;
+    };
+    let peer = inbound
+        .peer_addr()
+        .map(|addr| addr.to_string())
+        .unwrap_or_default();
+
+    let connection = {
+        let mut state_guard = state.lock().await;
+        let log = &mut state_guard.stratum_log;
+        log.next_connection += 1;
+        log.open_connections += 1;
+        log.next_connection
+    };
+
+    let (miner_read, miner_write) = inbound.into_split();
+    let (node_read, node_write) = outbound.into_split();
+    let to_node = forward(
+        miner_read,
+        node_write,
+        state.clone(),
+        move |log, message| log.record_from_miner(connection, message),
+    );
+    let to_miner = forward(
+        node_read,
+        miner_write,
+        state.clone(),
+        move |log, message| log.record_from_node(connection, &peer, message),
+    );
+    // Either side closing ends the connection
+    tokio::select! {
+        _ = to_node => {}
+        _ = to_miner => {}
+    }
+
+    let mut state_guard = state.lock().await;
+    let log = &mut state_guard.stratum_log;
+    log.open_connections = log.open_connections.saturating_sub(1);
+    log.pending.retain(|(c, _), _| *c != connection);
+}
+
+/// Start the local tap if it isn't running yet
+pub async fn start_tap(state: SharedState) -> Result<(), String> {
+    if state.lock().await.stratum_tap.is_some() {
+        return Ok(());
+    }
+
+    let listener = tokio::net::TcpListener::bind(SocketAddr::from(([127, 0, 0, 1], TAP_PORT)))
+        .await
+        .map_err(|e| format!("Failed to bind stratum tap port {}: {}", TAP_PORT, e))?;
+
+    let (shutdown_tx, mut shutdown_rx) = tokio::sync::oneshot::channel::<()>();
+    state.lock().await.stratum_tap = Some(shutdown_tx);
+
+    tokio::spawn(async move {
+        loop {
+            let inbound = tokio::select! {
+                _ = &mut shutdown_rx => break,
+                accepted = listener.accept() => match accepted {
+                    Ok((stream, _)) => stream,
+                    Err(_) => continue,
+                },
+            };
+            tokio::spawn(tap_connection(state.clone(), inbound));
+        }
+    });
+
+    Ok(())
+}
+
+/// Stratum port a miner should connect to: the tap when it runs and the miner
+/// targets the node's stratum server, `stratum_port` otherwise
+pub fn miner_port(state: &crate::AppState, stratum_port: u16) -> u16 {
+    if state.stratum_tap.is_some() && stratum_port == NodeConfig::default().stratum_port {
+        TAP_PORT
+    } else {
+        stratum_port
+    }
+}
+
+/// The most recent stratum jobs (up to `limit`, newest first) with their submissions
+pub async fn get_stratum_jobs_internal(state: &SharedState, limit: Option<usize>) -> StratumJobs {
+    let state_guard = state.lock().await;
+    let log = &state_guard.stratum_log;
+
+    StratumJobs {
+        tap_running: state_guard.stratum_tap.is_some(),
+        tap_url: format!("stratum+tcp://127.0.0.1:{}", TAP_PORT),
+        open_connections: log.open_connections,
+        total_jobs: log.total_jobs,
+        accepted_submissions: log.accepted,
+        rejected_submissions: log.rejected,
+        jobs: log
+            .jobs
+            .iter()
+            .rev()
+            .take(limit.unwrap_or(DEFAULT_JOBS_LIMIT))
+            .cloned()
+            .collect(),
+    }
+}
+
+// ============================================================================
+// Tauri Commands
+// ============================================================================
+
+// Get recent stratum jobs and the miners' submissions for them
+#[tauri::command]
+pub async fn get_stratum_jobs(
+    state: tauri::State<'_, SharedState>,
+    limit: Option<usize>,
+) -> Result<StratumJobs, String> {
+    Ok(get_stratum_jobs_internal(&state, limit).await)
+}
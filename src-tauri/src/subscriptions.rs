@@ -0,0 +1,227 @@
+//! Address subscriptions on the node WebSocket
+//!
+//! Instead of forwarding every node WebSocket message to the UI, Forge keeps
+//! one connection with a `subscribe_address` per watched address and emits
+//! `address-activity` only for transactions touching them. The set of
+//! addresses lives in `AppState.address_subscriptions`; the background task
+//! follows it, sending (un)subscribe messages as it changes and subscribing
+//! to everything again whenever it reconnects (e.g. after a node restart).
+
+use futures_util::{SinkExt, StreamExt};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::{BTreeMap, BTreeSet};
+use std::time::Duration;
+use tauri::{AppHandle, Emitter};
+use tokio::sync::watch;
+use tokio_tungstenite::tungstenite::Message;
+
+use crate::SharedState;
+
+const FULLNODE_WS_URL: &str = "ws://127.0.0.1:8080/v1a/ws/";
+const RECONNECT_DELAY: Duration = Duration::from_secs(2);
+const HTR_UID: &str = "00";
+
+/// A transaction touching a watched address
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AddressActivity {
+    pub address: String,
+    pub tx_id: String,
+    pub timestamp: Option<u64>,
+    pub is_voided: bool,
+    // Token UID -> net change for the address in cents (received minus spent)
+    pub balance_changes: BTreeMap<String, i64>,
+}
+
+/// Watched addresses, kept in `AppState`
+pub struct AddressSubscriptions {
+    addresses: watch::Sender<BTreeSet<String>>,
+}
+
+impl Default for AddressSubscriptions {
+    fn default() -> Self {
+        Self {
+            addresses: watch::channel(BTreeSet::new()).0,
+        }
+    }
+}
+
+impl AddressSubscriptions {
+    fn list(&self) -> Vec<String> {
+        self.addresses.borrow().iter().cloned().collect()
+    }
+}
+
+fn subscription_message(kind: &str, address: &str) -> Message {
+    Message::Text(json!({ "type": kind, "address": address }).to_string())
+}
+
+// Net change per token for `address`, skipping authority outputs (they carry
+// flags, not amounts)
+fn balance_changes(tx: &Value, address: &str) -> BTreeMap<String, i64> {
+    let mut changes = BTreeMap::new();
+    for (field, sign) in [("outputs", 1), ("inputs", -1)] {
+        let entries = tx[field].as_array().into_iter().flatten();
+        for entry in entries.filter(|entry| entry["decoded"]["address"].as_str() == Some(address)) {
+            if entry["token_data"].as_u64().unwrap_or(0) & 0x80 != 0 {
+                continue;
+            }
+            let token = entry["token"].as_str().unwrap_or(HTR_UID).to_string();
+            let value = entry["value"].as_i64().unwrap_or(0);
+            *changes.entry(token).or_insert(0) += sign * value;
+        }
+    }
+    changes
+}
+
+// Keep the node's subscriptions in line with the watched set until the set
+// empties or the connection drops
+async fn follow(
+    app: &AppHandle,
+    addresses: &mut watch::Receiver<BTreeSet<String>>,
+) -> Result<(), String> {
+    let (ws_stream, _) = tokio_tungstenite::connect_async(FULLNODE_WS_URL)
+        .await
+        .map_err(|e| format!("Failed to connect to node WebSocket: {}", e))?;
+    let (mut sink, mut stream) = ws_stream.split();
+
+    let mut subscribed = BTreeSet::new();
+    loop {
+        let watched = addresses.borrow_and_update().clone();
+        for address in watched.difference(&subscribed) {
+            sink.send(subscription_message("subscribe_address", address))
+                .await
+                .map_err(|e| format!("Failed to subscribe to address: {}", e))?;
+        }
+        for address in subscribed.difference(&watched) {
+            sink.send(subscription_message("unsubscribe_address", address))
+                .await
+                .map_err(|e| format!("Failed to unsubscribe from address: {}", e))?;
+        }
+        subscribed = watched;
+        if subscribed.is_empty() {
+            let _ = sink.send(Message::Close(None)).await;
+            return Ok(());
+        }
+
+        tokio::select! {
+            changed = addresses.changed() => {
+                if changed.is_err() {
+                    return Ok(());
+                }
+            }
+            message = stream.next() => {
+                let text = match message {
+                    Some(Ok(Message::Text(text))) => text,
+                    Some(Ok(Message::Close(_))) | Some(Err(_)) | None => return Ok(()),
+                    Some(Ok(_)) => continue,
+                };
+                let Ok(event) = serde_json::from_str::<Value>(&text) else {
+                    continue;
+                };
+                if event["type"] != "wallet:address_history" {
+                    continue;
+                }
+                // The node may still deliver for an address just unsubscribed
+                let address = event["address"].as_str().unwrap_or_default();
+                if !subscribed.contains(address) {
+                    continue;
+                }
+
+                let tx = &event["history"];
+                let activity = AddressActivity {
+                    address: address.to_string(),
+                    tx_id: tx["tx_id"].as_str().unwrap_or_default().to_string(),
+                    timestamp: tx["timestamp"].as_u64(),
+                    is_voided: tx["is_voided"].as_bool().unwrap_or(false),
+                    balance_changes: balance_changes(tx, address),
+                };
+                let _ = app.emit("address-activity", &activity);
+            }
+        }
+    }
+}
+
+/// Follow the watched addresses for the lifetime of the app
+pub async fn follow_addresses(app: AppHandle, state: SharedState) {
+    let mut addresses = state
+        .lock()
+        .await
+        .address_subscriptions
+        .addresses
+        .subscribe();
+
+    loop {
+        let active = state.lock().await.node_running && !addresses.borrow().is_empty();
+        if active {
+            if let Err(e) = follow(&app, &mut addresses).await {
+                eprintln!("{}", e);
+            }
+        }
+        tokio::time::sleep(RECONNECT_DELAY).await;
+    }
+}
+
+/// Start emitting `address-activity` for `address`; returns the watched addresses
+pub async fn subscribe_address_internal(
+    state: &SharedState,
+    address: &str,
+) -> Result<Vec<String>, String> {
+    crate::address::check_address(address)?;
+    let state_guard = state.lock().await;
+    let subscriptions = &state_guard.address_subscriptions;
+    subscriptions
+        .addresses
+        .send_if_modified(|addresses| addresses.insert(address.to_string()));
+    Ok(subscriptions.list())
+}
+
+/// Stop watching `address`; returns the remaining watched addresses
+pub async fn unsubscribe_address_internal(
+    state: &SharedState,
+    address: &str,
+) -> Result<Vec<String>, String> {
+    let state_guard = state.lock().await;
+    let subscriptions = &state_guard.address_subscriptions;
+    if !subscriptions
+        .addresses
+        .send_if_modified(|addresses| addresses.remove(address))
+    {
+        return Err(format!("Address {} is not subscribed", address));
+    }
+    Ok(subscriptions.list())
+}
+
+pub async fn list_address_subscriptions_internal(state: &SharedState) -> Vec<String> {
+    state.lock().await.address_subscriptions.list()
+}
+
+// ============================================================================
+// Tauri Commands
+// ============================================================================
+
+// Emit address-activity events for transactions touching an address
+#[tauri::command]
+pub async fn subscribe_address(
+    state: tauri::State<'_, SharedState>,
+    address: String,
+) -> Result<Vec<String>, String> {
+    subscribe_address_internal(&state, &address).await
+}
+
+// Stop emitting address-activity events for an address
+#[tauri::command]
+pub async fn unsubscribe_address(
+    state: tauri::State<'_, SharedState>,
+    address: String,
+) -> Result<Vec<String>, String> {
+    unsubscribe_address_internal(&state, &address).await
+}
+
+// List the addresses with address-activity events
+#[tauri::command]
+pub async fn list_address_subscriptions(
+    state: tauri::State<'_, SharedState>,
+) -> Result<Vec<String>, String> {
+    Ok(list_address_subscriptions_internal(&state).await)
+}
@@ -0,0 +1,199 @@
+//! Atomic swaps between two local wallets
+//!
+//! Drives wallet-headless' `/wallet/atomic-swap/*` endpoints: wallet A proposes
+//! its side, wallet B completes the partial transaction with its own, both sign
+//! and wallet B pushes. UTXOs locked by the proposals are released on failure.
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::{headless, SharedState};
+
+const HTR_UID: &str = "00";
+
+/// What one side of the swap gives away
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SwapOffer {
+    // Token UID (HTR when omitted)
+    #[serde(default)]
+    pub token: Option<String>,
+    pub amount: u64, // Amount in cents of the token
+}
+
+impl SwapOffer {
+    fn token_json(&self) -> Value {
+        json!({
+            "token": self.token.as_deref().unwrap_or(HTR_UID),
+            "value": self.amount,
+        })
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AtomicSwapRequest {
+    pub wallet_a: String,
+    pub offer_a: SwapOffer,
+    pub wallet_b: String,
+    pub offer_b: SwapOffer,
+    #[serde(default)]
+    pub instance: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AtomicSwapResult {
+    pub hash: Option<String>,
+    // Final partial transaction both wallets signed
+    pub partial_tx: String,
+    // Pushed transaction as returned by wallet-headless
+    pub tx: Value,
+}
+
+// Add one wallet's side to the swap (starting a new partial tx when `partial_tx` is None)
+async fn propose(
+    base_url: &str,
+    wallet_id: &str,
+    send: &SwapOffer,
+    receive: &SwapOffer,
+    partial_tx: Option<&str>,
+) -> Result<String, String> {
+    let mut body = json!({
+        "send": { "tokens": [send.token_json()] },
+        "receive": { "tokens": [receive.token_json()] },
+        "lock": true,
+    });
+    if let Some(partial_tx) = partial_tx {
+        body["partial_tx"] = json!(partial_tx);
+    }
+
+    let result = headless::wallet_post(
+        base_url,
+        wallet_id,
+        "/wallet/atomic-swap/tx-proposal",
+        &body,
+    )
+    .await
+    .map_err(|e| format!("Wallet '{}' failed to propose its side: {}", wallet_id, e))?;
+
+    result["data"]
+        .as_str()
+        .map(|s| s.to_string())
+        .ok_or_else(|| "Missing partial transaction in response".to_string())
+}
+
+async fn signatures(base_url: &str, wallet_id: &str, partial_tx: &str) -> Result<String, String> {
+    let result = headless::wallet_post(
+        base_url,
+        wallet_id,
+        "/wallet/atomic-swap/tx-proposal/get-my-signatures",
+        &json!({ "partial_tx": partial_tx }),
+    )
+    .await
+    .map_err(|e| format!("Wallet '{}' failed to sign: {}", wallet_id, e))?;
+
+    result["signatures"]
+        .as_str()
+        .map(|s| s.to_string())
+        .ok_or_else(|| format!("Wallet '{}' returned no signatures", wallet_id))
+}
+
+// Release the UTXOs a wallet locked for the swap
+async fn unlock(base_url: &str, wallet_id: &str, partial_tx: &str) {
+    let _ = headless::wallet_post(
+        base_url,
+        wallet_id,
+        "/wallet/atomic-swap/tx-proposal/unlock",
+        &json!({ "partial_tx": partial_tx }),
+    )
+    .await;
+}
+
+async fn complete_swap(
+    base_url: &str,
+    request: &AtomicSwapRequest,
+    partial_a: &str,
+) -> Result<AtomicSwapResult, String> {
+    let partial_tx = propose(
+        base_url,
+        &request.wallet_b,
+        &request.offer_b,
+        &request.offer_a,
+        Some(partial_a),
+    )
+    .await?;
+
+    let signatures_a = signatures(base_url, &request.wallet_a, &partial_tx).await;
+    let signatures_b = signatures(base_url, &request.wallet_b, &partial_tx).await;
+    let (signatures_a, signatures_b) = match (signatures_a, signatures_b) {
+        (Ok(a), Ok(b)) => (a, b),
+        (Err(e), _) | (_, Err(e)) => {
+            unlock(base_url, &request.wallet_b, &partial_tx).await;
+            return Err(e);
+        }
+    };
+
+    let result = headless::wallet_post(
+        base_url,
+        &request.wallet_b,
+        "/wallet/atomic-swap/tx-proposal/sign-and-push",
+        &json!({
+            "partial_tx": partial_tx,
+            "signatures": [signatures_a, signatures_b],
+        }),
+    )
+    .await;
+
+    match result {
+        Ok(tx) => Ok(AtomicSwapResult {
+            hash: tx["hash"].as_str().map(|s| s.to_string()),
+            partial_tx,
+            tx,
+        }),
+        Err(e) => {
+            unlock(base_url, &request.wallet_b, &partial_tx).await;
+            Err(format!("Failed to push swap transaction: {}", e))
+        }
+    }
+}
+
+/// Swap `offer_a` from wallet A for `offer_b` from wallet B in a single transaction
+pub async fn atomic_swap_internal(
+    state: &SharedState,
+    request: &AtomicSwapRequest,
+) -> Result<AtomicSwapResult, String> {
+    if request.wallet_a == request.wallet_b {
+        return Err("A swap needs two different wallets".to_string());
+    }
+    if request.offer_a.amount == 0 || request.offer_b.amount == 0 {
+        return Err("Both offers must have a positive amount".to_string());
+    }
+
+    let base_url = crate::headless_url(state, request.instance.as_deref()).await?;
+
+    let partial_a = propose(
+        &base_url,
+        &request.wallet_a,
+        &request.offer_a,
+        &request.offer_b,
+        None,
+    )
+    .await?;
+
+    let result = complete_swap(&base_url, request, &partial_a).await;
+    if result.is_err() {
+        unlock(&base_url, &request.wallet_a, &partial_a).await;
+    }
+    result
+}
+
+// ============================================================================
+// Tauri Commands
+// ============================================================================
+
+// Run a full atomic swap (propose, sign, push) between two wallets
+#[tauri::command]
+pub async fn atomic_swap(
+    state: tauri::State<'_, SharedState>,
+    request: AtomicSwapRequest,
+) -> Result<AtomicSwapResult, String> {
+    atomic_swap_internal(&state, &request).await
+}
@@ -0,0 +1,156 @@
+//! OpenTelemetry tracing
+//!
+//! Tauri commands (through tauri's `tracing` feature), MCP tool calls, the
+//! embedded servers' requests, proxied requests and the service start/stop
+//! paths are instrumented with `tracing` spans. When enabled, the spans are
+//! exported over OTLP/HTTP to a collector (Jaeger, Tempo, an OpenTelemetry
+//! Collector, ...), so a slow `quick_start` shows up as one trace with the
+//! node, miner and wallet-headless starts and their HTTP requests as children.
+//!
+//! It's opt-in: the endpoint lives in `telemetry.json` and the exporter is set
+//! up once, at app start. Without it the spans cost next to nothing.
+
+use opentelemetry::trace::TracerProvider as _;
+use opentelemetry_otlp::WithExportConfig;
+use opentelemetry_sdk::trace::SdkTracerProvider;
+use opentelemetry_sdk::Resource;
+use serde::{Deserialize, Serialize};
+use std::sync::OnceLock;
+use tracing::Level;
+use tracing_subscriber::filter::Targets;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+
+use crate::settings::JsonSettings;
+
+const SERVICE_NAME: &str = "hathor-forge";
+// OTLP/HTTP traces endpoint of a local collector
+const DEFAULT_ENDPOINT: &str = "http://localhost:4318/v1/traces";
+
+// Flushed and shut down on exit
+static PROVIDER: OnceLock<SdkTracerProvider> = OnceLock::new();
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TelemetrySettings {
+    pub enabled: bool,
+    // Full OTLP/HTTP URL, including /v1/traces
+    pub endpoint: String,
+}
+
+impl Default for TelemetrySettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            endpoint: DEFAULT_ENDPOINT.to_string(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TelemetryStatus {
+    pub settings: TelemetrySettings,
+    // Spans are being exported (the settings may have changed since)
+    pub active: bool,
+    pub restart_required: bool,
+}
+
+static SETTINGS: JsonSettings<TelemetrySettings> =
+    JsonSettings::new("telemetry.json", "telemetry settings");
+
+/// Set up span export when it's enabled; call once, before the runtime starts
+pub fn init() {
+    let settings = SETTINGS.load();
+    if !settings.enabled {
+        return;
+    }
+    let exporter = match opentelemetry_otlp::SpanExporter::builder()
+        .with_http()
+        .with_endpoint(&settings.endpoint)
+        .build()
+    {
+        Ok(exporter) => exporter,
+        Err(e) => {
+            eprintln!(
+                "Failed to set up OTLP export to {}: {}",
+                settings.endpoint, e
+            );
+            return;
+        }
+    };
+    // The batch processor exports from its own thread
+    let provider = SdkTracerProvider::builder()
+        .with_batch_exporter(exporter)
+        .with_resource(Resource::builder().with_service_name(SERVICE_NAME).build())
+        .build();
+    let tracer = provider.tracer(SERVICE_NAME);
+
+    // Forge's own spans, tauri's command spans and the HTTP servers' request spans
+    let targets = Targets::new()
+        .with_target("hathor_forge_lib", Level::DEBUG)
+        .with_target("tauri", Level::INFO)
+        .with_target("tower_http", Level::DEBUG);
+    if let Err(e) = tracing_subscriber::registry()
+        .with(tracing_opentelemetry::layer().with_tracer(tracer))
+        .with(targets)
+        .try_init()
+    {
+        eprintln!("Failed to install the tracing subscriber: {}", e);
+        return;
+    }
+    let _ = PROVIDER.set(provider);
+    eprintln!("Exporting traces to {}", settings.endpoint);
+}
+
+/// Export the spans still buffered; call on exit
+pub fn shutdown() {
+    if let Some(provider) = PROVIDER.get() {
+        let _ = provider.shutdown();
+    }
+}
+
+/// Span for an outgoing HTTP request, as a child of the current span; record
+/// `http.response.status_code` on it once the response arrives
+pub fn http_span(method: &str, url: &str) -> tracing::Span {
+    tracing::info_span!(
+        "http.request",
+        otel.kind = "client",
+        http.request.method = method,
+        url.full = url,
+        http.response.status_code = tracing::field::Empty,
+    )
+}
+
+fn status(settings: TelemetrySettings) -> TelemetryStatus {
+    let active = PROVIDER.get().is_some();
+    TelemetryStatus {
+        restart_required: active != settings.enabled,
+        settings,
+        active,
+    }
+}
+
+// ============================================================================
+// Tauri Commands
+// ============================================================================
+
+// Get the OTLP export settings and whether spans are being exported
+#[tauri::command]
+pub async fn get_telemetry_settings() -> Result<TelemetryStatus, String> {
+    Ok(status(SETTINGS.load()))
+}
+
+// Enable OTLP export (applied at the next app start)
+#[tauri::command]
+pub async fn set_telemetry(settings: TelemetrySettings) -> Result<TelemetryStatus, String> {
+    let endpoint = settings.endpoint.trim();
+    if settings.enabled && !(endpoint.starts_with("http://") || endpoint.starts_with("https://")) {
+        return Err("The OTLP endpoint must be an http:// or https:// URL".to_string());
+    }
+    let settings = TelemetrySettings {
+        enabled: settings.enabled,
+        endpoint: endpoint.to_string(),
+    };
+    SETTINGS.save(&settings)?;
+    Ok(status(settings))
+}
@@ -0,0 +1,211 @@
+//! Time travel for the localnet clock
+//!
+//! hathor-core has no API to move its clock, so the node (and wallet-headless
+//! instances started afterwards) run under libfaketime with a timestamp file
+//! holding the offset from real time. The library re-reads the file every
+//! second, so `advance_time`/`set_time` take effect without a restart once
+//! the node runs with the mocked clock; the first time, a running node is
+//! restarted to load it. The clock only moves forward: blocks already carry
+//! the later timestamps, so going back would stall mining. Resetting the data
+//! brings the clock back to real time.
+//!
+//! libfaketime has to be installed (`apt install faketime`,
+//! `brew install libfaketime`); it is not available on Windows.
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::PathBuf;
+
+use crate::SharedState;
+
+#[cfg(target_os = "linux")]
+const LIBRARY_PATHS: [&str; 4] = [
+    "/usr/lib/x86_64-linux-gnu/faketime/libfaketime.so.1",
+    "/usr/lib/aarch64-linux-gnu/faketime/libfaketime.so.1",
+    "/usr/lib/faketime/libfaketime.so.1",
+    "/usr/local/lib/faketime/libfaketime.so.1",
+];
+#[cfg(target_os = "macos")]
+const LIBRARY_PATHS: [&str; 2] = [
+    "/opt/homebrew/lib/faketime/libfaketime.1.dylib",
+    "/usr/local/lib/faketime/libfaketime.1.dylib",
+];
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+const LIBRARY_PATHS: [&str; 0] = [];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClockStatus {
+    pub real_time: u64, // Unix timestamp
+    // Real time plus the offset
+    pub localnet_time: u64,
+    pub offset_secs: u64,
+    // Whether the running node uses the mocked clock
+    pub node_mocked: bool,
+    pub library: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TimeTravelResult {
+    pub clock: ClockStatus,
+    // The node was restarted to load the mocked clock (wallet-headless needs a start)
+    pub node_restarted: bool,
+}
+
+fn offset_path() -> PathBuf {
+    crate::get_app_dir().join("faketime.rc")
+}
+
+fn library() -> Option<PathBuf> {
+    LIBRARY_PATHS
+        .iter()
+        .map(PathBuf::from)
+        .find(|path| path.exists())
+}
+
+/// Current offset from real time, if time travel has been used
+pub fn offset() -> Option<u64> {
+    fs::read_to_string(offset_path())
+        .ok()
+        .and_then(|content| content.trim().trim_start_matches('+').parse().ok())
+}
+
+fn write_offset(offset: u64) -> Result<(), String> {
+    let path = offset_path();
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("Failed to create app directory: {}", e))?;
+    }
+    // libfaketime's relative format: seconds ahead of real time
+    fs::write(&path, format!("+{}\n", offset))
+        .map_err(|e| format!("Failed to write clock offset: {}", e))
+}
+
+/// Back to real time; only safe along with a data reset
+pub fn clear_offset() {
+    let _ = fs::remove_file(offset_path());
+}
+
+/// Environment that runs a process on the localnet clock; empty until time
+/// travel is used (or when libfaketime is missing)
+pub fn clock_env() -> Vec<(String, String)> {
+    let (Some(library), Some(_)) = (library(), offset()) else {
+        return Vec::new();
+    };
+    let library = library.to_string_lossy().to_string();
+    let preload = if cfg!(target_os = "macos") {
+        vec![
+            ("DYLD_INSERT_LIBRARIES".to_string(), library),
+            ("DYLD_FORCE_FLAT_NAMESPACE".to_string(), "1".to_string()),
+        ]
+    } else {
+        vec![("LD_PRELOAD".to_string(), library)]
+    };
+    preload
+        .into_iter()
+        .chain([
+            (
+                "FAKETIME_TIMESTAMP_FILE".to_string(),
+                offset_path().to_string_lossy().to_string(),
+            ),
+            // Pick up offset changes within a second
+            ("FAKETIME_CACHE_DURATION".to_string(), "1".to_string()),
+        ])
+        .collect()
+}
+
+pub async fn get_clock_internal(state: &SharedState) -> ClockStatus {
+    let state_guard = state.lock().await;
+    let real_time = crate::unix_now();
+    let offset_secs = offset().unwrap_or(0);
+    ClockStatus {
+        real_time,
+        localnet_time: real_time.saturating_add(offset_secs),
+        offset_secs,
+        node_mocked: state_guard.node_running && state_guard.node_clock_mocked,
+        library: library().map(|path| path.to_string_lossy().to_string()),
+    }
+}
+
+// Store the new offset and make sure a running node follows it
+async fn move_clock(state: &SharedState, offset: u64) -> Result<TimeTravelResult, String> {
+    if library().is_none() {
+        return Err(
+            "libfaketime not found; install it (apt install faketime / brew install libfaketime)"
+                .to_string(),
+        );
+    }
+    write_offset(offset)?;
+
+    let needs_restart = {
+        let state_guard = state.lock().await;
+        state_guard.node_running && !state_guard.node_clock_mocked
+    };
+    if needs_restart {
+        crate::stop_node_internal(state).await?;
+        crate::start_node_internal(state).await?;
+    }
+
+    Ok(TimeTravelResult {
+        clock: get_clock_internal(state).await,
+        node_restarted: needs_restart,
+    })
+}
+
+/// Move the localnet clock `seconds` forward
+pub async fn advance_time_internal(
+    state: &SharedState,
+    seconds: u64,
+) -> Result<TimeTravelResult, String> {
+    if seconds == 0 {
+        return Err("Advance by at least 1 second".to_string());
+    }
+    let offset = offset()
+        .unwrap_or(0)
+        .checked_add(seconds)
+        .ok_or("Can't advance the localnet clock that far")?;
+    move_clock(state, offset).await
+}
+
+/// Set the localnet clock to `timestamp` (Unix seconds, not earlier than its
+/// current time)
+pub async fn set_time_internal(
+    state: &SharedState,
+    timestamp: u64,
+) -> Result<TimeTravelResult, String> {
+    let now = crate::unix_now();
+    let current = now.saturating_add(offset().unwrap_or(0));
+    if timestamp < current {
+        return Err(format!(
+            "The localnet clock can't go back (it is at {}); reset the data instead",
+            current
+        ));
+    }
+    move_clock(state, timestamp.saturating_sub(now)).await
+}
+
+// ============================================================================
+// Tauri Commands
+// ============================================================================
+
+// Move the localnet clock forward by a number of seconds
+#[tauri::command]
+pub async fn advance_time(
+    state: tauri::State<'_, SharedState>,
+    seconds: u64,
+) -> Result<TimeTravelResult, String> {
+    advance_time_internal(&state, seconds).await
+}
+
+// Set the localnet clock to a Unix timestamp in its future
+#[tauri::command]
+pub async fn set_time(
+    state: tauri::State<'_, SharedState>,
+    timestamp: u64,
+) -> Result<TimeTravelResult, String> {
+    set_time_internal(&state, timestamp).await
+}
+
+// Get the localnet clock, its offset from real time and whether the node follows it
+#[tauri::command]
+pub async fn get_clock(state: tauri::State<'_, SharedState>) -> Result<ClockStatus, String> {
+    Ok(get_clock_internal(&state).await)
+}
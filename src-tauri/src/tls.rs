@@ -0,0 +1,348 @@
+//! HTTPS for the explorer and MCP servers
+//!
+//! Some browser APIs (WebCrypto, service workers, camera access for scanning
+//! QR codes) and mobile OSes insist on HTTPS even for local development. With
+//! TLS on (`tls.json` in the app directory), the explorer server and the MCP
+//! server serve HTTPS with a self-signed certificate per workspace, generated
+//! on first use for localhost and the machine's LAN address. Certificates live
+//! in `tls/` of the app directory rather than the data dir, so a data reset or
+//! snapshot restore doesn't replace a certificate devices already trust, and
+//! snapshots never carry the private key. `export_tls_cert` writes the certificate out so it can be
+//! installed and trusted on test devices. The setting applies when a server
+//! starts: the explorer's next start, and the next app start for MCP.
+//!
+//! Connections are served over HTTP/1.1 only, which WebSocket upgrades (the
+//! proxied `/v1a/ws/`, MCP's SSE stream) rely on.
+
+use axum::extract::ConnectInfo;
+use axum::Router;
+use hyper_util::rt::{TokioExecutor, TokioIo};
+use hyper_util::server::conn::auto::Builder as ConnectionBuilder;
+use hyper_util::service::TowerToHyperService;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::future::Future;
+use std::path::PathBuf;
+use std::sync::Arc;
+use tokio::net::TcpListener;
+use tokio_rustls::rustls::pki_types::pem::PemObject;
+use tokio_rustls::rustls::pki_types::{CertificateDer, PrivateKeyDer};
+use tokio_rustls::rustls::{self, ServerConfig};
+use tokio_rustls::TlsAcceptor;
+use tower_http::add_extension::AddExtension;
+
+use crate::settings::JsonSettings;
+use crate::{stratum_lan, AppState, SharedState};
+
+// Apple platforms reject server certificates valid for longer
+const CERT_VALIDITY_DAYS: i64 = 825;
+const EXPORT_FILE_NAME: &str = "hathor-forge-localhost.crt";
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct TlsSettings {
+    #[serde(default)]
+    enabled: bool,
+}
+
+// Written next to the certificate when it's generated
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CertMetadata {
+    subject_alt_names: Vec<String>,
+    created_at: u64,
+    expires_at: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TlsInfo {
+    pub enabled: bool,
+    // The running explorer server serves HTTPS
+    pub explorer_tls: bool,
+    // The setting differs from the running explorer server
+    pub restart_required: bool,
+    // Unset until the certificate is generated
+    pub cert_path: Option<String>,
+    pub fingerprint_sha256: Option<String>,
+    pub subject_alt_names: Vec<String>,
+    pub expires_at: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TlsCertExport {
+    pub path: String,
+    pub pem: String,
+    pub fingerprint_sha256: String,
+}
+
+static SETTINGS: JsonSettings<TlsSettings> = JsonSettings::new("tls.json", "TLS settings");
+
+/// Whether servers starting now should serve HTTPS
+pub fn enabled() -> bool {
+    SETTINGS.load().enabled
+}
+
+/// URL scheme of a server, by whether it serves TLS
+pub fn scheme(tls: bool) -> &'static str {
+    if tls {
+        "https"
+    } else {
+        "http"
+    }
+}
+
+// `tls/<workspace>/` in the app dir, named after a hash of the workspace's data dir
+fn cert_dir(state: &AppState) -> PathBuf {
+    let data_dir = crate::workspace_data_dir(state);
+    let workspace: String = Sha256::digest(data_dir.to_string_lossy().as_bytes())[..8]
+        .iter()
+        .map(|byte| format!("{:02x}", byte))
+        .collect();
+    crate::get_app_dir().join("tls").join(workspace)
+}
+
+fn cert_path(state: &AppState) -> PathBuf {
+    cert_dir(state).join("cert.pem")
+}
+
+fn key_path(state: &AppState) -> PathBuf {
+    cert_dir(state).join("key.pem")
+}
+
+fn metadata_path(state: &AppState) -> PathBuf {
+    cert_dir(state).join("cert.json")
+}
+
+fn load_metadata(state: &AppState) -> Option<CertMetadata> {
+    fs::read_to_string(metadata_path(state))
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+}
+
+// AA:BB:... over the certificate's DER encoding, as browsers show it
+fn fingerprint(cert_pem: &[u8]) -> Result<String, String> {
+    let der = CertificateDer::from_pem_slice(cert_pem)
+        .map_err(|e| format!("Invalid TLS certificate: {}", e))?;
+    Ok(Sha256::digest(der.as_ref())
+        .iter()
+        .map(|byte| format!("{:02X}", byte))
+        .collect::<Vec<_>>()
+        .join(":"))
+}
+
+// Self-signed certificate for localhost and the current LAN address
+fn generate_cert(state: &AppState) -> Result<CertMetadata, String> {
+    let mut subject_alt_names = vec![
+        "localhost".to_string(),
+        "127.0.0.1".to_string(),
+        "::1".to_string(),
+    ];
+    subject_alt_names.extend(stratum_lan::lan_ip());
+
+    let mut params = rcgen::CertificateParams::new(subject_alt_names.clone())
+        .map_err(|e| format!("Invalid certificate names: {}", e))?;
+    params
+        .distinguished_name
+        .push(rcgen::DnType::CommonName, "Hathor Forge (localhost)");
+    let now = time::OffsetDateTime::now_utc();
+    params.not_before = now - time::Duration::days(1);
+    params.not_after = now + time::Duration::days(CERT_VALIDITY_DAYS);
+    let key_pair =
+        rcgen::KeyPair::generate().map_err(|e| format!("Failed to generate TLS key: {}", e))?;
+    let cert = params
+        .self_signed(&key_pair)
+        .map_err(|e| format!("Failed to generate TLS certificate: {}", e))?;
+
+    fs::create_dir_all(cert_dir(state))
+        .map_err(|e| format!("Failed to create certificate directory: {}", e))?;
+    crate::write_private_file(key_path(state), key_pair.serialize_pem())
+        .map_err(|e| format!("Failed to write TLS key: {}", e))?;
+    fs::write(cert_path(state), cert.pem())
+        .map_err(|e| format!("Failed to write TLS certificate: {}", e))?;
+
+    let created_at = crate::unix_now();
+    let metadata = CertMetadata {
+        subject_alt_names,
+        created_at,
+        expires_at: created_at + CERT_VALIDITY_DAYS as u64 * 86_400,
+    };
+    let content = serde_json::to_string_pretty(&metadata)
+        .map_err(|e| format!("Failed to serialize certificate metadata: {}", e))?;
+    fs::write(metadata_path(state), content)
+        .map_err(|e| format!("Failed to write certificate metadata: {}", e))?;
+    Ok(metadata)
+}
+
+// Generate the certificate unless the workspace has one
+fn ensure_cert(state: &AppState) -> Result<(), String> {
+    if cert_path(state).exists() && key_path(state).exists() {
+        return Ok(());
+    }
+    generate_cert(state).map(|_| ())
+}
+
+/// TLS acceptor with the workspace's certificate, generating it if needed
+pub fn acceptor(state: &AppState) -> Result<TlsAcceptor, String> {
+    ensure_cert(state)?;
+    let cert_pem =
+        fs::read(cert_path(state)).map_err(|e| format!("Failed to read TLS certificate: {}", e))?;
+    let key_pem =
+        fs::read(key_path(state)).map_err(|e| format!("Failed to read TLS key: {}", e))?;
+    let certs = CertificateDer::pem_slice_iter(&cert_pem)
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| format!("Invalid TLS certificate: {}", e))?;
+    let key =
+        PrivateKeyDer::from_pem_slice(&key_pem).map_err(|e| format!("Invalid TLS key: {}", e))?;
+
+    let mut config =
+        ServerConfig::builder_with_provider(Arc::new(rustls::crypto::ring::default_provider()))
+            .with_safe_default_protocol_versions()
+            .map_err(|e| format!("Failed to configure TLS: {}", e))?
+            .with_no_client_auth()
+            .with_single_cert(certs, key)
+            .map_err(|e| format!("Failed to configure TLS: {}", e))?;
+    config.alpn_protocols = vec![b"http/1.1".to_vec()];
+    Ok(TlsAcceptor::from(Arc::new(config)))
+}
+
+/// Serve a router over TLS until `shutdown` resolves; connections already
+/// open finish on their own
+pub async fn serve(
+    listener: TcpListener,
+    router: Router,
+    acceptor: TlsAcceptor,
+    shutdown: impl Future<Output = ()>,
+) {
+    tokio::pin!(shutdown);
+    loop {
+        let (stream, peer) = tokio::select! {
+            accepted = listener.accept() => match accepted {
+                Ok(accepted) => accepted,
+                Err(e) => {
+                    eprintln!("Failed to accept TLS connection: {}", e);
+                    continue;
+                }
+            },
+            _ = &mut shutdown => return,
+        };
+        let acceptor = acceptor.clone();
+        // Client address for handlers and middleware, as axum::serve provides it
+        let service =
+            TowerToHyperService::new(AddExtension::new(router.clone(), ConnectInfo(peer)));
+        tokio::spawn(async move {
+            // Failed handshakes are usually clients that don't trust the
+            // certificate yet; nothing to report
+            let Ok(stream) = acceptor.accept(stream).await else {
+                return;
+            };
+            let _ = ConnectionBuilder::new(TokioExecutor::new())
+                .http1_only()
+                .serve_connection_with_upgrades(TokioIo::new(stream), service)
+                .await;
+        });
+    }
+}
+
+/// TLS setting and the workspace's certificate
+pub async fn get_tls_info_internal(state: &SharedState) -> TlsInfo {
+    let enabled = enabled();
+    let state_guard = state.lock().await;
+    let explorer_running = crate::default_explorer(&state_guard).is_some();
+    let explorer_tls = crate::default_explorer(&state_guard).is_some_and(|server| server.tls);
+    let cert = cert_path(&state_guard);
+    let fingerprint_sha256 = fs::read(&cert).ok().and_then(|pem| fingerprint(&pem).ok());
+    let metadata = load_metadata(&state_guard);
+
+    TlsInfo {
+        enabled,
+        explorer_tls,
+        restart_required: explorer_running && explorer_tls != enabled,
+        cert_path: fingerprint_sha256
+            .is_some()
+            .then(|| cert.to_string_lossy().to_string()),
+        fingerprint_sha256,
+        subject_alt_names: metadata
+            .as_ref()
+            .map(|metadata| metadata.subject_alt_names.clone())
+            .unwrap_or_default(),
+        expires_at: metadata.map(|metadata| metadata.expires_at),
+    }
+}
+
+/// Turn HTTPS on or off from the next server start, generating the
+/// certificate when turning it on
+pub async fn set_tls_internal(state: &SharedState, enabled: bool) -> Result<TlsInfo, String> {
+    if enabled {
+        ensure_cert(&*state.lock().await)?;
+    }
+    SETTINGS.save(&TlsSettings { enabled })?;
+    Ok(get_tls_info_internal(state).await)
+}
+
+/// Replace the workspace's certificate (e.g. after the LAN address changed);
+/// devices that trusted the old one need the new one
+pub async fn regenerate_tls_cert_internal(state: &SharedState) -> Result<TlsInfo, String> {
+    generate_cert(&*state.lock().await)?;
+    Ok(get_tls_info_internal(state).await)
+}
+
+/// Write the certificate (PEM, as `.crt`, which iOS and Android install) to
+/// `path`, or to the downloads directory
+pub async fn export_tls_cert_internal(
+    state: &SharedState,
+    path: Option<String>,
+) -> Result<TlsCertExport, String> {
+    let cert = {
+        let state_guard = state.lock().await;
+        ensure_cert(&state_guard)?;
+        cert_path(&state_guard)
+    };
+    let pem = fs::read(&cert).map_err(|e| format!("Failed to read TLS certificate: {}", e))?;
+    let destination = match path {
+        Some(path) => PathBuf::from(path),
+        None => dirs::download_dir()
+            .or_else(dirs::home_dir)
+            .ok_or("No downloads directory; pass a path")?
+            .join(EXPORT_FILE_NAME),
+    };
+    fs::write(&destination, &pem).map_err(|e| format!("Failed to export certificate: {}", e))?;
+
+    Ok(TlsCertExport {
+        path: destination.to_string_lossy().to_string(),
+        fingerprint_sha256: fingerprint(&pem)?,
+        pem: String::from_utf8_lossy(&pem).to_string(),
+    })
+}
+
+// ============================================================================
+// Tauri Commands
+// ============================================================================
+
+// Get the HTTPS setting and the certificate's fingerprint and names
+#[tauri::command]
+pub async fn get_tls_info(state: tauri::State<'_, SharedState>) -> Result<TlsInfo, String> {
+    Ok(get_tls_info_internal(&state).await)
+}
+
+// Serve the explorer and MCP servers over HTTPS (or stop doing so)
+#[tauri::command]
+pub async fn set_tls(
+    state: tauri::State<'_, SharedState>,
+    enabled: bool,
+) -> Result<TlsInfo, String> {
+    set_tls_internal(&state, enabled).await
+}
+
+// Generate a new self-signed certificate for the workspace
+#[tauri::command]
+pub async fn regenerate_tls_cert(state: tauri::State<'_, SharedState>) -> Result<TlsInfo, String> {
+    regenerate_tls_cert_internal(&state).await
+}
+
+// Export the certificate for trusting on test devices
+#[tauri::command]
+pub async fn export_tls_cert(
+    state: tauri::State<'_, SharedState>,
+    path: Option<String>,
+) -> Result<TlsCertExport, String> {
+    export_tls_cert_internal(&state, path).await
+}
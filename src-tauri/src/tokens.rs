@@ -0,0 +1,953 @@
+//! Custom token management through wallet-headless
+//!
+//! Tokens created (or registered) in the environment are recorded in a
+//! per-workspace registry stored in the data directory, so it's wiped together
+//! with the chain on reset.
+
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::fs;
+use std::sync::Mutex as StdMutex;
+use tauri::Emitter;
+
+use crate::{headless, wallets, SharedState, DEFAULT_HEADLESS_INSTANCE};
+
+// Serializes read-modify-write cycles on the token registry file
+static TOKEN_REGISTRY_LOCK: StdMutex<()> = StdMutex::new(());
+
+const HTR_UID: &str = "00";
+
+// Output token_data flag marking authority outputs, and the authority bits in their value
+const TOKEN_AUTHORITY_MASK: u64 = 0x80;
+const TOKEN_MINT_MASK: u64 = 0x01;
+const TOKEN_MELT_MASK: u64 = 0x02;
+
+// Script opcodes used by NFT data outputs (`<data> OP_CHECKSIG`)
+const OP_PUSHDATA1: u8 = 0x4c;
+const OP_CHECKSIG: u8 = 0xac;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreatedToken {
+    pub wallet_id: String,
+    pub token_uid: String,
+    pub name: String,
+    pub symbol: String,
+    pub amount: u64, // Amount in token cents
+    // Shareable `[name:symbol:uid:checksum]` string used to register the token in wallets
+    pub configuration_string: String,
+    // Data field of the creation tx's first output, for NFTs
+    pub nft_data: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Authority {
+    Mint,
+    Melt,
+}
+
+impl Authority {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Authority::Mint => "mint",
+            Authority::Melt => "melt",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateNftRequest {
+    pub wallet_id: String,
+    pub name: String,
+    pub symbol: String,
+    #[serde(default = "default_nft_amount")]
+    pub amount: u64,
+    // Data field stored in the creation tx (usually a metadata URI)
+    pub data: String,
+    #[serde(default)]
+    pub instance: Option<String>,
+}
+
+fn default_nft_amount() -> u64 {
+    1
+}
+
+/// A token known to the workspace
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RegisteredToken {
+    pub token_uid: String,
+    pub name: String,
+    pub symbol: String,
+    // Wallet that created the token, when it was created through Forge
+    pub created_by: Option<String>,
+    pub configuration_string: String,
+    pub registered_at: u64,
+}
+
+/// Balance of one token, annotated with its registry name/symbol
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TokenBalance {
+    pub token_uid: String,
+    pub name: String,
+    pub symbol: String,
+    pub available: u64,
+    pub locked: u64,
+}
+
+/// An NFT held by a wallet
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NftInfo {
+    pub token_uid: String,
+    pub name: String,
+    pub symbol: String,
+    pub balance: i64,
+    // Decoded data field (usually a URI)
+    pub data: String,
+}
+
+/// Result of a mint, melt or delegation
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TokenOperation {
+    pub wallet_id: String,
+    pub token_uid: String,
+    pub hash: String,
+    pub amount: Option<u64>,
+}
+
+/// An unspent authority output of a token
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuthorityHolder {
+    pub authority: Authority,
+    pub tx_id: String,
+    pub index: u32,
+    pub address: Option<String>,
+    // Registered wallet owning the address, if any
+    pub wallet_id: Option<String>,
+}
+
+/// A token's authority outputs, and the registered wallets holding them
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TokenAuthorities {
+    pub token_uid: String,
+    pub holders: Vec<AuthorityHolder>,
+    // Registered wallets able to mint/melt right now
+    pub mint_wallets: Vec<String>,
+    pub melt_wallets: Vec<String>,
+}
+
+/// Build a token configuration string, as the Hathor wallets do:
+/// `[name:symbol:uid:checksum]` where checksum is the first 4 bytes of sha256d
+pub fn configuration_string(token_uid: &str, name: &str, symbol: &str) -> String {
+    let partial = format!("{}:{}:{}", name, symbol, token_uid);
+    let checksum = Sha256::digest(Sha256::digest(partial.as_bytes()));
+    format!("[{}:{}]", partial, hex::encode(&checksum[..4]))
+}
+
+/// Parse a `[name:symbol:uid:checksum]` configuration string, verifying its checksum.
+/// Returns `(token_uid, name, symbol)`.
+pub fn parse_configuration_string(config: &str) -> Result<(String, String, String), String> {
+    let inner = config
+        .trim()
+        .strip_prefix('[')
+        .and_then(|s| s.strip_suffix(']'))
+        .ok_or("Invalid configuration string: expected [name:symbol:uid:checksum]")?;
+
+    // The name may contain ':', so split from the right
+    let mut parts = inner.rsplitn(4, ':');
+    let (Some(_checksum), Some(uid), Some(symbol), Some(name)) =
+        (parts.next(), parts.next(), parts.next(), parts.next())
+    else {
+        return Err(
+            "Invalid configuration string: expected [name:symbol:uid:checksum]".to_string(),
+        );
+    };
+
+    if configuration_string(uid, name, symbol) != config.trim() {
+        return Err("Invalid configuration string: checksum mismatch".to_string());
+    }
+
+    Ok((uid.to_string(), name.to_string(), symbol.to_string()))
+}
+
+async fn token_registry_path(state: &SharedState) -> std::path::PathBuf {
+    let state_guard = state.lock().await;
+    crate::workspace_data_dir(&state_guard).join("forge-tokens.json")
+}
+
+fn load_token_registry(path: &std::path::Path) -> Vec<RegisteredToken> {
+    fs::read_to_string(path)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+/// Add (or update) a token in the workspace registry
+pub async fn register_token_internal(
+    state: &SharedState,
+    token_uid: &str,
+    name: &str,
+    symbol: &str,
+    created_by: Option<&str>,
+) -> Result<RegisteredToken, String> {
+    if token_uid == HTR_UID {
+        return Err("HTR can't be registered".to_string());
+    }
+
+    let path = token_registry_path(state).await;
+    let _guard = TOKEN_REGISTRY_LOCK
+        .lock()
+        .unwrap_or_else(|e| e.into_inner());
+
+    let mut tokens = load_token_registry(&path);
+    let existing = tokens.iter().position(|t| t.token_uid == token_uid);
+    let token = RegisteredToken {
+        token_uid: token_uid.to_string(),
+        name: name.to_string(),
+        symbol: symbol.to_string(),
+        // Keep the original creator when re-registering
+        created_by: created_by
+            .map(|id| id.to_string())
+            .or_else(|| existing.and_then(|i| tokens[i].created_by.clone())),
+        configuration_string: configuration_string(token_uid, name, symbol),
+        registered_at: crate::unix_now(),
+    };
+    match existing {
+        Some(i) => tokens[i] = token.clone(),
+        None => tokens.push(token.clone()),
+    }
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create data directory: {}", e))?;
+    }
+    let content = serde_json::to_string_pretty(&tokens)
+        .map_err(|e| format!("Failed to serialize token registry: {}", e))?;
+    fs::write(&path, content).map_err(|e| format!("Failed to write token registry: {}", e))?;
+
+    Ok(token)
+}
+
+/// List the tokens registered in the current workspace
+pub async fn list_tokens_internal(state: &SharedState) -> Vec<RegisteredToken> {
+    let path = token_registry_path(state).await;
+    let _guard = TOKEN_REGISTRY_LOCK
+        .lock()
+        .unwrap_or_else(|e| e.into_inner());
+    load_token_registry(&path)
+}
+
+/// Balances of HTR and every registered token held by a wallet
+pub async fn get_token_balances_internal(
+    state: &SharedState,
+    wallet_id: &str,
+    instance: Option<&str>,
+) -> Result<Vec<TokenBalance>, String> {
+    let base_url = crate::headless_url(state, instance).await?;
+    let client = reqwest::Client::new();
+
+    let mut tokens = vec![(HTR_UID.to_string(), "Hathor".to_string(), "HTR".to_string())];
+    tokens.extend(
+        list_tokens_internal(state)
+            .await
+            .into_iter()
+            .map(|t| (t.token_uid, t.name, t.symbol)),
+    );
+
+    let mut balances = Vec::new();
+    for (token_uid, name, symbol) in tokens {
+        let result: serde_json::Value = client
+            .get(format!("{}/wallet/balance", base_url))
+            .header("X-Wallet-Id", wallet_id)
+            .query(&[("token", token_uid.as_str())])
+            .send()
+            .await
+            .map_err(|e| format!("Failed to get wallet balance: {}", e))?
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse response: {}", e))?;
+
+        balances.push(TokenBalance {
+            token_uid,
+            name,
+            symbol,
+            available: result["available"].as_u64().unwrap_or(0),
+            locked: result["locked"].as_u64().unwrap_or(0),
+        });
+    }
+
+    Ok(balances)
+}
+
+/// A wallet's transaction history, with the symbol of every input/output token
+pub async fn get_tx_history_internal(
+    state: &SharedState,
+    wallet_id: &str,
+    limit: Option<u64>,
+    instance: Option<&str>,
+) -> Result<Vec<serde_json::Value>, String> {
+    let base_url = crate::headless_url(state, instance).await?;
+    let query: Vec<(&str, String)> = limit
+        .map(|l| ("limit", l.to_string()))
+        .into_iter()
+        .collect();
+    let history = headless::wallet_get(&base_url, wallet_id, "/wallet/tx-history", &query)
+        .await
+        .map_err(|e| format!("Failed to get transaction history: {}", e))?;
+
+    let mut symbols: BTreeMap<String, String> = list_tokens_internal(state)
+        .await
+        .into_iter()
+        .map(|t| (t.token_uid, t.symbol))
+        .collect();
+    symbols.insert(HTR_UID.to_string(), "HTR".to_string());
+
+    let mut txs = match history {
+        serde_json::Value::Array(txs) => txs,
+        _ => return Err("Unexpected transaction history response".to_string()),
+    };
+    for tx in txs.iter_mut() {
+        for key in ["inputs", "outputs"] {
+            let Some(entries) = tx[key].as_array_mut() else {
+                continue;
+            };
+            for entry in entries {
+                // Unregistered tokens keep only their UID
+                let symbol = entry["token"].as_str().and_then(|uid| symbols.get(uid));
+                if let Some(symbol) = symbol {
+                    entry["token_symbol"] = json!(symbol);
+                }
+            }
+        }
+    }
+
+    Ok(txs)
+}
+
+// Create a token (or an NFT when `nft_data` is set) and build its description
+async fn submit_token_creation(
+    state: &SharedState,
+    wallet_id: &str,
+    name: &str,
+    symbol: &str,
+    amount: u64,
+    nft_data: Option<&str>,
+    instance: Option<&str>,
+) -> Result<CreatedToken, String> {
+    if name.trim().is_empty() || symbol.trim().is_empty() {
+        return Err("Token name and symbol are required".to_string());
+    }
+    if amount == 0 {
+        return Err("Token amount must be positive".to_string());
+    }
+
+    let base_url = crate::headless_url(state, instance).await?;
+    let mut body = json!({
+        "name": name,
+        "symbol": symbol,
+        "amount": amount,
+    });
+    let path = match nft_data {
+        Some(data) => {
+            body["data"] = json!(data);
+            "/wallet/create-nft"
+        }
+        None => "/wallet/create-token",
+    };
+
+    let result = headless::wallet_post(&base_url, wallet_id, path, &body)
+        .await
+        .map_err(|e| {
+            let kind = if nft_data.is_some() { "NFT" } else { "token" };
+            format!("Failed to create {}: {}", kind, e)
+        })?;
+
+    // The token UID is the hash of the creation transaction
+    let token_uid = result["hash"]
+        .as_str()
+        .ok_or("Missing token hash in response")?
+        .to_string();
+    let configuration_string = result["configurationString"]
+        .as_str()
+        .map(|s| s.to_string())
+        .unwrap_or_else(|| configuration_string(&token_uid, name, symbol));
+
+    if let Err(e) = register_token_internal(state, &token_uid, name, symbol, Some(wallet_id)).await
+    {
+        eprintln!("Failed to register token: {}", e);
+    }
+
+    Ok(CreatedToken {
+        wallet_id: wallet_id.to_string(),
+        token_uid,
+        name: name.to_string(),
+        symbol: symbol.to_string(),
+        amount,
+        configuration_string,
+        nft_data: nft_data.map(|data| data.to_string()),
+    })
+}
+
+/// Create a custom token from a headless wallet
+pub async fn create_token_internal(
+    state: &SharedState,
+    wallet_id: &str,
+    name: &str,
+    symbol: &str,
+    amount: u64,
+    instance: Option<&str>,
+) -> Result<CreatedToken, String> {
+    submit_token_creation(state, wallet_id, name, symbol, amount, None, instance).await
+}
+
+/// Create an NFT whose creation tx carries `data` (e.g. a metadata URI)
+pub async fn create_nft_internal(
+    state: &SharedState,
+    wallet_id: &str,
+    name: &str,
+    symbol: &str,
+    amount: u64,
+    data: &str,
+    instance: Option<&str>,
+) -> Result<CreatedToken, String> {
+    if data.is_empty() {
+        return Err("NFT data is required".to_string());
+    }
+    submit_token_creation(state, wallet_id, name, symbol, amount, Some(data), instance).await
+}
+
+// Extract the data pushed by an NFT data script (`<data> OP_CHECKSIG`)
+fn decode_nft_script(script_b64: &str) -> Option<String> {
+    let script = BASE64.decode(script_b64).ok()?;
+    let (&last, body) = script.split_last()?;
+    if last != OP_CHECKSIG {
+        return None;
+    }
+
+    let (&opcode, rest) = body.split_first()?;
+    let data = match opcode {
+        1..=75 => rest.get(..opcode as usize)?,
+        OP_PUSHDATA1 => {
+            let (&len, rest) = rest.split_first()?;
+            rest.get(..len as usize)?
+        }
+        _ => return None,
+    };
+
+    String::from_utf8(data.to_vec()).ok()
+}
+
+// Read the NFT data field of a token, if its creation tx has one
+async fn nft_data(client: &reqwest::Client, token_uid: &str) -> Option<String> {
+    let tx: serde_json::Value = client
+        .get("http://127.0.0.1:8080/v1a/transaction")
+        .query(&[("id", token_uid)])
+        .send()
+        .await
+        .ok()?
+        .json()
+        .await
+        .ok()?;
+
+    // NFTs put their data in the first output, a plain HTR output
+    let output = &tx["tx"]["outputs"][0];
+    if output["token_data"].as_u64() != Some(0) {
+        return None;
+    }
+    decode_nft_script(output["script"].as_str()?)
+}
+
+/// List the NFTs held by a wallet, with their data fields decoded
+pub async fn nft_gallery_internal(
+    state: &SharedState,
+    wallet_id: &str,
+    instance: Option<&str>,
+) -> Result<Vec<NftInfo>, String> {
+    let base_url = crate::headless_url(state, instance).await?;
+    let client = reqwest::Client::new();
+
+    let addresses: serde_json::Value = client
+        .get(format!("{}/wallet/addresses", base_url))
+        .header("X-Wallet-Id", wallet_id)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to get wallet addresses: {}", e))?
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse response: {}", e))?;
+
+    // Sum token balances over every wallet address
+    let mut tokens: BTreeMap<String, (String, String, i64)> = BTreeMap::new();
+    for address in addresses["addresses"].as_array().into_iter().flatten() {
+        let Some(address) = address.as_str() else {
+            continue;
+        };
+
+        let balance: serde_json::Value = client
+            .get("http://127.0.0.1:8080/v1a/thin_wallet/address_balance")
+            .query(&[("address", address)])
+            .send()
+            .await
+            .map_err(|e| format!("Failed to get address balance: {}", e))?
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse address balance: {}", e))?;
+
+        if let Some(tokens_data) = balance["tokens_data"].as_object() {
+            for (uid, data) in tokens_data {
+                if uid == "00" {
+                    continue;
+                }
+                let amount =
+                    data["received"].as_i64().unwrap_or(0) - data["spent"].as_i64().unwrap_or(0);
+                let entry = tokens.entry(uid.clone()).or_insert_with(|| {
+                    (
+                        data["name"].as_str().unwrap_or_default().to_string(),
+                        data["symbol"].as_str().unwrap_or_default().to_string(),
+                        0,
+                    )
+                });
+                entry.2 += amount;
+            }
+        }
+    }
+
+    let mut nfts = Vec::new();
+    for (token_uid, (name, symbol, balance)) in tokens {
+        if balance <= 0 {
+            continue;
+        }
+        if let Some(data) = nft_data(&client, &token_uid).await {
+            nfts.push(NftInfo {
+                token_uid,
+                name,
+                symbol,
+                balance,
+                data,
+            });
+        }
+    }
+
+    Ok(nfts)
+}
+
+fn operation_result(
+    wallet_id: &str,
+    token_uid: &str,
+    amount: Option<u64>,
+    result: &serde_json::Value,
+) -> Result<TokenOperation, String> {
+    let hash = result["hash"]
+        .as_str()
+        .ok_or("Missing transaction hash in response")?
+        .to_string();
+    Ok(TokenOperation {
+        wallet_id: wallet_id.to_string(),
+        token_uid: token_uid.to_string(),
+        hash,
+        amount,
+    })
+}
+
+/// Mint more of a custom token (the wallet must hold a mint authority)
+pub async fn mint_tokens_internal(
+    state: &SharedState,
+    wallet_id: &str,
+    token_uid: &str,
+    amount: u64,
+    address: Option<&str>,
+    instance: Option<&str>,
+) -> Result<TokenOperation, String> {
+    if amount == 0 {
+        return Err("Amount must be positive".to_string());
+    }
+
+    let base_url = crate::headless_url(state, instance).await?;
+    let mut body = json!({ "token": token_uid, "amount": amount });
+    if let Some(address) = address {
+        body["address"] = json!(address);
+    }
+
+    let result = headless::wallet_post(&base_url, wallet_id, "/wallet/mint-tokens", &body)
+        .await
+        .map_err(|e| format!("Failed to mint tokens: {}", e))?;
+
+    operation_result(wallet_id, token_uid, Some(amount), &result)
+}
+
+/// Melt (destroy) tokens held by the wallet (the wallet must hold a melt authority)
+pub async fn melt_tokens_internal(
+    state: &SharedState,
+    wallet_id: &str,
+    token_uid: &str,
+    amount: u64,
+    instance: Option<&str>,
+) -> Result<TokenOperation, String> {
+    if amount == 0 {
+        return Err("Amount must be positive".to_string());
+    }
+
+    let base_url = crate::headless_url(state, instance).await?;
+    let result = headless::wallet_post(
+        &base_url,
+        wallet_id,
+        "/wallet/melt-tokens",
+        &json!({ "token": token_uid, "amount": amount }),
+    )
+    .await
+    .map_err(|e| format!("Failed to melt tokens: {}", e))?;
+
+    operation_result(wallet_id, token_uid, Some(amount), &result)
+}
+
+/// Send a mint or melt authority to another address, optionally keeping one
+pub async fn delegate_authority_internal(
+    state: &SharedState,
+    wallet_id: &str,
+    token_uid: &str,
+    authority: Authority,
+    address: &str,
+    keep_authority: bool,
+    instance: Option<&str>,
+) -> Result<TokenOperation, String> {
+    crate::address::check_address(address)?;
+
+    let base_url = crate::headless_url(state, instance).await?;
+    let result = headless::wallet_post(
+        &base_url,
+        wallet_id,
+        "/wallet/delegate-authority",
+        &json!({
+            "token": token_uid,
+            "type": authority.as_str(),
+            "address": address,
+            "create_another": keep_authority,
+        }),
+    )
+    .await
+    .map_err(|e| format!("Failed to delegate {} authority: {}", authority.as_str(), e))?;
+
+    operation_result(wallet_id, token_uid, None, &result)
+}
+
+// Find which registered wallet on `instance` owns an address
+async fn address_owner(base_url: &str, instance: &str, address: &str) -> Option<String> {
+    for wallet in wallets::list_wallets()
+        .into_iter()
+        .filter(|w| w.instance == instance)
+    {
+        let owned = headless::wallet_get(
+            base_url,
+            &wallet.wallet_id,
+            "/wallet/address-index",
+            &[("address", address.to_string())],
+        )
+        .await
+        .is_ok();
+        if owned {
+            return Some(wallet.wallet_id);
+        }
+    }
+    None
+}
+
+/// List the unspent mint/melt authority outputs of a token and who holds them
+pub async fn list_authority_holders_internal(
+    state: &SharedState,
+    token_uid: &str,
+    instance: Option<&str>,
+) -> Result<Vec<AuthorityHolder>, String> {
+    Ok(get_token_authorities_internal(state, token_uid, instance)
+        .await?
+        .holders)
+}
+
+/// Find a token's unspent mint/melt authority outputs, who holds them and
+/// which registered wallets can mint or melt
+pub async fn get_token_authorities_internal(
+    state: &SharedState,
+    token_uid: &str,
+    instance: Option<&str>,
+) -> Result<TokenAuthorities, String> {
+    if token_uid == HTR_UID {
+        return Err("HTR has no mint or melt authorities".to_string());
+    }
+    let client = reqwest::Client::new();
+
+    let token_info: serde_json::Value = client
+        .get("http://127.0.0.1:8080/v1a/thin_wallet/token")
+        .query(&[("id", token_uid)])
+        .send()
+        .await
+        .map_err(|e| format!("Failed to get token info: {}", e))?
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse token info: {}", e))?;
+
+    if !token_info["success"].as_bool().unwrap_or(false) {
+        let message = token_info["message"].as_str().unwrap_or("Unknown token");
+        return Err(format!("Failed to get token info: {}", message));
+    }
+
+    // Wallet ownership is resolved through wallet-headless when it's running
+    let instance_name = instance.unwrap_or(DEFAULT_HEADLESS_INSTANCE);
+    let base_url = crate::headless_url(state, instance).await.ok();
+
+    let mut holders = Vec::new();
+    for authority in [Authority::Mint, Authority::Melt] {
+        let utxos = token_info[authority.as_str()]
+            .as_array()
+            .cloned()
+            .unwrap_or_default();
+
+        for utxo in utxos {
+            let tx_id = utxo["tx_id"].as_str().unwrap_or_default().to_string();
+            let index = utxo["index"].as_u64().unwrap_or(0) as u32;
+
+            let tx: serde_json::Value = client
+                .get("http://127.0.0.1:8080/v1a/transaction")
+                .query(&[("id", tx_id.as_str())])
+                .send()
+                .await
+                .map_err(|e| format!("Failed to get transaction {}: {}", tx_id, e))?
+                .json()
+                .await
+                .map_err(|e| format!("Failed to parse transaction {}: {}", tx_id, e))?;
+
+            let output = &tx["tx"]["outputs"][index as usize];
+            let is_authority =
+                output["token_data"].as_u64().unwrap_or(0) & TOKEN_AUTHORITY_MASK != 0;
+            let mask = match authority {
+                Authority::Mint => TOKEN_MINT_MASK,
+                Authority::Melt => TOKEN_MELT_MASK,
+            };
+            if !is_authority || output["value"].as_u64().unwrap_or(0) & mask == 0 {
+                continue;
+            }
+
+            let address = output["decoded"]["address"].as_str().map(|s| s.to_string());
+            let wallet_id = match (&base_url, &address) {
+                (Some(base_url), Some(address)) => {
+                    address_owner(base_url, instance_name, address).await
+                }
+                _ => None,
+            };
+
+            holders.push(AuthorityHolder {
+                authority,
+                tx_id,
+                index,
+                address,
+                wallet_id,
+            });
+        }
+    }
+
+    let wallets_with = |authority: Authority| {
+        let mut ids: Vec<String> = holders
+            .iter()
+            .filter(|h| h.authority == authority)
+            .filter_map(|h| h.wallet_id.clone())
+            .collect();
+        ids.sort();
+        ids.dedup();
+        ids
+    };
+    Ok(TokenAuthorities {
+        token_uid: token_uid.to_string(),
+        mint_wallets: wallets_with(Authority::Mint),
+        melt_wallets: wallets_with(Authority::Melt),
+        holders,
+    })
+}
+
+// ============================================================================
+// Tauri Commands
+// ============================================================================
+
+// Create a custom token and notify the frontend
+#[tauri::command]
+pub async fn create_token(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, SharedState>,
+    wallet_id: String,
+    name: String,
+    symbol: String,
+    amount: u64,
+    instance: Option<String>,
+) -> Result<CreatedToken, String> {
+    let token = create_token_internal(
+        &state,
+        &wallet_id,
+        &name,
+        &symbol,
+        amount,
+        instance.as_deref(),
+    )
+    .await?;
+
+    let _ = app.emit("token-created", &token);
+
+    Ok(token)
+}
+
+// Create an NFT with a data field (e.g. a metadata URI)
+#[tauri::command]
+pub async fn create_nft(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, SharedState>,
+    request: CreateNftRequest,
+) -> Result<CreatedToken, String> {
+    let token = create_nft_internal(
+        &state,
+        &request.wallet_id,
+        &request.name,
+        &request.symbol,
+        request.amount,
+        &request.data,
+        request.instance.as_deref(),
+    )
+    .await?;
+
+    let _ = app.emit("token-created", &token);
+
+    Ok(token)
+}
+
+// List NFTs held by a wallet
+#[tauri::command]
+pub async fn nft_gallery(
+    state: tauri::State<'_, SharedState>,
+    wallet_id: String,
+    instance: Option<String>,
+) -> Result<Vec<NftInfo>, String> {
+    nft_gallery_internal(&state, &wallet_id, instance.as_deref()).await
+}
+
+// List the tokens registered in the current workspace
+#[tauri::command]
+pub async fn list_tokens(
+    state: tauri::State<'_, SharedState>,
+) -> Result<Vec<RegisteredToken>, String> {
+    Ok(list_tokens_internal(&state).await)
+}
+
+// Register a token by configuration string, or by UID, name and symbol
+#[tauri::command]
+pub async fn register_token(
+    state: tauri::State<'_, SharedState>,
+    configuration_string: Option<String>,
+    token_uid: Option<String>,
+    name: Option<String>,
+    symbol: Option<String>,
+) -> Result<RegisteredToken, String> {
+    let (token_uid, name, symbol) = match (configuration_string, token_uid, name, symbol) {
+        (Some(config), _, _, _) => parse_configuration_string(&config)?,
+        (None, Some(token_uid), Some(name), Some(symbol)) => (token_uid, name, symbol),
+        _ => {
+            return Err("Provide a configuration string, or token_uid, name and symbol".to_string())
+        }
+    };
+
+    register_token_internal(&state, &token_uid, &name, &symbol, None).await
+}
+
+// Get a wallet's balance of HTR and every registered token
+#[tauri::command]
+pub async fn get_token_balances(
+    state: tauri::State<'_, SharedState>,
+    wallet_id: String,
+    instance: Option<String>,
+) -> Result<Vec<TokenBalance>, String> {
+    get_token_balances_internal(&state, &wallet_id, instance.as_deref()).await
+}
+
+// Get a wallet's transaction history with registered token symbols
+#[tauri::command]
+pub async fn get_tx_history(
+    state: tauri::State<'_, SharedState>,
+    wallet_id: String,
+    limit: Option<u64>,
+    instance: Option<String>,
+) -> Result<Vec<serde_json::Value>, String> {
+    get_tx_history_internal(&state, &wallet_id, limit, instance.as_deref()).await
+}
+
+// Mint more of a custom token
+#[tauri::command]
+pub async fn mint_tokens(
+    state: tauri::State<'_, SharedState>,
+    wallet_id: String,
+    token_uid: String,
+    amount: u64,
+    address: Option<String>,
+    instance: Option<String>,
+) -> Result<TokenOperation, String> {
+    mint_tokens_internal(
+        &state,
+        &wallet_id,
+        &token_uid,
+        amount,
+        address.as_deref(),
+        instance.as_deref(),
+    )
+    .await
+}
+
+// Melt (destroy) tokens held by a wallet
+#[tauri::command]
+pub async fn melt_tokens(
+    state: tauri::State<'_, SharedState>,
+    wallet_id: String,
+    token_uid: String,
+    amount: u64,
+    instance: Option<String>,
+) -> Result<TokenOperation, String> {
+    melt_tokens_internal(&state, &wallet_id, &token_uid, amount, instance.as_deref()).await
+}
+
+// Hand a mint/melt authority to another address
+#[tauri::command]
+pub async fn delegate_authority(
+    state: tauri::State<'_, SharedState>,
+    wallet_id: String,
+    token_uid: String,
+    authority: Authority,
+    address: String,
+    keep_authority: Option<bool>,
+    instance: Option<String>,
+) -> Result<TokenOperation, String> {
+    delegate_authority_internal(
+        &state,
+        &wallet_id,
+        &token_uid,
+        authority,
+        &address,
+        keep_authority.unwrap_or(true),
+        instance.as_deref(),
+    )
+    .await
+}
+
+// List who holds the mint/melt authorities of a token
+#[tauri::command]
+pub async fn list_authority_holders(
+    state: tauri::State<'_, SharedState>,
+    token_uid: String,
+    instance: Option<String>,
+) -> Result<Vec<AuthorityHolder>, String> {
+    list_authority_holders_internal(&state, &token_uid, instance.as_deref()).await
+}
+
+// Show which registered wallets hold a token's mint/melt authorities
+#[tauri::command]
+pub async fn get_token_authorities(
+    state: tauri::State<'_, SharedState>,
+    token_uid: String,
+    instance: Option<String>,
+) -> Result<TokenAuthorities, String> {
+    get_token_authorities_internal(&state, &token_uid, instance.as_deref()).await
+}
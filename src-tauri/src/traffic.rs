@@ -0,0 +1,602 @@
+//! HTTP traffic recording on the explorer server's proxy
+//!
+//! While recording, every request going through the node and wallet-headless
+//! proxy (`/v1a/*`, `/headless/*`) is captured with its response, so a dApp
+//! developer can see exactly what their app sent during a failing flow and
+//! what came back. Bodies are kept up to `max_body_bytes` each (the rest is
+//! marked truncated), the oldest exchanges are dropped past `max_entries`, and
+//! secrets are redacted before anything is stored: credential headers
+//! (`Authorization`, `X-API-KEY`, cookies) and JSON fields such as `seed`,
+//! `password` or `pin`. `export_traffic_recording` writes the capture as a
+//! HAR 1.2 file (for browser devtools and HAR viewers) or as plain JSON.
+//!
+//! Request bodies are buffered while recording; responses keep streaming and
+//! are captured as they pass through. WebSocket traffic isn't recorded.
+
+use axum::body::{Body, Bytes};
+use axum::extract::{Request, State};
+use axum::http::{header, HeaderMap};
+use axum::middleware::Next;
+use axum::response::Response;
+use futures_util::StreamExt;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::VecDeque;
+use std::fs;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex as StdMutex};
+use std::time::Instant;
+
+use crate::access_log::{AccessError, Upstream};
+use crate::SharedState;
+
+const DEFAULT_MAX_ENTRIES: usize = 1_000;
+const DEFAULT_MAX_BODY_BYTES: usize = 64 * 1024;
+// Largest request body buffered while recording
+const MAX_REQUEST_BODY: usize = 16 * 1024 * 1024;
+pub(crate) const REDACTED: &str = "[REDACTED]";
+// Compared lowercased
+const SECRET_HEADERS: [&str; 5] = [
+    "authorization",
+    "proxy-authorization",
+    "x-api-key",
+    "cookie",
+    "set-cookie",
+];
+pub(crate) const SECRET_FIELDS: [&str; 13] = [
+    "seed",
+    "words",
+    "mnemonic",
+    "passphrase",
+    "password",
+    "pin",
+    "pincode",
+    "xpriv",
+    "xprivkey",
+    "private_key",
+    "privatekey",
+    "secret",
+    "api_key",
+];
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Header {
+    pub name: String,
+    pub value: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CapturedBody {
+    // Bytes sent, including any truncated part
+    pub size: usize,
+    pub text: String,
+    pub truncated: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrafficEntry {
+    pub id: u64,
+    // Unix time in milliseconds
+    pub started_at: u64,
+    // Until the response body was fully sent
+    pub time_ms: f64,
+    pub method: String,
+    // URL the client requested from the explorer server
+    pub url: String,
+    // Node or wallet-headless the proxy sent it to ("cache" when answered
+    // from the proxy cache)
+    pub upstream: Option<String>,
+    pub status: u16,
+    pub request_headers: Vec<Header>,
+    pub request_body: Option<CapturedBody>,
+    pub response_headers: Vec<Header>,
+    pub response_body: Option<CapturedBody>,
+    // Why the proxy failed the request
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrafficRecordingStatus {
+    pub recording: bool,
+    // Unix time in milliseconds of the current (or last) recording
+    pub started_at: Option<u64>,
+    pub entries: usize,
+    // Oldest entries dropped past `max_entries`
+    pub dropped: u64,
+    pub max_entries: usize,
+    pub max_body_bytes: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrafficExport {
+    pub path: String,
+    pub format: String,
+    pub entries: usize,
+}
+
+/// The capture, kept in `AppState` so it survives explorer restarts
+pub struct TrafficRecording {
+    recording: bool,
+    // Bumped on every start, so exchanges in flight don't land in a new recording
+    session: u64,
+    started_at: Option<u64>,
+    next_id: u64,
+    max_entries: usize,
+    max_body_bytes: usize,
+    entries: VecDeque<TrafficEntry>,
+    dropped: u64,
+}
+
+impl Default for TrafficRecording {
+    fn default() -> Self {
+        Self {
+            recording: false,
+            session: 0,
+            started_at: None,
+            next_id: 0,
+            max_entries: DEFAULT_MAX_ENTRIES,
+            max_body_bytes: DEFAULT_MAX_BODY_BYTES,
+            entries: VecDeque::new(),
+            dropped: 0,
+        }
+    }
+}
+
+pub type SharedTrafficRecording = Arc<StdMutex<TrafficRecording>>;
+
+impl TrafficRecording {
+    fn status(&self) -> TrafficRecordingStatus {
+        TrafficRecordingStatus {
+            recording: self.recording,
+            started_at: self.started_at,
+            entries: self.entries.len(),
+            dropped: self.dropped,
+            max_entries: self.max_entries,
+            max_body_bytes: self.max_body_bytes,
+        }
+    }
+
+    fn push(&mut self, session: u64, mut entry: TrafficEntry) {
+        if !self.recording || session != self.session {
+            return;
+        }
+        self.next_id += 1;
+        entry.id = self.next_id;
+        self.entries.push_back(entry);
+        while self.entries.len() > self.max_entries {
+            self.entries.pop_front();
+            self.dropped += 1;
+        }
+    }
+}
+
+/// Middleware state: where to record, and the scheme clients connect with
+#[derive(Clone)]
+pub struct TrafficState {
+    recording: SharedTrafficRecording,
+    scheme: &'static str,
+}
+
+impl TrafficState {
+    pub fn new(recording: SharedTrafficRecording, tls: bool) -> Self {
+        Self {
+            recording,
+            scheme: crate::tls::scheme(tls),
+        }
+    }
+}
+
+fn capture_headers(headers: &HeaderMap) -> Vec<Header> {
+    headers
+        .iter()
+        .map(|(name, value)| Header {
+            name: name.to_string(),
+            value: if SECRET_HEADERS.contains(&name.as_str()) {
+                REDACTED.to_string()
+            } else {
+                String::from_utf8_lossy(value.as_bytes()).to_string()
+            },
+        })
+        .collect()
+}
+
+fn redact_json(value: &mut Value) {
+    match value {
+        Value::Object(fields) => {
+            for (key, field) in fields.iter_mut() {
+                if SECRET_FIELDS.contains(&key.to_lowercase().as_str()) {
+                    *field = Value::String(REDACTED.to_string());
+                } else {
+                    redact_json(field);
+                }
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(redact_json),
+        _ => {}
+    }
+}
+
+// Body text with secrets redacted; bodies that aren't complete JSON but
+// mention a secret field are dropped altogether
+fn capture_body(bytes: &[u8], size: usize, max_bytes: usize) -> CapturedBody {
+    let truncated = size > bytes.len() || bytes.len() > max_bytes;
+    let text = if let Ok(mut json) = serde_json::from_slice::<Value>(bytes) {
+        redact_json(&mut json);
+        json.to_string()
+    } else {
+        let text = String::from_utf8_lossy(bytes).to_lowercase();
+        if SECRET_FIELDS
+            .iter()
+            .any(|field| text.contains(&format!("\"{}\"", field)))
+        {
+            format!("{} (body mentions a secret field)", REDACTED)
+        } else {
+            String::from_utf8_lossy(bytes).to_string()
+        }
+    };
+    let text = if text.len() > max_bytes {
+        let mut end = max_bytes;
+        while !text.is_char_boundary(end) {
+            end -= 1;
+        }
+        text[..end].to_string()
+    } else {
+        text
+    };
+    CapturedBody {
+        size,
+        text,
+        truncated,
+    }
+}
+
+// An exchange whose response is still streaming; recorded once the body is
+// done (or the client went away)
+struct PendingEntry {
+    recording: SharedTrafficRecording,
+    session: u64,
+    started: Instant,
+    max_body_bytes: usize,
+    entry: Option<TrafficEntry>,
+    body: Vec<u8>,
+    body_size: usize,
+}
+
+impl PendingEntry {
+    fn append(&mut self, chunk: &Bytes) {
+        self.body_size += chunk.len();
+        // One byte past the cap tells a body that was cut off
+        let room = (self.max_body_bytes + 1).saturating_sub(self.body.len());
+        self.body.extend_from_slice(&chunk[..chunk.len().min(room)]);
+    }
+}
+
+impl Drop for PendingEntry {
+    fn drop(&mut self) {
+        let Some(mut entry) = self.entry.take() else {
+            return;
+        };
+        entry.time_ms = self.started.elapsed().as_secs_f64() * 1000.0;
+        if self.body_size > 0 {
+            entry.response_body = Some(capture_body(
+                &self.body,
+                self.body_size,
+                self.max_body_bytes,
+            ));
+        }
+        self.recording
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .push(self.session, entry);
+    }
+}
+
+/// Middleware capturing proxied exchanges while recording; install on the
+/// proxy routes with `axum::middleware::from_fn_with_state(TrafficState::new(..), record_traffic)`
+pub async fn record_traffic(
+    State(state): State<TrafficState>,
+    req: Request,
+    next: Next,
+) -> Response {
+    let active = {
+        let recording = state.recording.lock().unwrap_or_else(|e| e.into_inner());
+        recording
+            .recording
+            .then_some((recording.session, recording.max_body_bytes))
+    };
+    let Some((session, max_body_bytes)) = active else {
+        return next.run(req).await;
+    };
+
+    let started_at = crate::access_log::unix_millis();
+    let started = Instant::now();
+    let host = req
+        .headers()
+        .get(header::HOST)
+        .and_then(|host| host.to_str().ok())
+        .unwrap_or("localhost")
+        .to_string();
+    let path = req
+        .uri()
+        .path_and_query()
+        .map(|path| path.to_string())
+        .unwrap_or_else(|| req.uri().path().to_string());
+    let method = req.method().to_string();
+    let request_headers = capture_headers(req.headers());
+
+    let (parts, body) = req.into_parts();
+    let body = match axum::body::to_bytes(body, MAX_REQUEST_BODY).await {
+        Ok(body) => body,
+        Err(e) => {
+            let message = format!("Failed to read request body while recording: {}", e);
+            let mut response = Response::builder()
+                .status(413)
+                .body(Body::from(message.clone()))
+                .unwrap();
+            response.extensions_mut().insert(AccessError(message));
+            return response;
+        }
+    };
+    let request_body = (!body.is_empty()).then(|| capture_body(&body, body.len(), max_body_bytes));
+
+    let response = next.run(Request::from_parts(parts, Body::from(body))).await;
+
+    let entry = TrafficEntry {
+        id: 0,
+        started_at,
+        time_ms: 0.0,
+        method,
+        url: format!("{}://{}{}", state.scheme, host, path),
+        upstream: response
+            .extensions()
+            .get::<Upstream>()
+            .map(|upstream| upstream.0.clone()),
+        status: response.status().as_u16(),
+        request_headers,
+        request_body,
+        response_headers: capture_headers(response.headers()),
+        response_body: None,
+        error: response
+            .extensions()
+            .get::<AccessError>()
+            .map(|error| error.0.clone()),
+    };
+    let mut pending = PendingEntry {
+        recording: state.recording.clone(),
+        session,
+        started,
+        max_body_bytes,
+        entry: Some(entry),
+        body: Vec::new(),
+        body_size: 0,
+    };
+    let (parts, body) = response.into_parts();
+    let body = body.into_data_stream().map(move |chunk| {
+        if let Ok(chunk) = &chunk {
+            pending.append(chunk);
+        }
+        chunk
+    });
+    Response::from_parts(parts, Body::from_stream(body))
+}
+
+// 2024-05-01T12:00:00.000Z
+fn iso_timestamp(unix_millis: u64) -> String {
+    let time = time::OffsetDateTime::from_unix_timestamp_nanos(unix_millis as i128 * 1_000_000)
+        .unwrap_or(time::OffsetDateTime::UNIX_EPOCH);
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.{:03}Z",
+        time.year(),
+        time.month() as u8,
+        time.day(),
+        time.hour(),
+        time.minute(),
+        time.second(),
+        time.millisecond()
+    )
+}
+
+fn header_value<'a>(headers: &'a [Header], name: &str) -> Option<&'a str> {
+    headers
+        .iter()
+        .find(|header| header.name.eq_ignore_ascii_case(name))
+        .map(|header| header.value.as_str())
+}
+
+fn har_entry(entry: &TrafficEntry) -> Value {
+    let query: Vec<Value> = reqwest::Url::parse(&entry.url)
+        .map(|url| {
+            url.query_pairs()
+                .map(|(name, value)| json!({ "name": name, "value": value }))
+                .collect()
+        })
+        .unwrap_or_default();
+    let mut request = json!({
+        "method": entry.method,
+        "url": entry.url,
+        "httpVersion": "HTTP/1.1",
+        "cookies": [],
+        "headers": entry.request_headers,
+        "queryString": query,
+        "headersSize": -1,
+        "bodySize": entry.request_body.as_ref().map_or(0, |body| body.size),
+    });
+    if let Some(body) = &entry.request_body {
+        request["postData"] = json!({
+            "mimeType": header_value(&entry.request_headers, "content-type").unwrap_or(""),
+            "text": body.text,
+        });
+    }
+    let status_text = axum::http::StatusCode::from_u16(entry.status)
+        .ok()
+        .and_then(|status| status.canonical_reason())
+        .unwrap_or("");
+    let mut har = json!({
+        "startedDateTime": iso_timestamp(entry.started_at),
+        "time": entry.time_ms,
+        "request": request,
+        "response": {
+            "status": entry.status,
+            "statusText": status_text,
+            "httpVersion": "HTTP/1.1",
+            "cookies": [],
+            "headers": entry.response_headers,
+            "content": {
+                "size": entry.response_body.as_ref().map_or(0, |body| body.size),
+                "mimeType": header_value(&entry.response_headers, "content-type").unwrap_or(""),
+                "text": entry.response_body.as_ref().map(|body| body.text.as_str()),
+            },
+            "redirectURL": "",
+            "headersSize": -1,
+            "bodySize": entry.response_body.as_ref().map_or(0, |body| body.size),
+        },
+        "cache": {},
+        "timings": { "send": 0, "wait": entry.time_ms, "receive": 0 },
+    });
+    // HAR custom fields start with an underscore
+    if let Some(upstream) = &entry.upstream {
+        har["_upstream"] = upstream.clone().into();
+    }
+    if let Some(error) = &entry.error {
+        har["_error"] = error.clone().into();
+    }
+    har
+}
+
+/// The capture as a HAR 1.2 log
+pub fn to_har(entries: &[TrafficEntry]) -> Value {
+    json!({
+        "log": {
+            "version": "1.2",
+            "creator": { "name": "Hathor Forge", "version": env!("CARGO_PKG_VERSION") },
+            "entries": entries.iter().map(har_entry).collect::<Vec<_>>(),
+        }
+    })
+}
+
+async fn shared_recording(state: &SharedState) -> SharedTrafficRecording {
+    state.lock().await.traffic_recording.clone()
+}
+
+/// Start recording (dropping any previous capture)
+pub async fn start_recording_internal(
+    state: &SharedState,
+    max_entries: Option<usize>,
+    max_body_bytes: Option<usize>,
+) -> Result<TrafficRecordingStatus, String> {
+    if max_entries == Some(0) {
+        return Err("max_entries must be at least 1".to_string());
+    }
+    let recording = shared_recording(state).await;
+    let mut recording = recording.lock().unwrap_or_else(|e| e.into_inner());
+    recording.recording = true;
+    recording.session += 1;
+    recording.started_at = Some(crate::access_log::unix_millis());
+    recording.max_entries = max_entries.unwrap_or(DEFAULT_MAX_ENTRIES);
+    recording.max_body_bytes = max_body_bytes.unwrap_or(DEFAULT_MAX_BODY_BYTES);
+    recording.entries.clear();
+    recording.dropped = 0;
+    Ok(recording.status())
+}
+
+/// Stop recording, keeping the capture for export
+pub async fn stop_recording_internal(state: &SharedState) -> TrafficRecordingStatus {
+    let recording = shared_recording(state).await;
+    let mut recording = recording.lock().unwrap_or_else(|e| e.into_inner());
+    recording.recording = false;
+    recording.status()
+}
+
+/// The captured exchanges, oldest first
+pub async fn get_entries_internal(state: &SharedState) -> Vec<TrafficEntry> {
+    let recording = shared_recording(state).await;
+    let recording = recording.lock().unwrap_or_else(|e| e.into_inner());
+    recording.entries.iter().cloned().collect()
+}
+
+/// Write the capture to `path` (default: the downloads directory) as `har`
+/// (the default) or `json`
+pub async fn export_recording_internal(
+    state: &SharedState,
+    path: Option<String>,
+    format: Option<String>,
+) -> Result<TrafficExport, String> {
+    let format = format.unwrap_or_else(|| "har".to_string()).to_lowercase();
+    let entries = get_entries_internal(state).await;
+    let document = match format.as_str() {
+        "har" => to_har(&entries),
+        "json" => json!(entries),
+        _ => return Err(format!("Unknown format '{}'; expected har or json", format)),
+    };
+    let destination = match path {
+        Some(path) => PathBuf::from(path),
+        None => dirs::download_dir()
+            .or_else(dirs::home_dir)
+            .ok_or("No downloads directory; pass a path")?
+            .join(format!(
+                "hathor-forge-traffic-{}.{}",
+                crate::access_log::unix_millis() / 1000,
+                format
+            )),
+    };
+    let content = serde_json::to_string_pretty(&document)
+        .map_err(|e| format!("Failed to serialize traffic recording: {}", e))?;
+    fs::write(&destination, content)
+        .map_err(|e| format!("Failed to export traffic recording: {}", e))?;
+
+    Ok(TrafficExport {
+        path: destination.to_string_lossy().to_string(),
+        format,
+        entries: entries.len(),
+    })
+}
+
+// ============================================================================
+// Tauri Commands
+// ============================================================================
+
+// Start recording proxied HTTP traffic
+#[tauri::command]
+pub async fn start_traffic_recording(
+    state: tauri::State<'_, SharedState>,
+    max_entries: Option<usize>,
+    max_body_bytes: Option<usize>,
+) -> Result<TrafficRecordingStatus, String> {
+    start_recording_internal(&state, max_entries, max_body_bytes).await
+}
+
+// Stop recording proxied HTTP traffic
+#[tauri::command]
+pub async fn stop_traffic_recording(
+    state: tauri::State<'_, SharedState>,
+) -> Result<TrafficRecordingStatus, String> {
+    Ok(stop_recording_internal(&state).await)
+}
+
+// Whether traffic is being recorded, and how much was captured
+#[tauri::command]
+pub async fn get_traffic_recording_status(
+    state: tauri::State<'_, SharedState>,
+) -> Result<TrafficRecordingStatus, String> {
+    let recording = shared_recording(&state).await;
+    let status = recording.lock().unwrap_or_else(|e| e.into_inner()).status();
+    Ok(status)
+}
+
+// Get the captured exchanges
+#[tauri::command]
+pub async fn get_traffic_entries(
+    state: tauri::State<'_, SharedState>,
+) -> Result<Vec<TrafficEntry>, String> {
+    Ok(get_entries_internal(&state).await)
+}
+
+// Export the capture as HAR or JSON
+#[tauri::command]
+pub async fn export_traffic_recording(
+    state: tauri::State<'_, SharedState>,
+    path: Option<String>,
+    format: Option<String>,
+) -> Result<TrafficExport, String> {
+    export_recording_internal(&state, path, format).await
+}
@@ -0,0 +1,614 @@
+//! Transaction builder for wallet-headless wallets
+//!
+//! Wraps `/wallet/send-tx`, which supports multiple outputs (any token, with
+//! optional timelocks), explicit inputs and a change address. Dry runs build the
+//! same transaction with `/wallet/tx-proposal` and decode it instead of pushing.
+//! `estimate_tx` runs the pre-send checks (addresses, unlocked balance per token)
+//! and estimates the transaction weight the same way hathor-core does.
+//!
+//! For external (hardware/air-gapped) signers, `unsigned` requests return the
+//! proposal with its sighash and the wallet path of every input; the signatures
+//! come back through `submit_signed_tx`, which assembles and pushes the tx.
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+
+use crate::{headless, wallets, SharedState};
+
+const HTR_UID: &str = "00";
+
+// hathor-core minimum tx weight parameters
+const MIN_TX_WEIGHT: f64 = 14.0;
+const MIN_TX_WEIGHT_K: f64 = 100.0;
+const MIN_TX_WEIGHT_COEFFICIENT: f64 = 1.6;
+
+// Unsigned proposals have empty input data; a P2PKH signature + pubkey adds about this much
+const INPUT_SIGNATURE_BYTES: usize = 107;
+
+// Rough CPU hash rate used to turn a weight into a mining time (hashes/second)
+const ASSUMED_TX_MINING_HASHRATE: f64 = 2_000_000.0;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TxOutput {
+    pub address: String,
+    pub value: u64, // Amount in cents of the token
+    // Token UID (HTR when omitted)
+    #[serde(default)]
+    pub token: Option<String>,
+    // Unix timestamp until which the output can't be spent
+    #[serde(default)]
+    pub timelock: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TxInput {
+    pub hash: String,
+    pub index: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BuildTxRequest {
+    pub wallet_id: String,
+    pub outputs: Vec<TxOutput>,
+    // Inputs to spend; chosen by the wallet when empty
+    #[serde(default)]
+    pub inputs: Vec<TxInput>,
+    #[serde(default)]
+    pub change_address: Option<String>,
+    // Build and decode the transaction without pushing it
+    #[serde(default)]
+    pub dry_run: bool,
+    // Return the unsigned proposal and its signing data for an external signer
+    #[serde(default)]
+    pub unsigned: bool,
+    #[serde(default)]
+    pub instance: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BuildTxResult {
+    pub dry_run: bool,
+    // Set once the transaction was pushed
+    pub hash: Option<String>,
+    // Set for dry runs
+    pub tx_hex: Option<String>,
+    // Decoded (dry run/unsigned) or pushed transaction as returned by wallet-headless
+    pub tx: Value,
+    // Set for unsigned requests
+    #[serde(default)]
+    pub signing_data: Option<SigningData>,
+}
+
+/// A wallet input of an unsigned transaction
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SigningInput {
+    pub input_index: u32,
+    pub address_index: u32,
+    pub address_path: String,
+}
+
+/// What an external signer needs: every input signs the same sighash
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SigningData {
+    // Serialized tx with empty input data (hex)
+    pub sighash: String,
+    // sha256(sighash), the digest to sign with ECDSA/secp256k1 (hex)
+    pub sighash_digest: String,
+    pub inputs: Vec<SigningInput>,
+}
+
+/// A signature for one input, produced outside of wallet-headless
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExternalSignature {
+    pub input_index: u32,
+    // Wallet address index of the input; needed with `signature`
+    #[serde(default)]
+    pub address_index: Option<u32>,
+    // DER-encoded signature (hex); the input data is built from it
+    #[serde(default)]
+    pub signature: Option<String>,
+    // Complete input data (hex), for scripts wallet-headless can't build
+    #[serde(default)]
+    pub input_data: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SubmitSignedTxRequest {
+    pub wallet_id: String,
+    pub tx_hex: String,
+    pub signatures: Vec<ExternalSignature>,
+    #[serde(default)]
+    pub instance: Option<String>,
+}
+
+/// Balance check for one token of a send
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TokenRequirement {
+    pub token: String,
+    pub required: u64,
+    pub available: u64,
+    pub sufficient: bool,
+}
+
+/// Pre-send report for a transaction
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TxEstimate {
+    pub valid: bool,
+    pub errors: Vec<String>,
+    pub tokens: Vec<TokenRequirement>,
+    pub num_inputs: Option<usize>,
+    pub num_outputs: Option<usize>,
+    pub size_bytes: Option<usize>,
+    pub weight: Option<f64>,
+    pub estimated_mining_seconds: Option<f64>,
+}
+
+// Byte reader over a serialized transaction
+struct TxReader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> TxReader<'a> {
+    fn take(&mut self, n: usize) -> Result<&'a [u8], String> {
+        let end = self.pos + n;
+        let slice = self
+            .bytes
+            .get(self.pos..end)
+            .ok_or("Transaction is truncated")?;
+        self.pos = end;
+        Ok(slice)
+    }
+
+    fn u8(&mut self) -> Result<u8, String> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u16(&mut self) -> Result<u16, String> {
+        let b = self.take(2)?;
+        Ok(u16::from_be_bytes([b[0], b[1]]))
+    }
+}
+
+// hathor-core's sighash_all: the funds part of the tx with every input's data emptied
+fn sighash_all(tx: &[u8]) -> Result<Vec<u8>, String> {
+    let mut reader = TxReader { bytes: tx, pos: 0 };
+    // signal_bits, version, tokens, inputs, outputs
+    let header = reader.take(5)?;
+    let (num_tokens, num_inputs, num_outputs) = (header[2], header[3], header[4]);
+
+    let mut sighash = header.to_vec();
+    sighash.extend_from_slice(reader.take(32 * num_tokens as usize)?);
+
+    for _ in 0..num_inputs {
+        // tx_id and index, then the (dropped) input data
+        sighash.extend_from_slice(reader.take(33)?);
+        sighash.extend_from_slice(&[0, 0]);
+        let data_len = reader.u16()?;
+        reader.take(data_len as usize)?;
+    }
+
+    let outputs_start = reader.pos;
+    for _ in 0..num_outputs {
+        // Values that don't fit in a positive i32 are stored as a negative i64
+        let value_len = if reader.take(1)?[0] & 0x80 != 0 { 8 } else { 4 };
+        reader.take(value_len - 1)?;
+        reader.u8()?; // token_data
+        let script_len = reader.u16()?;
+        reader.take(script_len as usize)?;
+    }
+    sighash.extend_from_slice(&tx[outputs_start..reader.pos]);
+
+    Ok(sighash)
+}
+
+// hathor-core's minimum weight for a tx of `size` bytes moving `amount` cents
+fn min_tx_weight(size: usize, amount: u64) -> f64 {
+    let amount = (amount.max(1)) as f64 / 100.0;
+    let weight = MIN_TX_WEIGHT_COEFFICIENT * (size as f64).log2()
+        + 4.0 / (1.0 + MIN_TX_WEIGHT_K / amount) * 4.0;
+    weight.max(MIN_TX_WEIGHT)
+}
+
+fn validate_request(request: &BuildTxRequest) -> Result<(), String> {
+    if request.outputs.is_empty() {
+        return Err("At least one output is required".to_string());
+    }
+    for (i, output) in request.outputs.iter().enumerate() {
+        crate::address::check_address(&output.address)
+            .map_err(|e| format!("Output {}: {}", i, e))?;
+        if output.value == 0 {
+            return Err(format!("Output {} must have a positive value", i));
+        }
+    }
+    Ok(())
+}
+
+// Request body shared by /wallet/send-tx and /wallet/tx-proposal
+fn request_body(request: &BuildTxRequest) -> Value {
+    let outputs: Vec<Value> = request
+        .outputs
+        .iter()
+        .map(|output| {
+            let mut out = json!({
+                "address": output.address,
+                "value": output.value,
+            });
+            if let Some(token) = &output.token {
+                out["token"] = json!(token);
+            }
+            if let Some(timelock) = output.timelock {
+                out["timelock"] = json!(timelock);
+            }
+            out
+        })
+        .collect();
+
+    let mut body = json!({ "outputs": outputs });
+    if !request.inputs.is_empty() {
+        body["inputs"] = json!(request.inputs);
+    }
+    if let Some(change_address) = &request.change_address {
+        body["change_address"] = json!(change_address);
+    }
+    body
+}
+
+// Fill in the wallet's default token and change address where the request left them out
+async fn with_wallet_defaults(
+    request: &BuildTxRequest,
+    base_url: &str,
+) -> Result<BuildTxRequest, String> {
+    let settings = wallets::wallet_settings(&request.wallet_id);
+    let mut request = request.clone();
+
+    if let Some(token) = &settings.default_token {
+        for output in request.outputs.iter_mut().filter(|o| o.token.is_none()) {
+            output.token = Some(token.clone());
+        }
+    }
+    if request.change_address.is_none() {
+        request.change_address =
+            wallets::change_address(&settings, base_url, &request.wallet_id).await?;
+    }
+    Ok(request)
+}
+
+/// Validate a send and estimate its size, weight and mining time without pushing it
+pub async fn estimate_tx_internal(
+    state: &SharedState,
+    request: &BuildTxRequest,
+) -> Result<TxEstimate, String> {
+    let base_url = crate::headless_url(state, request.instance.as_deref()).await?;
+    let request = &with_wallet_defaults(request, &base_url).await?;
+
+    let mut errors = Vec::new();
+    if let Err(e) = validate_request(request) {
+        errors.push(e);
+    }
+
+    // Unlocked balance per token
+    let mut required: BTreeMap<String, u64> = BTreeMap::new();
+    for output in &request.outputs {
+        let token = output.token.clone().unwrap_or_else(|| HTR_UID.to_string());
+        *required.entry(token).or_default() += output.value;
+    }
+
+    let client = reqwest::Client::new();
+    let mut tokens = Vec::new();
+    for (token, required) in required {
+        // /wallet/balance has no `success` field, so it's read directly
+        let balance: Value = client
+            .get(format!("{}/wallet/balance", base_url))
+            .header("X-Wallet-Id", &request.wallet_id)
+            .query(&[("token", token.as_str())])
+            .send()
+            .await
+            .map_err(|e| format!("Failed to get wallet balance: {}", e))?
+            .json()
+            .await
+            .map_err(|e| format!("Failed to parse response: {}", e))?;
+        let available = balance["available"].as_u64().unwrap_or(0);
+
+        let sufficient = available >= required;
+        if !sufficient {
+            errors.push(format!(
+                "Insufficient unlocked balance of token {}: need {}, have {}",
+                token, required, available
+            ));
+        }
+        tokens.push(TokenRequirement {
+            token,
+            required,
+            available,
+            sufficient,
+        });
+    }
+
+    let mut estimate = TxEstimate {
+        valid: false,
+        errors,
+        tokens,
+        num_inputs: None,
+        num_outputs: None,
+        size_bytes: None,
+        weight: None,
+        estimated_mining_seconds: None,
+    };
+    if !estimate.errors.is_empty() {
+        return Ok(estimate);
+    }
+
+    // Build the actual transaction to learn its inputs, outputs (with change) and size
+    let body = request_body(request);
+    let proposal =
+        match headless::wallet_post(&base_url, &request.wallet_id, "/wallet/tx-proposal", &body)
+            .await
+        {
+            Ok(proposal) => proposal,
+            Err(e) => {
+                estimate
+                    .errors
+                    .push(format!("Failed to build transaction: {}", e));
+                return Ok(estimate);
+            }
+        };
+    let tx_hex = proposal["txHex"].as_str().unwrap_or_default();
+    let decoded = headless::wallet_post(
+        &base_url,
+        &request.wallet_id,
+        "/wallet/decode",
+        &json!({ "txHex": tx_hex }),
+    )
+    .await
+    .map_err(|e| format!("Failed to decode transaction: {}", e))?;
+
+    let num_inputs = decoded["tx"]["inputs"].as_array().map_or(0, |a| a.len());
+    let num_outputs = decoded["tx"]["outputs"].as_array().map_or(0, |a| a.len());
+    let size = tx_hex.len() / 2 + num_inputs * INPUT_SIGNATURE_BYTES;
+    let amount: u64 = decoded["tx"]["outputs"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .filter_map(|output| output["value"].as_u64())
+        .sum();
+    let weight = min_tx_weight(size, amount);
+
+    estimate.valid = true;
+    estimate.num_inputs = Some(num_inputs);
+    estimate.num_outputs = Some(num_outputs);
+    estimate.size_bytes = Some(size);
+    estimate.weight = Some(weight);
+    estimate.estimated_mining_seconds = Some(weight.exp2() / ASSUMED_TX_MINING_HASHRATE);
+    Ok(estimate)
+}
+
+/// Error message for a failed send, explained by the pre-send checks when they catch something
+pub async fn send_failure_message(
+    state: &SharedState,
+    request: &BuildTxRequest,
+    error: &str,
+) -> String {
+    let problems = estimate_tx_internal(state, request)
+        .await
+        .map(|estimate| estimate.errors)
+        .unwrap_or_default();
+    if problems.is_empty() {
+        format!("Transaction failed: {}", error)
+    } else {
+        format!("Transaction failed: {} ({})", error, problems.join("; "))
+    }
+}
+
+/// Build a transaction with many outputs and push it (or decode it on dry runs)
+pub async fn build_and_send_tx_internal(
+    state: &SharedState,
+    request: &BuildTxRequest,
+) -> Result<BuildTxResult, String> {
+    let base_url = crate::headless_url(state, request.instance.as_deref()).await?;
+    let request = &with_wallet_defaults(request, &base_url).await?;
+    validate_request(request)?;
+
+    let body = request_body(request);
+
+    if request.dry_run && request.unsigned {
+        return Err("dry_run and unsigned can't be combined".to_string());
+    }
+
+    if request.dry_run || request.unsigned {
+        let proposal =
+            headless::wallet_post(&base_url, &request.wallet_id, "/wallet/tx-proposal", &body)
+                .await
+                .map_err(|e| format!("Failed to build transaction: {}", e))?;
+        let tx_hex = proposal["txHex"]
+            .as_str()
+            .ok_or("Missing txHex in response")?
+            .to_string();
+
+        let decoded = headless::wallet_post(
+            &base_url,
+            &request.wallet_id,
+            "/wallet/decode",
+            &json!({ "txHex": tx_hex }),
+        )
+        .await
+        .map_err(|e| format!("Failed to decode transaction: {}", e))?;
+
+        let signing_data = if request.unsigned {
+            Some(signing_data(&base_url, &request.wallet_id, &tx_hex).await?)
+        } else {
+            None
+        };
+
+        return Ok(BuildTxResult {
+            dry_run: request.dry_run,
+            hash: None,
+            tx_hex: Some(tx_hex),
+            tx: decoded["tx"].clone(),
+            signing_data,
+        });
+    }
+
+    let result =
+        headless::wallet_post(&base_url, &request.wallet_id, "/wallet/send-tx", &body).await;
+    let result = match result {
+        Ok(result) => result,
+        Err(e) => return Err(send_failure_message(state, request, &e).await),
+    };
+
+    Ok(BuildTxResult {
+        dry_run: false,
+        hash: result["hash"].as_str().map(|s| s.to_string()),
+        tx_hex: None,
+        tx: result,
+        signing_data: None,
+    })
+}
+
+// Sighash and wallet input paths of an unsigned proposal
+async fn signing_data(
+    base_url: &str,
+    wallet_id: &str,
+    tx_hex: &str,
+) -> Result<SigningData, String> {
+    let tx = hex::decode(tx_hex).map_err(|e| format!("Invalid transaction hex: {}", e))?;
+    let sighash = sighash_all(&tx)?;
+
+    let wallet_inputs = headless::wallet_get(
+        base_url,
+        wallet_id,
+        "/wallet/tx-proposal/get-wallet-inputs",
+        &[("txHex", tx_hex.to_string())],
+    )
+    .await
+    .map_err(|e| format!("Failed to get wallet inputs: {}", e))?;
+
+    let inputs = wallet_inputs["inputs"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .map(|input| SigningInput {
+            input_index: input["inputIndex"].as_u64().unwrap_or(0) as u32,
+            address_index: input["addressIndex"].as_u64().unwrap_or(0) as u32,
+            address_path: input["addressPath"]
+                .as_str()
+                .unwrap_or_default()
+                .to_string(),
+        })
+        .collect();
+
+    Ok(SigningData {
+        sighash_digest: hex::encode(Sha256::digest(&sighash)),
+        sighash: hex::encode(sighash),
+        inputs,
+    })
+}
+
+/// Add externally produced signatures to an unsigned proposal and push it
+pub async fn submit_signed_tx_internal(
+    state: &SharedState,
+    request: &SubmitSignedTxRequest,
+) -> Result<BuildTxResult, String> {
+    if request.signatures.is_empty() {
+        return Err("At least one signature is required".to_string());
+    }
+    let base_url = crate::headless_url(state, request.instance.as_deref()).await?;
+
+    let mut signatures = Vec::new();
+    for signature in &request.signatures {
+        let data = match (
+            &signature.input_data,
+            &signature.signature,
+            signature.address_index,
+        ) {
+            (Some(input_data), _, _) => input_data.clone(),
+            (None, Some(sig), Some(address_index)) => {
+                let result = headless::wallet_post(
+                    &base_url,
+                    &request.wallet_id,
+                    "/wallet/tx-proposal/input-data",
+                    &json!({ "index": address_index, "signature": sig }),
+                )
+                .await
+                .map_err(|e| {
+                    format!(
+                        "Failed to build input data for input {}: {}",
+                        signature.input_index, e
+                    )
+                })?;
+                result["inputData"]
+                    .as_str()
+                    .ok_or("Missing inputData in response")?
+                    .to_string()
+            }
+            _ => {
+                return Err(format!(
+                    "Input {} needs input_data, or a signature with its address_index",
+                    signature.input_index
+                ))
+            }
+        };
+        signatures.push(json!({ "index": signature.input_index, "data": data }));
+    }
+
+    let signed = headless::wallet_post(
+        &base_url,
+        &request.wallet_id,
+        "/wallet/tx-proposal/add-signatures",
+        &json!({ "txHex": request.tx_hex, "signatures": signatures }),
+    )
+    .await
+    .map_err(|e| format!("Failed to add signatures: {}", e))?;
+    let tx_hex = signed["txHex"]
+        .as_str()
+        .ok_or("Missing txHex in response")?
+        .to_string();
+
+    let result = headless::post(&base_url, "/push-tx", &json!({ "txHex": tx_hex }))
+        .await
+        .map_err(|e| format!("Transaction failed: {}", e))?;
+
+    Ok(BuildTxResult {
+        dry_run: false,
+        hash: result["tx"]["hash"]
+            .as_str()
+            .or_else(|| result["hash"].as_str())
+            .map(|s| s.to_string()),
+        tx_hex: Some(tx_hex),
+        tx: result["tx"].clone(),
+        signing_data: None,
+    })
+}
+
+// ============================================================================
+// Tauri Commands
+// ============================================================================
+
+// Check a send and estimate its weight and mining time without pushing it
+#[tauri::command]
+pub async fn estimate_tx(
+    state: tauri::State<'_, SharedState>,
+    request: BuildTxRequest,
+) -> Result<TxEstimate, String> {
+    estimate_tx_internal(&state, &request).await
+}
+
+// Push a proposal signed by an external signer
+#[tauri::command]
+pub async fn submit_signed_tx(
+    state: tauri::State<'_, SharedState>,
+    request: SubmitSignedTxRequest,
+) -> Result<BuildTxResult, String> {
+    submit_signed_tx_internal(&state, &request).await
+}
+
+// Send a transaction with multiple outputs/inputs from a headless wallet
+#[tauri::command]
+pub async fn build_and_send_tx(
+    state: tauri::State<'_, SharedState>,
+    request: BuildTxRequest,
+) -> Result<BuildTxResult, String> {
+    build_and_send_tx_internal(&state, &request).await
+}
@@ -0,0 +1,140 @@
+//! Wallet sync progress
+//!
+//! wallet-headless only exposes a status code while a wallet syncs; every other
+//! wallet endpoint answers "not ready" until it is done. Progress is therefore
+//! reported as the sync phase plus elapsed time, and the address/history counts
+//! are filled in once the wallet is ready.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::time::{Duration, Instant};
+use tauri::{AppHandle, Emitter};
+
+use crate::SharedState;
+
+// wallet-lib status codes
+const WALLET_READY_STATUS: i32 = 3;
+const WALLET_ERROR_STATUS: i32 = 4;
+
+const POLL_INTERVAL: Duration = Duration::from_secs(1);
+const MAX_TRACKING: Duration = Duration::from_secs(120);
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WalletSyncProgress {
+    pub wallet_id: String,
+    pub instance: String,
+    pub status: String,
+    pub status_code: Option<i32>,
+    pub ready: bool,
+    // Seconds since tracking started (events only)
+    pub elapsed_secs: Option<u64>,
+    // Addresses derived so far: the used ones plus the gap limit
+    pub addresses_scanned: Option<usize>,
+    pub gap_limit: u32,
+    pub used_addresses: Option<usize>,
+    // Transactions in the wallet's history
+    pub history_loaded: Option<usize>,
+}
+
+async fn wallet_json(
+    client: &reqwest::Client,
+    base_url: &str,
+    wallet_id: &str,
+    path: &str,
+) -> Result<Value, String> {
+    client
+        .get(format!("{}{}", base_url, path))
+        .header("X-Wallet-Id", wallet_id)
+        .send()
+        .await
+        .map_err(|e| format!("Failed to reach wallet-headless: {}", e))?
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse response: {}", e))
+}
+
+/// Sync phase of a wallet, with address and history counts once it is ready
+pub async fn get_wallet_sync_progress_internal(
+    state: &SharedState,
+    wallet_id: &str,
+    instance: Option<&str>,
+) -> Result<WalletSyncProgress, String> {
+    let base_url = crate::headless_url(state, instance).await?;
+    let client = reqwest::Client::new();
+
+    let status = wallet_json(&client, &base_url, wallet_id, "/wallet/status").await?;
+    let status_code = status["statusCode"].as_i64().map(|c| c as i32);
+    let ready = status_code == Some(WALLET_READY_STATUS);
+
+    let mut progress = WalletSyncProgress {
+        wallet_id: wallet_id.to_string(),
+        instance: instance
+            .unwrap_or(crate::DEFAULT_HEADLESS_INSTANCE)
+            .to_string(),
+        status: status["statusMessage"]
+            .as_str()
+            .or_else(|| status["message"].as_str())
+            .unwrap_or("Unknown")
+            .to_string(),
+        status_code,
+        ready,
+        elapsed_secs: None,
+        addresses_scanned: None,
+        gap_limit: crate::HEADLESS_GAP_LIMIT,
+        used_addresses: None,
+        history_loaded: None,
+    };
+
+    if ready {
+        let addresses = wallet_json(&client, &base_url, wallet_id, "/wallet/addresses").await?;
+        let scanned = addresses["addresses"].as_array().map(|a| a.len());
+        progress.addresses_scanned = scanned;
+        progress.used_addresses =
+            scanned.map(|n| n.saturating_sub(crate::HEADLESS_GAP_LIMIT as usize));
+
+        let history = wallet_json(&client, &base_url, wallet_id, "/wallet/tx-history").await?;
+        progress.history_loaded = history.as_array().map(|a| a.len());
+    }
+
+    Ok(progress)
+}
+
+/// Emit `wallet-sync-progress` events until the wallet is ready, fails, or
+/// tracking times out
+pub async fn track_wallet_sync(
+    app: AppHandle,
+    state: SharedState,
+    wallet_id: String,
+    instance: Option<String>,
+) {
+    let started = Instant::now();
+
+    while started.elapsed() < MAX_TRACKING {
+        // The wallet may not be started yet (e.g. while a restore is queued)
+        if let Ok(mut progress) =
+            get_wallet_sync_progress_internal(&state, &wallet_id, instance.as_deref()).await
+        {
+            progress.elapsed_secs = Some(started.elapsed().as_secs());
+            let done = progress.ready || progress.status_code == Some(WALLET_ERROR_STATUS);
+            let _ = app.emit("wallet-sync-progress", &progress);
+            if done {
+                return;
+            }
+        }
+        tokio::time::sleep(POLL_INTERVAL).await;
+    }
+}
+
+// ============================================================================
+// Tauri Commands
+// ============================================================================
+
+// Get a wallet's sync phase and, once ready, its address/history counts
+#[tauri::command]
+pub async fn get_wallet_sync_progress(
+    state: tauri::State<'_, SharedState>,
+    wallet_id: String,
+    instance: Option<String>,
+) -> Result<WalletSyncProgress, String> {
+    get_wallet_sync_progress_internal(&state, &wallet_id, instance.as_deref()).await
+}
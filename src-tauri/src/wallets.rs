@@ -0,0 +1,579 @@
+//! Persistent wallet registry
+//!
+//! Wallets created through Forge are recorded in `wallets.json` in the
+//! workspace's data directory, so they belong to that chain: a data reset
+//! drops them, and snapshots carry them along with the node data. Each entry
+//! carries per-wallet settings (label, default token, change address strategy)
+//! that the send commands apply when a request omits them.
+//!
+//! The registry never holds seeds. They go to the OS keychain (see secrets.rs)
+//! when the user has opted in, and only those wallets are re-opened
+//! automatically when wallet-headless (or Forge itself) restarts.
+
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex as StdMutex, RwLock};
+use std::time::Duration;
+use tracing::Instrument;
+
+const REGISTRY_FILE: &str = "wallets.json";
+
+// Serializes read-modify-write cycles on the registry file
+static REGISTRY_LOCK: StdMutex<()> = StdMutex::new(());
+// Data dir of the current workspace; the default one until the node starts
+static WORKSPACE: RwLock<Option<PathBuf>> = RwLock::new(None);
+
+// wallet-headless status code for a fully synced wallet
+const WALLET_READY_STATUS: i64 = 3;
+
+// Hathor wallets only accept 24-word seeds
+const SEED_WORD_COUNT: usize = 24;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct RegisteredWallet {
+    wallet_id: String,
+    // Only wallets whose seed is in the keychain can be re-opened
+    auto_start: bool,
+    created_at: u64,
+    // wallet-headless instance the wallet lives on
+    #[serde(default = "crate::default_headless_instance")]
+    instance: String,
+    // Multisig definition name, for P2SH wallets
+    #[serde(default)]
+    multisig_config: Option<String>,
+    #[serde(default)]
+    settings: WalletSettings,
+}
+
+/// Where the change of a send goes when the request doesn't say
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "strategy", rename_all = "snake_case")]
+pub enum ChangeAddressStrategy {
+    // Let wallet-headless pick (a fresh address)
+    #[default]
+    Default,
+    // Always the wallet's first address (index 0)
+    FirstAddress,
+    Fixed {
+        address: String,
+    },
+}
+
+/// Per-wallet defaults applied by the send commands
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct WalletSettings {
+    #[serde(default)]
+    pub label: Option<String>,
+    // Token UID used when a send doesn't name one (HTR when unset)
+    #[serde(default)]
+    pub default_token: Option<String>,
+    #[serde(default)]
+    pub change_address: ChangeAddressStrategy,
+}
+
+/// Registry entry as exposed to the frontend (never includes the seed)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RegisteredWalletInfo {
+    pub wallet_id: String,
+    pub auto_start: bool,
+    pub created_at: u64,
+    pub instance: String,
+    pub multisig_config: Option<String>,
+    pub settings: WalletSettings,
+}
+
+/// Outcome of re-opening a registered wallet
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WalletRestoreResult {
+    pub wallet_id: String,
+    pub ready: bool,
+    pub error: Option<String>,
+}
+
+fn registry_path() -> PathBuf {
+    WORKSPACE
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone()
+        .unwrap_or_else(crate::get_default_data_dir)
+        .join(REGISTRY_FILE)
+}
+
+/// Use the registry of the workspace in `data_dir` from now on
+pub fn set_workspace(data_dir: &Path) {
+    *WORKSPACE.write().unwrap_or_else(|e| e.into_inner()) = Some(data_dir.to_path_buf());
+}
+
+fn load_registry() -> Vec<RegisteredWallet> {
+    fs::read_to_string(registry_path())
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn save_registry(wallets: &[RegisteredWallet]) -> Result<(), String> {
+    let path = registry_path();
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create data directory: {}", e))?;
+    }
+    let content = serde_json::to_string_pretty(wallets)
+        .map_err(|e| format!("Failed to serialize wallet registry: {}", e))?;
+    fs::write(path, content).map_err(|e| format!("Failed to write wallet registry: {}", e))
+}
+
+/// Stop re-opening every registered wallet (their seeds are gone); the
+/// entries and their settings stay
+pub fn disable_all_auto_start() -> Result<(), String> {
+    let _guard = REGISTRY_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+
+    let mut wallets = load_registry();
+    if wallets.iter().all(|w| !w.auto_start) {
+        return Ok(());
+    }
+    for wallet in wallets.iter_mut() {
+        wallet.auto_start = false;
+    }
+
+    save_registry(&wallets)
+}
+
+/// Add (or replace) a wallet in the registry, storing its seed in the
+/// keychain when the user has opted in; only then is auto-start enabled.
+/// Returns whether the seed was stored.
+pub async fn register_wallet(
+    wallet_id: &str,
+    seed: &str,
+    instance: &str,
+    multisig_config: Option<&str>,
+) -> Result<bool, String> {
+    let seed_stored = crate::secrets::store_wallet_seed(wallet_id, seed).await?;
+
+    let _guard = REGISTRY_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut wallets = load_registry();
+    // Re-registering (e.g. after an import) keeps the wallet's settings
+    let settings = wallets
+        .iter()
+        .find(|w| w.wallet_id == wallet_id)
+        .map(|w| w.settings.clone())
+        .unwrap_or_default();
+    wallets.retain(|w| w.wallet_id != wallet_id);
+    wallets.push(RegisteredWallet {
+        wallet_id: wallet_id.to_string(),
+        auto_start: seed_stored,
+        created_at: crate::unix_now(),
+        instance: instance.to_string(),
+        multisig_config: multisig_config.map(|name| name.to_string()),
+        settings,
+    });
+
+    save_registry(&wallets)?;
+    Ok(seed_stored)
+}
+
+/// Settings of a wallet (defaults for wallets that aren't registered)
+pub fn wallet_settings(wallet_id: &str) -> WalletSettings {
+    let _guard = REGISTRY_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    load_registry()
+        .into_iter()
+        .find(|w| w.wallet_id == wallet_id)
+        .map(|w| w.settings)
+        .unwrap_or_default()
+}
+
+/// Replace a registered wallet's settings
+pub fn set_wallet_settings_internal(
+    wallet_id: &str,
+    settings: WalletSettings,
+) -> Result<(), String> {
+    if let ChangeAddressStrategy::Fixed { address } = &settings.change_address {
+        crate::address::check_address(address)?;
+    }
+
+    let _guard = REGISTRY_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    let mut wallets = load_registry();
+    let wallet = wallets
+        .iter_mut()
+        .find(|w| w.wallet_id == wallet_id)
+        .ok_or_else(|| format!("Wallet '{}' is not registered", wallet_id))?;
+    wallet.settings = settings;
+
+    save_registry(&wallets)
+}
+
+/// Fill a `/wallet/simple-send-tx` or `/wallet/send-tx` body with the wallet's
+/// default token and change address where the request left them out
+pub async fn apply_send_defaults(
+    body: &mut serde_json::Value,
+    base_url: &str,
+    wallet_id: &str,
+) -> Result<(), String> {
+    let settings = wallet_settings(wallet_id);
+
+    if let Some(token) = &settings.default_token {
+        if let Some(outputs) = body["outputs"].as_array_mut() {
+            for output in outputs.iter_mut().filter(|o| o.get("token").is_none()) {
+                output["token"] = token.clone().into();
+            }
+        } else if body.get("token").is_none() {
+            body["token"] = token.clone().into();
+        }
+    }
+
+    if body.get("change_address").is_none() {
+        if let Some(address) = change_address(&settings, base_url, wallet_id).await? {
+            body["change_address"] = address.into();
+        }
+    }
+    Ok(())
+}
+
+/// Change address for a send from the wallet's strategy (None lets the wallet pick)
+pub async fn change_address(
+    settings: &WalletSettings,
+    base_url: &str,
+    wallet_id: &str,
+) -> Result<Option<String>, String> {
+    match &settings.change_address {
+        ChangeAddressStrategy::Default => Ok(None),
+        ChangeAddressStrategy::Fixed { address } => Ok(Some(address.clone())),
+        ChangeAddressStrategy::FirstAddress => {
+            let result: serde_json::Value = reqwest::Client::new()
+                .get(format!("{}/wallet/address", base_url))
+                .header("X-Wallet-Id", wallet_id)
+                .query(&[("index", "0")])
+                .send()
+                .await
+                .map_err(|e| format!("Failed to get first address: {}", e))?
+                .json()
+                .await
+                .map_err(|e| format!("Failed to parse response: {}", e))?;
+            result["address"]
+                .as_str()
+                .map(|address| Some(address.to_string()))
+                .ok_or_else(|| "Failed to get the wallet's first address".to_string())
+        }
+    }
+}
+
+/// Get a wallet's seed from the keychain (None without the user's consent)
+pub async fn wallet_seed(wallet_id: &str) -> Result<Option<String>, String> {
+    crate::secrets::load_wallet_seed(wallet_id).await
+}
+
+/// Toggle whether a registered wallet is re-opened when wallet-headless starts;
+/// enabling it requires the seed in the keychain
+pub async fn set_auto_start(wallet_id: &str, auto_start: bool) -> Result<(), String> {
+    if auto_start && crate::secrets::load_wallet_seed(wallet_id).await?.is_none() {
+        return Err(format!(
+            "Wallet '{}' can't auto-start: its seed isn't in the keychain",
+            wallet_id
+        ));
+    }
+
+    let _guard = REGISTRY_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+
+    let mut wallets = load_registry();
+    let wallet = wallets
+        .iter_mut()
+        .find(|w| w.wallet_id == wallet_id)
+        .ok_or_else(|| format!("Wallet '{}' is not registered", wallet_id))?;
+    wallet.auto_start = auto_start;
+
+    save_registry(&wallets)
+}
+
+/// Remove a wallet from the registry
+pub fn unregister_wallet_internal(wallet_id: &str) -> Result<bool, String> {
+    let _guard = REGISTRY_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+
+    let mut wallets = load_registry();
+    let before = wallets.len();
+    wallets.retain(|w| w.wallet_id != wallet_id);
+    if wallets.len() == before {
+        return Ok(false);
+    }
+
+    save_registry(&wallets)?;
+    Ok(true)
+}
+
+/// List registered wallets (without seeds)
+pub fn list_wallets() -> Vec<RegisteredWalletInfo> {
+    let _guard = REGISTRY_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+
+    load_registry()
+        .into_iter()
+        .map(|w| RegisteredWalletInfo {
+            wallet_id: w.wallet_id,
+            auto_start: w.auto_start,
+            created_at: w.created_at,
+            instance: w.instance,
+            multisig_config: w.multisig_config,
+            settings: w.settings,
+        })
+        .collect()
+}
+
+/// Whether any registered wallet should be re-opened when `instance` starts
+pub fn has_auto_start_wallets(instance: &str) -> bool {
+    let _guard = REGISTRY_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+    load_registry()
+        .iter()
+        .any(|w| w.auto_start && w.instance == instance)
+}
+
+/// Wait until wallet-headless answers HTTP requests
+pub async fn wait_for_headless(client: &reqwest::Client, base_url: &str) -> bool {
+    for _ in 0..60 {
+        if client
+            .get(format!("{}/wallet/status", base_url))
+            .send()
+            .await
+            .is_ok()
+        {
+            return true;
+        }
+        tokio::time::sleep(Duration::from_millis(500)).await;
+    }
+    false
+}
+
+/// Poll a wallet's status until it reports Ready (or we give up)
+pub async fn wait_for_wallet_ready(
+    client: &reqwest::Client,
+    base_url: &str,
+    wallet_id: &str,
+) -> Result<(), String> {
+    for _ in 0..120 {
+        if let Ok(resp) = client
+            .get(format!("{}/wallet/status", base_url))
+            .header("X-Wallet-Id", wallet_id)
+            .send()
+            .await
+        {
+            if let Ok(status) = resp.json::<serde_json::Value>().await {
+                if status["statusCode"].as_i64() == Some(WALLET_READY_STATUS) {
+                    return Ok(());
+                }
+            }
+        }
+        tokio::time::sleep(Duration::from_secs(1)).await;
+    }
+    Err("Timed out waiting for wallet to sync".to_string())
+}
+
+async fn restore_wallet(
+    client: &reqwest::Client,
+    base_url: &str,
+    wallet: &RegisteredWallet,
+) -> Result<(), String> {
+    let seed = wallet_seed(&wallet.wallet_id).await?.ok_or_else(|| {
+        "Seed not in the keychain; enable keychain storage and import the wallet again".to_string()
+    })?;
+
+    let mut body = serde_json::json!({
+        "wallet-id": wallet.wallet_id,
+        "seed": seed,
+    });
+    if let Some(config_name) = &wallet.multisig_config {
+        body["multisig"] = true.into();
+        body["multisigKey"] = config_name.clone().into();
+    }
+
+    let url = format!("{}/start", base_url);
+    let response = client
+        .post(&url)
+        .json(&body)
+        .send()
+        .instrument(crate::telemetry::http_span("POST", &url))
+        .await
+        .map_err(|e| format!("Failed to start wallet: {}", e))?;
+
+    let result: serde_json::Value = response
+        .json()
+        .await
+        .map_err(|e| format!("Failed to parse response: {}", e))?;
+
+    // headless refuses to start a wallet id twice; that's fine for a restore
+    if !result["success"].as_bool().unwrap_or(false)
+        && result["errorCode"] != "WALLET_ALREADY_STARTED"
+    {
+        let message = result["message"].as_str().unwrap_or("Unknown error");
+        return Err(format!("Failed to start wallet: {}", message));
+    }
+
+    wait_for_wallet_ready(client, base_url, &wallet.wallet_id).await
+}
+
+/// Check a mnemonic's words and BIP39 checksum, returning it normalized
+/// (lowercase, single spaces)
+pub fn validate_mnemonic(seed: &str) -> Result<String, String> {
+    use bip39::{Language, Mnemonic};
+
+    let words: Vec<String> = seed.split_whitespace().map(|w| w.to_lowercase()).collect();
+    if words.len() != SEED_WORD_COUNT {
+        return Err(format!(
+            "Seed has {} words; Hathor wallets need exactly {}",
+            words.len(),
+            SEED_WORD_COUNT
+        ));
+    }
+    if let Some((i, word)) = words
+        .iter()
+        .enumerate()
+        .find(|(_, word)| Language::English.find_word(word).is_none())
+    {
+        return Err(format!(
+            "Word {} ('{}') is not in the BIP39 English word list",
+            i + 1,
+            word
+        ));
+    }
+
+    let normalized = words.join(" ");
+    Mnemonic::parse_in_normalized(Language::English, &normalized).map_err(|e| match e {
+        bip39::Error::InvalidChecksum => {
+            "Seed checksum is invalid; check the word order and for typos".to_string()
+        }
+        e => format!("Invalid seed: {}", e),
+    })?;
+    Ok(normalized)
+}
+
+/// Read a seed from a mnemonic string or a path to a file containing one
+pub fn read_seed_source(seed_source: &str) -> Result<String, String> {
+    let source = seed_source.trim();
+    let path = match source.strip_prefix("~/") {
+        Some(rest) => dirs::home_dir().unwrap_or_default().join(rest),
+        None => std::path::PathBuf::from(source),
+    };
+
+    let seed = if path.is_file() {
+        fs::read_to_string(&path)
+            .map_err(|e| format!("Failed to read seed file {:?}: {}", path, e))?
+    } else if source.split_whitespace().count() == 1 {
+        // A single "word" is a path that doesn't exist rather than a mnemonic
+        return Err(format!("Seed file {:?} not found", path));
+    } else {
+        source.to_string()
+    };
+
+    validate_mnemonic(&seed)
+}
+
+/// Re-open every auto-start wallet registered to `instance` (listening on
+/// `port`) and wait for them to sync
+#[tracing::instrument(skip(port))]
+pub async fn restore_wallets(instance: &str, port: u16) -> Vec<WalletRestoreResult> {
+    let wallets: Vec<RegisteredWallet> = {
+        let _guard = REGISTRY_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+        load_registry()
+            .into_iter()
+            .filter(|w| w.auto_start && w.instance == instance)
+            .collect()
+    };
+
+    if wallets.is_empty() {
+        return Vec::new();
+    }
+
+    let client = reqwest::Client::new();
+    let base_url = format!("http://localhost:{}", port);
+
+    if !wait_for_headless(&client, &base_url).await {
+        return wallets
+            .into_iter()
+            .map(|w| WalletRestoreResult {
+                wallet_id: w.wallet_id,
+                ready: false,
+                error: Some("Wallet-headless did not become available".to_string()),
+            })
+            .collect();
+    }
+
+    let restores = wallets.iter().map(|wallet| {
+        let client = &client;
+        let base_url = &base_url;
+        async move {
+            let result = restore_wallet(client, base_url, wallet).await;
+            WalletRestoreResult {
+                wallet_id: wallet.wallet_id.clone(),
+                ready: result.is_ok(),
+                error: result.err(),
+            }
+        }
+    });
+
+    futures_util::future::join_all(restores).await
+}
+
+// ============================================================================
+// Tauri Commands
+// ============================================================================
+
+// Import a wallet from a mnemonic or a seed file, validating the seed first
+#[tauri::command]
+pub async fn import_wallet(
+    app: tauri::AppHandle,
+    state: tauri::State<'_, crate::SharedState>,
+    wallet_id: String,
+    seed_source: String,
+    instance: Option<String>,
+) -> Result<crate::HeadlessWallet, String> {
+    let seed = read_seed_source(&seed_source)?;
+    crate::create_headless_wallet(
+        app,
+        state,
+        crate::CreateHeadlessWalletRequest {
+            wallet_id,
+            seed,
+            instance,
+        },
+    )
+    .await
+}
+
+// Get a wallet's settings (label, default token, change address strategy)
+#[tauri::command]
+pub async fn get_wallet_settings(wallet_id: String) -> Result<WalletSettings, String> {
+    Ok(wallet_settings(&wallet_id))
+}
+
+// Save a registered wallet's settings
+#[tauri::command]
+pub async fn set_wallet_settings(
+    wallet_id: String,
+    settings: WalletSettings,
+) -> Result<String, String> {
+    set_wallet_settings_internal(&wallet_id, settings)?;
+    Ok(format!("Settings saved for wallet '{}'", wallet_id))
+}
+
+// List wallets saved in the registry
+#[tauri::command]
+pub async fn list_registered_wallets() -> Result<Vec<RegisteredWalletInfo>, String> {
+    Ok(list_wallets())
+}
+
+// Enable or disable automatic re-opening of a registered wallet
+#[tauri::command]
+pub async fn set_wallet_auto_start(wallet_id: String, auto_start: bool) -> Result<String, String> {
+    set_auto_start(&wallet_id, auto_start).await?;
+    Ok(format!(
+        "Auto-start {} for wallet '{}'",
+        if auto_start { "enabled" } else { "disabled" },
+        wallet_id
+    ))
+}
+
+// Remove a wallet from the registry (it won't be restored anymore)
+#[tauri::command]
+pub async fn unregister_wallet(wallet_id: String) -> Result<String, String> {
+    if unregister_wallet_internal(&wallet_id)? {
+        Ok(format!("Wallet '{}' removed from registry", wallet_id))
+    } else {
+        Err(format!("Wallet '{}' is not registered", wallet_id))
+    }
+}
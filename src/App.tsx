@@ -22,6 +22,7 @@ import {
   Send,
   Check,
 } from "lucide-react";
+import { formatHashRate, formatHTR, truncateHash } from "@/lib/utils";
 
 type NodeStatusType = "stopped" | "starting" | "running" | "error";
 type MinerStatusType = "stopped" | "starting" | "mining" | "error";
@@ -32,6 +33,102 @@ interface NodeStatus {
   block_height: number | null;
   hash_rate: number | null;
   peer_count: number | null;
+  version: string | null;
+  network: string | null;
+  uptime_secs: number | null;
+  sync_state: string | null;
+  best_block_hash: string | null;
+  best_block_timestamp: number | null;
+}
+
+interface MinerMetrics {
+  miner: string;
+  hash_rate: number | null;
+  average_hash_rate: number | null;
+  accepted_shares: number;
+  rejected_shares: number;
+  threads: number;
+}
+
+interface BlockFound {
+  miner: string;
+  address: string;
+  height: number;
+  hash: string;
+  reward: number;
+}
+
+interface StratumConnectionInfo {
+  lan_enabled: boolean;
+  relay_running: boolean;
+  local_url: string;
+  lan_url: string | null;
+  lan_ip: string | null;
+  warning: string | null;
+}
+
+interface LanShareInfo {
+  enabled: boolean;
+  explorer_running: boolean;
+  shared: boolean;
+  restart_required: boolean;
+  local_url: string;
+  lan_url: string | null;
+  lan_node_url: string | null;
+  lan_headless_url: string | null;
+  lan_ip: string | null;
+  warning: string | null;
+}
+
+interface TlsInfo {
+  enabled: boolean;
+  explorer_tls: boolean;
+  restart_required: boolean;
+  cert_path: string | null;
+  fingerprint_sha256: string | null;
+  subject_alt_names: string[];
+  expires_at: number | null;
+}
+
+interface ControlApiInfo {
+  enabled: boolean;
+  running: boolean;
+  port: number;
+  url: string | null;
+  token: string;
+  token_path: string;
+}
+
+interface RateLimitSettings {
+  enabled: boolean;
+  requests_per_second: number;
+  burst: number;
+  max_body_bytes: number;
+}
+
+interface LogSettings {
+  enabled: boolean;
+  max_file_bytes: number;
+  max_files: number;
+  max_age_days: number | null;
+}
+
+interface CorsSettings {
+  permissive: boolean;
+  allowed_origins: string[];
+}
+
+interface TelemetryStatus {
+  settings: { enabled: boolean; endpoint: string };
+  active: boolean;
+  restart_required: boolean;
+}
+
+interface PublicFaucetSettings {
+  enabled: boolean;
+  max_amount: number;
+  cooldown_secs: number;
+  requests_per_minute: number;
 }
 
 type LogSource = "node" | "miner" | "headless";
@@ -44,10 +141,19 @@ interface LogEntry {
   message: string;
 }
 
+// A line kept by the backend, as returned by get_logs
+interface LogLine {
+  timestamp: number; // Unix time in milliseconds
+  service: string;
+  message: string;
+}
+
 interface WalletAddress {
   address: string;
   index: number;
   balance: number | null;
+  used: boolean;
+  current: boolean;
 }
 
 interface HeadlessWallet {
@@ -84,9 +190,12 @@ function App() {
   const [hashRate, setHashRate] = useState("0 H/s");
   const [logs, setLogs] = useState<LogEntry[]>([]);
   const [error, setError] = useState<string | null>(null);
+  // The explorer's WebSocket proxy lost the node and is reconnecting
+  const [explorerReconnecting, setExplorerReconnecting] = useState(false);
   const [logFilters, setLogFilters] = useState<Set<LogSource>>(new Set(["node", "miner", "headless"]));
   const logsEndRef = useRef<HTMLDivElement>(null);
   const logIdRef = useRef(0);
+  const minerRatesRef = useRef<Record<string, number>>({});
 
   const toggleLogFilter = (source: LogSource) => {
     setLogFilters((prev) => {
@@ -152,6 +261,38 @@ function App() {
     return () => clearInterval(interval);
   }, [nodeStatus]);
 
+  // Fill the log view with output emitted before this page loaded
+  useEffect(() => {
+    const mountedAt = Date.now();
+    const sources: LogSource[] = ["node", "miner", "headless"];
+    Promise.all(
+      sources.map((source) =>
+        invoke<LogLine[]>("get_logs", { service: source, lines: 1000 }).catch(() => [] as LogLine[])
+      )
+    ).then((results) => {
+      const history = results
+        .flat()
+        // Later lines arrive through the listeners below
+        .filter((line) => line.timestamp < mountedAt)
+        .sort((a, b) => a.timestamp - b.timestamp)
+        .map((line): LogEntry | null => {
+          const message = stripAnsi(line.message);
+          if (!message.trim()) return null;
+          return {
+            id: logIdRef.current++,
+            timestamp: new Date(line.timestamp),
+            source: line.service as LogSource,
+            level: parseLogLevel(message),
+            message,
+          };
+        })
+        .filter((entry): entry is LogEntry => entry !== null);
+      if (history.length > 0) {
+        setLogs((prev) => [...history, ...prev].slice(-1000));
+      }
+    });
+  }, []);
+
   // Listen for events from the backend
   useEffect(() => {
     const unlistenLog = listen<string>("node-log", (event) => {
@@ -175,18 +316,34 @@ function App() {
     });
 
     const unlistenMinerStats = listen<string>("miner-stats", (event) => {
-      // Match formats like "1423 khash/s" or "1.5 MH/s"
-      const match = event.payload.match(/(\d+\.?\d*)\s*(k|M|G|T)?hash\/s/i);
-      if (match) {
-        const value = match[1];
-        const unit = match[2] ? match[2].toUpperCase() + "H/s" : "H/s";
-        setHashRate(`${value} ${unit}`);
-      }
       addLog("miner", event.payload);
     });
 
+    const unlistenMinerMetrics = listen<MinerMetrics>("miner-metrics", (event) => {
+      if (event.payload.hash_rate !== null) {
+        // Show the combined rate of all running miners
+        minerRatesRef.current[event.payload.miner] = event.payload.hash_rate;
+        const total = Object.values(minerRatesRef.current).reduce((sum, rate) => sum + rate, 0);
+        setHashRate(formatHashRate(total));
+      }
+    });
+
+    // The backend started the miner by itself (auto-start setting)
+    const unlistenMinerAutoStarted = listen<string>("miner-auto-started", () => {
+      setMinerStatus("mining");
+    });
+
+    const unlistenBlockFound = listen<BlockFound>("block-found", (event) => {
+      const block = event.payload;
+      addLog(
+        "miner",
+        `Block ${block.height} found (${truncateHash(block.hash)}): ${formatHTR(block.reward)} HTR to ${block.address}`
+      );
+    });
+
     const unlistenMinerTerminated = listen<number | null>("miner-terminated", () => {
       setMinerStatus("stopped");
+      minerRatesRef.current = {};
       setHashRate("0 H/s");
     });
 
@@ -199,15 +356,45 @@ function App() {
       setHeadlessWallets([]);
     });
 
+    // Backend watches the node and reports balance changes of registered wallets
+    const unlistenBalanceChanged = listen<{ wallet_id: string; token: string }>(
+      "wallet-balance-changed",
+      (event) => {
+        if (event.payload.token === "00") {
+          loadWalletDetails(event.payload.wallet_id);
+        }
+      }
+    );
+
+    const unlistenWsReconnecting = listen<number>("proxy-ws-reconnecting", () => {
+      setExplorerReconnecting(true);
+    });
+
+    const unlistenWsReconnected = listen<number>("proxy-ws-reconnected", () => {
+      setExplorerReconnecting(false);
+    });
+
+    const unlistenWsLost = listen<number>("proxy-ws-lost", () => {
+      setExplorerReconnecting(false);
+      addLog("node", "Explorer lost its connection to the node; reload the explorer to retry");
+    });
+
     return () => {
       unlistenLog.then((f) => f());
       unlistenError.then((f) => f());
       unlistenTerminated.then((f) => f());
       unlistenMinerLog.then((f) => f());
       unlistenMinerStats.then((f) => f());
+      unlistenMinerMetrics.then((f) => f());
+      unlistenBlockFound.then((f) => f());
+      unlistenMinerAutoStarted.then((f) => f());
       unlistenMinerTerminated.then((f) => f());
       unlistenHeadlessLog.then((f) => f());
       unlistenHeadlessTerminated.then((f) => f());
+      unlistenBalanceChanged.then((f) => f());
+      unlistenWsReconnecting.then((f) => f());
+      unlistenWsReconnected.then((f) => f());
+      unlistenWsLost.then((f) => f());
     };
   }, []);
 
@@ -220,6 +407,7 @@ function App() {
       // Auto-start explorer server
       try {
         await invoke("start_explorer_server");
+        setLanShareInfo(await invoke<LanShareInfo>("get_lan_share_info"));
       } catch (e) {
         console.warn("Explorer server failed to start:", e);
       }
@@ -545,8 +733,14 @@ function App() {
 
     return (
       <div className="h-full flex flex-col -m-6">
+        {explorerReconnecting && (
+          <div className="flex items-center gap-2 px-4 py-2 bg-amber-500/10 border-b border-amber-500/20 text-amber-400 text-sm">
+            <Loader2 className="w-4 h-4 animate-spin" />
+            Reconnecting to the node…
+          </div>
+        )}
         <iframe
-          src="http://localhost:3001"
+          src={lanShareInfo?.local_url ?? "http://localhost:3001"}
           className="w-full flex-1 border-0"
           title="Hathor Explorer"
         />
@@ -593,6 +787,262 @@ function App() {
         <p className="text-slate-500">Configure your local development environment</p>
       </div>
 
+      {/* Network */}
+      <div className="border border-slate-800 rounded-xl bg-slate-900/30 p-6 space-y-3">
+        <h3 className="text-lg font-semibold text-white">Network</h3>
+        <label className="flex items-center gap-2 text-sm text-slate-300">
+          <input
+            type="checkbox"
+            checked={stratumInfo?.lan_enabled ?? false}
+            onChange={(e) => toggleStratumLan(e.target.checked)}
+            className="accent-amber-500"
+          />
+          Expose stratum to the LAN for external miners
+        </label>
+        {stratumInfo?.warning && (
+          <div className="flex items-start gap-2 p-3 rounded-lg text-sm bg-amber-500/10 text-amber-400 border border-amber-500/30">
+            <AlertTriangle className="w-4 h-4 mt-0.5 shrink-0" />
+            {stratumInfo.warning}
+          </div>
+        )}
+        {stratumInfo?.lan_url && (
+          <p className="text-sm text-slate-400">
+            LAN URL: <span className="font-mono text-white">{stratumInfo.lan_url}</span>
+            {!stratumInfo.relay_running && " (applied when the node starts)"}
+          </p>
+        )}
+        <label className="flex items-center gap-2 text-sm text-slate-300">
+          <input
+            type="checkbox"
+            checked={lanShareInfo?.enabled ?? false}
+            onChange={(e) => toggleLanShare(e.target.checked)}
+            className="accent-amber-500"
+          />
+          Share the explorer and node API on the LAN
+        </label>
+        {lanShareInfo?.warning && (
+          <div className="flex items-start gap-2 p-3 rounded-lg text-sm bg-amber-500/10 text-amber-400 border border-amber-500/30">
+            <AlertTriangle className="w-4 h-4 mt-0.5 shrink-0" />
+            {lanShareInfo.warning}
+          </div>
+        )}
+        {lanShareInfo?.lan_url && (
+          <p className="text-sm text-slate-400">
+            Explorer: <span className="font-mono text-white">{lanShareInfo.lan_url}</span>, node API:{" "}
+            <span className="font-mono text-white">{lanShareInfo.lan_node_url}</span>, wallet-headless:{" "}
+            <span className="font-mono text-white">{lanShareInfo.lan_headless_url}</span>
+          </p>
+        )}
+        {lanShareInfo?.restart_required && (
+          <p className="text-sm text-slate-400">Restart the network to apply LAN sharing to the explorer</p>
+        )}
+        <label className="flex items-center gap-2 text-sm text-slate-300">
+          <input
+            type="checkbox"
+            checked={tlsInfo?.enabled ?? false}
+            onChange={(e) => toggleTls(e.target.checked)}
+            className="accent-amber-500"
+          />
+          Serve the explorer and MCP server over HTTPS (self-signed certificate)
+        </label>
+        {tlsInfo?.fingerprint_sha256 && (
+          <div className="space-y-2">
+            <p className="text-sm text-slate-400">
+              Certificate for {tlsInfo.subject_alt_names.join(", ")}, SHA-256:{" "}
+              <span className="font-mono text-xs text-white break-all">{tlsInfo.fingerprint_sha256}</span>
+            </p>
+            <button
+              onClick={exportTlsCert}
+              className="px-4 py-2 rounded-lg bg-slate-800 border border-slate-700 text-slate-300 text-sm font-medium hover:bg-slate-700 transition-colors"
+            >
+              Export certificate
+            </button>
+            {tlsExportPath && (
+              <p className="text-sm text-slate-400">
+                Saved to <span className="font-mono text-white">{tlsExportPath}</span>; install and trust it on
+                test devices
+              </p>
+            )}
+          </div>
+        )}
+        {tlsInfo?.restart_required && (
+          <p className="text-sm text-slate-400">
+            Restart the network to apply HTTPS to the explorer (MCP applies it on the next app start)
+          </p>
+        )}
+        <label className="flex items-center gap-2 text-sm text-slate-300">
+          <input
+            type="checkbox"
+            checked={graphqlEnabled}
+            onChange={(e) => toggleGraphql(e.target.checked)}
+            className="accent-amber-500"
+          />
+          GraphQL endpoint at /graphql on the explorer server (applied when the explorer starts)
+        </label>
+        <label className="flex items-center gap-2 text-sm text-slate-300">
+          <input
+            type="checkbox"
+            checked={rateLimits?.enabled ?? false}
+            onChange={(e) => rateLimits && updateRateLimits({ ...rateLimits, enabled: e.target.checked })}
+            className="accent-amber-500"
+          />
+          Rate-limit the node proxy, faucet and control API per client IP
+        </label>
+        {rateLimits?.enabled && (
+          <div className="flex items-center gap-2 text-sm text-slate-300">
+            <input
+              type="number"
+              min="1"
+              defaultValue={rateLimits.requests_per_second}
+              onBlur={(e) => updateRateLimits({ ...rateLimits, requests_per_second: Number(e.target.value) })}
+              className="w-20 px-2 py-1 rounded bg-slate-800 border border-slate-700 text-white"
+            />
+            requests/s, bursts of
+            <input
+              type="number"
+              min="1"
+              defaultValue={rateLimits.burst}
+              onBlur={(e) => updateRateLimits({ ...rateLimits, burst: Number(e.target.value) })}
+              className="w-20 px-2 py-1 rounded bg-slate-800 border border-slate-700 text-white"
+            />
+            , bodies up to
+            <input
+              type="number"
+              min="1"
+              defaultValue={Math.round(rateLimits.max_body_bytes / 1024)}
+              onBlur={(e) => updateRateLimits({ ...rateLimits, max_body_bytes: Number(e.target.value) * 1024 })}
+              className="w-24 px-2 py-1 rounded bg-slate-800 border border-slate-700 text-white"
+            />
+            KiB
+          </div>
+        )}
+        <label className="flex items-center gap-2 text-sm text-slate-300">
+          <input
+            type="checkbox"
+            checked={logSettings?.enabled ?? false}
+            onChange={(e) => logSettings && updateLogSettings({ ...logSettings, enabled: e.target.checked })}
+            className="accent-amber-500"
+          />
+          Write service logs to files in the workspace's logs/ directory
+        </label>
+        {logSettings?.enabled && (
+          <div className="flex items-center gap-2 text-sm text-slate-300">
+            Rotate at
+            <input
+              type="number"
+              min="1"
+              defaultValue={Math.round(logSettings.max_file_bytes / (1024 * 1024))}
+              onBlur={(e) => updateLogSettings({ ...logSettings, max_file_bytes: Number(e.target.value) * 1024 * 1024 })}
+              className="w-20 px-2 py-1 rounded bg-slate-800 border border-slate-700 text-white"
+            />
+            MiB, keep
+            <input
+              type="number"
+              min="0"
+              defaultValue={logSettings.max_files}
+              onBlur={(e) => updateLogSettings({ ...logSettings, max_files: Number(e.target.value) })}
+              className="w-20 px-2 py-1 rounded bg-slate-800 border border-slate-700 text-white"
+            />
+            rotated files for up to
+            <input
+              type="number"
+              min="1"
+              placeholder="any"
+              defaultValue={logSettings.max_age_days ?? ""}
+              onBlur={(e) =>
+                updateLogSettings({ ...logSettings, max_age_days: e.target.value ? Number(e.target.value) : null })
+              }
+              className="w-20 px-2 py-1 rounded bg-slate-800 border border-slate-700 text-white"
+            />
+            days
+          </div>
+        )}
+        <label className="flex items-center gap-2 text-sm text-slate-300">
+          <input
+            type="checkbox"
+            checked={corsSettings?.permissive ?? false}
+            onChange={(e) => updateCors(e.target.checked, corsOrigins)}
+            className="accent-amber-500"
+          />
+          Allow cross-origin requests from any origin (dev only; by default only localhost origins)
+        </label>
+        {!corsSettings?.permissive && (
+          <label className="flex items-center gap-2 text-sm text-slate-300">
+            Also allow
+            <input
+              type="text"
+              value={corsOrigins}
+              placeholder="https://app.example.com, http://192.168.1.20:5173"
+              onChange={(e) => setCorsOrigins(e.target.value)}
+              onBlur={() => updateCors(false, corsOrigins)}
+              className="flex-1 px-2 py-1 rounded bg-slate-800 border border-slate-700 font-mono text-xs text-white"
+            />
+          </label>
+        )}
+        <label className="flex items-center gap-2 text-sm text-slate-300">
+          <input
+            type="checkbox"
+            checked={telemetry?.settings.enabled ?? false}
+            onChange={(e) => updateTelemetry(e.target.checked, telemetryEndpoint)}
+            className="accent-amber-500"
+          />
+          Export traces over OTLP/HTTP to
+          <input
+            type="text"
+            value={telemetryEndpoint}
+            onChange={(e) => setTelemetryEndpoint(e.target.value)}
+            onBlur={() => updateTelemetry(telemetry?.settings.enabled ?? false, telemetryEndpoint)}
+            className="flex-1 px-2 py-1 rounded bg-slate-800 border border-slate-700 font-mono text-xs text-white"
+          />
+        </label>
+        {telemetry?.restart_required && (
+          <p className="text-sm text-slate-400">Restart Forge to apply the trace export setting</p>
+        )}
+        <label className="flex items-center gap-2 text-sm text-slate-300">
+          <input
+            type="checkbox"
+            checked={controlApiInfo?.enabled ?? false}
+            onChange={(e) => toggleControlApi(e.target.checked)}
+            className="accent-amber-500"
+          />
+          HTTP control API for scripts and CI (port {controlApiInfo?.port ?? 9877})
+        </label>
+        {controlApiInfo?.url && (
+          <div className="space-y-2">
+            <p className="text-sm text-slate-400">
+              Listening on <span className="font-mono text-white">{controlApiInfo.url}/v1</span>; send{" "}
+              <span className="font-mono text-white">Authorization: Bearer</span> with the token in{" "}
+              <span className="font-mono text-xs text-white break-all">{controlApiInfo.token_path}</span>. API
+              reference: <span className="font-mono text-white">{controlApiInfo.url}/docs</span>
+            </p>
+            <button
+              onClick={regenerateControlApiToken}
+              className="px-4 py-2 rounded-lg bg-slate-800 border border-slate-700 text-slate-300 text-sm font-medium hover:bg-slate-700 transition-colors"
+            >
+              Regenerate token
+            </button>
+          </div>
+        )}
+        <label className="flex items-center gap-2 text-sm text-slate-300">
+          <input
+            type="checkbox"
+            checked={blockNotifications}
+            onChange={(e) => toggleBlockNotifications(e.target.checked)}
+            className="accent-amber-500"
+          />
+          Desktop notification when a miner finds a block
+        </label>
+        <label className="flex items-center gap-2 text-sm text-slate-300">
+          <input
+            type="checkbox"
+            checked={autoStartMiner}
+            onChange={(e) => toggleAutoStartMiner(e.target.checked)}
+            className="accent-amber-500"
+          />
+          Start mining automatically when the node is ready
+        </label>
+      </div>
+
       {/* Danger Zone */}
       <div className="border border-red-500/30 rounded-xl bg-red-500/5 p-6">
         <div className="flex items-center gap-3 mb-4">
@@ -683,6 +1133,253 @@ function App() {
   const [faucetBalance, setFaucetBalance] = useState<{ available: number; locked: number } | null>(null);
   const [sendingTx, setSendingTx] = useState(false);
   const [txResult, setTxResult] = useState<{ type: "success" | "error"; message: string } | null>(null);
+  const [rotateFaucetAddresses, setRotateFaucetAddresses] = useState(false);
+  const [publicFaucet, setPublicFaucet] = useState<PublicFaucetSettings | null>(null);
+  const [stratumInfo, setStratumInfo] = useState<StratumConnectionInfo | null>(null);
+
+  useEffect(() => {
+    invoke<StratumConnectionInfo>("get_stratum_connection_info")
+      .then(setStratumInfo)
+      .catch((e) => console.error("Failed to load stratum info:", e));
+  }, []);
+
+  const toggleStratumLan = async (enabled: boolean) => {
+    try {
+      setStratumInfo(await invoke<StratumConnectionInfo>("set_stratum_lan", { enabled }));
+    } catch (e) {
+      setError(String(e));
+    }
+  };
+
+  const [lanShareInfo, setLanShareInfo] = useState<LanShareInfo | null>(null);
+
+  useEffect(() => {
+    invoke<LanShareInfo>("get_lan_share_info")
+      .then(setLanShareInfo)
+      .catch((e) => console.error("Failed to load LAN share info:", e));
+  }, []);
+
+  const toggleLanShare = async (enabled: boolean) => {
+    try {
+      setLanShareInfo(await invoke<LanShareInfo>("set_lan_share", { enabled }));
+    } catch (e) {
+      setError(String(e));
+    }
+  };
+
+  const [tlsInfo, setTlsInfo] = useState<TlsInfo | null>(null);
+  const [tlsExportPath, setTlsExportPath] = useState<string | null>(null);
+
+  useEffect(() => {
+    invoke<TlsInfo>("get_tls_info")
+      .then(setTlsInfo)
+      .catch((e) => console.error("Failed to load TLS info:", e));
+  }, []);
+
+  const toggleTls = async (enabled: boolean) => {
+    try {
+      setTlsInfo(await invoke<TlsInfo>("set_tls", { enabled }));
+      setLanShareInfo(await invoke<LanShareInfo>("get_lan_share_info"));
+    } catch (e) {
+      setError(String(e));
+    }
+  };
+
+  const exportTlsCert = async () => {
+    try {
+      const exported = await invoke<{ path: string }>("export_tls_cert", { path: null });
+      setTlsExportPath(exported.path);
+    } catch (e) {
+      setError(String(e));
+    }
+  };
+
+  const [graphqlEnabled, setGraphqlEnabled] = useState(false);
+
+  useEffect(() => {
+    invoke<{ enabled: boolean }>("get_graphql_settings")
+      .then((settings) => setGraphqlEnabled(settings.enabled))
+      .catch((e) => console.error("Failed to load GraphQL settings:", e));
+  }, []);
+
+  const toggleGraphql = async (enabled: boolean) => {
+    try {
+      const settings = await invoke<{ enabled: boolean }>("set_graphql", { enabled });
+      setGraphqlEnabled(settings.enabled);
+    } catch (e) {
+      setError(String(e));
+    }
+  };
+
+  const [rateLimits, setRateLimits] = useState<RateLimitSettings | null>(null);
+
+  useEffect(() => {
+    invoke<RateLimitSettings>("get_rate_limit_settings")
+      .then(setRateLimits)
+      .catch((e) => console.error("Failed to load rate limit settings:", e));
+  }, []);
+
+  const updateRateLimits = async (settings: RateLimitSettings) => {
+    try {
+      setRateLimits(await invoke<RateLimitSettings>("set_rate_limit_settings", { settings }));
+    } catch (e) {
+      setError(String(e));
+    }
+  };
+
+  const [logSettings, setLogSettings] = useState<LogSettings | null>(null);
+
+  useEffect(() => {
+    invoke<LogSettings>("get_log_settings")
+      .then(setLogSettings)
+      .catch((e) => console.error("Failed to load log settings:", e));
+  }, []);
+
+  const updateLogSettings = async (settings: LogSettings) => {
+    try {
+      setLogSettings(await invoke<LogSettings>("set_log_settings", { settings }));
+    } catch (e) {
+      setError(String(e));
+    }
+  };
+
+  const [corsSettings, setCorsSettings] = useState<CorsSettings | null>(null);
+  const [corsOrigins, setCorsOrigins] = useState("");
+
+  useEffect(() => {
+    invoke<CorsSettings>("get_cors_settings")
+      .then((settings) => {
+        setCorsSettings(settings);
+        setCorsOrigins(settings.allowed_origins.join(", "));
+      })
+      .catch((e) => console.error("Failed to load CORS settings:", e));
+  }, []);
+
+  const updateCors = async (permissive: boolean, origins: string) => {
+    try {
+      const allowed_origins = origins.split(/[\s,]+/).filter((origin) => origin !== "");
+      const settings = await invoke<CorsSettings>("set_cors_settings", {
+        settings: { permissive, allowed_origins },
+      });
+      setCorsSettings(settings);
+      setCorsOrigins(settings.allowed_origins.join(", "));
+    } catch (e) {
+      setError(String(e));
+    }
+  };
+
+  const [telemetry, setTelemetry] = useState<TelemetryStatus | null>(null);
+  const [telemetryEndpoint, setTelemetryEndpoint] = useState("");
+
+  useEffect(() => {
+    invoke<TelemetryStatus>("get_telemetry_settings")
+      .then((status) => {
+        setTelemetry(status);
+        setTelemetryEndpoint(status.settings.endpoint);
+      })
+      .catch((e) => console.error("Failed to load telemetry settings:", e));
+  }, []);
+
+  const updateTelemetry = async (enabled: boolean, endpoint: string) => {
+    try {
+      const status = await invoke<TelemetryStatus>("set_telemetry", { settings: { enabled, endpoint } });
+      setTelemetry(status);
+      setTelemetryEndpoint(status.settings.endpoint);
+    } catch (e) {
+      setError(String(e));
+    }
+  };
+
+  const [controlApiInfo, setControlApiInfo] = useState<ControlApiInfo | null>(null);
+
+  useEffect(() => {
+    invoke<ControlApiInfo>("get_control_api_info")
+      .then(setControlApiInfo)
+      .catch((e) => console.error("Failed to load control API info:", e));
+  }, []);
+
+  const toggleControlApi = async (enabled: boolean) => {
+    try {
+      setControlApiInfo(
+        await invoke<ControlApiInfo>("set_control_api", {
+          settings: { enabled, port: controlApiInfo?.port ?? 9877 },
+        })
+      );
+    } catch (e) {
+      setError(String(e));
+    }
+  };
+
+  const regenerateControlApiToken = async () => {
+    try {
+      setControlApiInfo(await invoke<ControlApiInfo>("regenerate_control_api_token"));
+    } catch (e) {
+      setError(String(e));
+    }
+  };
+
+  const [blockNotifications, setBlockNotifications] = useState(false);
+  const [autoStartMiner, setAutoStartMiner] = useState(false);
+
+  useEffect(() => {
+    invoke<{ auto_start_miner: boolean }>("get_auto_miner_settings")
+      .then((settings) => setAutoStartMiner(settings.auto_start_miner))
+      .catch((e) => console.error("Failed to load auto-miner settings:", e));
+  }, []);
+
+  const toggleAutoStartMiner = async (enabled: boolean) => {
+    try {
+      const settings = await invoke<{ auto_start_miner: boolean }>("set_auto_start_miner", { enabled });
+      setAutoStartMiner(settings.auto_start_miner);
+    } catch (e) {
+      setError(String(e));
+    }
+  };
+
+  useEffect(() => {
+    invoke<{ block_found: boolean }>("get_notification_settings")
+      .then((settings) => setBlockNotifications(settings.block_found))
+      .catch((e) => console.error("Failed to load notification settings:", e));
+  }, []);
+
+  const toggleBlockNotifications = async (enabled: boolean) => {
+    try {
+      const settings = await invoke<{ block_found: boolean }>("set_block_notifications", { enabled });
+      setBlockNotifications(settings.block_found);
+    } catch (e) {
+      setError(String(e));
+    }
+  };
+
+  useEffect(() => {
+    invoke<{ rotate_addresses: boolean; public: PublicFaucetSettings }>("get_faucet_settings")
+      .then((settings) => {
+        setRotateFaucetAddresses(settings.rotate_addresses);
+        setPublicFaucet(settings.public);
+      })
+      .catch((e) => console.error("Failed to load faucet settings:", e));
+  }, []);
+
+  const toggleFaucetRotation = async (enabled: boolean) => {
+    try {
+      const settings = await invoke<{ rotate_addresses: boolean }>("set_faucet_address_rotation", { enabled });
+      setRotateFaucetAddresses(settings.rotate_addresses);
+    } catch (e) {
+      setError(String(e));
+    }
+  };
+
+  const togglePublicFaucet = async (enabled: boolean) => {
+    if (!publicFaucet) return;
+    try {
+      const settings = await invoke<{ public: PublicFaucetSettings }>("set_public_faucet_settings", {
+        settings: { ...publicFaucet, enabled },
+      });
+      setPublicFaucet(settings.public);
+    } catch (e) {
+      setError(String(e));
+    }
+  };
 
   // Headless wallet state
   const [headlessStatus, setHeadlessStatus] = useState<HeadlessStatus>({ running: false, port: null });
@@ -1234,6 +1931,29 @@ function App() {
                 </>
               )}
             </button>
+
+            <label className="flex items-center gap-2 text-sm text-slate-400">
+              <input
+                type="checkbox"
+                checked={rotateFaucetAddresses}
+                onChange={(e) => toggleFaucetRotation(e.target.checked)}
+                className="accent-amber-500"
+              />
+              Use a fresh faucet address for each funding operation
+            </label>
+
+            {publicFaucet && (
+              <label className="flex items-center gap-2 text-sm text-slate-400">
+                <input
+                  type="checkbox"
+                  checked={publicFaucet.enabled}
+                  onChange={(e) => togglePublicFaucet(e.target.checked)}
+                  className="accent-amber-500"
+                />
+                Serve POST /faucet on the explorer server (up to {(publicFaucet.max_amount / 100).toFixed(2)} HTR,
+                once per address every {Math.round(publicFaucet.cooldown_secs / 60)} min)
+              </label>
+            )}
           </div>
         </div>
 